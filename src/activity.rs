@@ -0,0 +1,108 @@
+use crate::git::OperationStatus;
+use std::io::Write;
+use std::path::Path;
+use std::time::SystemTime;
+
+/// Kind of repository mutation performed by gitix
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ActivityKind {
+    Init,
+    Stage,
+    Unstage,
+    Commit,
+    Pull,
+    Push,
+    SyncFork,
+    Reset,
+    Discard,
+    Restore,
+    Clean,
+    Stash,
+    Branch,
+    Tag,
+    Remote,
+    Notes,
+    Resolve,
+    Abort,
+    Publish,
+    PullRequest,
+    CherryPick,
+    Rebase,
+    Merge,
+}
+
+impl ActivityKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ActivityKind::Init => "Init",
+            ActivityKind::Stage => "Stage",
+            ActivityKind::Unstage => "Unstage",
+            ActivityKind::Commit => "Commit",
+            ActivityKind::Pull => "Pull",
+            ActivityKind::Push => "Push",
+            ActivityKind::SyncFork => "Sync Fork",
+            ActivityKind::Reset => "Reset",
+            ActivityKind::Discard => "Discard",
+            ActivityKind::Restore => "Restore",
+            ActivityKind::Clean => "Clean",
+            ActivityKind::Stash => "Stash",
+            ActivityKind::Branch => "Branch",
+            ActivityKind::Tag => "Tag",
+            ActivityKind::Remote => "Remote",
+            ActivityKind::Notes => "Notes",
+            ActivityKind::Resolve => "Resolve",
+            ActivityKind::Abort => "Abort",
+            ActivityKind::Publish => "Publish",
+            ActivityKind::PullRequest => "Pull Request",
+            ActivityKind::CherryPick => "Cherry-pick",
+            ActivityKind::Rebase => "Rebase",
+            ActivityKind::Merge => "Merge",
+        }
+    }
+}
+
+/// One git mutation gitix performed during this session
+#[derive(Debug, Clone)]
+pub struct ActivityEntry {
+    pub kind: ActivityKind,
+    pub target: String,    // What was acted on (file path, branch, remote)
+    pub operation: String, // The exact underlying git operation
+    pub status: OperationStatus,
+    pub message: String, // Result or error message
+    pub timestamp: SystemTime,
+}
+
+/// Format a timestamp as local wall-clock time for the log
+pub fn format_timestamp(timestamp: SystemTime) -> String {
+    let datetime: chrono::DateTime<chrono::Local> = timestamp.into();
+    datetime.format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+/// Short label for an operation result
+pub fn status_label(status: &OperationStatus) -> &'static str {
+    match status {
+        OperationStatus::Pending => "pending",
+        OperationStatus::InProgress => "running",
+        OperationStatus::Success => "ok",
+        OperationStatus::Error => "error",
+    }
+}
+
+/// Write the activity log as tab-separated lines, oldest first
+pub fn export_log(entries: &[ActivityEntry], path: &Path) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    writeln!(file, "time\taction\tresult\ttarget\toperation\tmessage")?;
+    for entry in entries.iter().rev() {
+        writeln!(
+            file,
+            "{}\t{}\t{}\t{}\t{}\t{}",
+            format_timestamp(entry.timestamp),
+            entry.kind.as_str(),
+            status_label(&entry.status),
+            entry.target,
+            entry.operation,
+            entry.message.replace('\n', " "),
+        )?;
+    }
+    Ok(())
+}
@@ -1,16 +1,25 @@
 use crate::tui::theme::{AccentColor, TitleColor};
+use ratatui::layout::Rect;
 use ratatui::widgets::ScrollbarState;
 use ratatui::widgets::TableState;
 use std::path::PathBuf;
+use std::time::Instant;
 use tui_textarea::TextArea;
 
 pub struct AppState {
     pub git_enabled: bool,          // Is this a git repo?
+    pub read_only: bool,            // Started with --read-only: nothing may change the repository
     pub show_init_prompt: bool,     // Should we prompt to init?
+    pub init_setup: Option<InitSetup>, // Branch name and first commit, asked right after init
+    pub publish: Option<PublishSetup>, // Creating a forge repository for origin, offered after init
     pub repo_root: Option<PathBuf>, // Path to repo root if found
+    pub tab_bar: crate::tui::tabs::TabRegistry, // The tabs shown, in order (gitix.ui.tabs)
+    pub active_tab: crate::tui::tabs::Tab,      // The tab on screen
     pub root_dir: PathBuf,          // The directory jail root
     pub current_dir: PathBuf,       // The directory currently being browsed
     pub files_selected_row: usize,  // Selected row in files tab
+    pub blame_view: Option<BlameView>, // The blame of a file picked in the Files tab, while shown
+    pub blame_cache: std::collections::HashMap<String, crate::git::FileBlame>, // Blames computed this session, by path
 
     // Save changes tab state
     pub save_changes_table_state: TableState, // Table state for save changes file list
@@ -22,6 +31,14 @@ pub struct AppState {
     pub help_popup_scrollbar_state: ScrollbarState, // Scrollbar state for help popup
     pub show_template_popup: bool,            // Whether to show template selection popup
     pub template_popup_selection: TemplatePopupSelection, // Which button is selected in template popup
+    pub commit_skip_hooks: bool, // Skip pre-commit/commit-msg hooks for the next commit (--no-verify)
+    pub last_commit_summary: Option<String>, // Summary of the most recent commit made from the UI
+    pub staged_encoding_warning: Option<String>, // Set when the staged files mix text encodings
+    pub format_report: Option<FormatReportView>, // What the commit formatter changed, while shown
+    pub commit_draft_restored: bool, // Whether the commit message came from a saved draft and is untouched since
+    pub commit_amend: Option<String>, // Message of the commit being amended, while amending HEAD
+    pub amend_confirm: bool,          // Whether the message diff is shown before amending
+    pub discard_confirm: Option<DiscardConfirm>, // File waiting for Y/N before its changes are thrown away
 
     // Settings tab state
     pub settings_focus: SettingsFocus, // Which settings section has focus
@@ -35,38 +52,952 @@ pub struct AppState {
     pub current_theme_accent3: AccentColor, // Current tertiary accent color
     pub current_theme_title: TitleColor, // Current title color
     pub settings_status_message: Option<String>, // Status message for settings operations
+    pub settings_hooks: Vec<crate::hooks::HookInfo>, // Hooks found in the hooks directory
+    pub settings_hooks_dir: Option<PathBuf>, // Resolved hooks directory (honors core.hooksPath)
+    pub settings_hook_selected: usize, // Selected row in the hooks list
+    pub show_hook_template_popup: bool, // Whether to show the hook template picker
+    pub hook_template_selected: usize, // Selected template in the hook template picker
+    pub settings_remotes: Vec<crate::git::RemoteInfo>, // Configured remotes, origin first
+    pub settings_remote_selected: usize, // Selected row in the remotes list
+    pub remote_edit: Option<RemoteEdit>, // The add or edit remote form, while open
+    pub remote_remove: Option<String>, // Remote waiting for the remove confirmation
 
     // Git configuration
+    pub profile: Profile, // Beginner hides advanced actions, expert skips prompts (gitix.profile)
+    pub time_format: TimeFormat, // How History, Overview and Update show times (gitix.ui.timeformat)
+    pub time_zone: CommitTimeZone, // Whose clock commit times are shown on (gitix.ui.timezone)
     pub pull_rebase: bool, // Whether to use rebase when pulling (gitix.pull.rebase)
+    pub notifications_enabled: bool, // Desktop notifications for long operations (gitix.notifications.enabled)
+    pub auto_refresh_minutes: u32, // Update tab auto-refresh interval, 0 = off (gitix.update.autoRefresh)
+    pub fork_push_after_sync: bool, // Push the synced branch to origin after a fork sync (gitix.fork.push)
+    pub commit_wrap_column: usize, // Hard-wrap the commit body at this column, 0 = off (gitix.commit.wrap)
+    pub init_template: Option<String>, // gitix template offered for new repositories (gitix.init.template)
+    pub permanent_delete: bool, // Delete files for good instead of moving them to the trash (gitix.files.permanentDelete)
+    pub update_check: bool, // Look for a newer gitix release on startup (gitix.update.check)
+    pub forge_override: Option<crate::forge::ForgeKind>, // Forge of the remotes, None detects it (gitix.forge)
+
+    // Release check state
+    pub update_check_rx: Option<std::sync::mpsc::Receiver<Result<crate::release::Release, String>>>, // Release lookup in flight
+    pub update_available: Option<crate::release::Release>, // A newer release than the running one, once found
+
+    // Where the user's attention is, for deciding whether to notify
+    pub terminal_focused: bool, // Whether the terminal window has focus
+    pub on_update_tab: bool,    // Whether the Update tab is the active tab
 
     // Git status caching for save changes tab
     pub save_changes_git_status: Vec<crate::git::GitFileStatus>, // Cached git status for save changes tab
     pub save_changes_git_status_loaded: bool, // Whether git status has been loaded for save changes tab
+    pub file_detail: Option<FileDetail>, // Detail popup for a file in the Save Changes list
+    pub save_changes_diff: Option<SaveChangesDiff>, // Diff of the selected file, recomputed when it changes
+    pub show_save_changes_diff: bool, // Whether the diff pane is shown beside the file list
+    pub review_notes: Vec<crate::review::ReviewNote>, // Notes left on diff lines, from .git/gitix/notes.json
+    pub review_note_input: Option<ReviewNoteInput>, // Note being written for the line under the review cursor
+    pub review_notes_view: Option<ReviewNotesView>, // Popup listing every review note
+    pub clean_preview: Option<CleanPreview>, // Files a clean would remove, while it is being confirmed
+    pub stage_pattern: Option<StagePattern>, // Pathspec being typed to stage matching files in one go
+    pub save_changes_reconcile: Option<std::sync::mpsc::Receiver<Vec<crate::git::GitFileStatus>>>, // Background re-scan after an optimistic stage/unstage
 
     // Git status caching for files tab (reused from old status tab)
     pub status_git_status: Vec<crate::git::GitFileStatus>, // Cached git status for files tab
     pub status_git_status_loaded: bool, // Whether git status has been loaded for files tab
+    pub status_summary: Option<crate::git::StatusSummary>, // Porcelain-style summary for the status tab
+    pub status_flagged_files: Vec<(String, crate::git::IndexFlag)>, // Files marked assume-unchanged or skip-worktree
+    pub flagged_files_view: Option<usize>, // Selected row while the flagged files list is open
+    pub files_scope: FilesScope,           // How far above the launch directory the Files tab may go
+    pub overview_calendar_cursor: Option<chrono::NaiveDate>, // Day picked on the calendar, once arrows are used
+    pub overview_calendar_start: Option<chrono::NaiveDate>, // First day the calendar currently shows
+    pub day_commits: Option<DayCommits>, // Commits of the picked calendar day, while listed
+    pub repo_snapshot: Option<RepoSnapshot>, // Branch and history figures, until something changes them
+    pub watcher: Option<crate::watcher::RepoWatcher>, // Notices changes made outside gitix
+    pub history: HistoryView, // The History tab's commit list and filter
+    pub history_filter_edit: Option<HistoryFilterEdit>, // The History filter form, while open
+    pub history_export: Option<std::sync::mpsc::Receiver<Result<(usize, std::path::PathBuf), String>>>, // Export being written
+    pub history_message: Option<String>, // Result of the last History export or notes push/fetch
+    pub history_notes_sync: Option<std::sync::mpsc::Receiver<(&'static str, Result<String, String>)>>, // Notes push or fetch running
+    pub note_edit: Option<NoteEdit>, // The note being written for a History commit
+    pub reflog_view: Option<ReflogView>, // HEAD's reflog, opened from the History tab
+    pub undo_confirm: Option<crate::git::UndoPlan>, // Undo waiting for the confirmation
+    pub abort_confirm: Option<&'static str>, // "merge", "rebase" or "cherry-pick" to abort, once confirmed
+    pub cherry_pick_confirm: Option<CherryPickConfirm>, // Commit waiting to be cherry-picked
+    pub reset_confirm: Option<ResetConfirm>, // Reset to a History commit, while its mode is picked
+    pub rebase_plan: Option<RebasePlanView>, // Interactive rebase of the current branch, while it's planned
+    pub credential_requests: Option<std::sync::mpsc::Receiver<crate::credentials::CredentialRequest>>, // From fetches and pushes, while the TUI runs
+    pub credential_prompt: Option<CredentialPrompt>, // The request being answered
+    pub branches: BranchesView, // The Branches tab's list
+    pub branch_create: Option<TextArea<'static>>, // Name of the branch being created, while asked for
+    pub branch_delete: Option<BranchDelete>, // Branch waiting for the delete confirmation
+    pub branch_action: Option<BranchAction>, // Switch to or merge the branch Enter was pressed on
+    pub merge_draft: Option<MergeDraft>, // Merging a branch into the current one, while its message is written
+    pub tags: TagsView, // The Tags tab's list
+    pub tag_create: Option<TagCreate>, // The new tag form, while open
+    pub tag_delete: Option<String>, // Tag waiting for the delete confirmation
+
+    // Onboarding tour state
+    pub tour: Option<Tour>, // The guided tour, while it is shown
+    pub explain: Option<Vec<String>>, // The "Explain this" popup's paragraphs, while it is open
+
+    // Backup snapshot state
+    pub backup_minutes: u32, // Snapshot the working tree this often, 0 = off (gitix.backup.minutes)
+    pub backup_since: Instant, // Start of the current backup interval
+    pub backup_rx: Option<std::sync::mpsc::Receiver<Result<Option<String>, String>>>, // Snapshot in progress
+    pub backup_error: Option<String>, // Why the last snapshot failed, if it did
+    pub backup_browser: Option<BackupBrowser>, // The snapshot browser, while open
+    pub sparkline_bucket: SparklineBucket, // What one bar of the activity sparkline covers
+    pub sparkline_range_months: u32,     // How far back the activity sparkline goes
+    pub activity_filter: ActivityFilter, // Commits the calendar and sparkline leave out
+    pub churn_view: Option<ChurnView>,   // The hotspots popup, while open
+    pub churn_cache: Vec<crate::git::ChurnReport>, // Reports computed so far, one per window
+    pub churn_scan: Option<std::sync::mpsc::Receiver<Result<crate::git::ChurnReport, String>>>, // Report being computed
+    pub language_stats: Option<crate::languages::LanguageStats>, // Lines per language at the HEAD it names
+    pub language_scan: Option<std::sync::mpsc::Receiver<Result<crate::languages::LanguageStats, (String, String)>>>, // Count in progress
+    pub language_error: Option<(String, String)>, // HEAD whose count failed, and why; not retried until HEAD moves
+    pub language_view: Option<usize>,             // Scroll of the languages popup, while open
+    pub recursive_status: Option<RecursiveStatus>, // This repository and those nested in it, once read
+    pub recursive_scan: Option<std::sync::mpsc::Receiver<Result<RecursiveStatus, String>>>, // Read in progress
+    pub nested_view: Option<usize>, // Selected row of the nested repositories popup, while open
+    pub files_delete_confirm: Option<PathBuf>, // Entry awaiting confirmation before it is deleted
+    pub last_status_counts: Option<(usize, usize, usize)>, // Staged/unstaged/untracked counts last sent as a status-changed event
 
     // Update tab state
     pub update_remote_status: Option<crate::git::RemoteStatus>, // Cached remote status
-    pub update_recent_operations: Vec<crate::git::SyncOperation>, // Recent sync operations
+    pub update_recent_operations: Vec<crate::git::SyncOperation>, // Recent sync operations, newest first
+    pub sync_history_size: usize, // Sync operations kept in .git/gitix/sync-log.json (gitix.update.historySize)
+    pub update_last_refreshed: Option<Instant>, // When the remote status was last fetched this session
+    pub update_auto_refresh_since: Instant, // Start of the current auto-refresh interval
+    pub update_fork: Option<crate::git::ForkInfo>, // Detected origin/upstream fork setup
+    pub update_forge: Option<crate::forge::Forge>, // Forge origin is hosted on, when recognized
+    pub forge_ci: Option<crate::forge::CiStatus>, // CI result for HEAD, once looked up
+    pub forge_ci_rx: Option<std::sync::mpsc::Receiver<Result<crate::forge::CiStatus, String>>>, // CI lookup in flight
+    pub sync_confirm: Option<crate::git::SyncOperationType>, // Pull or push awaiting a yes (beginner profile)
+    pub push_target: Option<String>, // Branch being pushed when it isn't the current one (fork sync)
+
+    // Activity tab state
+    pub activity_log: Vec<crate::activity::ActivityEntry>, // Git mutations performed this session, newest first
+    pub activity_table_state: TableState, // Table state for the activity list
+    pub activity_status_message: Option<String>, // Result of the last export
+
+    // What was selected on each tab when the user left it
+    pub focus_memory: TabFocusMemory,
+
+    // Hook output popup state
+    pub hook_output: Option<HookOutput>, // Output of the hook currently (or last) shown in the popup
+
+    // Conflict resolution popup state
+    pub conflicts: Option<ConflictsView>, // Conflicted files of an in-progress merge, while open
+
+    // Pull request list popup state
+    pub pull_requests: Option<PullRequestsView>, // Open pull requests on the forge, while shown
+    pub pull_request_draft: Option<PullRequestDraft>, // A pull request being written, while shown
+
+    // Tables drawn in the last frame, for mapping mouse clicks to rows
+    pub mouse_targets: Vec<MouseTarget>,
+
+    // Quit confirmation state
+    pub quit_confirm: Option<QuitConfirm>, // Reasons to think twice, while the quit confirmation is shown
+    pub quit_warn_unpushed: bool, // Mention unpushed commits and old stashes on quit (gitix.quit.warnUnpushed)
+    pub quit_stash_days: u32,     // Stashes older than this many days are mentioned on quit (gitix.quit.stashDays)
 
     // Error popup state
     pub show_error_popup: bool,      // Whether to show error popup
     pub error_popup_title: String,   // Title of the error popup
     pub error_popup_message: String, // Error message to display
+    pub error_popup_chain: Vec<String>, // Underlying causes of the error, outermost first
+    pub error_popup_operation: Option<crate::activity::ActivityEntry>, // The failed operation behind the error, if one was just logged
+    pub error_popup_copied: Option<String>, // Outcome of copying the error report
 
     // Loading indicator state
     pub is_loading: bool, // Whether a long-running operation is in progress
     pub loading_message: String, // Message to show while loading
     pub spinner_state: usize, // Current spinner animation frame
     pub pending_refresh_work: bool, // Whether refresh work is pending (to show loading indicator first)
+    pub pending_refresh_is_auto: bool, // Whether the pending refresh was started by the auto-refresh timer
+    pub pending_fetch_all: bool, // Whether the pending work fetches every remote instead of origin
+    pub sync_worker: Option<SyncWorker>, // Fetch, pull or push running off the UI thread
+    pub sync_progress: Option<crate::git::TransferProgress>, // Latest progress the worker reported
+}
+
+/// A network operation running on its own thread
+pub struct SyncWorker {
+    pub progress: std::sync::mpsc::Receiver<crate::git::TransferProgress>,
+    pub done: std::sync::mpsc::Receiver<SyncWorkerResult>,
+}
+
+/// What a sync worker hands back when it finishes
+pub enum SyncWorkerResult {
+    Refresh {
+        result: Result<(crate::git::RemoteStatus, crate::git::SyncOperation), String>,
+        is_auto: bool,
+    },
+    FetchAll(Result<Vec<(String, crate::git::SyncOperation)>, String>),
+    Pull(Result<crate::git::SyncOperation, String>),
+    Push {
+        result: Result<crate::git::SyncOperation, String>,
+        branch: String,
+        new_branch: bool, // Origin didn't have the branch before
+    },
+    ForkSync {
+        result: Result<crate::git::SyncOperation, String>,
+        branch: String, // The fork's default branch, pushed afterwards if configured
+    },
+}
+
+/// How far the Files tab may navigate
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FilesScope {
+    Launch,     // The directory gitix was started in
+    Repository, // The root of the repository it belongs to
+    Anywhere,   // The whole disk
+}
+
+impl FilesScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FilesScope::Launch => "launch directory",
+            FilesScope::Repository => "repository",
+            FilesScope::Anywhere => "anywhere",
+        }
+    }
+}
+
+/// Selections on tabs whose lists can change while the user is on another tab
+///
+/// Rows are remembered by what they show rather than by index, so coming back
+/// lands on the same file or entry even if others appeared or went away.
+#[derive(Debug, Clone, Default)]
+pub struct TabFocusMemory {
+    pub save_changes_path: Option<PathBuf>, // Selected file in the Save Changes list
+    pub files_entry: Option<String>,        // Selected entry in the Files tab's directory
+    pub activity_len: usize,                // Activity log length when the Activity tab was left
+}
+
+/// How much gitix shows and asks (gitix.profile)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Profile {
+    Beginner, // Advanced and destructive actions hidden, more confirmations
+    Standard,
+    Expert, // Everything available, fewer prompts
+}
+
+impl Profile {
+    /// The value stored in gitix.profile
+    pub fn key(self) -> &'static str {
+        match self {
+            Profile::Beginner => "beginner",
+            Profile::Standard => "standard",
+            Profile::Expert => "expert",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key.trim().to_ascii_lowercase().as_str() {
+            "beginner" => Some(Profile::Beginner),
+            "standard" => Some(Profile::Standard),
+            "expert" => Some(Profile::Expert),
+            _ => None,
+        }
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Profile::Beginner => "Beginner",
+            Profile::Standard => "Standard",
+            Profile::Expert => "Expert",
+        }
+    }
+
+    /// The next profile for ←/→ in Settings
+    pub fn cycle(self, forward: bool) -> Self {
+        match (self, forward) {
+            (Profile::Beginner, true) | (Profile::Expert, false) => Profile::Standard,
+            (Profile::Standard, true) => Profile::Expert,
+            (Profile::Standard, false) => Profile::Beginner,
+            (Profile::Expert, true) => Profile::Beginner,
+            (Profile::Beginner, false) => Profile::Expert,
+        }
+    }
+}
+
+/// How commit and fetch times are shown (gitix.ui.timeformat)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeFormat {
+    Relative, // "3 hours ago"
+    Absolute, // The locale's date and time, e.g. 18.10.2026 14:05:09 for de_DE
+    Iso,      // 2026-10-18 14:05
+}
+
+impl TimeFormat {
+    /// The value stored in gitix.ui.timeformat
+    pub fn key(self) -> &'static str {
+        match self {
+            TimeFormat::Relative => "relative",
+            TimeFormat::Absolute => "absolute",
+            TimeFormat::Iso => "iso",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key.trim().to_ascii_lowercase().as_str() {
+            "relative" => Some(TimeFormat::Relative),
+            "absolute" => Some(TimeFormat::Absolute),
+            "iso" => Some(TimeFormat::Iso),
+            _ => None,
+        }
+    }
+
+    /// The next format for the T key
+    pub fn next(self) -> Self {
+        match self {
+            TimeFormat::Relative => TimeFormat::Absolute,
+            TimeFormat::Absolute => TimeFormat::Iso,
+            TimeFormat::Iso => TimeFormat::Relative,
+        }
+    }
+
+    /// Format `time` this way
+    pub fn format(self, time: chrono::DateTime<chrono::Local>) -> String {
+        match self {
+            TimeFormat::Relative => crate::git::format_relative_time(time),
+            TimeFormat::Absolute => time.format_localized("%x %X", time_locale()).to_string(),
+            TimeFormat::Iso => time.format("%Y-%m-%d %H:%M").to_string(),
+        }
+    }
+
+    /// Format a commit's time this way, on the clock `zone` picks
+    pub fn format_commit(
+        self,
+        time: chrono::DateTime<chrono::FixedOffset>,
+        zone: CommitTimeZone,
+    ) -> String {
+        match self {
+            TimeFormat::Relative => {
+                crate::git::format_relative_time(time.with_timezone(&chrono::Local))
+            }
+            TimeFormat::Absolute => zone.format(time, "%x %X"),
+            TimeFormat::Iso => zone.format(time, "%Y-%m-%d %H:%M"),
+        }
+    }
+}
+
+/// Whose clock commit times are shown on (gitix.ui.timezone)
+///
+/// Commits keep the offset of the machine they were made on, so a history from
+/// people in several timezones reads in each author's own hours by default. Any
+/// time not on this machine's clock is shown with its offset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitTimeZone {
+    Original, // The committer's own, e.g. 2026-10-18 14:05 +0900
+    Local,    // This machine's
+    Utc,      // 2026-10-18 05:05 +0000
+}
+
+impl CommitTimeZone {
+    /// The value stored in gitix.ui.timezone
+    pub fn key(self) -> &'static str {
+        match self {
+            CommitTimeZone::Original => "original",
+            CommitTimeZone::Local => "local",
+            CommitTimeZone::Utc => "utc",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key.trim().to_ascii_lowercase().as_str() {
+            "original" => Some(CommitTimeZone::Original),
+            "local" => Some(CommitTimeZone::Local),
+            "utc" => Some(CommitTimeZone::Utc),
+            _ => None,
+        }
+    }
+
+    /// `time` moved onto this clock
+    pub fn convert(
+        self,
+        time: chrono::DateTime<chrono::FixedOffset>,
+    ) -> chrono::DateTime<chrono::FixedOffset> {
+        match self {
+            CommitTimeZone::Original => time,
+            CommitTimeZone::Local => time.with_timezone(&chrono::Local).fixed_offset(),
+            CommitTimeZone::Utc => time.with_timezone(&chrono::Utc).fixed_offset(),
+        }
+    }
+
+    /// Format `time` on this clock with a strftime `pattern`, adding the offset unless it's local
+    pub fn format(self, time: chrono::DateTime<chrono::FixedOffset>, pattern: &str) -> String {
+        let time = self.convert(time);
+        let shown = time.format_localized(pattern, time_locale());
+        match self {
+            CommitTimeZone::Local => shown.to_string(),
+            _ => format!("{} {}", shown, time.format("%z")),
+        }
+    }
+}
+
+/// The locale for dates and times, from LC_ALL, LC_TIME or LANG as the C library picks it
+fn time_locale() -> chrono::Locale {
+    ["LC_ALL", "LC_TIME", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .and_then(|value| {
+            // en_US.UTF-8 or de_DE@euro name the locale de_DE
+            let name = value.split(['.', '@']).next().unwrap_or_default();
+            chrono::Locale::try_from(name).ok()
+        })
+        .unwrap_or(chrono::Locale::POSIX)
+}
+
+/// What to commit first in a freshly initialized repository
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InitialCommit {
+    Empty, // An empty commit, so HEAD exists
+    Files, // Everything already in the folder (honoring .gitignore)
+    Skip,  // No commit yet
+}
+
+/// The follow-up step of `git init`: naming the branch and creating the first commit
+pub struct InitSetup {
+    pub branch: TextArea<'static>,
+    pub commit: InitialCommit,
+    pub has_files: bool,          // Whether the folder had anything to commit
+    pub template: Option<String>, // gitix template to copy in before the first commit
+    pub templates: Vec<String>,   // Templates found in the gitix templates folder
+}
+
+/// Which row of the publish popup has focus
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PublishField {
+    Forge,
+    Name,
+    Visibility,
+    Protocol,
+}
+
+/// Creating a repository on a forge for a repository just initialized, then pushing to it
+pub struct PublishSetup {
+    pub kind: crate::forge::ForgeKind,
+    pub name: TextArea<'static>,
+    pub private: bool,
+    pub ssh: bool, // Add the SSH URL as origin rather than the HTTPS one
+    pub field: PublishField,
+    pub rx: Option<std::sync::mpsc::Receiver<Result<crate::forge::CreatedRepository, String>>>, // Creation in flight
+    pub message: Option<String>, // Why the last attempt failed
+}
+
+/// A Save Changes file whose changes are about to be discarded
+pub struct DiscardConfirm {
+    pub path: String,
+    pub untracked: bool, // Not in HEAD, so discarding removes the file itself
+}
+
+/// A review note being written for one diff line
+pub struct ReviewNoteInput {
+    pub path: String,
+    pub line: u32,
+    pub old: bool,    // On a removed line
+    pub code: String, // The diff line itself
+    pub input: TextArea<'static>,
+}
+
+/// The popup listing every review note in the repository
+pub struct ReviewNotesView {
+    pub selected: usize,
+    pub message: Option<String>, // Export result, shown in place of the key help
+}
+
+/// The detail popup for one file of the Save Changes list
+pub struct FileDetail {
+    pub file: crate::git::GitFileStatus,
+    pub diff: Option<crate::git::FileDiffDetail>, // None when the diff couldn't be computed
+    pub confirm_discard: bool,                    // Waiting for Y/N before discarding
+}
+
+/// Diff of the selected Save Changes file, shown beside the list
+pub struct SaveChangesDiff {
+    pub path: PathBuf,      // File the diff was computed for
+    pub lines: Vec<String>, // Staged then unstaged hunks, with section headings when both exist
+    pub scroll: usize,      // First line shown
+    pub hunks: Vec<DiffHunk>, // Where each hunk sits in `lines`
+    pub selected_hunk: usize, // Hunk picked in the hunk view
+    pub numbers: Vec<Option<(u32, bool)>>, // Line number and whether it is a removed line, per line
+    pub cursor: usize,                      // Line under the review cursor
+    pub encoding: Option<crate::encoding::TextEncoding>, // Of the working tree file, for the badge
+}
+
+/// One hunk of the Save Changes diff pane
+pub struct DiffHunk {
+    pub staged: bool, // From the staged side (HEAD to index) rather than the unstaged one
+    pub index: usize, // Position among the hunks of its side, as `apply_hunk_to_index` counts
+    pub start: usize, // Line of its `@@` header in `SaveChangesDiff::lines`
+    pub len: usize,   // Lines from the header to the end of the hunk
+}
+
+/// How many days each bar of the Overview activity sparkline covers
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SparklineBucket {
+    Day,
+    Week,
+    Month,
+}
+
+impl SparklineBucket {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SparklineBucket::Day => "day",
+            SparklineBucket::Week => "week",
+            SparklineBucket::Month => "month",
+        }
+    }
+}
+
+/// Time ranges offered for the Overview activity sparkline, in months
+pub const SPARKLINE_RANGE_CHOICES: [u32; 3] = [3, 6, 12];
+
+/// Authors left out of the activity calendar and sparkline unless gitix.activity.excludeAuthor says otherwise
+pub const DEFAULT_EXCLUDED_AUTHORS: [&str; 1] = ["[bot]"];
+
+/// Which commits the Overview calendar and activity sparkline count
+///
+/// Bots like dependabot or renovate can commit more than everyone else put
+/// together, and merge commits count a change a second time. Commit totals and
+/// a day's commit list still show everything.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActivityFilter {
+    pub hide_merges: bool,             // Skip commits with more than one parent (gitix.activity.excludeMerges)
+    pub hide_authors: bool,            // Skip commits by authors matching `author_patterns`
+    pub author_patterns: Vec<String>, // Lowercase; matched anywhere in the name or email (gitix.activity.excludeAuthor)
+}
+
+impl Default for ActivityFilter {
+    fn default() -> Self {
+        ActivityFilter {
+            hide_merges: false,
+            hide_authors: true,
+            author_patterns: DEFAULT_EXCLUDED_AUTHORS.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+}
+
+impl ActivityFilter {
+    /// Whether a commit by `name` <`email`> with `parents` parents is counted
+    pub fn counts(&self, name: &str, email: &str, parents: usize) -> bool {
+        if self.hide_merges && parents > 1 {
+            return false;
+        }
+        if self.hide_authors {
+            let (name, email) = (name.to_lowercase(), email.to_lowercase());
+            if self
+                .author_patterns
+                .iter()
+                .any(|pattern| name.contains(pattern.as_str()) || email.contains(pattern.as_str()))
+            {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// What is left out, e.g. "without merges and bots"; `None` when everything counts
+    pub fn describe(&self) -> Option<String> {
+        let mut hidden = Vec::new();
+        if self.hide_merges {
+            hidden.push("merges");
+        }
+        if self.hide_authors && !self.author_patterns.is_empty() {
+            hidden.push("bots");
+        }
+        match hidden.as_slice() {
+            [] => None,
+            _ => Some(format!("without {}", hidden.join(" and "))),
+        }
+    }
+}
+
+/// Windows offered for the churn report, in days
+pub const CHURN_WINDOW_CHOICES: [u32; 4] = [30, 90, 180, 365];
+
+/// The repository and everything nested in it, for one "all committed and pushed?" answer
+pub struct RecursiveStatus {
+    pub own: crate::git::WorkspaceRepoStatus,
+    pub nested: Vec<crate::git::NestedRepo>, // Submodules and clones inside the working tree
+}
+
+impl RecursiveStatus {
+    /// How many repositories, this one included, have uncommitted or unpushed work
+    pub fn unsettled(&self) -> usize {
+        let nested = self
+            .nested
+            .iter()
+            .filter(|repo| {
+                !repo
+                    .status
+                    .as_ref()
+                    .is_ok_and(crate::git::WorkspaceRepoStatus::is_settled)
+            })
+            .count();
+        nested + usize::from(!self.own.is_settled())
+    }
+}
+
+/// The hotspots popup on the Overview
+pub struct ChurnView {
+    pub days: u32,
+    pub scroll: usize,
+    pub error: Option<String>, // Why the last scan failed
+}
+
+/// What the status bar and Overview tab show about the repository
+///
+/// Read once and kept until an event that can change it (a tab switch, a
+/// status refresh, a finished sync) drops it with `invalidate_repo_snapshot`,
+/// instead of reopening the repository on every frame.
+#[derive(Default)]
+pub struct RepoSnapshot {
+    pub branch: Option<String>,        // None when HEAD can't be read
+    pub remote_branch: Option<String>, // origin/<branch>, when it exists
+    pub head: Option<String>,          // HEAD's commit id; None before the first commit
+    pub has_origin: bool,
+    pub conflicted: Vec<String>, // Files a merge, pull, cherry-pick or rebase stopped on
+    pub merge_in_progress: bool,
+    pub planned_rebase_in_progress: bool, // A rebase planned in gitix has stopped
+    pub cherry_pick_in_progress: bool,
+    pub rebase_in_progress: bool,
+    pub overview: Option<std::rc::Rc<OverviewData>>, // Read the first time the Overview is drawn
+}
+
+/// History and branch figures for the Overview tab
+#[derive(Default)]
+pub struct OverviewData {
+    pub num_commits: Option<u64>,
+    pub num_branches: Option<u64>,
+    pub latest_author: Option<String>,
+    pub commit_dates: Vec<chrono::NaiveDate>, // One per commit reachable from HEAD the activity filter counts
+    pub recent_commits: Vec<RecentCommit>,
+    pub branch_tips: Vec<BranchTip>,
+    pub branch_activity: Vec<BranchActivity>, // Newest first
+}
+
+/// A commit in the Overview's Recent Changes
+#[derive(Debug, Clone)]
+pub struct RecentCommit {
+    pub message: String,
+    pub author: String,
+    pub time: chrono::DateTime<chrono::FixedOffset>,
+    pub oid: String, // For matching the branches that point at it
+}
+
+/// A local or remote-tracking branch and the commit it points at
+#[derive(Debug, Clone)]
+pub struct BranchTip {
+    pub name: String,
+    pub commit_oid: String,
+    pub is_remote: bool,
+}
+
+/// A branch and its latest commit, for the Overview's active branches
+#[derive(Debug, Clone)]
+pub struct BranchActivity {
+    pub name: String,
+    pub is_remote: bool,
+    pub message: String,
+    pub author: String,
+    pub time: chrono::DateTime<chrono::FixedOffset>,
+}
+
+/// Commits of the day picked on the Overview calendar
+pub struct DayCommits {
+    pub date: chrono::NaiveDate,
+    pub commits: Vec<crate::git::CommitSummary>,
+    pub selected: usize,
+    pub show_message: bool, // Whether the selected commit's full message is expanded
+}
+
+/// Commits listed on the History tab, loaded a page at a time as the list is scrolled
+#[derive(Default)]
+pub struct HistoryView {
+    pub filter: crate::git::HistoryFilter,
+    pub commits: Vec<crate::git::CommitSummary>,
+    pub cursor: crate::git::HistoryCursor, // Where loading continues from
+    pub head: Option<String>, // HEAD the list was loaded from; None until the first load
+    pub message_scroll: Option<usize>, // The selected commit's full message popup, while open
+    pub notes: std::collections::HashMap<String, String>, // Git notes on commits, by full commit id
+    pub error: Option<String>,
+    pub table_state: TableState,
+}
+
+/// Local branches listed on the Branches tab
+#[derive(Default)]
+pub struct BranchesView {
+    pub branches: Vec<crate::git::BranchInfo>,
+    pub loaded: bool, // Whether the list is current; cleared to re-read it
+    pub ahead_behind: crate::git::AheadBehindCache, // Kept across re-reads
+    pub error: Option<String>,
+    pub table_state: TableState,
+}
+
+/// A commit picked from History or Branches, waiting for the go-ahead to cherry-pick it
+#[derive(Debug, Clone)]
+pub struct CherryPickConfirm {
+    pub commit: crate::git::CommitSummary,
+    pub onto: String, // The current branch
+}
+
+/// The reset-to-commit modal: which commit, how far to reset and what that leaves behind
+#[derive(Debug, Clone)]
+pub struct ResetConfirm {
+    pub commit: crate::git::CommitSummary,
+    pub branch: String, // The current branch, or HEAD when detached
+    pub mode: crate::git::ResetMode,
+    pub preview: crate::git::ResetPreview,
+    pub typed: String, // A hard reset only goes ahead once this is the commit's short id
+}
+
+/// The interactive rebase planner: the commits on top of the upstream, in the order they'll be replayed
+#[derive(Debug, Clone)]
+pub struct RebasePlanView {
+    pub branch: String,
+    pub upstream: String, // e.g. origin/main
+    pub steps: Vec<crate::git::RebaseStep>,
+    pub selected: usize,
+    pub message: Option<String>, // Why the plan can't run as it is
+}
+
+/// Tags listed on the Tags tab
+#[derive(Default)]
+pub struct TagsView {
+    pub tags: Vec<crate::git::TagInfo>,
+    pub loaded: bool, // Whether the list is current; cleared to re-read it
+    pub error: Option<String>,
+    pub table_state: TableState,
+    pub push_rx: Option<std::sync::mpsc::Receiver<(String, Result<(), String>)>>, // Push in flight
+    pub message: Option<Result<String, String>>, // Result of the last push
+}
+
+/// The new tag form: a name, and a message that makes the tag annotated
+pub struct TagCreate {
+    pub name: TextArea<'static>,
+    pub message: TextArea<'static>,
+    pub editing_message: bool, // Whether the message field has focus rather than the name
+}
+
+/// A fetch or push waiting for the user to type credentials
+pub struct CredentialPrompt {
+    pub request: crate::credentials::CredentialRequest,
+    pub username: TextArea<'static>,
+    pub secret: TextArea<'static>, // Password, token or passphrase, shown masked
+    pub editing_secret: bool,      // Whether the secret field has focus rather than the username
+}
+
+/// The Settings form for adding a remote or changing one's name and URL
+pub struct RemoteEdit {
+    pub original: Option<String>, // Name of the remote being edited; None when adding one
+    pub name: TextArea<'static>,
+    pub url: TextArea<'static>,
+    pub editing_url: bool, // Whether the URL field has focus rather than the name
+    pub error: Option<String>, // Why the last attempt to save was refused
+}
+
+/// A branch deletion, while it is being confirmed
+pub struct BranchDelete {
+    pub name: String,
+    pub merged: bool, // Unmerged branches need a force delete and lose their commits
+}
+
+/// The choice Enter on a branch offers: switch to it, or merge it into the current branch
+pub struct BranchAction {
+    pub name: String,
+    pub merge: bool, // Merge is highlighted rather than switch
+}
+
+/// A merge into the current branch, while its mode is picked and its message written
+pub struct MergeDraft {
+    pub branch: String, // The branch being merged
+    pub into: String,   // The current branch
+    pub mode: crate::git::MergeMode,
+    pub preview: crate::git::MergePreview,
+    pub message: TextArea<'static>,
+}
+
+/// The blame popup's position in a file from `AppState::blame_cache`
+pub struct BlameView {
+    pub path: String,  // Relative to the repository root
+    pub cursor: usize, // Selected line, 0-based
+    pub offset: usize, // First line shown
+}
+
+/// The git note being written for a commit, while the editor is open
+pub struct NoteEdit {
+    pub commit: String,   // Full commit id
+    pub short_id: String, // For the popup title
+    pub input: TextArea<'static>,
+}
+
+/// HEAD's recent movements, while the reflog popup is open
+pub struct ReflogView {
+    pub entries: Vec<crate::git::ReflogEntry>,
+    pub selected: usize,
+}
+
+/// The History filter form, while it is open
+pub struct HistoryFilterEdit {
+    pub fields: [TextArea<'static>; 4], // Author, path, since, until
+    pub focus: usize,
+    pub error: Option<String>, // Why the last attempt to apply it was refused
+}
+
+/// The backup snapshot browser: snapshots, then the files one of them can restore
+pub struct BackupBrowser {
+    pub snapshots: Vec<crate::git::BackupSnapshot>, // Newest first
+    pub selected: usize,
+    pub files: Option<BackupFiles>, // Set once a snapshot is opened
+}
+
+/// Files in a snapshot that differ from the working tree
+pub struct BackupFiles {
+    pub paths: Vec<String>,
+    pub selected: usize,
+    pub confirm: bool, // Waiting for Y/N before overwriting the file
+}
+
+/// The first-launch tour, while it is shown
+pub struct Tour {
+    pub steps: Vec<usize>, // Indexes into TOUR_STEPS, leaving out hidden tabs
+    pub current: usize,    // Position in `steps`
+}
+
+/// The preview and confirmation steps of cleaning untracked files
+pub struct CleanPreview {
+    pub paths: Vec<String>,
+    pub include_ignored: bool,
+    pub confirming: bool, // Second step: the last chance before deleting
+    pub scroll: usize,
+}
+
+/// The stage-by-pattern prompt: a pathspec and the unstaged files it picks
+pub struct StagePattern {
+    pub input: TextArea<'static>,
+    pub candidates: Vec<String>, // Every path with unstaged changes, untracked files included
+    pub matches: Vec<String>,    // The candidates the pathspec picks
+    pub error: Option<String>,   // Why the pathspec can't be used, if it can't
+    pub scroll: usize,
+}
+
+/// What the quit confirmation lists
+pub struct QuitConfirm {
+    pub reasons: Vec<String>,  // Work that quitting would interrupt or leave behind
+    pub warnings: Vec<String>, // Unpushed commits and old stashes; can be switched off
+}
+
+/// Which version of a conflicted file the conflicts popup shows
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConflictPane {
+    Ours,
+    Theirs,
+    Base,
+}
+
+/// Conflicted files listed in the conflicts popup
+pub struct ConflictsView {
+    pub entries: Vec<crate::git::ConflictEntry>, // Conflicts found when the popup opened
+    pub resolved: Vec<String>,                   // Paths resolved since then
+    pub selected: usize,
+    pub pane: ConflictPane,
+    pub scroll: usize,
+    pub message: Option<String>, // Result of the last action
+}
+
+/// Tables whose rows can be selected with a mouse click
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MouseList {
+    Files,
+    SaveChanges,
+    History,
+    Branches,
+    Tags,
+    Activity,
+}
+
+/// Where a clickable table's rows were drawn, and which row is at the top
+#[derive(Debug, Clone, Copy)]
+pub struct MouseTarget {
+    pub list: MouseList,
+    pub rows: Rect, // Below the header, inside the border
+    pub offset: usize,
+}
+
+/// Open pull requests listed in the pull request popup
+pub struct PullRequestsView {
+    pub pulls: Vec<crate::forge::PullRequest>,
+    pub selected: usize,
+    pub rx: Option<std::sync::mpsc::Receiver<Result<Vec<crate::forge::PullRequest>, String>>>, // Listing in flight
+    pub message: Option<String>, // Lookup error or result of the last action
+}
+
+/// Which part of the new pull request popup has focus
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DraftField {
+    Base,
+    Title,
+    Body,
+}
+
+/// A pull request for the current branch, prefilled from its commits
+pub struct PullRequestDraft {
+    pub branch: String,
+    pub bases: Vec<String>, // Origin's branches, its default branch first
+    pub base: usize,
+    pub title: TextArea<'static>,
+    pub body: TextArea<'static>,
+    pub field: DraftField,
+    pub rx: Option<std::sync::mpsc::Receiver<Result<String, String>>>, // Submission in flight
+    pub created: Option<String>, // Web page of the pull request once it exists
+    pub message: Option<String>, // Why the last submission failed
+}
+
+/// The popup listing what the commit formatter changed before the last commit
+pub struct FormatReportView {
+    pub report: crate::hooks::FormatReport,
+    pub scroll: usize, // First line shown
+}
+
+/// Streamed output of a hook run, shown in a popup while it executes
+pub struct HookOutput {
+    pub hook_name: String,
+    pub lines: Vec<String>,
+    pub status: HookRunStatus,
+    pub scroll: usize,
+    pub then: AfterHook,
+    running: Option<crate::hooks::RunningHook>,
+}
+
+impl HookOutput {
+    /// Whether this hook decides if a push goes ahead
+    pub fn gates_push(&self) -> bool {
+        matches!(self.then, AfterHook::RunPrePushHook | AfterHook::Push)
+    }
+}
+
+/// What to do once a hook in the popup exits successfully
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AfterHook {
+    Nothing,        // Informational command (post-commit-ui, post-push-ui, ...)
+    RunPrePushHook, // pre-push-ui passed: continue with git's pre-push hook
+    Push,           // pre-push hook passed: push
+    Commit,         // gitix.verify.command passed: commit the staged files
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum HookRunStatus {
+    Running,
+    Passed,
+    Failed(Option<i32>), // Exit code, if the hook wasn't killed by a signal
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum SaveChangesFocus {
     FileList,
     CommitMessage,
+    HunkView, // Picking hunks of the selected file in the diff pane
+    Review,   // Moving a line cursor over the diff to leave notes
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -80,6 +1011,8 @@ pub enum SettingsFocus {
     Author,
     Theme,
     Git,
+    Hooks,
+    Remotes,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -98,19 +1031,47 @@ pub enum ThemeFocus {
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum GitFocus {
+    Profile,
     PullRebase,
+    Notifications,
+    AutoRefresh,
+    ForkPush,
+    CommitWrap,
+    InitTemplate,
+    PermanentDelete,
+    UpdateCheck,
+    Forge,
 }
 
+/// Auto-refresh intervals offered in Settings, in minutes (0 = off)
+pub const AUTO_REFRESH_CHOICES: [u32; 6] = [0, 1, 2, 5, 10, 30];
+
+/// Frames of the loading spinner, advanced once per event loop tick
+const SPINNER_CHARS: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Width in cells of the transfer progress bar in the status bar
+const LOADING_BAR_WIDTH: usize = 12;
+
+/// Commit body hard-wrap columns offered in Settings (0 = off)
+pub const COMMIT_WRAP_CHOICES: [usize; 4] = [0, 72, 80, 100];
+
 impl Default for AppState {
     fn default() -> Self {
         let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
         let mut state = AppState {
             git_enabled: false,
+            read_only: false,
             show_init_prompt: false,
+            init_setup: None,
+            publish: None,
             repo_root: None,
+            tab_bar: crate::tui::tabs::TabRegistry::default(),
+            active_tab: crate::tui::tabs::Tab::Overview,
             root_dir: cwd.clone(),
             current_dir: cwd,
             files_selected_row: 0,
+            blame_view: None,
+            blame_cache: std::collections::HashMap::new(),
             save_changes_table_state: TableState::default(),
             staged_files: Vec::new(),
             commit_message: TextArea::new(vec![String::new()]),
@@ -120,12 +1081,20 @@ impl Default for AppState {
             help_popup_scrollbar_state: ScrollbarState::default(),
             show_template_popup: false,
             template_popup_selection: TemplatePopupSelection::No,
+            commit_skip_hooks: false,
+            commit_amend: None,
+            amend_confirm: false,
+            discard_confirm: None,
+            last_commit_summary: None,
+            staged_encoding_warning: None,
+            format_report: None,
+            commit_draft_restored: false,
 
             // Settings state
             settings_focus: SettingsFocus::Author,
             settings_author_focus: AuthorFocus::Name,
             settings_theme_focus: ThemeFocus::Accent,
-            settings_git_focus: GitFocus::PullRebase,
+            settings_git_focus: GitFocus::Profile,
             user_name_input: TextArea::new(vec![String::new()]),
             user_email_input: TextArea::new(vec![String::new()]),
             current_theme_accent: AccentColor::Blue,
@@ -133,29 +1102,148 @@ impl Default for AppState {
             current_theme_accent3: AccentColor::Pink,
             current_theme_title: TitleColor::Overlay0,
             settings_status_message: None,
+            settings_hooks: Vec::new(),
+            settings_hooks_dir: None,
+            settings_hook_selected: 0,
+            show_hook_template_popup: false,
+            settings_remotes: Vec::new(),
+            settings_remote_selected: 0,
+            remote_edit: None,
+            remote_remove: None,
+            hook_template_selected: 0,
 
             // Git configuration
+            profile: Profile::Standard,
+            time_format: TimeFormat::Relative,
+            time_zone: CommitTimeZone::Original,
             pull_rebase: true, // Default to rebase
+            notifications_enabled: false, // Opt-in
+            auto_refresh_minutes: 5,
+            fork_push_after_sync: false,
+            commit_wrap_column: 0,
+            init_template: None,
+            permanent_delete: false,
+            update_check: false, // Opt-in, it talks to GitHub
+            forge_override: None,
+            update_check_rx: None,
+            update_available: None,
+            terminal_focused: true,
+            on_update_tab: false,
 
             save_changes_git_status: Vec::new(),
             save_changes_git_status_loaded: false,
+            file_detail: None,
+            save_changes_diff: None,
+            show_save_changes_diff: true,
+            review_notes: Vec::new(),
+            review_note_input: None,
+            review_notes_view: None,
+            clean_preview: None,
+            stage_pattern: None,
+            save_changes_reconcile: None,
             status_git_status: Vec::new(),
             status_git_status_loaded: false,
+            status_summary: None,
+            status_flagged_files: Vec::new(),
+            flagged_files_view: None,
+            files_scope: FilesScope::Launch,
+            overview_calendar_cursor: None,
+            overview_calendar_start: None,
+            day_commits: None,
+            repo_snapshot: None,
+            watcher: None,
+            history: HistoryView::default(),
+            history_filter_edit: None,
+            history_export: None,
+            history_message: None,
+            history_notes_sync: None,
+            note_edit: None,
+            reflog_view: None,
+            undo_confirm: None,
+            abort_confirm: None,
+            cherry_pick_confirm: None,
+            reset_confirm: None,
+            rebase_plan: None,
+            credential_requests: None,
+            credential_prompt: None,
+            branches: BranchesView::default(),
+            branch_create: None,
+            branch_delete: None,
+            branch_action: None,
+            merge_draft: None,
+            tags: TagsView::default(),
+            tag_create: None,
+            tag_delete: None,
+            tour: None,
+            explain: None,
+            backup_minutes: 0,
+            backup_since: Instant::now(),
+            backup_rx: None,
+            backup_error: None,
+            backup_browser: None,
+            sparkline_bucket: SparklineBucket::Week,
+            sparkline_range_months: 6,
+            activity_filter: ActivityFilter::default(),
+            churn_view: None,
+            churn_cache: Vec::new(),
+            churn_scan: None,
+            language_stats: None,
+            language_scan: None,
+            language_error: None,
+            language_view: None,
+            recursive_status: None,
+            recursive_scan: None,
+            nested_view: None,
+            files_delete_confirm: None,
+            last_status_counts: None,
 
             // Update tab state
             update_remote_status: None,
             update_recent_operations: Vec::new(),
+            sync_history_size: crate::sync_log::DEFAULT_HISTORY_SIZE,
+            update_last_refreshed: None,
+            update_auto_refresh_since: Instant::now(),
+            update_fork: None,
+            update_forge: None,
+            forge_ci: None,
+            forge_ci_rx: None,
+            sync_confirm: None,
+            push_target: None,
+
+            // Activity tab state
+            activity_log: Vec::new(),
+            activity_table_state: TableState::default(),
+            activity_status_message: None,
+
+            focus_memory: TabFocusMemory::default(),
+
+            // Hook output popup state
+            hook_output: None,
+            conflicts: None,
+            pull_requests: None,
+            pull_request_draft: None,
+            mouse_targets: Vec::new(),
 
             // Error popup state
+            quit_confirm: None,
+            quit_warn_unpushed: true,
+            quit_stash_days: 14,
             show_error_popup: false,
             error_popup_title: String::new(),
             error_popup_message: String::new(),
+            error_popup_chain: Vec::new(),
+            error_popup_operation: None,
+            error_popup_copied: None,
 
             // Loading indicator state
             is_loading: false,
             loading_message: String::new(),
             spinner_state: 0,
             pending_refresh_work: false,
+            pending_refresh_is_auto: false,
+            pending_fetch_all: false,
+            sync_worker: None,
+            sync_progress: None,
         };
         state.check_git_status();
         state.load_settings();
@@ -165,16 +1253,19 @@ impl Default for AppState {
 
 impl AppState {
     pub fn check_git_status(&mut self) {
+        self.invalidate_repo_snapshot();
         match gix::discover(&self.current_dir) {
             Ok(repo) => {
                 self.git_enabled = true;
                 self.show_init_prompt = false;
                 self.repo_root = Some(repo.path().to_path_buf());
+                self.watcher = crate::watcher::RepoWatcher::start(&self.current_dir);
             }
             Err(_) => {
                 self.git_enabled = false;
                 self.show_init_prompt = true;
                 self.repo_root = None;
+                self.watcher = None;
             }
         }
     }
@@ -208,67 +1299,533 @@ impl AppState {
         }
 
         // Load git configuration
+        if let Ok(Some(profile)) = crate::config::get_profile() {
+            self.profile = profile;
+        }
+        if let Ok(Some(format)) = crate::config::get_time_format() {
+            self.time_format = format;
+        }
+        if let Ok(Some(zone)) = crate::config::get_time_zone() {
+            self.time_zone = zone;
+        }
+        if let Ok(Some(hide_merges)) = crate::config::get_activity_exclude_merges() {
+            self.activity_filter.hide_merges = hide_merges;
+        }
+        if let Ok(Some(patterns)) = crate::config::get_activity_exclude_authors() {
+            self.activity_filter.author_patterns = patterns;
+        }
         if let Ok(Some(pull_rebase)) = crate::config::get_pull_rebase() {
             self.pull_rebase = pull_rebase;
         }
-    }
-
-    /// Save current settings to git config
-    pub fn save_settings(&mut self) -> Result<(), String> {
-        if !self.git_enabled {
-            return Err("Not in a git repository".to_string());
+        if let Ok(Some(enabled)) = crate::config::get_notifications_enabled() {
+            self.notifications_enabled = enabled;
         }
-
-        // Save user name and email
-        let name = self.user_name_input.lines()[0].clone();
-        let email = self.user_email_input.lines()[0].clone();
-
-        if !name.is_empty() {
-            if let Err(e) = crate::config::set_user_name(&name) {
-                return Err(format!("Failed to save user name: {}", e));
-            }
+        if let Ok(Some(minutes)) = crate::config::get_auto_refresh_minutes() {
+            self.auto_refresh_minutes = minutes;
         }
-
-        if !email.is_empty() {
-            if let Err(e) = crate::config::set_user_email(&email) {
-                return Err(format!("Failed to save user email: {}", e));
-            }
+        if let Ok(Some(minutes)) = crate::config::get_backup_minutes() {
+            self.backup_minutes = minutes;
         }
-
-        // Save theme settings
-        if let Err(e) = crate::config::set_theme_accent(self.current_theme_accent) {
-            return Err(format!("Failed to save theme accent: {}", e));
+        if let Ok(Some(size)) = crate::config::get_sync_history_size() {
+            self.sync_history_size = size;
         }
-        if let Err(e) = crate::config::set_theme_accent2(self.current_theme_accent2) {
-            return Err(format!("Failed to save theme accent2: {}", e));
+        if let Ok(Some(push)) = crate::config::get_fork_push_after_sync() {
+            self.fork_push_after_sync = push;
         }
-        if let Err(e) = crate::config::set_theme_accent3(self.current_theme_accent3) {
-            return Err(format!("Failed to save theme accent3: {}", e));
+        if let Ok(Some(permanent)) = crate::config::get_permanent_delete() {
+            self.permanent_delete = permanent;
         }
-        if let Err(e) = crate::config::set_theme_title_color(self.current_theme_title) {
-            return Err(format!("Failed to save theme title color: {}", e));
+        if let Ok(template) = crate::config::get_init_template() {
+            self.init_template = template;
+        }
+        if let Ok(Some(column)) = crate::config::get_commit_wrap_column() {
+            self.commit_wrap_column = column;
+        }
+        if let Ok(Some(warn)) = crate::config::get_quit_warn_unpushed() {
+            self.quit_warn_unpushed = warn;
+        }
+        if let Ok(Some(days)) = crate::config::get_quit_stash_days() {
+            self.quit_stash_days = days;
+        }
+        if let Ok(Some(check)) = crate::config::get_update_check() {
+            self.update_check = check;
+        }
+        if let Ok(kind) = crate::config::get_forge() {
+            self.forge_override = kind;
         }
 
-        // Save git configuration
-        if let Err(e) = crate::config::set_pull_rebase(self.pull_rebase) {
-            return Err(format!("Failed to save pull rebase setting: {}", e));
+        self.refresh_hooks();
+        self.refresh_remotes();
+    }
+
+    /// Reload the hooks list from disk, keeping the selection in range
+    pub fn refresh_hooks(&mut self) {
+        self.settings_hooks_dir = crate::hooks::hooks_dir().ok();
+        self.settings_hooks = crate::hooks::list_hooks().unwrap_or_default();
+        if self.settings_hook_selected >= self.settings_hooks.len() {
+            self.settings_hook_selected = self.settings_hooks.len().saturating_sub(1);
         }
+    }
 
-        Ok(())
+    /// Get the currently selected hook, if any
+    pub fn selected_hook(&self) -> Option<&crate::hooks::HookInfo> {
+        self.settings_hooks.get(self.settings_hook_selected)
     }
 
-    pub fn try_init_repo(&mut self) -> Result<(), gix::init::Error> {
-        match gix::init(&self.current_dir) {
-            Ok(repo) => {
+    /// Toggle the selected hook between enabled and disabled (.sample)
+    pub fn toggle_selected_hook(&mut self) {
+        if self.refuse_read_only("Enabling or disabling hooks") {
+            return;
+        }
+        let hook = match self.selected_hook() {
+            Some(hook) => hook.clone(),
+            None => return,
+        };
+
+        let result = if hook.enabled {
+            crate::hooks::disable_hook(&hook)
+        } else {
+            crate::hooks::enable_hook(&hook)
+        };
+
+        self.settings_status_message = Some(match result {
+            Ok(()) if hook.enabled => format!("✓ Disabled {} hook", hook.name),
+            Ok(()) => format!("✓ Enabled {} hook", hook.name),
+            Err(e) => format!("✗ Failed to toggle {} hook: {}", hook.name, e),
+        });
+        self.refresh_hooks();
+    }
+
+    /// Install the hook template selected in the template picker
+    pub fn install_selected_hook_template(&mut self) {
+        if self.refuse_read_only("Installing hooks") {
+            return;
+        }
+        self.show_hook_template_popup = false;
+        let template = match crate::hooks::HOOK_TEMPLATES.get(self.hook_template_selected) {
+            Some(template) => template,
+            None => return,
+        };
+
+        self.settings_status_message = Some(match crate::hooks::install_template(template) {
+            Ok(_) => format!("✓ Installed {} as {} hook", template.name, template.hook),
+            Err(e) => format!("✗ Failed to install {}: {}", template.name, e),
+        });
+        self.refresh_hooks();
+
+        // Select the freshly installed hook
+        if let Some(idx) = self
+            .settings_hooks
+            .iter()
+            .position(|h| h.name == template.hook)
+        {
+            self.settings_hook_selected = idx;
+        }
+    }
+
+    /// Reload the remotes list, keeping the selection in range
+    pub fn refresh_remotes(&mut self) {
+        self.settings_remotes = crate::git::remote_details().unwrap_or_default();
+        if self.settings_remote_selected >= self.settings_remotes.len() {
+            self.settings_remote_selected = self.settings_remotes.len().saturating_sub(1);
+        }
+    }
+
+    /// Get the currently selected remote, if any
+    pub fn selected_remote(&self) -> Option<&crate::git::RemoteInfo> {
+        self.settings_remotes.get(self.settings_remote_selected)
+    }
+
+    /// Open the remote form empty, named origin when there is no origin yet
+    pub fn open_remote_add(&mut self) {
+        let name = if self.settings_remotes.iter().any(|r| r.name == "origin") {
+            String::new()
+        } else {
+            "origin".to_string()
+        };
+        self.open_remote_form(None, name, String::new());
+    }
+
+    /// Open the remote form on the selected remote to rename it or change its URL
+    pub fn open_remote_edit(&mut self) {
+        if let Some(remote) = self.selected_remote() {
+            let (name, url) = (remote.name.clone(), remote.url.clone());
+            self.open_remote_form(Some(name.clone()), name, url);
+        }
+    }
+
+    fn open_remote_form(&mut self, original: Option<String>, name: String, url: String) {
+        let field = |value: String| {
+            let mut input = TextArea::new(vec![value]);
+            input.move_cursor(tui_textarea::CursorMove::End);
+            input
+        };
+        self.remote_edit = Some(RemoteEdit {
+            editing_url: !name.is_empty(),
+            original,
+            name: field(name),
+            url: field(url),
+            error: None,
+        });
+    }
+
+    /// Add the remote in the form, or apply a rename and URL change to the one being edited
+    ///
+    /// The form stays open with the reason when the name or URL is refused.
+    pub fn save_remote_edit(&mut self) {
+        if self.refuse_read_only("Changing remotes") {
+            self.remote_edit = None;
+            return;
+        }
+        let edit = match self.remote_edit.as_mut() {
+            Some(edit) => edit,
+            None => return,
+        };
+        let name = edit.name.lines().join("").trim().to_string();
+        let url = edit.url.lines().join("").trim().to_string();
+        let original = edit.original.clone();
+        let taken = self
+            .settings_remotes
+            .iter()
+            .any(|r| r.name == name && original.as_deref() != Some(name.as_str()));
+        let invalid = crate::git::validate_remote_name(&name)
+            .and_then(|()| match taken {
+                true => Err(format!("There is already a remote named {}", name)),
+                false => Ok(()),
+            })
+            .and_then(|()| crate::git::validate_remote_url(&url));
+        if let Err(e) = invalid {
+            edit.error = Some(e);
+            return;
+        }
+        self.remote_edit = None;
+
+        let (operation, result) = match original {
+            None => (
+                format!("git2: remote add {} {}", name, url),
+                crate::git::add_remote(&name, &url).map(|()| format!("Added {} ({})", name, url)),
+            ),
+            Some(old) => {
+                let old_url = self
+                    .settings_remotes
+                    .iter()
+                    .find(|r| r.name == old)
+                    .map(|r| r.url.clone())
+                    .unwrap_or_default();
+                let mut steps = Vec::new();
+                let mut messages = Vec::new();
+                let mut result = Ok(());
+                if old != name {
+                    steps.push(format!("remote rename {} {}", old, name));
+                    result = crate::git::rename_remote(&old, &name).map(|problems| {
+                        messages.push(format!("Renamed {} to {}", old, name));
+                        if !problems.is_empty() {
+                            let problems = problems.join(", ");
+                            messages.push(format!("fix these refspecs by hand: {}", problems));
+                        }
+                    });
+                }
+                if result.is_ok() && old_url != url {
+                    steps.push(format!("remote set-url {} {}", name, url));
+                    result = crate::git::set_remote_url(&name, &url)
+                        .map(|()| messages.push(format!("{} now points at {}", name, url)));
+                }
+                if steps.is_empty() {
+                    return;
+                }
+                (
+                    format!("git2: {}", steps.join(", then ")),
+                    result.map(|()| messages.join("; ")),
+                )
+            }
+        };
+        let result = result.map_err(|e| e.to_string());
+        self.record_activity(
+            crate::activity::ActivityKind::Remote,
+            &name,
+            &operation,
+            result.clone(),
+        );
+        self.settings_status_message = Some(match result {
+            Ok(message) => format!("✓ {}", message),
+            Err(e) => format!("✗ Failed to save remote {}: {}", name, e),
+        });
+        self.remotes_changed();
+        if let Some(idx) = self.settings_remotes.iter().position(|r| r.name == name) {
+            self.settings_remote_selected = idx;
+        }
+    }
+
+    /// Ask before removing the selected remote
+    pub fn request_remote_remove(&mut self) {
+        if self.refuse_read_only("Removing remotes") {
+            return;
+        }
+        self.remote_remove = self.selected_remote().map(|remote| remote.name.clone());
+    }
+
+    /// Remove the remote waiting for confirmation
+    pub fn confirm_remote_remove(&mut self) {
+        let name = match self.remote_remove.take() {
+            Some(name) => name,
+            None => return,
+        };
+        let result = crate::git::remove_remote(&name)
+            .map(|()| format!("Removed {}", name))
+            .map_err(|e| e.to_string());
+        self.record_activity(
+            crate::activity::ActivityKind::Remote,
+            &name,
+            &format!("git2: remote remove {}", name),
+            result.clone(),
+        );
+        self.settings_status_message = Some(match result {
+            Ok(message) => format!("✓ {}", message),
+            Err(e) => format!("✗ Failed to remove {}: {}", name, e),
+        });
+        self.remotes_changed();
+    }
+
+    /// Re-read the remotes and origin's status so the Update tab reflects the change at once
+    fn remotes_changed(&mut self) {
+        self.refresh_remotes();
+        self.update_remote_status = crate::git::get_remote_status().ok();
+    }
+
+    /// Save current settings to git config
+    pub fn save_settings(&mut self) -> Result<(), String> {
+        if !self.git_enabled {
+            return Err("Not in a git repository".to_string());
+        }
+
+        // Save user name and email
+        let name = self.user_name_input.lines()[0].clone();
+        let email = self.user_email_input.lines()[0].clone();
+
+        if !name.is_empty() {
+            if let Err(e) = crate::config::set_user_name(&name) {
+                return Err(format!("Failed to save user name: {}", e));
+            }
+        }
+
+        if !email.is_empty() {
+            if let Err(e) = crate::config::set_user_email(&email) {
+                return Err(format!("Failed to save user email: {}", e));
+            }
+        }
+
+        // Save theme settings
+        if let Err(e) = crate::config::set_theme_accent(self.current_theme_accent) {
+            return Err(format!("Failed to save theme accent: {}", e));
+        }
+        if let Err(e) = crate::config::set_theme_accent2(self.current_theme_accent2) {
+            return Err(format!("Failed to save theme accent2: {}", e));
+        }
+        if let Err(e) = crate::config::set_theme_accent3(self.current_theme_accent3) {
+            return Err(format!("Failed to save theme accent3: {}", e));
+        }
+        if let Err(e) = crate::config::set_theme_title_color(self.current_theme_title) {
+            return Err(format!("Failed to save theme title color: {}", e));
+        }
+
+        // Save git configuration
+        if let Err(e) = crate::config::set_profile(self.profile) {
+            return Err(format!("Failed to save profile: {}", e));
+        }
+        if let Err(e) = crate::config::set_pull_rebase(self.pull_rebase) {
+            return Err(format!("Failed to save pull rebase setting: {}", e));
+        }
+        if let Err(e) = crate::config::set_notifications_enabled(self.notifications_enabled) {
+            return Err(format!("Failed to save notification setting: {}", e));
+        }
+        if let Err(e) = crate::config::set_auto_refresh_minutes(self.auto_refresh_minutes) {
+            return Err(format!("Failed to save auto-refresh setting: {}", e));
+        }
+        if let Err(e) = crate::config::set_fork_push_after_sync(self.fork_push_after_sync) {
+            return Err(format!("Failed to save fork sync setting: {}", e));
+        }
+        if let Err(e) = crate::config::set_commit_wrap_column(self.commit_wrap_column) {
+            return Err(format!("Failed to save commit wrap setting: {}", e));
+        }
+        if let Err(e) = crate::config::set_permanent_delete(self.permanent_delete) {
+            return Err(format!("Failed to save delete setting: {}", e));
+        }
+        if let Err(e) = crate::config::set_update_check(self.update_check) {
+            return Err(format!("Failed to save update check setting: {}", e));
+        }
+        if let Err(e) = crate::config::set_init_template(self.init_template.as_deref()) {
+            return Err(format!("Failed to save new repository template: {}", e));
+        }
+        if let Err(e) = crate::config::set_forge(self.forge_override) {
+            return Err(format!("Failed to save forge setting: {}", e));
+        }
+        self.detect_forge();
+
+        Ok(())
+    }
+
+    pub fn try_init_repo(&mut self) -> Result<(), gix::init::Error> {
+        let operation = format!("gix::init({})", self.current_dir.display());
+        let target = self.current_dir.display().to_string();
+        match gix::init(&self.current_dir) {
+            Ok(repo) => {
                 self.git_enabled = true;
                 self.show_init_prompt = false;
                 self.repo_root = Some(repo.path().to_path_buf());
+                // gix doesn't know init.templateDir; copy it the way `git init` would
+                let _ = crate::init_template::apply_git_template_dir(repo.path());
+                self.record_activity(
+                    crate::activity::ActivityKind::Init,
+                    &target,
+                    &operation,
+                    Ok("Initialized empty repository".to_string()),
+                );
+                self.open_init_setup();
                 Ok(())
             }
-            Err(e) => Err(e),
+            Err(e) => {
+                self.record_activity(
+                    crate::activity::ActivityKind::Init,
+                    &target,
+                    &operation,
+                    Err(e.to_string()),
+                );
+                Err(e)
+            }
+        }
+    }
+
+    /// Ask for the branch name and first commit of the repository just created
+    fn open_init_setup(&mut self) {
+        let branch = crate::config::get_init_default_branch()
+            .ok()
+            .flatten()
+            .or_else(crate::git::unborn_branch_name)
+            .unwrap_or_else(|| "main".to_string());
+        let mut input = TextArea::new(vec![branch]);
+        input.move_cursor(tui_textarea::CursorMove::End);
+        let has_files = std::fs::read_dir(&self.current_dir)
+            .map(|entries| entries.flatten().any(|entry| entry.file_name() != ".git"))
+            .unwrap_or(false);
+        let templates = crate::init_template::list_templates();
+        // Settings aren't loaded outside a repository, so read the default directly
+        let template = crate::config::get_init_template()
+            .ok()
+            .flatten()
+            .filter(|name| templates.contains(name));
+        self.init_setup = Some(InitSetup {
+            branch: input,
+            commit: if has_files || template.is_some() {
+                InitialCommit::Files
+            } else {
+                InitialCommit::Empty
+            },
+            has_files,
+            template,
+            templates,
+        });
+    }
+
+    /// Cycle the gitix template of the init setup, including "none"
+    pub fn cycle_init_setup_template(&mut self, forward: bool) {
+        if let Some(setup) = self.init_setup.as_mut() {
+            let mut choices: Vec<Option<String>> = vec![None];
+            choices.extend(setup.templates.iter().cloned().map(Some));
+            let idx = choices.iter().position(|c| *c == setup.template).unwrap_or(0);
+            let next = if forward {
+                (idx + 1) % choices.len()
+            } else {
+                (idx + choices.len() - 1) % choices.len()
+            };
+            setup.template = choices[next].clone();
+            if !setup.has_files && setup.template.is_none() && setup.commit == InitialCommit::Files {
+                setup.commit = InitialCommit::Empty;
+            }
         }
     }
 
+    /// Cycle the first-commit choice of the init setup (Files only when there are files)
+    pub fn cycle_initial_commit(&mut self, forward: bool) {
+        if let Some(setup) = self.init_setup.as_mut() {
+            let mut choices = vec![InitialCommit::Empty];
+            if setup.has_files || setup.template.is_some() {
+                choices.push(InitialCommit::Files);
+            }
+            choices.push(InitialCommit::Skip);
+            let idx = choices.iter().position(|c| *c == setup.commit).unwrap_or(0);
+            let next = if forward {
+                (idx + 1) % choices.len()
+            } else {
+                (idx + choices.len() - 1) % choices.len()
+            };
+            setup.commit = choices[next];
+        }
+    }
+
+    /// Apply the init setup: rename the unborn branch, copy the template, then the first commit
+    pub fn apply_init_setup(&mut self) {
+        let (branch, commit, template) = match &self.init_setup {
+            Some(setup) => (
+                setup.branch.lines().join("").trim().to_string(),
+                setup.commit,
+                setup.template.clone(),
+            ),
+            None => return,
+        };
+
+        if let Err(e) = crate::git::set_initial_branch(&branch) {
+            // Leave the popup open so the name can be fixed
+            self.show_error(
+                "Invalid Branch Name",
+                &format!("Could not use '{}':\n\n{}", branch, e),
+            );
+            return;
+        }
+        self.init_setup = None;
+
+        if let Some(name) = template {
+            let result = crate::init_template::apply_template(&name, &self.current_dir);
+            if let Err(e) = &result {
+                self.show_error(
+                    "Template Failed",
+                    &format!("Could not apply template '{}':\n\n{}", name, e),
+                );
+            }
+            self.record_activity(
+                crate::activity::ActivityKind::Init,
+                &name,
+                &format!("gitix: apply template {}", name),
+                result
+                    .map(|count| format!("Copied {} file(s) from template", count))
+                    .map_err(|e| e.to_string()),
+            );
+        }
+
+        if commit == InitialCommit::Skip {
+            return;
+        }
+        let result = crate::git::create_initial_commit(commit == InitialCommit::Files);
+        self.record_activity(
+            crate::activity::ActivityKind::Commit,
+            &branch,
+            "libgit2: commit \"Initial commit\"",
+            match &result {
+                Ok(()) => Ok("Initial commit".to_string()),
+                Err(e) => Err(e.to_string()),
+            },
+        );
+        match result {
+            // With a first commit there is something to push, so offer a remote for it
+            Ok(()) => self.open_publish(),
+            Err(e) => self.show_error(
+                "Initial Commit Failed",
+                &format!("The repository was created, but the first commit failed:\n\n{}", e),
+            ),
+        }
+        self.invalidate_status_git_status();
+        self.invalidate_save_changes_git_status();
+    }
+
     pub fn decline_init_repo(&mut self) {
         self.git_enabled = false;
         self.show_init_prompt = false;
@@ -325,13 +1882,91 @@ impl AppState {
         if !self.save_changes_git_status_loaded {
             self.save_changes_git_status = crate::git::get_git_status().unwrap_or_default();
             self.save_changes_git_status_loaded = true;
+            self.save_changes_diff = None;
+            self.check_staged_encodings();
+            self.emit_status_changed(status_counts(&self.save_changes_git_status));
         }
     }
 
+    /// Look for staged files in different text encodings, to warn before they are committed together
+    fn check_staged_encodings(&mut self) {
+        self.staged_encoding_warning = if self.save_changes_git_status.iter().any(|f| f.staged) {
+            crate::git::staged_encodings()
+                .ok()
+                .and_then(|files| crate::encoding::mixed_encoding_warning(&files))
+        } else {
+            None
+        };
+    }
+
     /// Refresh git status for save changes tab (called after staging/unstaging operations)
+    ///
+    /// The selection follows the selected file by path rather than by index, and
+    /// the table's scroll offset is left alone so the list doesn't jump.
     pub fn refresh_save_changes_git_status(&mut self) {
-        self.save_changes_git_status = crate::git::get_git_status().unwrap_or_default();
+        self.invalidate_repo_snapshot();
+        self.save_changes_reconcile = None;
+        self.apply_save_changes_git_status(crate::git::get_git_status().unwrap_or_default());
+    }
+
+    /// Re-scan the working tree on a background thread; `poll_save_changes_reconcile` applies the result
+    ///
+    /// Only the latest scan counts: starting a new one drops the previous receiver,
+    /// so a slow scan can't overwrite a newer optimistic update.
+    pub fn start_save_changes_reconcile(&mut self) {
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            if let Ok(files) = crate::git::get_git_status() {
+                let _ = tx.send(files);
+            }
+        });
+        self.save_changes_reconcile = Some(rx);
+    }
+
+    /// Apply a finished background re-scan, if there is one
+    pub fn poll_save_changes_reconcile(&mut self) {
+        let result = match &self.save_changes_reconcile {
+            Some(rx) => rx.try_recv(),
+            None => return,
+        };
+        match result {
+            Ok(files) => {
+                self.save_changes_reconcile = None;
+                self.apply_save_changes_git_status(files);
+            }
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => self.save_changes_reconcile = None,
+            Err(std::sync::mpsc::TryRecvError::Empty) => {}
+        }
+    }
+
+    /// Replace the Save Changes list, keeping the selection on the same file
+    fn apply_save_changes_git_status(&mut self, files: Vec<crate::git::GitFileStatus>) {
+        let selected_path = self
+            .save_changes_table_state
+            .selected()
+            .and_then(|idx| self.save_changes_git_status.get(idx))
+            .map(|file| file.path.clone());
+
+        self.save_changes_git_status = files;
         self.save_changes_git_status_loaded = true;
+        self.save_changes_diff = None;
+        self.check_staged_encodings();
+        self.emit_status_changed(status_counts(&self.save_changes_git_status));
+
+        if self.save_changes_git_status.is_empty() {
+            self.save_changes_table_state.select(None);
+            return;
+        }
+        let restored = selected_path.and_then(|path| {
+            self.save_changes_git_status.iter().position(|file| file.path == path)
+        });
+        let idx = match (restored, self.save_changes_table_state.selected()) {
+            (Some(idx), _) => idx,
+            // The file is gone (committed, reverted); stay at the same height
+            (None, Some(old_idx)) => old_idx.min(self.save_changes_git_status.len() - 1),
+            (None, None) => 0,
+        };
+        self.save_changes_table_state.select(Some(idx));
     }
 
     /// Get cached git status for save changes tab
@@ -342,14 +1977,279 @@ impl AppState {
     /// Mark git status as needing refresh (called when leaving save changes tab)
     pub fn invalidate_save_changes_git_status(&mut self) {
         self.save_changes_git_status_loaded = false;
+        self.invalidate_repo_snapshot();
     }
 
-    /// Load git status for files tab (called when tab becomes active)
+    /// Drop the cached branch and history figures so the next draw reads them again
+    pub fn invalidate_repo_snapshot(&mut self) {
+        self.repo_snapshot = None;
+    }
+
+    /// The cached branch and history figures, read now if something dropped them
+    pub fn repo_snapshot(&mut self) -> &mut RepoSnapshot {
+        self.repo_snapshot.get_or_insert_with(|| {
+            let mut snapshot =
+                match (crate::git::get_current_branch(), crate::git::get_current_remote_branch()) {
+                    (Ok(branch), Ok(remote_branch)) => RepoSnapshot {
+                        branch: Some(branch),
+                        remote_branch,
+                        ..RepoSnapshot::default()
+                    },
+                    _ => RepoSnapshot::default(),
+                };
+            snapshot.head = crate::git::head_id().ok();
+            snapshot.has_origin = crate::git::has_remote_origin().unwrap_or(false);
+            snapshot.conflicted = crate::git::conflicted_paths();
+            snapshot.merge_in_progress = crate::git::merge_in_progress();
+            snapshot.planned_rebase_in_progress = crate::git::planned_rebase_in_progress();
+            snapshot.cherry_pick_in_progress = crate::git::cherry_pick_in_progress();
+            snapshot.rebase_in_progress = crate::git::rebase_in_progress();
+            snapshot
+        })
+    }
+
+    /// Note what is selected on `tab` before switching away from it
+    pub fn remember_tab_focus(&mut self, tab: crate::tui::tabs::Tab) {
+        use crate::tui::tabs::Tab;
+        match tab {
+            Tab::SaveChanges => {
+                self.focus_memory.save_changes_path = self
+                    .save_changes_table_state
+                    .selected()
+                    .and_then(|idx| self.save_changes_git_status.get(idx))
+                    .map(|file| file.path.clone());
+            }
+            Tab::Files => {
+                let add_parent = self.files_add_parent();
+                let files = crate::files::list_files(&self.current_dir, add_parent);
+                self.focus_memory.files_entry =
+                    files.get(self.files_selected_row).map(|entry| entry.name.clone());
+            }
+            Tab::Activity => self.focus_memory.activity_len = self.activity_log.len(),
+            _ => {}
+        }
+    }
+
+    /// Put the selection on `tab` back where it was, after its data has been reloaded
+    pub fn restore_tab_focus(&mut self, tab: crate::tui::tabs::Tab) {
+        use crate::tui::tabs::Tab;
+        match tab {
+            Tab::SaveChanges => {
+                self.load_save_changes_git_status();
+                let restored = self.focus_memory.save_changes_path.as_ref().and_then(|path| {
+                    self.save_changes_git_status.iter().position(|file| &file.path == path)
+                });
+                if let Some(idx) = restored {
+                    self.save_changes_table_state.select(Some(idx));
+                } else if let Some(idx) = self.save_changes_table_state.selected() {
+                    // The file is gone (committed, reverted); stay at the same height
+                    let last = self.save_changes_git_status.len().saturating_sub(1);
+                    self.save_changes_table_state.select(Some(idx.min(last)));
+                }
+            }
+            Tab::Files => {
+                // The directory may have been removed while we were away
+                if !self.current_dir.is_dir() {
+                    self.current_dir = self.root_dir.clone();
+                    self.files_selected_row = 0;
+                }
+                let add_parent = self.files_add_parent();
+                let files = crate::files::list_files(&self.current_dir, add_parent);
+                if let Some(name) = &self.focus_memory.files_entry {
+                    if let Some(idx) = files.iter().position(|entry| &entry.name == name) {
+                        self.files_selected_row = idx;
+                    }
+                }
+            }
+            Tab::Activity => {
+                // New entries are added at the top; shift down so the same one stays selected
+                let added = self.activity_log.len().saturating_sub(self.focus_memory.activity_len);
+                if let Some(idx) = self.activity_table_state.selected() {
+                    self.activity_table_state.select(Some(idx + added));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Load git status and the porcelain summary for the files and status tabs
     pub fn load_status_git_status(&mut self) {
         if !self.status_git_status_loaded {
             self.status_git_status = crate::git::get_git_status().unwrap_or_default();
+            self.status_summary = crate::git::get_status_summary().ok();
+            self.status_flagged_files = crate::git::list_flagged_files().unwrap_or_default();
             self.status_git_status_loaded = true;
+            self.emit_status_changed(status_counts(&self.status_git_status));
+        }
+    }
+
+    /// Path, relative to the repository root, of the file selected in the Files tab
+    pub fn selected_files_entry_path(&self) -> Option<String> {
+        let add_parent = self.files_add_parent();
+        let files = crate::files::list_files(&self.current_dir, add_parent);
+        let entry = files.get(self.files_selected_row).filter(|entry| !entry.is_dir)?;
+        self.repo_relative_path(&self.current_dir.join(&entry.name))
+    }
+
+    /// Working tree of the open repository
+    pub fn repo_workdir(&self) -> Option<PathBuf> {
+        self.repo_root
+            .as_ref()
+            .and_then(|git_dir| git_dir.parent())
+            .map(|workdir| workdir.to_path_buf())
+    }
+
+    /// `path` relative to the repository root, when it is inside the repository
+    pub fn repo_relative_path(&self, path: &std::path::Path) -> Option<String> {
+        let workdir = self.repo_workdir()?;
+        let relative = path.strip_prefix(&workdir).ok()?;
+        Some(relative.display().to_string())
+    }
+
+    /// The directory the Files tab can't go above, None when it may go anywhere
+    pub fn files_jail(&self) -> Option<PathBuf> {
+        match self.files_scope {
+            FilesScope::Launch => Some(self.root_dir.clone()),
+            FilesScope::Repository => {
+                Some(self.repo_workdir().unwrap_or_else(|| self.root_dir.clone()))
+            }
+            FilesScope::Anywhere => None,
+        }
+    }
+
+    /// Whether the Files tab lists a ".." entry for the current directory
+    pub fn files_add_parent(&self) -> bool {
+        match self.files_jail() {
+            Some(jail) => self.current_dir != jail && self.current_dir.starts_with(&jail),
+            None => self.current_dir.parent().is_some(),
+        }
+    }
+
+    /// Whether the Files tab is browsing a directory outside the repository
+    pub fn files_outside_repo(&self) -> bool {
+        match self.repo_workdir() {
+            Some(workdir) => self.git_enabled && !self.current_dir.starts_with(workdir),
+            None => false,
+        }
+    }
+
+    /// Ask before deleting the entry selected in the Files tab
+    pub fn request_files_delete(&mut self) {
+        if self.refuse_read_only("Deleting files") {
+            return;
+        }
+        let files = crate::files::list_files(&self.current_dir, self.files_add_parent());
+        if let Some(entry) = files.get(self.files_selected_row).filter(|entry| entry.name != "..") {
+            self.files_delete_confirm = Some(self.current_dir.join(&entry.name));
+            // Experts aren't asked about what the trash can give back
+            if self.profile == Profile::Expert && !self.deletes_permanently() {
+                self.perform_files_delete();
+            }
+        }
+    }
+
+    /// Delete the confirmed Files tab entry, through the trash unless permanent deletion is on
+    pub fn perform_files_delete(&mut self) {
+        let path = match self.files_delete_confirm.take() {
+            Some(path) => path,
+            None => return,
+        };
+        if let Err(e) = crate::trash::remove(&path, self.deletes_permanently()) {
+            let action = if self.deletes_permanently() {
+                "delete"
+            } else {
+                "move to the trash"
+            };
+            self.show_error(
+                "Delete Failed",
+                &format!("Failed to {} {}:\n\n{}", action, path.display(), e),
+            );
+        }
+        let count = crate::files::list_files(&self.current_dir, self.files_add_parent()).len();
+        self.files_selected_row = self.files_selected_row.min(count.saturating_sub(1));
+        self.invalidate_status_git_status();
+        self.invalidate_save_changes_git_status();
+    }
+
+    /// Widen or narrow how far the Files tab may navigate, going back to the launch
+    /// directory when the current one is no longer allowed
+    pub fn cycle_files_scope(&mut self) {
+        self.files_scope = match self.files_scope {
+            FilesScope::Launch if self.git_enabled => FilesScope::Repository,
+            FilesScope::Launch | FilesScope::Repository => FilesScope::Anywhere,
+            FilesScope::Anywhere => FilesScope::Launch,
+        };
+        if let Some(jail) = self.files_jail() {
+            if !self.current_dir.starts_with(&jail) {
+                self.current_dir = self.root_dir.clone();
+                self.files_selected_row = 0;
+            }
+        }
+    }
+
+    /// The index flag set on `path`, if any
+    pub fn index_flag(&self, path: &str) -> Option<crate::git::IndexFlag> {
+        self.status_flagged_files
+            .iter()
+            .find(|(flagged, _)| flagged == path)
+            .map(|(_, flag)| *flag)
+    }
+
+    /// Turn `flag` on or off for the file selected in the Files tab
+    pub fn toggle_selected_index_flag(&mut self, flag: crate::git::IndexFlag) {
+        if let Some(path) = self.selected_files_entry_path() {
+            let on = self.index_flag(&path) != Some(flag);
+            self.set_index_flag(&path, flag, on);
+        }
+    }
+
+    /// Clear the flag of the row selected in the flagged files list
+    pub fn clear_selected_flagged_file(&mut self) {
+        let selected = self.flagged_files_view.unwrap_or(0);
+        if let Some((path, flag)) = self.status_flagged_files.get(selected).cloned() {
+            self.set_index_flag(&path, flag, false);
+            let last = self.status_flagged_files.len().saturating_sub(1);
+            self.flagged_files_view = Some(selected.min(last));
+        }
+    }
+
+    fn set_index_flag(&mut self, path: &str, flag: crate::git::IndexFlag, on: bool) {
+        if self.refuse_read_only("Changing index flags") {
+            return;
+        }
+        // The two flags are exclusive in practice; switching replaces the other one
+        if on {
+            if let Some(other) = self.index_flag(path).filter(|other| *other != flag) {
+                let _ = crate::git::set_index_flag(path, other, false);
+            }
         }
+        if let Err(e) = crate::git::set_index_flag(path, flag, on) {
+            self.show_error(
+                "Index Flag Failed",
+                &format!("Failed to update {} on {}:\n\n{}", flag.as_option(), path, e),
+            );
+        }
+        self.invalidate_status_git_status();
+        self.invalidate_save_changes_git_status();
+        self.load_status_git_status();
+    }
+
+    /// Emit a status-changed event when the working tree counts differ from the last one sent
+    pub fn emit_status_changed(&mut self, counts: (usize, usize, usize)) {
+        if !crate::events::enabled() || self.last_status_counts == Some(counts) {
+            return;
+        }
+        self.last_status_counts = Some(counts);
+
+        crate::events::emit(
+            "status-changed",
+            serde_json::json!({
+                "branch": crate::git::get_current_branch().ok(),
+                "staged": counts.0,
+                "unstaged": counts.1,
+                "untracked": counts.2,
+            }),
+        );
     }
 
     /// Get cached git status for files tab
@@ -357,17 +2257,138 @@ impl AppState {
         &self.status_git_status
     }
 
-    /// Mark git status as needing refresh (called when leaving files tab)
+    /// Mark git status as needing refresh (called when entering the files or status tab)
     pub fn invalidate_status_git_status(&mut self) {
         self.status_git_status_loaded = false;
+        self.invalidate_repo_snapshot();
+    }
+
+    /// Re-read status once files or git state changed outside gitix (an editor, a build,
+    /// git on the command line)
+    ///
+    /// The Files and Status tabs re-read on their next draw; a loaded Save Changes list
+    /// is re-scanned in the background so the selection and scroll stay put.
+    pub fn poll_watcher(&mut self) {
+        if !self.watcher.as_mut().is_some_and(|watcher| watcher.poll()) {
+            return;
+        }
+        self.invalidate_status_git_status();
+        if self.save_changes_git_status_loaded {
+            self.start_save_changes_reconcile();
+        }
+    }
+
+    /// Refresh remote status for update tab
+    pub fn refresh_update_remote_status(&mut self) {
+        if self.is_syncing() {
+            return;
+        }
+        // Start loading indicator with generic message
+        self.start_loading("Loading...");
+        // Set flag to perform work in next UI cycle
+        self.pending_refresh_work = true;
+    }
+
+    /// Fetch every configured remote (e.g. origin and upstream in a fork setup)
+    pub fn fetch_all_remotes(&mut self) {
+        if self.is_syncing() {
+            return;
+        }
+        self.start_loading("Fetching all remotes...");
+        self.pending_refresh_work = true;
+        self.pending_fetch_all = true;
+    }
+
+    /// Start an auto-refresh of the Update tab once the configured interval has elapsed
+    ///
+    /// Only runs while the Update tab is visible and nothing else is in progress.
+    pub fn maybe_auto_refresh(&mut self) {
+        if self.auto_refresh_minutes == 0
+            || !self.on_update_tab
+            || !self.git_enabled
+            || self.is_loading
+            || self.is_hook_running()
+            || self.show_error_popup
+        {
+            return;
+        }
+
+        let interval = std::time::Duration::from_secs(u64::from(self.auto_refresh_minutes) * 60);
+        if self.update_auto_refresh_since.elapsed() < interval {
+            return;
+        }
+
+        // Nothing to fetch from; check again after the next interval
+        if !crate::git::has_remote_origin().unwrap_or(false) {
+            self.update_auto_refresh_since = Instant::now();
+            return;
+        }
+
+        self.start_loading("Auto-refreshing...");
+        self.pending_refresh_work = true;
+        self.pending_refresh_is_auto = true;
+    }
+
+    /// Step the auto-refresh interval through the preset choices
+    pub fn cycle_auto_refresh(&mut self, forward: bool) {
+        let choices = AUTO_REFRESH_CHOICES;
+        let current = choices
+            .iter()
+            .position(|m| *m == self.auto_refresh_minutes)
+            .unwrap_or(0);
+        let next = if forward {
+            (current + 1) % choices.len()
+        } else {
+            (current + choices.len() - 1) % choices.len()
+        };
+        self.auto_refresh_minutes = choices[next];
+    }
+
+    /// Step the commit body wrap column through the preset choices
+    pub fn cycle_commit_wrap(&mut self, forward: bool) {
+        let choices = COMMIT_WRAP_CHOICES;
+        let current = choices
+            .iter()
+            .position(|c| *c == self.commit_wrap_column)
+            .unwrap_or(0);
+        let next = if forward {
+            (current + 1) % choices.len()
+        } else {
+            (current + choices.len() - 1) % choices.len()
+        };
+        self.commit_wrap_column = choices[next];
+    }
+
+    /// Step the new repository template through "none" and the installed gitix templates
+    /// Cycle the forge setting through auto-detection and each known forge
+    pub fn cycle_forge_override(&mut self, forward: bool) {
+        let mut choices = vec![None];
+        choices.extend(crate::forge::ForgeKind::ALL.into_iter().map(Some));
+        let current = choices
+            .iter()
+            .position(|c| *c == self.forge_override)
+            .unwrap_or(0);
+        let next = if forward {
+            (current + 1) % choices.len()
+        } else {
+            (current + choices.len() - 1) % choices.len()
+        };
+        self.forge_override = choices[next];
     }
 
-    /// Refresh remote status for update tab
-    pub fn refresh_update_remote_status(&mut self) {
-        // Start loading indicator with generic message
-        self.start_loading("Loading...");
-        // Set flag to perform work in next UI cycle
-        self.pending_refresh_work = true;
+    pub fn cycle_init_template(&mut self, forward: bool) {
+        let mut choices: Vec<Option<String>> = vec![None];
+        choices.extend(crate::init_template::list_templates().into_iter().map(Some));
+        let current = choices
+            .iter()
+            .position(|c| *c == self.init_template)
+            .unwrap_or(0);
+        let next = if forward {
+            (current + 1) % choices.len()
+        } else {
+            (current + choices.len() - 1) % choices.len()
+        };
+        self.init_template = choices[next].clone();
     }
 
     /// Perform the actual refresh work (called after loading indicator is shown)
@@ -376,20 +2397,140 @@ impl AppState {
             return; // Not in loading state or no work pending
         }
 
-        // Clear the pending work flag
+        // Clear the pending work flags
         self.pending_refresh_work = false;
+        let is_auto = std::mem::take(&mut self.pending_refresh_is_auto);
+
+        // Manual and automatic refreshes both restart the interval
+        self.update_last_refreshed = Some(Instant::now());
+        self.update_auto_refresh_since = Instant::now();
+
+        if std::mem::take(&mut self.pending_fetch_all) {
+            let message = self.loading_message.clone();
+            self.start_sync_worker(&message, |_| {
+                let results = crate::git::fetch_all_remotes().map_err(|e| e.to_string());
+                SyncWorkerResult::FetchAll(results)
+            });
+            return;
+        }
+
+        let message = self.loading_message.clone();
+        self.start_sync_worker(&message, move |progress| SyncWorkerResult::Refresh {
+            result: crate::git::refresh_remote_status(Some(progress)).map_err(|e| e.to_string()),
+            is_auto,
+        });
+    }
+
+    /// Run `work` on a background thread, showing `message` and its progress until it is done
+    fn start_sync_worker<F>(&mut self, message: &str, work: F)
+    where
+        F: FnOnce(&crate::git::ProgressSender) -> SyncWorkerResult + Send + 'static,
+    {
+        self.start_loading(message);
+        self.sync_progress = None;
+        let (progress_tx, progress_rx) = std::sync::mpsc::channel();
+        let (done_tx, done_rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = done_tx.send(work(&progress_tx));
+        });
+        self.sync_worker = Some(SyncWorker {
+            progress: progress_rx,
+            done: done_rx,
+        });
+    }
+
+    /// Take the worker's latest progress and, once it has finished, its result
+    pub fn poll_sync_worker(&mut self) {
+        let worker = match &self.sync_worker {
+            Some(worker) => worker,
+            None => return,
+        };
+        if let Some(progress) = worker.progress.try_iter().last() {
+            self.sync_progress = Some(progress);
+        }
+        let result = match worker.done.try_recv() {
+            Ok(result) => Some(result),
+            Err(std::sync::mpsc::TryRecvError::Empty) => return,
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => None,
+        };
+        self.sync_worker = None;
+        self.sync_progress = None;
+        self.stop_loading();
+        self.invalidate_repo_snapshot();
+
+        match result {
+            Some(SyncWorkerResult::Refresh { result, is_auto }) => {
+                self.finish_refresh(result, is_auto)
+            }
+            Some(SyncWorkerResult::FetchAll(result)) => self.finish_fetch_all(result),
+            Some(SyncWorkerResult::Pull(result)) => self.finish_pull(result),
+            Some(SyncWorkerResult::Push {
+                result,
+                branch,
+                new_branch,
+            }) => self.finish_push(result, branch, new_branch),
+            Some(SyncWorkerResult::ForkSync { result, branch }) => {
+                self.finish_fork_sync(result, branch)
+            }
+            None => {
+                self.show_error("Sync Failed", "The background operation stopped unexpectedly.")
+            }
+        }
+    }
+
+    /// Whether a fetch, pull, push or fork sync is running in the background
+    pub fn is_syncing(&self) -> bool {
+        self.sync_worker.is_some()
+    }
+
+    /// The status bar text while something is loading: spinner, message and transfer progress
+    pub fn loading_status(&self) -> String {
+        let mut status = format!("{} {}", self.get_spinner_char(), self.loading_message);
+        if let Some(progress) = &self.sync_progress {
+            status.push_str(&format!("  {} ", progress.stage));
+            if progress.total > 0 {
+                let filled = progress.current * LOADING_BAR_WIDTH / progress.total;
+                status.push_str(&format!(
+                    "{}{} {}/{}",
+                    "█".repeat(filled),
+                    "░".repeat(LOADING_BAR_WIDTH - filled),
+                    progress.current,
+                    progress.total
+                ));
+            }
+            if progress.bytes > 0 {
+                status.push_str(&format!(
+                    " · {}",
+                    crate::git::format_file_size(Some(progress.bytes as u64))
+                ));
+            }
+            if let Some(detail) = &progress.detail {
+                status.push_str(&format!(": {}", detail));
+            }
+        }
+        status
+    }
 
-        match crate::git::refresh_remote_status() {
+    /// Show the outcome of a status refresh
+    fn finish_refresh(
+        &mut self,
+        result: Result<(crate::git::RemoteStatus, crate::git::SyncOperation), String>,
+        is_auto: bool,
+    ) {
+        match result {
             Ok((remote_status, sync_operation)) => {
                 self.update_remote_status = Some(remote_status);
                 self.add_sync_operation(sync_operation);
+                self.start_forge_status();
             }
             Err(e) => {
-                // Show user-friendly error popup
-                self.show_error(
-                    "Refresh Failed",
-                    &format!("Failed to refresh repository status:\n\n{}", e),
-                );
+                // Background refreshes only log failures instead of interrupting the user
+                if !is_auto {
+                    self.show_error(
+                        "Refresh Failed",
+                        &format!("Failed to refresh repository status:\n\n{}", e),
+                    );
+                }
 
                 // Also add to sync operations log for debugging
                 let error_operation = crate::git::SyncOperation {
@@ -401,19 +2542,95 @@ impl AppState {
                 self.add_sync_operation(error_operation);
             }
         }
+    }
 
-        // Stop loading indicator
-        self.stop_loading();
+    /// Log one entry per fetched remote plus an aggregated summary
+    fn finish_fetch_all(
+        &mut self,
+        results: Result<Vec<(String, crate::git::SyncOperation)>, String>,
+    ) {
+        let start_time = std::time::SystemTime::now();
+
+        let results = match results {
+            Ok(results) => results,
+            Err(e) => {
+                self.show_error("Fetch Failed", &format!("Failed to list remotes:\n\n{}", e));
+                return;
+            }
+        };
+
+        if results.is_empty() {
+            self.show_error("Fetch Failed", "No remotes are configured for this repository.");
+            return;
+        }
+
+        let failed: Vec<&str> = results
+            .iter()
+            .filter(|(_, op)| matches!(op.status, crate::git::OperationStatus::Error))
+            .map(|(name, _)| name.as_str())
+            .collect();
+
+        let summary = crate::git::SyncOperation {
+            operation_type: crate::git::SyncOperationType::Fetch,
+            status: if failed.is_empty() {
+                crate::git::OperationStatus::Success
+            } else {
+                crate::git::OperationStatus::Error
+            },
+            message: if failed.is_empty() {
+                format!("Fetched all {} remotes", results.len())
+            } else {
+                format!(
+                    "Fetched {} of {} remotes (failed: {})",
+                    results.len() - failed.len(),
+                    results.len(),
+                    failed.join(", ")
+                )
+            },
+            timestamp: start_time,
+        };
+
+        for (name, mut operation) in results {
+            operation.message = format!("{}: {}", name, operation.message);
+            self.push_recent_operation(operation);
+        }
+        self.add_sync_operation(summary);
+
+        if let Ok(remote_status) = crate::git::get_remote_status() {
+            self.update_remote_status = Some(remote_status);
+        }
     }
 
-    /// Perform pull operation
+    /// Pull from origin in the background
     pub fn perform_pull(&mut self) {
-        // Start loading indicator
-        self.start_loading("Downloading changes from remote...");
+        if self.is_syncing() {
+            return;
+        }
+        let rebase = self.pulls_with_rebase();
+        self.start_sync_worker("Downloading changes from remote...", move |progress| {
+            SyncWorkerResult::Pull(
+                crate::git::pull_origin(rebase, Some(progress)).map_err(|e| e.to_string()),
+            )
+        });
+    }
 
-        match crate::git::pull_origin(self.pull_rebase) {
+    /// Log a finished pull, then run the post-pull command and re-read the remote status
+    fn finish_pull(&mut self, result: Result<crate::git::SyncOperation, String>) {
+        match result {
             Ok(sync_operation) => {
+                let pulled = !matches!(sync_operation.status, crate::git::OperationStatus::Error);
                 self.add_sync_operation(sync_operation);
+                if !pulled && crate::git::has_conflicts() {
+                    self.open_conflicts();
+                }
+                if pulled {
+                    let branch = crate::git::get_current_branch().unwrap_or_else(|_| "HEAD".to_string());
+                    let env = [
+                        ("GITIX_REMOTE", "origin".to_string()),
+                        ("GITIX_BRANCH", branch),
+                    ];
+                    self.run_ui_hook("post-pull-ui", &env);
+                }
                 // Refresh remote status after pull
                 if let Ok(remote_status) = crate::git::get_remote_status() {
                     self.update_remote_status = Some(remote_status);
@@ -436,22 +2653,322 @@ impl AppState {
                 self.add_sync_operation(error_operation);
             }
         }
+    }
 
-        // Stop loading indicator
-        self.stop_loading();
+    /// Sync the fork's default branch with upstream, then optionally push it to origin
+    pub fn perform_fork_sync(&mut self) {
+        if self.refuse_read_only("Syncing the fork") {
+            return;
+        }
+        let fork = match self.update_fork.clone() {
+            Some(fork) if !self.is_syncing() => fork,
+            _ => return,
+        };
+
+        self.start_sync_worker("Syncing fork with upstream...", move |progress| {
+            SyncWorkerResult::ForkSync {
+                result: crate::git::sync_fork(&fork, Some(progress)).map_err(|e| e.to_string()),
+                branch: fork.default_branch,
+            }
+        });
+    }
+
+    fn finish_fork_sync(
+        &mut self,
+        result: Result<crate::git::SyncOperation, String>,
+        branch: String,
+    ) {
+        let pushed = match result {
+            Ok(sync_operation) => {
+                let succeeded = !matches!(sync_operation.status, crate::git::OperationStatus::Error);
+                self.add_sync_operation(sync_operation);
+                succeeded && self.fork_push_after_sync
+            }
+            Err(e) => {
+                self.show_error(
+                    "Fork Sync Failed",
+                    &format!("Failed to sync with upstream:\n\n{}", e),
+                );
+                self.add_sync_operation(crate::git::SyncOperation {
+                    operation_type: crate::git::SyncOperationType::ForkSync,
+                    status: crate::git::OperationStatus::Error,
+                    message: format!("Fork sync failed: {}", e),
+                    timestamp: std::time::SystemTime::now(),
+                });
+                false
+            }
+        };
+
+        if pushed {
+            self.start_push(Some(branch));
+        } else if let Ok(remote_status) = crate::git::get_remote_status() {
+            self.update_remote_status = Some(remote_status);
+        }
     }
 
     /// Perform push operation
     pub fn perform_push(&mut self) {
-        // Start loading indicator
-        self.start_loading("Uploading changes to remote...");
+        self.start_push(None);
+    }
+
+    /// Pull, after asking first in the beginner profile
+    pub fn request_pull(&mut self) {
+        if self.refuse_read_only("Pulling") {
+            return;
+        }
+        if self.profile == Profile::Beginner {
+            self.sync_confirm = Some(crate::git::SyncOperationType::Pull);
+        } else {
+            self.perform_pull();
+        }
+    }
+
+    /// Push, after asking first in the beginner profile
+    pub fn request_push(&mut self) {
+        if self.refuse_read_only("Pushing") {
+            return;
+        }
+        if self.profile == Profile::Beginner {
+            self.sync_confirm = Some(crate::git::SyncOperationType::Push);
+        } else {
+            self.perform_push();
+        }
+    }
+
+    /// Run the pull or push that was just confirmed
+    pub fn confirm_sync(&mut self) {
+        match self.sync_confirm.take() {
+            Some(crate::git::SyncOperationType::Pull) => self.perform_pull(),
+            Some(crate::git::SyncOperationType::Push) => self.perform_push(),
+            _ => {}
+        }
+    }
+
+    /// Push `branch` (or the current branch) to origin
+    ///
+    /// libgit2 does not run hooks, so an installed pre-push hook is started
+    /// first and the push only happens once it exits successfully.
+    fn start_push(&mut self, branch: Option<String>) {
+        if self.is_syncing() {
+            return;
+        }
+        self.push_target = branch;
+        let branch_name = self.push_branch_name();
+
+        // A configured gitix.hooks.pre-push-ui command runs first and can veto the push
+        let env = [
+            ("GITIX_REMOTE", "origin".to_string()),
+            ("GITIX_BRANCH", branch_name),
+        ];
+        match crate::hooks::spawn_ui_hook("pre-push-ui", &env) {
+            Ok(Some(running)) => self.show_hook_output(running, AfterHook::RunPrePushHook),
+            Ok(None) => self.run_pre_push_hook(),
+            Err(e) => {
+                self.show_error(
+                    "Push Failed",
+                    &format!("Failed to run pre-push-ui command:\n\n{}", e),
+                );
+                self.add_sync_operation(crate::git::SyncOperation {
+                    operation_type: crate::git::SyncOperationType::Push,
+                    status: crate::git::OperationStatus::Error,
+                    message: format!("pre-push-ui command could not run: {}", e),
+                    timestamp: std::time::SystemTime::now(),
+                });
+            }
+        }
+    }
+
+    /// The branch the current push is for
+    fn push_branch_name(&self) -> String {
+        match &self.push_target {
+            Some(branch) => branch.clone(),
+            None => crate::git::get_current_branch().unwrap_or_else(|_| "HEAD".to_string()),
+        }
+    }
+
+    /// Run git's pre-push hook, pushing once it passes (or right away if there is none)
+    fn run_pre_push_hook(&mut self) {
+        let branch_name = self.push_branch_name();
+        let hook = crate::hooks::pre_push_input("origin", &branch_name).and_then(|(args, stdin)| {
+            let args: Vec<&str> = args.iter().map(String::as_str).collect();
+            crate::hooks::spawn_hook("pre-push", &args, &stdin)
+        });
+
+        match hook {
+            Ok(Some(running)) => self.show_hook_output(running, AfterHook::Push),
+            Ok(None) => self.push_to_remote(),
+            Err(e) => {
+                self.show_error(
+                    "Push Failed",
+                    &format!("Failed to run pre-push hook:\n\n{}", e),
+                );
+                self.add_sync_operation(crate::git::SyncOperation {
+                    operation_type: crate::git::SyncOperationType::Push,
+                    status: crate::git::OperationStatus::Error,
+                    message: format!("pre-push hook could not run: {}", e),
+                    timestamp: std::time::SystemTime::now(),
+                });
+            }
+        }
+    }
+
+    /// Show a running hook's output in the popup
+    pub(crate) fn show_hook_output(&mut self, running: crate::hooks::RunningHook, then: AfterHook) {
+        // Keep the output of earlier steps in the same chain (pre-push-ui → pre-push → post-push-ui)
+        let lines = match self.hook_output.take() {
+            Some(previous) if previous.running.is_none() => {
+                let mut lines = previous.lines;
+                lines.push(String::new());
+                lines
+            }
+            _ => Vec::new(),
+        };
+
+        self.hook_output = Some(HookOutput {
+            hook_name: running.name.clone(),
+            lines,
+            status: HookRunStatus::Running,
+            scroll: 0,
+            then,
+            running: Some(running),
+        });
+    }
 
-        match crate::git::push_origin() {
+    /// Run the gitix.hooks.<event> command, if configured, showing its output in the popup
+    pub fn run_ui_hook(&mut self, event: &str, env: &[(&str, String)]) {
+        match crate::hooks::spawn_ui_hook(event, env) {
+            Ok(Some(running)) => self.show_hook_output(running, AfterHook::Nothing),
+            Ok(None) => {}
+            Err(e) => self.show_error(
+                "Command Failed",
+                &format!("Failed to run {} command:\n\n{}", event, e),
+            ),
+        }
+    }
+
+    /// Collect output from a running hook and act on its result once it exits
+    /// (call this periodically from the UI loop)
+    pub fn poll_hook_output(&mut self) {
+        let finished = match self.hook_output.as_mut() {
+            Some(output) => match output.running.as_mut() {
+                Some(running) => {
+                    let (lines, status) = running.poll();
+                    output.lines.extend(lines);
+                    status
+                }
+                None => None,
+            },
+            None => None,
+        };
+
+        let exit_status = match finished {
+            Some(status) => status,
+            None => return,
+        };
+
+        let output = self.hook_output.as_mut().unwrap();
+        output.running = None;
+        let then = output.then;
+
+        if exit_status.success() {
+            output.status = HookRunStatus::Passed;
+            output.lines.push(format!("{} hook passed", output.hook_name));
+            match then {
+                AfterHook::Nothing => {}
+                AfterHook::RunPrePushHook => self.run_pre_push_hook(),
+                AfterHook::Push => self.push_to_remote(),
+                AfterHook::Commit => self.finish_commit(),
+            }
+        } else {
+            output.status = HookRunStatus::Failed(exit_status.code());
+            if !output.gates_push() {
+                return;
+            }
+            let message = match exit_status.code() {
+                Some(code) => format!("Push aborted: {} hook exited with code {}", output.hook_name, code),
+                None => format!("Push aborted: {} hook was terminated", output.hook_name),
+            };
+            self.add_sync_operation(crate::git::SyncOperation {
+                operation_type: crate::git::SyncOperationType::Push,
+                status: crate::git::OperationStatus::Error,
+                message,
+                timestamp: std::time::SystemTime::now(),
+            });
+        }
+    }
+
+    /// Whether a hook is still executing
+    pub fn is_hook_running(&self) -> bool {
+        matches!(
+            self.hook_output.as_ref().map(|o| &o.status),
+            Some(HookRunStatus::Running)
+        )
+    }
+
+    /// Close the hook output popup, killing the hook (and cancelling the push) if it's still running
+    pub fn close_hook_output(&mut self) {
+        if let Some(mut output) = self.hook_output.take() {
+            if let Some(mut running) = output.running.take() {
+                running.kill();
+                if !output.gates_push() {
+                    return;
+                }
+                self.add_sync_operation(crate::git::SyncOperation {
+                    operation_type: crate::git::SyncOperationType::Push,
+                    status: crate::git::OperationStatus::Error,
+                    message: format!("Push cancelled while {} hook was running", output.hook_name),
+                    timestamp: std::time::SystemTime::now(),
+                });
+            }
+        }
+    }
+
+    /// Push the target branch to origin in the background (after hooks have passed)
+    fn push_to_remote(&mut self) {
+        let branch = self.push_branch_name();
+        let target = self.push_target.take();
+        let new_branch = !crate::git::remote_branch_exists("origin", &branch);
+        self.start_sync_worker("Uploading changes to remote...", move |progress| {
+            let result = match &target {
+                Some(branch) => crate::git::push_branch("origin", branch, Some(progress)),
+                None => crate::git::push_origin(Some(progress)),
+            };
+            SyncWorkerResult::Push {
+                result: result.map_err(|e| e.to_string()),
+                branch,
+                new_branch,
+            }
+        });
+    }
+
+    /// Log a finished push, then run the post-push command and re-read the remote status
+    fn finish_push(
+        &mut self,
+        result: Result<crate::git::SyncOperation, String>,
+        branch_name: String,
+        new_branch: bool,
+    ) {
+        match result {
             Ok(sync_operation) => {
+                let pushed = !matches!(sync_operation.status, crate::git::OperationStatus::Error);
                 self.add_sync_operation(sync_operation);
                 // Refresh remote status after push
                 if let Ok(remote_status) = crate::git::get_remote_status() {
                     self.update_remote_status = Some(remote_status);
+                    self.detect_forge();
+                }
+                if pushed {
+                    // A branch's first push is when a pull request usually follows
+                    if new_branch {
+                        self.offer_pull_request(&branch_name);
+                    }
+                    let env = [
+                        ("GITIX_REMOTE", "origin".to_string()),
+                        ("GITIX_BRANCH", branch_name),
+                    ];
+                    self.run_ui_hook("post-push-ui", &env);
+                    // The forge starts CI on the pushed commit
+                    self.start_forge_status();
                 }
             }
             Err(e) => {
@@ -471,17 +2988,170 @@ impl AppState {
                 self.add_sync_operation(error_operation);
             }
         }
+    }
 
-        // Stop loading indicator
-        self.stop_loading();
+    /// Record a git mutation in the session activity log
+    pub fn record_activity(
+        &mut self,
+        kind: crate::activity::ActivityKind,
+        target: &str,
+        operation: &str,
+        result: Result<String, String>,
+    ) {
+        let (status, message) = match result {
+            Ok(message) => (crate::git::OperationStatus::Success, message),
+            Err(message) => (crate::git::OperationStatus::Error, message),
+        };
+        self.activity_log.insert(
+            0,
+            crate::activity::ActivityEntry {
+                kind,
+                target: target.to_string(),
+                operation: operation.to_string(),
+                status,
+                message,
+                timestamp: std::time::SystemTime::now(),
+            },
+        );
+    }
+
+    /// Export the session activity log into the git directory
+    pub fn export_activity_log(&mut self) {
+        let path = crate::export::default_export_path("activity", "log");
+        self.activity_status_message = Some(
+            match crate::activity::export_log(&self.activity_log, &path) {
+                Ok(()) => format!("✓ Exported {} entries to {}", self.activity_log.len(), path.display()),
+                Err(e) => format!("✗ Failed to export activity log: {}", e),
+            },
+        );
+    }
+
+    /// Send a desktop notification for a long operation the user isn't watching
+    fn notify_if_unattended(&self, operation: &crate::git::SyncOperation) {
+        if !self.notifications_enabled || (self.terminal_focused && self.on_update_tab) {
+            return;
+        }
+
+        let elapsed = operation.timestamp.elapsed().unwrap_or_default();
+        if elapsed < crate::notify::LONG_OPERATION {
+            return;
+        }
+
+        let name = match operation.operation_type {
+            crate::git::SyncOperationType::Fetch => "Fetch",
+            crate::git::SyncOperationType::Pull => "Pull",
+            crate::git::SyncOperationType::Push => "Push",
+            crate::git::SyncOperationType::Refresh => "Refresh",
+            crate::git::SyncOperationType::ForkSync => "Fork sync",
+            crate::git::SyncOperationType::CherryPick => "Cherry-pick",
+            crate::git::SyncOperationType::Merge => "Merge",
+        };
+        let outcome = match operation.status {
+            crate::git::OperationStatus::Error => "failed",
+            _ => "finished",
+        };
+        crate::notify::send(&format!("{} {}", name, outcome), &operation.message);
     }
 
     /// Add a sync operation to the recent operations list
-    fn add_sync_operation(&mut self, operation: crate::git::SyncOperation) {
+    pub fn add_sync_operation(&mut self, operation: crate::git::SyncOperation) {
+        self.notify_if_unattended(&operation);
+        self.emit_sync_finished(&operation);
+
+        // Pulls, pushes and fork syncs mutate the repository, so they also go in the activity log
+        let branch = match &self.push_target {
+            Some(branch) => branch.clone(),
+            None => crate::git::get_current_branch().unwrap_or_else(|_| "HEAD".to_string()),
+        };
+        let activity = match operation.operation_type {
+            crate::git::SyncOperationType::Pull => Some((
+                crate::activity::ActivityKind::Pull,
+                format!("origin/{}", branch),
+                if self.pulls_with_rebase() {
+                    format!("libgit2: fetch origin, rebase {} onto origin/{}", branch, branch)
+                } else {
+                    format!("libgit2: fetch origin, merge origin/{} into {}", branch, branch)
+                },
+            )),
+            crate::git::SyncOperationType::Push => Some((
+                crate::activity::ActivityKind::Push,
+                format!("origin/{}", branch),
+                format!("libgit2: push origin refs/heads/{}:refs/heads/{}", branch, branch),
+            )),
+            crate::git::SyncOperationType::ForkSync => self.update_fork.as_ref().map(|fork| {
+                let branch = &fork.default_branch;
+                (
+                    crate::activity::ActivityKind::SyncFork,
+                    branch.clone(),
+                    format!(
+                        "libgit2: fetch upstream, fast-forward or rebase {} onto upstream/{}",
+                        branch, branch
+                    ),
+                )
+            }),
+            _ => None,
+        };
+        if let Some((kind, target, command)) = activity {
+            let result = match operation.status {
+                crate::git::OperationStatus::Error => Err(operation.message.clone()),
+                _ => Ok(operation.message.clone()),
+            };
+            self.record_activity(kind, &target, &command, result);
+        }
+
+        self.push_recent_operation(operation);
+    }
+
+    /// Emit a push-finished/pull-finished/fetch-finished/fork-sync-finished event
+    fn emit_sync_finished(&self, operation: &crate::git::SyncOperation) {
+        if !crate::events::enabled() {
+            return;
+        }
+
+        let event = match operation.operation_type {
+            crate::git::SyncOperationType::Push => "push-finished",
+            crate::git::SyncOperationType::Pull => "pull-finished",
+            crate::git::SyncOperationType::Fetch => "fetch-finished",
+            crate::git::SyncOperationType::ForkSync => "fork-sync-finished",
+            crate::git::SyncOperationType::Refresh
+            | crate::git::SyncOperationType::CherryPick
+            | crate::git::SyncOperationType::Merge => return,
+        };
+        let branch = match &self.push_target {
+            Some(branch) => Some(branch.clone()),
+            None => crate::git::get_current_branch().ok(),
+        };
+
+        crate::events::emit(
+            event,
+            serde_json::json!({
+                "branch": branch,
+                "success": !matches!(operation.status, crate::git::OperationStatus::Error),
+                "message": operation.message,
+            }),
+        );
+    }
+
+    /// Insert into the recent operations list and the repository's sync log,
+    /// without notifying or logging activity
+    fn push_recent_operation(&mut self, operation: crate::git::SyncOperation) {
+        // A failure to write the log only costs history, so it isn't reported
+        if self.sync_history_size > 0 {
+            let _ = crate::sync_log::record(&operation, self.sync_history_size);
+        }
         self.update_recent_operations.insert(0, operation);
-        // Keep only the last 10 operations
-        if self.update_recent_operations.len() > 10 {
-            self.update_recent_operations.truncate(10);
+        // With the log off, the panel still shows this session's last few
+        let keep = match self.sync_history_size {
+            0 => 10,
+            size => size,
+        };
+        self.update_recent_operations.truncate(keep);
+    }
+
+    /// Read the sync operations earlier sessions logged for this repository
+    pub fn load_sync_history(&mut self) {
+        if self.git_enabled {
+            self.update_recent_operations = crate::sync_log::load(self.sync_history_size);
         }
     }
 
@@ -499,15 +3169,54 @@ impl AppState {
     pub fn load_update_tab(&mut self) {
         // Load remote status if not already loaded
         self.load_update_remote_status();
+        // Remotes may have changed since the tab was last shown
+        self.update_fork = crate::git::detect_fork().ok().flatten();
+        self.detect_forge();
+        self.start_forge_status();
         // Note: Timestamps are refreshed automatically when rendering since they're calculated
         // relative to the current time each time the UI is drawn
     }
 
+    /// In a read-only session, explain that `action` is disabled and return true
+    pub fn refuse_read_only(&mut self, action: &str) -> bool {
+        if self.read_only {
+            self.show_error(
+                "Read-Only Session",
+                &format!("{} is disabled: gitix was started with --read-only.", action),
+            );
+        }
+        self.read_only
+    }
+
     /// Show an error popup with title and message
     pub fn show_error(&mut self, title: &str, message: &str) {
         self.show_error_popup = true;
         self.error_popup_title = title.to_string();
         self.error_popup_message = message.to_string();
+        self.error_popup_chain.clear();
+        self.error_popup_copied = None;
+        // Errors are shown right after the failed operation is logged
+        self.error_popup_operation = self
+            .activity_log
+            .first()
+            .filter(|entry| matches!(entry.status, crate::git::OperationStatus::Error))
+            .filter(|entry| entry.timestamp.elapsed().is_ok_and(|age| age.as_secs() < 5))
+            .cloned();
+    }
+
+    /// Show an error popup, keeping the error's chain of causes for the report
+    pub fn show_error_with_source(
+        &mut self,
+        title: &str,
+        message: &str,
+        error: &(dyn std::error::Error + 'static),
+    ) {
+        self.show_error(title, message);
+        let mut source = Some(error);
+        while let Some(cause) = source {
+            self.error_popup_chain.push(cause.to_string());
+            source = cause.source();
+        }
     }
 
     /// Hide the error popup
@@ -515,6 +3224,204 @@ impl AppState {
         self.show_error_popup = false;
         self.error_popup_title.clear();
         self.error_popup_message.clear();
+        self.error_popup_chain.clear();
+        self.error_popup_operation = None;
+        self.error_popup_copied = None;
+    }
+
+    /// Everything a bug report about the error on screen needs, as plain text
+    pub fn error_report(&self) -> String {
+        let mut report = format!("gitix error report\n\nError: {}\n", self.error_popup_title);
+        report.push_str(&format!("Message:\n{}\n", indent(&self.error_popup_message)));
+        if !self.error_popup_chain.is_empty() {
+            report.push_str("Caused by:\n");
+            for (idx, cause) in self.error_popup_chain.iter().enumerate() {
+                report.push_str(&format!("  {}: {}\n", idx, cause));
+            }
+        }
+        if let Some(entry) = &self.error_popup_operation {
+            report.push_str(&format!(
+                "\nOperation: {}\nCommand: {}\nTarget: {}\nAt: {}\n",
+                entry.kind.as_str(),
+                entry.operation,
+                entry.target,
+                crate::activity::format_timestamp(entry.timestamp)
+            ));
+        }
+
+        let git = std::process::Command::new("git")
+            .arg("--version")
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+            .unwrap_or_else(|| "git not found".to_string());
+        let branch = if self.git_enabled {
+            crate::git::get_current_branch().unwrap_or_else(|_| "(unknown)".to_string())
+        } else {
+            "(not a repository)".to_string()
+        };
+        report.push_str(&format!(
+            "\nEnvironment:\n  gitix {}\n  {}\n  libgit2 {}\n  OS: {} {}\n  TERM: {}\n  Branch: {}\n",
+            env!("CARGO_PKG_VERSION"),
+            git,
+            {
+                let (major, minor, patch) = git2::Version::get().libgit2_version();
+                format!("{}.{}.{}", major, minor, patch)
+            },
+            std::env::consts::OS,
+            std::env::consts::ARCH,
+            std::env::var("TERM").unwrap_or_default(),
+            branch
+        ));
+        report
+    }
+
+    /// Copy the report of the error on screen to the clipboard
+    pub fn copy_error_report(&mut self) {
+        let report = self.error_report();
+        self.error_popup_copied = Some(match crate::clipboard::copy(&report) {
+            Ok(via) => format!("✓ Report copied via {}", via),
+            Err(e) => format!("✗ Failed to copy the report: {}", e),
+        });
+    }
+
+    /// Work that would be interrupted or left behind by quitting now
+    pub fn quit_blockers(&self) -> Vec<String> {
+        let mut reasons = Vec::new();
+
+        if self.is_loading {
+            reasons.push(format!("An operation is still running: {}", self.loading_message));
+        }
+        if self.is_hook_running() {
+            reasons.push("A hook is still running".to_string());
+        }
+        if self.git_enabled {
+            let staged = crate::git::get_git_status()
+                .map(|files| files.iter().filter(|f| f.staged).count())
+                .unwrap_or(0);
+            if staged > 0 {
+                reasons.push(format!("{} staged file(s) not committed yet", staged));
+            }
+            if !self.commit_message.is_empty() {
+                reasons.push("An unfinished commit message (it will be kept as a draft)".to_string());
+            }
+        }
+
+        reasons
+    }
+
+    /// Work that exists only on this machine: commits on no remote and forgotten stashes
+    pub fn unpushed_warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if !self.git_enabled || !self.quit_warn_unpushed || self.profile == Profile::Expert {
+            return warnings;
+        }
+
+        if let Ok(work) = crate::git::unpushed_work() {
+            if work.commits > 0 {
+                warnings.push(format!(
+                    "{} commit(s) on {} not pushed to any remote",
+                    work.commits,
+                    work.branches.join(", ")
+                ));
+            }
+        }
+        if let Ok(stashes) = crate::git::stashes_older_than(self.quit_stash_days) {
+            if stashes > 0 {
+                warnings.push(format!(
+                    "{} stash(es) older than {} days",
+                    stashes, self.quit_stash_days
+                ));
+            }
+        }
+
+        warnings
+    }
+
+    /// Whether pulls rebase: the beginner profile always merges
+    pub fn pulls_with_rebase(&self) -> bool {
+        self.pull_rebase && self.profile != Profile::Beginner
+    }
+
+    /// Whether deleting skips the system trash: never in the beginner profile
+    pub fn deletes_permanently(&self) -> bool {
+        self.permanent_delete && self.profile != Profile::Beginner
+    }
+
+    /// Refuse an action the beginner profile hides, saying how to get it back; true when refused
+    pub fn hidden_by_profile(&mut self, action: &str) -> bool {
+        if self.profile != Profile::Beginner {
+            return false;
+        }
+        self.show_error(
+            "Not in Beginner Profile",
+            &format!(
+                "{} is hidden in the beginner profile.\n\n\
+                 Switch Settings → Profile to Standard or Expert to use it.",
+                action
+            ),
+        );
+        true
+    }
+
+    /// Ask to quit: returns true when nothing is in progress, otherwise shows the confirmation
+    pub fn request_quit(&mut self) -> bool {
+        let reasons = self.quit_blockers();
+        let warnings = self.unpushed_warnings();
+        if reasons.is_empty() && warnings.is_empty() {
+            return true;
+        }
+        self.quit_confirm = Some(QuitConfirm { reasons, warnings });
+        false
+    }
+
+    /// Stop warning about unpushed work on quit, from now on; false if that couldn't be saved
+    pub fn suppress_unpushed_warnings(&mut self) -> bool {
+        self.quit_warn_unpushed = false;
+        match crate::config::set_quit_warn_unpushed(false) {
+            Ok(()) => true,
+            Err(e) => {
+                self.quit_confirm = None;
+                self.show_error(
+                    "Settings Error",
+                    &format!("Failed to save gitix.quit.warnUnpushed: {}", e),
+                );
+                false
+            }
+        }
+    }
+
+    /// Look for a newer gitix release in the background, if the check is enabled
+    pub fn start_update_check(&mut self) {
+        if !self.update_check || self.update_check_rx.is_some() {
+            return;
+        }
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(crate::release::latest_release());
+        });
+        self.update_check_rx = Some(rx);
+    }
+
+    /// Note a newer release once the background lookup is back; failures are dropped quietly
+    pub fn poll_update_check(&mut self) {
+        let result = match &self.update_check_rx {
+            Some(rx) => rx.try_recv(),
+            None => return,
+        };
+        match result {
+            Ok(Ok(release)) => {
+                self.update_check_rx = None;
+                if crate::release::is_newer(&release.version, crate::release::CURRENT_VERSION) {
+                    self.update_available = Some(release);
+                }
+            }
+            Ok(Err(_)) | Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                self.update_check_rx = None
+            }
+            Err(std::sync::mpsc::TryRecvError::Empty) => {}
+        }
     }
 
     /// Start a loading operation with a message
@@ -534,17 +3441,34 @@ impl AppState {
     /// Update the spinner animation (call this periodically during loading)
     pub fn update_spinner(&mut self) {
         if self.is_loading {
-            self.spinner_state = (self.spinner_state + 1) % 4; // 4-frame spinner animation
+            self.spinner_state = (self.spinner_state + 1) % SPINNER_CHARS.len();
         }
     }
 
     /// Get the current spinner character
     pub fn get_spinner_char(&self) -> &'static str {
-        const SPINNER_CHARS: [&str; 4] = [".", "..", "...", "...."];
         if self.is_loading {
-            SPINNER_CHARS[self.spinner_state % 4]
+            SPINNER_CHARS[self.spinner_state % SPINNER_CHARS.len()]
         } else {
             ""
         }
     }
 }
+
+/// Staged, unstaged and untracked file counts for a status list
+pub fn status_counts(files: &[crate::git::GitFileStatus]) -> (usize, usize, usize) {
+    let staged = files.iter().filter(|f| f.staged).count();
+    let untracked = files
+        .iter()
+        .filter(|f| !f.staged && matches!(f.status, crate::git::FileStatusType::Untracked))
+        .count();
+    (staged, files.len() - staged - untracked, untracked)
+}
+
+/// Indent every line of `text` by two spaces
+fn indent(text: &str) -> String {
+    text.lines()
+        .map(|line| format!("  {}", line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
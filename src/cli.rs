@@ -0,0 +1,576 @@
+use crate::git::OperationStatus;
+use crate::tui::tabs::Tab;
+use crate::tui::theme::AccentColor;
+use clap::builder::{PossibleValue, PossibleValuesParser, TypedValueParser};
+use clap::error::ErrorKind;
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueHint};
+use std::path::PathBuf;
+
+/// A terminal interface for everyday Git
+///
+/// Run without a command to start the interactive interface, in PATH if given.
+#[derive(Parser)]
+#[command(
+    name = "gitix",
+    version,
+    override_usage = "gitix [OPTIONS] [PATH]\n       gitix [OPTIONS] <COMMAND>"
+)]
+pub struct Cli {
+    /// Start the interface in this directory
+    #[arg(value_name = "PATH", value_hint = ValueHint::DirPath)]
+    path: Option<PathBuf>,
+
+    /// Stream JSONL events to a listening Unix socket
+    #[arg(long, global = true, value_name = "PATH", value_hint = ValueHint::FilePath)]
+    event_socket: Option<String>,
+
+    /// Print JSONL events to stdout (commands only)
+    #[arg(long, global = true)]
+    events: bool,
+
+    /// Dashboard of every git repository under DIR
+    #[arg(long, value_name = "DIR", value_hint = ValueHint::DirPath, conflicts_with = "path")]
+    workspace: Option<PathBuf>,
+
+    #[command(flatten)]
+    startup: Startup,
+
+    #[command(subcommand)]
+    command: Option<CliCommand>,
+}
+
+#[derive(Subcommand)]
+enum CliCommand {
+    /// Fetch from origin
+    Fetch {
+        /// Fetch every configured remote concurrently
+        #[arg(long)]
+        all: bool,
+    },
+    /// Execute a .gitix script (stage, commit, push, ...)
+    Run {
+        #[arg(value_name = "FILE", value_hint = ValueHint::FilePath)]
+        file: PathBuf,
+    },
+    /// List the most frequently changed files
+    Churn {
+        /// Look back this many days
+        #[arg(long, value_name = "N", default_value_t = 90)]
+        days: u32,
+        /// Show at most this many files
+        #[arg(long, value_name = "N", default_value_t = 20)]
+        limit: usize,
+    },
+    /// List changed files; exits 0 when clean, 1 when dirty, 2 on error
+    Status {
+        /// Print the stable, versioned format for scripts
+        #[arg(long)]
+        porcelain: bool,
+    },
+    /// Print the branch, changes and recent commits and exit
+    Summary {
+        /// Print one line for shell prompts
+        #[arg(long)]
+        short: bool,
+    },
+    /// Print commits with their change stats (default: JSON, all commits)
+    Log(LogArgs),
+    /// Print a shell completion script
+    Completions {
+        #[arg(value_name = "SHELL", value_enum)]
+        shell: clap_complete::Shell,
+    },
+    /// Print the man page (roff)
+    Man,
+}
+
+#[derive(Args)]
+struct LogArgs {
+    /// Output format
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        default_value = "json",
+        value_parser = PossibleValuesParser::new(["json", "csv"])
+            .map(|format| {
+                crate::export::ExportFormat::parse(&format).unwrap_or(crate::export::ExportFormat::Json)
+            })
+    )]
+    format: crate::export::ExportFormat,
+    /// Only commits whose author matches TEXT
+    #[arg(long, value_name = "TEXT")]
+    author: Option<String>,
+    /// Only commits touching PATH
+    #[arg(long, value_name = "PATH", value_hint = ValueHint::AnyPath)]
+    path: Option<String>,
+    /// Only commits on or after DATE (YYYY-MM-DD)
+    #[arg(long, value_name = "DATE", value_parser = parse_date)]
+    since: Option<chrono::NaiveDate>,
+    /// Only commits on or before DATE (YYYY-MM-DD)
+    #[arg(long, value_name = "DATE", value_parser = parse_date)]
+    until: Option<chrono::NaiveDate>,
+    /// Follow only the first parent of merges
+    #[arg(long)]
+    first_parent: bool,
+    /// Leave out merge commits
+    #[arg(long)]
+    no_merges: bool,
+    /// Print at most N commits
+    #[arg(long, value_name = "N")]
+    limit: Option<usize>,
+}
+
+fn parse_date(value: &str) -> Result<chrono::NaiveDate, String> {
+    chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| "expected a date as YYYY-MM-DD".to_string())
+}
+
+/// Accent colors `--theme` takes
+const ACCENTS: [&str; 14] = [
+    "rosewater", "flamingo", "pink", "mauve", "red", "maroon", "peach", "yellow", "green", "teal",
+    "sky", "sapphire", "blue", "lavender",
+];
+
+/// `--tab` takes a tab's key; `save` is short for save-changes
+fn tab_parser() -> impl TypedValueParser<Value = Tab> {
+    PossibleValuesParser::new(Tab::ALL.map(|tab| match tab {
+        Tab::SaveChanges => PossibleValue::new(tab.key()).alias("save"),
+        _ => PossibleValue::new(tab.key()),
+    }))
+    .map(|key| Tab::from_key(&key).unwrap_or(Tab::Overview))
+}
+
+fn accent_parser() -> impl TypedValueParser<Value = AccentColor> {
+    PossibleValuesParser::new(ACCENTS)
+        .map(|name| crate::config::string_to_accent_color(&name).unwrap_or_default())
+}
+
+/// Options for the interactive interface
+#[derive(Args, Default)]
+pub struct Startup {
+    /// Open the interface on a tab
+    #[arg(long, value_name = "TAB", value_parser = tab_parser())]
+    pub tab: Option<Tab>,
+    /// Use this accent color for the session
+    #[arg(long = "theme", value_name = "ACCENT", value_parser = accent_parser())]
+    pub accent: Option<AccentColor>,
+    /// Browse without changing the repository: no staging, committing, pushing or checking out
+    #[arg(long)]
+    pub read_only: bool,
+}
+
+impl Startup {
+    /// Apply the options that change the freshly loaded state
+    pub fn apply(&self, state: &mut crate::app::AppState) {
+        if let Some(accent) = self.accent {
+            state.current_theme_accent = accent;
+        }
+        if self.read_only {
+            state.read_only = true;
+            // Creating a repository is a change too
+            state.show_init_prompt = false;
+        }
+    }
+}
+
+/// Run a command-line subcommand if one was given
+///
+/// Returns the process exit code, or `None` when gitix should start the TUI with the
+/// options left in `startup`. `gitix PATH` changes into PATH first.
+pub fn run(args: &[String], startup: &mut Startup) -> Option<i32> {
+    let cli = match Cli::try_parse_from(std::iter::once("gitix").chain(args.iter().map(String::as_str))) {
+        Ok(cli) => cli,
+        Err(e) => {
+            // Help and version go to stdout and exit 0, mistakes to stderr and exit 2
+            let _ = e.print();
+            return Some(e.exit_code());
+        }
+    };
+
+    let has_command = cli.command.is_some() || cli.workspace.is_some();
+    let interface_options =
+        cli.startup.tab.is_some() || cli.startup.accent.is_some() || cli.startup.read_only;
+    let conflict = if cli.workspace.is_some() && cli.command.is_some() {
+        Some("--workspace opens its own dashboard and can't be combined with a command")
+    } else if has_command && (interface_options || cli.path.is_some()) {
+        Some("PATH, --tab, --theme and --read-only only apply to the interface")
+    } else {
+        None
+    };
+    if let Some(message) = conflict {
+        let e = Cli::command().error(ErrorKind::ArgumentConflict, message);
+        let _ = e.print();
+        return Some(e.exit_code());
+    }
+    if let Some(path) = &cli.event_socket {
+        if let Err(e) = crate::events::connect_socket(path) {
+            eprintln!("gitix: cannot connect to event socket {}: {}", path, e);
+            return Some(2);
+        }
+    } else if cli.events {
+        // The interface draws on stdout, so events can only share it with a command
+        if !has_command {
+            eprintln!("gitix: --events needs a command; use --event-socket with the interface");
+            return Some(2);
+        }
+        crate::events::to_stdout();
+    }
+
+    if let Some(dir) = &cli.workspace {
+        return Some(crate::tui::workspace::run_workspace(dir));
+    }
+    let command = match cli.command {
+        Some(command) => command,
+        None => {
+            *startup = cli.startup;
+            return match &cli.path {
+                Some(dir) if !dir.is_dir() => {
+                    eprintln!("gitix: '{}' is neither a command nor a directory", dir.display());
+                    Some(2)
+                }
+                Some(dir) => match std::env::set_current_dir(dir) {
+                    Ok(()) => None,
+                    Err(e) => {
+                        eprintln!("gitix: cannot open {}: {}", dir.display(), e);
+                        Some(2)
+                    }
+                },
+                None => None,
+            };
+        }
+    };
+
+    Some(match command {
+        CliCommand::Fetch { all } => fetch(all),
+        CliCommand::Run { file } => crate::script::run_file(&file),
+        CliCommand::Churn { days, limit } => churn(days, limit),
+        CliCommand::Status { porcelain } => status(porcelain),
+        CliCommand::Summary { short } => summary(short),
+        CliCommand::Log(args) => log(args),
+        CliCommand::Completions { shell } => {
+            print!("{}", crate::completions::script(shell));
+            0
+        }
+        CliCommand::Man => {
+            print!("{}", crate::completions::man_page());
+            0
+        }
+    })
+}
+
+fn churn(days: u32, limit: usize) -> i32 {
+    let report = match crate::git::file_churn(days) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("gitix churn: {}", e);
+            return 1;
+        }
+    };
+    if report.files.is_empty() {
+        println!("No files changed in the last {} days", days);
+        return 0;
+    }
+    println!("{:>7}  {:>8}  {:>8}  Path", "Commits", "Added", "Deleted");
+    for file in report.files.iter().take(limit) {
+        println!(
+            "{:>7}  {:>8}  {:>8}  {}",
+            file.commits, file.insertions, file.deletions, file.path
+        );
+    }
+    0
+}
+
+/// How many commits `gitix summary` lists
+const SUMMARY_RECENT_COMMITS: usize = 5;
+
+/// Wrap `text` in an ANSI color when stdout is a terminal that takes colors
+fn paint(text: &str, code: &str) -> String {
+    use std::io::IsTerminal;
+    let color = std::io::stdout().is_terminal()
+        && crate::tui::theme::ColorSupport::detect() != crate::tui::theme::ColorSupport::None;
+    if color {
+        format!("\x1b[{}m{}\x1b[0m", code, text)
+    } else {
+        text.to_string()
+    }
+}
+
+fn summary(short: bool) -> i32 {
+    let status = match crate::git::get_status_summary() {
+        Ok(status) => status,
+        Err(e) => {
+            eprintln!("gitix summary: {}", e);
+            return 1;
+        }
+    };
+
+    let branch = match &status.branch {
+        Some(branch) => paint(branch, "1;36"),
+        None => paint("(detached)", "33"),
+    };
+    let mut sync = Vec::new();
+    if status.ahead > 0 {
+        sync.push(paint(&format!("↑{}", status.ahead), "32"));
+    }
+    if status.behind > 0 {
+        sync.push(paint(&format!("↓{}", status.behind), "33"));
+    }
+    let counts = [
+        (status.staged.len(), "staged", "+", "32"),
+        (status.unstaged.len(), "unstaged", "~", "33"),
+        (status.untracked.len(), "untracked", "?", "35"),
+        (status.conflicted.len(), "conflicted", "!", "31"),
+    ];
+
+    if short {
+        // One line for prompts: branch, ahead/behind, then +staged ~unstaged ?untracked !conflicted
+        let mut parts = vec![branch];
+        parts.extend(sync);
+        for (count, _, symbol, code) in counts {
+            if count > 0 {
+                parts.push(paint(&format!("{}{}", symbol, count), code));
+            }
+        }
+        if let Some(operation) = status.operation {
+            parts.push(paint(operation, "1;31"));
+        }
+        println!("{}", parts.join(" "));
+        return 0;
+    }
+
+    let label = |name: &str| paint(&format!("{:<10}", name), "1");
+    let upstream = match &status.upstream {
+        Some(upstream) if sync.is_empty() => format!(" → {} {}", upstream, paint("up to date", "32")),
+        Some(upstream) => format!(" → {} {}", upstream, sync.join(" ")),
+        None => format!(" {}", paint("(no upstream)", "2")),
+    };
+    println!("{}{}{}", label("Branch"), branch, upstream);
+    if let Some(head) = &status.head {
+        println!("{}{}", label("HEAD"), head);
+    }
+    let changes: Vec<String> = counts
+        .iter()
+        .filter(|(count, ..)| *count > 0)
+        .map(|(count, name, _, code)| paint(&format!("{} {}", count, name), code))
+        .collect();
+    if changes.is_empty() {
+        println!("{}{}", label("Changes"), paint("clean", "32"));
+    } else {
+        println!("{}{}", label("Changes"), changes.join(", "));
+    }
+    if let Ok((commits, branches)) = crate::git::repository_counts() {
+        println!("{}{} commits, {} local branches", label("History"), commits, branches);
+    }
+    if let Some(operation) = status.operation {
+        println!("{}{}", label("State"), paint(operation, "1;31"));
+    }
+
+    let filter = crate::git::HistoryFilter::default();
+    let mut cursor = crate::git::HistoryCursor::default();
+    let recent = crate::git::history_page(&filter, &mut cursor, SUMMARY_RECENT_COMMITS);
+    if let Some(commits) = recent.ok().filter(|commits| !commits.is_empty()) {
+        println!();
+        println!("{}", paint("Recent commits", "1"));
+        for commit in commits {
+            let time = crate::git::format_relative_time(commit.time.into());
+            println!(
+                "  {}  {:<16} {}  {}",
+                paint(&commit.short_id, "33"),
+                time,
+                paint(&commit.author, "34"),
+                commit.summary
+            );
+        }
+    }
+    0
+}
+
+fn log(args: LogArgs) -> i32 {
+    let filter = crate::git::HistoryFilter {
+        author: args.author.unwrap_or_default(),
+        path: args.path.unwrap_or_default(),
+        since: args.since,
+        until: args.until,
+        first_parent: args.first_parent,
+        hide_merges: args.no_merges,
+        ..crate::git::HistoryFilter::default()
+    };
+    let (format, limit) = (args.format, args.limit);
+
+    match crate::git::history_export(&filter, limit) {
+        Ok(commits) => {
+            print!("{}", crate::export::render(&commits, format));
+            0
+        }
+        Err(e) => {
+            eprintln!("gitix log: {}", e);
+            1
+        }
+    }
+}
+
+/// Exit codes of `gitix status`; part of the scripting contract, so never renumber them
+const STATUS_CLEAN: i32 = 0;
+const STATUS_DIRTY: i32 = 1;
+const STATUS_ERROR: i32 = 2;
+
+/// First line of `gitix status --porcelain`; bump the version for any incompatible change
+const PORCELAIN_HEADER: &str = "# gitix-porcelain v1";
+
+fn status(porcelain: bool) -> i32 {
+    let status = match crate::git::get_status_summary() {
+        Ok(status) => status,
+        Err(e) => {
+            eprintln!("gitix status: {}", e);
+            return STATUS_ERROR;
+        }
+    };
+
+    // One entry per path with git's two-letter index/worktree code
+    let mut entries: std::collections::BTreeMap<String, (char, char, Option<String>)> =
+        std::collections::BTreeMap::new();
+    for entry in &status.staged {
+        let from = match &entry.status {
+            crate::git::FileStatusType::Renamed { from } => Some(from.clone()),
+            _ => None,
+        };
+        let letter = status_letter(&entry.status);
+        entries.insert(entry.path.clone(), (letter, '.', from));
+    }
+    for entry in &status.unstaged {
+        let letter = status_letter(&entry.status);
+        entries.entry(entry.path.clone()).or_insert(('.', '.', None)).1 = letter;
+    }
+    for path in &status.untracked {
+        entries.insert(path.clone(), ('?', '?', None));
+    }
+    for path in &status.conflicted {
+        entries.insert(path.clone(), ('U', 'U', None));
+    }
+
+    let branch = status.branch.as_deref().unwrap_or("(detached)");
+    if porcelain {
+        println!("{}", PORCELAIN_HEADER);
+        println!("# branch {}", branch);
+        if let Some(upstream) = &status.upstream {
+            println!("# upstream {}", upstream);
+            println!("# ab +{} -{}", status.ahead, status.behind);
+        }
+        if let Some(operation) = status.operation {
+            println!("# state {}", porcelain_state(operation));
+        }
+        for (path, (index, worktree, from)) in &entries {
+            match from {
+                Some(from) => println!("{}{} {} -> {}", index, worktree, from, path),
+                None => println!("{}{} {}", index, worktree, path),
+            }
+        }
+    } else {
+        match &status.upstream {
+            Some(upstream) => println!(
+                "On {} → {} ↑{} ↓{}",
+                paint(branch, "1;36"),
+                upstream,
+                status.ahead,
+                status.behind
+            ),
+            None => println!("On {}", paint(branch, "1;36")),
+        }
+        if let Some(operation) = status.operation {
+            println!("{}", paint(operation, "1;31"));
+        }
+        if entries.is_empty() {
+            println!("{}", paint("Nothing to commit, working tree clean", "32"));
+        }
+        for (path, (index, worktree, from)) in &entries {
+            let code = format!("{}{}", index, worktree);
+            let color = match code.as_str() {
+                "??" => "35",
+                "UU" => "31",
+                _ if *worktree == '.' => "32",
+                _ => "33",
+            };
+            match from {
+                Some(from) => println!("  {} {} → {}", paint(&code, color), from, path),
+                None => println!("  {} {}", paint(&code, color), path),
+            }
+        }
+    }
+
+    if entries.is_empty() {
+        STATUS_CLEAN
+    } else {
+        STATUS_DIRTY
+    }
+}
+
+/// The letter `git status --short` uses for a change
+fn status_letter(status: &crate::git::FileStatusType) -> char {
+    match status {
+        crate::git::FileStatusType::Modified => 'M',
+        crate::git::FileStatusType::Added => 'A',
+        crate::git::FileStatusType::Deleted => 'D',
+        crate::git::FileStatusType::Untracked => '?',
+        crate::git::FileStatusType::Renamed { .. } => 'R',
+        crate::git::FileStatusType::TypeChange => 'T',
+    }
+}
+
+/// Stable one-word name for an operation in progress, for `# state` lines
+fn porcelain_state(operation: &str) -> &'static str {
+    match operation {
+        "Merge in progress" => "merge",
+        "Revert in progress" => "revert",
+        "Cherry-pick in progress" => "cherry-pick",
+        "Bisect in progress" => "bisect",
+        "Rebase in progress" => "rebase",
+        _ => "am",
+    }
+}
+
+fn fetch(all: bool) -> i32 {
+    let results = if all {
+        crate::git::fetch_all_remotes()
+    } else {
+        crate::git::fetch_origin(None).map(|operation| vec![("origin".to_string(), operation)])
+    };
+
+    let results = match results {
+        Ok(results) => results,
+        Err(e) => {
+            eprintln!("gitix fetch: {}", e);
+            return 1;
+        }
+    };
+
+    if results.is_empty() {
+        eprintln!("gitix fetch: no remotes configured");
+        return 1;
+    }
+
+    let mut failed = 0;
+    for (name, operation) in &results {
+        crate::events::emit(
+            "fetch-finished",
+            serde_json::json!({
+                "remote": name,
+                "success": !matches!(operation.status, OperationStatus::Error),
+                "message": operation.message.trim_end(),
+            }),
+        );
+        match operation.status {
+            OperationStatus::Error => {
+                failed += 1;
+                eprintln!("✗ {}: {}", name, operation.message.trim_end());
+            }
+            _ => println!("✓ {}: {}", name, operation.message),
+        }
+    }
+
+    if failed > 0 {
+        eprintln!("{} of {} remotes failed to fetch", failed, results.len());
+        1
+    } else {
+        0
+    }
+}
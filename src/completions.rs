@@ -0,0 +1,50 @@
+use clap::CommandFactory;
+use clap_complete::Shell;
+
+/// The completion script for `shell`, generated from the command line definition in `cli`
+pub fn script(shell: Shell) -> String {
+    let mut command = crate::cli::Cli::command();
+    let mut out = Vec::new();
+    clap_complete::generate(shell, &mut command, "gitix", &mut out);
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// The gitix man page in roff format: the top-level page, then the options of each command
+pub fn man_page() -> String {
+    let command = crate::cli::Cli::command();
+    let man = clap_mangen::Man::new(command.clone());
+    let mut out = Vec::new();
+    // Writing into a Vec cannot fail
+    let _ = man.render_title(&mut out);
+    let _ = man.render_name_section(&mut out);
+    let _ = man.render_synopsis_section(&mut out);
+    let _ = man.render_description_section(&mut out);
+    let _ = man.render_options_section(&mut out);
+    let _ = man.render_subcommands_section(&mut out);
+
+    for sub in command.get_subcommands() {
+        if !sub.get_arguments().any(|arg| !arg.is_global_set() && !arg.is_hide_set()) {
+            continue;
+        }
+        let mut section = Vec::new();
+        let _ = clap_mangen::Man::new(sub.clone()).render_options_section(&mut section);
+        // Each command's options get their own heading instead of a second OPTIONS
+        let section = String::from_utf8_lossy(&section).replacen(
+            ".SH OPTIONS",
+            &format!(".SH \"{} OPTIONS\"", sub.get_name().to_uppercase()),
+            1,
+        );
+        out.extend_from_slice(section.as_bytes());
+    }
+
+    out.extend_from_slice(
+        b".SH \"EXIT STATUS\"\n\
+          0 on success, 1 when a git operation fails, 2 for a usage error. \
+          \\fBstatus\\fR exits 0 when the working tree is clean, 1 when it has changes \
+          and 2 on any error.\n\
+          .SH \"SEE ALSO\"\n\
+          \\fBgit\\fR(1)\n",
+    );
+    let _ = man.render_version_section(&mut out);
+    String::from_utf8_lossy(&out).into_owned()
+}
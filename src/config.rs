@@ -1,3 +1,6 @@
+use crate::app::{CommitTimeZone, Profile, TimeFormat};
+use crate::forge::ForgeKind;
+use crate::git::WipMode;
 use crate::tui::theme::{AccentColor, TitleColor};
 use git2::{Config, Repository};
 
@@ -161,6 +164,421 @@ pub fn get_pull_rebase() -> Result<Option<bool>, ConfigError> {
     }
 }
 
+/// Set gitix desktop notification setting in local repository config
+pub fn set_notifications_enabled(enabled: bool) -> Result<(), ConfigError> {
+    let repo = Repository::open(".")?;
+    let mut config = repo.config()?;
+    config.set_bool("gitix.notifications.enabled", enabled)?;
+    Ok(())
+}
+
+/// Get gitix desktop notification setting from repository config
+pub fn get_notifications_enabled() -> Result<Option<bool>, ConfigError> {
+    let repo = Repository::open(".")?;
+    let config = repo.config()?;
+    match config.get_bool("gitix.notifications.enabled") {
+        Ok(enabled) => Ok(Some(enabled)),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(ConfigError::Git2(e)),
+    }
+}
+
+/// Set Update tab auto-refresh interval (minutes, 0 disables) in local repository config
+pub fn set_auto_refresh_minutes(minutes: u32) -> Result<(), ConfigError> {
+    let repo = Repository::open(".")?;
+    let mut config = repo.config()?;
+    config.set_i32("gitix.update.autoRefresh", minutes as i32)?;
+    Ok(())
+}
+
+/// Get Update tab auto-refresh interval from repository config
+pub fn get_auto_refresh_minutes() -> Result<Option<u32>, ConfigError> {
+    let repo = Repository::open(".")?;
+    let config = repo.config()?;
+    match config.get_i32("gitix.update.autoRefresh") {
+        Ok(minutes) => Ok(Some(minutes.max(0) as u32)),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(ConfigError::Git2(e)),
+    }
+}
+
+/// Get how often the working tree is snapshotted to refs/gitix/backup, in minutes (0 = off)
+pub fn get_backup_minutes() -> Result<Option<u32>, ConfigError> {
+    let repo = Repository::open(".")?;
+    let config = repo.config()?;
+    match config.get_i32("gitix.backup.minutes") {
+        Ok(minutes) => Ok(Some(minutes.max(0) as u32)),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(ConfigError::Git2(e)),
+    }
+}
+
+/// Get how many sync operations the Update tab keeps in its log (gitix.update.historySize)
+pub fn get_sync_history_size() -> Result<Option<usize>, ConfigError> {
+    let repo = Repository::open(".")?;
+    let config = repo.config()?;
+    match config.get_i64("gitix.update.historySize") {
+        Ok(size) => Ok(Some(size.max(0) as usize)),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(ConfigError::Git2(e)),
+    }
+}
+
+/// Get how many directory levels of a new, untracked folder are listed file by file
+/// (gitix.status.untrackedDepth, 0 = one row per folder like `git status`)
+pub fn get_untracked_depth() -> Result<Option<usize>, ConfigError> {
+    let repo = Repository::open(".")?;
+    let config = repo.config()?;
+    match config.get_i32("gitix.status.untrackedDepth") {
+        Ok(depth) => Ok(Some(depth.max(0) as usize)),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(ConfigError::Git2(e)),
+    }
+}
+
+/// Set whether fork sync also pushes to origin in local repository config
+pub fn set_fork_push_after_sync(push: bool) -> Result<(), ConfigError> {
+    let repo = Repository::open(".")?;
+    let mut config = repo.config()?;
+    config.set_bool("gitix.fork.push", push)?;
+    Ok(())
+}
+
+/// Set whether deleted files skip the system trash in local repository config
+pub fn set_permanent_delete(permanent: bool) -> Result<(), ConfigError> {
+    let repo = Repository::open(".")?;
+    let mut config = repo.config()?;
+    config.set_bool("gitix.files.permanentDelete", permanent)?;
+    Ok(())
+}
+
+/// Get whether deleted files skip the system trash from repository config
+pub fn get_permanent_delete() -> Result<Option<bool>, ConfigError> {
+    let repo = Repository::open(".")?;
+    let config = repo.config()?;
+    match config.get_bool("gitix.files.permanentDelete") {
+        Ok(permanent) => Ok(Some(permanent)),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(ConfigError::Git2(e)),
+    }
+}
+
+/// Get whether fork sync also pushes to origin from repository config
+pub fn get_fork_push_after_sync() -> Result<Option<bool>, ConfigError> {
+    let repo = Repository::open(".")?;
+    let config = repo.config()?;
+    match config.get_bool("gitix.fork.push") {
+        Ok(push) => Ok(Some(push)),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(ConfigError::Git2(e)),
+    }
+}
+
+/// Set which forge the remotes are hosted on in local repository config, `None` to detect it
+pub fn set_forge(kind: Option<ForgeKind>) -> Result<(), ConfigError> {
+    let repo = Repository::open(".")?;
+    let mut config = repo.config()?;
+    config.set_str("gitix.forge", kind.map_or("auto", ForgeKind::key))?;
+    Ok(())
+}
+
+/// Get the forge set for this repository (gitix.forge); `auto` or unset means detect it
+pub fn get_forge() -> Result<Option<ForgeKind>, ConfigError> {
+    let repo = Repository::open(".")?;
+    let config = repo.config()?;
+    match config.get_string("gitix.forge") {
+        Ok(value) if value.trim().eq_ignore_ascii_case("auto") => Ok(None),
+        Ok(value) => ForgeKind::from_key(&value).map(Some).ok_or_else(|| {
+            ConfigError::InvalidValue(format!(
+                "gitix.forge must be auto, github, gitlab, gitea, forgejo or bitbucket, got {}",
+                value
+            ))
+        }),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(ConfigError::Git2(e)),
+    }
+}
+
+/// Set the commit body hard-wrap column (0 disables) in local repository config
+pub fn set_commit_wrap_column(column: usize) -> Result<(), ConfigError> {
+    let repo = Repository::open(".")?;
+    let mut config = repo.config()?;
+    config.set_i32("gitix.commit.wrap", column as i32)?;
+    Ok(())
+}
+
+/// Get the commit body hard-wrap column from repository config
+pub fn get_commit_wrap_column() -> Result<Option<usize>, ConfigError> {
+    let repo = Repository::open(".")?;
+    let config = repo.config()?;
+    match config.get_i32("gitix.commit.wrap") {
+        Ok(column) => Ok(Some(column.max(0) as usize)),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(ConfigError::Git2(e)),
+    }
+}
+
+/// Get the command that formats staged files before a commit (gitix.commit.formatter)
+pub fn get_commit_formatter() -> Result<Option<String>, ConfigError> {
+    let repo = Repository::open(".")?;
+    let config = repo.config()?;
+    match config.get_string("gitix.commit.formatter") {
+        Ok(command) if command.trim().is_empty() => Ok(None),
+        Ok(command) => Ok(Some(command)),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(ConfigError::Git2(e)),
+    }
+}
+
+/// Get the command that checks the working tree before a commit (gitix.verify.command)
+pub fn get_verify_command() -> Result<Option<String>, ConfigError> {
+    let repo = Repository::open(".")?;
+    let config = repo.config()?;
+    match config.get_string("gitix.verify.command") {
+        Ok(command) if command.trim().is_empty() => Ok(None),
+        Ok(command) => Ok(Some(command)),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(ConfigError::Git2(e)),
+    }
+}
+
+/// Get the configured tab bar layout (comma-separated tab names) from repository config
+pub fn get_ui_tabs() -> Result<Option<String>, ConfigError> {
+    let repo = Repository::open(".")?;
+    let config = repo.config()?;
+    match config.get_string("gitix.ui.tabs") {
+        Ok(tabs) => Ok(Some(tabs)),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(ConfigError::Git2(e)),
+    }
+}
+
+/// Get how times are shown, from repository config (falling back to global/system)
+pub fn get_time_format() -> Result<Option<TimeFormat>, ConfigError> {
+    let repo = Repository::open(".")?;
+    let config = repo.config()?;
+    match config.get_string("gitix.ui.timeformat") {
+        Ok(value) => TimeFormat::from_key(&value).map(Some).ok_or_else(|| {
+            ConfigError::InvalidValue(format!(
+                "gitix.ui.timeformat must be relative, absolute or iso, got {}",
+                value
+            ))
+        }),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(ConfigError::Git2(e)),
+    }
+}
+
+/// Get whose clock commit times are shown on, from repository config (falling back to global/system)
+pub fn get_time_zone() -> Result<Option<CommitTimeZone>, ConfigError> {
+    let repo = Repository::open(".")?;
+    let config = repo.config()?;
+    match config.get_string("gitix.ui.timezone") {
+        Ok(value) => CommitTimeZone::from_key(&value).map(Some).ok_or_else(|| {
+            ConfigError::InvalidValue(format!(
+                "gitix.ui.timezone must be original, local or utc, got {}",
+                value
+            ))
+        }),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(ConfigError::Git2(e)),
+    }
+}
+
+/// Get whether merge commits are left out of the Overview activity calendar and sparkline
+pub fn get_activity_exclude_merges() -> Result<Option<bool>, ConfigError> {
+    let repo = Repository::open(".")?;
+    let config = repo.config()?;
+    match config.get_bool("gitix.activity.excludeMerges") {
+        Ok(exclude) => Ok(Some(exclude)),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(ConfigError::Git2(e)),
+    }
+}
+
+/// Get the author patterns left out of the Overview activity, one per gitix.activity.excludeAuthor
+///
+/// An empty value leaves no author out. `None` when the key isn't set at all.
+pub fn get_activity_exclude_authors() -> Result<Option<Vec<String>>, ConfigError> {
+    let repo = Repository::open(".")?;
+    let config = repo.config()?;
+    let mut patterns = Vec::new();
+    let mut found = false;
+    config
+        .multivar("gitix.activity.excludeAuthor", None)?
+        .for_each(|entry| {
+            found = true;
+            if let Some(pattern) = entry.value().map(|v| v.trim().to_lowercase()) {
+                if !pattern.is_empty() {
+                    patterns.push(pattern);
+                }
+            }
+        })?;
+    Ok(found.then_some(patterns))
+}
+
+/// Get git's init.defaultBranch (repository config falls back to global/system)
+pub fn get_init_default_branch() -> Result<Option<String>, ConfigError> {
+    let repo = Repository::open(".")?;
+    let config = repo.config()?;
+    match config.get_string("init.defaultBranch") {
+        Ok(branch) => Ok(Some(branch)),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(ConfigError::Git2(e)),
+    }
+}
+
+/// The user's global git config (~/.gitconfig), for settings needed outside any repository
+fn global_config() -> Result<Config, ConfigError> {
+    let path = match Config::find_global() {
+        Ok(path) => path,
+        Err(_) => std::env::var_os("HOME")
+            .map(|home| std::path::PathBuf::from(home).join(".gitconfig"))
+            .ok_or_else(|| ConfigError::InvalidValue("HOME is not set".to_string()))?,
+    };
+    Ok(Config::open(&path)?)
+}
+
+/// Set the gitix template applied to new repositories in global config, or clear it
+pub fn set_init_template(template: Option<&str>) -> Result<(), ConfigError> {
+    let mut config = global_config()?;
+    match template {
+        Some(name) => config.set_str("gitix.init.template", name)?,
+        None => match config.remove("gitix.init.template") {
+            Err(e) if e.code() != git2::ErrorCode::NotFound => return Err(ConfigError::Git2(e)),
+            _ => {}
+        },
+    }
+    Ok(())
+}
+
+/// Get the gitix template applied to new repositories from global config
+pub fn get_init_template() -> Result<Option<String>, ConfigError> {
+    let config = Config::open_default()?;
+    match config.get_string("gitix.init.template") {
+        Ok(name) if !name.is_empty() => Ok(Some(name)),
+        Ok(_) => Ok(None),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(ConfigError::Git2(e)),
+    }
+}
+
+/// Set whether quitting warns about unpushed commits and old stashes, in global config
+pub fn set_quit_warn_unpushed(warn: bool) -> Result<(), ConfigError> {
+    let mut config = global_config()?;
+    config.set_bool("gitix.quit.warnUnpushed", warn)?;
+    Ok(())
+}
+
+/// Get whether quitting warns about unpushed commits and old stashes
+pub fn get_quit_warn_unpushed() -> Result<Option<bool>, ConfigError> {
+    let config = Config::open_default()?;
+    match config.get_bool("gitix.quit.warnUnpushed") {
+        Ok(warn) => Ok(Some(warn)),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(ConfigError::Git2(e)),
+    }
+}
+
+/// Get the age in days after which a stash is mentioned on quit
+pub fn get_quit_stash_days() -> Result<Option<u32>, ConfigError> {
+    let config = Config::open_default()?;
+    match config.get_i32("gitix.quit.stashDays") {
+        Ok(days) if days >= 0 => Ok(Some(days as u32)),
+        Ok(days) => Err(ConfigError::InvalidValue(format!(
+            "gitix.quit.stashDays must not be negative, got {}",
+            days
+        ))),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(ConfigError::Git2(e)),
+    }
+}
+
+/// Set how much gitix shows and asks (beginner, standard or expert) in global config
+pub fn set_profile(profile: Profile) -> Result<(), ConfigError> {
+    let mut config = global_config()?;
+    config.set_str("gitix.profile", profile.key())?;
+    Ok(())
+}
+
+/// Get how much gitix shows and asks
+pub fn get_profile() -> Result<Option<Profile>, ConfigError> {
+    let config = Config::open_default()?;
+    match config.get_string("gitix.profile") {
+        Ok(value) => Profile::from_key(&value).map(Some).ok_or_else(|| {
+            ConfigError::InvalidValue(format!(
+                "gitix.profile must be beginner, standard or expert, got {}",
+                value
+            ))
+        }),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(ConfigError::Git2(e)),
+    }
+}
+
+/// Get where WIP quick-saves go (gitix.wip.mode: commit or stash)
+pub fn get_wip_mode() -> Result<Option<WipMode>, ConfigError> {
+    let repo = Repository::open(".")?;
+    let config = repo.config()?;
+    match config.get_string("gitix.wip.mode") {
+        Ok(value) => match value.trim().to_ascii_lowercase().as_str() {
+            "commit" => Ok(Some(WipMode::Commit)),
+            "stash" => Ok(Some(WipMode::Stash)),
+            _ => Err(ConfigError::InvalidValue(format!(
+                "gitix.wip.mode must be commit or stash, got {}",
+                value
+            ))),
+        },
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(ConfigError::Git2(e)),
+    }
+}
+
+/// Record in global config whether the first-launch tour has been seen
+pub fn set_tour_completed(completed: bool) -> Result<(), ConfigError> {
+    let mut config = global_config()?;
+    config.set_bool("gitix.tour.completed", completed)?;
+    Ok(())
+}
+
+/// Get whether the first-launch tour has been seen
+pub fn get_tour_completed() -> Result<Option<bool>, ConfigError> {
+    let config = Config::open_default()?;
+    match config.get_bool("gitix.tour.completed") {
+        Ok(completed) => Ok(Some(completed)),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(ConfigError::Git2(e)),
+    }
+}
+
+/// Set whether gitix looks for a newer release on startup in global config
+pub fn set_update_check(check: bool) -> Result<(), ConfigError> {
+    let mut config = global_config()?;
+    config.set_bool("gitix.update.check", check)?;
+    Ok(())
+}
+
+/// Get whether gitix looks for a newer release on startup
+pub fn get_update_check() -> Result<Option<bool>, ConfigError> {
+    let config = Config::open_default()?;
+    match config.get_bool("gitix.update.check") {
+        Ok(check) => Ok(Some(check)),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(ConfigError::Git2(e)),
+    }
+}
+
+/// Get the external command configured for a gitix UI event (gitix.hooks.<event>)
+pub fn get_ui_hook(event: &str) -> Result<Option<String>, ConfigError> {
+    let repo = Repository::open(".")?;
+    let config = repo.config()?;
+    match config.get_string(&format!("gitix.hooks.{}", event)) {
+        Ok(command) => Ok(Some(command)),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(ConfigError::Git2(e)),
+    }
+}
+
 /// Convert AccentColor to string for storage
 fn accent_color_to_string(accent: AccentColor) -> String {
     match accent {
@@ -182,7 +600,7 @@ fn accent_color_to_string(accent: AccentColor) -> String {
 }
 
 /// Convert string to AccentColor
-fn string_to_accent_color(s: &str) -> Result<AccentColor, ConfigError> {
+pub fn string_to_accent_color(s: &str) -> Result<AccentColor, ConfigError> {
     match s.to_lowercase().as_str() {
         "rosewater" => Ok(AccentColor::Rosewater),
         "flamingo" => Ok(AccentColor::Flamingo),
@@ -0,0 +1,196 @@
+use git2::{Cred, CredentialType};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Mutex;
+
+/// Times one fetch or push asks before giving up, so a wrong password can be retyped
+const MAX_PROMPTS: usize = 3;
+
+/// Private keys tried after the SSH agent, in the order ssh itself tries them
+const SSH_KEY_FILES: [&str; 3] = ["id_ed25519", "id_ecdsa", "id_rsa"];
+
+/// What a credential prompt asks for
+#[derive(Debug, Clone, PartialEq)]
+pub enum CredentialKind {
+    UserPass,            // HTTPS: a username and a password or token
+    Passphrase(PathBuf), // The passphrase of an SSH private key
+}
+
+/// A question from a fetch or push for the UI, answered on `reply`
+pub struct CredentialRequest {
+    pub url: String,
+    pub username: Option<String>, // From the URL, filled in to start with
+    pub kind: CredentialKind,
+    pub reply: Sender<Option<(String, String)>>, // Username and secret, or None when cancelled
+}
+
+/// Where credential callbacks send their questions, if a UI is there to answer them
+static PROMPTER: Mutex<Option<Sender<CredentialRequest>>> = Mutex::new(None);
+
+/// Have credential callbacks ask whoever reads the returned receiver instead of failing
+pub fn install_prompter() -> Receiver<CredentialRequest> {
+    let (tx, rx) = mpsc::channel();
+    if let Ok(mut guard) = PROMPTER.lock() {
+        *guard = Some(tx);
+    }
+    rx
+}
+
+/// Ask the UI and block until it answers
+fn ask(
+    url: &str,
+    username: Option<&str>,
+    kind: CredentialKind,
+) -> Result<(String, String), git2::Error> {
+    let (reply, answer) = mpsc::channel();
+    let request = CredentialRequest {
+        url: url.to_string(),
+        username: username.map(str::to_string),
+        kind,
+        reply,
+    };
+    let sent = PROMPTER
+        .lock()
+        .ok()
+        .and_then(|guard| guard.as_ref().map(|prompter| prompter.send(request).is_ok()))
+        .unwrap_or(false);
+    if !sent {
+        return Err(git2::Error::from_str("No suitable authentication method found"));
+    }
+    match answer.recv() {
+        Ok(Some(credentials)) => Ok(credentials),
+        // A user error, so callers can tell it from a failure worth retrying another way
+        _ => Err(git2::Error::new(
+            git2::ErrorCode::User,
+            git2::ErrorClass::Callback,
+            "Authentication cancelled",
+        )),
+    }
+}
+
+/// The credentials callback for fetches and pushes
+///
+/// libgit2 calls it again whenever what it returned was refused, so each call
+/// moves on to the next thing to try. For SSH that is the agent, then the key
+/// files in ~/.ssh, asking for the passphrase of encrypted ones. For HTTPS it is
+/// git's credential helper, then asking for a username and password or token.
+pub fn callback() -> impl FnMut(&str, Option<&str>, CredentialType) -> Result<Cred, git2::Error> {
+    let mut tried_agent = false;
+    let mut tried_helper = false;
+    let mut keys = ssh_key_files().into_iter();
+    let mut prompts = 0;
+    let mut last_username: Option<String> = None;
+    move |url, username_from_url, allowed_types| {
+        let username = username_from_url.unwrap_or("git");
+        if allowed_types.contains(CredentialType::USERNAME) {
+            return Cred::username(username);
+        }
+
+        if allowed_types.contains(CredentialType::SSH_KEY) {
+            if !tried_agent {
+                tried_agent = true;
+                if let Ok(cred) = Cred::ssh_key_from_agent(username) {
+                    return Ok(cred);
+                }
+            }
+            if let Some(key) = keys.next() {
+                if !is_encrypted_key(&key) {
+                    return Cred::ssh_key(username, None, &key, None);
+                }
+                if prompts < MAX_PROMPTS {
+                    prompts += 1;
+                    let (_, passphrase) =
+                        ask(url, Some(username), CredentialKind::Passphrase(key.clone()))?;
+                    return Cred::ssh_key(username, None, &key, Some(&passphrase));
+                }
+            }
+        }
+
+        if allowed_types.contains(CredentialType::USER_PASS_PLAINTEXT) {
+            if !tried_helper {
+                tried_helper = true;
+                // Without a readable git config there is no helper to ask; go on to the prompt
+                let helper = git2::Config::open_default().and_then(|config| {
+                    Cred::credential_helper(&config, url, username_from_url)
+                });
+                if let Ok(cred) = helper {
+                    return Ok(cred);
+                }
+            }
+            if prompts < MAX_PROMPTS {
+                prompts += 1;
+                // After a refusal the username typed last time is most likely still right
+                let known = last_username.as_deref().or(username_from_url);
+                let (username, password) = ask(url, known, CredentialKind::UserPass)?;
+                let cred = Cred::userpass_plaintext(&username, &password);
+                last_username = Some(username);
+                return cred;
+            }
+        }
+
+        Err(git2::Error::from_str("No suitable authentication method found"))
+    }
+}
+
+/// The default private keys in ~/.ssh that exist
+fn ssh_key_files() -> Vec<PathBuf> {
+    let ssh_dir = match std::env::var_os("HOME") {
+        Some(home) => PathBuf::from(home).join(".ssh"),
+        None => return Vec::new(),
+    };
+    SSH_KEY_FILES
+        .iter()
+        .map(|name| ssh_dir.join(name))
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+/// Whether a private key needs a passphrase, in either the PEM or the OpenSSH format
+fn is_encrypted_key(path: &std::path::Path) -> bool {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(_) => return false,
+    };
+    if contents.contains("ENCRYPTED") {
+        return true; // PEM: "Proc-Type: 4,ENCRYPTED" or "BEGIN ENCRYPTED PRIVATE KEY"
+    }
+    // OpenSSH: "openssh-key-v1\0", then the cipher name, which is "none" when unencrypted
+    let body: String = contents
+        .lines()
+        .filter(|line| !line.starts_with("-----"))
+        .collect();
+    let blob = decode_base64(&body);
+    let magic = b"openssh-key-v1\0";
+    match blob.get(magic.len()..magic.len() + 4) {
+        Some(len) if blob.starts_with(magic) => {
+            let len = u32::from_be_bytes([len[0], len[1], len[2], len[3]]) as usize;
+            let start = magic.len() + 4;
+            blob.get(start..start + len).is_some_and(|cipher| cipher != b"none")
+        }
+        _ => false,
+    }
+}
+
+/// Decode standard base64, skipping anything that isn't part of the alphabet
+fn decode_base64(text: &str) -> Vec<u8> {
+    let mut out = Vec::new();
+    let (mut buffer, mut bits) = (0u32, 0);
+    for byte in text.bytes() {
+        let value = match byte {
+            b'A'..=b'Z' => byte - b'A',
+            b'a'..=b'z' => byte - b'a' + 26,
+            b'0'..=b'9' => byte - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => continue,
+        };
+        buffer = (buffer << 6) | u32::from(value);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    out
+}
@@ -0,0 +1,115 @@
+use std::path::Path;
+
+/// Bytes git looks at when deciding whether a file is binary
+const BINARY_CHECK_LEN: usize = 8000;
+
+/// Characters for bytes 0x80 to 0x9F in Windows-1252; the rest of the range is Latin-1
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '€', '\u{FFFD}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{FFFD}', 'Ž',
+    '\u{FFFD}', '\u{FFFD}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ',
+    '\u{FFFD}', 'ž', 'Ÿ',
+];
+
+/// The text encoding of a file, as far as its bytes tell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextEncoding {
+    Utf8,
+    Utf8Bom,     // UTF-8 starting with a byte order mark
+    Utf16Le,     // With a byte order mark; git diffs these as binary
+    Utf16Be,     // Likewise
+    Windows1252, // Any other text that isn't valid UTF-8: the usual legacy encoding, a superset of Latin-1
+}
+
+impl TextEncoding {
+    pub fn label(self) -> &'static str {
+        match self {
+            TextEncoding::Utf8 => "UTF-8",
+            TextEncoding::Utf8Bom => "UTF-8 BOM",
+            TextEncoding::Utf16Le => "UTF-16LE",
+            TextEncoding::Utf16Be => "UTF-16BE",
+            TextEncoding::Windows1252 => "Windows-1252",
+        }
+    }
+}
+
+/// Guess the encoding of a file's contents; `None` for binary content
+pub fn detect(bytes: &[u8]) -> Option<TextEncoding> {
+    if bytes.starts_with(&[0xEF, 0xBB, 0xBF]) {
+        return Some(TextEncoding::Utf8Bom);
+    }
+    if bytes.starts_with(&[0xFF, 0xFE]) {
+        return Some(TextEncoding::Utf16Le);
+    }
+    if bytes.starts_with(&[0xFE, 0xFF]) {
+        return Some(TextEncoding::Utf16Be);
+    }
+    if bytes[..bytes.len().min(BINARY_CHECK_LEN)].contains(&0) {
+        return None;
+    }
+    match std::str::from_utf8(bytes) {
+        Ok(_) => Some(TextEncoding::Utf8),
+        Err(_) => Some(TextEncoding::Windows1252),
+    }
+}
+
+/// The encoding of a working tree file; `None` when it is binary or can't be read
+pub fn detect_file(path: &Path) -> Option<TextEncoding> {
+    detect(&std::fs::read(path).ok()?)
+}
+
+/// Decode a file's contents, replacing what doesn't fit the encoding with U+FFFD
+pub fn decode(bytes: &[u8], encoding: TextEncoding) -> String {
+    let utf16 = |to_unit: fn([u8; 2]) -> u16| {
+        let units: Vec<u16> = bytes[2..]
+            .chunks_exact(2)
+            .map(|pair| to_unit([pair[0], pair[1]]))
+            .collect();
+        String::from_utf16_lossy(&units)
+    };
+    match encoding {
+        TextEncoding::Utf8 => String::from_utf8_lossy(bytes).into_owned(),
+        TextEncoding::Utf8Bom => String::from_utf8_lossy(&bytes[3..]).into_owned(),
+        TextEncoding::Utf16Le => utf16(u16::from_le_bytes),
+        TextEncoding::Utf16Be => utf16(u16::from_be_bytes),
+        TextEncoding::Windows1252 => bytes
+            .iter()
+            .map(|&byte| match byte {
+                0x80..=0x9F => WINDOWS_1252_HIGH[(byte - 0x80) as usize],
+                _ => char::from(byte),
+            })
+            .collect(),
+    }
+}
+
+/// Decode text of unknown encoding: as UTF-8 when it is valid UTF-8, otherwise as Windows-1252
+///
+/// Diffs decode line by line, which keeps both sides of a file that changed
+/// encoding readable.
+pub fn decode_lossy(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(_) => decode(bytes, TextEncoding::Windows1252),
+    }
+}
+
+/// A warning for a commit whose files aren't all in one encoding, naming those not in UTF-8
+///
+/// `files` lists staged text files with their encodings; plain ASCII files fit
+/// any encoding and shouldn't be in it.
+pub fn mixed_encoding_warning(files: &[(String, TextEncoding)]) -> Option<String> {
+    let first = files.first()?.1;
+    if files.iter().all(|(_, encoding)| *encoding == first) {
+        return None;
+    }
+    let odd: Vec<String> = files
+        .iter()
+        .filter(|(_, encoding)| *encoding != TextEncoding::Utf8)
+        .map(|(path, encoding)| format!("{} is {}", path, encoding.label()))
+        .collect();
+    let shown = odd.len().min(2);
+    let mut warning = format!("Mixed encodings: {}", odd[..shown].join(", "));
+    if odd.len() > shown {
+        warning.push_str(&format!(" and {} more", odd.len() - shown));
+    }
+    Some(warning)
+}
@@ -0,0 +1,66 @@
+use serde_json::{json, Map, Value};
+use std::io::Write;
+use std::sync::Mutex;
+
+/// Where JSONL events go, if an integration asked for them
+static SINK: Mutex<Option<Box<dyn Write + Send>>> = Mutex::new(None);
+
+/// Send events to a listening Unix domain socket (`--event-socket <path>`)
+#[cfg(unix)]
+pub fn connect_socket(path: &str) -> std::io::Result<()> {
+    let stream = std::os::unix::net::UnixStream::connect(path)?;
+    set_sink(Box::new(stream));
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn connect_socket(_path: &str) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "event sockets are only supported on Unix",
+    ))
+}
+
+/// Write events to stdout (`--events`); only usable when the TUI isn't drawing there
+pub fn to_stdout() {
+    set_sink(Box::new(std::io::stdout()));
+}
+
+fn set_sink(sink: Box<dyn Write + Send>) {
+    if let Ok(mut guard) = SINK.lock() {
+        *guard = Some(sink);
+    }
+}
+
+/// Whether anyone is listening, so callers can skip building event payloads
+pub fn enabled() -> bool {
+    SINK.lock().map(|guard| guard.is_some()).unwrap_or(false)
+}
+
+/// Emit one event as a single JSON line
+///
+/// Every event carries `event` and an RFC 3339 `time`; `fields` adds the
+/// event-specific data. A listener that goes away simply stops receiving
+/// events - it never interrupts gitix.
+pub fn emit(event: &str, fields: Value) {
+    let mut guard = match SINK.lock() {
+        Ok(guard) => guard,
+        Err(_) => return,
+    };
+    let sink = match guard.as_mut() {
+        Some(sink) => sink,
+        None => return,
+    };
+
+    let mut object = Map::new();
+    object.insert("event".to_string(), json!(event));
+    object.insert("time".to_string(), json!(chrono::Local::now().to_rfc3339()));
+    if let Value::Object(fields) = fields {
+        object.extend(fields);
+    }
+
+    let line = Value::Object(object).to_string();
+    if writeln!(sink, "{}", line).and_then(|_| sink.flush()).is_err() {
+        *guard = None;
+    }
+}
@@ -0,0 +1,93 @@
+use crate::git::ExportedCommit;
+use serde_json::json;
+use std::path::PathBuf;
+
+/// File formats commit history can be exported in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    Json,
+    Csv,
+}
+
+impl ExportFormat {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "json" => Some(ExportFormat::Json),
+            "csv" => Some(ExportFormat::Csv),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Csv => "csv",
+        }
+    }
+}
+
+/// Render commits as a JSON array or as CSV with a header row
+pub fn render(commits: &[ExportedCommit], format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Json => {
+            let commits: Vec<_> = commits
+                .iter()
+                .map(|commit| {
+                    json!({
+                        "hash": commit.id,
+                        "author": commit.author,
+                        "email": commit.email,
+                        "date": commit.date.to_rfc3339(),
+                        "message": commit.message,
+                        "files_changed": commit.files_changed,
+                        "insertions": commit.insertions,
+                        "deletions": commit.deletions,
+                    })
+                })
+                .collect();
+            let mut out = serde_json::to_string_pretty(&commits).unwrap_or_default();
+            out.push('\n');
+            out
+        }
+        ExportFormat::Csv => {
+            let mut out =
+                String::from("hash,author,email,date,files_changed,insertions,deletions,message\n");
+            for commit in commits {
+                let fields = [
+                    commit.id.clone(),
+                    csv_field(&commit.author),
+                    csv_field(&commit.email),
+                    commit.date.to_rfc3339(),
+                    commit.files_changed.to_string(),
+                    commit.insertions.to_string(),
+                    commit.deletions.to_string(),
+                    csv_field(&commit.message),
+                ];
+                out.push_str(&fields.join(","));
+                out.push('\n');
+            }
+            out
+        }
+    }
+}
+
+/// Quote a CSV field when it holds a separator, quote or line break (RFC 4180)
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Default location for an export named `gitix-<kind>-<timestamp>.<extension>`
+///
+/// Inside the git directory so the worktree stays clean. History, review notes and the
+/// activity log all export here.
+pub fn default_export_path(kind: &str, extension: &str) -> PathBuf {
+    let git_dir = git2::Repository::open(".")
+        .map(|repo| repo.path().to_path_buf())
+        .unwrap_or_else(|_| PathBuf::from("."));
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    git_dir.join(format!("gitix-{}-{}.{}", kind, stamp, extension))
+}
@@ -1,5 +1,5 @@
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 #[cfg(unix)]
@@ -24,8 +24,10 @@ pub struct FileEntry {
     pub size: u64,
     pub permissions: u32,
     pub modified: u64,
-    pub is_dir: bool,
+    pub is_dir: bool, // For symlinks, whether the target is a directory
     pub git_status: Option<crate::git::FileStatusType>,
+    pub link_target: Option<PathBuf>, // Where the entry points, when it is a symlink
+    pub broken_link: bool,            // A symlink whose target doesn't exist
 }
 
 pub fn list_files(dir: &PathBuf, add_parent: bool) -> Vec<FileEntry> {
@@ -38,12 +40,25 @@ pub fn list_files(dir: &PathBuf, add_parent: bool) -> Vec<FileEntry> {
             modified: 0,
             is_dir: true,
             git_status: None,
+            link_target: None,
+            broken_link: false,
         });
     }
     if let Ok(read_dir) = fs::read_dir(dir) {
         for entry in read_dir.flatten() {
-            if let Ok(metadata) = entry.metadata() {
+            if let Ok(link_metadata) = entry.metadata() {
                 let name = entry.file_name().to_string_lossy().to_string();
+                // Describe links by what they point to, falling back to the link itself when broken
+                let link_target = if link_metadata.file_type().is_symlink() {
+                    fs::read_link(entry.path()).ok()
+                } else {
+                    None
+                };
+                let target_metadata = link_target
+                    .as_ref()
+                    .and_then(|_| fs::metadata(entry.path()).ok());
+                let broken_link = link_target.is_some() && target_metadata.is_none();
+                let metadata = target_metadata.unwrap_or(link_metadata);
                 let size = metadata.len();
                 let permissions = get_permissions(&metadata);
                 let modified = metadata
@@ -60,6 +75,8 @@ pub fn list_files(dir: &PathBuf, add_parent: bool) -> Vec<FileEntry> {
                     modified,
                     is_dir,
                     git_status: None,
+                    link_target,
+                    broken_link,
                 });
             }
         }
@@ -125,6 +142,33 @@ pub fn list_files_with_git_status(
     entries
 }
 
+/// Resolve the directory `name` in `dir` for the Files tab to enter
+///
+/// Symlinks are followed to the real directory, so a link pointing at one of its
+/// own parents lands there instead of nesting forever. The result is spelled under
+/// `jail` when given, and a link leading outside of it is refused.
+pub fn resolve_dir(dir: &Path, name: &str, jail: Option<&Path>) -> std::io::Result<PathBuf> {
+    let path = dir.join(name);
+    let is_link = fs::symlink_metadata(&path)?.file_type().is_symlink();
+    if !is_link {
+        return Ok(path);
+    }
+
+    let real = fs::canonicalize(&path)?;
+    let jail = match jail {
+        Some(jail) => jail,
+        None => return Ok(real),
+    };
+    let real_jail = fs::canonicalize(jail)?;
+    match real.strip_prefix(&real_jail) {
+        Ok(inside) => Ok(jail.join(inside)),
+        Err(_) => Err(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            format!("'{}' points to {}, outside {}", name, real.display(), jail.display()),
+        )),
+    }
+}
+
 /// Find the git repository root by looking for .git directory
 fn find_git_root(start_dir: &PathBuf) -> Option<PathBuf> {
     let mut current = start_dir.clone();
@@ -140,3 +184,80 @@ fn find_git_root(start_dir: &PathBuf) -> Option<PathBuf> {
         }
     }
 }
+
+/// Open a file in $EDITOR (falling back to vi) and wait for the editor to exit
+pub fn open_in_editor(file_path: &std::path::Path) -> std::io::Result<std::process::ExitStatus> {
+    open_in_editor_at(file_path, None)
+}
+
+/// Open a file in $EDITOR with the cursor on `line` (1-based), when the editor supports it
+pub fn open_in_editor_at(
+    file_path: &std::path::Path,
+    line: Option<usize>,
+) -> std::io::Result<std::process::ExitStatus> {
+    let editor = std::env::var("EDITOR").unwrap_or_else(|_| "vi".to_string());
+    let mut cmd = std::process::Command::new(&editor);
+    // Add --wait for VSCode
+    if editor.contains("code") {
+        cmd.arg("--wait");
+    }
+
+    let name = std::path::Path::new(&editor)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    match line {
+        // VSCode and friends take --goto file:line
+        Some(line) if name.contains("code") || name.contains("codium") => {
+            cmd.arg("--goto").arg(format!("{}:{}", file_path.display(), line));
+        }
+        Some(line) if matches!(name.as_str(), "subl" | "zed" | "hx" | "helix") => {
+            cmd.arg(format!("{}:{}", file_path.display(), line));
+        }
+        // vi, vim, nvim, nano, emacs, micro, kak, ... understand +line
+        Some(line) => {
+            cmd.arg(format!("+{}", line)).arg(file_path);
+        }
+        None => {
+            cmd.arg(file_path);
+        }
+    }
+    cmd.status()
+}
+
+/// Show `path` in the system file manager: selected in its folder where the platform
+/// supports that (Finder, Explorer), otherwise by opening the folder with xdg-open
+pub fn reveal_in_file_manager(path: &Path) -> std::io::Result<()> {
+    let mut cmd = if cfg!(target_os = "macos") {
+        let mut cmd = std::process::Command::new("open");
+        if path.is_dir() {
+            cmd.arg(path);
+        } else {
+            cmd.arg("-R").arg(path);
+        }
+        cmd
+    } else if cfg!(windows) {
+        let mut cmd = std::process::Command::new("explorer");
+        if path.is_dir() {
+            cmd.arg(path);
+        } else {
+            cmd.arg(format!("/select,{}", path.display()));
+        }
+        cmd
+    } else {
+        let folder = if path.is_dir() {
+            path
+        } else {
+            path.parent().unwrap_or(path)
+        };
+        let mut cmd = std::process::Command::new("xdg-open");
+        cmd.arg(folder);
+        cmd
+    };
+    // Don't wait: the file manager lives on after gitix, and its output would garble the screen
+    cmd.stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .map(|_| ())
+}
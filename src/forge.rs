@@ -0,0 +1,618 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Hosting services gitix knows the web pages and APIs of
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ForgeKind {
+    GitHub,
+    GitLab,
+    Gitea, // Also Forgejo and Codeberg, which share Gitea's API
+    Bitbucket,
+}
+
+impl ForgeKind {
+    pub const ALL: [ForgeKind; 4] = [
+        ForgeKind::GitHub,
+        ForgeKind::GitLab,
+        ForgeKind::Gitea,
+        ForgeKind::Bitbucket,
+    ];
+
+    /// The value stored in gitix.forge
+    pub fn key(self) -> &'static str {
+        match self {
+            ForgeKind::GitHub => "github",
+            ForgeKind::GitLab => "gitlab",
+            ForgeKind::Gitea => "gitea",
+            ForgeKind::Bitbucket => "bitbucket",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key.trim().to_ascii_lowercase().as_str() {
+            "github" => Some(ForgeKind::GitHub),
+            "gitlab" => Some(ForgeKind::GitLab),
+            "gitea" | "forgejo" => Some(ForgeKind::Gitea),
+            "bitbucket" => Some(ForgeKind::Bitbucket),
+            _ => None,
+        }
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            ForgeKind::GitHub => "GitHub",
+            ForgeKind::GitLab => "GitLab",
+            ForgeKind::Gitea => "Gitea/Forgejo",
+            ForgeKind::Bitbucket => "Bitbucket",
+        }
+    }
+
+    /// What the forge calls a pull request
+    pub fn pull_request_name(self) -> &'static str {
+        match self {
+            ForgeKind::GitLab => "Merge Requests",
+            _ => "Pull Requests",
+        }
+    }
+
+    /// The host of the hosted service, where repositories are created
+    fn hosted_host(self) -> &'static str {
+        match self {
+            ForgeKind::GitHub => "github.com",
+            ForgeKind::GitLab => "gitlab.com",
+            ForgeKind::Gitea => "codeberg.org",
+            ForgeKind::Bitbucket => "bitbucket.org",
+        }
+    }
+
+    /// Guess the forge from a remote's host name
+    fn from_host(host: &str) -> Option<Self> {
+        let host = host.to_ascii_lowercase();
+        if host == "github.com" || host.starts_with("github.") {
+            Some(ForgeKind::GitHub)
+        } else if host.contains("gitlab") {
+            Some(ForgeKind::GitLab)
+        } else if host == "bitbucket.org" {
+            Some(ForgeKind::Bitbucket)
+        } else if host == "codeberg.org" || host.contains("gitea") || host.contains("forgejo") {
+            Some(ForgeKind::Gitea)
+        } else {
+            None
+        }
+    }
+}
+
+/// A repository on a forge, as named by one of its remote URLs
+#[derive(Debug, Clone, PartialEq)]
+pub struct Forge {
+    pub kind: ForgeKind,
+    pub web: String,   // Scheme, host and any path prefix, without a trailing slash
+    pub owner: String, // User, organization, Bitbucket workspace or GitLab group path
+    pub repo: String,
+}
+
+/// An open pull (or merge) request
+#[derive(Debug, Clone)]
+pub struct PullRequest {
+    pub number: u64,
+    pub title: String,
+    pub author: String,
+    pub branch: String, // Source branch
+    pub url: String,
+}
+
+/// Combined CI result for a commit
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CiStatus {
+    Success,
+    Pending,
+    Failure,
+    None, // No CI reported anything for the commit
+}
+
+/// Work out the forge of `remote_url`, honoring a manual `kind` for self-hosted instances
+///
+/// Understands https, `ssh://` and scp-style (`git@host:owner/repo.git`) URLs. Returns
+/// `None` when the URL has no owner/repo path or the host isn't recognized and no
+/// kind was given.
+pub fn detect(remote_url: &str, kind: Option<ForgeKind>) -> Option<Forge> {
+    let url = remote_url.trim();
+    let (scheme, rest) = match url.split_once("://") {
+        Some((scheme, rest)) => (scheme.to_ascii_lowercase(), rest),
+        None => ("ssh".to_string(), url),
+    };
+    let (authority, path) = if scheme == "ssh" && !url.contains("://") {
+        rest.split_once(':')?
+    } else {
+        rest.split_once('/')?
+    };
+    let host_port = authority.rsplit('@').next().unwrap_or(authority);
+    let host = host_port.split(':').next().unwrap_or(host_port);
+    if host.is_empty() {
+        return None;
+    }
+
+    let mut segments: Vec<&str> = path
+        .trim_matches('/')
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .collect();
+    let repo = segments.pop()?.trim_end_matches(".git").to_string();
+    if segments.is_empty() || repo.is_empty() {
+        return None;
+    }
+
+    let kind = kind.or_else(|| ForgeKind::from_host(host))?;
+    // Web pages are served over https from the host, keeping a port only when it was already https
+    let mut web = if scheme == "http" || scheme == "https" {
+        format!("{}://{}", scheme, host_port)
+    } else {
+        format!("https://{}", host)
+    };
+    // GitLab nests groups, elsewhere anything before the owner is where the forge is mounted
+    let owner = if kind == ForgeKind::GitLab {
+        segments.join("/")
+    } else {
+        let owner = segments.pop()?.to_string();
+        for prefix in segments {
+            web.push('/');
+            web.push_str(prefix);
+        }
+        owner
+    };
+    Some(Forge {
+        kind,
+        web,
+        owner,
+        repo,
+    })
+}
+
+impl Forge {
+    /// The host name the forge is served from, without scheme or port
+    pub fn host(&self) -> &str {
+        let rest = self.web.split_once("://").map_or(self.web.as_str(), |(_, rest)| rest);
+        let host_port = rest.split('/').next().unwrap_or(rest);
+        host_port.split(':').next().unwrap_or(host_port)
+    }
+
+    /// The repository's home page
+    pub fn repo_url(&self) -> String {
+        format!("{}/{}/{}", self.web, self.owner, self.repo)
+    }
+
+    /// The page of `branch`'s files
+    pub fn branch_url(&self, branch: &str) -> String {
+        let repo = self.repo_url();
+        let branch = encode_path(branch);
+        match self.kind {
+            ForgeKind::GitHub => format!("{}/tree/{}", repo, branch),
+            ForgeKind::GitLab => format!("{}/-/tree/{}", repo, branch),
+            ForgeKind::Gitea => format!("{}/src/branch/{}", repo, branch),
+            ForgeKind::Bitbucket => format!("{}/src/{}", repo, branch),
+        }
+    }
+
+    /// The list of open pull requests
+    pub fn pull_requests_url(&self) -> String {
+        let repo = self.repo_url();
+        match self.kind {
+            ForgeKind::GitHub | ForgeKind::Gitea => format!("{}/pulls", repo),
+            ForgeKind::GitLab => format!("{}/-/merge_requests", repo),
+            ForgeKind::Bitbucket => format!("{}/pull-requests", repo),
+        }
+    }
+
+    /// The form for opening a pull request from `branch`
+    pub fn new_pull_request_url(&self, branch: &str) -> String {
+        let repo = self.repo_url();
+        match self.kind {
+            ForgeKind::GitHub => format!("{}/compare/{}?expand=1", repo, encode_path(branch)),
+            ForgeKind::GitLab => format!(
+                "{}/-/merge_requests/new?merge_request%5Bsource_branch%5D={}",
+                repo,
+                encode(branch)
+            ),
+            ForgeKind::Gitea => format!("{}/compare/{}", repo, encode_path(branch)),
+            ForgeKind::Bitbucket => {
+                format!("{}/pull-requests/new?source={}", repo, encode(branch))
+            }
+        }
+    }
+
+    /// The form for a pull request from `branch` into `base`, filled in where the forge allows
+    pub fn compare_url(&self, branch: &str, base: &str, title: &str, body: &str) -> String {
+        let repo = self.repo_url();
+        let (title, body) = (encode(title), encode(body));
+        match self.kind {
+            ForgeKind::GitHub => format!(
+                "{}/compare/{}...{}?expand=1&title={}&body={}",
+                repo,
+                encode_path(base),
+                encode_path(branch),
+                title,
+                body
+            ),
+            ForgeKind::GitLab => format!(
+                "{}/-/merge_requests/new?merge_request%5Bsource_branch%5D={}\
+                 &merge_request%5Btarget_branch%5D={}&merge_request%5Btitle%5D={}\
+                 &merge_request%5Bdescription%5D={}",
+                repo,
+                encode(branch),
+                encode(base),
+                title,
+                body
+            ),
+            ForgeKind::Gitea => format!(
+                "{}/compare/{}...{}?title={}&body={}",
+                repo,
+                encode_path(base),
+                encode_path(branch),
+                title,
+                body
+            ),
+            // Bitbucket's form has no parameters for the text
+            ForgeKind::Bitbucket => format!(
+                "{}/pull-requests/new?source={}&dest={}",
+                repo,
+                encode(branch),
+                encode(base)
+            ),
+        }
+    }
+
+    /// Where the forge's REST API for this repository lives
+    fn api_url(&self) -> String {
+        match self.kind {
+            ForgeKind::GitHub if self.web == "https://github.com" => {
+                format!("https://api.github.com/repos/{}/{}", self.owner, self.repo)
+            }
+            ForgeKind::GitHub => format!("{}/api/v3/repos/{}/{}", self.web, self.owner, self.repo),
+            ForgeKind::GitLab => format!(
+                "{}/api/v4/projects/{}%2F{}",
+                self.web,
+                self.owner.replace('/', "%2F"),
+                self.repo
+            ),
+            ForgeKind::Gitea => format!("{}/api/v1/repos/{}/{}", self.web, self.owner, self.repo),
+            ForgeKind::Bitbucket => format!(
+                "https://api.bitbucket.org/2.0/repositories/{}/{}",
+                self.owner, self.repo
+            ),
+        }
+    }
+
+    /// Fetch the open pull requests, newest first as the forge lists them
+    ///
+    /// Blocks on the network, so run it off the UI thread.
+    pub fn list_pull_requests(&self) -> Result<Vec<PullRequest>, String> {
+        let api = self.api_url();
+        let (url, list_key) = match self.kind {
+            ForgeKind::GitHub | ForgeKind::Gitea => (format!("{}/pulls?state=open", api), None),
+            ForgeKind::GitLab => (format!("{}/merge_requests?state=opened", api), None),
+            ForgeKind::Bitbucket => (format!("{}/pullrequests?state=OPEN", api), Some("values")),
+        };
+        let json = get_json(&url, self.host())?;
+        let items = match list_key {
+            Some(key) => &json[key],
+            None => &json,
+        };
+        let items = items
+            .as_array()
+            .ok_or_else(|| "Unexpected pull request response".to_string())?;
+
+        let text = |value: &serde_json::Value| value.as_str().unwrap_or_default().to_string();
+        Ok(items
+            .iter()
+            .map(|item| match self.kind {
+                ForgeKind::GitHub | ForgeKind::Gitea => PullRequest {
+                    number: item["number"].as_u64().unwrap_or(0),
+                    title: text(&item["title"]),
+                    author: text(&item["user"]["login"]),
+                    branch: text(&item["head"]["ref"]),
+                    url: text(&item["html_url"]),
+                },
+                ForgeKind::GitLab => PullRequest {
+                    number: item["iid"].as_u64().unwrap_or(0),
+                    title: text(&item["title"]),
+                    author: text(&item["author"]["username"]),
+                    branch: text(&item["source_branch"]),
+                    url: text(&item["web_url"]),
+                },
+                ForgeKind::Bitbucket => PullRequest {
+                    number: item["id"].as_u64().unwrap_or(0),
+                    title: text(&item["title"]),
+                    author: text(&item["author"]["display_name"]),
+                    branch: text(&item["source"]["branch"]["name"]),
+                    url: text(&item["links"]["html"]["href"]),
+                },
+            })
+            .collect())
+    }
+
+    /// Open a pull request from `branch` into `base` and return its web page
+    ///
+    /// Blocks on the network, so run it off the UI thread.
+    pub fn create_pull_request(
+        &self,
+        branch: &str,
+        base: &str,
+        title: &str,
+        body: &str,
+    ) -> Result<String, String> {
+        let api = self.api_url();
+        let (url, request, link) = match self.kind {
+            ForgeKind::GitHub | ForgeKind::Gitea => (
+                format!("{}/pulls", api),
+                serde_json::json!({ "head": branch, "base": base, "title": title, "body": body }),
+                "/html_url",
+            ),
+            ForgeKind::GitLab => (
+                format!("{}/merge_requests", api),
+                serde_json::json!({
+                    "source_branch": branch,
+                    "target_branch": base,
+                    "title": title,
+                    "description": body,
+                }),
+                "/web_url",
+            ),
+            ForgeKind::Bitbucket => (
+                format!("{}/pullrequests", api),
+                serde_json::json!({
+                    "title": title,
+                    "description": body,
+                    "source": { "branch": { "name": branch } },
+                    "destination": { "branch": { "name": base } },
+                }),
+                "/links/html/href",
+            ),
+        };
+        let json = send_json(&url, Some(&request), token(self.host()).as_deref())?;
+        json.pointer(link)
+            .and_then(|link| link.as_str())
+            .map(str::to_string)
+            .ok_or_else(|| "Unexpected pull request response".to_string())
+    }
+
+    /// Fetch the combined CI result the forge has for commit `sha`
+    ///
+    /// Blocks on the network, so run it off the UI thread.
+    pub fn commit_status(&self, sha: &str) -> Result<CiStatus, String> {
+        let api = self.api_url();
+        match self.kind {
+            ForgeKind::GitHub | ForgeKind::Gitea => {
+                let json = get_json(&format!("{}/commits/{}/status", api, sha), self.host())?;
+                if json["total_count"].as_u64() == Some(0) {
+                    return Ok(CiStatus::None);
+                }
+                Ok(match json["state"].as_str().unwrap_or_default() {
+                    "success" => CiStatus::Success,
+                    "pending" => CiStatus::Pending,
+                    "failure" | "error" => CiStatus::Failure,
+                    _ => CiStatus::None,
+                })
+            }
+            ForgeKind::GitLab => {
+                // A commit's status is that of its latest pipeline
+                let json = get_json(&format!("{}/repository/commits/{}", api, sha), self.host())?;
+                Ok(match json["status"].as_str().unwrap_or_default() {
+                    "success" => CiStatus::Success,
+                    "created"
+                    | "waiting_for_resource"
+                    | "preparing"
+                    | "pending"
+                    | "running"
+                    | "scheduled" => CiStatus::Pending,
+                    "failed" | "canceled" => CiStatus::Failure,
+                    _ => CiStatus::None,
+                })
+            }
+            ForgeKind::Bitbucket => {
+                let json = get_json(&format!("{}/commit/{}/statuses", api, sha), self.host())?;
+                let states: Vec<&str> = json["values"]
+                    .as_array()
+                    .map(|values| values.iter().filter_map(|v| v["state"].as_str()).collect())
+                    .unwrap_or_default();
+                Ok(if states.is_empty() {
+                    CiStatus::None
+                } else if states.iter().any(|s| *s == "FAILED" || *s == "STOPPED") {
+                    CiStatus::Failure
+                } else if states.iter().any(|s| *s == "INPROGRESS") {
+                    CiStatus::Pending
+                } else {
+                    CiStatus::Success
+                })
+            }
+        }
+    }
+}
+
+/// A repository just created on a forge
+#[derive(Debug, Clone)]
+pub struct CreatedRepository {
+    pub ssh_url: String,
+    pub https_url: String,
+    pub web_url: String,
+}
+
+/// Forges gitix can create repositories on: the hosted GitHub and GitLab
+pub const CREATE_KINDS: [ForgeKind; 2] = [ForgeKind::GitHub, ForgeKind::GitLab];
+
+/// Create an empty repository called `name` for the owner of the API token
+///
+/// Blocks on the network, so run it off the UI thread.
+pub fn create_repository(
+    kind: ForgeKind,
+    name: &str,
+    private: bool,
+) -> Result<CreatedRepository, String> {
+    let host = kind.hosted_host();
+    let token = match token(host) {
+        Some(token) => token,
+        None => {
+            return Err(format!(
+                "Creating a repository needs a {} API token in {}",
+                kind.title(),
+                token_variable(host)
+            ))
+        }
+    };
+    let text = |value: &serde_json::Value| value.as_str().unwrap_or_default().to_string();
+    match kind {
+        ForgeKind::GitHub => {
+            let body = serde_json::json!({ "name": name, "private": private });
+            let json = send_json("https://api.github.com/user/repos", Some(&body), Some(&token))?;
+            Ok(CreatedRepository {
+                ssh_url: text(&json["ssh_url"]),
+                https_url: text(&json["clone_url"]),
+                web_url: text(&json["html_url"]),
+            })
+        }
+        ForgeKind::GitLab => {
+            let visibility = if private { "private" } else { "public" };
+            let body = serde_json::json!({ "name": name, "visibility": visibility });
+            let json = send_json("https://gitlab.com/api/v4/projects", Some(&body), Some(&token))?;
+            Ok(CreatedRepository {
+                ssh_url: text(&json["ssh_url_to_repo"]),
+                https_url: text(&json["http_url_to_repo"]),
+                web_url: text(&json["web_url"]),
+            })
+        }
+        _ => Err(format!(
+            "gitix can't create repositories on {}",
+            kind.title()
+        )),
+    }
+}
+
+/// GET `url` from the forge on `host`, with that host's token if there is one
+fn get_json(url: &str, host: &str) -> Result<serde_json::Value, String> {
+    send_json(url, None, token(host).as_deref())
+}
+
+/// GET `url`, or POST `body` to it, and parse the JSON response
+///
+/// Shells out to `curl` like the release check does. The bearer token is handed over on
+/// stdin as curl config, since other users can read a command line.
+fn send_json(
+    url: &str,
+    body: Option<&serde_json::Value>,
+    token: Option<&str>,
+) -> Result<serde_json::Value, String> {
+    let mut cmd = Command::new("curl");
+    cmd.args([
+        "-fsSL",
+        "--max-time",
+        "10",
+        "-H",
+        "Accept: application/json",
+        "-A",
+        concat!("gitix/", env!("CARGO_PKG_VERSION")),
+        "--config",
+        "-",
+    ]);
+    if let Some(body) = body {
+        cmd.args(["-H", "Content-Type: application/json", "--data-binary", &body.to_string()]);
+    }
+    let mut child = cmd
+        .arg(url)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run curl: {}", e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        if let Some(token) = token {
+            let header = format!("Authorization: Bearer {}", token)
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"");
+            writeln!(stdin, "header = \"{}\"", header)
+                .map_err(|e| format!("Failed to run curl: {}", e))?;
+        }
+        // Dropping stdin ends the config
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("Failed to run curl: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Forge request failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    serde_json::from_slice(&output.stdout).map_err(|e| format!("Unexpected forge response: {}", e))
+}
+
+/// The environment variable holding the API token for the forge on `host`,
+/// e.g. GITIX_FORGE_TOKEN_GITHUB_COM for github.com
+///
+/// A token is only ever sent to the host it is named after, so a remote URL pointing
+/// somewhere else can't collect it.
+pub fn token_variable(host: &str) -> String {
+    let host: String = host
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("GITIX_FORGE_TOKEN_{}", host)
+}
+
+/// The API token for the forge on `host`, if one is set
+fn token(host: &str) -> Option<String> {
+    std::env::var(token_variable(host))
+        .ok()
+        .filter(|token| !token.is_empty())
+}
+
+/// Whether an API token is set for the forge on `host`, without which nothing can be created there
+pub fn has_token(host: &str) -> bool {
+    token(host).is_some()
+}
+
+/// The variable holding the token for repositories created on `kind`'s hosted service
+pub fn create_token_variable(kind: ForgeKind) -> String {
+    token_variable(kind.hosted_host())
+}
+
+/// Percent-encode `text` for a URL query
+fn encode(text: &str) -> String {
+    let mut out = String::new();
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(byte as char)
+            }
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    out
+}
+
+/// Percent-encode a branch name for a URL path, keeping its `/` separators
+fn encode_path(text: &str) -> String {
+    text.split('/').map(encode).collect::<Vec<_>>().join("/")
+}
+
+/// Open `url` in the default web browser without waiting for it
+pub fn open_in_browser(url: &str) -> std::io::Result<()> {
+    let mut cmd = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(windows) {
+        Command::new("explorer")
+    } else {
+        Command::new("xdg-open")
+    };
+    // The browser outlives gitix, and its output would garble the screen
+    cmd.arg(url)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .map(|_| ())
+}
@@ -1,4 +1,5 @@
 use gix::Repository;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone)]
@@ -25,7 +26,14 @@ pub struct RemoteStatus {
     pub url: String,
     pub ahead: usize,
     pub behind: usize,
-    pub last_fetch: Option<String>,
+    pub last_fetch: Option<chrono::DateTime<chrono::Local>>,
+}
+
+/// A GitHub-style fork setup: origin is the user's fork, upstream the source project
+#[derive(Debug, Clone)]
+pub struct ForkInfo {
+    pub upstream_url: String,
+    pub default_branch: String, // Upstream's default branch (usually main)
 }
 
 #[derive(Debug, Clone)]
@@ -36,12 +44,30 @@ pub struct SyncOperation {
     pub timestamp: std::time::SystemTime,
 }
 
+/// How far a fetch, push or pull has got, as libgit2's transfer callbacks
+/// and the rebase loop report it
+#[derive(Debug, Clone)]
+pub struct TransferProgress {
+    // "Receiving objects", "Resolving deltas", "Writing objects", "Applying" or "Merging"
+    pub stage: &'static str,
+    pub current: usize,
+    pub total: usize,
+    pub bytes: usize,           // Transferred so far
+    pub detail: Option<String>, // What the step is on, e.g. the commit being applied
+}
+
+/// Where network operations send their progress, if anyone is watching
+pub type ProgressSender = std::sync::mpsc::Sender<TransferProgress>;
+
 #[derive(Debug, Clone)]
 pub enum SyncOperationType {
     Fetch,
     Pull,
     Push,
     Refresh,
+    ForkSync,
+    CherryPick,
+    Merge,
 }
 
 #[derive(Debug, Clone)]
@@ -137,11 +163,85 @@ impl FileStatusType {
     }
 }
 
+/// The sections of the status list, in the order `git status` shows them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StatusGroup {
+    Staged,
+    Changes,
+    Untracked,
+}
+
+impl StatusGroup {
+    pub fn title(&self) -> &'static str {
+        match self {
+            StatusGroup::Staged => "Staged",
+            StatusGroup::Changes => "Changes",
+            StatusGroup::Untracked => "Untracked",
+        }
+    }
+}
+
+impl GitFileStatus {
+    /// The section the file is listed under; partly staged files count as staged
+    pub fn group(&self) -> StatusGroup {
+        if self.staged {
+            StatusGroup::Staged
+        } else if matches!(self.status, FileStatusType::Untracked) {
+            StatusGroup::Untracked
+        } else {
+            StatusGroup::Changes
+        }
+    }
+}
+
+/// Put a status list in a stable order: by section, then directory, then file name
+pub fn sort_status(files: &mut [GitFileStatus]) {
+    files.sort_by(|a, b| {
+        a.group()
+            .cmp(&b.group())
+            .then_with(|| a.path.parent().cmp(&b.path.parent()))
+            .then_with(|| a.path.file_name().cmp(&b.path.file_name()))
+    });
+}
+
 pub fn init_repo() -> Result<(), gix::init::Error> {
     gix::init(".")?;
     Ok(())
 }
 
+/// Branch an unborn HEAD points at, e.g. `main` right after init
+pub fn unborn_branch_name() -> Option<String> {
+    let repo = git2::Repository::open(".").ok()?;
+    let head = repo.find_reference("HEAD").ok()?;
+    head.symbolic_target()
+        .and_then(|target| target.strip_prefix("refs/heads/"))
+        .map(str::to_string)
+}
+
+/// Name the branch of a repository that has no commits yet
+pub fn set_initial_branch(branch: &str) -> Result<(), GitError> {
+    if !git2::Branch::name_is_valid(branch)? {
+        return Err(GitError::Other(format!("'{}' is not a valid branch name", branch)));
+    }
+    let repo = git2::Repository::open(".")?;
+    repo.set_head(&format!("refs/heads/{}", branch))?;
+    Ok(())
+}
+
+/// Create the first commit of a new repository, empty or with every non-ignored file
+pub fn create_initial_commit(include_files: bool) -> Result<(), GitError> {
+    let repo = git2::Repository::open(".")?;
+    let mut index = repo.index()?;
+    if include_files {
+        index.add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)?;
+        index.write()?;
+    }
+    let tree = repo.find_tree(index.write_tree()?)?;
+    let signature = repo.signature()?;
+    repo.commit(Some("HEAD"), &signature, &signature, "Initial commit", &tree, &[])?;
+    Ok(())
+}
+
 /// Get git status using pure gix implementation (PHASE 1: PURE GIX IMPLEMENTATION ✅)
 ///
 /// This function now uses pure gix for both staged and unstaged changes:
@@ -151,15 +251,20 @@ pub fn init_repo() -> Result<(), gix::init::Error> {
 /// The gix 0.72 API provides:
 /// - `repo.status().into_index_worktree_iter()` for unstaged changes ✅
 /// - `repo.head_commit() -> index_from_tree() -> open_index() -> diff` for staged changes ✅
+///
+/// Files come in `sort_status` order.
 pub fn get_git_status() -> Result<Vec<GitFileStatus>, Box<dyn std::error::Error>> {
     // Try gix first, but fall back to git command if it fails
-    match get_git_status_pure_gix() {
-        Ok(status) => Ok(status),
+    let mut files = match get_git_status_pure_gix() {
+        Ok(status) => status,
         Err(_e) => {
             // Silent fallback to git command - this is expected for some configurations
-            get_git_status_fallback()
+            get_git_status_fallback()?
         }
-    }
+    };
+    // Neither yields a dependable order (gix walks the tree in parallel)
+    sort_status(&mut files);
+    Ok(files)
 }
 
 /// Get git status using pure gix implementation (PHASE 1: PURE GIX IMPLEMENTATION ✅)
@@ -317,9 +422,32 @@ fn get_unstaged_changes_gix(
 ) -> Result<Vec<GitFileStatus>, Box<dyn std::error::Error>> {
     let status = repo.status(gix::progress::Discard)?;
     let mut files = Vec::new();
+    let depth = crate::config::get_untracked_depth()
+        .ok()
+        .flatten()
+        .unwrap_or(DEFAULT_UNTRACKED_DEPTH);
 
     for item in status.into_index_worktree_iter(Vec::<gix::bstr::BString>::new())? {
         let item = item?;
+
+        // gix reports a new folder as one entry; list what is in it instead
+        if let gix::status::index_worktree::Item::DirectoryContents { entry, .. } = &item {
+            if entry.disk_kind == Some(gix::dir::entry::Kind::Directory) && depth > 0 {
+                let dir = entry.rela_path.to_string();
+                for path in expand_untracked_dir(dir.trim_end_matches('/'), depth)? {
+                    let path = PathBuf::from(path);
+                    let file_size = std::fs::metadata(&path).ok().map(|m| m.len());
+                    files.push(GitFileStatus {
+                        path,
+                        status: FileStatusType::Untracked,
+                        file_size,
+                        staged: false,
+                    });
+                }
+                continue;
+            }
+        }
+
         let path = PathBuf::from(item.rela_path().to_string());
         let file_size = std::fs::metadata(&path).ok().map(|m| m.len());
 
@@ -343,6 +471,51 @@ fn get_unstaged_changes_gix(
     Ok(files)
 }
 
+/// How many directory levels of an untracked folder the status lists file by file by default
+pub const DEFAULT_UNTRACKED_DEPTH: usize = 3;
+
+/// The untracked files under `dir`, leaving out ignored ones
+///
+/// Files more than `depth` directory levels below `dir` are folded back into one row for the
+/// folder at that level, so a new `node_modules`-sized tree doesn't flood the list.
+fn expand_untracked_dir(dir: &str, depth: usize) -> Result<Vec<String>, GitError> {
+    let repo = git2::Repository::open(".")?;
+    let mut options = git2::StatusOptions::new();
+    options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .disable_pathspec_match(true)
+        .pathspec(dir);
+
+    let mut paths: Vec<String> = Vec::new();
+    for entry in repo.statuses(Some(&mut options))?.iter() {
+        if !entry.status().contains(git2::Status::WT_NEW) {
+            continue;
+        }
+        let path = match entry.path() {
+            Some(path) => path,
+            None => continue,
+        };
+        let rest = match path.strip_prefix(dir).and_then(|rest| rest.strip_prefix('/')) {
+            Some(rest) => rest,
+            None => continue,
+        };
+        let parts: Vec<&str> = rest.split('/').collect();
+        let row = if parts.len() > depth {
+            format!("{}/{}", dir, parts[..depth].join("/"))
+        } else {
+            path.to_string()
+        };
+        if !paths.contains(&row) {
+            paths.push(row);
+        }
+    }
+    if paths.is_empty() {
+        paths.push(dir.to_string());
+    }
+    Ok(paths)
+}
+
 /// Fallback to git command if gix fails (TEMPORARY)
 fn get_git_status_fallback() -> Result<Vec<GitFileStatus>, Box<dyn std::error::Error>> {
     let output = std::process::Command::new("git")
@@ -359,6 +532,10 @@ fn get_git_status_fallback() -> Result<Vec<GitFileStatus>, Box<dyn std::error::E
 
     let status_output = String::from_utf8_lossy(&output.stdout);
     let mut files = Vec::new();
+    let depth = crate::config::get_untracked_depth()
+        .ok()
+        .flatten()
+        .unwrap_or(DEFAULT_UNTRACKED_DEPTH);
 
     // Parse git status output
     for line in status_output.split('\0') {
@@ -374,6 +551,21 @@ fn get_git_status_fallback() -> Result<Vec<GitFileStatus>, Box<dyn std::error::E
         let worktree_status = line.chars().nth(1).unwrap_or(' ');
         let file_path = &line[3..];
 
+        // A new folder comes as one "dir/" entry; list what is in it instead
+        if line.starts_with("??") && file_path.ends_with('/') && depth > 0 {
+            for path in expand_untracked_dir(file_path.trim_end_matches('/'), depth)? {
+                let path = PathBuf::from(path);
+                let file_size = std::fs::metadata(&path).ok().map(|m| m.len());
+                files.push(GitFileStatus {
+                    path,
+                    status: FileStatusType::Untracked,
+                    file_size,
+                    staged: false,
+                });
+            }
+            continue;
+        }
+
         let path = PathBuf::from(file_path);
         let file_size = std::fs::metadata(&path).ok().map(|m| m.len());
 
@@ -431,13 +623,20 @@ pub fn stage_file(file_path: &str) -> Result<(), GitError> {
 }
 
 /// Stage multiple files using git2-rs (PRODUCTION READY ✅)
+///
+/// Files deleted from the working tree are removed from the index.
 pub fn stage_files(file_paths: &[&str]) -> Result<(), GitError> {
     let repo = git2::Repository::open(".")?;
     let mut index = repo.index()?;
+    let workdir = repo.workdir().map(Path::to_path_buf).unwrap_or_default();
 
     // Stage all files
     for file_path in file_paths {
-        index.add_path(Path::new(file_path))?;
+        if workdir.join(file_path).symlink_metadata().is_ok() {
+            index.add_path(Path::new(file_path))?;
+        } else {
+            index.remove_path(Path::new(file_path))?;
+        }
     }
 
     // Write the index to persist changes
@@ -446,6 +645,60 @@ pub fn stage_files(file_paths: &[&str]) -> Result<(), GitError> {
     Ok(())
 }
 
+/// Every path with changes that aren't staged, untracked files inside new directories included
+pub fn unstaged_paths() -> Result<Vec<String>, GitError> {
+    let repo = git2::Repository::open(".")?;
+    let mut options = git2::StatusOptions::new();
+    options.include_untracked(true).recurse_untracked_dirs(true);
+    let changed = git2::Status::WT_NEW
+        | git2::Status::WT_MODIFIED
+        | git2::Status::WT_DELETED
+        | git2::Status::WT_TYPECHANGE
+        | git2::Status::WT_RENAMED;
+    let mut paths: Vec<String> = repo
+        .statuses(Some(&mut options))?
+        .iter()
+        .filter(|entry| entry.status().intersects(changed))
+        .filter_map(|entry| entry.path().map(str::to_string))
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+/// The paths matching a pathspec, as `git add <pathspec>` would pick them
+///
+/// `pattern` holds one or more pathspecs separated by spaces. Globs match
+/// across directories ("*.rs" and "src/**/*.rs" both reach src/tui/mod.rs),
+/// a directory matches everything under it and ":!" or ":^" excludes paths.
+pub fn match_pathspec(pattern: &str, paths: &[String]) -> Result<Vec<String>, GitError> {
+    let mut specs: Vec<String> = Vec::new();
+    for spec in pattern.split_whitespace() {
+        // libgit2 spells an exclusion with a plain "!"
+        let spec = match spec.strip_prefix(":!").or_else(|| spec.strip_prefix(":^")) {
+            Some(excluded) => format!("!{}", excluded),
+            None => spec.to_string(),
+        };
+        // libgit2 reads "**/" as a literal star and slash; git also lets it stand for no directory
+        let collapsed = spec.replace("**/", "");
+        if collapsed != spec && collapsed.trim_start_matches('!') != "" {
+            specs.push(collapsed);
+        }
+        specs.push(spec);
+    }
+    // The first spec that matches decides, so exclusions go first; only exclusions, like git,
+    // means everything else
+    specs.sort_by_key(|spec| !spec.starts_with('!'));
+    if specs.iter().all(|spec| spec.starts_with('!')) {
+        specs.push("*".to_string());
+    }
+    let pathspec = git2::Pathspec::new(specs)?;
+    Ok(paths
+        .iter()
+        .filter(|path| pathspec.matches_path(Path::new(path), git2::PathspecFlags::DEFAULT))
+        .cloned()
+        .collect())
+}
+
 /// Stage all modified and new files using git2-rs (PRODUCTION READY ✅)
 pub fn stage_all_files() -> Result<(), GitError> {
     let repo = git2::Repository::open(".")?;
@@ -649,6 +902,363 @@ pub fn is_file_staged(file_path: &str) -> Result<bool, GitError> {
     Ok(false)
 }
 
+/// What changed in one file between HEAD and the working tree
+#[derive(Debug, Clone, Default)]
+pub struct FileDiffDetail {
+    pub old_mode: Option<u32>, // None when the file is new
+    pub new_mode: Option<u32>, // None when the file was deleted
+    pub insertions: usize,
+    pub deletions: usize,
+    pub binary: bool,
+    pub encoding: Option<crate::encoding::TextEncoding>, // Of the working tree file; None when binary or deleted
+}
+
+/// Diff of one file from HEAD to the working tree, covering staged and unstaged changes
+fn head_to_workdir_patch<'r>(
+    repo: &'r git2::Repository,
+    file_path: &str,
+) -> Result<Option<git2::Patch<'r>>, GitError> {
+    // No HEAD yet (initial commit): everything is new
+    let head_tree = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
+
+    let mut opts = git2::DiffOptions::new();
+    opts.pathspec(file_path)
+        .disable_pathspec_match(true)
+        .include_untracked(true)
+        .show_untracked_content(true)
+        .recurse_untracked_dirs(true);
+    let diff = repo.diff_tree_to_workdir_with_index(head_tree.as_ref(), Some(&mut opts))?;
+
+    Ok(git2::Patch::from_diff(&diff, 0)?)
+}
+
+/// Mode change and diff stat of one file, HEAD against the working tree (staged and unstaged)
+pub fn get_file_diff_detail(file_path: &str) -> Result<FileDiffDetail, GitError> {
+    let repo = git2::Repository::open(".")?;
+    let mut detail = FileDiffDetail::default();
+    if let Some(patch) = head_to_workdir_patch(&repo, file_path)? {
+        let delta = patch.delta();
+        let mode = |file: git2::DiffFile| match file.mode() {
+            git2::FileMode::Unreadable => None,
+            mode => Some(u32::from(mode)),
+        };
+        detail.old_mode = mode(delta.old_file());
+        detail.new_mode = mode(delta.new_file());
+        detail.binary = delta.flags().is_binary();
+        let (_, insertions, deletions) = patch.line_stats()?;
+        detail.insertions = insertions;
+        detail.deletions = deletions;
+    }
+    detail.encoding = crate::encoding::detect_file(Path::new(file_path));
+
+    Ok(detail)
+}
+
+/// Unified diff of one file, either staged (HEAD to index) or unstaged (index to working tree)
+///
+/// Returns the hunk headers and `+`/`-`/` ` prefixed lines without the file
+/// header; untracked files come out as all additions. Empty when that side has
+/// no changes.
+pub fn get_file_diff(file_path: &str, staged: bool) -> Result<Vec<String>, GitError> {
+    let repo = git2::Repository::open(".")?;
+    let mut opts = git2::DiffOptions::new();
+    opts.pathspec(file_path).disable_pathspec_match(true);
+    let diff = if staged {
+        // No HEAD yet (initial commit): everything staged is new
+        let head_tree = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
+        repo.diff_tree_to_index(head_tree.as_ref(), None, Some(&mut opts))?
+    } else {
+        opts.include_untracked(true)
+            .show_untracked_content(true)
+            .recurse_untracked_dirs(true);
+        repo.diff_index_to_workdir(None, Some(&mut opts))?
+    };
+
+    let mut lines = Vec::new();
+    diff.print(git2::DiffFormat::Patch, |_, _, line| {
+        let content = crate::encoding::decode_lossy(line.content());
+        let content = content.trim_end_matches(['\n', '\r']);
+        match line.origin() {
+            '+' | '-' | ' ' => lines.push(format!("{}{}", line.origin(), content)),
+            // Hunk headers, "Binary files differ" and "\ No newline at end of file"
+            'H' | 'B' | '=' | '>' | '<' => lines.push(content.to_string()),
+            _ => {} // File header: the pane already names the file
+        }
+        true
+    })?;
+    Ok(lines)
+}
+
+/// Encodings of the staged text files that aren't plain ASCII, for spotting a commit that mixes them
+pub fn staged_encodings() -> Result<Vec<(String, crate::encoding::TextEncoding)>, GitError> {
+    let repo = git2::Repository::open(".")?;
+    let head_tree = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
+    let diff = repo.diff_tree_to_index(head_tree.as_ref(), None, None)?;
+    let mut files = Vec::new();
+    for delta in diff.deltas() {
+        let new_file = delta.new_file();
+        // Deleted files have no new blob, submodules no blob at all
+        let blob = match repo.find_blob(new_file.id()) {
+            Ok(blob) => blob,
+            Err(_) => continue,
+        };
+        if blob.content().is_ascii() {
+            continue;
+        }
+        if let (Some(encoding), Some(path)) =
+            (crate::encoding::detect(blob.content()), new_file.path())
+        {
+            files.push((path.display().to_string(), encoding));
+        }
+    }
+    Ok(files)
+}
+
+/// Stage one hunk of a file's unstaged changes, or unstage one of its staged hunks
+///
+/// `hunk` counts the hunks of that side in the order `get_file_diff` lists them.
+/// Staging applies the index-to-working-tree hunk to the index, the way
+/// `git add -p` does; unstaging applies the HEAD-to-index hunk in reverse.
+pub fn apply_hunk_to_index(file_path: &str, hunk: usize, unstage: bool) -> Result<(), GitError> {
+    let repo = git2::Repository::open(".")?;
+    let mut index = repo.index()?;
+    if !unstage && index.get_path(Path::new(file_path), 0).is_none() {
+        // An untracked file's one hunk is the whole file, and apply can't create index entries
+        index.add_path(Path::new(file_path))?;
+        index.write()?;
+        return Ok(());
+    }
+    let mut opts = git2::DiffOptions::new();
+    opts.pathspec(file_path).disable_pathspec_match(true);
+    let diff = if unstage {
+        opts.reverse(true);
+        let head_tree = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
+        repo.diff_tree_to_index(head_tree.as_ref(), None, Some(&mut opts))?
+    } else {
+        opts.include_untracked(true)
+            .show_untracked_content(true)
+            .recurse_untracked_dirs(true);
+        repo.diff_index_to_workdir(None, Some(&mut opts))?
+    };
+
+    let mut seen = 0;
+    let mut apply_opts = git2::ApplyOptions::new();
+    apply_opts.hunk_callback(|candidate| {
+        let wanted = candidate.is_some() && seen == hunk;
+        seen += 1;
+        wanted
+    });
+    repo.apply(&diff, git2::ApplyLocation::Index, Some(&mut apply_opts))?;
+    Ok(())
+}
+
+/// First line of the working tree file that differs from HEAD (1-based)
+///
+/// Points at the first added line, or at the line following a removal when
+/// lines were deleted first. None when the file has no text changes.
+pub fn first_changed_line(file_path: &str) -> Result<Option<usize>, GitError> {
+    let repo = git2::Repository::open(".")?;
+    let patch = match head_to_workdir_patch(&repo, file_path)? {
+        Some(patch) if patch.num_hunks() > 0 => patch,
+        _ => return Ok(None),
+    };
+
+    let (hunk, line_count) = patch.hunk(0)?;
+    let mut next_line = hunk.new_start() as usize;
+    for idx in 0..line_count {
+        let line = patch.line_in_hunk(0, idx)?;
+        match (line.origin(), line.new_lineno()) {
+            ('+', Some(lineno)) => return Ok(Some(lineno as usize)),
+            ('-', _) => return Ok(Some(next_line.max(1))),
+            (_, Some(lineno)) => next_line = lineno as usize + 1,
+            _ => {}
+        }
+    }
+    Ok(Some(next_line.max(1)))
+}
+
+/// Throw away the changes to one file, staged and unstaged, like `git checkout HEAD -- <file>`
+///
+/// Files that don't exist in HEAD (untracked or newly added) are removed from the
+/// index and moved to the trash, or deleted when `permanent`.
+pub fn discard_file_changes(file_path: &str, permanent: bool) -> Result<(), GitError> {
+    let repo = git2::Repository::open(".")?;
+    let head_tree = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
+    let in_head = head_tree
+        .as_ref()
+        .is_some_and(|tree| tree.get_path(Path::new(file_path)).is_ok());
+
+    if !in_head {
+        let mut index = repo.index()?;
+        if index.get_path(Path::new(file_path), 0).is_some() {
+            index.remove_path(Path::new(file_path))?;
+            index.write()?;
+        }
+        let path = Path::new(file_path);
+        if path.symlink_metadata().is_ok() {
+            crate::trash::remove(path, permanent)?;
+        }
+        return Ok(());
+    }
+
+    let head_commit = repo.head()?.peel_to_commit()?;
+    repo.reset_default(Some(head_commit.as_object()), [file_path].iter())?;
+    let mut checkout = git2::build::CheckoutBuilder::new();
+    checkout.force().path(file_path);
+    repo.checkout_head(Some(&mut checkout))?;
+
+    Ok(())
+}
+
+/// Bring a deleted file back from HEAD, unstaging the deletion if it was staged
+///
+/// Refuses when something now exists at that path, so nothing gets overwritten.
+pub fn restore_deleted_file(file_path: &str) -> Result<(), GitError> {
+    if Path::new(file_path).exists() {
+        return Err(GitError::Other(format!(
+            "{} exists in the working tree; move it away first",
+            file_path
+        )));
+    }
+    // Nothing is at the path, so nothing can end up deleted
+    discard_file_changes(file_path, true)
+}
+
+/// Untracked paths `git clean -d` would remove, optionally with ignored ones (`-x`)
+///
+/// Untracked directories are listed once, with a trailing slash, like `git clean -n`.
+pub fn list_cleanable(include_ignored: bool) -> Result<Vec<String>, GitError> {
+    let repo = git2::Repository::open(".")?;
+    let mut opts = git2::StatusOptions::new();
+    opts.include_untracked(true)
+        .recurse_untracked_dirs(false)
+        .include_ignored(include_ignored)
+        .recurse_ignored_dirs(false);
+
+    let mut paths: Vec<String> = repo
+        .statuses(Some(&mut opts))?
+        .iter()
+        .filter(|entry| {
+            let status = entry.status();
+            status.is_wt_new() || (include_ignored && status.is_ignored())
+        })
+        .filter_map(|entry| entry.path().map(str::to_string))
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+/// Delete untracked (and, if asked, ignored) paths, like `git clean -d [-x]`
+///
+/// Only paths that are still cleanable are touched, so a file that was
+/// staged or committed since the preview was built survives. Paths go to the
+/// system trash unless `permanent`. Returns how many paths were removed.
+pub fn clean(paths: &[String], include_ignored: bool, permanent: bool) -> Result<usize, GitError> {
+    let cleanable = list_cleanable(include_ignored)?;
+    let mut removed = 0;
+    for path in paths.iter().filter(|path| cleanable.contains(path)) {
+        crate::trash::remove(Path::new(path), permanent)?;
+        removed += 1;
+    }
+    Ok(removed)
+}
+
+/// Index flags that make git stop looking at a tracked file's working copy
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IndexFlag {
+    AssumeUnchanged, // `git update-index --assume-unchanged`: a performance hint
+    SkipWorktree,    // `git update-index --skip-worktree`: keep local edits out of status
+}
+
+impl IndexFlag {
+    pub fn as_description(&self) -> &'static str {
+        match self {
+            IndexFlag::AssumeUnchanged => "Assume unch.",
+            IndexFlag::SkipWorktree => "Skip worktree",
+        }
+    }
+
+    pub fn as_option(&self) -> &'static str {
+        match self {
+            IndexFlag::AssumeUnchanged => "--assume-unchanged",
+            IndexFlag::SkipWorktree => "--skip-worktree",
+        }
+    }
+}
+
+// Bits of a git index entry (see git's Documentation/gitformat-index)
+const INDEX_ENTRY_VALID: u16 = 0x8000; // assume-unchanged, in `flags`
+const INDEX_ENTRY_EXTENDED: u16 = 0x4000; // `flags_extended` is in use, in `flags`
+const INDEX_ENTRY_SKIP_WORKTREE: u16 = 0x4000; // in `flags_extended`
+
+/// Tracked files with assume-unchanged or skip-worktree set, sorted by path
+pub fn list_flagged_files() -> Result<Vec<(String, IndexFlag)>, GitError> {
+    let repo = git2::Repository::open(".")?;
+    let index = repo.index()?;
+
+    let mut flagged = Vec::new();
+    for entry in index.iter() {
+        let path = String::from_utf8_lossy(&entry.path).to_string();
+        if entry.flags_extended & INDEX_ENTRY_SKIP_WORKTREE != 0 {
+            flagged.push((path, IndexFlag::SkipWorktree));
+        } else if entry.flags & INDEX_ENTRY_VALID != 0 {
+            flagged.push((path, IndexFlag::AssumeUnchanged));
+        }
+    }
+    flagged.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(flagged)
+}
+
+/// Set or clear assume-unchanged / skip-worktree on a tracked file
+pub fn set_index_flag(file_path: &str, flag: IndexFlag, on: bool) -> Result<(), GitError> {
+    let repo = git2::Repository::open(".")?;
+    let mut index = repo.index()?;
+    let mut entry = index
+        .get_path(Path::new(file_path), 0)
+        .ok_or_else(|| GitError::Other(format!("{} is not tracked", file_path)))?;
+
+    match flag {
+        IndexFlag::AssumeUnchanged if on => entry.flags |= INDEX_ENTRY_VALID,
+        IndexFlag::AssumeUnchanged => entry.flags &= !INDEX_ENTRY_VALID,
+        IndexFlag::SkipWorktree if on => entry.flags_extended |= INDEX_ENTRY_SKIP_WORKTREE,
+        IndexFlag::SkipWorktree => entry.flags_extended &= !INDEX_ENTRY_SKIP_WORKTREE,
+    }
+    if entry.flags_extended != 0 {
+        entry.flags |= INDEX_ENTRY_EXTENDED;
+    } else {
+        entry.flags &= !INDEX_ENTRY_EXTENDED;
+    }
+
+    index.add(&entry)?;
+    index.write()?;
+    Ok(())
+}
+
+/// Add a path to the repository's top-level .gitignore
+pub fn ignore_path(file_path: &str) -> Result<(), GitError> {
+    let repo = git2::Repository::open(".")?;
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| GitError::Other("bare repository has no .gitignore".to_string()))?;
+    let gitignore = workdir.join(".gitignore");
+
+    let existing = std::fs::read_to_string(&gitignore).unwrap_or_default();
+    let entry = format!("/{}", file_path.trim_start_matches('/'));
+    if existing.lines().any(|line| line.trim() == entry) {
+        return Ok(());
+    }
+
+    let mut contents = existing;
+    if !contents.is_empty() && !contents.ends_with('\n') {
+        contents.push('\n');
+    }
+    contents.push_str(&entry);
+    contents.push('\n');
+    std::fs::write(gitignore, contents)?;
+
+    Ok(())
+}
+
 /// Get detailed git status using git2-rs (UTILITY FUNCTION ✅)
 ///
 /// This provides a git2-rs based status check that can be used alongside
@@ -712,24 +1322,102 @@ pub fn get_git_status_git2() -> Result<Vec<GitFileStatus>, GitError> {
     Ok(files)
 }
 
-/// Commit changes using git command (PHASE 2: TO BE MIGRATED TO PURE GIX)
+/// Commit what is staged with `message`, like `git commit -m`
 ///
-/// This function currently uses the git command line tool for compatibility.
-/// It will be migrated to use pure gix implementation in Phase 2 of the migration plan.
+/// The commit is made with libgit2, so no git binary is needed and nothing is
+/// printed while the TUI owns the terminal: the index is written out as a tree,
+/// committed on top of HEAD (or as the first commit of an unborn branch) with the
+/// configured author and committer, and HEAD's branch is moved to it. A merge in
+/// progress becomes a merge commit with MERGE_HEAD as its second parent.
 ///
-/// The migration will use:
-/// - `gix::Repository::commit()` to create commits
-/// - `gix::Repository::index()` to access the current index
-/// - Pure Rust implementation without external git dependency
-pub fn commit(message: &str) -> Result<(), Box<dyn std::error::Error>> {
-    // TODO: PHASE 2 MIGRATION - Replace with pure gix implementation
-    // Current implementation uses git command for compatibility
+/// Hooks run as `git commit` runs them: pre-commit and commit-msg (skipped with
+/// `no_verify`, like `--no-verify`), prepare-commit-msg, and post-commit once the
+/// commit exists. Signed commits (commit.gpgSign) are still handed to git, which
+/// knows how to reach gpg or ssh-agent.
+pub fn commit(message: &str, no_verify: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let mut repo = git2::Repository::open(".")?;
+    if repo.config()?.get_bool("commit.gpgsign").unwrap_or(false) {
+        return commit_with_git(message, no_verify);
+    }
+    let mut merge_heads = Vec::new();
+    if repo.state() == git2::RepositoryState::Merge {
+        repo.mergehead_foreach(|oid| {
+            merge_heads.push(*oid);
+            true
+        })?;
+    }
 
-    let output = std::process::Command::new("git")
-        .arg("commit")
-        .arg("-m")
-        .arg(message)
-        .output()?;
+    if !no_verify {
+        crate::hooks::run_hook("pre-commit", &[])?;
+    }
+    // The message goes through COMMIT_EDITMSG so hooks can read and rewrite it
+    let message_file = repo.path().join("COMMIT_EDITMSG");
+    std::fs::write(&message_file, git2::message_prettify(message, None)?)?;
+    let message_path = message_file.to_string_lossy();
+    crate::hooks::run_hook("prepare-commit-msg", &[&message_path, "message"])?;
+    if !no_verify {
+        crate::hooks::run_hook("commit-msg", &[&message_path])?;
+    }
+    let message = git2::message_prettify(std::fs::read_to_string(&message_file)?, None)?;
+    if message.trim().is_empty() {
+        return Err("Commit message cannot be empty".into());
+    }
+
+    // Read the index only now: pre-commit may have staged more (a formatter, say)
+    let mut index = repo.index()?;
+    if index.has_conflicts() {
+        return Err("Resolve the conflicted files before committing".into());
+    }
+    let tree = repo.find_tree(index.write_tree()?)?;
+
+    let signature = repo.signature().map_err(|_| {
+        "Set your name and email (user.name and user.email) in Settings before committing"
+    })?;
+    let head = match repo.head() {
+        Ok(head) => Some(head.peel_to_commit()?),
+        Err(e) if e.code() == git2::ErrorCode::UnbornBranch => None,
+        Err(e) => return Err(e.into()),
+    };
+    let mut parents: Vec<git2::Commit> = head.into_iter().collect();
+    for oid in merge_heads {
+        parents.push(repo.find_commit(oid)?);
+    }
+    if parents.len() == 1 && parents[0].tree_id() == tree.id() {
+        return Err("Nothing to commit: the staged files match the last commit".into());
+    }
+
+    let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+    let oid = repo.commit(None, &signature, &signature, &message, &tree, &parent_refs)?;
+
+    // Move HEAD's branch, with the reflog entry git would write
+    let kind = match parents.len() {
+        0 => "commit (initial)",
+        1 => "commit",
+        _ => "commit (merge)",
+    };
+    let summary = message.lines().next().unwrap_or_default();
+    let reflog_message = format!("{}: {}", kind, summary);
+    let head_ref = repo.find_reference("HEAD")?;
+    match head_ref.symbolic_target() {
+        Some(branch) => repo.reference(branch, oid, true, &reflog_message)?,
+        None => repo.reference("HEAD", oid, true, &reflog_message)?,
+    };
+    repo.cleanup_state()?; // MERGE_HEAD, MERGE_MSG and the like
+    let _ = std::fs::remove_file(repo.path().join("AUTO_MERGE")); // Newer than libgit2
+
+    // Like git, a failing post-commit hook doesn't undo the commit
+    let _ = crate::hooks::run_hook("post-commit", &[]);
+    Ok(())
+}
+
+/// Commit through the git binary, for what libgit2 can't do on its own (signing)
+fn commit_with_git(message: &str, no_verify: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = std::process::Command::new("git");
+    cmd.arg("commit").arg("-m").arg(message);
+    if no_verify {
+        cmd.arg("--no-verify");
+    }
+    let output = cmd.output()?;
 
     if !output.status.success() {
         return Err(format!(
@@ -738,519 +1426,3959 @@ pub fn commit(message: &str) -> Result<(), Box<dyn std::error::Error>> {
         )
         .into());
     }
+    Ok(())
+}
+
+/// Rewrite the HEAD commit with `message` and whatever is staged, like `git commit --amend`
+pub fn amend_commit(message: &str, no_verify: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = std::process::Command::new("git");
+    cmd.arg("commit").arg("--amend").arg("-m").arg(message);
+    if no_verify {
+        cmd.arg("--no-verify");
+    }
+    let output = cmd.output()?;
 
-    println!("Created commit successfully");
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to amend commit: {}",
+            String::from_utf8_lossy(&output.stderr)
+        )
+        .into());
+    }
     Ok(())
 }
 
-pub fn status() -> Result<Vec<GitFileStatus>, Box<dyn std::error::Error>> {
-    get_git_status()
+/// Full message of the HEAD commit, without trailing newlines
+pub fn head_message() -> Result<String, GitError> {
+    let repo = git2::Repository::open(".")?;
+    let commit = repo.head()?.peel_to_commit()?;
+    Ok(commit.message().unwrap_or_default().trim_end().to_string())
 }
 
-pub fn push() -> Result<(), Box<dyn std::error::Error>> {
-    // TODO: Implement git push logic
-    Ok(())
+/// Start of the subject of every WIP quick-save, so it can be found again
+pub const WIP_MARKER: &str = "WIP (gitix)";
+
+/// Where a WIP quick-save puts the work
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WipMode {
+    Commit, // A commit on the current branch, undone with a soft reset
+    Stash,  // A stash entry, popped to resume
 }
 
-pub fn pull_rebase() -> Result<(), Box<dyn std::error::Error>> {
-    // TODO: Implement git pull --rebase logic
-    Ok(())
+/// A WIP quick-save that can be resumed
+#[derive(Debug, Clone, PartialEq)]
+pub enum WipSave {
+    Commit { pushed: bool }, // HEAD is the WIP commit
+    Stash(usize),            // stash@{n}
 }
 
-// Helper function to format file size
-pub fn format_file_size(size: Option<u64>) -> String {
-    match size {
-        Some(bytes) => {
-            if bytes < 1024 {
-                format!("{} B", bytes)
-            } else if bytes < 1024 * 1024 {
-                format!("{:.1} KB", bytes as f64 / 1024.0)
-            } else if bytes < 1024 * 1024 * 1024 {
-                format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
-            } else {
-                format!("{:.1} GB", bytes as f64 / (1024.0 * 1024.0 * 1024.0))
-            }
+/// Save everything, untracked files included, as a WIP commit or stash
+///
+/// Commits skip hooks: a quick-save shouldn't be blocked by linters.
+pub fn wip_save(mode: WipMode, message: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let steps: Vec<Vec<&str>> = match mode {
+        WipMode::Commit => vec![
+            vec!["add", "--all"],
+            vec!["commit", "--no-verify", "-m", message],
+        ],
+        WipMode::Stash => vec![vec!["stash", "push", "--include-untracked", "-m", message]],
+    };
+    for args in steps {
+        let output = std::process::Command::new("git").args(&args).output()?;
+        if !output.status.success() {
+            return Err(format!(
+                "git {} failed: {}",
+                args[0],
+                String::from_utf8_lossy(&output.stderr).trim()
+            )
+            .into());
         }
-        None => "-".to_string(),
     }
+    Ok(())
 }
 
-/// Check if repository has a remote origin configured
-pub fn has_remote_origin() -> Result<bool, GitError> {
-    let repo = git2::Repository::open(".")?;
-    let result = repo.find_remote("origin");
-    match result {
-        Ok(_) => Ok(true),
-        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(false),
-        Err(e) => Err(GitError::Git2(e)),
+/// The most recent WIP quick-save: HEAD if it is one, otherwise the newest WIP stash
+pub fn find_wip() -> Result<Option<WipSave>, GitError> {
+    let mut repo = git2::Repository::open(".")?;
+
+    if let Ok(head) = repo.head().and_then(|head| head.peel_to_commit()) {
+        let is_wip = head.summary().is_some_and(|s| s.starts_with(WIP_MARKER));
+        if is_wip && head.parent_count() > 0 {
+            // Pushed if any remote-tracking branch already contains it
+            let mut pushed = false;
+            for reference in repo.references_glob("refs/remotes/*")?.flatten() {
+                if let Some(oid) = reference.target() {
+                    let contains = repo.graph_descendant_of(oid, head.id()).unwrap_or(false);
+                    if oid == head.id() || contains {
+                        pushed = true;
+                        break;
+                    }
+                }
+            }
+            return Ok(Some(WipSave::Commit { pushed }));
+        }
     }
-}
-
-/// Get remote status information
-pub fn get_remote_status() -> Result<RemoteStatus, GitError> {
-    let repo = git2::Repository::open(".")?;
 
-    // Get remote origin
-    let remote = repo.find_remote("origin")?;
-    let remote_name = remote.name().unwrap_or("origin").to_string();
-    let remote_url = remote.url().unwrap_or("unknown").to_string();
+    let mut found = None;
+    repo.stash_foreach(|index, message, _| {
+        if message.contains(WIP_MARKER) {
+            found = Some(index);
+            return false;
+        }
+        true
+    })?;
+    Ok(found.map(WipSave::Stash))
+}
 
-    // Get ahead/behind counts
-    let (ahead, behind) = get_ahead_behind_counts(&repo)?;
+/// Bring a WIP quick-save back into the working tree
+///
+/// A WIP commit is undone with `git reset --soft HEAD~1`, leaving its changes
+/// staged; a WIP stash is popped.
+pub fn wip_resume(save: &WipSave) -> Result<(), Box<dyn std::error::Error>> {
+    let stash_ref;
+    let args: Vec<&str> = match save {
+        WipSave::Commit { .. } => vec!["reset", "--soft", "HEAD~1"],
+        WipSave::Stash(index) => {
+            stash_ref = format!("stash@{{{}}}", index);
+            vec!["stash", "pop", &stash_ref]
+        }
+    };
+    let output = std::process::Command::new("git").args(&args).output()?;
+    if !output.status.success() {
+        return Err(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )
+        .into());
+    }
+    Ok(())
+}
 
-    // Get last fetch time (from reflog)
-    let last_fetch = get_last_fetch_time(&repo);
+/// One movement of HEAD, as recorded in its reflog
+#[derive(Debug, Clone)]
+pub struct ReflogEntry {
+    pub old_id: String,
+    pub new_id: String,
+    pub short_id: String, // Of new_id
+    pub action: String,   // "commit", "commit (merge)", "reset", "checkout", "pull", ...
+    pub message: String,  // What follows the action, e.g. the commit's subject
+    pub time: chrono::DateTime<chrono::Local>,
+}
 
-    Ok(RemoteStatus {
-        name: remote_name,
-        url: remote_url,
-        ahead,
-        behind,
-        last_fetch,
-    })
+/// The most recent movements of HEAD, newest first
+pub fn get_reflog(limit: usize) -> Result<Vec<ReflogEntry>, GitError> {
+    let repo = git2::Repository::open(".")?;
+    let reflog = repo.reflog("HEAD")?;
+    Ok(reflog
+        .iter()
+        .take(limit)
+        .map(|entry| {
+            let full = entry.message().unwrap_or_default();
+            let (action, message) = match full.split_once(": ") {
+                Some((action, message)) => (action, message),
+                None => (full, ""),
+            };
+            let new_id = entry.id_new().to_string();
+            ReflogEntry {
+                old_id: entry.id_old().to_string(),
+                short_id: new_id[..7].to_string(),
+                new_id,
+                action: action.to_string(),
+                message: message.to_string(),
+                time: chrono::DateTime::from_timestamp(entry.committer().when().seconds(), 0)
+                    .unwrap_or_default()
+                    .with_timezone(&chrono::Local),
+            }
+        })
+        .collect())
 }
 
-/// Get ahead/behind counts compared to remote tracking branch
-fn get_ahead_behind_counts(repo: &git2::Repository) -> Result<(usize, usize), GitError> {
-    // Get current branch
-    let head = repo.head()?;
-    let local_oid = head
-        .target()
-        .ok_or_else(|| GitError::Other("No HEAD commit".to_string()))?;
+/// The kinds of operation "undo last operation" knows how to take back
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UndoKind {
+    Commit, // Soft reset to the parent, the changes stay staged
+    Amend,  // Soft reset to the commit before it was amended
+    Merge,  // Hard reset to where the branch was before the merge or pull
+    Rebase, // Hard reset to where the branch was before the rebase started
+    Reset,  // Reset back to where HEAD was before
+}
 
-    // Get remote tracking branch
-    let branch_name = head.shorthand().unwrap_or("HEAD");
-    let remote_branch_name = format!("origin/{}", branch_name);
+/// What undoing HEAD's newest reflog entry would do
+#[derive(Debug, Clone)]
+pub struct UndoPlan {
+    pub kind: UndoKind,
+    pub entry: ReflogEntry, // The operation being undone
+    pub target: String,     // Full id HEAD goes back to
+    pub hard: bool,         // Whether the index and working tree are reset too
+}
 
-    match repo.find_branch(&remote_branch_name, git2::BranchType::Remote) {
-        Ok(remote_branch) => {
-            let remote_oid = remote_branch
-                .get()
-                .target()
-                .ok_or_else(|| GitError::Other("No remote branch commit".to_string()))?;
+/// Work out how to undo the newest movement of HEAD, refusing anything that
+/// would lose work or rewrite pushed history
+pub fn plan_undo() -> Result<UndoPlan, GitError> {
+    let repo = git2::Repository::open(".")?;
+    if repo.state() != git2::RepositoryState::Clean {
+        return Err(GitError::Other(
+            "A merge, rebase or cherry-pick is in progress. Finish or abort it first.".to_string(),
+        ));
+    }
+    let entries = get_reflog(1000)?;
+    let entry = entries
+        .first()
+        .cloned()
+        .ok_or_else(|| GitError::Other("HEAD has no reflog entries yet".to_string()))?;
+    let head = repo.head()?.peel_to_commit()?.id().to_string();
+    if head != entry.new_id {
+        return Err(GitError::Other(
+            "HEAD has moved since the last reflog entry, so it can't be undone safely".to_string(),
+        ));
+    }
+    if git2::Oid::from_str(&entry.old_id)?.is_zero() {
+        return Err(GitError::Other(
+            "The first commit has nothing before it to go back to".to_string(),
+        ));
+    }
 
-            // Calculate ahead/behind
-            let (ahead, behind) = repo.graph_ahead_behind(local_oid, remote_oid)?;
-            Ok((ahead, behind))
+    let kind = match entry.action.as_str() {
+        "commit" => UndoKind::Commit,
+        "commit (amend)" => UndoKind::Amend,
+        "commit (merge)" => UndoKind::Merge,
+        "cherry-pick" => UndoKind::Commit,
+        _ if rebase_step(&entry) == Some("finish") => UndoKind::Rebase,
+        action if action.starts_with("merge ") || action.starts_with("pull") => UndoKind::Merge,
+        "gitix" if entry.message.starts_with("fast-forward") => UndoKind::Merge,
+        "reset" => UndoKind::Reset,
+        action => {
+            return Err(GitError::Other(format!(
+                "The last operation was '{}', which can't be undone from here. \
+                 Only commits, cherry-picks, merges, pulls, rebases and resets can.",
+                action
+            )))
         }
-        Err(_) => {
-            // No remote tracking branch, assume we're ahead by the number of commits
-            let revwalk = repo.revwalk()?;
-            let mut walk = revwalk;
-            walk.push(local_oid)?;
-            let ahead = walk.count();
-            Ok((ahead, 0))
+    };
+
+    let mut has_changes = false;
+    let mut options = git2::StatusOptions::new();
+    options.include_untracked(false);
+    for status in repo.statuses(Some(&mut options))?.iter() {
+        if status.status() != git2::Status::CURRENT {
+            has_changes = true;
+            break;
         }
     }
-}
 
-/// Get last fetch time from reflog
-fn get_last_fetch_time(repo: &git2::Repository) -> Option<String> {
-    // Try to get the reflog for the remote tracking branch
-    if let Ok(reflog) = repo.reflog("refs/remotes/origin/HEAD") {
-        if let Some(entry) = reflog.iter().next() {
-            let time = entry.committer().when();
-            let datetime = chrono::DateTime::from_timestamp(time.seconds(), 0)?;
-            let local_time = datetime.with_timezone(&chrono::Local);
-            return Some(format_relative_time(local_time));
+    if kind != UndoKind::Reset {
+        // Pushed if any remote-tracking branch already contains it
+        let head_oid = git2::Oid::from_str(&entry.new_id)?;
+        for reference in repo.references_glob("refs/remotes/*")?.flatten() {
+            if let Some(oid) = reference.target() {
+                let contains = repo.graph_descendant_of(oid, head_oid).unwrap_or(false);
+                if oid == head_oid || contains {
+                    return Err(GitError::Other(format!(
+                        "{} is already on {}, so undoing it would rewrite shared history. \
+                         Revert it with a new commit instead.",
+                        entry.short_id,
+                        reference.shorthand().unwrap_or("a remote")
+                    )));
+                }
+            }
         }
     }
+    if matches!(kind, UndoKind::Merge | UndoKind::Rebase) && has_changes {
+        return Err(GitError::Other(
+            "Undoing a merge or rebase resets the working tree. Commit or save your changes first."
+                .to_string(),
+        ));
+    }
 
-    // Fallback: check if .git/FETCH_HEAD exists and get its modification time
-    if let Ok(metadata) = std::fs::metadata(".git/FETCH_HEAD") {
-        if let Ok(modified) = metadata.modified() {
-            let datetime = chrono::DateTime::<chrono::Local>::from(modified);
-            return Some(format_relative_time(datetime));
+    // Every step of a rebase has its own entry; the branch was where its start entry began.
+    // Stop there: the entries before it may belong to an earlier rebase.
+    let mut target = entry.old_id.clone();
+    if kind == UndoKind::Rebase {
+        for step_entry in &entries {
+            let Some(step) = rebase_step(step_entry) else {
+                break;
+            };
+            target = step_entry.old_id.clone();
+            if step == "start" {
+                break;
+            }
         }
     }
 
-    None
+    Ok(UndoPlan {
+        kind,
+        target,
+        // A reset is undone exactly when nothing is left to lose, otherwise softly
+        hard: matches!(kind, UndoKind::Merge | UndoKind::Rebase)
+            || (kind == UndoKind::Reset && !has_changes),
+        entry,
+    })
 }
 
-/// Format time relative to now (e.g., "2 minutes ago")
-pub fn format_relative_time(time: chrono::DateTime<chrono::Local>) -> String {
-    let now = chrono::Local::now();
-    let duration = now.signed_duration_since(time);
-
-    if duration.num_seconds() < 60 {
-        "Just now".to_string()
-    } else if duration.num_minutes() < 60 {
-        format!("{} minutes ago", duration.num_minutes())
-    } else if duration.num_hours() < 24 {
-        format!("{} hours ago", duration.num_hours())
-    } else {
-        format!("{} days ago", duration.num_days())
+/// The step of a rebase a reflog entry records ("start", "pick", "finish", ...), or `None`
+/// when it isn't part of one. Covers `git rebase`, `git rebase -i` and `git pull --rebase`,
+/// and the `rebase: checkout` / `rebase finished` entries of older git versions.
+fn rebase_step(entry: &ReflogEntry) -> Option<&str> {
+    let action = entry.action.as_str();
+    // `git pull` records its arguments too: "pull --rebase origin main (pick)"
+    if action.starts_with("rebase") || action.starts_with("pull ") {
+        if let Some((_, step)) = action.rsplit_once('(') {
+            return step.strip_suffix(')');
+        }
+    }
+    match action {
+        "rebase finished" => Some("finish"),
+        "rebase" | "rebase -i" if entry.message.starts_with("checkout ") => Some("start"),
+        "rebase" | "rebase -i" => Some("pick"),
+        _ => None,
     }
 }
 
-/// Format SystemTime as relative time string
-pub fn format_system_time_relative(system_time: std::time::SystemTime) -> String {
-    match system_time.duration_since(std::time::UNIX_EPOCH) {
-        Ok(duration) => {
-            if let Some(datetime) = chrono::DateTime::from_timestamp(duration.as_secs() as i64, 0) {
-                let local_time = datetime.with_timezone(&chrono::Local);
-                format_relative_time(local_time)
-            } else {
-                "Unknown time".to_string()
-            }
-        }
-        Err(_) => "Unknown time".to_string(),
+/// Move HEAD back as the plan says; the reset is itself recorded in the reflog,
+/// so it can be undone in turn
+pub fn undo_operation(plan: &UndoPlan) -> Result<(), GitError> {
+    let repo = git2::Repository::open(".")?;
+    let head = repo.head()?.peel_to_commit()?.id().to_string();
+    if head != plan.entry.new_id {
+        return Err(GitError::Other(
+            "HEAD has moved since the undo was planned".to_string(),
+        ));
     }
+    let target = repo.find_object(git2::Oid::from_str(&plan.target)?, None)?;
+    let mode = if plan.hard {
+        git2::ResetType::Hard
+    } else {
+        git2::ResetType::Soft
+    };
+    repo.reset(&target, mode, None)?;
+    Ok(())
 }
 
-/// Fetch from remote origin
-pub fn fetch_origin() -> Result<SyncOperation, GitError> {
-    let start_time = std::time::SystemTime::now();
+/// How much `reset_to` resets, like `git reset --soft/--mixed/--hard`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResetMode {
+    Soft,  // Only HEAD moves
+    Mixed, // HEAD and the index
+    Hard,  // HEAD, the index and tracked files in the working tree
+}
 
-    // Try git2-rs first, but with a fallback to git command
-    match fetch_origin_git2() {
-        Ok(operation) => Ok(operation),
-        Err(_e) => {
-            // Silent fallback to git command - this is expected for some SSH configurations
-            fetch_origin_fallback(start_time)
+impl ResetMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ResetMode::Soft => "soft",
+            ResetMode::Mixed => "mixed",
+            ResetMode::Hard => "hard",
         }
     }
 }
 
-/// Fetch using git2-rs
-fn fetch_origin_git2() -> Result<SyncOperation, GitError> {
-    let start_time = std::time::SystemTime::now();
+/// What resetting to a commit would leave behind, for the confirmation
+#[derive(Debug, Clone, Default)]
+pub struct ResetPreview {
+    pub left_behind: usize, // Commits HEAD has that the target doesn't
+    pub unpushed: usize,    // Of those, the ones on no remote-tracking branch
+    pub ahead: usize,       // Commits the target has that HEAD doesn't, when it isn't an ancestor
+    pub staged: usize,      // Files with staged changes now
+    pub unstaged: usize,    // Tracked files with unstaged changes now
+}
 
+/// Count what resetting HEAD to `target` would affect
+pub fn preview_reset(target: &str) -> Result<ResetPreview, GitError> {
     let repo = git2::Repository::open(".")?;
-    let mut remote = repo.find_remote("origin")?;
-
-    // Create callbacks for authentication and progress
-    let mut callbacks = git2::RemoteCallbacks::new();
-    callbacks.credentials(|url, username_from_url, allowed_types| {
-        // Try different authentication methods in order of preference
-        if allowed_types.contains(git2::CredentialType::SSH_KEY) {
-            // Try SSH key from agent first
-            if let Ok(cred) = git2::Cred::ssh_key_from_agent(username_from_url.unwrap_or("git")) {
-                return Ok(cred);
-            }
+    let head = repo.head()?.peel_to_commit()?.id();
+    let target = git2::Oid::from_str(target)?;
+    let (left_behind, ahead) = repo.graph_ahead_behind(head, target)?;
+
+    let mut walk = repo.revwalk()?;
+    walk.push(head)?;
+    walk.hide(target)?;
+    for reference in repo.references_glob("refs/remotes/*")?.flatten() {
+        if let Some(oid) = reference.target() {
+            walk.hide(oid)?;
         }
+    }
+    let unpushed = walk.count();
 
-        if allowed_types.contains(git2::CredentialType::USER_PASS_PLAINTEXT) {
-            // For HTTPS, try default credentials (this will prompt if needed)
-            if let Ok(cred) = git2::Cred::credential_helper(
-                &git2::Config::open_default().unwrap_or_else(|_| git2::Config::new().unwrap()),
-                url,
-                username_from_url,
-            ) {
-                return Ok(cred);
-            }
+    let mut preview = ResetPreview {
+        left_behind,
+        unpushed,
+        ahead,
+        ..ResetPreview::default()
+    };
+    let mut options = git2::StatusOptions::new();
+    options.include_untracked(false);
+    let staged = git2::Status::INDEX_NEW
+        | git2::Status::INDEX_MODIFIED
+        | git2::Status::INDEX_DELETED
+        | git2::Status::INDEX_RENAMED
+        | git2::Status::INDEX_TYPECHANGE;
+    let unstaged = git2::Status::WT_MODIFIED
+        | git2::Status::WT_DELETED
+        | git2::Status::WT_RENAMED
+        | git2::Status::WT_TYPECHANGE;
+    for entry in repo.statuses(Some(&mut options))?.iter() {
+        if entry.status().intersects(staged) {
+            preview.staged += 1;
         }
+        if entry.status().intersects(unstaged) {
+            preview.unstaged += 1;
+        }
+    }
+    Ok(preview)
+}
 
-        // If all else fails, return an error
-        Err(git2::Error::from_str(
-            "No suitable authentication method found",
-        ))
-    });
+/// Reset HEAD (and the branch it is on) to `oid`, leaving the index and working tree as `mode` says
+///
+/// Untracked files are never touched. The reset is recorded in the reflog, so
+/// the History tab's undo can take it back.
+pub fn reset_to(oid: &str, mode: ResetMode) -> Result<(), GitError> {
+    let repo = git2::Repository::open(".")?;
+    if repo.state() != git2::RepositoryState::Clean {
+        return Err(GitError::Other(
+            "A merge, rebase or cherry-pick is in progress. Finish or abort it first.".to_string(),
+        ));
+    }
+    let target = repo.find_object(git2::Oid::from_str(oid)?, None)?;
+    let kind = match mode {
+        ResetMode::Soft => git2::ResetType::Soft,
+        ResetMode::Mixed => git2::ResetType::Mixed,
+        ResetMode::Hard => git2::ResetType::Hard,
+    };
+    repo.reset(&target, kind, None)?;
+    Ok(())
+}
 
-    // Set up fetch options
-    let mut fetch_options = git2::FetchOptions::new();
-    fetch_options.remote_callbacks(callbacks);
+/// Where working tree snapshots are kept, out of the way of branches and tags
+pub const BACKUP_REF: &str = "refs/gitix/backup";
 
-    match remote.fetch(&[] as &[&str], Some(&mut fetch_options), None) {
-        Ok(()) => Ok(SyncOperation {
-            operation_type: SyncOperationType::Fetch,
-            status: OperationStatus::Success,
-            message: "Successfully fetched from remote".to_string(),
-            timestamp: start_time,
-        }),
-        Err(e) => Err(GitError::Git2(e)),
-    }
+/// One snapshot of the working tree under refs/gitix/backup
+#[derive(Debug, Clone)]
+pub struct BackupSnapshot {
+    pub id: String,
+    pub short_id: String,
+    pub time: chrono::DateTime<chrono::Local>,
+    pub summary: String, // "Backup of <branch>"
 }
 
-/// Fallback fetch using git command
-fn fetch_origin_fallback(start_time: std::time::SystemTime) -> Result<SyncOperation, GitError> {
-    let output = std::process::Command::new("git")
-        .args(&["fetch", "origin"])
-        .output()
-        .map_err(GitError::Io)?;
+/// Snapshot the working tree (tracked and untracked, minus ignored files) to refs/gitix/backup
+///
+/// The tree is built from a copy of the index in memory, so neither the index
+/// file nor HEAD is touched. Each snapshot's parent is the previous one. Returns
+/// the new snapshot's id, or None when nothing changed since the last snapshot.
+pub fn backup_snapshot() -> Result<Option<String>, GitError> {
+    let repo = git2::Repository::open(".")?;
+    if repo.workdir().is_none() {
+        return Err(GitError::Other("Bare repositories have no working tree".to_string()));
+    }
 
-    if output.status.success() {
-        Ok(SyncOperation {
-            operation_type: SyncOperationType::Fetch,
-            status: OperationStatus::Success,
-            message: "Successfully fetched from remote (fallback)".to_string(),
-            timestamp: start_time,
-        })
-    } else {
-        Ok(SyncOperation {
-            operation_type: SyncOperationType::Fetch,
-            status: OperationStatus::Error,
-            message: format!(
-                "Failed to fetch: {}",
-                String::from_utf8_lossy(&output.stderr)
-            ),
-            timestamp: start_time,
-        })
+    // Changes stay in memory: the index is never written back
+    let mut index = repo.index()?;
+    index.add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)?;
+    index.update_all(["*"].iter(), None)?;
+    let tree_id = index.write_tree_to(&repo)?;
+
+    let previous = repo
+        .find_reference(BACKUP_REF)
+        .ok()
+        .and_then(|reference| reference.peel_to_commit().ok());
+    if previous.as_ref().is_some_and(|commit| commit.tree_id() == tree_id) {
+        return Ok(None);
     }
+
+    let tree = repo.find_tree(tree_id)?;
+    let signature = repo
+        .signature()
+        .or_else(|_| git2::Signature::now("gitix", "gitix@localhost"))?;
+    let branch = repo
+        .head()
+        .ok()
+        .and_then(|head| head.shorthand().map(str::to_string))
+        .unwrap_or_else(|| "HEAD".to_string());
+    let parents: Vec<&git2::Commit> = previous.iter().collect();
+    let commit_id = repo.commit(
+        None,
+        &signature,
+        &signature,
+        &format!("Backup of {}", branch),
+        &tree,
+        &parents,
+    )?;
+    repo.reference(BACKUP_REF, commit_id, true, "gitix backup")?;
+    Ok(Some(commit_id.to_string()))
 }
 
-/// Pull from remote origin (with optional rebase)
-pub fn pull_origin(use_rebase: bool) -> Result<SyncOperation, GitError> {
-    let start_time = std::time::SystemTime::now();
+/// The most recent snapshots, newest first
+pub fn list_backups(limit: usize) -> Result<Vec<BackupSnapshot>, GitError> {
+    let repo = git2::Repository::open(".")?;
+    let mut commit = match repo.find_reference(BACKUP_REF) {
+        Ok(reference) => reference.peel_to_commit()?,
+        Err(e) if e.code() == git2::ErrorCode::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(GitError::Git2(e)),
+    };
 
-    // First fetch
-    let fetch_result = fetch_origin()?;
-    if matches!(fetch_result.status, OperationStatus::Error) {
-        return Ok(SyncOperation {
-            operation_type: SyncOperationType::Pull,
-            status: OperationStatus::Error,
-            message: format!("Pull failed during fetch: {}", fetch_result.message),
-            timestamp: start_time,
+    let mut snapshots = Vec::new();
+    loop {
+        let time = chrono::DateTime::from_timestamp(commit.time().seconds(), 0)
+            .unwrap_or_default()
+            .with_timezone(&chrono::Local);
+        let id = commit.id().to_string();
+        snapshots.push(BackupSnapshot {
+            short_id: id[..7].to_string(),
+            id,
+            time,
+            summary: commit.summary().unwrap_or_default().to_string(),
         });
+        if snapshots.len() >= limit || commit.parent_count() == 0 {
+            break;
+        }
+        commit = commit.parent(0)?;
     }
+    Ok(snapshots)
+}
 
+/// Files whose content in a snapshot differs from the working tree, or that are gone from it
+pub fn backup_changed_files(id: &str) -> Result<Vec<String>, GitError> {
     let repo = git2::Repository::open(".")?;
+    let tree = repo.find_commit(git2::Oid::from_str(id)?)?.tree()?;
+    let diff = repo.diff_tree_to_workdir(Some(&tree), None)?;
+    let mut paths: Vec<String> = diff
+        .deltas()
+        .filter(|delta| {
+            matches!(
+                delta.status(),
+                git2::Delta::Modified | git2::Delta::Deleted | git2::Delta::Typechange
+            )
+        })
+        .filter_map(|delta| delta.old_file().path().map(|p| p.display().to_string()))
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
 
-    // Get current branch and remote tracking branch
-    let head = repo.head()?;
-    let local_oid = head
-        .target()
-        .ok_or_else(|| GitError::Other("No HEAD commit".to_string()))?;
+/// Overwrite `path` in the working tree with its content from a snapshot
+pub fn restore_from_backup(id: &str, path: &str) -> Result<(), GitError> {
+    let repo = git2::Repository::open(".")?;
+    let tree = repo.find_commit(git2::Oid::from_str(id)?)?.tree()?;
+    let entry = tree.get_path(std::path::Path::new(path))?;
+    let blob = repo.find_blob(entry.id())?;
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| GitError::Other("Bare repositories have no working tree".to_string()))?;
+    let target = workdir.join(path);
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&target, blob.content())?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if entry.filemode() == i32::from(git2::FileMode::BlobExecutable) {
+            std::fs::set_permissions(&target, std::fs::Permissions::from_mode(0o755))?;
+        }
+    }
+    Ok(())
+}
 
-    let branch_name = head.shorthand().unwrap_or("HEAD");
-    let remote_branch_name = format!("origin/{}", branch_name);
+/// Local commits that have not reached any remote
+#[derive(Debug, Clone, Default)]
+pub struct UnpushedWork {
+    pub commits: usize,        // Distinct commits, however many branches share them
+    pub branches: Vec<String>, // Local branches holding at least one of them
+}
 
-    let remote_branch = match repo.find_branch(&remote_branch_name, git2::BranchType::Remote) {
-        Ok(branch) => branch,
-        Err(_) => {
-            return Ok(SyncOperation {
-                operation_type: SyncOperationType::Pull,
-                status: OperationStatus::Error,
-                message: "No remote tracking branch found".to_string(),
-                timestamp: start_time,
-            });
+/// Commits on local branches that no remote-tracking branch contains
+///
+/// A repository without remotes has nowhere to push to, so nothing counts as
+/// unpushed there.
+pub fn unpushed_work() -> Result<UnpushedWork, GitError> {
+    let repo = git2::Repository::open(".")?;
+    if repo.remotes()?.is_empty() {
+        return Ok(UnpushedWork::default());
+    }
+
+    let walk = |branch: Option<&str>| -> Result<usize, git2::Error> {
+        let mut revwalk = repo.revwalk()?;
+        match branch {
+            Some(refname) => revwalk.push_ref(refname)?,
+            None => revwalk.push_glob("refs/heads/*")?,
         }
+        revwalk.hide_glob("refs/remotes/*")?;
+        Ok(revwalk.count())
     };
 
-    let remote_oid = remote_branch
-        .get()
-        .target()
-        .ok_or_else(|| GitError::Other("No remote branch commit".to_string()))?;
-
-    // Check if we're already up to date
-    if local_oid == remote_oid {
+    let mut branches = Vec::new();
+    for branch in repo.branches(Some(git2::BranchType::Local))? {
+        let (branch, _) = branch?;
+        let (Some(refname), Ok(Some(name))) = (branch.get().name(), branch.name()) else {
+            continue;
+        };
+        if walk(Some(refname))? > 0 {
+            branches.push(name.to_string());
+        }
+    }
+    if branches.is_empty() {
+        return Ok(UnpushedWork::default());
+    }
+    Ok(UnpushedWork {
+        commits: walk(None)?,
+        branches,
+    })
+}
+
+/// Number of stash entries made more than `days` days ago
+pub fn stashes_older_than(days: u32) -> Result<usize, GitError> {
+    let repo = git2::Repository::open(".")?;
+    let reflog = match repo.reflog("refs/stash") {
+        Ok(reflog) => reflog,
+        Err(e) if e.code() == git2::ErrorCode::NotFound => return Ok(0),
+        Err(e) => return Err(e.into()),
+    };
+    let cutoff = chrono::Utc::now().timestamp() - i64::from(days) * 24 * 60 * 60;
+    Ok(reflog
+        .iter()
+        .filter(|entry| entry.committer().when().seconds() < cutoff)
+        .count())
+}
+
+/// One commit as listed in pickers and popups
+#[derive(Debug, Clone)]
+pub struct CommitSummary {
+    pub id: String,       // Full hex id
+    pub short_id: String, // Abbreviated id, as git shows it
+    pub author: String,
+    pub time: chrono::DateTime<chrono::FixedOffset>, // Committed, on the committer's clock
+    pub summary: String,
+    pub message: String,
+}
+
+impl CommitSummary {
+    fn from_commit(commit: &git2::Commit) -> Self {
+        let id = commit.id().to_string();
+        CommitSummary {
+            short_id: commit
+                .as_object()
+                .short_id()
+                .ok()
+                .and_then(|short| short.as_str().map(|s| s.to_string()))
+                .unwrap_or_else(|| id[..7].to_string()),
+            id,
+            author: commit.author().name().unwrap_or_default().to_string(),
+            time: git_time(commit.time()),
+            summary: commit.summary().unwrap_or_default().to_string(),
+            message: commit.message().unwrap_or_default().trim_end().to_string(),
+        }
+    }
+}
+
+/// The commit `spec` names (an id, branch or other revision), summarized
+pub fn find_commit_summary(spec: &str) -> Result<CommitSummary, GitError> {
+    let repo = git2::Repository::open(".")?;
+    let commit = repo.revparse_single(spec)?.peel_to_commit()?;
+    Ok(CommitSummary::from_commit(&commit))
+}
+
+/// Commits reachable from HEAD made on `date`, newest first
+///
+/// Days are counted in UTC, the same way the Overview calendar marks them.
+pub fn commits_on_day(date: chrono::NaiveDate) -> Result<Vec<CommitSummary>, GitError> {
+    let repo = git2::Repository::open(".")?;
+    let mut walk = repo.revwalk()?;
+    walk.set_sorting(git2::Sort::TIME)?;
+    walk.push_head()?;
+
+    let mut commits = Vec::new();
+    for oid in walk {
+        let commit = repo.find_commit(oid?)?;
+        let day = chrono::DateTime::from_timestamp(commit.time().seconds(), 0)
+            .map(|time| time.date_naive());
+        if day == Some(date) {
+            commits.push(CommitSummary::from_commit(&commit));
+        }
+    }
+    Ok(commits)
+}
+
+/// Which commits the History tab lists; empty fields don't filter
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HistoryFilter {
+    pub branch: String,                   // Local branch whose history is listed instead of HEAD's
+    pub author: String,                   // Substring of the author name or email, any case
+    pub path: String,                     // Only commits touching paths starting with this
+    pub since: Option<chrono::NaiveDate>, // First day included (UTC, like the calendar)
+    pub until: Option<chrono::NaiveDate>, // Last day included
+    pub first_parent: bool,               // Follow only the first parent of merges, like --first-parent
+    pub hide_merges: bool,                // Leave out merge commits, like --no-merges
+    pub follow: bool,                     // Track `path` as one file across renames, like --follow
+}
+
+impl HistoryFilter {
+    pub fn is_empty(&self) -> bool {
+        *self == HistoryFilter::default()
+    }
+
+    /// Short description for titles, e.g. "author ~ ana, since 2024-01-01"
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.branch.is_empty() {
+            parts.push(format!("branch {}", self.branch));
+        }
+        if !self.author.is_empty() {
+            parts.push(format!("author ~ {}", self.author));
+        }
+        if self.following() {
+            parts.push(format!("file {} (following renames)", self.path));
+        } else if !self.path.is_empty() {
+            parts.push(format!("path {}", self.path));
+        }
+        if let Some(since) = self.since {
+            parts.push(format!("since {}", since));
+        }
+        if let Some(until) = self.until {
+            parts.push(format!("until {}", until));
+        }
+        if self.first_parent {
+            parts.push("first parent".to_string());
+        }
+        if self.hide_merges {
+            parts.push("no merges".to_string());
+        }
+        parts.join(", ")
+    }
+
+    /// Whether `path` names one file whose renames are followed
+    pub fn following(&self) -> bool {
+        self.follow && !self.path.is_empty()
+    }
+
+    /// Whether `commit` passes the filter; `followed` is the followed file's name at that commit
+    fn matches(
+        &self,
+        repo: &git2::Repository,
+        commit: &git2::Commit,
+        followed: Option<&str>,
+    ) -> Result<bool, git2::Error> {
+        if self.hide_merges && commit.parent_count() > 1 {
+            return Ok(false);
+        }
+
+        let day = chrono::DateTime::from_timestamp(commit.time().seconds(), 0)
+            .map(|time| time.date_naive())
+            .unwrap_or_default();
+        if self.since.is_some_and(|since| day < since)
+            || self.until.is_some_and(|until| day > until)
+        {
+            return Ok(false);
+        }
+
+        if !self.author.is_empty() {
+            let needle = self.author.to_lowercase();
+            let author = commit.author();
+            let found = [author.name(), author.email()]
+                .iter()
+                .flatten()
+                .any(|field| field.to_lowercase().contains(&needle));
+            if !found {
+                return Ok(false);
+            }
+        }
+
+        let pathspec = match followed {
+            Some(path) => path.to_string(),
+            None if !self.path.is_empty() => format!("{}*", self.path.trim_start_matches("./")),
+            None => return Ok(true),
+        };
+        Ok(first_parent_diff(repo, commit, Some(&pathspec))?.deltas().len() > 0)
+    }
+}
+
+/// Changes `commit` made over its first parent, the way `git log -- <path>` sees merges
+fn first_parent_diff<'r>(
+    repo: &'r git2::Repository,
+    commit: &git2::Commit,
+    pathspec: Option<&str>,
+) -> Result<git2::Diff<'r>, git2::Error> {
+    let tree = commit.tree()?;
+    let parent_tree = match commit.parent(0) {
+        Ok(parent) => Some(parent.tree()?),
+        Err(_) => None,
+    };
+    let mut options = git2::DiffOptions::new();
+    if let Some(pathspec) = pathspec {
+        options.pathspec(pathspec);
+    }
+    repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut options))
+}
+
+/// The name `path` had before `commit`, if that commit renamed the file to `path`
+///
+/// Only commits that add `path` pay for the full-tree diff rename detection needs.
+fn rename_source(
+    repo: &git2::Repository,
+    commit: &git2::Commit,
+    path: &str,
+) -> Result<Option<String>, git2::Error> {
+    if commit.parent_count() == 0 {
+        return Ok(None);
+    }
+    let added = first_parent_diff(repo, commit, Some(path))?
+        .deltas()
+        .any(|delta| delta.status() == git2::Delta::Added);
+    if !added {
+        return Ok(None);
+    }
+
+    let mut diff = first_parent_diff(repo, commit, None)?;
+    diff.find_similar(Some(git2::DiffFindOptions::new().renames(true)))?;
+    let source = diff
+        .deltas()
+        .find(|delta| {
+            delta.status() == git2::Delta::Renamed
+                && delta.new_file().path() == Some(std::path::Path::new(path))
+        })
+        .and_then(|delta| delta.old_file().path().map(|old| old.display().to_string()));
+    Ok(source)
+}
+
+/// Commits walked per call of `history_page`, matching or not
+pub const HISTORY_SCAN_LIMIT: usize = 2000;
+
+/// How far a History listing has got, so the next page carries on from there
+///
+/// The walk itself is kept between pages: starting it again and skipping what
+/// earlier pages listed would make each page slower than the one before.
+#[derive(Default)]
+pub struct HistoryCursor {
+    walk: Option<HistoryWalk>,    // Started by the first page
+    pub looked_at: usize,         // Commits walked so far, matching or not
+    pub done: bool,               // Whether the walk reached the first commit
+    followed: Option<String>,     // The followed file's name where the walk stopped
+}
+
+type CommitTimeWalk = Box<dyn Iterator<Item = Result<gix::traverse::commit::Info, GitError>>>;
+
+enum HistoryWalk {
+    /// libgit2's topological order, which it only hands out after reading the whole history
+    Topological(std::vec::IntoIter<git2::Oid>),
+    /// gix's commit-time walk, paused where the last page stopped
+    CommitTime(CommitTimeWalk),
+}
+
+/// The next commits from HEAD matching `filter`, newest first, continuing from `cursor`
+///
+/// No more than `HISTORY_SCAN_LIMIT` commits are looked at per call, so a narrow
+/// filter over a long history hands back control (possibly with no matches)
+/// instead of blocking; call again with the same cursor until it is `done`.
+pub fn history_page(
+    filter: &HistoryFilter,
+    cursor: &mut HistoryCursor,
+    limit: usize,
+) -> Result<Vec<CommitSummary>, GitError> {
+    if cursor.done {
+        return Ok(Vec::new());
+    }
+    if filter.path.is_empty() {
+        return history_page_gix(filter, cursor, limit);
+    }
+    let repo = git2::Repository::open(".")?;
+    if cursor.walk.is_none() {
+        let mut walk = repo.revwalk()?;
+        // Children always before parents, which following renames depends on
+        walk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)?;
+        if filter.first_parent {
+            walk.simplify_first_parent()?;
+        }
+        match filter.branch.as_str() {
+            "" => walk.push_head()?,
+            branch => walk.push_ref(&format!("refs/heads/{}", branch))?,
+        }
+        let ids = walk.collect::<Result<Vec<_>, _>>()?;
+        cursor.walk = Some(HistoryWalk::Topological(ids.into_iter()));
+        if filter.following() {
+            cursor.followed = Some(filter.path.trim_start_matches("./").to_string());
+        }
+    }
+    let Some(HistoryWalk::Topological(ids)) = cursor.walk.as_mut() else {
+        return Err(GitError::Other(
+            "The history cursor was started for another filter".to_string(),
+        ));
+    };
+
+    let mut commits = Vec::new();
+    let mut scanned = 0;
+    while commits.len() < limit && scanned < HISTORY_SCAN_LIMIT {
+        let Some(oid) = ids.next() else {
+            cursor.done = true;
+            break;
+        };
+        scanned += 1;
+        cursor.looked_at += 1;
+        let commit = repo.find_commit(oid)?;
+        if filter.matches(&repo, &commit, cursor.followed.as_deref())? {
+            commits.push(CommitSummary::from_commit(&commit));
+        }
+        // Older commits know the file by the name it had before this one
+        if let Some(path) = cursor.followed.as_mut() {
+            if let Some(source) = rename_source(&repo, &commit, path)? {
+                *path = source;
+            }
+        }
+    }
+    Ok(commits)
+}
+
+/// `history_page` for filters without a path, walked with gix
+///
+/// The libgit2 walk sorts topologically, which reads the whole history before
+/// returning the first commit. Without paths to diff there are no renames to
+/// follow in order, so commit-time order is enough and the walk streams: a page
+/// only reads the commits up to its end.
+fn history_page_gix(
+    filter: &HistoryFilter,
+    cursor: &mut HistoryCursor,
+    limit: usize,
+) -> Result<Vec<CommitSummary>, GitError> {
+    use gix::traverse::commit::simple::{CommitTimeOrder, Sorting};
+    use gix::traverse::commit::{Parents, Simple};
+    let other = |e: &dyn std::fmt::Display| GitError::Other(e.to_string());
+
+    let repo = gix::open(".")?;
+    if cursor.walk.is_none() {
+        let tip = match filter.branch.as_str() {
+            "" => repo.head_id().map_err(|e| other(&e))?.detach(),
+            branch => repo
+                .find_reference(&format!("refs/heads/{}", branch))
+                .map_err(|e| other(&e))?
+                .peel_to_id_in_place()
+                .map_err(|e| other(&e))?
+                .detach(),
+        };
+        // The walk owns its object database handle so it can outlive this call. A
+        // shallow clone stops where the objects do.
+        let objects = repo.objects.clone();
+        let shallow = repo.is_shallow().then(|| repo.objects.clone());
+        let mut walk = Simple::filtered([tip], objects, move |id| {
+            shallow
+                .as_ref()
+                .is_none_or(|objects| gix::objs::Exists::exists(objects, id))
+        })
+        .sorting(Sorting::ByCommitTime(CommitTimeOrder::NewestFirst))
+        .map_err(|e| other(&e))?
+        .commit_graph(repo.commit_graph().ok());
+        if filter.first_parent {
+            walk = walk.parents(Parents::First);
+        }
+        let walk: CommitTimeWalk = Box::new(walk.map(move |info| info.map_err(|e| other(&e))));
+        cursor.walk = Some(HistoryWalk::CommitTime(walk));
+    }
+    let Some(HistoryWalk::CommitTime(walk)) = cursor.walk.as_mut() else {
+        return Err(GitError::Other(
+            "The history cursor was started for another filter".to_string(),
+        ));
+    };
+    let needle = filter.author.to_lowercase();
+
+    let mut commits = Vec::new();
+    let mut scanned = 0;
+    while commits.len() < limit && scanned < HISTORY_SCAN_LIMIT {
+        let Some(info) = walk.next() else {
+            cursor.done = true;
+            break;
+        };
+        let info = info?;
+        scanned += 1;
+        cursor.looked_at += 1;
+        if filter.hide_merges && info.parent_ids.len() > 1 {
+            continue;
+        }
+        let commit = repo.find_commit(info.id).map_err(|e| other(&e))?;
+        let time = commit.time().map_err(|e| other(&e))?;
+        let time = time_with_offset(time.seconds, time.offset / 60);
+        let day = time.naive_utc().date();
+        if filter.since.is_some_and(|since| day < since)
+            || filter.until.is_some_and(|until| day > until)
+        {
+            continue;
+        }
+        let author = commit.author().map_err(|e| other(&e))?;
+        let author_name = author.name.to_string();
+        if !needle.is_empty()
+            && !author_name.to_lowercase().contains(&needle)
+            && !author.email.to_string().to_lowercase().contains(&needle)
+        {
+            continue;
+        }
+
+        let id = info.id.to_string();
+        let message = commit.message_raw_sloppy().to_string();
+        let summary = commit.message().map_err(|e| other(&e))?.summary().to_string();
+        commits.push(CommitSummary {
+            short_id: commit.id().shorten_or_id().to_string(),
+            id,
+            author: author_name,
+            time,
+            summary,
+            message: message.trim_end().to_string(),
+        });
+    }
+    Ok(commits)
+}
+
+/// One commit with its change stats, as `gitix log` and the History export write it
+#[derive(Debug, Clone)]
+pub struct ExportedCommit {
+    pub id: String,
+    pub author: String,
+    pub email: String,
+    pub date: chrono::DateTime<chrono::FixedOffset>, // Author date, in the author's time zone
+    pub message: String,
+    pub files_changed: usize, // Against the first parent, like `git log --stat`
+    pub insertions: usize,
+    pub deletions: usize,
+}
+
+/// Every commit matching `filter` (or the newest `limit` of them) with its change stats
+pub fn history_export(
+    filter: &HistoryFilter,
+    limit: Option<usize>,
+) -> Result<Vec<ExportedCommit>, GitError> {
+    let limit = limit.unwrap_or(usize::MAX);
+    let mut summaries = Vec::new();
+    let mut cursor = HistoryCursor::default();
+    while summaries.len() < limit && !cursor.done {
+        summaries.extend(history_page(filter, &mut cursor, limit - summaries.len())?);
+    }
+
+    let repo = git2::Repository::open(".")?;
+    let mut commits = Vec::with_capacity(summaries.len());
+    for summary in summaries {
+        let commit = repo.find_commit(git2::Oid::from_str(&summary.id)?)?;
+        let stats = first_parent_diff(&repo, &commit, None)?.stats()?;
+        let author = commit.author();
+        commits.push(ExportedCommit {
+            id: summary.id,
+            author: author.name().unwrap_or_default().to_string(),
+            email: author.email().unwrap_or_default().to_string(),
+            date: git_time(author.when()),
+            message: summary.message,
+            files_changed: stats.files_changed(),
+            insertions: stats.insertions(),
+            deletions: stats.deletions(),
+        });
+    }
+    Ok(commits)
+}
+
+/// How often one file changed over a window of history
+#[derive(Debug, Clone)]
+pub struct FileChurn {
+    pub path: String,
+    pub commits: usize, // Commits that touched the file
+    pub insertions: usize,
+    pub deletions: usize,
+}
+
+/// A churn report, tied to the HEAD it was computed from so it can be cached
+#[derive(Debug, Clone)]
+pub struct ChurnReport {
+    pub head: String,
+    pub days: u32,
+    pub files: Vec<FileChurn>,
+}
+
+/// Full id of the HEAD commit
+pub fn head_id() -> Result<String, GitError> {
+    let repo = git2::Repository::open(".")?;
+    let commit = repo.head()?.peel_to_commit()?;
+    Ok(commit.id().to_string())
+}
+
+/// The commit a blamed line was last changed in
+#[derive(Debug, Clone)]
+pub struct BlameCommit {
+    pub short_id: String,
+    pub author: String,
+    pub time: chrono::DateTime<chrono::FixedOffset>, // Committed, on the committer's clock
+    pub summary: String,
+}
+
+/// One line of a blamed file
+#[derive(Debug, Clone)]
+pub struct BlameLine {
+    pub commit: Option<String>, // Full id of the commit that last changed it; None if not committed yet
+    pub text: String,
+}
+
+/// A file's lines as they are on disk, each with the commit that last changed it
+#[derive(Debug, Clone)]
+pub struct FileBlame {
+    pub path: String, // Relative to the repository root
+    pub lines: Vec<BlameLine>,
+    pub commits: HashMap<String, BlameCommit>, // Every commit the lines point at, by full id
+    pub head: Option<String>,                  // HEAD the blame was computed at
+    pub modified: Option<std::time::SystemTime>, // The file's mtime when it was read
+}
+
+impl FileBlame {
+    /// Whether the blame still describes the file: HEAD and the file are unchanged
+    pub fn is_current(&self) -> bool {
+        let modified = git2::Repository::open(".").ok().and_then(|repo| {
+            let workdir = repo.workdir()?.to_path_buf();
+            std::fs::metadata(workdir.join(&self.path))
+                .and_then(|meta| meta.modified())
+                .ok()
+        });
+        self.head == head_id().ok() && self.modified == modified
+    }
+}
+
+/// Blame the working copy of `path` (relative to the repository root), like `git blame`
+///
+/// Lines changed since HEAD have no commit.
+pub fn blame_file(path: &str) -> Result<FileBlame, GitError> {
+    let repo = git2::Repository::open(".")?;
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| GitError::Other("a bare repository has no files to blame".to_string()))?;
+    let full_path = workdir.join(path);
+    let contents = std::fs::read(&full_path)?;
+    if contents.contains(&0) {
+        return Err(GitError::Other(format!("{} is a binary file", path)));
+    }
+    let modified = std::fs::metadata(&full_path)
+        .and_then(|meta| meta.modified())
+        .ok();
+
+    let committed = match repo.blame_file(Path::new(path), None) {
+        Ok(blame) => blame,
+        Err(e) if e.code() == git2::ErrorCode::NotFound => {
+            return Err(GitError::Other(format!("{} isn't committed yet", path)));
+        }
+        Err(e) => return Err(e.into()),
+    };
+    let blame = committed.blame_buffer(&contents)?;
+
+    let mut lines = Vec::new();
+    let mut commits = HashMap::new();
+    // Legacy-encoded files read as Windows-1252 rather than as replacement characters
+    let text = match crate::encoding::detect(&contents) {
+        Some(crate::encoding::TextEncoding::Windows1252) => {
+            crate::encoding::decode(&contents, crate::encoding::TextEncoding::Windows1252)
+        }
+        _ => String::from_utf8_lossy(&contents).into_owned(),
+    };
+    for (idx, text) in text.lines().enumerate() {
+        let commit = blame
+            .get_line(idx + 1)
+            .map(|hunk| hunk.final_commit_id())
+            .filter(|oid| !oid.is_zero());
+        if let Some(oid) = commit {
+            if let std::collections::hash_map::Entry::Vacant(entry) =
+                commits.entry(oid.to_string())
+            {
+                let commit = repo.find_commit(oid)?;
+                entry.insert(BlameCommit {
+                    short_id: commit
+                        .as_object()
+                        .short_id()
+                        .ok()
+                        .and_then(|short| short.as_str().map(|s| s.to_string()))
+                        .unwrap_or_else(|| oid.to_string()[..7].to_string()),
+                    author: commit.author().name().unwrap_or_default().to_string(),
+                    time: git_time(commit.time()),
+                    summary: commit.summary().unwrap_or_default().to_string(),
+                });
+            }
+        }
+        lines.push(BlameLine {
+            commit: commit.map(|oid| oid.to_string()),
+            text: text.to_string(),
+        });
+    }
+
+    Ok(FileBlame {
+        path: path.to_string(),
+        lines,
+        commits,
+        head: head_id().ok(),
+        modified,
+    })
+}
+
+/// The most frequently changed files in the last `days`, most commits first
+///
+/// Each non-merge commit is diffed against its first parent; merges are skipped
+/// because they repeat the changes of the commits they bring in. This walks and
+/// diffs history, so run it off the UI thread.
+pub fn file_churn(days: u32) -> Result<ChurnReport, GitError> {
+    let repo = git2::Repository::open(".")?;
+    let head = repo.head()?.peel_to_commit()?.id();
+    let since = chrono::Utc::now().timestamp() - days as i64 * 86_400;
+
+    let mut walk = repo.revwalk()?;
+    walk.set_sorting(git2::Sort::TIME)?;
+    walk.push(head)?;
+
+    let mut by_path: HashMap<String, FileChurn> = HashMap::new();
+    for oid in walk {
+        let commit = repo.find_commit(oid?)?;
+        if commit.time().seconds() < since {
+            break;
+        }
+        if commit.parent_count() > 1 {
+            continue;
+        }
+        let tree = commit.tree()?;
+        let parent_tree = match commit.parent(0) {
+            Ok(parent) => Some(parent.tree()?),
+            Err(_) => None,
+        };
+        let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)?;
+        for (idx, delta) in diff.deltas().enumerate() {
+            let path = match delta.new_file().path().or_else(|| delta.old_file().path()) {
+                Some(path) => path.display().to_string(),
+                None => continue,
+            };
+            let (insertions, deletions) = match git2::Patch::from_diff(&diff, idx)? {
+                Some(patch) => {
+                    let (_, insertions, deletions) = patch.line_stats()?;
+                    (insertions, deletions)
+                }
+                None => (0, 0),
+            };
+            let entry = by_path.entry(path.clone()).or_insert(FileChurn {
+                path,
+                commits: 0,
+                insertions: 0,
+                deletions: 0,
+            });
+            entry.commits += 1;
+            entry.insertions += insertions;
+            entry.deletions += deletions;
+        }
+    }
+
+    let mut files: Vec<FileChurn> = by_path.into_values().collect();
+    files.sort_by(|a, b| {
+        b.commits
+            .cmp(&a.commits)
+            .then((b.insertions + b.deletions).cmp(&(a.insertions + a.deletions)))
+            .then(a.path.cmp(&b.path))
+    });
+    Ok(ChurnReport {
+        head: head.to_string(),
+        days,
+        files,
+    })
+}
+
+/// Commits reachable from HEAD and the number of local branches, as the Overview counts them
+pub fn repository_counts() -> Result<(usize, usize), GitError> {
+    let repo = git2::Repository::open(".")?;
+    let commits = match repo.head() {
+        Ok(_) => {
+            let mut walk = repo.revwalk()?;
+            walk.push_head()?;
+            walk.count()
+        }
+        Err(_) => 0, // No commits yet
+    };
+    let branches = repo.branches(Some(git2::BranchType::Local))?.count();
+    Ok((commits, branches))
+}
+
+/// Get the abbreviated id and subject line of the HEAD commit
+pub fn head_commit_summary() -> Result<(String, String), GitError> {
+    let repo = git2::Repository::open(".")?;
+    let commit = repo.head()?.peel_to_commit()?;
+    let short_id = commit
+        .as_object()
+        .short_id()?
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+    let subject = commit.summary().unwrap_or_default().to_string();
+    Ok((short_id, subject))
+}
+
+pub fn status() -> Result<Vec<GitFileStatus>, Box<dyn std::error::Error>> {
+    get_git_status()
+}
+
+pub fn push() -> Result<(), Box<dyn std::error::Error>> {
+    // TODO: Implement git push logic
+    Ok(())
+}
+
+pub fn pull_rebase() -> Result<(), Box<dyn std::error::Error>> {
+    // TODO: Implement git pull --rebase logic
+    Ok(())
+}
+
+// Helper function to format file size
+pub fn format_file_size(size: Option<u64>) -> String {
+    match size {
+        Some(bytes) => {
+            if bytes < 1024 {
+                format!("{} B", bytes)
+            } else if bytes < 1024 * 1024 {
+                format!("{:.1} KB", bytes as f64 / 1024.0)
+            } else if bytes < 1024 * 1024 * 1024 {
+                format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+            } else {
+                format!("{:.1} GB", bytes as f64 / (1024.0 * 1024.0 * 1024.0))
+            }
+        }
+        None => "-".to_string(),
+    }
+}
+
+/// Check if repository has a remote origin configured
+pub fn has_remote_origin() -> Result<bool, GitError> {
+    let repo = git2::Repository::open(".")?;
+    let result = repo.find_remote("origin");
+    match result {
+        Ok(_) => Ok(true),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(false),
+        Err(e) => Err(GitError::Git2(e)),
+    }
+}
+
+/// A configured remote as listed in Settings
+#[derive(Debug, Clone)]
+pub struct RemoteInfo {
+    pub name: String,
+    pub url: String,
+    pub push_url: Option<String>, // Set only when pushes go somewhere else
+}
+
+/// Every configured remote, origin first and the rest by name
+pub fn remote_details() -> Result<Vec<RemoteInfo>, GitError> {
+    let repo = git2::Repository::open(".")?;
+    let mut remotes = Vec::new();
+    for name in repo.remotes()?.iter().flatten() {
+        let remote = repo.find_remote(name)?;
+        remotes.push(RemoteInfo {
+            name: name.to_string(),
+            url: remote.url().unwrap_or_default().to_string(),
+            push_url: remote.pushurl().map(|url| url.to_string()),
+        });
+    }
+    remotes.sort_by(|a, b| (a.name != "origin", &a.name).cmp(&(b.name != "origin", &b.name)));
+    Ok(remotes)
+}
+
+/// Check that `name` can be used for a new remote
+pub fn validate_remote_name(name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("Enter a name for the remote".to_string());
+    }
+    if !git2::Remote::is_valid_name(name) {
+        return Err(format!("'{}' isn't a valid remote name", name));
+    }
+    Ok(())
+}
+
+/// Check that `url` is something git can fetch from: a URL with a scheme git
+/// speaks, an scp-style `user@host:path`, or an existing local repository path
+pub fn validate_remote_url(url: &str) -> Result<(), String> {
+    if url.is_empty() {
+        return Err("Enter the remote's URL".to_string());
+    }
+    if url.chars().any(char::is_whitespace) {
+        return Err("URLs can't contain spaces".to_string());
+    }
+    if let Some((scheme, rest)) = url.split_once("://") {
+        let host = rest.split('/').next().unwrap_or_default();
+        return match scheme {
+            "file" if rest.len() > 1 => Ok(()),
+            "https" | "http" | "ssh" | "git" | "git+ssh" | "ssh+git" if !host.is_empty() => {
+                Ok(())
+            }
+            "https" | "http" | "ssh" | "git" | "git+ssh" | "ssh+git" | "file" => {
+                Err(format!("'{}' is missing the host or path", url))
+            }
+            _ => Err(format!("git can't fetch over '{}://'", scheme)),
+        };
+    }
+    // user@host:path, as long as the colon comes before any slash
+    if let Some((host, path)) = url.split_once(':') {
+        if !host.is_empty() && !host.contains('/') && !path.is_empty() {
+            return Ok(());
+        }
+    }
+    if Path::new(url).is_dir() {
+        return Ok(());
+    }
+    Err(format!(
+        "'{}' isn't a URL, user@host:path or an existing directory",
+        url
+    ))
+}
+
+/// Add a remote with the default fetch refspec
+pub fn add_remote(name: &str, url: &str) -> Result<(), GitError> {
+    let repo = git2::Repository::open(".")?;
+    repo.remote(name, url)?;
+    Ok(())
+}
+
+/// Rename a remote, moving its remote-tracking branches and the branches that track it
+///
+/// Returns the refspecs git couldn't rewrite, which need fixing by hand.
+pub fn rename_remote(name: &str, new_name: &str) -> Result<Vec<String>, GitError> {
+    let repo = git2::Repository::open(".")?;
+    let problems = repo.remote_rename(name, new_name)?;
+    Ok(problems.iter().flatten().map(|p| p.to_string()).collect())
+}
+
+/// Remove a remote along with its remote-tracking branches and config
+pub fn remove_remote(name: &str) -> Result<(), GitError> {
+    let repo = git2::Repository::open(".")?;
+    repo.remote_delete(name)?;
+    Ok(())
+}
+
+/// Point a remote at a new URL for fetches and pushes alike
+pub fn set_remote_url(name: &str, url: &str) -> Result<(), GitError> {
+    let repo = git2::Repository::open(".")?;
+    repo.remote_set_url(name, url)?;
+    Ok(())
+}
+
+/// Get remote status information
+pub fn get_remote_status() -> Result<RemoteStatus, GitError> {
+    let repo = git2::Repository::open(".")?;
+
+    // Get remote origin
+    let remote = repo.find_remote("origin")?;
+    let remote_name = remote.name().unwrap_or("origin").to_string();
+    let remote_url = remote.url().unwrap_or("unknown").to_string();
+
+    // Get ahead/behind counts
+    let (ahead, behind) = get_ahead_behind_counts(&repo)?;
+
+    // Get last fetch time (from reflog)
+    let last_fetch = get_last_fetch_time(&repo);
+
+    Ok(RemoteStatus {
+        name: remote_name,
+        url: remote_url,
+        ahead,
+        behind,
+        last_fetch,
+    })
+}
+
+/// Get ahead/behind counts compared to remote tracking branch
+fn get_ahead_behind_counts(repo: &git2::Repository) -> Result<(usize, usize), GitError> {
+    // Get current branch
+    let head = repo.head()?;
+    let local_oid = head
+        .target()
+        .ok_or_else(|| GitError::Other("No HEAD commit".to_string()))?;
+
+    // Get remote tracking branch
+    let branch_name = head.shorthand().unwrap_or("HEAD");
+    let remote_branch_name = format!("origin/{}", branch_name);
+
+    match repo.find_branch(&remote_branch_name, git2::BranchType::Remote) {
+        Ok(remote_branch) => {
+            let remote_oid = remote_branch
+                .get()
+                .target()
+                .ok_or_else(|| GitError::Other("No remote branch commit".to_string()))?;
+
+            // Calculate ahead/behind
+            let (ahead, behind) = repo.graph_ahead_behind(local_oid, remote_oid)?;
+            Ok((ahead, behind))
+        }
+        Err(_) => {
+            // No remote tracking branch, assume we're ahead by the number of commits
+            let revwalk = repo.revwalk()?;
+            let mut walk = revwalk;
+            walk.push(local_oid)?;
+            let ahead = walk.count();
+            Ok((ahead, 0))
+        }
+    }
+}
+
+/// Get last fetch time from reflog
+fn get_last_fetch_time(repo: &git2::Repository) -> Option<chrono::DateTime<chrono::Local>> {
+    // Try to get the reflog for the remote tracking branch
+    if let Ok(reflog) = repo.reflog("refs/remotes/origin/HEAD") {
+        if let Some(entry) = reflog.iter().next() {
+            let time = entry.committer().when();
+            let datetime = chrono::DateTime::from_timestamp(time.seconds(), 0)?;
+            return Some(datetime.with_timezone(&chrono::Local));
+        }
+    }
+
+    // Fallback: check if .git/FETCH_HEAD exists and get its modification time
+    if let Ok(metadata) = std::fs::metadata(".git/FETCH_HEAD") {
+        if let Ok(modified) = metadata.modified() {
+            return Some(chrono::DateTime::<chrono::Local>::from(modified));
+        }
+    }
+
+    None
+}
+
+/// A git time on the clock of the machine that recorded it
+pub fn git_time(time: git2::Time) -> chrono::DateTime<chrono::FixedOffset> {
+    time_with_offset(time.seconds(), time.offset_minutes())
+}
+
+/// Seconds since the epoch on the clock `offset_minutes` east of UTC, as git stores times
+pub fn time_with_offset(seconds: i64, offset_minutes: i32) -> chrono::DateTime<chrono::FixedOffset> {
+    let offset = chrono::FixedOffset::east_opt(offset_minutes * 60)
+        .unwrap_or_else(|| chrono::FixedOffset::east_opt(0).unwrap());
+    chrono::DateTime::from_timestamp(seconds, 0)
+        .unwrap_or_default()
+        .with_timezone(&offset)
+}
+
+/// Format time relative to now (e.g., "2 minutes ago")
+pub fn format_relative_time(time: chrono::DateTime<chrono::Local>) -> String {
+    let now = chrono::Local::now();
+    let duration = now.signed_duration_since(time);
+
+    if duration.num_seconds() < 60 {
+        "Just now".to_string()
+    } else if duration.num_minutes() < 60 {
+        format!("{} minutes ago", duration.num_minutes())
+    } else if duration.num_hours() < 24 {
+        format!("{} hours ago", duration.num_hours())
+    } else {
+        format!("{} days ago", duration.num_days())
+    }
+}
+
+/// Format SystemTime as relative time string
+pub fn format_system_time_relative(system_time: std::time::SystemTime) -> String {
+    match system_time.duration_since(std::time::UNIX_EPOCH) {
+        Ok(duration) => {
+            if let Some(datetime) = chrono::DateTime::from_timestamp(duration.as_secs() as i64, 0) {
+                let local_time = datetime.with_timezone(&chrono::Local);
+                format_relative_time(local_time)
+            } else {
+                "Unknown time".to_string()
+            }
+        }
+        Err(_) => "Unknown time".to_string(),
+    }
+}
+
+/// Fetch from remote origin
+pub fn fetch_origin(progress: Option<&ProgressSender>) -> Result<SyncOperation, GitError> {
+    fetch_remote("origin", progress)
+}
+
+/// Fetch from a named remote
+pub fn fetch_remote(
+    remote_name: &str,
+    progress: Option<&ProgressSender>,
+) -> Result<SyncOperation, GitError> {
+    let start_time = std::time::SystemTime::now();
+
+    // Try git2-rs first, but with a fallback to git command
+    match fetch_remote_git2(remote_name, progress) {
+        Ok(operation) => Ok(operation),
+        // Cancelled at the credential prompt; git would only ask again
+        Err(GitError::Git2(e)) if e.code() == git2::ErrorCode::User => {
+            Err(GitError::Other(e.message().to_string()))
+        }
+        Err(_e) => {
+            // Silent fallback to git command - this is expected for some SSH configurations
+            fetch_remote_fallback(remote_name, start_time)
+        }
+    }
+}
+
+/// List the names of all configured remotes
+pub fn list_remotes() -> Result<Vec<String>, GitError> {
+    let repo = git2::Repository::open(".")?;
+    let remotes = repo.remotes()?;
+    Ok(remotes.iter().flatten().map(|name| name.to_string()).collect())
+}
+
+/// Fetch every configured remote concurrently, one thread per remote
+///
+/// Returns one operation per remote in configuration order; a remote that
+/// fails to fetch is reported as an error operation rather than aborting the rest.
+pub fn fetch_all_remotes() -> Result<Vec<(String, SyncOperation)>, GitError> {
+    let remotes = list_remotes()?;
+
+    let handles: Vec<_> = remotes
+        .into_iter()
+        .map(|name| {
+            // Each thread opens its own repository handle; git2 handles aren't shared across threads
+            let thread_name = name.clone();
+            let handle = std::thread::spawn(move || fetch_remote(&thread_name, None));
+            (name, handle)
+        })
+        .collect();
+
+    Ok(handles
+        .into_iter()
+        .map(|(name, handle)| {
+            let operation = match handle.join() {
+                Ok(Ok(operation)) => operation,
+                Ok(Err(e)) => SyncOperation {
+                    operation_type: SyncOperationType::Fetch,
+                    status: OperationStatus::Error,
+                    message: format!("Failed to fetch: {}", e),
+                    timestamp: std::time::SystemTime::now(),
+                },
+                Err(_) => SyncOperation {
+                    operation_type: SyncOperationType::Fetch,
+                    status: OperationStatus::Error,
+                    message: "Fetch thread panicked".to_string(),
+                    timestamp: std::time::SystemTime::now(),
+                },
+            };
+            (name, operation)
+        })
+        .collect())
+}
+
+/// Fetch using git2-rs
+fn fetch_remote_git2(
+    remote_name: &str,
+    progress: Option<&ProgressSender>,
+) -> Result<SyncOperation, GitError> {
+    let start_time = std::time::SystemTime::now();
+
+    let repo = git2::Repository::open(".")?;
+    let mut remote = repo.find_remote(remote_name)?;
+
+    // Authentication (asking in the TUI when nothing else works) and progress
+    let mut callbacks = git2::RemoteCallbacks::new();
+    callbacks.credentials(crate::credentials::callback());
+
+    callbacks.transfer_progress(|stats| {
+        if let Some(progress) = progress {
+            let (stage, current, total) = if stats.received_objects() < stats.total_objects() {
+                ("Receiving objects", stats.received_objects(), stats.total_objects())
+            } else {
+                ("Resolving deltas", stats.indexed_deltas(), stats.total_deltas())
+            };
+            let _ = progress.send(TransferProgress {
+                stage,
+                current,
+                total,
+                bytes: stats.received_bytes(),
+                detail: None,
+            });
+        }
+        true
+    });
+
+    // Set up fetch options
+    let mut fetch_options = git2::FetchOptions::new();
+    fetch_options.remote_callbacks(callbacks);
+
+    match remote.fetch(&[] as &[&str], Some(&mut fetch_options), None) {
+        Ok(()) => Ok(SyncOperation {
+            operation_type: SyncOperationType::Fetch,
+            status: OperationStatus::Success,
+            message: "Successfully fetched from remote".to_string(),
+            timestamp: start_time,
+        }),
+        Err(e) => Err(GitError::Git2(e)),
+    }
+}
+
+/// Fallback fetch using git command
+fn fetch_remote_fallback(
+    remote_name: &str,
+    start_time: std::time::SystemTime,
+) -> Result<SyncOperation, GitError> {
+    let output = std::process::Command::new("git")
+        .args(&["fetch", remote_name])
+        .env("GIT_TERMINAL_PROMPT", "0") // The TUI owns the terminal
+        .output()
+        .map_err(GitError::Io)?;
+
+    if output.status.success() {
+        Ok(SyncOperation {
+            operation_type: SyncOperationType::Fetch,
+            status: OperationStatus::Success,
+            message: "Successfully fetched from remote (fallback)".to_string(),
+            timestamp: start_time,
+        })
+    } else {
+        Ok(SyncOperation {
+            operation_type: SyncOperationType::Fetch,
+            status: OperationStatus::Error,
+            message: format!(
+                "Failed to fetch: {}",
+                String::from_utf8_lossy(&output.stderr)
+            ),
+            timestamp: start_time,
+        })
+    }
+}
+
+/// Pull from remote origin (with optional rebase)
+pub fn pull_origin(
+    use_rebase: bool,
+    progress: Option<&ProgressSender>,
+) -> Result<SyncOperation, GitError> {
+    let start_time = std::time::SystemTime::now();
+
+    // First fetch
+    let fetch_result = fetch_origin(progress)?;
+    if matches!(fetch_result.status, OperationStatus::Error) {
+        return Ok(SyncOperation {
+            operation_type: SyncOperationType::Pull,
+            status: OperationStatus::Error,
+            message: format!("Pull failed during fetch: {}", fetch_result.message),
+            timestamp: start_time,
+        });
+    }
+
+    let repo = git2::Repository::open(".")?;
+
+    // Get current branch and remote tracking branch
+    let head = repo.head()?;
+    let local_oid = head
+        .target()
+        .ok_or_else(|| GitError::Other("No HEAD commit".to_string()))?;
+
+    let branch_name = head.shorthand().unwrap_or("HEAD");
+    let remote_branch_name = format!("origin/{}", branch_name);
+
+    let remote_branch = match repo.find_branch(&remote_branch_name, git2::BranchType::Remote) {
+        Ok(branch) => branch,
+        Err(_) => {
+            return Ok(SyncOperation {
+                operation_type: SyncOperationType::Pull,
+                status: OperationStatus::Error,
+                message: "No remote tracking branch found".to_string(),
+                timestamp: start_time,
+            });
+        }
+    };
+
+    let remote_oid = remote_branch
+        .get()
+        .target()
+        .ok_or_else(|| GitError::Other("No remote branch commit".to_string()))?;
+
+    // Check if we're already up to date
+    if local_oid == remote_oid {
+        return Ok(SyncOperation {
+            operation_type: SyncOperationType::Pull,
+            status: OperationStatus::Success,
+            message: "Already up to date".to_string(),
+            timestamp: start_time,
+        });
+    }
+
+    // Perform merge or rebase
+    if use_rebase {
+        match perform_rebase(&repo, local_oid, remote_oid, progress) {
+            Ok(()) => Ok(SyncOperation {
+                operation_type: SyncOperationType::Pull,
+                status: OperationStatus::Success,
+                message: "Successfully rebased local changes".to_string(),
+                timestamp: start_time,
+            }),
+            Err(e) => Ok(SyncOperation {
+                operation_type: SyncOperationType::Pull,
+                status: OperationStatus::Error,
+                message: format!("Rebase failed: {}", e),
+                timestamp: start_time,
+            }),
+        }
+    } else {
+        if let Some(progress) = progress {
+            let _ = progress.send(TransferProgress {
+                stage: "Merging",
+                current: 0,
+                total: 0,
+                bytes: 0,
+                detail: Some(format!("{} into {}", remote_branch_name, branch_name)),
+            });
+        }
+        match perform_merge(&repo, remote_oid) {
+            Ok(()) => Ok(SyncOperation {
+                operation_type: SyncOperationType::Pull,
+                status: OperationStatus::Success,
+                message: "Successfully merged remote changes".to_string(),
+                timestamp: start_time,
+            }),
+            Err(e) => Ok(SyncOperation {
+                operation_type: SyncOperationType::Pull,
+                status: OperationStatus::Error,
+                message: format!("Merge failed: {}", e),
+                timestamp: start_time,
+            }),
+        }
+    }
+}
+
+/// Perform a rebase operation, reporting each commit as it is applied
+fn perform_rebase(
+    repo: &git2::Repository,
+    local_oid: git2::Oid,
+    remote_oid: git2::Oid,
+    progress: Option<&ProgressSender>,
+) -> Result<(), GitError> {
+    // Get commits for rebase
+    let local_commit = repo.find_commit(local_oid)?;
+    let remote_commit = repo.find_commit(remote_oid)?;
+
+    // Find merge base
+    let merge_base = repo.merge_base(local_oid, remote_oid)?;
+    let base_commit = repo.find_commit(merge_base)?;
+
+    // Create AnnotatedCommit objects for rebase
+    // Rebase the checked-out branch itself so HEAD stays attached afterwards
+    let local_annotated = match repo.head() {
+        Ok(head) if head.is_branch() && head.target() == Some(local_oid) => {
+            repo.reference_to_annotated_commit(&head)?
+        }
+        _ => repo.find_annotated_commit(local_oid)?,
+    };
+    let base_annotated = repo.find_annotated_commit(merge_base)?;
+    let remote_annotated = repo.find_annotated_commit(remote_oid)?;
+
+    // Resolve the committer before touching HEAD so a missing identity can't strand a rebase
+    let signature = repo.signature()?;
+
+    // Initialize rebase
+    let mut rebase = repo.rebase(
+        Some(&local_annotated),
+        Some(&base_annotated),
+        Some(&remote_annotated),
+        None,
+    )?;
+
+    // Process each rebase operation, restoring the original branch if any step fails
+    let result = (|| -> Result<(), git2::Error> {
+        let total = rebase.len();
+        while let Some(operation) = rebase.next() {
+            let operation = operation?;
+            if let Some(progress) = progress {
+                let subject = repo
+                    .find_commit(operation.id())
+                    .ok()
+                    .and_then(|commit| commit.summary().map(|s| s.to_string()));
+                let _ = progress.send(TransferProgress {
+                    stage: "Applying",
+                    current: rebase.operation_current().map_or(0, |idx| idx + 1),
+                    total,
+                    bytes: 0,
+                    detail: subject,
+                });
+            }
+
+            // Commit the rebased changes
+            rebase.commit(None, &signature, None)?;
+        }
+        Ok(())
+    })();
+
+    if let Err(e) = result {
+        let _ = rebase.abort();
+        return Err(GitError::Git2(e));
+    }
+
+    // Finish the rebase
+    rebase.finish(None)?;
+
+    Ok(())
+}
+
+/// Perform a merge operation
+fn perform_merge(repo: &git2::Repository, remote_oid: git2::Oid) -> Result<(), GitError> {
+    // Get the remote commit
+    let remote_commit = repo.find_commit(remote_oid)?;
+    let remote_tree = remote_commit.tree()?;
+
+    // Get current HEAD
+    let head = repo.head()?;
+    let local_commit = head.peel_to_commit()?;
+    let local_tree = local_commit.tree()?;
+
+    // Find common ancestor
+    let merge_base = repo.merge_base(local_commit.id(), remote_oid)?;
+    let base_commit = repo.find_commit(merge_base)?;
+    let base_tree = base_commit.tree()?;
+
+    // Perform the merge
+    let mut index = repo.merge_trees(&base_tree, &local_tree, &remote_tree, None)?;
+    let message = format!(
+        "Merge remote-tracking branch 'origin/{}'",
+        head.shorthand().unwrap_or("HEAD")
+    );
+
+    // Leave a real merge in progress so the conflicts can be resolved and the merge continued
+    if index.has_conflicts() {
+        let remote_annotated = repo.find_annotated_commit(remote_oid)?;
+        repo.merge(&[&remote_annotated], None, None)?;
+        std::fs::write(repo.path().join("MERGE_MSG"), format!("{}\n", message))?;
+        return Err(GitError::Other("Merge conflicts detected".to_string()));
+    }
+
+    // Write the merged index
+    let tree_oid = index.write_tree_to(repo)?;
+    let tree = repo.find_tree(tree_oid)?;
+
+    // Create merge commit
+    let signature = repo.signature()?;
+
+    repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        &message,
+        &tree,
+        &[&local_commit, &remote_commit],
+    )?;
+
+    Ok(())
+}
+
+/// A path the index records as conflicted, with each side's content
+#[derive(Debug, Clone)]
+pub struct ConflictEntry {
+    pub path: String,
+    pub base: Option<String>,   // Common ancestor; None when both sides added the file
+    pub ours: Option<String>,   // Our version; None when we deleted the file
+    pub theirs: Option<String>, // Their version; None when they deleted the file
+}
+
+/// Which side of a conflict to keep
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConflictSide {
+    Ours,
+    Theirs,
+}
+
+/// List the conflicted paths in the index with their base, ours and theirs content
+pub fn list_conflicts() -> Result<Vec<ConflictEntry>, GitError> {
+    let repo = git2::Repository::open(".")?;
+    let index = repo.index()?;
+    if !index.has_conflicts() {
+        return Ok(Vec::new());
+    }
+
+    let blob_text = |entry: Option<git2::IndexEntry>| {
+        entry
+            .and_then(|entry| repo.find_blob(entry.id).ok())
+            .map(|blob| crate::encoding::decode_lossy(blob.content()))
+    };
+
+    let mut conflicts = Vec::new();
+    for conflict in index.conflicts()? {
+        let conflict = conflict?;
+        let path = [&conflict.our, &conflict.their, &conflict.ancestor]
+            .into_iter()
+            .flatten()
+            .next()
+            .map(|entry| String::from_utf8_lossy(&entry.path).into_owned())
+            .unwrap_or_default();
+        conflicts.push(ConflictEntry {
+            path,
+            base: blob_text(conflict.ancestor),
+            ours: blob_text(conflict.our),
+            theirs: blob_text(conflict.their),
+        });
+    }
+    conflicts.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(conflicts)
+}
+
+/// Whether the index has any conflicted paths
+pub fn has_conflicts() -> bool {
+    git2::Repository::open(".")
+        .and_then(|repo| repo.index())
+        .is_ok_and(|index| index.has_conflicts())
+}
+
+/// Paths the index still has conflicts for, without reading their sides
+pub fn conflicted_paths() -> Vec<String> {
+    let index = match git2::Repository::open(".").and_then(|repo| repo.index()) {
+        Ok(index) if index.has_conflicts() => index,
+        _ => return Vec::new(),
+    };
+    let mut paths: Vec<String> = match index.conflicts() {
+        Ok(conflicts) => conflicts
+            .filter_map(Result::ok)
+            .filter_map(|conflict| conflict.our.or(conflict.their).or(conflict.ancestor))
+            .map(|entry| String::from_utf8_lossy(&entry.path).into_owned())
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    paths.sort();
+    paths
+}
+
+/// Whether a merge has stopped and is waiting to be committed
+pub fn merge_in_progress() -> bool {
+    git2::Repository::open(".").is_ok_and(|repo| repo.state() == git2::RepositoryState::Merge)
+}
+
+/// Whether a cherry-pick has stopped on conflicts, whoever started it
+///
+/// A rebase planned in gitix stops as a cherry-pick too; that counts as a rebase.
+pub fn cherry_pick_in_progress() -> bool {
+    git2::Repository::open(".").is_ok_and(|repo| {
+        matches!(
+            repo.state(),
+            git2::RepositoryState::CherryPick | git2::RepositoryState::CherryPickSequence
+        ) && !PlannedRebase::path(&repo).exists()
+    })
+}
+
+/// Whether a rebase has stopped part-way, whoever started it
+pub fn rebase_in_progress() -> bool {
+    git2::Repository::open(".").is_ok_and(|repo| {
+        matches!(
+            repo.state(),
+            git2::RepositoryState::Rebase
+                | git2::RepositoryState::RebaseInteractive
+                | git2::RepositoryState::RebaseMerge
+        ) || PlannedRebase::path(&repo).exists()
+    })
+}
+
+/// Resolve a conflict by keeping one side: write it to the working tree and stage it
+///
+/// When the chosen side deleted the file, the file is removed instead.
+pub fn resolve_conflict(path: &str, side: ConflictSide) -> Result<(), GitError> {
+    let repo = git2::Repository::open(".")?;
+    let mut index = repo.index()?;
+    let conflict = index
+        .conflicts()?
+        .filter_map(Result::ok)
+        .find(|conflict| {
+            [&conflict.our, &conflict.their, &conflict.ancestor]
+                .into_iter()
+                .flatten()
+                .any(|entry| entry.path == path.as_bytes())
+        })
+        .ok_or_else(|| GitError::Other(format!("{} is not conflicted", path)))?;
+
+    let chosen = match side {
+        ConflictSide::Ours => conflict.our,
+        ConflictSide::Theirs => conflict.their,
+    };
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| GitError::Other("Repository has no working tree".to_string()))?;
+    let file = workdir.join(path);
+
+    index.remove_path(Path::new(path))?;
+    match chosen {
+        Some(entry) => {
+            let blob = repo.find_blob(entry.id)?;
+            if let Some(parent) = file.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            // Never write through a symlink left by the other side
+            if file.is_symlink() {
+                std::fs::remove_file(&file)?;
+            }
+            let is_link = entry.mode == u32::from(git2::FileMode::Link);
+            #[cfg(unix)]
+            {
+                use std::os::unix::ffi::OsStrExt;
+                use std::os::unix::fs::PermissionsExt;
+                if is_link {
+                    std::os::unix::fs::symlink(std::ffi::OsStr::from_bytes(blob.content()), &file)?;
+                } else {
+                    std::fs::write(&file, blob.content())?;
+                    // Executable wherever it is readable, like checkout does under the umask
+                    let mut permissions = std::fs::metadata(&file)?.permissions();
+                    let bits = permissions.mode();
+                    permissions.set_mode(
+                        if entry.mode == u32::from(git2::FileMode::BlobExecutable) {
+                            bits | (bits & 0o444) >> 2
+                        } else {
+                            bits & !0o111
+                        },
+                    );
+                    std::fs::set_permissions(&file, permissions)?;
+                }
+            }
+            #[cfg(not(unix))]
+            std::fs::write(&file, blob.content())?;
+
+            // add_path takes the mode from the disk, which has no executable bit or symlinks
+            // everywhere; stage the mode the chosen side had
+            index.add_path(Path::new(path))?;
+            if let Some(mut staged) = index.get_path(Path::new(path), 0) {
+                if staged.mode != entry.mode {
+                    staged.mode = entry.mode;
+                    index.add(&staged)?;
+                }
+            }
+        }
+        None => {
+            if file.exists() || file.is_symlink() {
+                std::fs::remove_file(&file)?;
+            }
+        }
+    }
+    index.write()?;
+    Ok(())
+}
+
+/// Stage a conflicted file as it is in the working tree, e.g. after editing out the markers
+pub fn mark_conflict_resolved(path: &str) -> Result<(), GitError> {
+    let repo = git2::Repository::open(".")?;
+    let mut index = repo.index()?;
+    let exists = repo.workdir().is_some_and(|workdir| workdir.join(path).exists());
+    if exists {
+        index.add_path(Path::new(path))?;
+    } else {
+        index.remove_path(Path::new(path))?;
+    }
+    index.write()?;
+    Ok(())
+}
+
+/// Run `git mergetool` on one conflicted file, in the current terminal
+///
+/// The tool is whatever `merge.tool` names, or git's own pick when it is unset.
+/// Git stages the file itself when the tool reports success.
+pub fn run_mergetool(path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let status = std::process::Command::new("git")
+        .args(["mergetool", "--no-prompt", "--", path])
+        .status()?;
+    if !status.success() {
+        return Err(format!("git mergetool exited with {}", status).into());
+    }
+    Ok(())
+}
+
+/// The first line (1-based) of a working tree file that is still a conflict marker
+pub fn conflict_marker_line(path: &str) -> Option<usize> {
+    let repo = git2::Repository::open(".").ok()?;
+    let content = std::fs::read_to_string(repo.workdir()?.join(path)).ok()?;
+    content
+        .lines()
+        .position(|line| {
+            line.starts_with("<<<<<<< ") || line == "=======" || line.starts_with(">>>>>>> ")
+        })
+        .map(|index| index + 1)
+}
+
+/// Commit an in-progress merge once every conflict is resolved; returns the short commit id
+pub fn continue_merge() -> Result<String, GitError> {
+    let repo = git2::Repository::open(".")?;
+    if repo.state() != git2::RepositoryState::Merge {
+        return Err(GitError::Other(
+            "No merge is in progress; commit the resolved files from Save Changes".to_string(),
+        ));
+    }
+    if repo.index()?.has_conflicts() {
+        return Err(GitError::Other(
+            "Resolve every conflicted file before continuing the merge".to_string(),
+        ));
+    }
+
+    // The regular commit path signs, runs the hooks and cleans up after the merge
+    commit(&repo.message()?, false).map_err(|e| GitError::Other(e.to_string()))?;
+
+    let id = repo.head()?.peel_to_commit()?.id().to_string();
+    Ok(id[..7].to_string())
+}
+
+/// How a cherry-pick went
+#[derive(Debug, Clone)]
+pub enum CherryPickOutcome {
+    Committed(String),      // Short id of the new commit
+    Conflicts(Vec<String>), // Stopped with these files conflicted, like `git cherry-pick` does
+}
+
+/// Apply the commit `id` onto the current branch as a new commit, like `git cherry-pick`
+///
+/// The new commit keeps the original's author and message. Staged changes are
+/// refused rather than folded into it, and unstaged ones in files the commit
+/// touches make libgit2's checkout refuse. On conflicts the cherry-pick is left
+/// in progress for the conflicts view, which commits it once they're resolved.
+pub fn cherry_pick(id: &str) -> Result<CherryPickOutcome, GitError> {
+    let repo = git2::Repository::open(".")?;
+    if repo.state() != git2::RepositoryState::Clean {
+        return Err(GitError::Other(
+            "A merge, rebase or cherry-pick is in progress. Finish or abort it first.".to_string(),
+        ));
+    }
+    let commit = repo.find_commit(git2::Oid::from_str(id)?)?;
+    let short = &id[..7.min(id.len())];
+    if commit.parent_count() > 1 {
+        return Err(GitError::Other(format!(
+            "{} is a merge commit; pick one of the commits it merged instead",
+            short
+        )));
+    }
+    let head = match repo.head() {
+        Ok(head) => head.peel_to_commit()?,
+        Err(e) if e.code() == git2::ErrorCode::UnbornBranch => {
+            return Err(GitError::Other(
+                "This branch has no commits yet to cherry-pick onto".to_string(),
+            ))
+        }
+        Err(e) => return Err(e.into()),
+    };
+    if head.id() == commit.id() || repo.graph_descendant_of(head.id(), commit.id())? {
+        return Err(GitError::Other(format!("{} is already on this branch", short)));
+    }
+    let staged = repo.diff_tree_to_index(Some(&head.tree()?), None, None)?;
+    if staged.deltas().len() > 0 {
+        return Err(GitError::Other(
+            "Commit or unstage your staged changes before cherry-picking".to_string(),
+        ));
+    }
+
+    repo.cherrypick(&commit, None).map_err(|e| match e.class() {
+        git2::ErrorClass::Checkout => GitError::Other(format!(
+            "Your uncommitted changes to files {} touches would be overwritten. \
+             Commit or stash them first.",
+            short
+        )),
+        _ => GitError::Git2(e),
+    })?;
+    let mut index = repo.index()?;
+    if index.has_conflicts() {
+        return Ok(CherryPickOutcome::Conflicts(conflicted_paths()));
+    }
+    if index.write_tree()? == head.tree_id() {
+        repo.cleanup_state()?;
+        return Err(GitError::Other(format!(
+            "The changes of {} are already on this branch; nothing was committed",
+            short
+        )));
+    }
+    let oid = commit_cherry_pick(&repo, &commit, &mut index)?;
+    Ok(CherryPickOutcome::Committed(oid.to_string()[..7].to_string()))
+}
+
+/// Commit a cherry-pick whose conflicts are resolved; returns the short commit id
+pub fn continue_cherry_pick() -> Result<String, GitError> {
+    let repo = git2::Repository::open(".")?;
+    let mut index = repo.index()?;
+    if index.has_conflicts() {
+        return Err(GitError::Other(
+            "Resolve every conflicted file before continuing the cherry-pick".to_string(),
+        ));
+    }
+    let picked = repo.revparse_single("CHERRY_PICK_HEAD")?.peel_to_commit()?;
+    let oid = commit_cherry_pick(&repo, &picked, &mut index)?;
+    Ok(oid.to_string()[..7].to_string())
+}
+
+/// Commit the index on HEAD as `picked` re-made here, then end the cherry-pick
+fn commit_cherry_pick(
+    repo: &git2::Repository,
+    picked: &git2::Commit,
+    index: &mut git2::Index,
+) -> Result<git2::Oid, GitError> {
+    let tree = repo.find_tree(index.write_tree()?)?;
+    let committer = repo.signature().map_err(|_| {
+        GitError::Other(
+            "Set your name and email (user.name and user.email) in Settings before committing"
+                .to_string(),
+        )
+    })?;
+    let head = repo.head()?;
+    let parent = head.peel_to_commit()?;
+    let message = picked.message().unwrap_or_default();
+    let oid = repo.commit(None, &picked.author(), &committer, message, &tree, &[&parent])?;
+
+    let reflog_message = format!("cherry-pick: {}", picked.summary().unwrap_or_default());
+    match head.name().filter(|_| head.is_branch()) {
+        Some(branch) => repo.reference(branch, oid, true, &reflog_message)?,
+        None => repo.reference("HEAD", oid, true, &reflog_message)?,
+    };
+    repo.cleanup_state()?; // CHERRY_PICK_HEAD and MERGE_MSG
+    Ok(oid)
+}
+
+/// Abort the merge, rebase or cherry-pick in progress; returns which it was
+///
+/// Runs `git merge --abort`, `git rebase --abort` or `git cherry-pick --abort`,
+/// which put HEAD, the index and the working tree back as they were before it
+/// started (ORIG_HEAD for a merge, the original branch tip for a rebase).
+/// A rebase planned in gitix is aborted by `abort_planned_rebase` instead.
+pub fn abort_operation() -> Result<&'static str, Box<dyn std::error::Error>> {
+    if planned_rebase_in_progress() {
+        abort_planned_rebase()?;
+        return Ok("rebase");
+    }
+    let operation = if merge_in_progress() {
+        "merge"
+    } else if rebase_in_progress() {
+        "rebase"
+    } else if cherry_pick_in_progress() {
+        "cherry-pick"
+    } else {
+        return Err("No merge, rebase or cherry-pick is in progress".into());
+    };
+    let output = std::process::Command::new("git")
+        .args([operation, "--abort"])
+        .output()?;
+    if !output.status.success() {
+        return Err(format!(
+            "git {} --abort failed: {}",
+            operation,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )
+        .into());
+    }
+    Ok(operation)
+}
+
+/// What an interactive rebase does with one commit
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RebaseAction {
+    Pick,   // Replay it as it is
+    Squash, // Fold it into the commit before, joining both messages
+    Fixup,  // Fold it into the commit before, keeping only that one's message
+    Drop,   // Leave it out
+}
+
+impl RebaseAction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RebaseAction::Pick => "pick",
+            RebaseAction::Squash => "squash",
+            RebaseAction::Fixup => "fixup",
+            RebaseAction::Drop => "drop",
+        }
+    }
+
+    fn parse(word: &str) -> Option<Self> {
+        match word {
+            "pick" => Some(RebaseAction::Pick),
+            "squash" => Some(RebaseAction::Squash),
+            "fixup" => Some(RebaseAction::Fixup),
+            "drop" => Some(RebaseAction::Drop),
+            _ => None,
+        }
+    }
+}
+
+/// One row of an interactive rebase plan
+#[derive(Debug, Clone)]
+pub struct RebaseStep {
+    pub action: RebaseAction,
+    pub commit: CommitSummary,
+}
+
+/// How a planned rebase went
+#[derive(Debug, Clone)]
+pub enum PlannedRebaseOutcome {
+    Rebased(String),        // Short id of the branch's new tip
+    Conflicts(Vec<String>), // Stopped with these files conflicted, to continue or abort
+}
+
+/// A planned rebase on its way, kept in .git/gitix-rebase-todo so it survives a stop on conflicts
+struct PlannedRebase {
+    head_name: String,    // The branch being rebased, e.g. refs/heads/main
+    orig_head: git2::Oid, // Where it was before
+    onto: git2::Oid,      // The upstream commit the steps are replayed on
+    done: usize,          // Steps already carried out
+    steps: Vec<(RebaseAction, git2::Oid)>,
+}
+
+impl PlannedRebase {
+    fn path(repo: &git2::Repository) -> PathBuf {
+        repo.path().join("gitix-rebase-todo")
+    }
+
+    fn load(repo: &git2::Repository) -> Result<Self, GitError> {
+        let text = std::fs::read_to_string(Self::path(repo))
+            .map_err(|_| GitError::Other("No interactive rebase is in progress".to_string()))?;
+        let corrupt =
+            || GitError::Other("The rebase plan in .git/gitix-rebase-todo is damaged".to_string());
+        let mut head_name = None;
+        let mut orig_head = None;
+        let mut onto = None;
+        let mut done = 0;
+        let mut steps = Vec::new();
+        for line in text.lines() {
+            let (key, value) = line.split_once(' ').ok_or_else(corrupt)?;
+            match key {
+                "head-name" => head_name = Some(value.to_string()),
+                "orig-head" => orig_head = Some(git2::Oid::from_str(value)?),
+                "onto" => onto = Some(git2::Oid::from_str(value)?),
+                "done" => done = value.parse().map_err(|_| corrupt())?,
+                action => steps.push((
+                    RebaseAction::parse(action).ok_or_else(corrupt)?,
+                    git2::Oid::from_str(value)?,
+                )),
+            }
+        }
+        Ok(PlannedRebase {
+            head_name: head_name.ok_or_else(corrupt)?,
+            orig_head: orig_head.ok_or_else(corrupt)?,
+            onto: onto.ok_or_else(corrupt)?,
+            done,
+            steps,
+        })
+    }
+
+    fn save(&self, repo: &git2::Repository) -> Result<(), GitError> {
+        let mut text = format!(
+            "head-name {}\norig-head {}\nonto {}\ndone {}\n",
+            self.head_name, self.orig_head, self.onto, self.done
+        );
+        for (action, oid) in &self.steps {
+            text.push_str(&format!("{} {}\n", action.as_str(), oid));
+        }
+        std::fs::write(Self::path(repo), text)?;
+        Ok(())
+    }
+}
+
+/// Whether an interactive rebase started from gitix has stopped on conflicts
+pub fn planned_rebase_in_progress() -> bool {
+    git2::Repository::open(".").is_ok_and(|repo| PlannedRebase::path(&repo).exists())
+}
+
+/// The branch a planned rebase that has stopped is rebasing, e.g. main
+pub fn planned_rebase_branch() -> Option<String> {
+    let repo = git2::Repository::open(".").ok()?;
+    let plan = PlannedRebase::load(&repo).ok()?;
+    Some(plan.head_name.trim_start_matches("refs/heads/").to_string())
+}
+
+/// The current branch's upstream: its name, tip and the commit the two last shared
+fn rebase_upstream(repo: &git2::Repository) -> Result<(String, git2::Oid, git2::Oid), GitError> {
+    let head = repo.head()?;
+    let branch_name = match head.shorthand().filter(|_| head.is_branch()) {
+        Some(name) => name.to_string(),
+        None => {
+            return Err(GitError::Other(
+                "HEAD is detached; check out a branch to rebase it".to_string(),
+            ))
+        }
+    };
+    let upstream = repo
+        .find_branch(&branch_name, git2::BranchType::Local)?
+        .upstream()
+        .map_err(|_| {
+            GitError::Other(format!(
+                "{} has no upstream branch to rebase onto; push it or set one first",
+                branch_name
+            ))
+        })?;
+    let upstream_name = upstream.name()?.unwrap_or("upstream").to_string();
+    let upstream_oid = upstream.get().peel_to_commit()?.id();
+    let head_oid = head.peel_to_commit()?.id();
+    let base = repo.merge_base(head_oid, upstream_oid)?;
+    Ok((upstream_name, upstream_oid, base))
+}
+
+/// The commits on the current branch that its upstream doesn't have, oldest first,
+/// with the upstream's name: what an interactive rebase would replay
+pub fn rebase_plan_commits() -> Result<(String, Vec<CommitSummary>), GitError> {
+    let repo = git2::Repository::open(".")?;
+    let (upstream_name, _, base) = rebase_upstream(&repo)?;
+    let mut walk = repo.revwalk()?;
+    walk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE)?;
+    walk.push_head()?;
+    walk.hide(base)?;
+
+    let mut commits = Vec::new();
+    for oid in walk {
+        let commit = repo.find_commit(oid?)?;
+        if commit.parent_count() > 1 {
+            return Err(GitError::Other(format!(
+                "{} is a merge commit; interactive rebase only replays plain commits",
+                &commit.id().to_string()[..7]
+            )));
+        }
+        commits.push(CommitSummary::from_commit(&commit));
+    }
+    if commits.is_empty() {
+        return Err(GitError::Other(format!(
+            "There are no commits on top of {} to rebase",
+            upstream_name
+        )));
+    }
+    Ok((upstream_name, commits))
+}
+
+/// Replay the current branch on its upstream as `steps` say, like `git rebase -i @{upstream}`
+///
+/// libgit2's rebase only replays commits in their original order, so each
+/// step is a cherry-pick committed on a detached HEAD, and the branch moves
+/// once all are done. Every step is in the reflog as "rebase (...)", so the
+/// result can be undone from History. On conflicts the rebase stops with the
+/// step's cherry-pick in progress, for `continue_planned_rebase` or
+/// `abort_planned_rebase`.
+pub fn start_planned_rebase(
+    steps: &[(RebaseAction, String)],
+) -> Result<PlannedRebaseOutcome, GitError> {
+    let repo = git2::Repository::open(".")?;
+    if repo.state() != git2::RepositoryState::Clean || PlannedRebase::path(&repo).exists() {
+        return Err(GitError::Other(
+            "A merge, rebase or cherry-pick is in progress. Finish or abort it first.".to_string(),
+        ));
+    }
+    match steps.iter().find(|(action, _)| *action != RebaseAction::Drop) {
+        Some((RebaseAction::Pick, _)) => {}
+        Some(_) => {
+            return Err(GitError::Other(
+                "The first commit kept has to be a pick: there's nothing before it to fold into"
+                    .to_string(),
+            ))
+        }
+        None => {
+            return Err(GitError::Other(
+                "Every commit is dropped; reset to the upstream instead".to_string(),
+            ))
+        }
+    }
+    let mut options = git2::StatusOptions::new();
+    options.include_untracked(false);
+    if repo
+        .statuses(Some(&mut options))?
+        .iter()
+        .any(|status| status.status() != git2::Status::CURRENT)
+    {
+        return Err(GitError::Other("Commit or stash your changes before rebasing".to_string()));
+    }
+
+    let (upstream_name, onto, _) = rebase_upstream(&repo)?;
+    let head = repo.head()?;
+    let plan = PlannedRebase {
+        head_name: head.name().unwrap_or("HEAD").to_string(),
+        orig_head: head.peel_to_commit()?.id(),
+        onto,
+        done: 0,
+        steps: steps
+            .iter()
+            .map(|(action, id)| Ok((*action, git2::Oid::from_str(id)?)))
+            .collect::<Result<_, GitError>>()?,
+    };
+
+    let onto_commit = repo.find_commit(onto)?;
+    let mut checkout = git2::build::CheckoutBuilder::new();
+    checkout.safe();
+    repo.checkout_tree(onto_commit.as_object(), Some(&mut checkout))?;
+    repo.reference(
+        "HEAD",
+        onto,
+        true,
+        &format!("rebase (start): checkout {}", upstream_name),
+    )?;
+    plan.save(&repo)?;
+    run_planned_rebase(&repo, plan)
+}
+
+/// Carry out the steps left, stopping on conflicts or moving the branch once all are done
+fn run_planned_rebase(
+    repo: &git2::Repository,
+    mut plan: PlannedRebase,
+) -> Result<PlannedRebaseOutcome, GitError> {
+    while let Some(&(action, oid)) = plan.steps.get(plan.done) {
+        if action != RebaseAction::Drop {
+            let picked = repo.find_commit(oid)?;
+            repo.cherrypick(&picked, None)?;
+            let mut index = repo.index()?;
+            if index.has_conflicts() {
+                return Ok(PlannedRebaseOutcome::Conflicts(conflicted_paths()));
+            }
+            commit_rebase_step(repo, &plan, action, &picked, &mut index)?;
+        }
+        plan.done += 1;
+        plan.save(repo)?;
+    }
+
+    let tip = repo.head()?.peel_to_commit()?.id();
+    repo.reference(
+        &plan.head_name,
+        tip,
+        true,
+        &format!("rebase (finish): {} onto {}", plan.head_name, plan.onto),
+    )?;
+    repo.reference_symbolic(
+        "HEAD",
+        &plan.head_name,
+        true,
+        &format!("rebase (finish): returning to {}", plan.head_name),
+    )?;
+    std::fs::remove_file(PlannedRebase::path(repo))?;
+    Ok(PlannedRebaseOutcome::Rebased(tip.to_string()[..7].to_string()))
+}
+
+/// Commit the index for one step: on top of HEAD for a pick, in place of HEAD for a squash or fixup
+fn commit_rebase_step(
+    repo: &git2::Repository,
+    plan: &PlannedRebase,
+    action: RebaseAction,
+    picked: &git2::Commit,
+    index: &mut git2::Index,
+) -> Result<(), GitError> {
+    let tree = repo.find_tree(index.write_tree()?)?;
+    let committer = repo.signature().map_err(|_| {
+        GitError::Other(
+            "Set your name and email (user.name and user.email) in Settings before rebasing"
+                .to_string(),
+        )
+    })?;
+    let head = repo.head()?.peel_to_commit()?;
+    let message = picked.message().unwrap_or_default();
+    // Nothing replayed yet (the first pick was already upstream): fold into nothing, pick instead
+    let action = if head.id() == plan.onto {
+        RebaseAction::Pick
+    } else {
+        action
+    };
+
+    let oid = if action == RebaseAction::Pick {
+        if tree.id() == head.tree_id() {
+            // Its changes are already upstream, so there's nothing left to pick
+            repo.cleanup_state()?;
+            return Ok(());
+        }
+        if picked.parent_id(0).ok() == Some(head.id()) && picked.tree_id() == tree.id() {
+            // Already on top of HEAD as it is: keep the commit, like git does
+            picked.id()
+        } else {
+            repo.commit(None, &picked.author(), &committer, message, &tree, &[&head])?
+        }
+    } else {
+        let message = match action {
+            RebaseAction::Squash => format!(
+                "{}\n\n{}",
+                head.message().unwrap_or_default().trim_end(),
+                message.trim_end()
+            ),
+            _ => head.message().unwrap_or_default().to_string(),
+        };
+        let parents: Vec<git2::Commit> = head.parents().collect();
+        let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+        repo.commit(None, &head.author(), &committer, &message, &tree, &parent_refs)?
+    };
+    repo.reference(
+        "HEAD",
+        oid,
+        true,
+        &format!("rebase ({}): {}", action.as_str(), picked.summary().unwrap_or_default()),
+    )?;
+    repo.cleanup_state()?; // CHERRY_PICK_HEAD and MERGE_MSG
+    Ok(())
+}
+
+/// Commit the step a planned rebase stopped on, once its conflicts are resolved, and carry on
+pub fn continue_planned_rebase() -> Result<PlannedRebaseOutcome, GitError> {
+    let repo = git2::Repository::open(".")?;
+    let mut plan = PlannedRebase::load(&repo)?;
+    let mut index = repo.index()?;
+    if index.has_conflicts() {
+        return Err(GitError::Other(
+            "Resolve every conflicted file before continuing the rebase".to_string(),
+        ));
+    }
+    if let Some(&(action, oid)) = plan.steps.get(plan.done) {
+        if repo.state() == git2::RepositoryState::CherryPick {
+            let picked = repo.find_commit(oid)?;
+            commit_rebase_step(&repo, &plan, action, &picked, &mut index)?;
+        }
+        plan.done += 1;
+        plan.save(&repo)?;
+    }
+    run_planned_rebase(&repo, plan)
+}
+
+/// Give up a planned rebase: the branch never moved, so check it out again as it was
+pub fn abort_planned_rebase() -> Result<(), GitError> {
+    let repo = git2::Repository::open(".")?;
+    let plan = PlannedRebase::load(&repo)?;
+    repo.cleanup_state()?;
+    repo.reference_symbolic(
+        "HEAD",
+        &plan.head_name,
+        true,
+        &format!("rebase (abort): returning to {}", plan.head_name),
+    )?;
+    let mut checkout = git2::build::CheckoutBuilder::new();
+    checkout.force();
+    repo.checkout_head(Some(&mut checkout))?;
+    std::fs::remove_file(PlannedRebase::path(&repo))?;
+    Ok(())
+}
+
+/// Push the current branch to remote origin
+pub fn push_origin(progress: Option<&ProgressSender>) -> Result<SyncOperation, GitError> {
+    let branch_name = get_current_branch()?;
+    push_branch("origin", &branch_name, progress)
+}
+
+/// Push a local branch to the branch of the same name on a remote
+pub fn push_branch(
+    remote_name: &str,
+    branch_name: &str,
+    progress: Option<&ProgressSender>,
+) -> Result<SyncOperation, GitError> {
+    let start_time = std::time::SystemTime::now();
+
+    let repo = git2::Repository::open(".")?;
+    let mut remote = repo.find_remote(remote_name)?;
+
+    let refspec = format!("refs/heads/{}:refs/heads/{}", branch_name, branch_name);
+
+    // Set up push options
+    let mut push_options = git2::PushOptions::new();
+    push_options.remote_callbacks(push_callbacks(progress));
+
+    match remote.push(&[&refspec], Some(&mut push_options)) {
+        Ok(()) => Ok(SyncOperation {
+            operation_type: SyncOperationType::Push,
+            status: OperationStatus::Success,
+            message: "Successfully pushed to remote".to_string(),
+            timestamp: start_time,
+        }),
+        Err(e) => Ok(SyncOperation {
+            operation_type: SyncOperationType::Push,
+            status: OperationStatus::Error,
+            message: format!("Failed to push: {}", e),
+            timestamp: start_time,
+        }),
+    }
+}
+
+/// Authentication (SSH agent and keys, credential helper, then asking) and progress for a push
+fn push_callbacks(progress: Option<&ProgressSender>) -> git2::RemoteCallbacks<'_> {
+    let mut callbacks = git2::RemoteCallbacks::new();
+    callbacks.credentials(crate::credentials::callback());
+
+    callbacks.push_transfer_progress(move |current, total, bytes| {
+        if let Some(progress) = progress {
+            let _ = progress.send(TransferProgress {
+                stage: "Writing objects",
+                current,
+                total,
+                bytes,
+                detail: None,
+            });
+        }
+    });
+
+    callbacks
+}
+
+/// Add `url` as origin and make it `branch`'s upstream, as `git push -u` would
+///
+/// The upstream is written to the config directly because the remote-tracking
+/// branch doesn't exist until the first push.
+pub fn add_origin(url: &str, branch: &str) -> Result<(), GitError> {
+    let repo = git2::Repository::open(".")?;
+    repo.remote("origin", url)?;
+    let mut config = repo.config()?;
+    config.set_str(&format!("branch.{}.remote", branch), "origin")?;
+    config.set_str(
+        &format!("branch.{}.merge", branch),
+        &format!("refs/heads/{}", branch),
+    )?;
+    Ok(())
+}
+
+/// Whether `remote` already has `branch`, as of the last fetch or push
+pub fn remote_branch_exists(remote: &str, branch: &str) -> bool {
+    git2::Repository::open(".").is_ok_and(|repo| {
+        repo.find_branch(&format!("{}/{}", remote, branch), git2::BranchType::Remote)
+            .is_ok()
+    })
+}
+
+/// Branches origin has, its default branch first, for picking a pull request's base
+pub fn origin_branches() -> Result<Vec<String>, GitError> {
+    let repo = git2::Repository::open(".")?;
+    let default = remote_default_branch(&repo, "origin");
+    let mut names = vec![default.clone()];
+    for branch in repo.branches(Some(git2::BranchType::Remote))? {
+        let (branch, _) = branch?;
+        if let Some(name) = branch.name()?.and_then(|name| name.strip_prefix("origin/")) {
+            if name != "HEAD" && name != default {
+                names.push(name.to_string());
+            }
+        }
+    }
+    names[1..].sort();
+    Ok(names)
+}
+
+/// Commits on local `branch` that origin's `base` branch doesn't have, oldest first
+pub fn commits_ahead_of(branch: &str, base: &str) -> Result<Vec<CommitSummary>, GitError> {
+    let repo = git2::Repository::open(".")?;
+    let mut walk = repo.revwalk()?;
+    walk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE)?;
+    walk.push_ref(&format!("refs/heads/{}", branch))?;
+    if let Ok(base) = repo.find_branch(&format!("origin/{}", base), git2::BranchType::Remote) {
+        if let Some(oid) = base.get().target() {
+            walk.hide(oid)?;
+        }
+    }
+
+    let mut commits = Vec::new();
+    for oid in walk {
+        commits.push(CommitSummary::from_commit(&repo.find_commit(oid?)?));
+    }
+    Ok(commits)
+}
+
+/// Directories not worth descending into when looking for repositories
+const WORKSPACE_SKIP_DIRS: &[&str] = &["node_modules", "target", "vendor", "dist", "build"];
+
+/// Find the git repositories under `root`, at most `max_depth` directories down
+///
+/// `root` itself is included when it is a repository, and searched either way;
+/// below it a repository's own subdirectories aren't searched (its submodules
+/// and clones belong to it), nor are hidden directories or common dependency
+/// and build output folders. Sorted by path.
+pub fn find_repositories(root: &Path, max_depth: usize) -> Vec<PathBuf> {
+    let mut repos = Vec::new();
+    let mut pending = vec![(root.to_path_buf(), 0)];
+    while let Some((dir, depth)) = pending.pop() {
+        if dir.join(".git").exists() {
+            repos.push(dir.clone());
+            if depth > 0 {
+                continue;
+            }
+        }
+        if depth == max_depth {
+            continue;
+        }
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(_) => continue, // Unreadable directories are skipped, not fatal
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let is_dir = entry.file_type().is_ok_and(|t| t.is_dir());
+            if is_dir && !name.starts_with('.') && !WORKSPACE_SKIP_DIRS.contains(&name.as_str()) {
+                pending.push((entry.path(), depth + 1));
+            }
+        }
+    }
+    repos.sort();
+    repos
+}
+
+/// One repository's state for the workspace dashboard
+#[derive(Debug, Clone, Default)]
+pub struct WorkspaceRepoStatus {
+    pub branch: Option<String>,   // None when HEAD is detached
+    pub changed: usize,           // Files with staged, unstaged or untracked changes
+    pub upstream: Option<String>, // Tracking branch, e.g. origin/main
+    pub ahead: usize,
+    pub behind: usize,
+}
+
+impl WorkspaceRepoStatus {
+    /// Whether everything is committed and nothing is waiting to be pushed
+    pub fn is_settled(&self) -> bool {
+        self.changed == 0 && self.ahead == 0
+    }
+}
+
+/// How far below a repository's top folder to look for nested clones
+const NESTED_REPO_DEPTH: usize = 4;
+
+/// A repository inside another one's working tree
+#[derive(Debug, Clone)]
+pub struct NestedRepo {
+    pub path: PathBuf,   // Relative to the containing repository
+    pub submodule: bool, // Registered in .gitmodules, rather than a stray clone
+    pub status: Result<WorkspaceRepoStatus, String>,
+}
+
+/// Submodules and other repositories inside the working tree of the repository at `repo_path`
+///
+/// Submodules that were never checked out are listed with an error status, so
+/// "is everything committed and pushed?" can't be answered yes by leaving them out.
+pub fn nested_repositories(repo_path: &Path) -> Result<Vec<NestedRepo>, GitError> {
+    let repo = git2::Repository::open(repo_path)?;
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| GitError::Other("Bare repositories have no working tree".to_string()))?
+        .to_path_buf();
+    let submodules: Vec<PathBuf> = repo
+        .submodules()?
+        .iter()
+        .map(|submodule| submodule.path().to_path_buf())
+        .collect();
+
+    let mut paths: Vec<PathBuf> = find_repositories(&workdir, NESTED_REPO_DEPTH)
+        .into_iter()
+        .filter_map(|path| path.strip_prefix(&workdir).ok().map(Path::to_path_buf))
+        .filter(|path| !path.as_os_str().is_empty())
+        .collect();
+    for submodule in &submodules {
+        if !paths.contains(submodule) {
+            paths.push(submodule.clone());
+        }
+    }
+    paths.sort();
+
+    Ok(paths
+        .into_iter()
+        .map(|path| {
+            let full = workdir.join(&path);
+            let status = if full.join(".git").exists() {
+                workspace_repo_status(&full).map_err(|e| e.to_string())
+            } else {
+                Err("Not checked out (git submodule update --init)".to_string())
+            };
+            NestedRepo {
+                submodule: submodules.contains(&path),
+                path,
+                status,
+            }
+        })
+        .collect())
+}
+
+/// Branch, dirty files and ahead/behind of the repository at `path`
+///
+/// Ahead/behind compare against the branch's configured upstream as of the last
+/// fetch; nothing is fetched here.
+pub fn workspace_repo_status(path: &Path) -> Result<WorkspaceRepoStatus, GitError> {
+    let repo = git2::Repository::open(path)?;
+    let mut status = WorkspaceRepoStatus::default();
+
+    let mut opts = git2::StatusOptions::new();
+    opts.include_untracked(true).include_ignored(false);
+    status.changed = repo.statuses(Some(&mut opts))?.len();
+
+    let head = match repo.head() {
+        Ok(head) => head,
+        Err(e) if e.code() == git2::ErrorCode::UnbornBranch => {
+            status.branch = repo
+                .find_reference("HEAD")?
+                .symbolic_target()
+                .and_then(|target| target.strip_prefix("refs/heads/"))
+                .map(str::to_string);
+            return Ok(status);
+        }
+        Err(e) => return Err(e.into()),
+    };
+    if !head.is_branch() {
+        return Ok(status);
+    }
+    status.branch = head.shorthand().map(str::to_string);
+    let local = head.target();
+
+    if let Ok(upstream) = git2::Branch::wrap(head).upstream() {
+        status.upstream = upstream.name().ok().flatten().map(str::to_string);
+        if let (Some(local), Some(remote)) = (local, upstream.get().target()) {
+            let (ahead, behind) = repo.graph_ahead_behind(local, remote)?;
+            status.ahead = ahead;
+            status.behind = behind;
+        }
+    }
+    Ok(status)
+}
+
+/// Where HEAD is and what Git is in the middle of, for explaining the repository to a beginner
+#[derive(Debug, Clone)]
+pub struct RepoSituation {
+    pub branch: Option<String>,   // None when HEAD is detached
+    pub unborn: bool,             // The branch has no commits yet
+    pub operation: Option<&'static str>, // "merge", "rebase", ... while one is unfinished
+    pub has_origin: bool,
+    pub tracking: Option<String>, // origin/<branch>, when it exists
+}
+
+/// Describe HEAD, the operation in progress and the branch's counterpart on origin
+pub fn repo_situation() -> Result<RepoSituation, GitError> {
+    let repo = git2::Repository::open(".")?;
+
+    let operation = match repo.state() {
+        git2::RepositoryState::Clean => None,
+        git2::RepositoryState::Merge => Some("merge"),
+        git2::RepositoryState::Revert | git2::RepositoryState::RevertSequence => Some("revert"),
+        git2::RepositoryState::CherryPick | git2::RepositoryState::CherryPickSequence => {
+            Some("cherry-pick")
+        }
+        git2::RepositoryState::Bisect => Some("bisect"),
+        git2::RepositoryState::Rebase
+        | git2::RepositoryState::RebaseInteractive
+        | git2::RepositoryState::RebaseMerge => Some("rebase"),
+        git2::RepositoryState::ApplyMailbox | git2::RepositoryState::ApplyMailboxOrRebase => {
+            Some("patch application")
+        }
+    };
+
+    let unborn = matches!(repo.head(), Err(e) if e.code() == git2::ErrorCode::UnbornBranch);
+    let branch = if repo.head_detached().unwrap_or(false) {
+        None
+    } else if unborn {
+        // HEAD names a branch that doesn't exist yet
+        repo.find_reference("HEAD")
+            .ok()
+            .and_then(|head| head.symbolic_target().map(str::to_string))
+            .map(|target| target.trim_start_matches("refs/heads/").to_string())
+    } else {
+        repo.head()?.shorthand().map(str::to_string)
+    };
+
+    let has_origin = repo.find_remote("origin").is_ok();
+    let tracking = branch
+        .as_ref()
+        .map(|name| format!("origin/{}", name))
+        .filter(|name| repo.find_branch(name, git2::BranchType::Remote).is_ok());
+
+    Ok(RepoSituation {
+        branch,
+        unborn,
+        operation,
+        has_origin,
+        tracking,
+    })
+}
+
+/// Detect a fork setup: both origin and upstream remotes pointing at different repositories
+pub fn detect_fork() -> Result<Option<ForkInfo>, GitError> {
+    let repo = git2::Repository::open(".")?;
+
+    let origin_url = match repo.find_remote("origin") {
+        Ok(remote) => remote.url().unwrap_or("").to_string(),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => return Ok(None),
+        Err(e) => return Err(GitError::Git2(e)),
+    };
+    let upstream_url = match repo.find_remote("upstream") {
+        Ok(remote) => remote.url().unwrap_or("").to_string(),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => return Ok(None),
+        Err(e) => return Err(GitError::Git2(e)),
+    };
+
+    if upstream_url.is_empty() || origin_url == upstream_url {
+        return Ok(None);
+    }
+
+    Ok(Some(ForkInfo {
+        upstream_url,
+        default_branch: remote_default_branch(&repo, "upstream"),
+    }))
+}
+
+/// A remote's default branch, from refs/remotes/<remote>/HEAD when it has been recorded
+fn remote_default_branch(repo: &git2::Repository, remote: &str) -> String {
+    if let Ok(head) = repo.find_reference(&format!("refs/remotes/{}/HEAD", remote)) {
+        if let Some(target) = head.symbolic_target() {
+            if let Some(branch) = target.strip_prefix(&format!("refs/remotes/{}/", remote)) {
+                return branch.to_string();
+            }
+        }
+    }
+
+    for candidate in ["main", "master"] {
+        if repo
+            .find_branch(&format!("{}/{}", remote, candidate), git2::BranchType::Remote)
+            .is_ok()
+        {
+            return candidate.to_string();
+        }
+    }
+
+    "main".to_string()
+}
+
+/// Sync the fork's default branch with upstream
+///
+/// Fetches upstream, then fast-forwards the local branch to upstream's tip. If
+/// the branch has diverged it is rebased onto upstream, which requires it to be
+/// checked out. Pushing to origin is left to the caller so hooks can run first.
+pub fn sync_fork(
+    fork: &ForkInfo,
+    progress: Option<&ProgressSender>,
+) -> Result<SyncOperation, GitError> {
+    let start_time = std::time::SystemTime::now();
+    let branch_name = fork.default_branch.as_str();
+    let error = |message: String| SyncOperation {
+        operation_type: SyncOperationType::ForkSync,
+        status: OperationStatus::Error,
+        message,
+        timestamp: start_time,
+    };
+
+    let fetch_result = fetch_remote("upstream", progress)?;
+    if matches!(fetch_result.status, OperationStatus::Error) {
+        return Ok(error(format!("Sync failed during fetch: {}", fetch_result.message)));
+    }
+
+    let repo = git2::Repository::open(".")?;
+    let upstream_name = format!("upstream/{}", branch_name);
+    let upstream_oid = match repo
+        .find_branch(&upstream_name, git2::BranchType::Remote)
+        .ok()
+        .and_then(|branch| branch.get().target())
+    {
+        Some(oid) => oid,
+        None => return Ok(error(format!("{} not found", upstream_name))),
+    };
+
+    let local_ref_name = format!("refs/heads/{}", branch_name);
+    let on_branch = repo
+        .head()
+        .ok()
+        .and_then(|head| head.name().map(|name| name == local_ref_name))
+        .unwrap_or(false);
+
+    let mut local_ref = match repo.find_reference(&local_ref_name) {
+        Ok(reference) => reference,
+        Err(e) if e.code() == git2::ErrorCode::NotFound => {
+            // No local copy yet: create it at upstream's tip
+            repo.branch(branch_name, &repo.find_commit(upstream_oid)?, false)?;
+            return Ok(SyncOperation {
+                operation_type: SyncOperationType::ForkSync,
+                status: OperationStatus::Success,
+                message: format!("Created {} from {}", branch_name, upstream_name),
+                timestamp: start_time,
+            });
+        }
+        Err(e) => return Err(GitError::Git2(e)),
+    };
+    let local_oid = local_ref
+        .target()
+        .ok_or_else(|| GitError::Other(format!("{} has no commit", branch_name)))?;
+
+    if local_oid == upstream_oid || repo.graph_descendant_of(local_oid, upstream_oid)? {
         return Ok(SyncOperation {
-            operation_type: SyncOperationType::Pull,
+            operation_type: SyncOperationType::ForkSync,
             status: OperationStatus::Success,
-            message: "Already up to date".to_string(),
+            message: format!("{} is already up to date with {}", branch_name, upstream_name),
+            timestamp: start_time,
+        });
+    }
+
+    if repo.graph_descendant_of(upstream_oid, local_oid)? {
+        // Fast-forward; update the working tree too when the branch is checked out
+        if on_branch {
+            let target = repo.find_object(upstream_oid, None)?;
+            repo.checkout_tree(&target, Some(git2::build::CheckoutBuilder::new().safe()))?;
+        }
+        local_ref.set_target(
+            upstream_oid,
+            &format!("gitix: fast-forward {} to {}", branch_name, upstream_name),
+        )?;
+        return Ok(SyncOperation {
+            operation_type: SyncOperationType::ForkSync,
+            status: OperationStatus::Success,
+            message: format!("Fast-forwarded {} to {}", branch_name, upstream_name),
             timestamp: start_time,
         });
     }
 
-    // Perform merge or rebase
-    if use_rebase {
-        match perform_rebase(&repo, local_oid, remote_oid) {
-            Ok(()) => Ok(SyncOperation {
-                operation_type: SyncOperationType::Pull,
-                status: OperationStatus::Success,
-                message: "Successfully rebased local changes".to_string(),
-                timestamp: start_time,
-            }),
-            Err(e) => Ok(SyncOperation {
-                operation_type: SyncOperationType::Pull,
-                status: OperationStatus::Error,
-                message: format!("Rebase failed: {}", e),
-                timestamp: start_time,
-            }),
+    if !on_branch {
+        return Ok(error(format!(
+            "{} has diverged from {}; check it out to rebase",
+            branch_name, upstream_name
+        )));
+    }
+
+    match perform_rebase(&repo, local_oid, upstream_oid, None) {
+        Ok(()) => Ok(SyncOperation {
+            operation_type: SyncOperationType::ForkSync,
+            status: OperationStatus::Success,
+            message: format!("Rebased {} onto {}", branch_name, upstream_name),
+            timestamp: start_time,
+        }),
+        Err(e) => Ok(error(format!("Rebase failed: {}", e))),
+    }
+}
+
+/// Refresh remote status (fetch + get status)
+pub fn refresh_remote_status(
+    progress: Option<&ProgressSender>,
+) -> Result<(RemoteStatus, SyncOperation), GitError> {
+    let fetch_op = fetch_origin(progress)?;
+    let remote_status = get_remote_status()?;
+
+    let refresh_op = SyncOperation {
+        operation_type: SyncOperationType::Refresh,
+        status: if matches!(fetch_op.status, OperationStatus::Success) {
+            OperationStatus::Success
+        } else {
+            OperationStatus::Error
+        },
+        message: if matches!(fetch_op.status, OperationStatus::Success) {
+            format!(
+                "Updated status - {} ahead, {} behind",
+                remote_status.ahead, remote_status.behind
+            )
+        } else {
+            format!("Failed to refresh: {}", fetch_op.message)
+        },
+        timestamp: fetch_op.timestamp,
+    };
+
+    Ok((remote_status, refresh_op))
+}
+
+/// One path in a status summary section
+#[derive(Debug, Clone)]
+pub struct StatusEntry {
+    pub status: FileStatusType,
+    pub path: String,
+}
+
+/// Porcelain-style snapshot of the branch and working tree, for read-only inspection
+#[derive(Debug, Clone, Default)]
+pub struct StatusSummary {
+    pub branch: Option<String>,   // None when HEAD is detached or unborn
+    pub head: Option<String>,     // Short id and subject of the HEAD commit
+    pub upstream: Option<String>, // Tracking branch, e.g. origin/main
+    pub ahead: usize,
+    pub behind: usize,
+    pub operation: Option<&'static str>, // Merge/rebase/etc. in progress
+    pub staged: Vec<StatusEntry>,
+    pub unstaged: Vec<StatusEntry>,
+    pub untracked: Vec<String>,
+    pub conflicted: Vec<String>,
+}
+
+/// Collect a `git status`-style summary: branch state plus staged, unstaged and untracked paths
+///
+/// Unlike `get_git_status`, a file with both staged and unstaged changes
+/// appears in both sections, matching `git status --porcelain`.
+pub fn get_status_summary() -> Result<StatusSummary, GitError> {
+    let repo = git2::Repository::open(".")?;
+    let mut summary = StatusSummary::default();
+
+    if let Ok(head) = repo.head() {
+        if head.is_branch() {
+            summary.branch = head.shorthand().map(str::to_string);
+        }
+        if let Ok(commit) = head.peel_to_commit() {
+            let id = commit.id().to_string();
+            summary.head = Some(format!("{} {}", &id[..7], commit.summary().unwrap_or("")));
+        }
+
+        if let Some(branch_name) = summary.branch.clone() {
+            if let Ok(branch) = repo.find_branch(&branch_name, git2::BranchType::Local) {
+                if let Ok(upstream) = branch.upstream() {
+                    summary.upstream = upstream.name().ok().flatten().map(str::to_string);
+                    if let (Some(local), Some(remote)) = (head.target(), upstream.get().target()) {
+                        if let Ok((ahead, behind)) = repo.graph_ahead_behind(local, remote) {
+                            summary.ahead = ahead;
+                            summary.behind = behind;
+                        }
+                    }
+                }
+            }
+        }
+    } else if let Ok(head) = repo.find_reference("HEAD") {
+        // Unborn branch: HEAD points at a branch with no commits yet
+        summary.branch = head
+            .symbolic_target()
+            .and_then(|target| target.strip_prefix("refs/heads/"))
+            .map(str::to_string);
+    }
+
+    summary.operation = match repo.state() {
+        git2::RepositoryState::Clean => None,
+        git2::RepositoryState::Merge => Some("Merge in progress"),
+        git2::RepositoryState::Revert | git2::RepositoryState::RevertSequence => {
+            Some("Revert in progress")
+        }
+        git2::RepositoryState::CherryPick | git2::RepositoryState::CherryPickSequence => {
+            Some("Cherry-pick in progress")
+        }
+        git2::RepositoryState::Bisect => Some("Bisect in progress"),
+        git2::RepositoryState::Rebase
+        | git2::RepositoryState::RebaseInteractive
+        | git2::RepositoryState::RebaseMerge => Some("Rebase in progress"),
+        git2::RepositoryState::ApplyMailbox | git2::RepositoryState::ApplyMailboxOrRebase => {
+            Some("git am in progress")
+        }
+    };
+
+    let mut options = git2::StatusOptions::new();
+    options
+        .include_untracked(true)
+        .recurse_untracked_dirs(false)
+        .renames_head_to_index(true);
+
+    for entry in repo.statuses(Some(&mut options))?.iter() {
+        let mut path = entry.path().unwrap_or("").to_string();
+        let status = entry.status();
+
+        if status.is_conflicted() {
+            summary.conflicted.push(path);
+            continue;
+        }
+        if status.is_wt_new() {
+            summary.untracked.push(path);
+            continue;
+        }
+
+        let index_status = if status.is_index_new() {
+            Some(FileStatusType::Added)
+        } else if status.is_index_modified() {
+            Some(FileStatusType::Modified)
+        } else if status.is_index_deleted() {
+            Some(FileStatusType::Deleted)
+        } else if status.is_index_renamed() {
+            // entry.path() is the old name for renames; report the new one like git does
+            let delta = entry.head_to_index();
+            let from = delta
+                .as_ref()
+                .and_then(|delta| delta.old_file().path().map(|p| p.display().to_string()))
+                .unwrap_or_default();
+            if let Some(to) = delta.as_ref().and_then(|delta| delta.new_file().path()) {
+                path = to.display().to_string();
+            }
+            Some(FileStatusType::Renamed { from })
+        } else if status.is_index_typechange() {
+            Some(FileStatusType::TypeChange)
+        } else {
+            None
+        };
+        if let Some(status) = index_status {
+            summary.staged.push(StatusEntry {
+                status,
+                path: path.clone(),
+            });
+        }
+
+        let worktree_status = if status.is_wt_modified() {
+            Some(FileStatusType::Modified)
+        } else if status.is_wt_deleted() {
+            Some(FileStatusType::Deleted)
+        } else if status.is_wt_typechange() {
+            Some(FileStatusType::TypeChange)
+        } else {
+            None
+        };
+        if let Some(status) = worktree_status {
+            summary.unstaged.push(StatusEntry { status, path });
+        }
+    }
+
+    Ok(summary)
+}
+
+/// A local branch as listed on the Branches tab
+#[derive(Debug, Clone)]
+pub struct BranchInfo {
+    pub name: String,
+    pub is_head: bool,            // The branch that is checked out
+    pub upstream: Option<String>, // Tracking branch, e.g. origin/main
+    pub ahead: usize,             // Commits the upstream doesn't have
+    pub behind: usize,            // Upstream commits this branch doesn't have
+    pub merged: bool,             // Whether HEAD already contains every commit of the branch
+    pub summary: String,          // Subject of the tip commit
+    pub time: chrono::DateTime<chrono::Local>, // When the tip commit was made
+}
+
+/// Ahead/behind counts already worked out, keyed by (branch tip, tip it's compared with)
+pub type AheadBehindCache = HashMap<(git2::Oid, git2::Oid), (usize, usize)>;
+
+/// Every local branch with its upstream and how far apart they are, checked-out branch first
+///
+/// Ahead/behind against the upstream and against HEAD (for `merged`) come from one
+/// walk of the commit graph for all branches together. Pairs of tips already in
+/// `cache` aren't walked again, so re-reading the list after an operation only
+/// pays for the branches that moved.
+pub fn list_branches(cache: &mut AheadBehindCache) -> Result<Vec<BranchInfo>, GitError> {
+    let repo = git2::Repository::open(".")?;
+    let head_oid = repo.head().ok().and_then(|head| head.target());
+
+    let mut branches = Vec::new();
+    let mut tips = Vec::new(); // (tip, upstream tip) for each entry in `branches`
+    for branch in repo.branches(Some(git2::BranchType::Local))? {
+        let (branch, _) = branch?;
+        let name = match branch.name()? {
+            Some(name) => name.to_string(),
+            None => continue, // Not valid UTF-8; can't be typed back in anyway
+        };
+        let commit = branch.get().peel_to_commit()?;
+
+        let mut info = BranchInfo {
+            name,
+            is_head: branch.is_head(),
+            upstream: None,
+            ahead: 0,
+            behind: 0,
+            merged: false,
+            summary: commit.summary().unwrap_or_default().to_string(),
+            time: chrono::DateTime::from_timestamp(commit.time().seconds(), 0)
+                .unwrap_or_default()
+                .with_timezone(&chrono::Local),
+        };
+        let mut remote = None;
+        if let Ok(upstream) = branch.upstream() {
+            info.upstream = upstream.name().ok().flatten().map(str::to_string);
+            remote = upstream.get().target();
         }
-    } else {
-        match perform_merge(&repo, remote_oid) {
-            Ok(()) => Ok(SyncOperation {
-                operation_type: SyncOperationType::Pull,
-                status: OperationStatus::Success,
-                message: "Successfully merged remote changes".to_string(),
-                timestamp: start_time,
-            }),
-            Err(e) => Ok(SyncOperation {
-                operation_type: SyncOperationType::Pull,
-                status: OperationStatus::Error,
-                message: format!("Merge failed: {}", e),
-                timestamp: start_time,
-            }),
+        tips.push((commit.id(), remote));
+        branches.push(info);
+    }
+
+    let wanted: std::collections::HashSet<(git2::Oid, git2::Oid)> = tips
+        .iter()
+        .flat_map(|&(tip, remote)| remote.into_iter().chain(head_oid).map(move |to| (tip, to)))
+        .collect();
+    cache.retain(|pair, _| wanted.contains(pair)); // Drop tips that have since moved
+    let missing: Vec<_> = wanted.into_iter().filter(|pair| !cache.contains_key(pair)).collect();
+    let counts = ahead_behind_batch(&repo, &missing)?;
+    cache.extend(missing.into_iter().zip(counts));
+
+    for (info, (tip, remote)) in branches.iter_mut().zip(tips) {
+        if let Some(remote) = remote {
+            (info.ahead, info.behind) = cache[&(tip, remote)];
         }
+        // Merged when the branch has nothing HEAD doesn't
+        info.merged = head_oid.is_some_and(|head| cache[&(tip, head)].0 == 0);
     }
+
+    branches.sort_by(|a, b| b.is_head.cmp(&a.is_head).then_with(|| a.name.cmp(&b.name)));
+    Ok(branches)
 }
 
-/// Perform a rebase operation
-fn perform_rebase(
+/// Ahead/behind for many (tip, other tip) pairs from a single walk of the commit graph
+///
+/// Every distinct tip gets a bit. Walking children before parents from all tips at
+/// once, each commit collects the bits of the tips that reach it and hands them on
+/// to its parents; a commit reached from a pair's first tip but not the second
+/// counts as ahead, the other way round as behind. History shared by every tip
+/// can't change any count, so the walk stops at their common merge base.
+fn ahead_behind_batch(
     repo: &git2::Repository,
-    local_oid: git2::Oid,
-    remote_oid: git2::Oid,
-) -> Result<(), GitError> {
-    // Get commits for rebase
-    let local_commit = repo.find_commit(local_oid)?;
-    let remote_commit = repo.find_commit(remote_oid)?;
+    pairs: &[(git2::Oid, git2::Oid)],
+) -> Result<Vec<(usize, usize)>, GitError> {
+    let mut counts = vec![(0, 0); pairs.len()];
+    if pairs.is_empty() {
+        return Ok(counts);
+    }
+    let mut bits: HashMap<git2::Oid, usize> = HashMap::new();
+    for &(tip, other) in pairs {
+        for oid in [tip, other] {
+            let next = bits.len();
+            bits.entry(oid).or_insert(next);
+        }
+    }
+    let pair_bits: Vec<(usize, usize)> = pairs.iter().map(|(a, b)| (bits[a], bits[b])).collect();
+    let words = bits.len().div_ceil(64);
+    let has = |mask: &[u64], bit: usize| mask[bit / 64] & (1 << (bit % 64)) != 0;
+
+    let mut walk = repo.revwalk()?;
+    walk.set_sorting(git2::Sort::TOPOLOGICAL)?;
+    let mut masks: HashMap<git2::Oid, Vec<u64>> = HashMap::new();
+    for (&oid, &bit) in &bits {
+        walk.push(oid)?;
+        masks.entry(oid).or_insert_with(|| vec![0; words])[bit / 64] |= 1 << (bit % 64);
+    }
+    let tips: Vec<git2::Oid> = bits.keys().copied().collect();
+    if let Ok(base) = repo.merge_base_octopus(&tips) {
+        walk.hide(base)?; // Reached by every tip, as are all its ancestors
+    }
 
-    // Find merge base
-    let merge_base = repo.merge_base(local_oid, remote_oid)?;
-    let base_commit = repo.find_commit(merge_base)?;
+    for oid in walk {
+        let oid = oid?;
+        let mask = masks.remove(&oid).unwrap_or_else(|| vec![0; words]);
+        for (count, &(a, b)) in counts.iter_mut().zip(&pair_bits) {
+            match (has(&mask, a), has(&mask, b)) {
+                (true, false) => count.0 += 1,
+                (false, true) => count.1 += 1,
+                _ => {}
+            }
+        }
+        for parent in repo.find_commit(oid)?.parent_ids() {
+            let parent_mask = masks.entry(parent).or_insert_with(|| vec![0; words]);
+            parent_mask.iter_mut().zip(&mask).for_each(|(word, bits)| *word |= bits);
+        }
+    }
+    Ok(counts)
+}
 
-    // Create AnnotatedCommit objects for rebase
-    let local_annotated = repo.find_annotated_commit(local_oid)?;
-    let base_annotated = repo.find_annotated_commit(merge_base)?;
-    let remote_annotated = repo.find_annotated_commit(remote_oid)?;
+/// Create a branch at HEAD, without switching to it
+pub fn create_branch(name: &str) -> Result<(), GitError> {
+    if !git2::Branch::name_is_valid(name)? {
+        return Err(GitError::Other(format!("'{}' is not a valid branch name", name)));
+    }
+    let repo = git2::Repository::open(".")?;
+    let head = repo
+        .head()
+        .and_then(|head| head.peel_to_commit())
+        .map_err(|_| GitError::Other("Make the first commit before creating branches".to_string()))?;
+    repo.branch(name, &head, false)?;
+    Ok(())
+}
 
-    // Initialize rebase
-    let mut rebase = repo.rebase(
-        Some(&local_annotated),
-        Some(&base_annotated),
-        Some(&remote_annotated),
-        None,
-    )?;
+/// Switch to a local branch, like `git switch <name>`
+///
+/// Uses a safe checkout: changes in the working tree are carried over, and the
+/// switch is refused if any of them would be overwritten.
+pub fn checkout_branch(name: &str) -> Result<(), GitError> {
+    let repo = git2::Repository::open(".")?;
+    let branch = repo.find_branch(name, git2::BranchType::Local)?;
+    let refname = branch
+        .get()
+        .name()
+        .ok_or_else(|| GitError::Other(format!("Branch '{}' has an invalid name", name)))?
+        .to_string();
+    let target = branch.get().peel_to_commit()?;
+
+    let mut checkout = git2::build::CheckoutBuilder::new();
+    checkout.safe();
+    repo.checkout_tree(target.as_object(), Some(&mut checkout))
+        .map_err(|e| match e.code() {
+            git2::ErrorCode::Conflict => GitError::Other(format!(
+                "Your changes to some files would be overwritten by switching to '{}'. \
+                 Commit or stash them first.",
+                name
+            )),
+            _ => GitError::Git2(e),
+        })?;
+    repo.set_head(&refname)?;
+    Ok(())
+}
 
-    // Process each rebase operation
-    while let Some(operation) = rebase.next() {
-        let _op = operation?;
+/// Delete a local branch; unless `force`, only when HEAD already contains its commits
+pub fn delete_branch(name: &str, force: bool) -> Result<(), GitError> {
+    let repo = git2::Repository::open(".")?;
+    let mut branch = repo.find_branch(name, git2::BranchType::Local)?;
+    if branch.is_head() {
+        return Err(GitError::Other(format!(
+            "'{}' is checked out; switch to another branch first",
+            name
+        )));
+    }
+    if !force {
+        let tip = branch.get().peel_to_commit()?.id();
+        let head = repo.head()?.peel_to_commit()?.id();
+        if tip != head && !repo.graph_descendant_of(head, tip)? {
+            return Err(GitError::Other(format!("'{}' is not fully merged", name)));
+        }
+    }
+    branch.delete()?;
+    Ok(())
+}
 
-        // Get the signature for the commit
-        let signature = repo.signature()?;
+/// How to bring another branch into the current one
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MergeMode {
+    Commit,          // Always record a merge commit with both tips as parents, like --no-ff
+    FastForwardOnly, // Only move the branch forward; refused once the two have diverged
+    Squash,          // Commit the branch's changes as one ordinary commit
+}
 
-        // Commit the rebased changes
-        rebase.commit(None, &signature, None)?;
+impl MergeMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MergeMode::Commit => "merge commit",
+            MergeMode::FastForwardOnly => "fast-forward only",
+            MergeMode::Squash => "squash",
+        }
     }
+}
 
-    // Finish the rebase
-    rebase.finish(None)?;
+/// What merging a branch into the current one would bring in
+#[derive(Debug, Clone)]
+pub struct MergePreview {
+    pub commits: Vec<CommitSummary>, // On the branch but not the current one, oldest first
+    pub fast_forward: bool,          // The current branch is behind it, so it can just move forward
+}
 
-    Ok(())
+/// How a merge went
+#[derive(Debug, Clone)]
+pub enum MergeOutcome {
+    FastForwarded(String),  // Short id the branch moved to
+    Committed(String),      // Short id of the merge or squash commit
+    Conflicts(Vec<String>), // Stopped with these files conflicted
 }
 
-/// Perform a merge operation
-fn perform_merge(repo: &git2::Repository, remote_oid: git2::Oid) -> Result<(), GitError> {
-    // Get the remote commit
-    let remote_commit = repo.find_commit(remote_oid)?;
-    let remote_tree = remote_commit.tree()?;
+/// The commits `name` would bring into the current branch
+pub fn merge_preview(name: &str) -> Result<MergePreview, GitError> {
+    let repo = git2::Repository::open(".")?;
+    let theirs = repo.find_branch(name, git2::BranchType::Local)?.get().peel_to_commit()?;
+    let head = repo.head()?.peel_to_commit()?;
+
+    let mut walk = repo.revwalk()?;
+    walk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE)?;
+    walk.push(theirs.id())?;
+    walk.hide(head.id())?;
+    let mut commits = Vec::new();
+    for oid in walk {
+        commits.push(CommitSummary::from_commit(&repo.find_commit(oid?)?));
+    }
+    Ok(MergePreview {
+        fast_forward: repo.graph_descendant_of(theirs.id(), head.id())?,
+        commits,
+    })
+}
 
-    // Get current HEAD
+/// Merge the local branch `name` into the current branch, committing with `message`
+///
+/// Staged changes are refused so they can't end up in the merge, and
+/// unstaged ones in files the merge touches make libgit2's checkout refuse.
+/// On conflicts a merge commit is left in progress (with `message` in
+/// MERGE_MSG) for the conflicts view; a squash just leaves the conflicted
+/// files, to commit from Save Changes once resolved, as `git merge --squash` does.
+pub fn merge_branch(name: &str, mode: MergeMode, message: &str) -> Result<MergeOutcome, GitError> {
+    let repo = git2::Repository::open(".")?;
+    if repo.state() != git2::RepositoryState::Clean {
+        return Err(GitError::Other(
+            "A merge, rebase or cherry-pick is in progress. Finish or abort it first.".to_string(),
+        ));
+    }
     let head = repo.head()?;
-    let local_commit = head.peel_to_commit()?;
-    let local_tree = local_commit.tree()?;
-
-    // Find common ancestor
-    let merge_base = repo.merge_base(local_commit.id(), remote_oid)?;
-    let base_commit = repo.find_commit(merge_base)?;
-    let base_tree = base_commit.tree()?;
+    let refname = match head.name().filter(|_| head.is_branch()) {
+        Some(refname) => refname.to_string(),
+        None => {
+            return Err(GitError::Other(
+                "HEAD is detached; check out the branch to merge into first".to_string(),
+            ))
+        }
+    };
+    let ours = head.peel_to_commit()?;
+    let branch = repo.find_branch(name, git2::BranchType::Local)?;
+    let annotated = repo.reference_to_annotated_commit(branch.get())?;
+    let theirs = repo.find_commit(annotated.id())?;
+
+    let (analysis, _) = repo.merge_analysis(&[&annotated])?;
+    if analysis.is_up_to_date() {
+        return Err(GitError::Other(format!(
+            "Already up to date: every commit of {} is on this branch",
+            name
+        )));
+    }
+    let staged = repo.diff_tree_to_index(Some(&ours.tree()?), None, None)?;
+    if staged.deltas().len() > 0 {
+        return Err(GitError::Other(
+            "Commit or unstage your staged changes before merging".to_string(),
+        ));
+    }
+    let overwritten = |e: git2::Error| match e.class() {
+        git2::ErrorClass::Checkout => GitError::Other(format!(
+            "Your uncommitted changes to files {} touches would be overwritten. \
+             Commit or stash them first.",
+            name
+        )),
+        _ => GitError::Git2(e),
+    };
 
-    // Perform the merge
-    let mut index = repo.merge_trees(&base_tree, &local_tree, &remote_tree, None)?;
+    if mode == MergeMode::FastForwardOnly {
+        if !analysis.is_fast_forward() {
+            return Err(GitError::Other(format!(
+                "{} and this branch have diverged, so it can't be fast-forwarded. \
+                 Make a merge commit or squash instead.",
+                name
+            )));
+        }
+        let mut checkout = git2::build::CheckoutBuilder::new();
+        checkout.safe();
+        repo.checkout_tree(theirs.as_object(), Some(&mut checkout))
+            .map_err(overwritten)?;
+        repo.reference(&refname, theirs.id(), true, &format!("merge {}: Fast-forward", name))?;
+        return Ok(MergeOutcome::FastForwarded(theirs.id().to_string()[..7].to_string()));
+    }
 
-    // Check for conflicts
+    if message.trim().is_empty() {
+        return Err(GitError::Other("The commit message can't be empty".to_string()));
+    }
+    let mut checkout = git2::build::CheckoutBuilder::new();
+    checkout.safe();
+    repo.merge(&[&annotated], None, Some(&mut checkout))
+        .map_err(overwritten)?;
+    let mut index = repo.index()?;
     if index.has_conflicts() {
-        return Err(GitError::Other("Merge conflicts detected".to_string()));
+        if mode == MergeMode::Squash {
+            repo.cleanup_state()?; // No MERGE_HEAD: the result is an ordinary commit
+        } else {
+            std::fs::write(repo.path().join("MERGE_MSG"), format!("{}\n", message.trim_end()))?;
+        }
+        return Ok(MergeOutcome::Conflicts(conflicted_paths()));
     }
 
-    // Write the merged index
-    let tree_oid = index.write_tree_to(repo)?;
-    let tree = repo.find_tree(tree_oid)?;
+    let tree = repo.find_tree(index.write_tree()?)?;
+    let signature = repo.signature().map_err(|_| {
+        GitError::Other(
+            "Set your name and email (user.name and user.email) in Settings before merging"
+                .to_string(),
+        )
+    })?;
+    let (parents, reflog_message) = match mode {
+        MergeMode::Squash => (
+            vec![&ours],
+            format!("commit: {}", message.lines().next().unwrap_or_default()),
+        ),
+        _ => (vec![&ours, &theirs], format!("merge {}: Merge made by gitix", name)),
+    };
+    let oid = repo.commit(None, &signature, &signature, message.trim_end(), &tree, &parents)?;
+    repo.reference(&refname, oid, true, &reflog_message)?;
+    repo.cleanup_state()?; // MERGE_HEAD and MERGE_MSG
+    Ok(MergeOutcome::Committed(oid.to_string()[..7].to_string()))
+}
 
-    // Create merge commit
-    let signature = repo.signature()?;
-    let message = format!(
-        "Merge remote-tracking branch 'origin/{}'",
-        head.shorthand().unwrap_or("HEAD")
-    );
+/// A tag as listed on the Tags tab
+#[derive(Debug, Clone)]
+pub struct TagInfo {
+    pub name: String,
+    pub commit: String,          // Short id of the tagged commit
+    pub annotated: bool,         // Annotated tags carry a tagger and a message of their own
+    pub message: String,         // The annotation's subject, or the commit's for a lightweight tag
+    pub time: chrono::DateTime<chrono::Local>, // When it was tagged, or committed if lightweight
+}
 
-    repo.commit(
-        Some("HEAD"),
-        &signature,
-        &signature,
-        &message,
-        &tree,
-        &[&local_commit, &remote_commit],
-    )?;
+/// Every tag that points at a commit, newest first
+pub fn list_tags() -> Result<Vec<TagInfo>, GitError> {
+    let repo = git2::Repository::open(".")?;
+    let mut tags = Vec::new();
+    for name in repo.tag_names(None)?.iter().flatten() {
+        let reference = repo.find_reference(&format!("refs/tags/{}", name))?;
+        let commit = match reference.peel_to_commit() {
+            Ok(commit) => commit,
+            Err(_) => continue, // Tags of trees or blobs have nothing to show here
+        };
+        let tag = reference.peel_to_tag().ok();
+        let (message, seconds) = match &tag {
+            Some(tag) => (
+                tag.message()
+                    .unwrap_or_default()
+                    .lines()
+                    .next()
+                    .unwrap_or_default()
+                    .to_string(),
+                tag.tagger()
+                    .map_or(commit.time().seconds(), |tagger| tagger.when().seconds()),
+            ),
+            None => (
+                commit.summary().unwrap_or_default().to_string(),
+                commit.time().seconds(),
+            ),
+        };
+        tags.push(TagInfo {
+            name: name.to_string(),
+            commit: commit.id().to_string()[..7].to_string(),
+            annotated: tag.is_some(),
+            message,
+            time: chrono::DateTime::from_timestamp(seconds, 0)
+                .unwrap_or_default()
+                .with_timezone(&chrono::Local),
+        });
+    }
+    tags.sort_by(|a, b| b.time.cmp(&a.time).then_with(|| a.name.cmp(&b.name)));
+    Ok(tags)
+}
+
+/// Tag HEAD: annotated when there is a message, lightweight otherwise
+pub fn create_tag(name: &str, message: Option<&str>) -> Result<(), GitError> {
+    if !git2::Reference::is_valid_name(&format!("refs/tags/{}", name)) {
+        return Err(GitError::Other(format!("'{}' is not a valid tag name", name)));
+    }
+    let repo = git2::Repository::open(".")?;
+    let head = repo
+        .head()
+        .and_then(|head| head.peel(git2::ObjectType::Commit))
+        .map_err(|_| GitError::Other("Make the first commit before tagging".to_string()))?;
+    match message {
+        Some(message) => {
+            let tagger = repo.signature()?;
+            repo.tag(name, &head, &tagger, message, false)?;
+        }
+        None => {
+            repo.tag_lightweight(name, &head, false)?;
+        }
+    }
+    Ok(())
+}
 
+/// Delete a local tag; a copy already pushed to a remote stays there
+pub fn delete_tag(name: &str) -> Result<(), GitError> {
+    let repo = git2::Repository::open(".")?;
+    repo.tag_delete(name)?;
     Ok(())
 }
 
-/// Push to remote origin
-pub fn push_origin() -> Result<SyncOperation, GitError> {
-    let start_time = std::time::SystemTime::now();
+/// Push one tag to origin
+pub fn push_tag(name: &str) -> Result<(), GitError> {
+    push_tags(&[name.to_string()])
+}
 
+/// Push tags to origin under the same names, failing if origin refuses any of them
+///
+/// Like `git push`, a tag origin already has pointing somewhere else is never
+/// moved; tags are not force-pushed.
+pub fn push_tags(names: &[String]) -> Result<(), GitError> {
     let repo = git2::Repository::open(".")?;
     let mut remote = repo.find_remote("origin")?;
 
-    // Get current branch
-    let head = repo.head()?;
-    let branch_name = head.shorthand().unwrap_or("HEAD");
-    let refspec = format!("refs/heads/{}:refs/heads/{}", branch_name, branch_name);
+    // libgit2 would overwrite such a tag, so compare with origin's tags first
+    let connection = remote.connect_auth(git2::Direction::Push, Some(push_callbacks(None)), None)?;
+    let moved: Vec<&str> = connection
+        .list()?
+        .iter()
+        .filter_map(|head| {
+            let name = head.name().strip_prefix("refs/tags/")?;
+            let local = repo.refname_to_id(head.name()).ok()?;
+            let name = names.iter().find(|n| *n == name)?;
+            (local != head.oid()).then_some(name.as_str())
+        })
+        .collect();
+    drop(connection);
+    if !moved.is_empty() {
+        return Err(GitError::Other(format!(
+            "origin already has {} on another commit; delete it there to move it",
+            moved.join(", ")
+        )));
+    }
+    let refspecs: Vec<String> = names
+        .iter()
+        .map(|name| format!("refs/tags/{}:refs/tags/{}", name, name))
+        .collect();
+
+    let rejected = std::cell::RefCell::new(Vec::new());
+    let mut callbacks = push_callbacks(None);
+    callbacks.push_update_reference(|refname, status| {
+        if let Some(status) = status {
+            let name = refname.trim_start_matches("refs/tags/");
+            rejected.borrow_mut().push(format!("{} ({})", name, status));
+        }
+        Ok(())
+    });
+    let mut push_options = git2::PushOptions::new();
+    push_options.remote_callbacks(callbacks);
+    remote.push(&refspecs, Some(&mut push_options))?;
+    drop(push_options);
 
-    // Create callbacks for authentication
-    let mut callbacks = git2::RemoteCallbacks::new();
-    callbacks.credentials(|url, username_from_url, allowed_types| {
-        // Try different authentication methods in order of preference
-        if allowed_types.contains(git2::CredentialType::SSH_KEY) {
-            // Try SSH key from agent first
-            if let Ok(cred) = git2::Cred::ssh_key_from_agent(username_from_url.unwrap_or("git")) {
-                return Ok(cred);
-            }
+    let rejected = rejected.into_inner();
+    if rejected.is_empty() {
+        Ok(())
+    } else {
+        Err(GitError::Other(format!("origin rejected {}", rejected.join(", "))))
+    }
+}
+
+/// Notes on commits under the default notes ref (refs/notes/commits unless core.notesRef says
+/// otherwise), keyed by full commit id; empty when there are none yet
+pub fn commit_notes() -> Result<HashMap<String, String>, GitError> {
+    let repo = git2::Repository::open(".")?;
+    let notes = match repo.notes(None) {
+        Ok(notes) => notes,
+        Err(e) if e.code() == git2::ErrorCode::NotFound => return Ok(HashMap::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut by_commit = HashMap::new();
+    for entry in notes {
+        let (_, commit) = entry?;
+        if let Ok(note) = repo.find_note(None, commit) {
+            let text = note.message().unwrap_or_default().trim_end().to_string();
+            by_commit.insert(commit.to_string(), text);
         }
+    }
+    Ok(by_commit)
+}
 
-        if allowed_types.contains(git2::CredentialType::USER_PASS_PLAINTEXT) {
-            // For HTTPS, try default credentials (this will prompt if needed)
-            if let Ok(cred) = git2::Cred::credential_helper(
-                &git2::Config::open_default().unwrap_or_else(|_| git2::Config::new().unwrap()),
-                url,
-                username_from_url,
-            ) {
-                return Ok(cred);
-            }
+/// Replace the note on a commit; an empty note removes it
+pub fn set_commit_note(commit_id: &str, text: &str) -> Result<(), GitError> {
+    let repo = git2::Repository::open(".")?;
+    let oid = git2::Oid::from_str(commit_id)?;
+    let signature = repo.signature()?;
+    if text.trim().is_empty() {
+        return match repo.note_delete(oid, None, &signature, &signature) {
+            Err(e) if e.code() != git2::ErrorCode::NotFound => Err(e.into()),
+            _ => Ok(()),
+        };
+    }
+    repo.note(&signature, &signature, None, oid, &format!("{}\n", text.trim_end()), true)?;
+    Ok(())
+}
+
+/// Push the notes ref to origin under the same name
+///
+/// Refuses when origin's notes have commits this repository lacks, since
+/// libgit2 would overwrite them; fetch the notes first.
+pub fn push_notes() -> Result<String, GitError> {
+    let repo = git2::Repository::open(".")?;
+    let notes_ref = repo.note_default_ref()?;
+    let local = repo
+        .refname_to_id(&notes_ref)
+        .map_err(|_| GitError::Other("there are no notes to push".to_string()))?;
+    let mut remote = repo.find_remote("origin")?;
+
+    let connection = remote.connect_auth(git2::Direction::Push, Some(push_callbacks(None)), None)?;
+    let theirs = connection
+        .list()?
+        .iter()
+        .find(|head| head.name() == notes_ref)
+        .map(|head| head.oid());
+    drop(connection);
+    if let Some(theirs) = theirs {
+        if theirs == local {
+            return Ok("origin already has these notes".to_string());
         }
+        if !repo.graph_descendant_of(local, theirs).unwrap_or(false) {
+            return Err(GitError::Other(
+                "origin has notes this repository doesn't; fetch the notes first".to_string(),
+            ));
+        }
+    }
 
-        // If all else fails, return an error
-        Err(git2::Error::from_str(
-            "No suitable authentication method found",
-        ))
+    let rejected = std::cell::RefCell::new(None);
+    let mut callbacks = push_callbacks(None);
+    callbacks.push_update_reference(|_, status| {
+        if let Some(status) = status {
+            *rejected.borrow_mut() = Some(status.to_string());
+        }
+        Ok(())
     });
-
-    // Set up push options
     let mut push_options = git2::PushOptions::new();
     push_options.remote_callbacks(callbacks);
+    remote.push(
+        &[format!("{}:{}", notes_ref, notes_ref)],
+        Some(&mut push_options),
+    )?;
+    drop(push_options);
 
-    match remote.push(&[&refspec], Some(&mut push_options)) {
-        Ok(()) => Ok(SyncOperation {
-            operation_type: SyncOperationType::Push,
-            status: OperationStatus::Success,
-            message: "Successfully pushed to remote".to_string(),
-            timestamp: start_time,
-        }),
-        Err(e) => Ok(SyncOperation {
-            operation_type: SyncOperationType::Push,
-            status: OperationStatus::Error,
-            message: format!("Failed to push: {}", e),
-            timestamp: start_time,
-        }),
+    match rejected.into_inner() {
+        Some(status) => Err(GitError::Other(format!("origin rejected the notes ({})", status))),
+        None => Ok(format!("Pushed {} to origin", notes_ref)),
     }
 }
 
-/// Refresh remote status (fetch + get status)
-pub fn refresh_remote_status() -> Result<(RemoteStatus, SyncOperation), GitError> {
-    let fetch_op = fetch_origin()?;
-    let remote_status = get_remote_status()?;
+/// Fetch origin's notes ref into refs/notes/origin/<name>, fast-forwarding the local one
+///
+/// Notes that diverged are left for `git notes merge`, which knows how to
+/// combine two notes on the same commit.
+pub fn fetch_notes() -> Result<String, GitError> {
+    let repo = git2::Repository::open(".")?;
+    let notes_ref = repo.note_default_ref()?;
+    let name = notes_ref.strip_prefix("refs/notes/").unwrap_or(&notes_ref).to_string();
+    let fetched = format!("refs/notes/origin/{}", name);
+    let mut remote = repo.find_remote("origin")?;
+    let mut fetch_options = git2::FetchOptions::new();
+    fetch_options.remote_callbacks(push_callbacks(None));
+    remote.fetch(
+        &[format!("+{}:{}", notes_ref, fetched)],
+        Some(&mut fetch_options),
+        None,
+    )?;
 
-    let refresh_op = SyncOperation {
-        operation_type: SyncOperationType::Refresh,
-        status: if matches!(fetch_op.status, OperationStatus::Success) {
-            OperationStatus::Success
-        } else {
-            OperationStatus::Error
-        },
-        message: if matches!(fetch_op.status, OperationStatus::Success) {
-            format!(
-                "Updated status - {} ahead, {} behind",
-                remote_status.ahead, remote_status.behind
-            )
-        } else {
-            format!("Failed to refresh: {}", fetch_op.message)
-        },
-        timestamp: fetch_op.timestamp,
+    let theirs = match repo.refname_to_id(&fetched) {
+        Ok(oid) => oid,
+        Err(_) => return Ok("origin has no notes".to_string()),
     };
-
-    Ok((remote_status, refresh_op))
+    let message = match repo.refname_to_id(&notes_ref) {
+        Ok(local) if local == theirs => return Ok("Notes are up to date".to_string()),
+        Ok(local) if repo.graph_descendant_of(local, theirs)? => {
+            return Ok("Local notes are ahead of origin's".to_string())
+        }
+        Ok(local) if !repo.graph_descendant_of(theirs, local)? => {
+            return Err(GitError::Other(format!(
+                "local and origin notes have diverged; run `git notes merge origin/{}`",
+                name
+            )))
+        }
+        Ok(_) => "Fast-forwarded the notes to origin's",
+        Err(_) => "Fetched origin's notes",
+    };
+    repo.reference(&notes_ref, theirs, true, "gitix: fetch notes from origin")?;
+    Ok(message.to_string())
 }
 
 /// Get the current branch name
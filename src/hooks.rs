@@ -0,0 +1,578 @@
+use crate::git::GitError;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Client-side hook names git knows about, in the order they are listed in the UI
+pub const KNOWN_HOOKS: [&str; 13] = [
+    "applypatch-msg",
+    "pre-applypatch",
+    "post-applypatch",
+    "pre-commit",
+    "pre-merge-commit",
+    "prepare-commit-msg",
+    "commit-msg",
+    "post-commit",
+    "pre-rebase",
+    "post-checkout",
+    "post-merge",
+    "pre-push",
+    "post-rewrite",
+];
+
+const SAMPLE_SUFFIX: &str = ".sample";
+
+/// gitix events that can run a user command configured as gitix.hooks.<event>
+///
+/// Unlike git hooks these run inside gitix and their output is shown in a popup.
+/// A failing pre-push-ui command aborts the push; the others are informational.
+pub const UI_HOOK_EVENTS: [&str; 4] = ["post-commit-ui", "pre-push-ui", "post-push-ui", "post-pull-ui"];
+
+#[derive(Debug, Clone)]
+pub struct HookInfo {
+    pub name: String,     // Hook name without the .sample suffix
+    pub path: PathBuf,    // Path of the file on disk (may end in .sample)
+    pub enabled: bool,    // Whether git will run it (no .sample suffix)
+    pub executable: bool, // Whether the file has an executable bit set
+}
+
+/// A hook script bundled with gitix that can be installed into a repository
+#[derive(Debug, Clone, Copy)]
+pub struct HookTemplate {
+    pub name: &'static str,
+    pub hook: &'static str,
+    pub description: &'static str,
+    pub contents: &'static str,
+}
+
+pub const HOOK_TEMPLATES: [HookTemplate; 3] = [
+    HookTemplate {
+        name: "Conventional Commits lint",
+        hook: "commit-msg",
+        description: "Reject commit messages that don't follow <type>(scope): <description>",
+        contents: include_str!("../assets/hooks/commit-msg-conventional"),
+    },
+    HookTemplate {
+        name: "Whitespace check",
+        hook: "pre-commit",
+        description: "Refuse commits that add trailing whitespace or conflict markers",
+        contents: include_str!("../assets/hooks/pre-commit-whitespace"),
+    },
+    HookTemplate {
+        name: "Block WIP pushes",
+        hook: "pre-push",
+        description: "Refuse to push commits whose subject starts with WIP",
+        contents: include_str!("../assets/hooks/pre-push-no-wip"),
+    },
+];
+
+/// Resolve the hooks directory, honoring core.hooksPath
+///
+/// A relative core.hooksPath is resolved against the working tree root, matching git.
+pub fn hooks_dir() -> Result<PathBuf, GitError> {
+    let repo = git2::Repository::open(".")?;
+    let config = repo.config()?;
+
+    match config.get_path("core.hooksPath") {
+        Ok(path) if path.is_absolute() => Ok(path),
+        Ok(path) => {
+            let base = repo
+                .workdir()
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|| repo.path().to_path_buf());
+            Ok(base.join(path))
+        }
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(repo.path().join("hooks")),
+        Err(e) => Err(GitError::Git2(e)),
+    }
+}
+
+/// Whether core.hooksPath overrides the default .git/hooks directory
+pub fn has_custom_hooks_path() -> bool {
+    git2::Repository::open(".")
+        .and_then(|repo| repo.config())
+        .and_then(|config| config.get_path("core.hooksPath"))
+        .is_ok()
+}
+
+/// List the hooks present in the hooks directory
+///
+/// When both `name` and `name.sample` exist, only the active hook is reported.
+pub fn list_hooks() -> Result<Vec<HookInfo>, GitError> {
+    let dir = hooks_dir()?;
+    let mut hooks: Vec<HookInfo> = Vec::new();
+
+    let read_dir = match fs::read_dir(&dir) {
+        Ok(read_dir) => read_dir,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(hooks),
+        Err(e) => return Err(GitError::Io(e)),
+    };
+
+    for entry in read_dir.flatten() {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let (name, enabled) = match file_name.strip_suffix(SAMPLE_SUFFIX) {
+            Some(name) => (name.to_string(), false),
+            None => (file_name, true),
+        };
+
+        let hook = HookInfo {
+            executable: is_executable(&path),
+            name,
+            path,
+            enabled,
+        };
+
+        match hooks.iter_mut().find(|h| h.name == hook.name) {
+            Some(existing) if hook.enabled => *existing = hook,
+            Some(_) => {}
+            None => hooks.push(hook),
+        }
+    }
+
+    // Known hooks first in lifecycle order, anything else alphabetically after
+    hooks.sort_by(|a, b| {
+        let rank = |name: &str| {
+            KNOWN_HOOKS
+                .iter()
+                .position(|h| *h == name)
+                .unwrap_or(KNOWN_HOOKS.len())
+        };
+        rank(&a.name)
+            .cmp(&rank(&b.name))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    Ok(hooks)
+}
+
+/// Enable a hook by dropping its .sample suffix and making it executable
+pub fn enable_hook(hook: &HookInfo) -> Result<(), GitError> {
+    if hook.enabled {
+        return Ok(());
+    }
+
+    let target = hooks_dir()?.join(&hook.name);
+    if target.exists() {
+        return Err(GitError::Other(format!(
+            "{} already exists",
+            target.display()
+        )));
+    }
+
+    fs::rename(&hook.path, &target)?;
+    set_executable(&target)?;
+    Ok(())
+}
+
+/// Disable a hook by renaming it to <name>.sample so git skips it
+///
+/// An existing sample of the same name is replaced by the disabled hook.
+pub fn disable_hook(hook: &HookInfo) -> Result<(), GitError> {
+    if !hook.enabled {
+        return Ok(());
+    }
+
+    let target = hooks_dir()?.join(format!("{}{}", hook.name, SAMPLE_SUFFIX));
+    fs::rename(&hook.path, &target)?;
+    Ok(())
+}
+
+/// Install a bundled hook template, refusing to overwrite an active hook
+pub fn install_template(template: &HookTemplate) -> Result<PathBuf, GitError> {
+    let dir = hooks_dir()?;
+    fs::create_dir_all(&dir)?;
+
+    let target = dir.join(template.hook);
+    if target.exists() {
+        return Err(GitError::Other(format!(
+            "A {} hook is already installed. Disable it first to replace it.",
+            template.hook
+        )));
+    }
+
+    fs::write(&target, template.contents)?;
+    set_executable(&target)?;
+    Ok(target)
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path)
+        .map(|m| m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(_path: &Path) -> bool {
+    // Git for Windows runs hooks through its bundled shell regardless of mode bits
+    true
+}
+
+#[cfg(unix)]
+fn set_executable(path: &Path) -> Result<(), GitError> {
+    use std::os::unix::fs::PermissionsExt;
+    let mut permissions = fs::metadata(path)?.permissions();
+    permissions.set_mode(permissions.mode() | 0o755);
+    fs::set_permissions(path, permissions)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn set_executable(_path: &Path) -> Result<(), GitError> {
+    Ok(())
+}
+
+/// Find an enabled, executable hook by name
+pub fn find_active_hook(name: &str) -> Option<PathBuf> {
+    let path = hooks_dir().ok()?.join(name);
+    if path.is_file() && is_executable(&path) {
+        Some(path)
+    } else {
+        None
+    }
+}
+
+/// A hook process whose stdout/stderr are streamed line by line
+pub struct RunningHook {
+    pub name: String,
+    child: std::process::Child,
+    output: std::sync::mpsc::Receiver<String>,
+    streams_closed: bool,
+}
+
+impl RunningHook {
+    /// Drain output produced since the last call
+    ///
+    /// Returns the new lines and, once the hook has exited and all output has
+    /// been read, its exit status.
+    pub fn poll(&mut self) -> (Vec<String>, Option<std::process::ExitStatus>) {
+        let mut lines = Vec::new();
+        loop {
+            match self.output.try_recv() {
+                Ok(line) => lines.push(line),
+                Err(std::sync::mpsc::TryRecvError::Empty) => break,
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                    self.streams_closed = true;
+                    break;
+                }
+            }
+        }
+
+        // Only report completion after both pipes hit EOF so no output is lost
+        let status = if self.streams_closed {
+            self.child.try_wait().ok().flatten()
+        } else {
+            None
+        };
+
+        (lines, status)
+    }
+
+    /// Kill the hook process (used when the user aborts)
+    pub fn kill(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Spawn a hook with the given arguments, writing `stdin` to it
+///
+/// Returns `Ok(None)` when the hook is not installed or not executable,
+/// which git treats as success.
+pub fn spawn_hook(name: &str, args: &[&str], stdin: &str) -> Result<Option<RunningHook>, GitError> {
+    use std::process::Command;
+
+    let path = match find_active_hook(name) {
+        Some(path) => path,
+        None => return Ok(None),
+    };
+
+    // Git runs hooks from the top of the working tree
+    let repo = git2::Repository::open(".")?;
+    let workdir = repo
+        .workdir()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from("."));
+
+    let mut command = Command::new(&path);
+    command.args(args).current_dir(workdir);
+    spawn_streaming(name, command, stdin).map(Some)
+}
+
+/// Run a hook to completion the way `git commit` does, with its output captured
+///
+/// Returns `Ok(())` when the hook is not installed or exits successfully; otherwise
+/// the error carries what the hook printed, which is usually why it refused.
+pub fn run_hook(name: &str, args: &[&str]) -> Result<(), GitError> {
+    use std::process::{Command, Stdio};
+
+    let path = match find_active_hook(name) {
+        Some(path) => path,
+        None => return Ok(()),
+    };
+
+    let repo = git2::Repository::open(".")?;
+    let workdir = repo
+        .workdir()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from("."));
+
+    let output = Command::new(&path)
+        .args(args)
+        .current_dir(workdir)
+        .env("GIT_INDEX_FILE", repo.path().join("index"))
+        .env("GIT_EDITOR", ":")
+        .stdin(Stdio::null())
+        .output()?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let printed = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Err(GitError::Other(format!(
+        "The {} hook refused the commit:\n{}",
+        name,
+        printed.trim_end()
+    )))
+}
+
+/// Run the command configured for a gitix UI event (gitix.hooks.<event>) through the shell
+///
+/// `env` is added to the command's environment, along with GITIX_EVENT.
+/// Returns `Ok(None)` when nothing is configured for the event.
+pub fn spawn_ui_hook(event: &str, env: &[(&str, String)]) -> Result<Option<RunningHook>, GitError> {
+    let command_line = match crate::config::get_ui_hook(event) {
+        Ok(Some(command_line)) if !command_line.trim().is_empty() => command_line,
+        Ok(_) => return Ok(None),
+        Err(e) => return Err(GitError::Other(e.to_string())),
+    };
+
+    let mut command = shell_command(&command_line)?;
+    command.env("GITIX_EVENT", event);
+    for (key, value) in env {
+        command.env(key, value);
+    }
+
+    spawn_streaming(event, command, "").map(Some)
+}
+
+/// Run the gitix.verify.command check through the shell before a commit, streaming its output
+pub fn spawn_verify(command_line: &str) -> Result<RunningHook, GitError> {
+    let mut command = shell_command(command_line)?;
+    command.env("GITIX_EVENT", "verify");
+    spawn_streaming("verify", command, "")
+}
+
+/// A shell command running `command_line` from the repository root
+fn shell_command(command_line: &str) -> Result<std::process::Command, GitError> {
+    use std::process::Command;
+
+    let repo = git2::Repository::open(".")?;
+    let workdir = repo
+        .workdir()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from("."));
+
+    let mut command = if cfg!(windows) {
+        let mut command = Command::new("cmd");
+        command.arg("/C").arg(command_line);
+        command
+    } else {
+        let mut command = Command::new("sh");
+        command.arg("-c").arg(command_line);
+        command
+    };
+    command.current_dir(workdir);
+    Ok(command)
+}
+
+/// Spawn `command` with piped stdio, feeding it `stdin` and streaming stdout/stderr lines
+fn spawn_streaming(
+    name: &str,
+    mut command: std::process::Command,
+    stdin: &str,
+) -> Result<RunningHook, GitError> {
+    use std::io::{BufRead, BufReader, Write};
+    use std::process::Stdio;
+
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    if let Some(mut child_stdin) = child.stdin.take() {
+        // Ignore broken pipes: hooks are free not to read their input
+        let _ = child_stdin.write_all(stdin.as_bytes());
+    }
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    if let Some(stdout) = child.stdout.take() {
+        let tx = tx.clone();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                let _ = tx.send(line);
+            }
+        });
+    }
+    if let Some(stderr) = child.stderr.take() {
+        std::thread::spawn(move || {
+            for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                let _ = tx.send(line);
+            }
+        });
+    }
+
+    Ok(RunningHook {
+        name: name.to_string(),
+        child,
+        output: rx,
+        streams_closed: false,
+    })
+}
+
+/// Build the arguments and stdin git passes to pre-push for pushing a local branch
+///
+/// Arguments are the remote name and URL; stdin holds one
+/// `<local ref> <local sha> <remote ref> <remote sha>` line per ref being pushed.
+pub fn pre_push_input(remote_name: &str, branch_name: &str) -> Result<(Vec<String>, String), GitError> {
+    let repo = git2::Repository::open(".")?;
+    let remote = repo.find_remote(remote_name)?;
+    let url = remote.pushurl().or(remote.url()).unwrap_or("").to_string();
+
+    let local_oid = repo
+        .refname_to_id(&format!("refs/heads/{}", branch_name))
+        .map_err(|_| GitError::Other(format!("No commit on branch {}", branch_name)))?;
+
+    let remote_ref = format!("refs/heads/{}", branch_name);
+    let remote_oid = repo
+        .refname_to_id(&format!("refs/remotes/{}/{}", remote_name, branch_name))
+        .unwrap_or_else(|_| git2::Oid::zero());
+
+    let stdin = format!(
+        "refs/heads/{} {} {} {}\n",
+        branch_name, local_oid, remote_ref, remote_oid
+    );
+
+    Ok((vec![remote_name.to_string(), url], stdin))
+}
+
+/// A staged file the commit formatter changed, and how
+#[derive(Debug, Clone)]
+pub struct FormattedFile {
+    pub path: String,
+    pub diff: Vec<String>, // Hunk headers and `+`/`-`/` ` lines, from what was staged to the formatted file
+}
+
+/// What running the commit formatter (gitix.commit.formatter) did
+#[derive(Debug, Clone, Default)]
+pub struct FormatReport {
+    pub formatted: Vec<FormattedFile>, // Changed by the formatter and staged again
+    pub skipped: Vec<String>,          // Left alone: they have unstaged changes too
+}
+
+/// Run the commit formatter on the staged files and stage what it changed
+///
+/// The command runs through the shell from the repository root with the staged
+/// paths appended, so `prettier --write` or `rustfmt` work as they are. Files
+/// that also have unstaged changes are skipped: staging the formatted file would
+/// stage those changes as well. Fails with the formatter's output if it fails.
+pub fn format_staged_files(command_line: &str) -> Result<FormatReport, GitError> {
+    use std::process::{Command, Stdio};
+
+    let repo = git2::Repository::open(".")?;
+    let workdir = repo
+        .workdir()
+        .map(Path::to_path_buf)
+        .ok_or_else(|| GitError::Other("A bare repository has no files to format".to_string()))?;
+    let mut index = repo.index()?;
+    let head_tree = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
+    let diff = repo.diff_tree_to_index(head_tree.as_ref(), Some(&index), None)?;
+
+    let mut report = FormatReport::default();
+    let mut staged = Vec::new(); // Path and the blob that is staged
+    for delta in diff.deltas() {
+        let new_file = delta.new_file();
+        let (path, blob) = match (new_file.path(), repo.find_blob(new_file.id())) {
+            (Some(path), Ok(blob)) => (path.to_path_buf(), blob),
+            _ => continue, // Deleted, or a submodule
+        };
+        if crate::encoding::detect(blob.content()).is_none() {
+            continue; // Binary
+        }
+        match fs::read(workdir.join(&path)) {
+            Ok(contents) if contents == blob.content() => staged.push((path, blob)),
+            _ => report.skipped.push(path.display().to_string()),
+        }
+    }
+    if staged.is_empty() {
+        return Ok(report);
+    }
+
+    let paths: Vec<&Path> = staged.iter().map(|(path, _)| path.as_path()).collect();
+    let mut command = if cfg!(windows) {
+        let quoted: Vec<String> = paths.iter().map(|p| format!("\"{}\"", p.display())).collect();
+        let mut command = Command::new("cmd");
+        command.arg("/C").arg(format!("{} {}", command_line, quoted.join(" ")));
+        command
+    } else {
+        let mut command = Command::new("sh");
+        command
+            .arg("-c")
+            .arg(format!("{} \"$@\"", command_line))
+            .arg("gitix-format")
+            .args(&paths);
+        command
+    };
+    let output = command.current_dir(&workdir).stdin(Stdio::null()).output()?;
+    if !output.status.success() {
+        let printed = format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        return Err(GitError::Other(format!(
+            "The formatter ({}) failed:\n{}",
+            command_line,
+            printed.trim_end()
+        )));
+    }
+
+    for (path, blob) in &staged {
+        let contents = match fs::read(workdir.join(path)) {
+            Ok(contents) => contents,
+            Err(_) => continue, // The formatter removed it; leave that to the user
+        };
+        if contents == blob.content() {
+            continue;
+        }
+        index.add_path(path)?;
+        let mut patch =
+            git2::Patch::from_buffers(blob.content(), Some(path), &contents, Some(path), None)?;
+        let mut lines = Vec::new();
+        patch.print(&mut |_, _, line| {
+            let content = crate::encoding::decode_lossy(line.content());
+            let content = content.trim_end_matches(['\n', '\r']);
+            match line.origin() {
+                '+' | '-' | ' ' => lines.push(format!("{}{}", line.origin(), content)),
+                'H' => lines.push(content.to_string()),
+                _ => {}
+            }
+            true
+        })?;
+        report.formatted.push(FormattedFile {
+            path: path.display().to_string(),
+            diff: lines,
+        });
+    }
+    index.write()?;
+    Ok(report)
+}
@@ -0,0 +1,109 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Where gitix templates live: `$XDG_CONFIG_HOME/gitix/templates`, else `~/.config/gitix/templates`
+///
+/// Each subdirectory is one template. Its `hooks/` folder is copied into
+/// `.git/hooks`; everything else (`.gitignore`, `.gitattributes`, ...) goes
+/// into the working tree of the new repository.
+pub fn templates_dir() -> Option<PathBuf> {
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_home.join("gitix").join("templates"))
+}
+
+/// Names of the available gitix templates, sorted
+pub fn list_templates() -> Vec<String> {
+    let dir = match templates_dir() {
+        Some(dir) => dir,
+        None => return Vec::new(),
+    };
+    let mut names: Vec<String> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| entry.path().is_dir())
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
+/// Copy the gitix template `name` into the repository at `workdir`
+///
+/// Existing files are left alone. Returns how many files were copied.
+pub fn apply_template(name: &str, workdir: &Path) -> io::Result<usize> {
+    let template = templates_dir()
+        .map(|dir| dir.join(name))
+        .filter(|dir| dir.is_dir())
+        .ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, format!("template '{}' not found", name))
+        })?;
+
+    let mut copied = 0;
+    let mut visited = Vec::new();
+    for entry in std::fs::read_dir(&template)?.flatten() {
+        let name = entry.file_name();
+        let target = if name == "hooks" {
+            workdir.join(".git").join("hooks")
+        } else {
+            workdir.join(&name)
+        };
+        copied += copy_tree(&entry.path(), &target, &mut visited)?;
+    }
+    Ok(copied)
+}
+
+/// Copy git's template directory into a new repository's `.git`, as `git init` does
+///
+/// Uses `GIT_TEMPLATE_DIR` or `init.templateDir`; does nothing when neither is set.
+pub fn apply_git_template_dir(git_dir: &Path) -> io::Result<usize> {
+    let configured = std::env::var("GIT_TEMPLATE_DIR").ok().or_else(|| {
+        git2::Config::open_default()
+            .and_then(|config| config.get_path("init.templateDir"))
+            .ok()
+            .map(|path| path.display().to_string())
+    });
+    let dir = match configured {
+        Some(dir) => expand_home(&dir),
+        None => return Ok(0),
+    };
+    if !dir.is_dir() {
+        return Ok(0);
+    }
+    copy_tree(&dir, git_dir, &mut Vec::new())
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), std::env::var_os("HOME")) {
+        (Some(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Copy a file or directory tree without overwriting anything; permissions come along
+///
+/// Symlinked directories are followed once; `visited` keeps a link loop from recursing forever.
+fn copy_tree(from: &Path, to: &Path, visited: &mut Vec<PathBuf>) -> io::Result<usize> {
+    if from.is_dir() {
+        let real = std::fs::canonicalize(from)?;
+        if visited.contains(&real) {
+            return Ok(0);
+        }
+        visited.push(real);
+        std::fs::create_dir_all(to)?;
+        let mut copied = 0;
+        for entry in std::fs::read_dir(from)?.flatten() {
+            copied += copy_tree(&entry.path(), &to.join(entry.file_name()), visited)?;
+        }
+        Ok(copied)
+    } else if to.exists() {
+        Ok(0)
+    } else {
+        std::fs::copy(from, to)?;
+        Ok(1)
+    }
+}
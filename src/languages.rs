@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+
+/// Lines and files of one language at HEAD
+#[derive(Debug, Clone)]
+pub struct LanguageCount {
+    pub language: &'static str,
+    pub files: usize,
+    pub lines: usize,
+}
+
+/// Lines of code per language, tied to the HEAD it was counted at so it can be cached
+#[derive(Debug, Clone)]
+pub struct LanguageStats {
+    pub head: String,
+    pub total_lines: usize,
+    pub languages: Vec<LanguageCount>, // Most lines first
+}
+
+/// Count lines per language in the files committed at HEAD
+///
+/// Languages are guessed from file extensions (and a few well-known file names);
+/// binary and unrecognized files are left out. Reads every blob, so run it off
+/// the UI thread.
+pub fn language_stats() -> Result<LanguageStats, git2::Error> {
+    let repo = git2::Repository::open(".")?;
+    let commit = repo.head()?.peel_to_commit()?;
+    let tree = commit.tree()?;
+
+    let mut counts: HashMap<&'static str, LanguageCount> = HashMap::new();
+    let mut walk_error = None;
+    tree.walk(git2::TreeWalkMode::PreOrder, |dir, entry| {
+        if entry.kind() != Some(git2::ObjectType::Blob) {
+            return git2::TreeWalkResult::Ok;
+        }
+        let name = entry.name().unwrap_or_default();
+        let language = match language_for(dir, name) {
+            Some(language) => language,
+            None => return git2::TreeWalkResult::Ok,
+        };
+        let blob = match repo.find_blob(entry.id()) {
+            Ok(blob) => blob,
+            Err(e) => {
+                walk_error = Some(e);
+                return git2::TreeWalkResult::Abort;
+            }
+        };
+        if blob.is_binary() {
+            return git2::TreeWalkResult::Ok;
+        }
+        let content = blob.content();
+        let mut lines = content.iter().filter(|b| **b == b'\n').count();
+        if content.last().is_some_and(|b| *b != b'\n') {
+            lines += 1;
+        }
+        let count = counts.entry(language).or_insert(LanguageCount {
+            language,
+            files: 0,
+            lines: 0,
+        });
+        count.files += 1;
+        count.lines += lines;
+        git2::TreeWalkResult::Ok
+    })?;
+    if let Some(e) = walk_error {
+        return Err(e);
+    }
+
+    let mut languages: Vec<LanguageCount> = counts.into_values().collect();
+    languages.sort_by(|a, b| b.lines.cmp(&a.lines).then(a.language.cmp(b.language)));
+    Ok(LanguageStats {
+        head: commit.id().to_string(),
+        total_lines: languages.iter().map(|count| count.lines).sum(),
+        languages,
+    })
+}
+
+/// Guess the language of a file from its name; `dir` is its folder in the tree
+fn language_for(dir: &str, name: &str) -> Option<&'static str> {
+    // Checked-in dependencies would drown out the project's own code
+    if dir.starts_with("vendor/") || dir.starts_with("node_modules/") {
+        return None;
+    }
+    match name {
+        "Makefile" | "GNUmakefile" => return Some("Makefile"),
+        "Dockerfile" => return Some("Dockerfile"),
+        "CMakeLists.txt" => return Some("CMake"),
+        _ => {}
+    }
+    let extension = name.rsplit_once('.')?.1.to_ascii_lowercase();
+    Some(match extension.as_str() {
+        "rs" => "Rust",
+        "go" => "Go",
+        "py" | "pyi" => "Python",
+        "js" | "mjs" | "cjs" | "jsx" => "JavaScript",
+        "ts" | "tsx" | "mts" | "cts" => "TypeScript",
+        "java" => "Java",
+        "kt" | "kts" => "Kotlin",
+        "scala" => "Scala",
+        "swift" => "Swift",
+        "c" | "h" => "C",
+        "cc" | "cpp" | "cxx" | "hpp" | "hh" | "hxx" => "C++",
+        "cs" => "C#",
+        "fs" | "fsx" => "F#",
+        "m" | "mm" => "Objective-C",
+        "rb" => "Ruby",
+        "php" => "PHP",
+        "pl" | "pm" => "Perl",
+        "lua" => "Lua",
+        "dart" => "Dart",
+        "ex" | "exs" => "Elixir",
+        "erl" | "hrl" => "Erlang",
+        "hs" => "Haskell",
+        "ml" | "mli" => "OCaml",
+        "clj" | "cljs" | "cljc" => "Clojure",
+        "zig" => "Zig",
+        "nim" => "Nim",
+        "r" => "R",
+        "jl" => "Julia",
+        "sh" | "bash" | "zsh" | "fish" => "Shell",
+        "ps1" | "psm1" => "PowerShell",
+        "sql" => "SQL",
+        "html" | "htm" => "HTML",
+        "css" => "CSS",
+        "scss" | "sass" | "less" => "Sass/Less",
+        "vue" => "Vue",
+        "svelte" => "Svelte",
+        "md" | "markdown" => "Markdown",
+        "rst" => "reStructuredText",
+        "tex" => "TeX",
+        "json" => "JSON",
+        "yaml" | "yml" => "YAML",
+        "toml" => "TOML",
+        "xml" => "XML",
+        "proto" => "Protobuf",
+        "tf" | "hcl" => "HCL",
+        "nix" => "Nix",
+        _ => return None,
+    })
+}
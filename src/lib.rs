@@ -1,9 +1,28 @@
 #![allow(warnings)]
+pub mod activity;
 pub mod app;
+pub mod cli;
+pub mod clipboard;
+pub mod completions;
 pub mod config;
+pub mod credentials;
+pub mod encoding;
+pub mod events;
+pub mod export;
 pub mod files;
+pub mod forge;
 pub mod git;
+pub mod hooks;
+pub mod init_template;
+pub mod languages;
+pub mod notify;
+pub mod release;
+pub mod review;
+pub mod script;
+pub mod sync_log;
+pub mod trash;
 pub mod tui;
+pub mod watcher;
 
 // Re-export commonly used items
 pub use tui::theme::{AccentColor, Theme};
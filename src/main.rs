@@ -1,11 +1,37 @@
 #![allow(warnings)]
+mod activity;
 mod app;
+mod cli;
+mod clipboard;
+mod completions;
 mod config;
+mod credentials;
+mod encoding;
+mod events;
+mod export;
 mod files;
+mod forge;
 mod git;
+mod hooks;
+mod init_template;
+mod languages;
+mod notify;
+mod release;
+mod review;
+mod script;
+mod sync_log;
+mod trash;
 mod tui;
+mod watcher;
 
 fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mut startup = cli::Startup::default();
+    if let Some(code) = cli::run(&args, &mut startup) {
+        std::process::exit(code);
+    }
+
     let mut state = app::AppState::default();
-    tui::start_tui(&mut state);
+    startup.apply(&mut state);
+    tui::start_tui(&mut state, startup.tab);
 }
@@ -0,0 +1,25 @@
+/// Operations shorter than this finish before the user has looked away
+pub const LONG_OPERATION: std::time::Duration = std::time::Duration::from_secs(3);
+
+/// Show a desktop notification using the platform's notifier
+///
+/// Goes through `notify-rust`: the freedesktop notification service on Linux/BSD,
+/// Notification Center on macOS and toasts on Windows. Failures are ignored: a
+/// missing notifier should never interrupt a git operation.
+pub fn send(summary: &str, body: &str) {
+    let notification = notification(summary, body);
+
+    // Delivering can wait on the notification service, so keep it off the UI thread
+    std::thread::spawn(move || {
+        let _ = notification.show();
+    });
+}
+
+fn notification(summary: &str, body: &str) -> notify_rust::Notification {
+    let mut notification = notify_rust::Notification::new();
+    notification.appname("gitix").summary(summary).body(body);
+    // Notification Center has no app name line: title it "gitix" with the summary below
+    #[cfg(target_os = "macos")]
+    notification.subtitle(summary).summary("gitix");
+    notification
+}
@@ -0,0 +1,76 @@
+use std::process::Command;
+
+/// Where published releases are listed
+const LATEST_RELEASE_API: &str = "https://api.github.com/repos/NeonTowel/gitix/releases/latest";
+
+/// The version this binary was built as
+pub const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// A published gitix release
+#[derive(Debug, Clone)]
+pub struct Release {
+    pub version: String, // Without the leading "v"
+    pub url: String,     // Release page with the changelog
+}
+
+/// Look up the latest published release on GitHub
+///
+/// Shells out to `curl` so no HTTP client has to be linked in; gives up after a
+/// few seconds. Blocks, so run it off the UI thread.
+pub fn latest_release() -> Result<Release, String> {
+    let output = Command::new("curl")
+        .args([
+            "-fsSL",
+            "--max-time",
+            "5",
+            "-H",
+            "Accept: application/vnd.github+json",
+            "-A",
+            concat!("gitix/", env!("CARGO_PKG_VERSION")),
+            LATEST_RELEASE_API,
+        ])
+        .output()
+        .map_err(|e| format!("Failed to run curl: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Release lookup failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Unexpected release response: {}", e))?;
+    let tag = json["tag_name"]
+        .as_str()
+        .ok_or_else(|| "Release response has no tag_name".to_string())?;
+    let url = json["html_url"]
+        .as_str()
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("https://github.com/NeonTowel/gitix/releases/tag/{}", tag));
+    Ok(Release {
+        version: tag.trim_start_matches('v').to_string(),
+        url,
+    })
+}
+
+/// Whether `candidate` is a later version than `current`, comparing dot-separated numbers
+///
+/// Pre-release suffixes (`-rc.1`) are ignored, so a release candidate of the
+/// running version doesn't count as newer.
+pub fn is_newer(candidate: &str, current: &str) -> bool {
+    fn parts(version: &str) -> Vec<u64> {
+        version
+            .trim_start_matches('v')
+            .split(['-', '+'])
+            .next()
+            .unwrap_or_default()
+            .split('.')
+            .map(|part| part.parse().unwrap_or(0))
+            .collect()
+    }
+    let (mut candidate, mut current) = (parts(candidate), parts(current));
+    let len = candidate.len().max(current.len());
+    candidate.resize(len, 0);
+    current.resize(len, 0);
+    candidate > current
+}
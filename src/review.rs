@@ -0,0 +1,128 @@
+use serde_json::{json, Value};
+use std::path::PathBuf;
+
+/// A note left on one line of a diff while reviewing it
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReviewNote {
+    pub path: String, // File the note is on, relative to the repository root
+    pub line: u32,    // Line number, in the old file for removed lines and the new one otherwise
+    pub old: bool,    // On a removed line
+    pub code: String, // The diff line the note was written against, with its +/-/space prefix
+    pub text: String,
+    pub created: i64, // Unix seconds
+}
+
+impl ReviewNote {
+    pub fn is_at(&self, path: &str, line: u32, old: bool) -> bool {
+        self.path == path && self.line == line && self.old == old
+    }
+
+    /// `12` for a line of the new file, `12 (removed)` for one of the old file
+    pub fn location(&self) -> String {
+        if self.old {
+            format!("{} (removed)", self.line)
+        } else {
+            self.line.to_string()
+        }
+    }
+}
+
+/// Where review notes are kept: `.git/gitix/notes.json`, so they never show up as changes
+fn notes_path() -> Option<PathBuf> {
+    git2::Repository::open(".")
+        .ok()
+        .map(|repo| repo.path().join("gitix").join("notes.json"))
+}
+
+/// The repository's review notes, sorted by file and line; none if the file is missing or unreadable
+pub fn load() -> Vec<ReviewNote> {
+    let contents = match notes_path().and_then(|path| std::fs::read(path).ok()) {
+        Some(contents) => contents,
+        None => return Vec::new(),
+    };
+    let entries: Vec<Value> = serde_json::from_slice(&contents).unwrap_or_default();
+    let mut notes: Vec<ReviewNote> = entries
+        .iter()
+        .filter_map(|entry| {
+            Some(ReviewNote {
+                path: entry["path"].as_str()?.to_string(),
+                line: entry["line"].as_u64()? as u32,
+                old: entry["old"].as_bool().unwrap_or(false),
+                code: entry["code"].as_str().unwrap_or_default().to_string(),
+                text: entry["text"].as_str()?.to_string(),
+                created: entry["created"].as_i64().unwrap_or(0),
+            })
+        })
+        .collect();
+    sort(&mut notes);
+    notes
+}
+
+/// Write the notes back, removing the file once the last one is gone
+pub fn save(notes: &[ReviewNote]) -> std::io::Result<()> {
+    let path = notes_path().ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::NotFound, "not in a git repository")
+    })?;
+    if notes.is_empty() {
+        return match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        };
+    }
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let entries: Vec<Value> = notes
+        .iter()
+        .map(|note| {
+            json!({
+                "path": note.path,
+                "line": note.line,
+                "old": note.old,
+                "code": note.code,
+                "text": note.text,
+                "created": note.created,
+            })
+        })
+        .collect();
+    let mut out = serde_json::to_string_pretty(&entries).unwrap_or_default();
+    out.push('\n');
+    std::fs::write(path, out)
+}
+
+/// Order notes the way they are listed and exported: by file, then line
+pub fn sort(notes: &mut [ReviewNote]) {
+    notes.sort_by(|a, b| (&a.path, a.line, a.old).cmp(&(&b.path, b.line, b.old)));
+}
+
+/// Render the notes as a markdown review summary, one section per file
+pub fn summary(notes: &[ReviewNote], branch: &str) -> String {
+    let files = {
+        let mut paths: Vec<&str> = notes.iter().map(|note| note.path.as_str()).collect();
+        paths.dedup();
+        paths.len()
+    };
+    let mut out = String::from("# Review notes\n\n");
+    out.push_str(&format!(
+        "Branch `{}`: {} {} on {} {}, exported {}.\n",
+        branch,
+        notes.len(),
+        if notes.len() == 1 { "note" } else { "notes" },
+        files,
+        if files == 1 { "file" } else { "files" },
+        chrono::Local::now().format("%Y-%m-%d %H:%M"),
+    ));
+
+    let mut current: Option<&str> = None;
+    for note in notes {
+        if current != Some(note.path.as_str()) {
+            out.push_str(&format!("\n## `{}`\n\n", note.path));
+            current = Some(&note.path);
+        }
+        out.push_str(&format!("- **Line {}**: {}\n", note.location(), note.text));
+        if !note.code.trim().is_empty() {
+            out.push_str(&format!("  ```diff\n  {}\n  ```\n", note.code));
+        }
+    }
+    out
+}
@@ -0,0 +1,489 @@
+use crate::app::AppState;
+use crate::git::OperationStatus;
+use std::path::Path;
+
+/// One statement of a .gitix script
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Stage(Vec<String>),   // Empty means every changed file
+    Unstage(Vec<String>), // Empty means every staged file
+    Commit { message: String, no_verify: bool },
+    Pull,
+    Push,
+    Fetch { all: bool },
+    Status,
+    Echo(String),
+}
+
+/// A script line that couldn't be parsed
+#[derive(Debug)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// Parse a script into (line number, command) pairs
+///
+/// One command per line; `#` starts a comment and arguments containing spaces
+/// are written in double quotes, e.g. `commit "Fix typo in README"`.
+pub fn parse(source: &str) -> Result<Vec<(usize, Command)>, ParseError> {
+    let mut commands = Vec::new();
+
+    for (idx, raw) in source.lines().enumerate() {
+        let line = idx + 1;
+        let error = |message: String| ParseError { line, message };
+
+        let words = tokenize(raw).map_err(error)?;
+        let (name, args) = match words.split_first() {
+            Some(split) => split,
+            None => continue,
+        };
+
+        let is_all = |args: &[String]| args.len() == 1 && (args[0] == "--all" || args[0] == ".");
+        let command = match name.as_str() {
+            "stage" | "add" if args.is_empty() => {
+                return Err(error(format!("{} needs paths, or --all", name)))
+            }
+            "stage" | "add" if is_all(args) => Command::Stage(Vec::new()),
+            "stage" | "add" => Command::Stage(args.to_vec()),
+            "unstage" if args.is_empty() => {
+                return Err(error("unstage needs paths, or --all".to_string()))
+            }
+            "unstage" if is_all(args) => Command::Unstage(Vec::new()),
+            "unstage" => Command::Unstage(args.to_vec()),
+            "commit" => {
+                let no_verify = args.iter().any(|a| a == "--no-verify");
+                let rest: Vec<&String> = args.iter().filter(|a| *a != "--no-verify").collect();
+                match rest.as_slice() {
+                    [message] => Command::Commit {
+                        message: message.to_string(),
+                        no_verify,
+                    },
+                    _ => return Err(error("usage: commit \"message\" [--no-verify]".to_string())),
+                }
+            }
+            "pull" if args.is_empty() => Command::Pull,
+            "push" if args.is_empty() => Command::Push,
+            "fetch" if args.is_empty() => Command::Fetch { all: false },
+            "fetch" if args.len() == 1 && args[0] == "--all" => Command::Fetch { all: true },
+            "status" if args.is_empty() => Command::Status,
+            "echo" => Command::Echo(args.join(" ")),
+            "pull" | "push" | "fetch" | "status" => {
+                return Err(error(format!("unexpected arguments to {}", name)))
+            }
+            other => return Err(error(format!("unknown command '{}'", other))),
+        };
+        commands.push((line, command));
+    }
+
+    Ok(commands)
+}
+
+/// Split a line into words, honoring double quotes and `#` comments
+fn tokenize(line: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut chars = line.chars().peekable();
+
+    loop {
+        while chars.peek().is_some_and(|c| c.is_whitespace()) {
+            chars.next();
+        }
+        match chars.peek() {
+            None | Some('#') => break,
+            Some('"') => {
+                chars.next();
+                let mut word = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some('n') => word.push('\n'),
+                            Some(c) => word.push(c),
+                            None => return Err("unterminated escape".to_string()),
+                        },
+                        Some(c) => word.push(c),
+                        None => return Err("unterminated quote".to_string()),
+                    }
+                }
+                words.push(word);
+            }
+            Some(_) => {
+                let mut word = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() {
+                        break;
+                    }
+                    word.push(c);
+                    chars.next();
+                }
+                words.push(word);
+            }
+        }
+    }
+
+    Ok(words)
+}
+
+/// Run a script file, printing each command and its result
+///
+/// Stops at the first failing command. Returns the process exit code.
+pub fn run_file(path: &Path) -> i32 {
+    let source = match std::fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("gitix run: cannot read {}: {}", path.display(), e);
+            return 2;
+        }
+    };
+
+    let commands = match parse(&source) {
+        Ok(commands) => commands,
+        Err(e) => {
+            eprintln!("gitix run: {}: {}", path.display(), e);
+            return 2;
+        }
+    };
+
+    let mut state = AppState::default();
+    if !state.git_enabled {
+        eprintln!("gitix run: not a git repository");
+        return 1;
+    }
+
+    for (line, command) in commands {
+        println!("> {}", describe(&command));
+        match execute(&mut state, &command) {
+            Ok(message) => {
+                if !message.is_empty() {
+                    println!("{}", message);
+                }
+            }
+            Err(message) => {
+                eprintln!("✗ {}", message);
+                eprintln!("gitix run: stopped at line {}", line);
+                return 1;
+            }
+        }
+    }
+
+    0
+}
+
+fn describe(command: &Command) -> String {
+    match command {
+        Command::Stage(paths) if paths.is_empty() => "stage --all".to_string(),
+        Command::Stage(paths) => format!("stage {}", paths.join(" ")),
+        Command::Unstage(paths) if paths.is_empty() => "unstage --all".to_string(),
+        Command::Unstage(paths) => format!("unstage {}", paths.join(" ")),
+        Command::Commit { message, no_verify } => format!(
+            "commit {:?}{}",
+            message,
+            if *no_verify { " --no-verify" } else { "" }
+        ),
+        Command::Pull => "pull".to_string(),
+        Command::Push => "push".to_string(),
+        Command::Fetch { all: true } => "fetch --all".to_string(),
+        Command::Fetch { all: false } => "fetch".to_string(),
+        Command::Status => "status".to_string(),
+        Command::Echo(text) => format!("echo {:?}", text),
+    }
+}
+
+/// Execute one command through the same AppState actions the TUI uses
+fn execute(state: &mut AppState, command: &Command) -> Result<String, String> {
+    state.update_recent_operations.clear();
+
+    match command {
+        Command::Stage(paths) => {
+            let paths = if paths.is_empty() {
+                changed_paths(|file| !file.staged)?
+            } else {
+                paths.clone()
+            };
+            for path in &paths {
+                state
+                    .stage_path(path)
+                    .map_err(|e| format!("stage {}: {}", path, e))?;
+            }
+            state.refresh_save_changes_git_status();
+            Ok(format!("✓ Staged {} file(s)", paths.len()))
+        }
+        Command::Unstage(paths) => {
+            let paths = if paths.is_empty() {
+                changed_paths(|file| file.staged)?
+            } else {
+                paths.clone()
+            };
+            for path in &paths {
+                state
+                    .unstage_path(path)
+                    .map_err(|e| format!("unstage {}: {}", path, e))?;
+            }
+            state.refresh_save_changes_git_status();
+            Ok(format!("✓ Unstaged {} file(s)", paths.len()))
+        }
+        Command::Commit { message, no_verify } => {
+            state.refresh_save_changes_git_status();
+            state.commit_message = tui_textarea::TextArea::new(
+                message.lines().map(str::to_string).collect(),
+            );
+            state.commit_skip_hooks = *no_verify;
+            state
+                .commit_staged_files()
+                .map_err(|e| format!("commit: {}", e))?;
+            wait_for_background(state)?;
+            Ok(state.last_commit_summary.clone().unwrap_or_default())
+        }
+        Command::Pull => {
+            state.perform_pull();
+            wait_for_background(state)?;
+            sync_result(state)
+        }
+        Command::Push => {
+            state.perform_push();
+            wait_for_background(state)?;
+            sync_result(state)
+        }
+        Command::Fetch { all } => {
+            if *all {
+                state.fetch_all_remotes();
+            } else {
+                state.refresh_update_remote_status();
+            }
+            state.perform_refresh_work();
+            wait_for_background(state)?;
+            sync_result(state)
+        }
+        Command::Status => {
+            let summary = crate::git::get_status_summary().map_err(|e| e.to_string())?;
+            let mut lines = vec![format!(
+                "On branch {}",
+                summary.branch.as_deref().unwrap_or("(detached HEAD)")
+            )];
+            for entry in &summary.staged {
+                lines.push(format!("{}  {}", entry.status.as_symbol(), entry.path));
+            }
+            for entry in &summary.unstaged {
+                lines.push(format!(" {} {}", entry.status.as_symbol(), entry.path));
+            }
+            for path in &summary.untracked {
+                lines.push(format!("?? {}", path));
+            }
+            Ok(lines.join("\n"))
+        }
+        Command::Echo(text) => Ok(text.clone()),
+    }
+}
+
+/// Paths from the working tree status matching `filter`
+fn changed_paths(
+    filter: impl Fn(&crate::git::GitFileStatus) -> bool,
+) -> Result<Vec<String>, String> {
+    let files = crate::git::get_git_status().map_err(|e| e.to_string())?;
+    Ok(files
+        .iter()
+        .filter(|file| filter(file))
+        .map(|file| file.path.display().to_string())
+        .collect())
+}
+
+/// Let hooks, gitix.hooks.* commands and the background fetch, pull or push started by an
+/// action run to completion, echoing hook output
+///
+/// A push goes through both: the pre-push hooks run first and only then start the push.
+fn wait_for_background(state: &mut AppState) -> Result<(), String> {
+    let mut printed = 0;
+    loop {
+        state.poll_hook_output();
+        state.poll_sync_worker();
+
+        if let Some(output) = &state.hook_output {
+            // A hook that doesn't continue an earlier one's output starts over
+            if output.lines.len() < printed {
+                printed = 0;
+            }
+            for line in output.lines.iter().skip(printed) {
+                println!("  | {}", line);
+            }
+            printed = output.lines.len();
+        }
+
+        if !state.is_hook_running() && !state.is_syncing() {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+
+    let failed = matches!(
+        state.hook_output.as_ref().map(|o| &o.status),
+        Some(crate::app::HookRunStatus::Failed(_))
+    );
+    let name = state.hook_output.as_ref().map(|o| o.hook_name.clone());
+    state.hook_output = None;
+
+    // A failed push-gating hook is reported through the sync result instead
+    match name {
+        Some(name) if failed && state.update_recent_operations.is_empty() => {
+            Err(format!("{} hook failed", name))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Outcome of the most recent sync operation, or the error popup the action raised
+fn sync_result(state: &mut AppState) -> Result<String, String> {
+    if state.show_error_popup {
+        let message = state
+            .error_popup_message
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        state.hide_error();
+        return Err(message);
+    }
+
+    match state.update_recent_operations.first() {
+        Some(operation) if matches!(operation.status, OperationStatus::Error) => {
+            Err(operation.message.clone())
+        }
+        Some(operation) => Ok(format!("✓ {}", operation.message)),
+        None => Ok(String::new()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_one(line: &str) -> Command {
+        let mut commands = parse(line).unwrap();
+        assert_eq!(commands.len(), 1, "{:?}", commands);
+        commands.remove(0).1
+    }
+
+    fn parse_error(source: &str) -> ParseError {
+        parse(source).expect_err("should not parse")
+    }
+
+    #[test]
+    fn quoted_arguments_keep_their_spaces() {
+        assert_eq!(
+            parse_one(r#"commit "Fix typo in README""#),
+            Command::Commit {
+                message: "Fix typo in README".to_string(),
+                no_verify: false,
+            }
+        );
+        assert_eq!(
+            parse_one(r#"stage "docs/a file.md" src/main.rs"#),
+            Command::Stage(vec![
+                "docs/a file.md".to_string(),
+                "src/main.rs".to_string()
+            ])
+        );
+        assert_eq!(parse_one(r#"echo """#), Command::Echo(String::new()));
+    }
+
+    #[test]
+    fn quotes_understand_escapes() {
+        assert_eq!(
+            parse_one(r#"commit "Say \"hi\"\n\nWith a body \\o/" --no-verify"#),
+            Command::Commit {
+                message: "Say \"hi\"\n\nWith a body \\o/".to_string(),
+                no_verify: true,
+            }
+        );
+    }
+
+    #[test]
+    fn unterminated_quotes_are_errors() {
+        let error = parse_error("status\ncommit \"Never closed");
+        assert_eq!(error.line, 2);
+        assert_eq!(error.message, "unterminated quote");
+        assert_eq!(
+            parse_error(r#"commit "ends in \"#).message,
+            "unterminated escape"
+        );
+    }
+
+    #[test]
+    fn comments_and_blank_lines_are_skipped_but_counted() {
+        let source = "# Release script\n\n   \nstage --all  # everything\n\tpush\n";
+        let commands = parse(source).unwrap();
+        assert_eq!(
+            commands,
+            vec![(4, Command::Stage(Vec::new())), (5, Command::Push)]
+        );
+        // A # inside a quoted argument or a word is not a comment
+        assert_eq!(
+            parse_one(r##"commit "Fix #12""##),
+            Command::Commit {
+                message: "Fix #12".to_string(),
+                no_verify: false,
+            }
+        );
+        assert_eq!(
+            parse_one("stage notes#1.txt"),
+            Command::Stage(vec!["notes#1.txt".to_string()])
+        );
+    }
+
+    #[test]
+    fn unknown_commands_are_reported_with_their_line() {
+        let error = parse_error("status\n\nrebase main\n");
+        assert_eq!(error.line, 3);
+        assert_eq!(error.message, "unknown command 'rebase'");
+        assert_eq!(error.to_string(), "line 3: unknown command 'rebase'");
+    }
+
+    #[test]
+    fn fetch_takes_only_all() {
+        assert_eq!(parse_one("fetch"), Command::Fetch { all: false });
+        assert_eq!(parse_one("fetch --all"), Command::Fetch { all: true });
+        assert_eq!(
+            parse_error("fetch origin").message,
+            "unexpected arguments to fetch"
+        );
+        assert_eq!(
+            parse_error("fetch --all origin").message,
+            "unexpected arguments to fetch"
+        );
+        assert_eq!(
+            parse_error("fetch --all --all").message,
+            "unexpected arguments to fetch"
+        );
+    }
+
+    #[test]
+    fn commands_check_their_arguments() {
+        assert_eq!(parse_error("stage").message, "stage needs paths, or --all");
+        assert_eq!(
+            parse_error("unstage").message,
+            "unstage needs paths, or --all"
+        );
+        assert_eq!(parse_one("add ."), Command::Stage(Vec::new()));
+        assert_eq!(parse_one("unstage --all"), Command::Unstage(Vec::new()));
+        assert_eq!(
+            parse_error("commit").message,
+            "usage: commit \"message\" [--no-verify]"
+        );
+        assert_eq!(
+            parse_error("commit one two").message,
+            "usage: commit \"message\" [--no-verify]"
+        );
+        assert_eq!(
+            parse_error("push --force").message,
+            "unexpected arguments to push"
+        );
+        assert_eq!(
+            parse_error("status -s").message,
+            "unexpected arguments to status"
+        );
+    }
+}
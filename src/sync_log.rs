@@ -0,0 +1,107 @@
+use crate::git::{OperationStatus, SyncOperation, SyncOperationType};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use std::time::{Duration, UNIX_EPOCH};
+
+/// How many sync operations are kept when gitix.update.historySize isn't set
+pub const DEFAULT_HISTORY_SIZE: usize = 50;
+
+/// Where the sync log lives: `.git/gitix/sync-log.json`, one per repository
+fn log_path() -> Option<PathBuf> {
+    git2::Repository::open(".")
+        .ok()
+        .map(|repo| repo.path().join("gitix").join("sync-log.json"))
+}
+
+fn type_name(operation_type: &SyncOperationType) -> &'static str {
+    match operation_type {
+        SyncOperationType::Fetch => "fetch",
+        SyncOperationType::Pull => "pull",
+        SyncOperationType::Push => "push",
+        SyncOperationType::Refresh => "refresh",
+        SyncOperationType::ForkSync => "fork-sync",
+        SyncOperationType::CherryPick => "cherry-pick",
+        SyncOperationType::Merge => "merge",
+    }
+}
+
+fn status_name(status: &OperationStatus) -> &'static str {
+    match status {
+        OperationStatus::Pending => "pending",
+        OperationStatus::InProgress => "in-progress",
+        OperationStatus::Success => "success",
+        OperationStatus::Error => "error",
+    }
+}
+
+fn parse(entry: &Value) -> Option<SyncOperation> {
+    let operation_type = match entry["type"].as_str()? {
+        "fetch" => SyncOperationType::Fetch,
+        "pull" => SyncOperationType::Pull,
+        "push" => SyncOperationType::Push,
+        "refresh" => SyncOperationType::Refresh,
+        "fork-sync" => SyncOperationType::ForkSync,
+        "cherry-pick" => SyncOperationType::CherryPick,
+        "merge" => SyncOperationType::Merge,
+        _ => return None,
+    };
+    let status = match entry["status"].as_str()? {
+        "pending" => OperationStatus::Pending,
+        "in-progress" => OperationStatus::InProgress,
+        "success" => OperationStatus::Success,
+        "error" => OperationStatus::Error,
+        _ => return None,
+    };
+    Some(SyncOperation {
+        operation_type,
+        status,
+        message: entry["message"].as_str().unwrap_or_default().to_string(),
+        timestamp: UNIX_EPOCH + Duration::from_secs(entry["time"].as_u64()?),
+    })
+}
+
+/// The repository's logged sync operations, newest first, at most `limit` of them
+///
+/// A missing or unreadable log is an empty history.
+pub fn load(limit: usize) -> Vec<SyncOperation> {
+    let contents = match log_path().and_then(|path| std::fs::read(path).ok()) {
+        Some(contents) => contents,
+        None => return Vec::new(),
+    };
+    let entries: Vec<Value> = serde_json::from_slice(&contents).unwrap_or_default();
+    entries.iter().filter_map(parse).take(limit).collect()
+}
+
+/// Add an operation to the front of the log, dropping the oldest beyond `limit`
+///
+/// The log is re-read first so sessions running side by side don't drop each other's entries.
+pub fn record(operation: &SyncOperation, limit: usize) -> std::io::Result<()> {
+    let path = log_path().ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::NotFound, "not in a git repository")
+    })?;
+    let mut operations = load(limit);
+    operations.insert(0, operation.clone());
+    operations.truncate(limit);
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let entries: Vec<Value> = operations
+        .iter()
+        .map(|operation| {
+            let time = operation
+                .timestamp
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |since| since.as_secs());
+            json!({
+                "type": type_name(&operation.operation_type),
+                "status": status_name(&operation.status),
+                "message": operation.message,
+                "time": time,
+            })
+        })
+        .collect();
+    let mut out = serde_json::to_string_pretty(&entries).unwrap_or_default();
+    out.push('\n');
+    std::fs::write(path, out)
+}
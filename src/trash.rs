@@ -0,0 +1,20 @@
+use std::io;
+use std::path::Path;
+
+/// Remove a file or directory, through the system trash unless `permanent`
+///
+/// The trash is the default so a slip of the finger can be undone from the file
+/// manager. Symlinks are removed themselves, never what they point to.
+pub fn remove(path: &Path, permanent: bool) -> io::Result<()> {
+    if !permanent {
+        // The `trash` crate handles each platform's trash (freedesktop, Finder, Recycle Bin),
+        // including files on another filesystem than the home trash
+        return ::trash::delete_all([path]).map_err(io::Error::other);
+    }
+    let metadata = std::fs::symlink_metadata(path)?;
+    if metadata.is_dir() {
+        std::fs::remove_dir_all(path)
+    } else {
+        std::fs::remove_file(path)
+    }
+}
@@ -0,0 +1,186 @@
+use crate::activity::{format_timestamp, status_label};
+use crate::app::{AppState, MouseList};
+use crate::tui::text;
+use crate::tui::theme::Theme;
+use ratatui::layout::{Alignment, Constraint, Direction, Layout};
+use ratatui::style::Modifier;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table, Wrap};
+use ratatui::{layout::Rect, Frame};
+
+pub fn render_activity_tab(f: &mut Frame, area: Rect, state: &mut AppState) {
+    // Use configured theme from app state
+    let theme = Theme::with_accents_and_title(
+        state.current_theme_accent,
+        state.current_theme_accent2,
+        state.current_theme_accent3,
+        state.current_theme_title,
+    );
+
+    // Set panel background
+    f.render_widget(
+        Block::default().style(theme.secondary_background_style()),
+        area,
+    );
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(5),    // Activity table
+            Constraint::Length(6), // Selected entry details
+        ])
+        .split(area);
+
+    render_activity_table(f, chunks[0], state, &theme);
+    state.record_mouse_target(
+        MouseList::Activity,
+        chunks[0],
+        state.activity_table_state.offset(),
+        state.activity_log.len(),
+    );
+    render_activity_details(f, chunks[1], state, &theme);
+}
+
+fn render_activity_table(f: &mut Frame, area: Rect, state: &mut AppState, theme: &Theme) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(
+            "Session Activity ({} operations) - [E] Export",
+            state.activity_log.len()
+        ))
+        .title_style(theme.title_style())
+        .border_style(theme.border_style())
+        .style(theme.secondary_background_style());
+
+    if state.activity_log.is_empty() {
+        let empty = Paragraph::new(vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                "No git operations performed yet this session",
+                theme.muted_text_style(),
+            )),
+            Line::from(""),
+            Line::from(Span::styled(
+                "Staging, commits, pulls and pushes made through gitix will be listed here",
+                theme.secondary_text_style(),
+            )),
+        ])
+        .alignment(Alignment::Center)
+        .block(block);
+        f.render_widget(empty, area);
+        return;
+    }
+
+    // Keep the selection valid as the log grows
+    match state.activity_table_state.selected() {
+        Some(idx) if idx < state.activity_log.len() => {}
+        _ => state.activity_table_state.select(Some(0)),
+    }
+
+    let header = Row::new(vec![
+        Cell::from("Time").style(theme.accent2_style()),
+        Cell::from("Action").style(theme.accent2_style()),
+        Cell::from("Result").style(theme.accent2_style()),
+        Cell::from("Target").style(theme.accent2_style()),
+        Cell::from("Operation").style(theme.accent2_style()),
+    ]);
+
+    let widths = [
+        Constraint::Length(19),     // Time
+        Constraint::Length(8),      // Action
+        Constraint::Length(7),      // Result
+        Constraint::Percentage(25), // Target
+        Constraint::Min(20),        // Operation
+    ];
+    let columns = text::column_widths(area, &widths, "► ");
+
+    let rows: Vec<Row> = state
+        .activity_log
+        .iter()
+        .map(|entry| {
+            let result_style = match entry.status {
+                crate::git::OperationStatus::Success => theme.success_style(),
+                crate::git::OperationStatus::Error => theme.error_style(),
+                _ => theme.info_style(),
+            };
+
+            Row::new(vec![
+                Cell::from(format_timestamp(entry.timestamp)).style(theme.timestamp_style()),
+                Cell::from(entry.kind.as_str())
+                    .style(theme.accent_style().add_modifier(Modifier::BOLD)),
+                Cell::from(status_label(&entry.status)).style(result_style),
+                Cell::from(text::truncate_path(&entry.target, columns[3]))
+                    .style(theme.text_style()),
+                Cell::from(text::truncate(&entry.operation, columns[4]))
+                    .style(theme.secondary_text_style()),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(block)
+        .row_highlight_style(theme.highlight_style())
+        .highlight_symbol("► ");
+
+    f.render_stateful_widget(table, area, &mut state.activity_table_state);
+}
+
+fn render_activity_details(f: &mut Frame, area: Rect, state: &AppState, theme: &Theme) {
+    let selected = state
+        .activity_table_state
+        .selected()
+        .and_then(|idx| state.activity_log.get(idx));
+
+    let mut lines = match selected {
+        Some(entry) => vec![
+            Line::from(vec![
+                Span::styled("Operation: ", theme.stats_label_style()),
+                Span::styled(entry.operation.as_str(), theme.text_style()),
+            ]),
+            Line::from(vec![
+                Span::styled("Result: ", theme.stats_label_style()),
+                Span::styled(entry.message.as_str(), theme.text_style()),
+            ]),
+        ],
+        None => vec![Line::from(Span::styled(
+            "Select an entry to see its details",
+            theme.muted_text_style(),
+        ))],
+    };
+
+    if let Some(message) = &state.activity_status_message {
+        lines.push(Line::from(Span::styled(
+            message.as_str(),
+            theme.accent3_style(),
+        )));
+    }
+
+    let details = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Details")
+            .title_style(theme.title_style())
+            .border_style(theme.border_style())
+            .style(theme.secondary_background_style()),
+    );
+    f.render_widget(details, area);
+}
+
+impl AppState {
+    /// Move the activity selection up
+    pub fn activity_navigate_up(&mut self) {
+        let current = self.activity_table_state.selected().unwrap_or(0);
+        self.activity_table_state.select(Some(current.saturating_sub(1)));
+    }
+
+    /// Move the activity selection down
+    pub fn activity_navigate_down(&mut self) {
+        if self.activity_log.is_empty() {
+            return;
+        }
+        let current = self.activity_table_state.selected().unwrap_or(0);
+        self.activity_table_state
+            .select(Some((current + 1).min(self.activity_log.len() - 1)));
+    }
+}
@@ -0,0 +1,289 @@
+use crate::app::{AppState, BackupBrowser, BackupFiles};
+use crate::tui::text;
+use crate::tui::theme::Theme;
+use ratatui::layout::{Constraint, Layout, Margin};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::{layout::Rect, Frame};
+use std::time::Instant;
+
+/// How many snapshots the browser lists
+const BACKUP_LIST_LIMIT: usize = 200;
+
+/// Render the backup browser: the snapshot list, or the files of the opened snapshot
+pub fn render_backup_browser(f: &mut Frame, area: Rect, state: &AppState, theme: &Theme) {
+    let browser = match &state.backup_browser {
+        Some(browser) => browser,
+        None => return,
+    };
+
+    let popup_area = super::centered_rect(70, 20, area);
+    f.render_widget(Clear, popup_area);
+
+    let title = match (&browser.files, state.backup_minutes) {
+        (Some(_), _) => {
+            let snapshot = &browser.snapshots[browser.selected];
+            format!("Snapshot {} - Files That Differ", snapshot.short_id)
+        }
+        (None, 0) => "Backups (automatic snapshots off)".to_string(),
+        (None, minutes) => format!("Backups (every {} min)", minutes),
+    };
+    let block = Block::default()
+        .title(title)
+        .title_style(theme.title_style())
+        .borders(Borders::ALL)
+        .border_style(theme.popup_border_style())
+        .style(theme.secondary_background_style());
+    let inner = block.inner(popup_area).inner(Margin {
+        vertical: 0,
+        horizontal: 1,
+    });
+    f.render_widget(block, popup_area);
+
+    let chunks = Layout::vertical([
+        Constraint::Min(1),    // List
+        Constraint::Length(1), // Error or confirmation
+        Constraint::Length(1), // Keys
+    ])
+    .split(inner);
+    let height = chunks[0].height as usize;
+    let width = chunks[0].width as usize;
+
+    let (rows, selected, empty): (Vec<String>, usize, &str) = match &browser.files {
+        Some(files) => (
+            files.paths.clone(),
+            files.selected,
+            "Every file matches the working tree; nothing to restore",
+        ),
+        None => (
+            browser
+                .snapshots
+                .iter()
+                .map(|snapshot| {
+                    format!(
+                        "{}  {}  {:<16}  {}",
+                        snapshot.short_id,
+                        snapshot.time.format("%Y-%m-%d %H:%M"),
+                        crate::git::format_relative_time(snapshot.time),
+                        snapshot.summary
+                    )
+                })
+                .collect(),
+            browser.selected,
+            "No snapshots yet: press S to take one, or set gitix.backup.minutes",
+        ),
+    };
+
+    let mut lines = Vec::new();
+    if rows.is_empty() {
+        lines.push(Line::styled(empty, theme.muted_text_style()));
+    }
+    let start = selected.saturating_sub(height.saturating_sub(1));
+    for (idx, row) in rows.iter().enumerate().skip(start).take(height) {
+        let row = text::truncate(row, width.saturating_sub(2));
+        if idx == selected {
+            lines.push(Line::from(vec![
+                Span::styled("► ", theme.accent_style()),
+                Span::styled(row, theme.accent_style()),
+            ]));
+        } else {
+            lines.push(Line::styled(format!("  {}", row), theme.text_style()));
+        }
+    }
+    f.render_widget(Paragraph::new(lines), chunks[0]);
+
+    let status = match (&browser.files, &state.backup_error) {
+        (Some(files), _) if files.confirm => Line::styled(
+            format!(
+                "Overwrite {} with the snapshot's version? [Y/N]",
+                files.paths.get(files.selected).map(String::as_str).unwrap_or_default()
+            ),
+            theme.warning_style(),
+        ),
+        (_, Some(error)) => Line::styled(
+            text::truncate(&format!("Last snapshot failed: {}", error), width),
+            theme.error_style(),
+        ),
+        _ => Line::from(""),
+    };
+    f.render_widget(Paragraph::new(status), chunks[1]);
+
+    let keys = if browser.files.is_some() {
+        "[↑↓] Navigate  [Enter] Restore File  [Esc] Back to Snapshots"
+    } else {
+        "[↑↓] Navigate  [Enter] Open Snapshot  [S] Snapshot Now  [Esc] Close"
+    };
+    f.render_widget(
+        Paragraph::new(Line::styled(keys, theme.muted_text_style())),
+        chunks[2],
+    );
+}
+
+impl AppState {
+    /// Start a snapshot in the background once the backup interval has passed
+    pub fn maybe_backup(&mut self) {
+        if self.backup_minutes == 0 || !self.git_enabled || self.read_only || self.backup_rx.is_some() {
+            return;
+        }
+        let interval = std::time::Duration::from_secs(u64::from(self.backup_minutes) * 60);
+        if self.backup_since.elapsed() < interval {
+            return;
+        }
+        self.start_backup();
+    }
+
+    /// Snapshot the working tree to refs/gitix/backup off the UI thread
+    pub fn start_backup(&mut self) {
+        if self.refuse_read_only("Taking a backup") || self.backup_rx.is_some() {
+            return;
+        }
+        self.backup_since = Instant::now();
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(crate::git::backup_snapshot().map_err(|e| e.to_string()));
+        });
+        self.backup_rx = Some(rx);
+    }
+
+    /// Note a finished snapshot, refreshing the browser if it is open
+    pub fn poll_backup(&mut self) {
+        let result = match &self.backup_rx {
+            Some(rx) => rx.try_recv(),
+            None => return,
+        };
+        match result {
+            Ok(result) => {
+                self.backup_rx = None;
+                self.backup_error = result.err();
+                if self.backup_browser.as_ref().is_some_and(|b| b.files.is_none()) {
+                    self.open_backup_browser();
+                }
+            }
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => self.backup_rx = None,
+            Err(std::sync::mpsc::TryRecvError::Empty) => {}
+        }
+    }
+
+    /// Open the browser on the list of snapshots
+    pub fn open_backup_browser(&mut self) {
+        match crate::git::list_backups(BACKUP_LIST_LIMIT) {
+            Ok(snapshots) => {
+                let selected = self
+                    .backup_browser
+                    .as_ref()
+                    .map_or(0, |browser| browser.selected.min(snapshots.len().saturating_sub(1)));
+                self.backup_browser = Some(BackupBrowser {
+                    snapshots,
+                    selected,
+                    files: None,
+                });
+            }
+            Err(e) => self.show_error(
+                "Backups",
+                &format!("Failed to list backup snapshots:\n\n{}", e),
+            ),
+        }
+    }
+
+    /// Move the selection in whichever list the browser shows
+    pub fn backup_browser_navigate(&mut self, delta: isize) {
+        let browser = match self.backup_browser.as_mut() {
+            Some(browser) => browser,
+            None => return,
+        };
+        let (selected, count) = match browser.files.as_mut() {
+            Some(files) if files.confirm => return,
+            Some(files) => (&mut files.selected, files.paths.len()),
+            None => (&mut browser.selected, browser.snapshots.len()),
+        };
+        if count > 0 {
+            *selected = selected.saturating_add_signed(delta).min(count - 1);
+        }
+    }
+
+    /// Enter: open the selected snapshot, or ask to restore the selected file
+    pub fn backup_browser_enter(&mut self) {
+        let browser = match self.backup_browser.as_mut() {
+            Some(browser) => browser,
+            None => return,
+        };
+        if let Some(files) = browser.files.as_mut() {
+            if !files.paths.is_empty() {
+                files.confirm = true;
+            }
+            return;
+        }
+        let id = match browser.snapshots.get(browser.selected) {
+            Some(snapshot) => snapshot.id.clone(),
+            None => return,
+        };
+        match crate::git::backup_changed_files(&id) {
+            Ok(paths) => {
+                browser.files = Some(BackupFiles {
+                    paths,
+                    selected: 0,
+                    confirm: false,
+                })
+            }
+            Err(e) => self.show_error(
+                "Backups",
+                &format!("Failed to compare the snapshot with the working tree:\n\n{}", e),
+            ),
+        }
+    }
+
+    /// Write the confirmed file back from the opened snapshot
+    pub fn restore_backup_file(&mut self) {
+        if self.refuse_read_only("Restoring from a backup") {
+            return;
+        }
+        let browser = match self.backup_browser.as_mut() {
+            Some(browser) => browser,
+            None => return,
+        };
+        let (id, files) = match (browser.snapshots.get(browser.selected), browser.files.as_mut()) {
+            (Some(snapshot), Some(files)) if files.confirm => (snapshot.id.clone(), files),
+            _ => return,
+        };
+        files.confirm = false;
+        let path = match files.paths.get(files.selected) {
+            Some(path) => path.clone(),
+            None => return,
+        };
+
+        let result = crate::git::restore_from_backup(&id, &path);
+        if result.is_ok() {
+            files.paths.remove(files.selected);
+            files.selected = files.selected.min(files.paths.len().saturating_sub(1));
+        }
+        self.record_activity(
+            crate::activity::ActivityKind::Restore,
+            &path,
+            &format!("libgit2: write {} from {}:{}", path, crate::git::BACKUP_REF, &id[..7]),
+            match &result {
+                Ok(()) => Ok("Restored from backup".to_string()),
+                Err(e) => Err(e.to_string()),
+            },
+        );
+        if let Err(e) = result {
+            self.show_error(
+                "Restore Failed",
+                &format!("Failed to restore {} from the snapshot:\n\n{}", path, e),
+            );
+        }
+        self.refresh_save_changes_git_status();
+    }
+
+    /// Esc: step back from a confirmation, then from the file list, then close
+    pub fn backup_browser_back(&mut self) {
+        let browser = match self.backup_browser.as_mut() {
+            Some(browser) => browser,
+            None => return,
+        };
+        match browser.files.as_mut() {
+            Some(files) if files.confirm => files.confirm = false,
+            Some(_) => browser.files = None,
+            None => self.backup_browser = None,
+        }
+    }
+}
@@ -0,0 +1,199 @@
+use crate::app::{AppState, BlameView};
+use crate::tui::text;
+use crate::tui::theme::Theme;
+use ratatui::layout::{Constraint, Layout, Margin};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::{layout::Rect, Frame};
+
+/// Width of the commit, author and age columns, before the line number
+const COMMIT_WIDTH: usize = 9;
+const AUTHOR_WIDTH: usize = 16;
+const AGE_WIDTH: usize = 5;
+
+/// Render the blame of the file picked in the Files tab, keeping the cursor line in view
+pub fn render_blame_popup(f: &mut Frame, area: Rect, state: &mut AppState, theme: &Theme) {
+    let time_zone = state.time_zone;
+    let view = match state.blame_view.as_mut() {
+        Some(view) => view,
+        None => return,
+    };
+    let blame = match state.blame_cache.get(&view.path) {
+        Some(blame) => blame,
+        None => return,
+    };
+
+    let popup_area = area.inner(Margin {
+        vertical: 1,
+        horizontal: 2,
+    });
+    f.render_widget(Clear, popup_area);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(
+            "Blame - {} ({} lines) - [↑↓/PgUp/PgDn] Scroll, [Esc] Close",
+            blame.path,
+            blame.lines.len()
+        ))
+        .title_style(theme.popup_title_style())
+        .border_style(theme.popup_border_style())
+        .style(theme.popup_background_style());
+    let inner = block.inner(popup_area).inner(Margin {
+        vertical: 0,
+        horizontal: 1,
+    });
+    f.render_widget(block, popup_area);
+
+    let chunks = Layout::vertical([
+        Constraint::Min(1),    // Lines
+        Constraint::Length(1), // The cursor line's commit
+    ])
+    .split(inner);
+
+    let height = chunks[0].height as usize;
+    if view.cursor < view.offset {
+        view.offset = view.cursor;
+    } else if height > 0 && view.cursor >= view.offset + height {
+        view.offset = view.cursor + 1 - height;
+    }
+
+    let number_width = blame.lines.len().to_string().len();
+    let text_width = (chunks[0].width as usize)
+        .saturating_sub(COMMIT_WIDTH + AUTHOR_WIDTH + AGE_WIDTH + number_width + 5);
+    let lines: Vec<Line> = blame
+        .lines
+        .iter()
+        .enumerate()
+        .skip(view.offset)
+        .take(height)
+        .map(|(idx, line)| {
+            // Only the first line of a run from the same commit is labelled
+            let first_of_run = idx == view.offset || blame.lines[idx - 1].commit != line.commit;
+            let commit = line.commit.as_ref().and_then(|id| blame.commits.get(id));
+            let (id, author, age) = match (first_of_run, commit) {
+                (false, _) => (String::new(), String::new(), String::new()),
+                (true, Some(commit)) => (
+                    commit.short_id.clone(),
+                    text::truncate(&commit.author, AUTHOR_WIDTH - 1),
+                    short_age(commit.time.timestamp()),
+                ),
+                (true, None) => ("-".to_string(), "Not committed".to_string(), String::new()),
+            };
+            let mut spans = vec![
+                Span::styled(
+                    format!("{:<w$}", id, w = COMMIT_WIDTH),
+                    theme.accent_style(),
+                ),
+                Span::styled(
+                    format!("{:<w$}", author, w = AUTHOR_WIDTH),
+                    theme.author_style(),
+                ),
+                Span::styled(
+                    format!("{:>w$}", age, w = AGE_WIDTH),
+                    theme.timestamp_style(),
+                ),
+                Span::styled(
+                    format!(" │ {:>w$} ", idx + 1, w = number_width),
+                    theme.muted_text_style(),
+                ),
+                Span::styled(
+                    text::truncate(&line.text.replace('\t', "    "), text_width),
+                    theme.text_style(),
+                ),
+            ];
+            if idx == view.cursor {
+                spans.insert(0, Span::styled("▌", theme.accent_style()));
+                Line::from(spans).style(theme.highlight_style())
+            } else {
+                spans.insert(0, Span::raw(" "));
+                Line::from(spans)
+            }
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines), chunks[0]);
+
+    let selected = blame
+        .lines
+        .get(view.cursor)
+        .and_then(|line| line.commit.as_ref())
+        .and_then(|id| blame.commits.get(id));
+    let footer = match selected {
+        Some(commit) => {
+            let date = time_zone.format(commit.time, "%Y-%m-%d");
+            let summary = format!(
+                "{} {}, {}: {}",
+                commit.short_id, commit.author, date, commit.summary
+            );
+            Line::styled(
+                text::truncate(&summary, chunks[1].width as usize),
+                theme.secondary_text_style(),
+            )
+        }
+        None => Line::styled("Changed since the last commit", theme.muted_text_style()),
+    };
+    f.render_widget(Paragraph::new(footer), chunks[1]);
+}
+
+/// How long ago a commit was made, in the largest unit that fits: `5m`, `3h`, `12d`, `4mo`, `2y`
+fn short_age(time: i64) -> String {
+    let seconds = (chrono::Local::now().timestamp() - time).max(0);
+    let (minutes, hours, days) = (seconds / 60, seconds / 3600, seconds / 86400);
+    if hours < 1 {
+        format!("{}m", minutes)
+    } else if days < 1 {
+        format!("{}h", hours)
+    } else if days < 60 {
+        format!("{}d", days)
+    } else if days < 365 {
+        format!("{}mo", days / 30)
+    } else {
+        format!("{}y", days / 365)
+    }
+}
+
+impl AppState {
+    /// Blame the file selected in the Files tab, reusing the last result while HEAD
+    /// and the file are unchanged
+    pub fn open_blame(&mut self) {
+        let path = match self.selected_files_entry_path() {
+            Some(path) => path,
+            None => return,
+        };
+        let current = self
+            .blame_cache
+            .get(&path)
+            .is_some_and(|blame| blame.is_current());
+        if !current {
+            match crate::git::blame_file(&path) {
+                Ok(blame) => {
+                    self.blame_cache.insert(path.clone(), blame);
+                }
+                Err(e) => {
+                    self.show_error("Blame", &format!("Can't blame {}:\n\n{}", path, e));
+                    return;
+                }
+            }
+        }
+        self.blame_view = Some(BlameView {
+            path,
+            cursor: 0,
+            offset: 0,
+        });
+    }
+
+    /// Move the blame cursor by `delta` lines, clamped to the file
+    pub fn blame_navigate(&mut self, delta: isize) {
+        let view = match self.blame_view.as_mut() {
+            Some(view) => view,
+            None => return,
+        };
+        let count = self
+            .blame_cache
+            .get(&view.path)
+            .map_or(0, |blame| blame.lines.len());
+        view.cursor = view
+            .cursor
+            .saturating_add_signed(delta)
+            .min(count.saturating_sub(1));
+    }
+}
@@ -0,0 +1,724 @@
+use crate::activity::ActivityKind;
+use crate::app::{AppState, BranchAction, BranchDelete, BranchesView, MergeDraft, MouseList};
+use crate::git::{
+    MergeMode, MergeOutcome, MergePreview, OperationStatus, SyncOperation, SyncOperationType,
+};
+use crate::tui::text;
+use crate::tui::theme::Theme;
+use ratatui::layout::{Alignment, Constraint, Layout, Margin};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table};
+use ratatui::{layout::Rect, Frame};
+use tui_textarea::{CursorMove, TextArea};
+
+/// The merge modes in the order the popup lists them
+const MERGE_MODES: [MergeMode; 3] =
+    [MergeMode::Commit, MergeMode::FastForwardOnly, MergeMode::Squash];
+
+pub fn render_branches_tab(f: &mut Frame, area: Rect, state: &mut AppState) {
+    // Use configured theme from app state
+    let theme = Theme::with_accents_and_title(
+        state.current_theme_accent,
+        state.current_theme_accent2,
+        state.current_theme_accent3,
+        state.current_theme_title,
+    );
+
+    state.load_branches();
+
+    // Set panel background
+    f.render_widget(
+        Block::default().style(theme.secondary_background_style()),
+        area,
+    );
+
+    render_branch_table(f, area, &mut state.branches, &theme);
+    state.record_mouse_target(
+        MouseList::Branches,
+        area,
+        state.branches.table_state.offset(),
+        state.branches.branches.len(),
+    );
+
+    if let Some(input) = state.branch_create.as_mut() {
+        render_create_popup(f, area, input, &theme);
+    }
+    if let Some(delete) = &state.branch_delete {
+        render_delete_popup(f, area, delete, &theme);
+    }
+    if let Some(action) = &state.branch_action {
+        let into = state
+            .branches
+            .branches
+            .iter()
+            .find(|branch| branch.is_head)
+            .map_or("HEAD", |branch| branch.name.as_str());
+        render_action_popup(f, area, action, into, &theme);
+    }
+    if let Some(draft) = state.merge_draft.as_mut() {
+        render_merge_popup(f, area, draft, &theme);
+    }
+}
+
+fn render_branch_table(f: &mut Frame, area: Rect, view: &mut BranchesView, theme: &Theme) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(
+            "Branches ({}) - [Enter] Switch/Merge, [N] New, [D] Delete",
+            view.branches.len()
+        ))
+        .title_style(theme.title_style())
+        .border_style(theme.border_style())
+        .style(theme.secondary_background_style());
+
+    if view.branches.is_empty() {
+        let message = match &view.error {
+            Some(error) => {
+                Line::styled(format!("Failed to list branches: {}", error), theme.error_style())
+            }
+            None => Line::styled(
+                "No branches yet: make the first commit to create one",
+                theme.muted_text_style(),
+            ),
+        };
+        let empty = Paragraph::new(vec![Line::from(""), message])
+            .alignment(Alignment::Center)
+            .block(block);
+        f.render_widget(empty, area);
+        return;
+    }
+
+    match view.table_state.selected() {
+        Some(idx) if idx < view.branches.len() => {}
+        Some(_) => view.table_state.select(Some(view.branches.len() - 1)),
+        None => view.table_state.select(Some(0)),
+    }
+
+    let header = Row::new(vec![
+        Cell::from(""),
+        Cell::from("Branch").style(theme.accent2_style()),
+        Cell::from("Upstream").style(theme.accent2_style()),
+        Cell::from("Ahead/Behind").style(theme.accent2_style()),
+        Cell::from("Last Commit").style(theme.accent2_style()),
+    ]);
+    let widths = [
+        Constraint::Length(1),      // Checked-out marker
+        Constraint::Percentage(25), // Branch
+        Constraint::Percentage(20), // Upstream
+        Constraint::Length(12),     // Ahead/behind
+        Constraint::Min(20),        // Last commit
+    ];
+    let column_widths = text::column_widths(area, &widths, "► ");
+
+    let rows: Vec<Row> = view
+        .branches
+        .iter()
+        .map(|branch| {
+            let marker = if branch.is_head {
+                Cell::from("●").style(theme.success_style())
+            } else {
+                Cell::from("")
+            };
+            let name = Cell::from(text::truncate(&branch.name, column_widths[1])).style(
+                if branch.is_head {
+                    theme.accent3_bold_style()
+                } else {
+                    theme.text_style()
+                },
+            );
+            let (upstream, sync) = match &branch.upstream {
+                None => (
+                    Cell::from("none").style(theme.muted_text_style()),
+                    Cell::from(""),
+                ),
+                Some(upstream) => (
+                    Cell::from(text::truncate(upstream, column_widths[2]))
+                        .style(theme.secondary_text_style()),
+                    match (branch.ahead, branch.behind) {
+                        (0, 0) => Cell::from("up to date").style(theme.success_style()),
+                        (ahead, behind) => Cell::from(Line::from(vec![
+                            Span::styled(format!("↑{} ", ahead), theme.info_style()),
+                            Span::styled(format!("↓{}", behind), theme.warning_style()),
+                        ])),
+                    },
+                ),
+            };
+            let last_commit = Cell::from(Line::from(vec![
+                Span::styled(
+                    format!("{}  ", crate::git::format_relative_time(branch.time)),
+                    theme.timestamp_style(),
+                ),
+                Span::styled(branch.summary.clone(), theme.commit_message_style()),
+            ]));
+            Row::new(vec![marker, name, upstream, sync, last_commit])
+        })
+        .collect();
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(block)
+        .row_highlight_style(theme.highlight_style())
+        .highlight_symbol("► ");
+    f.render_stateful_widget(table, area, &mut view.table_state);
+}
+
+fn render_create_popup(f: &mut Frame, area: Rect, input: &mut TextArea<'static>, theme: &Theme) {
+    let popup_area = super::centered_rect(50, 7, area);
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title("New Branch")
+        .title_style(theme.popup_title_style())
+        .borders(Borders::ALL)
+        .border_style(theme.popup_border_style())
+        .style(theme.popup_background_style());
+    let inner = block.inner(popup_area).inner(Margin {
+        vertical: 0,
+        horizontal: 1,
+    });
+    f.render_widget(block, popup_area);
+
+    let chunks = Layout::vertical([
+        Constraint::Length(3), // Name
+        Constraint::Length(1), // Help
+    ])
+    .split(inner);
+
+    input.set_block(
+        Block::default()
+            .title("Name (starts at the current commit)")
+            .borders(Borders::ALL)
+            .border_style(theme.focused_border_style()),
+    );
+    input.set_style(theme.text_style());
+    input.set_cursor_style(theme.text_style().add_modifier(ratatui::style::Modifier::REVERSED));
+    f.render_widget(&*input, chunks[0]);
+
+    f.render_widget(
+        Paragraph::new("[Enter] Create and Switch  [Esc] Cancel").style(theme.secondary_text_style()),
+        chunks[1],
+    );
+}
+
+fn render_delete_popup(f: &mut Frame, area: Rect, delete: &BranchDelete, theme: &Theme) {
+    let popup_area = super::centered_rect(60, 8, area);
+    f.render_widget(Clear, popup_area);
+
+    let (question, detail) = if delete.merged {
+        (
+            format!("Delete branch '{}'?", delete.name),
+            "Its commits are all part of the current branch, so nothing is lost.".to_string(),
+        )
+    } else {
+        (
+            format!("Force delete branch '{}'?", delete.name),
+            "It has commits the current branch doesn't; they will only be recoverable \
+             from the reflog."
+                .to_string(),
+        )
+    };
+    let lines = vec![
+        Line::styled(question, theme.text_style()),
+        Line::from(""),
+        Line::styled(
+            detail,
+            if delete.merged {
+                theme.secondary_text_style()
+            } else {
+                theme.warning_style()
+            },
+        ),
+        Line::from(""),
+        Line::styled("[Y] Delete  [N] Cancel", theme.secondary_text_style()),
+    ];
+    let popup = Paragraph::new(lines)
+        .wrap(ratatui::widgets::Wrap { trim: true })
+        .block(
+            Block::default()
+                .title("Delete Branch")
+                .title_style(theme.popup_title_style())
+                .borders(Borders::ALL)
+                .border_style(theme.popup_border_style())
+                .style(theme.popup_background_style()),
+        );
+    f.render_widget(popup, popup_area);
+}
+
+fn render_action_popup(f: &mut Frame, area: Rect, action: &BranchAction, into: &str, theme: &Theme) {
+    let popup_area = super::centered_rect(50, 7, area);
+    f.render_widget(Clear, popup_area);
+
+    let choices = [
+        (format!("Switch to {}", action.name), !action.merge),
+        (format!("Merge {} into {}", action.name, into), action.merge),
+    ];
+    let mut lines: Vec<Line> = choices
+        .into_iter()
+        .map(|(label, selected)| {
+            if selected {
+                Line::styled(format!("► {}", label), theme.highlight_style())
+            } else {
+                Line::styled(format!("  {}", label), theme.text_style())
+            }
+        })
+        .collect();
+    lines.push(Line::from(""));
+    lines.push(Line::styled(
+        "[↑↓] Choose  [Enter] Go  [S] Switch  [M] Merge  [Esc] Cancel",
+        theme.secondary_text_style(),
+    ));
+    let popup = Paragraph::new(lines).block(
+        Block::default()
+            .title(format!("Branch '{}'", action.name))
+            .title_style(theme.popup_title_style())
+            .borders(Borders::ALL)
+            .border_style(theme.popup_border_style())
+            .style(theme.popup_background_style()),
+    );
+    f.render_widget(popup, popup_area);
+}
+
+fn render_merge_popup(f: &mut Frame, area: Rect, draft: &mut MergeDraft, theme: &Theme) {
+    let popup_area = super::centered_rect(70, 20, area);
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(format!("Merge {} into {}", draft.branch, draft.into))
+        .title_style(theme.popup_title_style())
+        .borders(Borders::ALL)
+        .border_style(theme.popup_border_style())
+        .style(theme.popup_background_style());
+    let inner = block.inner(popup_area).inner(Margin {
+        vertical: 0,
+        horizontal: 1,
+    });
+    f.render_widget(block, popup_area);
+
+    let chunks = Layout::vertical([
+        Constraint::Length(1), // Modes
+        Constraint::Length(3), // What the picked mode does
+        Constraint::Min(3),    // Message
+        Constraint::Length(1), // Help
+    ])
+    .split(inner);
+
+    let mut modes = Vec::new();
+    for mode in MERGE_MODES {
+        let label = format!(" {} ", mode.as_str());
+        modes.push(if mode == draft.mode {
+            Span::styled(label, theme.highlight_style())
+        } else {
+            Span::styled(label, theme.secondary_text_style())
+        });
+        modes.push(Span::raw("  "));
+    }
+    f.render_widget(Paragraph::new(Line::from(modes)), chunks[0]);
+
+    let count = draft.preview.commits.len();
+    let commits = format!("{} commit{}", count, if count == 1 { "" } else { "s" });
+    let (effect, style) = match draft.mode {
+        MergeMode::Commit => (
+            format!(
+                "Brings in {} and records a merge commit with both branches as parents.",
+                commits
+            ),
+            theme.secondary_text_style(),
+        ),
+        MergeMode::FastForwardOnly if draft.preview.fast_forward => (
+            format!(
+                "{} moves forward to {}, picking up {}. No commit is made.",
+                draft.into, draft.branch, commits
+            ),
+            theme.secondary_text_style(),
+        ),
+        MergeMode::FastForwardOnly => (
+            format!(
+                "{} has commits {} doesn't, so it can't simply move forward.",
+                draft.into, draft.branch
+            ),
+            theme.warning_style(),
+        ),
+        MergeMode::Squash => (
+            format!(
+                "The changes of {} are committed as one ordinary commit; {} isn't recorded \
+                 as merged.",
+                commits, draft.branch
+            ),
+            theme.secondary_text_style(),
+        ),
+    };
+    f.render_widget(
+        Paragraph::new(effect)
+            .style(style)
+            .wrap(ratatui::widgets::Wrap { trim: true }),
+        chunks[1],
+    );
+
+    let editing = draft.mode != MergeMode::FastForwardOnly;
+    draft.message.set_block(
+        Block::default()
+            .title("Commit Message")
+            .borders(Borders::ALL)
+            .border_style(if editing {
+                theme.focused_border_style()
+            } else {
+                theme.border_style()
+            }),
+    );
+    draft.message.set_style(if editing {
+        theme.text_style()
+    } else {
+        theme.muted_text_style()
+    });
+    draft.message.set_cursor_style(if editing {
+        theme.text_style().add_modifier(ratatui::style::Modifier::REVERSED)
+    } else {
+        theme.muted_text_style()
+    });
+    f.render_widget(&draft.message, chunks[2]);
+
+    f.render_widget(
+        Paragraph::new("[Tab] Mode  [Ctrl+S] Merge  [Esc] Cancel")
+            .style(theme.secondary_text_style()),
+        chunks[3],
+    );
+}
+
+/// The message a merge in `mode` starts with, as git would write it
+fn default_merge_message(
+    mode: MergeMode,
+    branch: &str,
+    into: &str,
+    preview: &MergePreview,
+) -> String {
+    match mode {
+        MergeMode::Squash => {
+            let mut message = format!("Squash branch '{}' into {}\n", branch, into);
+            for commit in &preview.commits {
+                message.push_str(&format!("\n* {}", commit.summary));
+            }
+            message
+        }
+        _ => format!("Merge branch '{}' into {}", branch, into),
+    }
+}
+
+impl AppState {
+    /// Read the branch list unless it is already current
+    pub fn load_branches(&mut self) {
+        if self.branches.loaded {
+            return;
+        }
+        self.branches.loaded = true;
+        match crate::git::list_branches(&mut self.branches.ahead_behind) {
+            Ok(branches) => {
+                self.branches.branches = branches;
+                self.branches.error = None;
+            }
+            Err(e) => {
+                self.branches.branches.clear();
+                self.branches.error = Some(e.to_string());
+            }
+        }
+    }
+
+    /// Re-read the branch list, keeping the selection on the same branch
+    fn reload_branches(&mut self, select: Option<&str>) {
+        let selected = select.map(str::to_string).or_else(|| {
+            self.selected_branch().map(|branch| branch.name.clone())
+        });
+        self.branches.loaded = false;
+        self.load_branches();
+        let idx = selected.and_then(|name| {
+            self.branches.branches.iter().position(|branch| branch.name == name)
+        });
+        if idx.is_some() {
+            self.branches.table_state.select(idx);
+        }
+    }
+
+    pub fn branches_navigate(&mut self, delta: isize) {
+        if self.branches.branches.is_empty() {
+            return;
+        }
+        let current = self.branches.table_state.selected().unwrap_or(0);
+        let next = current
+            .saturating_add_signed(delta)
+            .min(self.branches.branches.len() - 1);
+        self.branches.table_state.select(Some(next));
+    }
+
+    pub fn selected_branch(&self) -> Option<&crate::git::BranchInfo> {
+        self.branches
+            .table_state
+            .selected()
+            .and_then(|idx| self.branches.branches.get(idx))
+    }
+
+    /// Switch to the selected branch
+    pub fn checkout_selected_branch(&mut self) {
+        if self.refuse_read_only("Switching branches") {
+            return;
+        }
+        let name = match self.selected_branch() {
+            Some(branch) if !branch.is_head => branch.name.clone(),
+            _ => return,
+        };
+        self.switch_branch(&name);
+    }
+
+    /// Check out `name`, log it and re-read everything that depends on HEAD
+    fn switch_branch(&mut self, name: &str) {
+        let result = crate::git::checkout_branch(name);
+        self.record_activity(
+            ActivityKind::Branch,
+            name,
+            &format!("libgit2: safe checkout of refs/heads/{} and set HEAD", name),
+            match &result {
+                Ok(()) => Ok(format!("Switched to {}", name)),
+                Err(e) => Err(e.to_string()),
+            },
+        );
+        if let Err(e) = result {
+            self.show_error("Switch Failed", &format!("Failed to switch to {}:\n\n{}", name, e));
+        }
+        self.reload_branches(Some(name));
+        self.invalidate_save_changes_git_status();
+        self.invalidate_status_git_status();
+        // Only local refs are read, so the Update tab can follow the new branch at once
+        self.update_remote_status = crate::git::get_remote_status().ok();
+    }
+
+    /// Ask for the name of a new branch
+    pub fn open_branch_create(&mut self) {
+        self.branch_create = Some(TextArea::default());
+    }
+
+    /// Create the branch named in the popup at HEAD and switch to it
+    pub fn create_branch_from_input(&mut self) {
+        if self.refuse_read_only("Creating branches") {
+            return;
+        }
+        let name = match &self.branch_create {
+            Some(input) => input.lines().join("").trim().to_string(),
+            None => return,
+        };
+        if name.is_empty() {
+            return;
+        }
+        self.branch_create = None;
+
+        let result = crate::git::create_branch(&name);
+        self.record_activity(
+            ActivityKind::Branch,
+            &name,
+            &format!("libgit2: create refs/heads/{} at HEAD", name),
+            match &result {
+                Ok(()) => Ok(format!("Created {}", name)),
+                Err(e) => Err(e.to_string()),
+            },
+        );
+        match result {
+            Ok(()) => self.switch_branch(&name),
+            Err(e) => {
+                self.show_error("Create Failed", &format!("Failed to create {}:\n\n{}", name, e))
+            }
+        }
+    }
+
+    /// Ask before deleting the selected branch
+    pub fn request_branch_delete(&mut self) {
+        let (name, merged, is_head) = match self.selected_branch() {
+            Some(branch) => (branch.name.clone(), branch.merged, branch.is_head),
+            None => return,
+        };
+        if is_head {
+            self.show_error(
+                "Delete Branch",
+                &format!("'{}' is checked out. Switch to another branch to delete it.", name),
+            );
+            return;
+        }
+        if !merged && self.hidden_by_profile("Deleting a branch that isn't merged") {
+            return;
+        }
+        self.branch_delete = Some(BranchDelete { name, merged });
+    }
+
+    /// Delete the branch waiting for confirmation, forcing it when it isn't merged
+    pub fn confirm_branch_delete(&mut self) {
+        if self.refuse_read_only("Deleting branches") {
+            return;
+        }
+        let delete = match self.branch_delete.take() {
+            Some(delete) => delete,
+            None => return,
+        };
+        let result = crate::git::delete_branch(&delete.name, !delete.merged);
+        self.record_activity(
+            ActivityKind::Branch,
+            &delete.name,
+            &format!("libgit2: delete refs/heads/{}", delete.name),
+            match &result {
+                Ok(()) => Ok(format!("Deleted {}", delete.name)),
+                Err(e) => Err(e.to_string()),
+            },
+        );
+        if let Err(e) = result {
+            self.show_error(
+                "Delete Failed",
+                &format!("Failed to delete {}:\n\n{}", delete.name, e),
+            );
+        }
+        self.reload_branches(None);
+    }
+
+    /// Offer to switch to the selected branch or merge it into the current one
+    pub fn open_branch_action(&mut self) {
+        let name = match self.selected_branch() {
+            Some(branch) if !branch.is_head => branch.name.clone(),
+            _ => return,
+        };
+        self.branch_action = Some(BranchAction { name, merge: false });
+    }
+
+    /// Carry out the choice in the branch popup: switch, or go on to the merge popup
+    pub fn confirm_branch_action(&mut self) {
+        match self.branch_action.take() {
+            Some(action) if action.merge => self.request_merge(&action.name),
+            Some(_) => self.checkout_selected_branch(),
+            None => {}
+        }
+    }
+
+    /// Open the merge popup for `name`, starting on a merge commit with git's message
+    fn request_merge(&mut self, name: &str) {
+        if self.refuse_read_only("Merging") {
+            return;
+        }
+        let preview = match crate::git::merge_preview(name) {
+            Ok(preview) if preview.commits.is_empty() => {
+                self.show_error(
+                    "Merge",
+                    &format!("Already up to date: every commit of {} is on this branch.", name),
+                );
+                return;
+            }
+            Ok(preview) => preview,
+            Err(e) => {
+                self.show_error("Merge", &format!("Failed to read {}:\n\n{}", name, e));
+                return;
+            }
+        };
+        let into = crate::git::get_current_branch().unwrap_or_else(|_| "HEAD".to_string());
+        let message = default_merge_message(MergeMode::Commit, name, &into, &preview);
+        let mut message = TextArea::new(message.lines().map(str::to_string).collect());
+        message.move_cursor(CursorMove::Bottom);
+        message.move_cursor(CursorMove::End);
+        self.merge_draft = Some(MergeDraft {
+            branch: name.to_string(),
+            into,
+            mode: MergeMode::Commit,
+            preview,
+            message,
+        });
+    }
+
+    /// Pick the next merge mode, swapping in its message unless the message was edited
+    pub fn merge_next_mode(&mut self, forward: bool) {
+        if let Some(draft) = self.merge_draft.as_mut() {
+            let current = MERGE_MODES.iter().position(|mode| *mode == draft.mode).unwrap_or(0);
+            let next = if forward {
+                (current + 1) % MERGE_MODES.len()
+            } else {
+                (current + MERGE_MODES.len() - 1) % MERGE_MODES.len()
+            };
+            let old = default_merge_message(draft.mode, &draft.branch, &draft.into, &draft.preview);
+            draft.mode = MERGE_MODES[next];
+            if draft.message.lines().join("\n") == old {
+                let new =
+                    default_merge_message(draft.mode, &draft.branch, &draft.into, &draft.preview);
+                draft.message = TextArea::new(new.lines().map(str::to_string).collect());
+                draft.message.move_cursor(CursorMove::Bottom);
+                draft.message.move_cursor(CursorMove::End);
+            }
+        }
+    }
+
+    /// Merge as the popup says, then re-read everything HEAD affects
+    ///
+    /// The result also goes in the Update tab's recent operations. Conflicts
+    /// open the conflicts view; after a squash the message waits in Save
+    /// Changes for the commit that follows.
+    pub fn confirm_merge(&mut self) {
+        let MergeDraft {
+            branch,
+            into,
+            mode,
+            message,
+            ..
+        } = match self.merge_draft.take() {
+            Some(draft) => draft,
+            None => return,
+        };
+        let message = message.lines().join("\n");
+        let start_time = std::time::SystemTime::now();
+        let result = crate::git::merge_branch(&branch, mode, &message);
+        let outcome = match &result {
+            Ok(MergeOutcome::FastForwarded(id)) => {
+                Ok(format!("Fast-forwarded {} to {} ({})", into, branch, id))
+            }
+            Ok(MergeOutcome::Committed(id)) => Ok(format!(
+                "Merged {} into {} as {} ({})",
+                branch,
+                into,
+                id,
+                mode.as_str()
+            )),
+            Ok(MergeOutcome::Conflicts(paths)) => Err(format!(
+                "Merge of {} stopped on conflicts in {} file{}",
+                branch,
+                paths.len(),
+                if paths.len() == 1 { "" } else { "s" }
+            )),
+            Err(e) => Err(format!("Failed to merge {}: {}", branch, e)),
+        };
+        let operation = match mode {
+            MergeMode::Commit => format!("libgit2: merge {}, then commit with both parents", branch),
+            MergeMode::FastForwardOnly => {
+                format!("libgit2: fast-forward refs/heads/{} to {}", into, branch)
+            }
+            MergeMode::Squash => format!("libgit2: merge {}, then commit with one parent", branch),
+        };
+        self.record_activity(ActivityKind::Merge, &into, &operation, outcome.clone());
+        let (status, summary) = match &outcome {
+            Ok(message) => (OperationStatus::Success, message.clone()),
+            Err(message) => (OperationStatus::Error, message.clone()),
+        };
+        self.add_sync_operation(SyncOperation {
+            operation_type: SyncOperationType::Merge,
+            status,
+            message: summary,
+            timestamp: start_time,
+        });
+
+        match result {
+            Ok(MergeOutcome::Conflicts(_)) => {
+                if mode == MergeMode::Squash {
+                    self.commit_message =
+                        TextArea::new(message.lines().map(str::to_string).collect());
+                }
+                self.open_conflicts();
+            }
+            Ok(_) => {
+                self.history_message = outcome.ok().map(|message| format!("✓ {}", message));
+            }
+            Err(e) => self.show_error("Merge Failed", &e.to_string()),
+        }
+        self.reload_branches(None);
+        self.refresh_save_changes_git_status();
+        self.invalidate_status_git_status();
+        self.reload_history_if_moved();
+        self.update_remote_status = crate::git::get_remote_status().ok();
+    }
+}
@@ -0,0 +1,136 @@
+use crate::activity::ActivityKind;
+use crate::app::{AppState, CherryPickConfirm};
+use crate::git::{CherryPickOutcome, OperationStatus, SyncOperation, SyncOperationType};
+use crate::tui::theme::Theme;
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::{layout::Rect, Frame};
+
+/// Render the confirmation for a cherry-pick: which commit, onto which branch
+pub fn render_cherry_pick_popup(f: &mut Frame, area: Rect, confirm: &CherryPickConfirm, theme: &Theme) {
+    let popup_area = super::centered_rect(64, 11, area);
+    f.render_widget(Clear, popup_area);
+
+    let commit = &confirm.commit;
+    let lines = vec![
+        Line::styled(
+            format!("Cherry-pick {} \"{}\" onto {}?", commit.short_id, commit.summary, confirm.onto),
+            theme.text_style(),
+        ),
+        Line::from(""),
+        Line::styled(
+            format!(
+                "Its changes are applied as a new commit on {}, keeping {}'s authorship \
+                 and message. On conflicts the conflicts view opens to resolve them.",
+                confirm.onto, commit.author
+            ),
+            theme.secondary_text_style(),
+        ),
+        Line::from(""),
+        Line::styled("[Y] Cherry-pick  [N] Cancel", theme.secondary_text_style()),
+    ];
+    let popup = Paragraph::new(lines)
+        .wrap(ratatui::widgets::Wrap { trim: true })
+        .block(
+            Block::default()
+                .title("Cherry-pick")
+                .title_style(theme.popup_title_style())
+                .borders(Borders::ALL)
+                .border_style(theme.popup_border_style())
+                .style(theme.popup_background_style()),
+        );
+    f.render_widget(popup, popup_area);
+}
+
+impl AppState {
+    /// Ask before cherry-picking the commit History has selected
+    pub fn request_cherry_pick_from_history(&mut self) {
+        let commit = self
+            .history
+            .table_state
+            .selected()
+            .and_then(|idx| self.history.commits.get(idx))
+            .cloned();
+        if let Some(commit) = commit {
+            self.request_cherry_pick(commit);
+        }
+    }
+
+    /// Ask before cherry-picking the tip of the branch Branches has selected
+    pub fn request_cherry_pick_from_branches(&mut self) {
+        let name = match self.selected_branch() {
+            Some(branch) if !branch.is_head => branch.name.clone(),
+            Some(_) => {
+                self.show_error("Cherry-pick", "Pick another branch: this one is checked out.");
+                return;
+            }
+            None => return,
+        };
+        match crate::git::find_commit_summary(&format!("refs/heads/{}", name)) {
+            Ok(commit) => self.request_cherry_pick(commit),
+            Err(e) => self.show_error("Cherry-pick", &format!("Failed to read {}:\n\n{}", name, e)),
+        }
+    }
+
+    fn request_cherry_pick(&mut self, commit: crate::git::CommitSummary) {
+        if self.refuse_read_only("Cherry-picking") {
+            return;
+        }
+        let onto = crate::git::get_current_branch().unwrap_or_else(|_| "HEAD".to_string());
+        self.cherry_pick_confirm = Some(CherryPickConfirm { commit, onto });
+    }
+
+    /// Cherry-pick the confirmed commit, then re-read everything HEAD affects
+    ///
+    /// The result also goes in the Update tab's recent operations. Conflicts
+    /// leave the cherry-pick in progress and open the conflicts view.
+    pub fn confirm_cherry_pick(&mut self) {
+        let CherryPickConfirm { commit, onto } = match self.cherry_pick_confirm.take() {
+            Some(confirm) => confirm,
+            None => return,
+        };
+        let start_time = std::time::SystemTime::now();
+        let result = crate::git::cherry_pick(&commit.id);
+        let picked = format!("{} \"{}\"", commit.short_id, commit.summary);
+        let outcome = match &result {
+            Ok(CherryPickOutcome::Committed(id)) => {
+                Ok(format!("Cherry-picked {} onto {} as {}", picked, onto, id))
+            }
+            Ok(CherryPickOutcome::Conflicts(paths)) => Err(format!(
+                "Cherry-pick of {} stopped on conflicts in {} file{}",
+                picked,
+                paths.len(),
+                if paths.len() == 1 { "" } else { "s" }
+            )),
+            Err(e) => Err(format!("Failed to cherry-pick {}: {}", picked, e)),
+        };
+        self.record_activity(
+            ActivityKind::CherryPick,
+            &onto,
+            &format!("libgit2: cherry-pick {} onto HEAD, then commit", commit.short_id),
+            outcome.clone(),
+        );
+        let (status, message) = match &outcome {
+            Ok(message) => (OperationStatus::Success, message.clone()),
+            Err(message) => (OperationStatus::Error, message.clone()),
+        };
+        self.add_sync_operation(SyncOperation {
+            operation_type: SyncOperationType::CherryPick,
+            status,
+            message,
+            timestamp: start_time,
+        });
+
+        match result {
+            Ok(CherryPickOutcome::Committed(_)) => {
+                self.history_message = outcome.ok().map(|message| format!("✓ {}", message));
+            }
+            Ok(CherryPickOutcome::Conflicts(_)) => self.open_conflicts(),
+            Err(e) => self.show_error("Cherry-pick Failed", &e.to_string()),
+        }
+        self.refresh_save_changes_git_status();
+        self.invalidate_status_git_status();
+        self.reload_history_if_moved();
+        self.branches.loaded = false;
+    }
+}
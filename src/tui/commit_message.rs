@@ -0,0 +1,154 @@
+use crate::tui::text;
+use crate::tui::theme::Theme;
+use ratatui::style::Modifier;
+use ratatui::text::{Line, Span};
+
+/// Lay out a commit message for a `width`-column view
+///
+/// The subject is bold, body paragraphs are wrapped at word boundaries, list
+/// items (`-`, `*`, `+`, `1.`) get a bullet with their continuation lines
+/// indented under the text, indented or fenced code is left as written, and
+/// a closing block of trailers (Signed-off-by, Co-authored-by, ...) is dimmed.
+pub fn message_lines(message: &str, width: usize, theme: &Theme) -> Vec<Line<'static>> {
+    let width = width.max(10);
+    let raw: Vec<&str> = message.trim_end().lines().collect();
+    let trailers_start = trailer_block_start(&raw);
+
+    let mut lines = Vec::new();
+    let mut in_fence = false;
+    for (idx, line) in raw.iter().enumerate() {
+        if idx == 0 {
+            for part in wrap(line, width) {
+                lines.push(Line::styled(
+                    part,
+                    theme.commit_message_style().add_modifier(Modifier::BOLD),
+                ));
+            }
+            continue;
+        }
+
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            lines.push(Line::styled(line.to_string(), theme.muted_text_style()));
+            continue;
+        }
+        if in_fence || line.starts_with("    ") || line.starts_with('\t') {
+            lines.push(Line::styled(line.to_string(), theme.accent3_style()));
+            continue;
+        }
+        if trailers_start.is_some_and(|start| idx >= start) {
+            for part in wrap(line, width) {
+                lines.push(Line::styled(part, theme.muted_text_style()));
+            }
+            continue;
+        }
+
+        match list_item(line) {
+            Some((indent, marker, rest)) => {
+                let bullet = format!("{}{} ", " ".repeat(indent), marker);
+                let hang = " ".repeat(text::width(&bullet));
+                let parts = wrap(rest, width.saturating_sub(hang.len()));
+                for (i, part) in parts.into_iter().enumerate() {
+                    let lead = if i == 0 { bullet.clone() } else { hang.clone() };
+                    lines.push(Line::from(vec![
+                        Span::styled(lead, theme.accent_style()),
+                        Span::styled(part, theme.commit_message_style()),
+                    ]));
+                }
+            }
+            None => {
+                // Keep the line's own indent on every wrapped piece
+                let indent = line.len() - line.trim_start().len();
+                let lead = " ".repeat(indent);
+                for part in wrap(line.trim_start(), width.saturating_sub(indent)) {
+                    lines.push(Line::styled(
+                        format!("{}{}", lead, part),
+                        theme.commit_message_style(),
+                    ));
+                }
+            }
+        }
+    }
+    lines
+}
+
+/// Split a list item into its indent, the bullet to show and the item's text
+fn list_item(line: &str) -> Option<(usize, String, &str)> {
+    let trimmed = line.trim_start();
+    let indent = line.len() - trimmed.len();
+    for marker in ["- ", "* ", "+ "] {
+        if let Some(rest) = trimmed.strip_prefix(marker) {
+            return Some((indent, "•".to_string(), rest.trim_start()));
+        }
+    }
+    // Numbered items keep their number
+    let digits = trimmed.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits > 0 && digits < 4 {
+        let rest = &trimmed[digits..];
+        for separator in [". ", ") "] {
+            if let Some(rest) = rest.strip_prefix(separator) {
+                let number = format!("{}{}", &trimmed[..digits], separator.trim_end());
+                return Some((indent, number, rest.trim_start()));
+            }
+        }
+    }
+    None
+}
+
+/// Where the trailer block starts: the last paragraph, if every line of it is `Token: value`
+fn trailer_block_start(lines: &[&str]) -> Option<usize> {
+    let end = lines.len();
+    let start = lines
+        .iter()
+        .rposition(|line| line.trim().is_empty())
+        .map(|blank| blank + 1)?;
+    if start >= end || start <= 1 {
+        return None;
+    }
+    let is_trailer = |line: &&str| match line.split_once(": ") {
+        Some((token, value)) => {
+            !value.trim().is_empty()
+                && token.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+                && token.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        }
+        None => false,
+    };
+    lines[start..end].iter().all(is_trailer).then_some(start)
+}
+
+/// Break `line` into pieces of at most `width` columns at spaces, splitting words that don't fit
+fn wrap(line: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut pieces = Vec::new();
+    let mut current = String::new();
+    for word in line.split_whitespace() {
+        let needed = if current.is_empty() {
+            text::width(word)
+        } else {
+            text::width(&current) + 1 + text::width(word)
+        };
+        if needed <= width {
+            if !current.is_empty() {
+                current.push(' ');
+            }
+            current.push_str(word);
+            continue;
+        }
+        if !current.is_empty() {
+            pieces.push(std::mem::take(&mut current));
+        }
+        // A word longer than the line is cut into line-sized chunks
+        let mut chunk = String::new();
+        for c in word.chars() {
+            if text::width(&chunk) + text::width(&c.to_string()) > width {
+                pieces.push(std::mem::take(&mut chunk));
+            }
+            chunk.push(c);
+        }
+        current = chunk;
+    }
+    if !current.is_empty() || pieces.is_empty() {
+        pieces.push(current);
+    }
+    pieces
+}
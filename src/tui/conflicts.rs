@@ -0,0 +1,492 @@
+use crate::activity::ActivityKind;
+use crate::app::{AppState, ConflictPane, ConflictsView};
+use crate::git::ConflictSide;
+use crate::tui::theme::Theme;
+use ratatui::layout::{Constraint, Direction, Layout, Margin};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph};
+use ratatui::{layout::Rect, Frame};
+
+/// Render the conflicted files of an in-progress merge with one side of the selected file
+pub fn render_conflicts_popup(f: &mut Frame, area: Rect, state: &mut AppState, theme: &Theme) {
+    let view = match state.conflicts.as_mut() {
+        Some(view) => view,
+        None => return,
+    };
+
+    let popup_area = area.inner(Margin {
+        vertical: 2,
+        horizontal: area.width / 20,
+    });
+    f.render_widget(Clear, popup_area);
+
+    let resolved = view
+        .entries
+        .iter()
+        .filter(|e| view.resolved.contains(&e.path))
+        .count();
+    let title = if view.entries.is_empty() {
+        "Merge Conflicts - all resolved".to_string()
+    } else {
+        format!(
+            "Merge Conflicts - {} of {} resolved",
+            resolved,
+            view.entries.len()
+        )
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .title_style(theme.popup_title_style())
+        .border_style(theme.popup_border_style())
+        .style(theme.popup_background_style());
+    f.render_widget(block, popup_area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(1)])
+        .split(popup_area.inner(Margin {
+            vertical: 1,
+            horizontal: 1,
+        }));
+    let panes = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
+        .split(rows[0]);
+
+    // Conflicted paths, ticked off as they are resolved
+    let items: Vec<ListItem> = view
+        .entries
+        .iter()
+        .map(|entry| {
+            if view.resolved.contains(&entry.path) {
+                ListItem::new(Line::styled(
+                    format!("✓ {}", entry.path),
+                    theme.success_style(),
+                ))
+            } else {
+                ListItem::new(Line::styled(
+                    format!("✗ {}", entry.path),
+                    theme.error_style(),
+                ))
+            }
+        })
+        .collect();
+    let mut list_state = ListState::default().with_selected(Some(view.selected));
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Files")
+                .border_style(theme.border_style()),
+        )
+        .highlight_style(theme.highlight_style())
+        .highlight_symbol("► ");
+    f.render_stateful_widget(list, panes[0], &mut list_state);
+
+    // The chosen side of the selected file, with the other sides named in the title
+    let entry = view.entries.get(view.selected);
+    let mut title = Vec::new();
+    for (pane, name) in [
+        (ConflictPane::Ours, "Ours"),
+        (ConflictPane::Theirs, "Theirs"),
+        (ConflictPane::Base, "Base"),
+    ] {
+        let style = if pane == view.pane {
+            theme.active_style()
+        } else {
+            theme.muted_text_style()
+        };
+        title.push(Span::styled(format!(" {} ", name), style));
+    }
+    title.push(Span::styled(" [←→] switch ", theme.muted_text_style()));
+
+    let content = entry.map(|entry| match view.pane {
+        ConflictPane::Ours => entry.ours.as_deref().ok_or("(deleted on our side)"),
+        ConflictPane::Theirs => entry.theirs.as_deref().ok_or("(deleted on their side)"),
+        ConflictPane::Base => entry
+            .base
+            .as_deref()
+            .ok_or("(added on both sides: no base)"),
+    });
+    let lines: Vec<Line> = match content {
+        Some(Ok(text)) => text
+            .lines()
+            .map(|line| Line::styled(line, theme.text_style()))
+            .collect(),
+        Some(Err(note)) => vec![Line::styled(note, theme.muted_text_style())],
+        None => Vec::new(),
+    };
+
+    let visible_height = panes[1].height.saturating_sub(2) as usize;
+    view.scroll = view.scroll.min(lines.len().saturating_sub(visible_height));
+    f.render_widget(
+        Paragraph::new(lines).scroll((view.scroll as u16, 0)).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(Line::from(title))
+                .border_style(theme.border_style()),
+        ),
+        panes[1],
+    );
+
+    let footer = match &view.message {
+        Some(message) => Line::styled(message.as_str(), theme.warning_style()),
+        None if resolved == view.entries.len() => Line::styled(
+            "All conflicts resolved - [C] Commit the merge  [A] Abort the merge  [Esc] Close",
+            theme.success_style(),
+        ),
+        None => Line::styled(
+            "[O] Keep ours  [T] Keep theirs  [E] Edit in $EDITOR  [M] Mergetool  [R] Mark resolved  \
+             [A] Abort  [PgUp/PgDn] Scroll  [Esc] Close",
+            theme.muted_text_style(),
+        ),
+    };
+    f.render_widget(Paragraph::new(footer), rows[1]);
+}
+
+/// Render the confirmation for aborting the merge or rebase in progress
+pub fn render_abort_popup(f: &mut Frame, area: Rect, operation: &str, theme: &Theme) {
+    let popup_area = super::centered_rect(60, 9, area);
+    f.render_widget(Clear, popup_area);
+
+    let lines = vec![
+        Line::styled(
+            format!("Abort the {} in progress?", operation),
+            theme.text_style(),
+        ),
+        Line::from(""),
+        Line::styled(
+            format!(
+                "The branch, staged changes and tracked files go back to how they were before \
+                 the {} started. Conflicts resolved so far are thrown away.",
+                operation
+            ),
+            theme.secondary_text_style(),
+        ),
+        Line::from(""),
+        Line::styled("[Y] Abort  [N] Keep going", theme.secondary_text_style()),
+    ];
+    let popup = Paragraph::new(lines)
+        .wrap(ratatui::widgets::Wrap { trim: true })
+        .block(
+            Block::default()
+                .title(match operation {
+                    "merge" => "Abort Merge",
+                    "cherry-pick" => "Abort Cherry-pick",
+                    _ => "Abort Rebase",
+                })
+                .title_style(theme.popup_title_style())
+                .borders(Borders::ALL)
+                .border_style(theme.popup_border_style())
+                .style(theme.popup_background_style()),
+        );
+    f.render_widget(popup, popup_area);
+}
+
+impl AppState {
+    /// Open the conflicts popup on the index's conflicted files
+    ///
+    /// A merge whose conflicts are already resolved opens with an empty list, ready to commit.
+    pub fn open_conflicts(&mut self) {
+        if self.refuse_read_only("Resolving conflicts") {
+            return;
+        }
+        match crate::git::list_conflicts() {
+            Ok(entries)
+                if entries.is_empty()
+                    && !crate::git::merge_in_progress()
+                    && !crate::git::cherry_pick_in_progress()
+                    && !crate::git::planned_rebase_in_progress() =>
+            {
+                self.show_error("No Conflicts", "There are no conflicted files to resolve.");
+            }
+            Ok(entries) => {
+                self.conflicts = Some(ConflictsView {
+                    entries,
+                    resolved: Vec::new(),
+                    selected: 0,
+                    pane: ConflictPane::Ours,
+                    scroll: 0,
+                    message: None,
+                });
+            }
+            Err(e) => self.show_error("Conflicts", &format!("Failed to read the index:\n\n{}", e)),
+        }
+    }
+
+    /// Move the selection up or down the conflicted files
+    pub fn conflicts_navigate(&mut self, down: bool) {
+        if let Some(view) = self.conflicts.as_mut() {
+            let last = view.entries.len().saturating_sub(1);
+            view.selected = if down {
+                (view.selected + 1).min(last)
+            } else {
+                view.selected.saturating_sub(1)
+            };
+            view.scroll = 0;
+        }
+    }
+
+    /// Show the next or previous side of the selected file
+    pub fn conflicts_switch_pane(&mut self, forward: bool) {
+        if let Some(view) = self.conflicts.as_mut() {
+            view.pane = match (view.pane, forward) {
+                (ConflictPane::Ours, true) | (ConflictPane::Base, false) => ConflictPane::Theirs,
+                (ConflictPane::Theirs, true) | (ConflictPane::Ours, false) => ConflictPane::Base,
+                (ConflictPane::Base, true) | (ConflictPane::Theirs, false) => ConflictPane::Ours,
+            };
+            view.scroll = 0;
+        }
+    }
+
+    /// Scroll the shown side by `lines`, negative for up
+    pub fn conflicts_scroll(&mut self, lines: isize) {
+        if let Some(view) = self.conflicts.as_mut() {
+            view.scroll = view.scroll.saturating_add_signed(lines);
+        }
+    }
+
+    /// The selected path, unless it has already been resolved
+    pub fn selected_conflict(&self) -> Option<String> {
+        let view = self.conflicts.as_ref()?;
+        let entry = view.entries.get(view.selected)?;
+        (!view.resolved.contains(&entry.path)).then(|| entry.path.clone())
+    }
+
+    /// Resolve the selected file by keeping one side
+    pub fn conflicts_take(&mut self, side: ConflictSide) {
+        let path = match self.selected_conflict() {
+            Some(path) => path,
+            None => return,
+        };
+        let name = match side {
+            ConflictSide::Ours => "ours",
+            ConflictSide::Theirs => "theirs",
+        };
+        let result = crate::git::resolve_conflict(&path, side);
+        self.record_activity(
+            ActivityKind::Resolve,
+            &path,
+            &format!(
+                "libgit2: write the {} blob to the working tree and stage it",
+                name
+            ),
+            match &result {
+                Ok(()) => Ok(format!("Kept {}", name)),
+                Err(e) => Err(e.to_string()),
+            },
+        );
+        self.finish_resolve(path, result.map(|()| format!("Kept {}", name)));
+    }
+
+    /// Stage the selected file after it was edited, if no conflict markers are left in it
+    pub fn conflicts_after_edit(&mut self, path: String) {
+        self.conflicts_stage_worktree(path, "Resolved by editing");
+    }
+
+    /// Stage the selected file as it is in the working tree, if no conflict markers are left in it
+    pub fn conflicts_mark_resolved(&mut self) {
+        if let Some(path) = self.selected_conflict() {
+            self.conflicts_stage_worktree(path, "Marked resolved");
+        }
+    }
+
+    /// Take the outcome of `git mergetool` on the selected file, which stages it on success
+    pub fn conflicts_after_mergetool(
+        &mut self,
+        path: String,
+        result: Result<(), Box<dyn std::error::Error>>,
+    ) {
+        let still_conflicted = crate::git::conflicted_paths().contains(&path);
+        let result = match result {
+            Ok(()) if still_conflicted => {
+                Err("the tool finished but left the file conflicted".to_string())
+            }
+            Ok(()) => Ok("Resolved with mergetool".to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        self.record_activity(
+            ActivityKind::Resolve,
+            &path,
+            &format!("git mergetool --no-prompt -- {}", path),
+            result.clone(),
+        );
+        match result {
+            Ok(message) => self.finish_resolve(path, Ok(message)),
+            Err(e) => {
+                self.invalidate_save_changes_git_status();
+                self.invalidate_status_git_status();
+                if let Some(view) = self.conflicts.as_mut() {
+                    view.message = Some(format!("{}: {} - try again or keep a side", path, e));
+                }
+            }
+        }
+    }
+
+    /// Stage the working tree copy of `path`, refusing while conflict markers are left in it
+    fn conflicts_stage_worktree(&mut self, path: String, how: &str) {
+        if let Some(line) = crate::git::conflict_marker_line(&path) {
+            if let Some(view) = self.conflicts.as_mut() {
+                view.message = Some(format!(
+                    "{} still has conflict markers (line {}) - edit again or keep a side",
+                    path, line
+                ));
+            }
+            return;
+        }
+        let result = crate::git::mark_conflict_resolved(&path);
+        self.record_activity(
+            ActivityKind::Resolve,
+            &path,
+            "libgit2: stage the working tree file",
+            match &result {
+                Ok(()) => Ok(how.to_string()),
+                Err(e) => Err(e.to_string()),
+            },
+        );
+        self.finish_resolve(path, result.map(|()| how.to_string()));
+    }
+
+    fn finish_resolve(&mut self, path: String, result: Result<String, crate::git::GitError>) {
+        self.invalidate_save_changes_git_status();
+        self.invalidate_status_git_status();
+        let view = match self.conflicts.as_mut() {
+            Some(view) => view,
+            None => return,
+        };
+        match result {
+            Ok(message) => {
+                view.message = Some(format!("{}: {}", path, message));
+                view.resolved.push(path);
+                // Move on to the next file that still needs a decision
+                let next = (0..view.entries.len())
+                    .map(|offset| (view.selected + offset) % view.entries.len())
+                    .find(|&index| !view.resolved.contains(&view.entries[index].path));
+                if let Some(next) = next {
+                    view.selected = next;
+                    view.scroll = 0;
+                } else {
+                    view.message = None;
+                }
+            }
+            Err(e) => view.message = Some(format!("Failed to resolve {}: {}", path, e)),
+        }
+    }
+
+    /// Ask before aborting the merge or rebase in progress
+    pub fn request_abort(&mut self) {
+        if self.refuse_read_only("Aborting a merge or rebase") {
+            return;
+        }
+        if crate::git::merge_in_progress() {
+            self.abort_confirm = Some("merge");
+        } else if crate::git::rebase_in_progress() {
+            self.abort_confirm = Some("rebase");
+        } else if crate::git::cherry_pick_in_progress() {
+            self.abort_confirm = Some("cherry-pick");
+        } else {
+            self.show_error("Nothing to Abort", "No merge, rebase or cherry-pick is in progress.");
+        }
+    }
+
+    /// Abort the confirmed merge or rebase, then re-read everything it touched
+    pub fn confirm_abort(&mut self) {
+        let operation = match self.abort_confirm.take() {
+            Some(operation) => operation,
+            None => return,
+        };
+        let result = crate::git::abort_operation();
+        let branch = crate::git::get_current_branch().unwrap_or_else(|_| "HEAD".to_string());
+        self.record_activity(
+            ActivityKind::Abort,
+            &branch,
+            &format!("git {} --abort", operation),
+            match &result {
+                Ok(aborted) => Ok(format!("Aborted the {}", aborted)),
+                Err(e) => Err(e.to_string()),
+            },
+        );
+        match result {
+            Ok(_) => {
+                self.conflicts = None;
+                self.invalidate_save_changes_git_status();
+                self.invalidate_status_git_status();
+                self.reload_history_if_moved();
+                if let Ok(remote_status) = crate::git::get_remote_status() {
+                    self.update_remote_status = Some(remote_status);
+                }
+            }
+            Err(e) => self.show_error(
+                "Abort Failed",
+                &format!("Failed to abort the {}:\n\n{}", operation, e),
+            ),
+        }
+    }
+
+    /// Commit the merge or cherry-pick once every conflict is resolved, then close the popup
+    ///
+    /// A stopped interactive rebase commits its step and carries on instead.
+    pub fn conflicts_continue(&mut self) {
+        let unresolved = self.conflicts.as_ref().is_some_and(|view| {
+            view.entries
+                .iter()
+                .any(|e| !view.resolved.contains(&e.path))
+        });
+        if unresolved || crate::git::has_conflicts() {
+            if let Some(view) = self.conflicts.as_mut() {
+                view.message = Some("Resolve every file before committing the merge".to_string());
+            }
+            return;
+        }
+
+        if crate::git::planned_rebase_in_progress() {
+            self.continue_planned_rebase();
+            return;
+        }
+
+        let (result, operation, kind) = if crate::git::cherry_pick_in_progress() {
+            (
+                crate::git::continue_cherry_pick(),
+                "libgit2: commit the index on HEAD as CHERRY_PICK_HEAD's author and message, \
+                 then clean up",
+                "Cherry-pick",
+            )
+        } else {
+            (
+                crate::git::continue_merge(),
+                "libgit2: commit the index with HEAD and MERGE_HEAD as parents, then clean up",
+                "Merge",
+            )
+        };
+        let branch = crate::git::get_current_branch().unwrap_or_else(|_| "HEAD".to_string());
+        self.record_activity(
+            ActivityKind::Commit,
+            &branch,
+            operation,
+            match &result {
+                Ok(id) => Ok(format!("{} committed as {}", kind, id)),
+                Err(e) => Err(e.to_string()),
+            },
+        );
+        match result {
+            Ok(_) => {
+                self.conflicts = None;
+                self.invalidate_save_changes_git_status();
+                self.invalidate_status_git_status();
+                if let Ok(remote_status) = crate::git::get_remote_status() {
+                    self.update_remote_status = Some(remote_status);
+                }
+            }
+            Err(e) => {
+                if let Some(view) = self.conflicts.as_mut() {
+                    view.message = Some(format!(
+                        "Could not commit the {}: {}",
+                        kind.to_lowercase(),
+                        e
+                    ));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use crate::app::{AppState, CredentialPrompt};
+use crate::credentials::CredentialKind;
+use crate::tui::text;
+use crate::tui::theme::Theme;
+use ratatui::layout::{Constraint, Layout, Margin};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::{layout::Rect, Frame};
+use tui_textarea::TextArea;
+
+/// Render the credential prompt: username and secret for HTTPS, or a key's passphrase
+pub fn render_credential_popup(f: &mut Frame, area: Rect, prompt: &mut CredentialPrompt, theme: &Theme) {
+    let asks_username = prompt.request.kind == CredentialKind::UserPass;
+    let height = if asks_username { 12 } else { 9 };
+    let popup_area = super::centered_rect(64, height, area);
+    f.render_widget(Clear, popup_area);
+
+    let title = match &prompt.request.kind {
+        CredentialKind::UserPass => "Sign In",
+        CredentialKind::Passphrase(_) => "SSH Key Passphrase",
+    };
+    let block = Block::default()
+        .title(title)
+        .title_style(theme.popup_title_style())
+        .borders(Borders::ALL)
+        .border_style(theme.popup_border_style())
+        .style(theme.popup_background_style());
+    let inner = block.inner(popup_area).inner(Margin {
+        vertical: 0,
+        horizontal: 1,
+    });
+    f.render_widget(block, popup_area);
+
+    let chunks = Layout::vertical([
+        Constraint::Length(2),                                 // What is asking
+        Constraint::Length(if asks_username { 3 } else { 0 }), // Username
+        Constraint::Length(3),                                 // Secret
+        Constraint::Min(0),
+        Constraint::Length(1), // Help
+    ])
+    .split(inner);
+
+    let width = chunks[0].width as usize;
+    let question = match &prompt.request.kind {
+        CredentialKind::UserPass => format!("{} needs a username and password or token", prompt.request.url),
+        CredentialKind::Passphrase(key) => format!("{} is protected by a passphrase", key.display()),
+    };
+    f.render_widget(
+        Paragraph::new(vec![
+            Line::styled(text::truncate_middle(&question, width), theme.text_style()),
+            Line::styled(
+                text::truncate("Nothing typed here is saved.", width),
+                theme.muted_text_style(),
+            ),
+        ]),
+        chunks[0],
+    );
+
+    let focused = |active: bool| {
+        if active {
+            theme.focused_border_style()
+        } else {
+            theme.border_style()
+        }
+    };
+    let cursor = |active: bool| {
+        if active {
+            theme
+                .text_style()
+                .add_modifier(ratatui::style::Modifier::REVERSED)
+        } else {
+            theme.text_style()
+        }
+    };
+
+    if asks_username {
+        prompt.username.set_block(
+            Block::default()
+                .title("Username")
+                .borders(Borders::ALL)
+                .border_style(focused(!prompt.editing_secret)),
+        );
+        prompt.username.set_style(theme.text_style());
+        prompt.username.set_cursor_style(cursor(!prompt.editing_secret));
+        f.render_widget(&prompt.username, chunks[1]);
+    }
+
+    let secret_title = match &prompt.request.kind {
+        CredentialKind::UserPass => "Password or Token",
+        CredentialKind::Passphrase(_) => "Passphrase",
+    };
+    prompt.secret.set_block(
+        Block::default()
+            .title(secret_title)
+            .borders(Borders::ALL)
+            .border_style(focused(prompt.editing_secret)),
+    );
+    prompt.secret.set_style(theme.text_style());
+    prompt.secret.set_cursor_style(cursor(prompt.editing_secret));
+    f.render_widget(&prompt.secret, chunks[2]);
+
+    let help = if asks_username {
+        "[Tab] Field  [Enter] Sign In  [Esc] Cancel"
+    } else {
+        "[Enter] Unlock  [Esc] Cancel"
+    };
+    f.render_widget(
+        Paragraph::new(help).style(theme.secondary_text_style()),
+        chunks[4],
+    );
+}
+
+impl AppState {
+    /// Show the next credential request from a fetch or push, if none is being answered
+    pub fn poll_credential_requests(&mut self) {
+        if self.credential_prompt.is_some() {
+            return;
+        }
+        let request = match self.credential_requests.as_ref().map(|rx| rx.try_recv()) {
+            Some(Ok(request)) => request,
+            _ => return,
+        };
+        let mut username = TextArea::new(vec![request.username.clone().unwrap_or_default()]);
+        username.move_cursor(tui_textarea::CursorMove::End);
+        let mut secret = TextArea::default();
+        secret.set_mask_char('•');
+        // Start on the password when the URL already named the user
+        let editing_secret =
+            request.kind != CredentialKind::UserPass || request.username.is_some();
+        self.credential_prompt = Some(CredentialPrompt {
+            request,
+            username,
+            secret,
+            editing_secret,
+        });
+    }
+
+    /// Hand what was typed to the waiting fetch or push
+    pub fn submit_credentials(&mut self) {
+        if let Some(prompt) = self.credential_prompt.take() {
+            let username = prompt.username.lines().join("");
+            let secret = prompt.secret.lines().join("");
+            let _ = prompt.request.reply.send(Some((username, secret)));
+        }
+    }
+
+    /// Give up on the request; the fetch or push fails with "Authentication cancelled"
+    pub fn cancel_credentials(&mut self) {
+        if let Some(prompt) = self.credential_prompt.take() {
+            let _ = prompt.request.reply.send(None);
+        }
+    }
+}
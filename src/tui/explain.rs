@@ -0,0 +1,217 @@
+use crate::app::AppState;
+use crate::git::{RemoteStatus, RepoSituation};
+use crate::tui::text;
+use crate::tui::theme::Theme;
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+use ratatui::{layout::Rect, Frame};
+
+/// Render the plain-language explanation of the repository state
+pub fn render_explain_popup(f: &mut Frame, area: Rect, state: &AppState, theme: &Theme) {
+    let paragraphs = match &state.explain {
+        Some(paragraphs) => paragraphs,
+        None => return,
+    };
+
+    let mut lines = Vec::new();
+    for paragraph in paragraphs {
+        lines.push(Line::styled(paragraph.as_str(), theme.text_style()));
+        lines.push(Line::from(""));
+    }
+    lines.push(Line::styled(
+        "Press [Enter] or [Esc] to close",
+        theme.muted_text_style(),
+    ));
+
+    // Wrapped paragraphs with a blank line after each, the closing hint and the borders
+    let text_width = (area.width as usize * 70 / 100).saturating_sub(2).max(1);
+    let text_height: usize = paragraphs
+        .iter()
+        .map(|paragraph| text::width(paragraph).div_ceil(text_width) + 1)
+        .sum();
+    let popup_area = super::centered_rect(70, (text_height + 3) as u16, area);
+    f.render_widget(Clear, popup_area);
+    let popup = Paragraph::new(lines).wrap(Wrap { trim: true }).block(
+        Block::default()
+            .title("What's Going On?")
+            .title_style(theme.title_style())
+            .borders(Borders::ALL)
+            .border_style(theme.popup_border_style())
+            .style(theme.secondary_background_style()),
+    );
+    f.render_widget(popup, popup_area);
+}
+
+impl AppState {
+    /// Work out what state the repository is in and open the explanation popup
+    pub fn open_explain(&mut self) {
+        let situation = match crate::git::repo_situation() {
+            Ok(situation) => situation,
+            Err(e) => {
+                self.show_error("Explain", &format!("Failed to read the repository: {}", e));
+                return;
+            }
+        };
+        // The same remote status the Update tab shows, read fresh since it's only local refs
+        if let Ok(remote_status) = crate::git::get_remote_status() {
+            self.update_remote_status = Some(remote_status);
+        }
+        let files = crate::git::get_git_status().unwrap_or_default();
+        let (staged, unstaged, untracked) = crate::app::status_counts(&files);
+
+        let remote = self.update_remote_status.as_ref().filter(|_| situation.has_origin);
+        self.explain = Some(explain(
+            &situation,
+            remote,
+            (staged, unstaged, untracked),
+            self.pull_rebase,
+        ));
+    }
+}
+
+/// Describe the repository in a few plain sentences, most urgent first
+fn explain(
+    situation: &RepoSituation,
+    remote: Option<&RemoteStatus>,
+    (staged, unstaged, untracked): (usize, usize, usize),
+    pull_rebase: bool,
+) -> Vec<String> {
+    let mut paragraphs = Vec::new();
+
+    if let Some(operation) = situation.operation {
+        paragraphs.push(format!(
+            "A {} is in progress. Git stopped part-way, usually because of a conflict: fix the \
+             files it marks as conflicted, stage them, and continue the {} before doing \
+             anything else. A merge or rebase can also be aborted with A on the Status tab.",
+            operation, operation
+        ));
+    }
+
+    match &situation.branch {
+        None => paragraphs.push(
+            "You are not on a branch (\"detached HEAD\"): you are looking at one specific commit. \
+             Commits you make here belong to no branch and are easy to lose, so create a branch \
+             first if you want to keep them."
+                .to_string(),
+        ),
+        Some(branch) if situation.unborn => paragraphs.push(format!(
+            "You are on the new branch {}, which has no commits yet. Stage some files and \
+             commit them in Save Changes to make the first one.",
+            branch
+        )),
+        Some(branch) => paragraphs.push(format!("You are on the branch {}.", branch)),
+    }
+
+    paragraphs.push(working_tree_sentence(staged, unstaged, untracked));
+
+    let branch = match (&situation.branch, situation.unborn) {
+        (Some(branch), false) => branch,
+        _ => return paragraphs,
+    };
+    if !situation.has_origin {
+        paragraphs.push(
+            "This repository has no remote called origin, so there is nowhere to push your \
+             commits to or pull others' commits from yet."
+                .to_string(),
+        );
+        return paragraphs;
+    }
+    let tracking = match &situation.tracking {
+        Some(tracking) => tracking,
+        None => {
+            paragraphs.push(format!(
+                "{} has never been pushed, so origin doesn't know about it yet. Pushing (U in the \
+                 Update tab) will create origin/{} with all of its commits.",
+                branch, branch
+            ));
+            return paragraphs;
+        }
+    };
+
+    let (ahead, behind) = remote.map_or((0, 0), |remote| (remote.ahead, remote.behind));
+    let commits = |n: usize| if n == 1 { "1 commit".to_string() } else { format!("{} commits", n) };
+    let sync = match (ahead, behind) {
+        (0, 0) => format!(
+            "Your branch is up to date with {}: there is nothing to push or pull.",
+            tracking
+        ),
+        (ahead, 0) => format!(
+            "Your branch is {} ahead of {}: you made commits that only exist on this computer. \
+             Pushing (U in the Update tab) will upload them.",
+            commits(ahead),
+            tracking
+        ),
+        (0, behind) => format!(
+            "Your branch is {} behind {}: someone pushed work you don't have yet. Pulling \
+             (P in the Update tab) will bring your branch up to date; none of your own work \
+             changes.",
+            commits(behind),
+            tracking
+        ),
+        (ahead, behind) if pull_rebase => format!(
+            "Your branch and {} have diverged: you have {} the remote doesn't, and the remote \
+             has {} you don't. Pulling will replay your commits on top of the remote ones \
+             (rebase), after which you can push.",
+            tracking,
+            commits(ahead),
+            commits(behind)
+        ),
+        (ahead, behind) => format!(
+            "Your branch and {} have diverged: you have {} the remote doesn't, and the remote \
+             has {} you don't. Pulling will combine both with a merge commit, after which you \
+             can push.",
+            tracking,
+            commits(ahead),
+            commits(behind)
+        ),
+    };
+    paragraphs.push(sync);
+
+    if behind > 0 && staged + unstaged > 0 {
+        paragraphs.push(
+            "Commit your changes before pulling, so the incoming commits can't collide with \
+             unsaved work."
+                .to_string(),
+        );
+    }
+    if let Some(last_fetch) = remote.and_then(|remote| remote.last_fetch.as_ref()) {
+        paragraphs.push(format!(
+            "This compares against what was on the remote when you last fetched ({}). Fetch \
+             (F in the Update tab) to look for anything newer.",
+            crate::git::format_relative_time(*last_fetch).to_lowercase()
+        ));
+    }
+
+    paragraphs
+}
+
+/// One sentence on what is staged, changed and untracked
+fn working_tree_sentence(staged: usize, unstaged: usize, untracked: usize) -> String {
+    if staged + unstaged + untracked == 0 {
+        return "Your working tree is clean: everything you changed is committed.".to_string();
+    }
+    let files = |n: usize| if n == 1 { "1 file".to_string() } else { format!("{} files", n) };
+    let mut parts = Vec::new();
+    if staged > 0 {
+        parts.push(format!("{} staged and ready to commit", files(staged)));
+    }
+    if unstaged > 0 {
+        parts.push(format!("{} changed but not staged", files(unstaged)));
+    }
+    if untracked > 0 {
+        parts.push(format!("{} Git isn't tracking yet", files(untracked)));
+    }
+    let list = match parts.len() {
+        1 => parts.remove(0),
+        _ => {
+            let last = parts.pop().unwrap_or_default();
+            format!("{} and {}", parts.join(", "), last)
+        }
+    };
+    let advice = if staged > 0 {
+        "Commit in Save Changes to save them."
+    } else {
+        "Stage what you want to keep with Space in Save Changes, then commit."
+    };
+    format!("You have {}. {}", list, advice)
+}
@@ -1,11 +1,13 @@
-use crate::app::AppState;
+use crate::app::{AppState, FilesScope, MouseList};
 use crate::files::{list_files, list_files_with_git_status, FileEntry};
 use crate::git::format_file_size;
+use crate::tui::text;
 use crate::tui::theme::Theme;
 use chrono::{Local, NaiveDateTime};
 use ratatui::layout::{Alignment, Constraint, Direction, Layout};
 use ratatui::style::{Color, Modifier, Style};
-use ratatui::widgets::{Block, Borders, Cell, Row, Table, TableState};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState};
 use ratatui::{layout::Rect, Frame};
 
 pub fn render_files_tab(f: &mut Frame, area: Rect, state: &mut AppState) {
@@ -23,7 +25,7 @@ pub fn render_files_tab(f: &mut Frame, area: Rect, state: &mut AppState) {
         area,
     );
 
-    let add_parent = state.current_dir != state.root_dir;
+    let add_parent = state.files_add_parent();
 
     // Load git status if git is enabled and not already loaded
     if state.git_enabled {
@@ -51,10 +53,36 @@ pub fn render_files_tab(f: &mut Frame, area: Rect, state: &mut AppState) {
         ["Permissions", "Size", "Modified", "", "", "Name"]
     };
 
+    // Update column widths to accommodate Tracked and Status columns
+    let widths = if state.git_enabled {
+        [
+            Constraint::Length(12), // Permissions
+            Constraint::Length(10), // Size
+            Constraint::Length(20), // Modified
+            Constraint::Length(8),  // Tracked
+            Constraint::Length(12), // Status
+            Constraint::Min(15),    // Name
+        ]
+    } else {
+        [
+            Constraint::Length(12), // Permissions
+            Constraint::Length(10), // Size
+            Constraint::Length(20), // Modified
+            Constraint::Length(0),  // Tracked (hidden)
+            Constraint::Length(0),  // Status (hidden)
+            Constraint::Min(10),    // Name
+        ]
+    };
+
+    let name_width = text::column_widths(area, &widths, "► ")[5];
+
     let rows: Vec<Row> = files
         .iter()
         .map(|entry| {
-            let perms = format_permissions(entry.permissions, entry.is_dir);
+            let mut perms = format_permissions(entry.permissions, entry.is_dir);
+            if entry.link_target.is_some() {
+                perms.replace_range(0..1, "l");
+            }
 
             // Use format_file_size function like in status tab
             let size = if entry.is_dir {
@@ -82,18 +110,32 @@ pub fn render_files_tab(f: &mut Frame, area: Rect, state: &mut AppState) {
                 ""
             };
 
+            // Files git has been told to ignore changes to get a badge instead of a status
+            let flag = if state.git_enabled && !entry.is_dir {
+                state
+                    .repo_relative_path(&state.current_dir.join(&entry.name))
+                    .and_then(|path| state.index_flag(&path))
+            } else {
+                None
+            };
+
             // Format git status description (only show for files with actual changes)
             let status_description = if state.git_enabled {
-                match &entry.git_status {
-                    Some(git_status) => git_status.as_description(),
-                    None => "", // Clean tracked files show no status
+                match (&entry.git_status, flag) {
+                    (_, Some(flag)) => flag.as_description(),
+                    (Some(git_status), None) => git_status.as_description(),
+                    (None, None) => "", // Clean tracked files show no status
                 }
             } else {
                 ""
             };
 
             let mut style = theme.text_style();
-            if entry.is_dir {
+            if entry.broken_link {
+                style = theme.error_style();
+            } else if entry.link_target.is_some() {
+                style = theme.accent2_style().add_modifier(Modifier::ITALIC);
+            } else if entry.is_dir {
                 style = theme.accent3_style().add_modifier(Modifier::BOLD);
             } else if entry.permissions & 0o111 != 0 {
                 style = theme.success_style();
@@ -111,13 +153,20 @@ pub fn render_files_tab(f: &mut Frame, area: Rect, state: &mut AppState) {
                 Cell::from(tracked).style(style)
             };
 
-            let name_cell = Cell::from(entry.name.clone()).style(style);
+            // Symlinks get an arrow icon and show where they point
+            let name = match &entry.link_target {
+                Some(target) => format!("↪ {} → {}", entry.name, target.display()),
+                None => entry.name.clone(),
+            };
+            let name_cell = Cell::from(text::truncate_middle(&name, name_width)).style(style);
 
             // Status cell with git status coloring
-            let status_cell = if let Some(git_status) = &entry.git_status {
+            let status_cell = if flag.is_some() {
+                Cell::from(status_description).style(theme.warning_style())
+            } else if let Some(git_status) = &entry.git_status {
                 Cell::from(status_description).style(
                     Style::default()
-                        .fg(git_status.color())
+                        .fg(theme.adapt(git_status.color()))
                         .add_modifier(Modifier::BOLD),
                 )
             } else {
@@ -135,34 +184,31 @@ pub fn render_files_tab(f: &mut Frame, area: Rect, state: &mut AppState) {
         })
         .collect();
 
-    // Update column widths to accommodate Tracked and Status columns
-    let widths = if state.git_enabled {
-        [
-            Constraint::Length(12), // Permissions
-            Constraint::Length(10), // Size
-            Constraint::Length(20), // Modified
-            Constraint::Length(8),  // Tracked
-            Constraint::Length(12), // Status
-            Constraint::Min(15),    // Name
-        ]
-    } else {
-        [
-            Constraint::Length(12), // Permissions
-            Constraint::Length(10), // Size
-            Constraint::Length(20), // Modified
-            Constraint::Length(0),  // Tracked (hidden)
-            Constraint::Length(0),  // Status (hidden)
-            Constraint::Min(10),    // Name
-        ]
-    };
-
     let mut table_state = TableState::default();
     if !files.is_empty() {
         table_state.select(Some(state.files_selected_row.min(files.len() - 1)));
     }
 
-    // Update title to reflect git integration
-    let title = "Files".to_string();
+    // Keep flagged files from being forgotten
+    let mut title = match state.status_flagged_files.len() {
+        0 => "Files".to_string(),
+        n => format!("Files ({} flagged - [Shift+W] to review)", n),
+    };
+    if state.files_scope != FilesScope::Launch {
+        title = format!(
+            "{} - {} [scope: {}]",
+            title,
+            state.current_dir.display(),
+            state.files_scope.as_str()
+        );
+    }
+    // Git status and flags don't apply out here, so make it obvious
+    let title_style = if state.files_outside_repo() {
+        title.push_str(" - OUTSIDE REPOSITORY");
+        theme.warning_style()
+    } else {
+        theme.title_style()
+    };
 
     let table = Table::new(rows, widths)
         .header(Row::new(header).style(theme.accent2_style()))
@@ -170,7 +216,7 @@ pub fn render_files_tab(f: &mut Frame, area: Rect, state: &mut AppState) {
             Block::default()
                 .borders(Borders::ALL)
                 .title(title)
-                .title_style(theme.title_style())
+                .title_style(title_style)
                 .border_style(theme.border_style())
                 .style(theme.secondary_background_style()),
         )
@@ -178,6 +224,114 @@ pub fn render_files_tab(f: &mut Frame, area: Rect, state: &mut AppState) {
         .row_highlight_style(theme.highlight_style())
         .highlight_symbol("► ");
     f.render_stateful_widget(table, area, &mut table_state);
+    state.record_mouse_target(MouseList::Files, area, table_state.offset(), files.len());
+
+    if let Some(selected) = state.flagged_files_view {
+        render_flagged_files_popup(f, area, state, selected, &theme);
+    }
+
+    crate::tui::blame::render_blame_popup(f, area, state, &theme);
+
+    if let Some(path) = &state.files_delete_confirm {
+        render_delete_confirm_popup(f, area, path, state.deletes_permanently(), &theme);
+    }
+}
+
+/// Ask before deleting a Files tab entry, saying whether it can be recovered
+fn render_delete_confirm_popup(
+    f: &mut Frame,
+    area: Rect,
+    path: &std::path::Path,
+    permanent: bool,
+    theme: &Theme,
+) {
+    let popup_area = crate::tui::centered_rect(60, 7, area);
+    f.render_widget(Clear, popup_area);
+
+    let (title, question) = if permanent {
+        ("Delete Permanently?", "This cannot be undone.")
+    } else {
+        ("Move to Trash?", "It can be restored from the system trash.")
+    };
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let width = popup_area.width.saturating_sub(4) as usize;
+    let lines = vec![
+        Line::styled(text::truncate_middle(&name, width), theme.accent2_style()),
+        Line::styled(question, theme.secondary_text_style()),
+        Line::from(""),
+        Line::styled("[Y] Delete  [N] Cancel", theme.text_style()),
+    ];
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .title_style(theme.popup_title_style())
+        .border_style(if permanent {
+            theme.warning_style()
+        } else {
+            theme.popup_border_style()
+        })
+        .style(theme.popup_background_style());
+    f.render_widget(
+        Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .block(block),
+        popup_area,
+    );
+}
+
+/// Render the list of files marked assume-unchanged or skip-worktree
+fn render_flagged_files_popup(
+    f: &mut Frame,
+    area: Rect,
+    state: &AppState,
+    selected: usize,
+    theme: &Theme,
+) {
+    let height = (state.status_flagged_files.len() as u16 + 4).clamp(6, area.height);
+    let popup_area = crate::tui::centered_rect(70, height, area);
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Flagged Files - [Space] to clear flag, [Esc] to close")
+        .title_style(theme.popup_title_style())
+        .border_style(theme.popup_border_style())
+        .style(theme.popup_background_style());
+
+    if state.status_flagged_files.is_empty() {
+        let empty = Paragraph::new("No files are marked assume-unchanged or skip-worktree.")
+            .style(theme.secondary_text_style())
+            .alignment(Alignment::Center)
+            .block(block);
+        f.render_widget(empty, popup_area);
+        return;
+    }
+
+    let widths = [Constraint::Length(14), Constraint::Min(10)];
+    let path_width = text::column_widths(popup_area, &widths, "► ")[1];
+    let rows: Vec<Row> = state
+        .status_flagged_files
+        .iter()
+        .map(|(path, flag)| {
+            Row::new(vec![
+                Cell::from(flag.as_description()).style(theme.warning_style()),
+                Cell::from(text::truncate_path(path, path_width)).style(theme.text_style()),
+            ])
+        })
+        .collect();
+
+    let mut table_state = TableState::default();
+    table_state.select(Some(selected.min(state.status_flagged_files.len() - 1)));
+    let table = Table::new(rows, widths)
+        .block(block)
+        .column_spacing(1)
+        .row_highlight_style(theme.highlight_style())
+        .highlight_symbol("► ");
+    f.render_stateful_widget(table, popup_area, &mut table_state);
 }
 
 fn format_permissions(perm: u32, is_dir: bool) -> String {
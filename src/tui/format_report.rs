@@ -0,0 +1,105 @@
+use crate::app::{AppState, FormatReportView};
+use crate::tui::text;
+use crate::tui::theme::Theme;
+use ratatui::layout::{Constraint, Layout, Margin};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::{layout::Rect, Frame};
+
+/// Render what the commit formatter changed: each re-staged file with its diff
+pub fn render_format_report_popup(
+    f: &mut Frame,
+    area: Rect,
+    view: &mut FormatReportView,
+    theme: &Theme,
+) {
+    let popup_area = area.inner(Margin {
+        vertical: 2,
+        horizontal: 4,
+    });
+    f.render_widget(Clear, popup_area);
+    let count = view.report.formatted.len();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(
+            "Formatted Before Commit ({} file{})",
+            count,
+            if count == 1 { "" } else { "s" }
+        ))
+        .title_style(theme.popup_title_style())
+        .border_style(theme.popup_border_style())
+        .style(theme.popup_background_style());
+    let inner = block.inner(popup_area).inner(Margin {
+        vertical: 0,
+        horizontal: 1,
+    });
+    f.render_widget(block, popup_area);
+
+    let chunks = Layout::vertical([
+        Constraint::Min(1),    // Files and their diffs
+        Constraint::Length(1), // Help
+    ])
+    .split(inner);
+
+    let width = chunks[0].width as usize;
+    let height = chunks[0].height as usize;
+    let all = report_lines(view, theme);
+    view.scroll = view.scroll.min(all.len().saturating_sub(height));
+    let lines: Vec<Line> = all
+        .into_iter()
+        .skip(view.scroll)
+        .take(height)
+        .map(|(line, style)| {
+            Line::styled(text::truncate(&line.replace('\t', "    "), width), style)
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines), chunks[0]);
+
+    f.render_widget(
+        Paragraph::new("[↑↓] Scroll  [Esc] Close").style(theme.secondary_text_style()),
+        chunks[1],
+    );
+}
+
+/// The popup's lines with their styles, before scrolling
+fn report_lines(view: &FormatReportView, theme: &Theme) -> Vec<(String, ratatui::style::Style)> {
+    let mut lines = Vec::new();
+    for file in &view.report.formatted {
+        if !lines.is_empty() {
+            lines.push((String::new(), theme.text_style()));
+        }
+        lines.push((file.path.clone(), theme.accent_style()));
+        for line in &file.diff {
+            let style = if line.starts_with("@@") {
+                theme.accent2_style()
+            } else if line.starts_with('+') {
+                theme.success_style()
+            } else if line.starts_with('-') {
+                theme.error_style()
+            } else {
+                theme.text_style()
+            };
+            lines.push((line.clone(), style));
+        }
+    }
+    if !view.report.skipped.is_empty() {
+        lines.push((String::new(), theme.text_style()));
+        lines.push((
+            format!(
+                "Not formatted, since they have unstaged changes too: {}",
+                view.report.skipped.join(", ")
+            ),
+            theme.muted_text_style(),
+        ));
+    }
+    lines
+}
+
+impl AppState {
+    /// Scroll the formatter report by `delta` lines; rendering stops it at the end
+    pub fn format_report_scroll(&mut self, delta: isize) {
+        if let Some(view) = self.format_report.as_mut() {
+            view.scroll = view.scroll.saturating_add_signed(delta);
+        }
+    }
+}
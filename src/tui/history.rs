@@ -0,0 +1,784 @@
+use crate::activity::ActivityKind;
+use crate::app::{
+    AppState, CommitTimeZone, HistoryFilterEdit, HistoryView, MouseList, NoteEdit, TimeFormat,
+};
+use crate::git::HistoryFilter;
+use crate::tui::commit_message::message_lines;
+use crate::tui::text;
+use crate::tui::theme::Theme;
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Margin};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table};
+use ratatui::{layout::Rect, Frame};
+use tui_textarea::TextArea;
+
+/// Matching commits loaded per page
+const HISTORY_PAGE_SIZE: usize = 200;
+
+/// Load the next page once the selection is this close to the end of the list
+const HISTORY_PREFETCH: usize = 20;
+
+const FILTER_LABELS: [&str; 4] = [
+    "Author (name or email contains)",
+    "Path (starts with)",
+    "Since (YYYY-MM-DD)",
+    "Until (YYYY-MM-DD)",
+];
+
+pub fn render_history_tab(f: &mut Frame, area: Rect, state: &mut AppState) {
+    // Use configured theme from app state
+    let theme = Theme::with_accents_and_title(
+        state.current_theme_accent,
+        state.current_theme_accent2,
+        state.current_theme_accent3,
+        state.current_theme_title,
+    );
+
+    state.load_history_page();
+
+    // Set panel background
+    f.render_widget(
+        Block::default().style(theme.secondary_background_style()),
+        area,
+    );
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(5),    // Commit list
+            Constraint::Length(8), // Selected commit's message
+        ])
+        .split(area);
+
+    render_history_table(
+        f,
+        chunks[0],
+        &mut state.history,
+        state.time_format,
+        state.time_zone,
+        &theme,
+    );
+    state.record_mouse_target(
+        MouseList::History,
+        chunks[0],
+        state.history.table_state.offset(),
+        state.history.commits.len(),
+    );
+    render_history_details(
+        f,
+        chunks[1],
+        &state.history,
+        state.history_message.as_deref(),
+        &theme,
+    );
+
+    if let Some(scroll) = state.history.message_scroll {
+        render_message_popup(f, area, &state.history, scroll, state.time_zone, &theme);
+    }
+    if let Some(edit) = state.history_filter_edit.as_mut() {
+        render_filter_popup(f, area, edit, &theme);
+    }
+    if let Some(edit) = state.note_edit.as_mut() {
+        render_note_popup(f, area, edit, &theme);
+    }
+    if let Some(view) = &state.reflog_view {
+        super::reflog::render_reflog_popup(f, area, view, &theme);
+    }
+    if let Some(plan) = &state.undo_confirm {
+        super::reflog::render_undo_popup(f, area, plan, &theme);
+    }
+}
+
+fn render_history_table(
+    f: &mut Frame,
+    area: Rect,
+    history: &mut HistoryView,
+    time_format: TimeFormat,
+    time_zone: CommitTimeZone,
+    theme: &Theme,
+) {
+    let count = if history.cursor.done {
+        format!("{} commits", history.commits.len())
+    } else {
+        format!("{}+ commits", history.commits.len())
+    };
+    let title = if history.filter.is_empty() {
+        format!("History ({}) - [/] Filter", count)
+    } else {
+        format!(
+            "History ({}) - {} - [/] Filter, [C] Clear",
+            count,
+            history.filter.describe()
+        )
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .title_style(theme.title_style())
+        .border_style(theme.border_style())
+        .style(theme.secondary_background_style());
+
+    if history.commits.is_empty() {
+        let message = if let Some(error) = &history.error {
+            Line::styled(format!("Failed to read history: {}", error), theme.error_style())
+        } else if !history.cursor.done {
+            Line::styled(
+                format!("Searching... {} commits looked at", history.cursor.looked_at),
+                theme.info_style(),
+            )
+        } else if history.filter.is_empty() {
+            Line::styled("No commits yet", theme.muted_text_style())
+        } else {
+            Line::styled("No commits match the filter", theme.muted_text_style())
+        };
+        let empty = Paragraph::new(vec![Line::from(""), message])
+            .alignment(Alignment::Center)
+            .block(block);
+        f.render_widget(empty, area);
+        return;
+    }
+
+    // Keep the selection valid as pages come and go
+    match history.table_state.selected() {
+        Some(idx) if idx < history.commits.len() => {}
+        Some(_) => history.table_state.select(Some(history.commits.len() - 1)),
+        None => history.table_state.select(Some(0)),
+    }
+
+    let header = Row::new(vec![
+        Cell::from("Commit").style(theme.accent2_style()),
+        Cell::from("Date").style(theme.accent2_style()),
+        Cell::from("Author").style(theme.accent2_style()),
+        Cell::from("Summary").style(theme.accent2_style()),
+    ]);
+
+    // Locale formats vary in length, so the date column fits the longest one
+    let dates: Vec<String> = history
+        .commits
+        .iter()
+        .map(|commit| time_format.format_commit(commit.time, time_zone))
+        .collect();
+    let date_width = dates.iter().map(|date| text::width(date)).max().unwrap_or(0).max(4);
+
+    let widths = [
+        Constraint::Length(9),                 // Commit
+        Constraint::Length(date_width as u16), // Date
+        Constraint::Length(20), // Author
+        Constraint::Min(20),    // Summary
+    ];
+    let columns = text::column_widths(area, &widths, "► ");
+
+    let rows: Vec<Row> = history
+        .commits
+        .iter()
+        .zip(dates)
+        .map(|(commit, date)| {
+            // Commits with a git note are marked before their summary
+            let summary = if history.notes.contains_key(&commit.id) {
+                Line::from(vec![
+                    Span::styled("✎ ", theme.accent3_style()),
+                    Span::styled(
+                        text::truncate(&commit.summary, columns[3].saturating_sub(2)),
+                        theme.commit_message_style(),
+                    ),
+                ])
+            } else {
+                Line::styled(
+                    text::truncate(&commit.summary, columns[3]),
+                    theme.commit_message_style(),
+                )
+            };
+            Row::new(vec![
+                Cell::from(commit.short_id.as_str()).style(theme.accent_style()),
+                Cell::from(date).style(theme.timestamp_style()),
+                Cell::from(text::truncate(&commit.author, columns[2])).style(theme.author_style()),
+                Cell::from(summary),
+            ])
+        })
+        .collect();
+
+    let block = if history.cursor.done {
+        block
+    } else {
+        block.title_bottom(
+            Line::styled(
+                format!(" {} commits looked at, more load as you scroll ", history.cursor.looked_at),
+                theme.muted_text_style(),
+            )
+            .right_aligned(),
+        )
+    };
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(block)
+        .row_highlight_style(theme.highlight_style())
+        .highlight_symbol("► ");
+
+    f.render_stateful_widget(table, area, &mut history.table_state);
+}
+
+fn render_history_details(
+    f: &mut Frame,
+    area: Rect,
+    history: &HistoryView,
+    export_message: Option<&str>,
+    theme: &Theme,
+) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Message - [Enter] Full Message, [N] Note, [E] Export CSV, [J] Export JSON")
+        .title_style(theme.title_style())
+        .border_style(theme.border_style())
+        .style(theme.secondary_background_style());
+    let width = block.inner(area).width as usize;
+
+    let selected = history
+        .table_state
+        .selected()
+        .and_then(|idx| history.commits.get(idx));
+    let mut lines = match selected {
+        Some(commit) => {
+            let mut lines = vec![Line::from(vec![
+                Span::styled(commit.id.as_str(), theme.muted_text_style()),
+                Span::raw("  "),
+                Span::styled(commit.author.as_str(), theme.author_style()),
+            ])];
+            // The note goes first so a long message can't push it out of the pane
+            if let Some(note) = history.notes.get(&commit.id) {
+                lines.extend(note.lines().map(|line| {
+                    Line::styled(
+                        text::truncate(&format!("✎ {}", line), width),
+                        theme.accent3_style(),
+                    )
+                }));
+            }
+            lines.extend(message_lines(&commit.message, width, theme));
+            lines
+        }
+        None => vec![Line::styled(
+            "Select a commit to see its message",
+            theme.muted_text_style(),
+        )],
+    };
+    // Keep the export result in view below however long the message is
+    if let Some(message) = export_message {
+        let height = block.inner(area).height as usize;
+        lines.truncate(height.saturating_sub(1));
+        lines.push(Line::styled(text::truncate(message, width), theme.accent3_style()));
+    }
+
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Render the selected commit's whole message, for bodies longer than the pane
+fn render_message_popup(
+    f: &mut Frame,
+    area: Rect,
+    history: &HistoryView,
+    scroll: usize,
+    time_zone: CommitTimeZone,
+    theme: &Theme,
+) {
+    let commit = match history
+        .table_state
+        .selected()
+        .and_then(|idx| history.commits.get(idx))
+    {
+        Some(commit) => commit,
+        None => return,
+    };
+
+    let popup_area = super::centered_rect(70, area.height.saturating_sub(4), area);
+    f.render_widget(Clear, popup_area);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!("{} - [↑↓] to scroll, [Esc] to close", commit.short_id))
+        .title_style(theme.popup_title_style())
+        .border_style(theme.popup_border_style())
+        .style(theme.popup_background_style());
+    let inner = block.inner(popup_area).inner(Margin {
+        vertical: 1,
+        horizontal: 2,
+    });
+    f.render_widget(block, popup_area);
+
+    let date = time_zone.format(commit.time, "%Y-%m-%d %H:%M");
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(commit.author.as_str(), theme.author_style()),
+            Span::raw("  "),
+            Span::styled(date, theme.timestamp_style()),
+        ]),
+        Line::styled(commit.id.as_str(), theme.muted_text_style()),
+        Line::from(""),
+    ];
+    lines.extend(message_lines(&commit.message, inner.width as usize, theme));
+    if let Some(note) = history.notes.get(&commit.id) {
+        lines.push(Line::from(""));
+        lines.push(Line::styled("Notes:", theme.accent2_style()));
+        lines.extend(
+            note.lines()
+                .map(|line| Line::styled(format!("    {}", line), theme.accent3_style())),
+        );
+    }
+
+    let scroll = scroll.min(lines.len().saturating_sub(inner.height as usize));
+    f.render_widget(Paragraph::new(lines).scroll((scroll as u16, 0)), inner);
+}
+
+/// Render the note editor for the selected commit
+fn render_note_popup(f: &mut Frame, area: Rect, edit: &mut NoteEdit, theme: &Theme) {
+    let popup_area = super::centered_rect(60, 12, area);
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(format!("Note on {}", edit.short_id))
+        .title_style(theme.popup_title_style())
+        .borders(Borders::ALL)
+        .border_style(theme.popup_border_style())
+        .style(theme.popup_background_style());
+    let inner = block.inner(popup_area).inner(Margin {
+        vertical: 0,
+        horizontal: 1,
+    });
+    f.render_widget(block, popup_area);
+
+    let chunks = Layout::vertical([
+        Constraint::Min(3),    // Note
+        Constraint::Length(1), // Help
+    ])
+    .split(inner);
+
+    edit.input.set_block(
+        Block::default()
+            .title("Note (refs/notes/commits)")
+            .borders(Borders::ALL)
+            .border_style(theme.focused_border_style()),
+    );
+    edit.input.set_style(theme.text_style());
+    edit.input.set_cursor_style(
+        theme
+            .text_style()
+            .add_modifier(ratatui::style::Modifier::REVERSED),
+    );
+    f.render_widget(&edit.input, chunks[0]);
+
+    f.render_widget(
+        Paragraph::new("[Ctrl+S] Save (empty removes the note)  [Esc] Cancel")
+            .style(theme.secondary_text_style()),
+        chunks[1],
+    );
+}
+
+/// Render the filter form: one input per field, the focused one highlighted
+fn render_filter_popup(f: &mut Frame, area: Rect, edit: &mut HistoryFilterEdit, theme: &Theme) {
+    let popup_area = super::centered_rect(60, 17, area);
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title("Filter History")
+        .title_style(theme.popup_title_style())
+        .borders(Borders::ALL)
+        .border_style(theme.popup_border_style())
+        .style(theme.popup_background_style());
+    let inner = block.inner(popup_area).inner(Margin {
+        vertical: 0,
+        horizontal: 1,
+    });
+    f.render_widget(block, popup_area);
+
+    let chunks = Layout::vertical([
+        Constraint::Length(3), // Author
+        Constraint::Length(3), // Path
+        Constraint::Length(3), // Since
+        Constraint::Length(3), // Until
+        Constraint::Length(1), // Error
+        Constraint::Length(1), // Help
+    ])
+    .split(inner);
+
+    for (idx, field) in edit.fields.iter_mut().enumerate() {
+        let focused = idx == edit.focus;
+        field.set_block(
+            Block::default()
+                .title(FILTER_LABELS[idx])
+                .borders(Borders::ALL)
+                .border_style(if focused {
+                    theme.focused_border_style()
+                } else {
+                    theme.border_style()
+                }),
+        );
+        field.set_style(theme.text_style());
+        // Only the field being typed in shows a cursor
+        field.set_cursor_style(if focused {
+            theme.text_style().add_modifier(ratatui::style::Modifier::REVERSED)
+        } else {
+            theme.text_style()
+        });
+        f.render_widget(&*field, chunks[idx]);
+    }
+
+    if let Some(error) = &edit.error {
+        f.render_widget(
+            Paragraph::new(error.as_str()).style(theme.error_style()),
+            chunks[4],
+        );
+    }
+    f.render_widget(
+        Paragraph::new("[Tab/↑↓] Field  [Enter] Apply  [Esc] Cancel")
+            .style(theme.secondary_text_style()),
+        chunks[5],
+    );
+}
+
+/// Parse an optional date field of the filter form
+fn parse_filter_date(label: &str, value: &str) -> Result<Option<chrono::NaiveDate>, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map(Some)
+        .map_err(|_| format!("{} must be a date like 2024-01-31", label))
+}
+
+// History list loading, navigation and filtering
+impl AppState {
+    /// Start the list over if HEAD moved since it was loaded (commits, pulls, checkouts)
+    pub fn reload_history_if_moved(&mut self) {
+        let head = crate::git::head_id().ok();
+        if self.history.head.is_none() || self.history.head != head {
+            self.reset_history();
+        }
+    }
+
+    /// Drop the loaded commits so the list is read again with the current filter
+    fn reset_history(&mut self) {
+        let filter = std::mem::take(&mut self.history.filter);
+        self.history = HistoryView {
+            filter,
+            head: crate::git::head_id().ok(),
+            notes: crate::git::commit_notes().unwrap_or_default(),
+            ..HistoryView::default()
+        };
+    }
+
+    /// Load another page when the selection nears the end of what is loaded
+    ///
+    /// Called on every draw, so a filter that matches little keeps searching
+    /// a page at a time without freezing the UI.
+    pub fn load_history_page(&mut self) {
+        if self.history.head.is_none() {
+            self.reset_history();
+        }
+        let history = &mut self.history;
+        if history.cursor.done || history.error.is_some() {
+            return;
+        }
+        let selected = history.table_state.selected().unwrap_or(0);
+        if selected + HISTORY_PREFETCH < history.commits.len() {
+            return;
+        }
+
+        let page =
+            crate::git::history_page(&history.filter, &mut history.cursor, HISTORY_PAGE_SIZE);
+        match page {
+            Ok(commits) => history.commits.extend(commits),
+            Err(e) => history.error = Some(e.to_string()),
+        }
+    }
+
+    /// Move the History selection by `delta` rows, clamped to what is loaded
+    pub fn history_navigate(&mut self, delta: isize) {
+        let count = self.history.commits.len();
+        if count == 0 {
+            return;
+        }
+        let current = self.history.table_state.selected().unwrap_or(0);
+        let next = current.saturating_add_signed(delta).min(count - 1);
+        self.history.table_state.select(Some(next));
+    }
+
+    /// Write every commit matching the filter, with stats, to a file in the git directory
+    ///
+    /// Runs in the background: the stats need a diff per commit.
+    pub fn start_history_export(&mut self, format: crate::export::ExportFormat) {
+        if self.history_export.is_some() {
+            return;
+        }
+        let filter = self.history.filter.clone();
+        let path = crate::export::default_export_path("history", format.extension());
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let result = crate::git::history_export(&filter, None)
+                .map_err(|e| e.to_string())
+                .and_then(|commits| {
+                    std::fs::write(&path, crate::export::render(&commits, format))
+                        .map(|()| (commits.len(), path))
+                        .map_err(|e| e.to_string())
+                });
+            let _ = tx.send(result);
+        });
+        self.history_export = Some(rx);
+        self.history_message = Some(format!(
+            "Exporting {}…",
+            format.extension().to_uppercase()
+        ));
+    }
+
+    /// Report a finished export under the History message
+    pub fn poll_history_export(&mut self) {
+        let result = match &self.history_export {
+            Some(rx) => rx.try_recv(),
+            None => return,
+        };
+        let message = match result {
+            Ok(Ok((count, path))) => {
+                format!("✓ Exported {} commits to {}", count, path.display())
+            }
+            Ok(Err(e)) => format!("✗ Failed to export history: {}", e),
+            Err(std::sync::mpsc::TryRecvError::Empty) => return,
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                "✗ Failed to export history".to_string()
+            }
+        };
+        self.history_export = None;
+        self.history_message = Some(message);
+    }
+
+    /// Open the note editor on the selected commit, filled in with its current note
+    pub fn open_note_edit(&mut self) {
+        let commit = match self
+            .history
+            .table_state
+            .selected()
+            .and_then(|idx| self.history.commits.get(idx))
+        {
+            Some(commit) => commit,
+            None => return,
+        };
+        let text = self.history.notes.get(&commit.id).cloned().unwrap_or_default();
+        let mut input = TextArea::new(text.lines().map(str::to_string).collect());
+        input.move_cursor(tui_textarea::CursorMove::Bottom);
+        input.move_cursor(tui_textarea::CursorMove::End);
+        self.note_edit = Some(NoteEdit {
+            commit: commit.id.clone(),
+            short_id: commit.short_id.clone(),
+            input,
+        });
+    }
+
+    /// Write the note being edited to the notes ref; an empty note removes the commit's note
+    pub fn save_note_edit(&mut self) {
+        if self.refuse_read_only("Editing notes") {
+            self.note_edit = None;
+            return;
+        }
+        let edit = match self.note_edit.take() {
+            Some(edit) => edit,
+            None => return,
+        };
+        let text = edit.input.lines().join("\n").trim().to_string();
+        let result = crate::git::set_commit_note(&edit.commit, &text).map_err(|e| e.to_string());
+        self.record_activity(
+            ActivityKind::Notes,
+            &edit.short_id,
+            if text.is_empty() {
+                "libgit2: note_delete on the default notes ref"
+            } else {
+                "libgit2: note (force) on the default notes ref"
+            },
+            match &result {
+                Ok(()) if text.is_empty() => Ok(format!("Removed the note on {}", edit.short_id)),
+                Ok(()) => Ok(format!("Saved the note on {}", edit.short_id)),
+                Err(e) => Err(e.clone()),
+            },
+        );
+        match result {
+            Ok(()) => {
+                if text.is_empty() {
+                    self.history.notes.remove(&edit.commit);
+                } else {
+                    self.history.notes.insert(edit.commit, text);
+                }
+            }
+            Err(e) => self.show_error("Note", &format!("Failed to save the note:\n\n{}", e)),
+        }
+    }
+
+    /// Push the notes ref to origin, or fetch origin's, in the background
+    pub fn sync_notes(&mut self, push: bool) {
+        if self.history_notes_sync.is_some() || (push && self.refuse_read_only("Pushing notes")) {
+            return;
+        }
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let result = if push {
+                ("push", crate::git::push_notes())
+            } else {
+                ("fetch", crate::git::fetch_notes())
+            };
+            let _ = tx.send((result.0, result.1.map_err(|e| e.to_string())));
+        });
+        self.history_notes_sync = Some(rx);
+        self.history_message = Some(if push {
+            "Pushing notes…".to_string()
+        } else {
+            "Fetching notes…".to_string()
+        });
+    }
+
+    /// Report a finished notes push or fetch, re-reading the notes after a fetch
+    pub fn poll_notes_sync(&mut self) {
+        let (direction, result) = match &self.history_notes_sync {
+            Some(rx) => match rx.try_recv() {
+                Ok(finished) => finished,
+                Err(std::sync::mpsc::TryRecvError::Empty) => return,
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                    ("sync", Err("the notes sync stopped unexpectedly".to_string()))
+                }
+            },
+            None => return,
+        };
+        self.history_notes_sync = None;
+        self.record_activity(
+            ActivityKind::Notes,
+            "origin",
+            if direction == "push" {
+                "libgit2: push the default notes ref to origin"
+            } else {
+                "libgit2: fetch origin's default notes ref, fast-forward only"
+            },
+            result.clone(),
+        );
+        if direction == "fetch" && result.is_ok() {
+            self.history.notes = crate::git::commit_notes().unwrap_or_default();
+        }
+        self.history_message = Some(match result {
+            Ok(message) => format!("✓ {}", message),
+            Err(e) => format!("✗ Notes {} failed: {}", direction, e),
+        });
+    }
+
+    /// Open the filter form, filled in with the current filter
+    pub fn open_history_filter(&mut self) {
+        let filter = &self.history.filter;
+        let values = [
+            filter.author.clone(),
+            filter.path.clone(),
+            filter.since.map(|date| date.to_string()).unwrap_or_default(),
+            filter.until.map(|date| date.to_string()).unwrap_or_default(),
+        ];
+        self.history_filter_edit = Some(HistoryFilterEdit {
+            fields: values.map(|value| {
+                let mut field = TextArea::new(vec![value]);
+                field.move_cursor(tui_textarea::CursorMove::End);
+                field
+            }),
+            focus: 0,
+            error: None,
+        });
+    }
+
+    /// Move between the fields of the filter form
+    pub fn cycle_history_filter_field(&mut self, forward: bool) {
+        if let Some(edit) = self.history_filter_edit.as_mut() {
+            let count = edit.fields.len();
+            edit.focus = if forward {
+                (edit.focus + 1) % count
+            } else {
+                (edit.focus + count - 1) % count
+            };
+        }
+    }
+
+    /// Check the form and, if it is valid, list the commits it matches
+    pub fn apply_history_filter(&mut self) {
+        let edit = match self.history_filter_edit.as_mut() {
+            Some(edit) => edit,
+            None => return,
+        };
+        let value = |idx: usize| edit.fields[idx].lines().join("").trim().to_string();
+        let dates = parse_filter_date("Since", &value(2))
+            .and_then(|since| Ok((since, parse_filter_date("Until", &value(3))?)));
+        let filter = match dates {
+            Ok((since, until)) if since.zip(until).is_some_and(|(since, until)| since > until) => {
+                edit.error = Some("Since must not be after Until".to_string());
+                return;
+            }
+            Ok((since, until)) => HistoryFilter {
+                author: value(0),
+                path: value(1),
+                since,
+                until,
+                ..self.history.filter.clone()
+            },
+            Err(e) => {
+                edit.error = Some(e);
+                return;
+            }
+        };
+
+        self.history_filter_edit = None;
+        self.set_history_filter(filter);
+    }
+
+    /// Switch between the whole graph and the first-parent line of history
+    pub fn toggle_history_first_parent(&mut self) {
+        let mut filter = self.history.filter.clone();
+        filter.first_parent = !filter.first_parent;
+        self.set_history_filter(filter);
+    }
+
+    /// Show or hide merge commits
+    pub fn toggle_history_merges(&mut self) {
+        let mut filter = self.history.filter.clone();
+        filter.hide_merges = !filter.hide_merges;
+        self.set_history_filter(filter);
+    }
+
+    /// Follow the path filter across renames, when it names a single file
+    pub fn toggle_history_follow(&mut self) {
+        let mut filter = self.history.filter.clone();
+        filter.follow = !filter.follow;
+        self.set_history_filter(filter);
+    }
+
+    /// Show the history of one file, following it across renames
+    pub fn show_file_history(&mut self, path: String) {
+        self.set_history_filter(HistoryFilter {
+            path,
+            follow: true,
+            ..HistoryFilter::default()
+        });
+    }
+
+    /// List the commits of a local branch other than the current one, to cherry-pick from
+    pub fn show_branch_history(&mut self, branch: String) {
+        self.set_history_filter(HistoryFilter {
+            branch,
+            ..HistoryFilter::default()
+        });
+    }
+
+    /// List every commit again
+    pub fn clear_history_filter(&mut self) {
+        self.set_history_filter(HistoryFilter::default());
+    }
+
+    /// List the commits of one calendar day, selecting the `selected`-th of them
+    pub fn show_day_in_history(&mut self, date: chrono::NaiveDate, selected: usize) {
+        self.set_history_filter(HistoryFilter {
+            since: Some(date),
+            until: Some(date),
+            ..HistoryFilter::default()
+        });
+        self.history.table_state.select(Some(selected));
+    }
+
+    fn set_history_filter(&mut self, filter: HistoryFilter) {
+        self.history.filter = filter;
+        self.reset_history();
+    }
+}
@@ -0,0 +1,101 @@
+use crate::app::{AfterHook, AppState, HookRunStatus};
+use crate::tui::theme::Theme;
+use ratatui::layout::{Constraint, Direction, Layout, Margin};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::{layout::Rect, Frame};
+
+/// Render the streamed output of the current hook run as a popup
+pub fn render_hook_output_popup(f: &mut Frame, area: Rect, state: &mut AppState, theme: &Theme) {
+    let output = match state.hook_output.as_mut() {
+        Some(output) => output,
+        None => return,
+    };
+
+    let popup_area = super::centered_rect(80, 20, area);
+
+    // Clear the background
+    f.render_widget(Clear, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(1),    // Output
+            Constraint::Length(1), // Result line
+        ])
+        .split(popup_area.inner(Margin {
+            vertical: 1,
+            horizontal: 2,
+        }));
+
+    let (title, border_style) = match output.status {
+        HookRunStatus::Running => (
+            format!("Running {} hook...", output.hook_name),
+            theme.popup_border_style(),
+        ),
+        HookRunStatus::Passed => (
+            format!("{} hook passed", output.hook_name),
+            theme.success_style(),
+        ),
+        HookRunStatus::Failed(_) => (
+            format!("{} hook failed", output.hook_name),
+            theme.error_style(),
+        ),
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .title_style(theme.popup_title_style())
+        .border_style(border_style)
+        .style(theme.popup_background_style());
+    f.render_widget(block, popup_area);
+
+    // Follow the tail while the hook runs, free scrolling afterwards
+    let visible_height = chunks[0].height as usize;
+    let max_scroll = output.lines.len().saturating_sub(visible_height);
+    if output.status == HookRunStatus::Running || output.scroll > max_scroll {
+        output.scroll = max_scroll;
+    }
+
+    let lines: Vec<Line> = if output.lines.is_empty() {
+        vec![Line::from(Span::styled("(no output yet)", theme.muted_text_style()))]
+    } else {
+        output
+            .lines
+            .iter()
+            .skip(output.scroll)
+            .take(visible_height)
+            .map(|line| Line::from(Span::styled(line.as_str(), theme.text_style())))
+            .collect()
+    };
+    f.render_widget(Paragraph::new(lines), chunks[0]);
+
+    // Only hooks that gate a push or commit mention it; UI event commands are informational
+    let (abort, started, aborted) = match output.then {
+        AfterHook::Commit => (
+            "[Esc] Abort commit",
+            ", commit continues",
+            ", commit aborted  [C] Commit anyway",
+        ),
+        _ if output.gates_push() => ("[Esc] Abort push", ", push continues", ", push aborted"),
+        _ => ("[Esc] Stop", "", ""),
+    };
+
+    let result = match output.status {
+        HookRunStatus::Running => Span::styled(abort, theme.muted_text_style()),
+        HookRunStatus::Passed => Span::styled(
+            format!("✓ Hook passed{} - [Enter]/[Esc] Close", started),
+            theme.success_style(),
+        ),
+        HookRunStatus::Failed(Some(code)) => Span::styled(
+            format!("✗ Exited with code {}{} - [↑↓] Scroll  [Enter]/[Esc] Close", code, aborted),
+            theme.error_style(),
+        ),
+        HookRunStatus::Failed(None) => Span::styled(
+            format!("✗ Hook was terminated{} - [↑↓] Scroll  [Enter]/[Esc] Close", aborted),
+            theme.error_style(),
+        ),
+    };
+    f.render_widget(Paragraph::new(Line::from(result)), chunks[1]);
+}
@@ -0,0 +1,95 @@
+use crate::app::{AppState, InitialCommit};
+use crate::tui::theme::Theme;
+use ratatui::layout::{Constraint, Layout, Margin};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::{layout::Rect, Frame};
+
+/// Render the follow-up to `git init`: branch name, first commit and template
+pub fn render_init_setup_popup(f: &mut Frame, area: Rect, state: &mut AppState, theme: &Theme) {
+    let setup = match state.init_setup.as_mut() {
+        Some(setup) => setup,
+        None => return,
+    };
+
+    let popup_area = super::centered_rect(60, 15, area);
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title("Set Up New Repository")
+        .title_style(theme.title_style())
+        .borders(Borders::ALL)
+        .border_style(theme.focused_border_style())
+        .style(theme.secondary_background_style());
+    let inner = block.inner(popup_area).inner(Margin {
+        vertical: 0,
+        horizontal: 1,
+    });
+    f.render_widget(block, popup_area);
+
+    let chunks = Layout::vertical([
+        Constraint::Length(3), // Branch name
+        Constraint::Length(1), // Spacer
+        Constraint::Length(1), // First commit label
+        Constraint::Length(1), // First commit choices
+        Constraint::Length(1), // Spacer
+        Constraint::Length(1), // Template label
+        Constraint::Length(1), // Template choice
+        Constraint::Length(1), // Spacer
+        Constraint::Length(1), // Help
+    ])
+    .split(inner);
+
+    setup.branch.set_block(
+        Block::default()
+            .title("Branch name")
+            .borders(Borders::ALL)
+            .border_style(theme.focused_border_style()),
+    );
+    setup.branch.set_style(theme.text_style());
+    f.render_widget(&setup.branch, chunks[0]);
+
+    f.render_widget(
+        Paragraph::new("First commit:").style(theme.accent2_style()),
+        chunks[2],
+    );
+
+    let mut choices = vec![(InitialCommit::Empty, "Empty commit")];
+    if setup.has_files || setup.template.is_some() {
+        choices.push((InitialCommit::Files, "Commit existing files"));
+    }
+    choices.push((InitialCommit::Skip, "No commit yet"));
+    let spans: Vec<Span> = choices
+        .iter()
+        .flat_map(|(choice, label)| {
+            let style = if *choice == setup.commit {
+                theme.highlight_style()
+            } else {
+                theme.secondary_text_style()
+            };
+            [Span::styled(format!(" {} ", label), style), Span::raw("  ")]
+        })
+        .collect();
+    f.render_widget(Paragraph::new(Line::from(spans)), chunks[3]);
+
+    f.render_widget(
+        Paragraph::new("Template:").style(theme.accent2_style()),
+        chunks[5],
+    );
+    let template = if setup.templates.is_empty() {
+        Span::styled(
+            " None installed (~/.config/gitix/templates) ",
+            theme.muted_text_style(),
+        )
+    } else {
+        let name = setup.template.as_deref().unwrap_or("None");
+        Span::styled(format!(" ◀ {} ▶ ", name), theme.highlight_style())
+    };
+    f.render_widget(Paragraph::new(Line::from(template)), chunks[6]);
+
+    f.render_widget(
+        Paragraph::new("[↑↓] First commit  [Tab] Template  [Enter] Create  [Esc] Skip")
+            .style(theme.muted_text_style()),
+        chunks[8],
+    );
+}
@@ -0,0 +1,2214 @@
+use crate::app::{AppState, SaveChangesFocus};
+use crate::git::{get_git_status, RebaseAction};
+use crate::tui::tabs::{Tab, TabRegistry};
+use ratatui::crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers, MouseEvent};
+use ratatui::layout::Rect;
+use std::path::PathBuf;
+
+/// Something the app reacts to: input from the terminal, or what a key stands for
+///
+/// The event loop turns terminal events into messages and hands them to `AppState::update`,
+/// the one place state changes in answer to the user. Keys that mean the same thing
+/// everywhere (Tab, Esc, q) are turned into their own messages, so they can be sent directly.
+#[derive(Debug)]
+pub enum AppMessage {
+    Key(KeyEvent),           // A key press, for whatever is on screen
+    Mouse(MouseEvent, Rect), // A mouse event on a screen of this size
+    Focus(bool),             // The terminal window gained or lost focus
+    Tick,                    // A frame was drawn; pick up background work
+    NextTab,
+    PreviousTab,
+    SwitchTab(Tab), // Show a tab, if it is in the tab bar and usable here
+    Back,           // Step back exactly one level: close the topmost popup or leave a text input
+    Quit,           // Quit, after asking if work would be interrupted or left behind
+    FileEdited,     // $EDITOR closed on a working tree file
+    ConflictEdited(String), // $EDITOR closed on a conflicted file
+    MergetoolExited(String, Result<(), Box<dyn std::error::Error>>), // git mergetool finished with a file
+    HookEdited, // $EDITOR closed on a hook script
+}
+
+/// What `AppState::update` needs the event loop to do: the things that take over the terminal
+#[derive(Debug)]
+pub enum Command {
+    None,
+    Quit,
+    // Open a file in $EDITOR (at a line, if given), then send `then`
+    Edit {
+        path: PathBuf,
+        line: Option<usize>,
+        then: AppMessage,
+    },
+    Mergetool(String), // Run git mergetool on a conflicted file, then send MergetoolExited
+}
+
+impl AppState {
+    /// React to a message, returning what the event loop has to do next
+    pub fn update(&mut self, message: AppMessage) -> Command {
+        match message {
+            AppMessage::Key(key_event) => return self.handle_key(key_event),
+            AppMessage::Mouse(mouse, area) => {
+                // Clicks are handled there; the wheel goes on as the arrow key it stands for
+                if let Some(key_event) = super::mouse::handle_mouse(self, mouse, area) {
+                    return self.handle_key(key_event);
+                }
+            }
+            AppMessage::Focus(focused) => self.terminal_focused = focused,
+            AppMessage::Tick => self.tick(),
+            AppMessage::NextTab => {
+                let next = self.tab_bar.next(self.active_tab, self.git_enabled);
+                self.switch_tab(next);
+            }
+            AppMessage::PreviousTab => {
+                let previous = self.tab_bar.prev(self.active_tab, self.git_enabled);
+                self.switch_tab(previous);
+            }
+            AppMessage::SwitchTab(tab) => {
+                if self.tab_bar.contains(tab) && (self.git_enabled || !tab.requires_git()) {
+                    self.switch_tab(tab);
+                }
+            }
+            AppMessage::Back => self.back(),
+            AppMessage::Quit => {
+                if self.request_quit() {
+                    return Command::Quit;
+                }
+            }
+            AppMessage::FileEdited => {
+                self.refresh_save_changes_git_status();
+                self.refresh_file_detail();
+            }
+            AppMessage::ConflictEdited(path) => self.conflicts_after_edit(path),
+            AppMessage::MergetoolExited(path, result) => self.conflicts_after_mergetool(path, result),
+            AppMessage::HookEdited => self.refresh_hooks(),
+        }
+        Command::None
+    }
+
+    /// Pick up background work once a frame has been drawn
+    fn tick(&mut self) {
+        // Hand any pending refresh to a sync worker once the loading indicator is drawn
+        if self.pending_refresh_work {
+            self.perform_refresh_work();
+        }
+
+        // Stream output from a running hook into its popup
+        self.poll_hook_output();
+
+        // Re-read status after changes made outside gitix, then pick up the real status
+        // after those or an optimistic stage/unstage
+        self.poll_watcher();
+        self.poll_save_changes_reconcile();
+
+        // Pick up a finished hotspots scan or language count
+        self.poll_churn_scan();
+        self.poll_language_scan();
+        self.poll_recursive_scan();
+        self.poll_history_export();
+        self.poll_notes_sync();
+
+        // Pick up transfer progress, credentials a fetch or push asks for, and its result
+        self.poll_credential_requests();
+        self.poll_sync_worker();
+        self.update_spinner();
+
+        // Pick up the startup release check
+        self.poll_update_check();
+
+        // Pick up finished forge requests: CI result, pull requests, a repository being created
+        self.poll_forge();
+        self.poll_pull_request_draft();
+        self.poll_publish();
+        self.poll_tag_push();
+
+        // Snapshot the working tree when the backup interval comes round
+        self.maybe_backup();
+        self.poll_backup();
+
+        // Track which tab is visible so background results can decide whether to notify
+        self.on_update_tab = self.active_tab == Tab::Update;
+
+        // Periodically refresh the remote status while the Update tab is open
+        self.maybe_auto_refresh();
+    }
+
+    /// Handle a key press: the popup on top gets it first, then a focused text input,
+    /// then the active tab
+    fn handle_key(&mut self, key_event: KeyEvent) -> Command {
+        let active_tab = self.active_tab;
+
+        // Esc always steps back exactly one level, whatever is on screen
+        if key_event.code == KeyCode::Esc {
+            return self.update(AppMessage::Back);
+        }
+
+        // If a fetch or push is waiting for credentials, the focused field gets the keys
+        if self.credential_prompt.is_some() {
+            match key_event.code {
+                KeyCode::Enter => self.submit_credentials(),
+                KeyCode::Tab | KeyCode::BackTab => {
+                    if let Some(prompt) = self.credential_prompt.as_mut() {
+                        if prompt.request.kind == crate::credentials::CredentialKind::UserPass {
+                            prompt.editing_secret = !prompt.editing_secret;
+                        }
+                    }
+                }
+                _ => {
+                    if let Some(prompt) = self.credential_prompt.as_mut() {
+                        if prompt.editing_secret {
+                            prompt.secret.input(Event::Key(key_event));
+                        } else {
+                            prompt.username.input(Event::Key(key_event));
+                        }
+                    }
+                }
+            }
+            return Command::None;
+        }
+
+        // If showing error popup, only handle Enter to close it and C to copy its report
+        if self.show_error_popup {
+            match key_event.code {
+                KeyCode::Enter => self.hide_error(),
+                KeyCode::Char('c') | KeyCode::Char('C') => self.copy_error_report(),
+                _ => {}
+            }
+            return Command::None;
+        }
+
+        // If confirming quit, only handle Y/N
+        if self.quit_confirm.is_some() {
+            match key_event.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => return Command::Quit,
+                KeyCode::Char('s') | KeyCode::Char('S')
+                    if self.quit_confirm.as_ref().is_some_and(|c| !c.warnings.is_empty()) =>
+                {
+                    if self.suppress_unpushed_warnings() {
+                        return Command::Quit;
+                    }
+                }
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Char('q') => {
+                    self.quit_confirm = None;
+                }
+                _ => {}
+            }
+            return Command::None;
+        }
+
+        // If showing hook output, only handle scrolling and closing
+        if self.hook_output.is_some() {
+            match key_event.code {
+                KeyCode::Up => {
+                    if let Some(output) = self.hook_output.as_mut() {
+                        output.scroll = output.scroll.saturating_sub(1);
+                    }
+                }
+                KeyCode::Down => {
+                    if let Some(output) = self.hook_output.as_mut() {
+                        output.scroll = output.scroll.saturating_add(1);
+                    }
+                }
+                KeyCode::Enter if !self.is_hook_running() => self.close_hook_output(),
+                KeyCode::Char('c') | KeyCode::Char('C') => self.commit_despite_verify(),
+                _ => {}
+            }
+            return Command::None;
+        }
+
+        // If confirming an abort, only handle the answer
+        if self.abort_confirm.is_some() {
+            match key_event.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => self.confirm_abort(),
+                KeyCode::Char('n') | KeyCode::Char('N') => self.abort_confirm = None,
+                _ => {}
+            }
+            return Command::None;
+        }
+
+        // If confirming a cherry-pick, only handle the answer
+        if self.cherry_pick_confirm.is_some() {
+            match key_event.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => self.confirm_cherry_pick(),
+                KeyCode::Char('n') | KeyCode::Char('N') => self.cherry_pick_confirm = None,
+                _ => {}
+            }
+            return Command::None;
+        }
+
+        // If picking how to reset, only handle the modal
+        if self.reset_confirm.is_some() {
+            match key_event.code {
+                KeyCode::Left | KeyCode::BackTab => self.reset_select_mode(false),
+                KeyCode::Right | KeyCode::Tab => self.reset_select_mode(true),
+                KeyCode::Enter => self.confirm_reset(),
+                KeyCode::Backspace => self.reset_type(None),
+                KeyCode::Char(c) => self.reset_type(Some(c)),
+                _ => {}
+            }
+            return Command::None;
+        }
+
+        // If planning an interactive rebase, only handle the planner
+        if self.rebase_plan.is_some() {
+            match (key_event.code, key_event.modifiers) {
+                (KeyCode::Up, KeyModifiers::SHIFT) | (KeyCode::Char('k'), _) | (KeyCode::Char('K'), _) => {
+                    self.rebase_plan_move(true)
+                }
+                (KeyCode::Down, KeyModifiers::SHIFT) | (KeyCode::Char('j'), _) | (KeyCode::Char('J'), _) => {
+                    self.rebase_plan_move(false)
+                }
+                (KeyCode::Up, _) => self.rebase_plan_navigate(-1),
+                (KeyCode::Down, _) => self.rebase_plan_navigate(1),
+                (KeyCode::PageUp, _) => self.rebase_plan_navigate(-10),
+                (KeyCode::PageDown, _) => self.rebase_plan_navigate(10),
+                (KeyCode::Char('p'), _) | (KeyCode::Char('P'), _) => self.rebase_plan_mark(RebaseAction::Pick),
+                (KeyCode::Char('s'), _) | (KeyCode::Char('S'), _) => self.rebase_plan_mark(RebaseAction::Squash),
+                (KeyCode::Char('f'), _) | (KeyCode::Char('F'), _) => self.rebase_plan_mark(RebaseAction::Fixup),
+                (KeyCode::Char('d'), _) | (KeyCode::Char('D'), _) => self.rebase_plan_mark(RebaseAction::Drop),
+                (KeyCode::Enter, _) => self.confirm_rebase_plan(),
+                _ => {}
+            }
+            return Command::None;
+        }
+
+        // If showing what the formatter changed, only scroll or close
+        if self.format_report.is_some() {
+            match key_event.code {
+                KeyCode::Up => self.format_report_scroll(-1),
+                KeyCode::Down => self.format_report_scroll(1),
+                KeyCode::PageUp => self.format_report_scroll(-10),
+                KeyCode::PageDown => self.format_report_scroll(10),
+                KeyCode::Enter | KeyCode::Char('q') => self.format_report = None,
+                _ => {}
+            }
+            return Command::None;
+        }
+
+        // If resolving conflicts, only handle the conflicts popup
+        if self.conflicts.is_some() {
+            match key_event.code {
+                KeyCode::Up => self.conflicts_navigate(false),
+                KeyCode::Down => self.conflicts_navigate(true),
+                KeyCode::Left => self.conflicts_switch_pane(false),
+                KeyCode::Right => self.conflicts_switch_pane(true),
+                KeyCode::PageUp => self.conflicts_scroll(-10),
+                KeyCode::PageDown => self.conflicts_scroll(10),
+                KeyCode::Char('o') | KeyCode::Char('O') => {
+                    self.conflicts_take(crate::git::ConflictSide::Ours);
+                }
+                KeyCode::Char('t') | KeyCode::Char('T') => {
+                    self.conflicts_take(crate::git::ConflictSide::Theirs);
+                }
+                KeyCode::Char('e') | KeyCode::Char('E') => {
+                    if let Some(path) = self.selected_conflict() {
+                        let line = crate::git::conflict_marker_line(&path);
+                        return Command::Edit {
+                            path: PathBuf::from(&path),
+                            line,
+                            then: AppMessage::ConflictEdited(path),
+                        };
+                    }
+                }
+                KeyCode::Char('m') | KeyCode::Char('M') => {
+                    if let Some(path) = self.selected_conflict() {
+                        return Command::Mergetool(path);
+                    }
+                }
+                KeyCode::Char('r') | KeyCode::Char('R') => self.conflicts_mark_resolved(),
+                KeyCode::Char('a') | KeyCode::Char('A') => self.request_abort(),
+                KeyCode::Char('c') | KeyCode::Char('C') => self.conflicts_continue(),
+                KeyCode::Char('q') => self.conflicts = None,
+                _ => {}
+            }
+            return Command::None;
+        }
+
+        // If writing a pull request, the title and description get the keys
+        if let Some(draft) = self.pull_request_draft.as_mut() {
+            let editable = draft.rx.is_none() && draft.created.is_none();
+            match (key_event.code, key_event.modifiers) {
+                (KeyCode::Char('s'), KeyModifiers::CONTROL) => self.submit_pull_request(),
+                (KeyCode::Char('o'), KeyModifiers::CONTROL) => {
+                    self.open_pull_request_draft_in_browser();
+                }
+                (KeyCode::Enter, _) if draft.created.is_some() => {
+                    self.pull_request_draft = None;
+                }
+                (KeyCode::Tab, _) => self.draft_move(true),
+                (KeyCode::BackTab, _) => self.draft_move(false),
+                (KeyCode::Left, _) if draft.field == crate::app::DraftField::Base => {
+                    self.draft_cycle_base(false);
+                }
+                (KeyCode::Right, _) if draft.field == crate::app::DraftField::Base => {
+                    self.draft_cycle_base(true);
+                }
+                // The title is one line; Enter moves on to the description
+                (KeyCode::Enter, _) if draft.field == crate::app::DraftField::Title => {
+                    self.draft_move(true);
+                }
+                _ if editable && draft.field == crate::app::DraftField::Title => {
+                    draft.title.input(Event::Key(key_event));
+                }
+                _ if editable && draft.field == crate::app::DraftField::Body => {
+                    draft.body.input(Event::Key(key_event));
+                }
+                _ => {}
+            }
+            return Command::None;
+        }
+
+        // If listing pull requests, only handle the pull request popup
+        if self.pull_requests.is_some() {
+            match key_event.code {
+                KeyCode::Up => self.pull_requests_navigate(false),
+                KeyCode::Down => self.pull_requests_navigate(true),
+                KeyCode::Enter => self.open_selected_pull_request(),
+                KeyCode::Char('n') | KeyCode::Char('N') => {
+                    self.open_forge_page(super::pull_requests::ForgePage::NewPullRequest);
+                }
+                KeyCode::Char('w') | KeyCode::Char('W') => {
+                    self.open_forge_page(super::pull_requests::ForgePage::PullRequests);
+                }
+                KeyCode::Char('q') => self.pull_requests = None,
+                _ => {}
+            }
+            return Command::None;
+        }
+
+        // If explaining the repository, only handle closing
+        if self.explain.is_some() {
+            if matches!(key_event.code, KeyCode::Enter | KeyCode::F(1) | KeyCode::Char('q')) {
+                self.explain = None;
+            }
+            return Command::None;
+        }
+
+        // If touring, only step through the tour
+        if self.tour.is_some() {
+            match key_event.code {
+                KeyCode::Enter | KeyCode::Right | KeyCode::Char(' ') => self.advance_tour(true),
+                KeyCode::Left | KeyCode::Backspace => self.advance_tour(false),
+                KeyCode::Char('q') => self.finish_tour(),
+                _ => {}
+            }
+            if let Some(tab) = self.tour_tab() {
+                self.switch_tab(tab);
+            }
+            return Command::None;
+        }
+
+        // If showing hook template picker, only handle picker navigation
+        if active_tab == Tab::Settings && self.show_hook_template_popup {
+            let template_count = crate::hooks::HOOK_TEMPLATES.len();
+            match key_event.code {
+                KeyCode::Up => {
+                    self.hook_template_selected =
+                        (self.hook_template_selected + template_count - 1) % template_count;
+                }
+                KeyCode::Down => {
+                    self.hook_template_selected =
+                        (self.hook_template_selected + 1) % template_count;
+                }
+                KeyCode::Enter => self.install_selected_hook_template(),
+                _ => {}
+            }
+            return Command::None;
+        }
+
+        // If adding or editing a remote, the form gets the keys
+        if active_tab == Tab::Settings && self.remote_edit.is_some() {
+            match key_event.code {
+                KeyCode::Enter => self.save_remote_edit(),
+                KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down => {
+                    if let Some(edit) = self.remote_edit.as_mut() {
+                        edit.editing_url = !edit.editing_url;
+                    }
+                }
+                _ => {
+                    if let Some(edit) = self.remote_edit.as_mut() {
+                        if edit.editing_url {
+                            edit.url.input(Event::Key(key_event));
+                        } else {
+                            edit.name.input(Event::Key(key_event));
+                        }
+                        edit.error = None;
+                    }
+                }
+            }
+            return Command::None;
+        }
+
+        // If confirming a remote's removal, only answer the question
+        if active_tab == Tab::Settings && self.remote_remove.is_some() {
+            match key_event.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => self.confirm_remote_remove(),
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Char('q') => {
+                    self.remote_remove = None;
+                }
+                _ => {}
+            }
+            return Command::None;
+        }
+
+        // If confirming a pull or push, only answer the question
+        if active_tab == Tab::Update && self.sync_confirm.is_some() {
+            match key_event.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => self.confirm_sync(),
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Char('q') => {
+                    self.sync_confirm = None;
+                }
+                _ => {}
+            }
+            return Command::None;
+        }
+
+        // If confirming an amend, only answer the question
+        if active_tab == Tab::SaveChanges && self.amend_confirm {
+            match key_event.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
+                    self.amend_commit();
+                }
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Char('q') => {
+                    self.amend_confirm = false;
+                }
+                _ => {}
+            }
+            return Command::None;
+        }
+
+        // If confirming a discard, only answer the question
+        if active_tab == Tab::SaveChanges && self.discard_confirm.is_some() {
+            match key_event.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => self.confirm_discard(),
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Char('q') => {
+                    self.discard_confirm = None;
+                }
+                _ => {}
+            }
+            return Command::None;
+        }
+
+        // If writing a review note, the note field gets the keys
+        if active_tab == Tab::SaveChanges && self.review_note_input.is_some() {
+            match key_event.code {
+                KeyCode::Enter => self.save_review_note(),
+                _ => {
+                    if let Some(note) = self.review_note_input.as_mut() {
+                        note.input.input(Event::Key(key_event));
+                    }
+                }
+            }
+            return Command::None;
+        }
+
+        // If listing review notes, only handle the list's keys
+        if active_tab == Tab::SaveChanges && self.review_notes_view.is_some() {
+            match key_event.code {
+                KeyCode::Up => self.review_notes_navigate(false),
+                KeyCode::Down => self.review_notes_navigate(true),
+                KeyCode::Enter => self.go_to_selected_review_note(),
+                KeyCode::Char('d') | KeyCode::Char('D') => self.delete_selected_review_note(),
+                KeyCode::Char('e') | KeyCode::Char('E') => self.export_review_notes(),
+                KeyCode::Char('q') => self.review_notes_view = None,
+                _ => {}
+            }
+            return Command::None;
+        }
+
+        // If showing the file detail popup, only handle its quick actions
+        if active_tab == Tab::SaveChanges && self.file_detail.is_some() {
+            let confirming = self.file_detail.as_ref().is_some_and(|d| d.confirm_discard);
+            match key_event.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') if confirming => {
+                    self.file_detail_discard();
+                }
+                _ if confirming => {
+                    if let Some(detail) = self.file_detail.as_mut() {
+                        detail.confirm_discard = false;
+                    }
+                }
+                KeyCode::Char('s') | KeyCode::Char('S') | KeyCode::Char(' ') => {
+                    self.file_detail_toggle_staging();
+                }
+                KeyCode::Char('d') | KeyCode::Char('D') => {
+                    if let Some(detail) = self.file_detail.as_mut() {
+                        detail.confirm_discard = true;
+                    }
+                }
+                KeyCode::Char('g') | KeyCode::Char('G') => self.file_detail_ignore(),
+                KeyCode::Char('r') | KeyCode::Char('R') => self.restore_selected_deleted_file(),
+                KeyCode::Char('o') | KeyCode::Char('O') => {
+                    if let Some(detail) = &self.file_detail {
+                        return edit_at_first_change(detail.file.path.clone());
+                    }
+                }
+                KeyCode::Enter | KeyCode::Char('i') | KeyCode::Char('q') => {
+                    self.file_detail = None;
+                }
+                _ => {}
+            }
+            return Command::None;
+        }
+
+        // If browsing backups, only handle the browser and its restore confirmation
+        if active_tab == Tab::SaveChanges && self.backup_browser.is_some() {
+            let confirming = self
+                .backup_browser
+                .as_ref()
+                .and_then(|b| b.files.as_ref())
+                .is_some_and(|files| files.confirm);
+            match key_event.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') if confirming => {
+                    self.restore_backup_file();
+                }
+                _ if confirming => self.backup_browser_back(),
+                KeyCode::Up => self.backup_browser_navigate(-1),
+                KeyCode::Down => self.backup_browser_navigate(1),
+                KeyCode::Enter => self.backup_browser_enter(),
+                KeyCode::Char('s') | KeyCode::Char('S') => self.start_backup(),
+                KeyCode::Char('q') | KeyCode::Char('B') => self.backup_browser = None,
+                _ => {}
+            }
+            return Command::None;
+        }
+
+        // If staging by pattern, the pathspec field gets the keys; the preview follows it
+        if active_tab == Tab::SaveChanges && self.stage_pattern.is_some() {
+            match key_event.code {
+                KeyCode::Enter => self.confirm_stage_pattern(),
+                KeyCode::Up => self.stage_pattern_scroll(-1),
+                KeyCode::Down => self.stage_pattern_scroll(1),
+                KeyCode::PageUp => self.stage_pattern_scroll(-10),
+                KeyCode::PageDown => self.stage_pattern_scroll(10),
+                _ => {
+                    if let Some(pattern) = self.stage_pattern.as_mut() {
+                        pattern.input.input(Event::Key(key_event));
+                    }
+                    self.update_stage_pattern_matches();
+                }
+            }
+            return Command::None;
+        }
+
+        // If previewing a clean, only handle the preview and its confirmation
+        if active_tab == Tab::SaveChanges && self.clean_preview.is_some() {
+            let (confirming, include_ignored, count) = match &self.clean_preview {
+                Some(p) => (p.confirming, p.include_ignored, p.paths.len()),
+                None => return Command::None,
+            };
+            match key_event.code {
+                KeyCode::Up => {
+                    if let Some(preview) = self.clean_preview.as_mut() {
+                        preview.scroll = preview.scroll.saturating_sub(1);
+                    }
+                }
+                KeyCode::Down => {
+                    if let Some(preview) = self.clean_preview.as_mut() {
+                        preview.scroll =
+                            (preview.scroll + 1).min(count.saturating_sub(1));
+                    }
+                }
+                KeyCode::Char('i') | KeyCode::Char('I') if !confirming => {
+                    if !self.hidden_by_profile("Cleaning ignored files") {
+                        self.open_clean_preview(!include_ignored);
+                    }
+                }
+                KeyCode::Enter if !confirming && count > 0 => {
+                    if let Some(preview) = self.clean_preview.as_mut() {
+                        preview.confirming = true;
+                    }
+                    // Experts skip the last check when everything goes to the trash
+                    if self.profile == crate::app::Profile::Expert && !self.deletes_permanently() {
+                        self.perform_clean();
+                    }
+                }
+                KeyCode::Char('y') | KeyCode::Char('Y') if confirming => {
+                    self.perform_clean();
+                }
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Char('q') => {
+                    self.clean_preview = None;
+                }
+                _ => {}
+            }
+            return Command::None;
+        }
+
+        // If confirming a delete in the Files tab, only handle Y/N
+        if active_tab == Tab::Files && self.files_delete_confirm.is_some() {
+            match key_event.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
+                    self.perform_files_delete();
+                }
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Char('q') => {
+                    self.files_delete_confirm = None;
+                }
+                _ => {}
+            }
+            return Command::None;
+        }
+
+        // If showing the hotspots report, only handle its controls
+        if active_tab == Tab::Overview && self.churn_view.is_some() {
+            match key_event.code {
+                KeyCode::Up => {
+                    if let Some(view) = self.churn_view.as_mut() {
+                        view.scroll = view.scroll.saturating_sub(1);
+                    }
+                }
+                KeyCode::Down => {
+                    if let Some(view) = self.churn_view.as_mut() {
+                        view.scroll += 1;
+                    }
+                }
+                KeyCode::Left => self.cycle_churn_window(false),
+                KeyCode::Right => self.cycle_churn_window(true),
+                KeyCode::Char('q') | KeyCode::Char('h') => self.churn_view = None,
+                _ => {}
+            }
+            return Command::None;
+        }
+
+        // If listing nested repositories, only handle its navigation
+        if active_tab == Tab::Overview && self.nested_view.is_some() {
+            match key_event.code {
+                KeyCode::Up => self.nested_navigate(-1),
+                KeyCode::Down => self.nested_navigate(1),
+                KeyCode::Char('q') | KeyCode::Char('n') | KeyCode::Char('N') => {
+                    self.nested_view = None;
+                }
+                _ => {}
+            }
+            return Command::None;
+        }
+
+        // If showing the language breakdown, only handle scrolling
+        if active_tab == Tab::Overview && self.language_view.is_some() {
+            let scroll = self.language_view.unwrap_or(0);
+            match key_event.code {
+                KeyCode::Up => self.language_view = Some(scroll.saturating_sub(1)),
+                KeyCode::Down => self.language_view = Some(scroll + 1),
+                KeyCode::Char('q') | KeyCode::Char('l') => self.language_view = None,
+                _ => {}
+            }
+            return Command::None;
+        }
+
+        // If listing a calendar day's commits, only handle its navigation
+        if active_tab == Tab::Overview && self.day_commits.is_some() {
+            if let Some(day) = self.day_commits.as_mut() {
+                match key_event.code {
+                    KeyCode::Up => {
+                        day.selected = day.selected.saturating_sub(1);
+                        day.show_message = false;
+                    }
+                    KeyCode::Down => {
+                        day.selected =
+                            (day.selected + 1).min(day.commits.len().saturating_sub(1));
+                        day.show_message = false;
+                    }
+                    KeyCode::Enter => day.show_message = !day.show_message,
+                    KeyCode::Char('q') => self.day_commits = None,
+                    KeyCode::Char('h') | KeyCode::Char('H') if self.tab_bar.contains(Tab::History) => {
+                        let (date, selected) = (day.date, day.selected);
+                        self.day_commits = None;
+                        self.show_day_in_history(date, selected);
+                        self.switch_tab(Tab::History);
+                    }
+                    _ => {}
+                }
+            }
+            return Command::None;
+        }
+
+        // If showing a commit's full message, only handle scrolling and closing
+        if active_tab == Tab::History && self.history.message_scroll.is_some() {
+            let scroll = self.history.message_scroll.unwrap_or(0);
+            match key_event.code {
+                KeyCode::Up => self.history.message_scroll = Some(scroll.saturating_sub(1)),
+                KeyCode::Down => self.history.message_scroll = Some(scroll + 1),
+                KeyCode::Enter | KeyCode::Char('q') => self.history.message_scroll = None,
+                _ => {}
+            }
+            return Command::None;
+        }
+
+        // If naming a new branch, the name field gets the keys
+        if active_tab == Tab::Branches && self.branch_create.is_some() {
+            match key_event.code {
+                KeyCode::Enter => self.create_branch_from_input(),
+                _ => {
+                    if let Some(input) = self.branch_create.as_mut() {
+                        input.input(Event::Key(key_event));
+                    }
+                }
+            }
+            return Command::None;
+        }
+
+        // If writing a new tag, the focused field gets the keys
+        if active_tab == Tab::Tags && self.tag_create.is_some() {
+            let editing_message = self.tag_create.as_ref().is_some_and(|c| c.editing_message);
+            match (key_event.code, key_event.modifiers) {
+                (KeyCode::Char('s'), KeyModifiers::CONTROL) => self.create_tag_from_input(),
+                (KeyCode::Enter, _) if !editing_message => self.create_tag_from_input(),
+                (KeyCode::Tab, _) | (KeyCode::BackTab, _) => {
+                    if let Some(create) = self.tag_create.as_mut() {
+                        create.editing_message = !create.editing_message;
+                    }
+                }
+                _ => {
+                    if let Some(create) = self.tag_create.as_mut() {
+                        if create.editing_message {
+                            create.message.input(Event::Key(key_event));
+                        } else {
+                            create.name.input(Event::Key(key_event));
+                        }
+                    }
+                }
+            }
+            return Command::None;
+        }
+
+        // If confirming a tag deletion, only answer the question
+        if active_tab == Tab::Tags && self.tag_delete.is_some() {
+            match key_event.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => self.confirm_tag_delete(),
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Char('q') => {
+                    self.tag_delete = None;
+                }
+                _ => {}
+            }
+            return Command::None;
+        }
+
+        // If choosing between switching to a branch and merging it, only handle the choice
+        if active_tab == Tab::Branches && self.branch_action.is_some() {
+            match key_event.code {
+                KeyCode::Up | KeyCode::Down => {
+                    if let Some(action) = self.branch_action.as_mut() {
+                        action.merge = !action.merge;
+                    }
+                }
+                KeyCode::Char('s') | KeyCode::Char('S') => {
+                    self.branch_action = None;
+                    self.checkout_selected_branch();
+                }
+                KeyCode::Char('m') | KeyCode::Char('M') => {
+                    if let Some(action) = self.branch_action.as_mut() {
+                        action.merge = true;
+                    }
+                    self.confirm_branch_action();
+                }
+                KeyCode::Enter => self.confirm_branch_action(),
+                KeyCode::Char('q') => self.branch_action = None,
+                _ => {}
+            }
+            return Command::None;
+        }
+
+        // If writing a merge, the mode keys and the message editor get the keys
+        if active_tab == Tab::Branches && self.merge_draft.is_some() {
+            match (key_event.code, key_event.modifiers) {
+                (KeyCode::Char('s'), KeyModifiers::CONTROL) => self.confirm_merge(),
+                (KeyCode::Tab, _) => self.merge_next_mode(true),
+                (KeyCode::BackTab, _) => self.merge_next_mode(false),
+                _ => {
+                    if let Some(draft) = self
+                        .merge_draft
+                        .as_mut()
+                        .filter(|draft| draft.mode != crate::git::MergeMode::FastForwardOnly)
+                    {
+                        draft.message.input(Event::Key(key_event));
+                    }
+                }
+            }
+            return Command::None;
+        }
+
+        // If confirming a branch deletion, only answer the question
+        if active_tab == Tab::Branches && self.branch_delete.is_some() {
+            match key_event.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => self.confirm_branch_delete(),
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Char('q') => {
+                    self.branch_delete = None;
+                }
+                _ => {}
+            }
+            return Command::None;
+        }
+
+        // If confirming an undo, only handle the answer
+        if active_tab == Tab::History && self.undo_confirm.is_some() {
+            match key_event.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => self.confirm_undo(),
+                KeyCode::Char('n') | KeyCode::Char('N') => self.undo_confirm = None,
+                _ => {}
+            }
+            return Command::None;
+        }
+
+        // If showing the reflog, only handle moving through it and undoing
+        if active_tab == Tab::History && self.reflog_view.is_some() {
+            match key_event.code {
+                KeyCode::Up => self.reflog_navigate(-1),
+                KeyCode::Down => self.reflog_navigate(1),
+                KeyCode::PageUp => self.reflog_navigate(-20),
+                KeyCode::PageDown => self.reflog_navigate(20),
+                KeyCode::Char('z') | KeyCode::Char('Z') => self.request_undo(),
+                KeyCode::Char('q') => self.reflog_view = None,
+                _ => {}
+            }
+            return Command::None;
+        }
+
+        // If writing a commit's note, the editor gets the keys
+        if active_tab == Tab::History && self.note_edit.is_some() {
+            match (key_event.code, key_event.modifiers) {
+                (KeyCode::Char('s'), KeyModifiers::CONTROL) => self.save_note_edit(),
+                _ => {
+                    if let Some(edit) = self.note_edit.as_mut() {
+                        edit.input.input(Event::Key(key_event));
+                    }
+                }
+            }
+            return Command::None;
+        }
+
+        // If editing the History filter, the form gets the keys
+        if active_tab == Tab::History && self.history_filter_edit.is_some() {
+            match key_event.code {
+                KeyCode::Enter => self.apply_history_filter(),
+                KeyCode::Tab | KeyCode::Down => self.cycle_history_filter_field(true),
+                KeyCode::BackTab | KeyCode::Up => self.cycle_history_filter_field(false),
+                _ => {
+                    if let Some(edit) = self.history_filter_edit.as_mut() {
+                        edit.fields[edit.focus].input(Event::Key(key_event));
+                        edit.error = None;
+                    }
+                }
+            }
+            return Command::None;
+        }
+
+        // If showing a file's blame, only handle scrolling it
+        if active_tab == Tab::Files && self.blame_view.is_some() {
+            match key_event.code {
+                KeyCode::Up => self.blame_navigate(-1),
+                KeyCode::Down => self.blame_navigate(1),
+                KeyCode::PageUp => self.blame_navigate(-20),
+                KeyCode::PageDown => self.blame_navigate(20),
+                KeyCode::Home => self.blame_navigate(isize::MIN),
+                KeyCode::End => self.blame_navigate(isize::MAX),
+                KeyCode::Char('q') => self.blame_view = None,
+                _ => {}
+            }
+            return Command::None;
+        }
+
+        // If showing the flagged files list, only handle its navigation
+        if active_tab == Tab::Files && self.flagged_files_view.is_some() {
+            let count = self.status_flagged_files.len();
+            let selected = self.flagged_files_view.unwrap_or(0);
+            match key_event.code {
+                KeyCode::Up => self.flagged_files_view = Some(selected.saturating_sub(1)),
+                KeyCode::Down => {
+                    self.flagged_files_view =
+                        Some((selected + 1).min(count.saturating_sub(1)));
+                }
+                KeyCode::Char(' ') | KeyCode::Enter => self.clear_selected_flagged_file(),
+                KeyCode::Char('q') | KeyCode::Char('W') => self.flagged_files_view = None,
+                _ => {}
+            }
+            return Command::None;
+        }
+
+        // If setting up a new repository, the branch name input gets the keys
+        if self.init_setup.is_some() {
+            match key_event.code {
+                KeyCode::Enter => self.apply_init_setup(),
+                KeyCode::Up => self.cycle_initial_commit(false),
+                KeyCode::Down => self.cycle_initial_commit(true),
+                KeyCode::BackTab => self.cycle_init_setup_template(false),
+                KeyCode::Tab => self.cycle_init_setup_template(true),
+                _ => {
+                    if let Some(setup) = self.init_setup.as_mut() {
+                        setup.branch.input(Event::Key(key_event));
+                    }
+                }
+            }
+            return Command::None;
+        }
+
+        // If offering to publish a new repository, the name input gets the keys
+        if let Some(setup) = self.publish.as_mut() {
+            let on_name = setup.field == crate::app::PublishField::Name;
+            match key_event.code {
+                KeyCode::Enter => self.start_publish(),
+                KeyCode::Up | KeyCode::BackTab => self.publish_move(false),
+                KeyCode::Down | KeyCode::Tab => self.publish_move(true),
+                KeyCode::Left if !on_name => self.publish_change(false),
+                KeyCode::Right | KeyCode::Char(' ') if !on_name => {
+                    self.publish_change(true)
+                }
+                _ if on_name && setup.rx.is_none() => {
+                    setup.name.input(Event::Key(key_event));
+                }
+                _ => {}
+            }
+            return Command::None;
+        }
+
+        // If showing prompt, only handle Y/N
+        if self.show_init_prompt {
+            match key_event.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => {
+                    if let Err(e) = self.try_init_repo() {
+                        // Show user-friendly error popup
+                        self.show_error(
+                            "Repository Initialization Failed",
+                            &format!("Failed to initialize Git repository:\n\n{}", e)
+                        );
+                    } else {
+                        // The new repository's config may customise the tab bar
+                        self.tab_bar = TabRegistry::load();
+                        if !self.tab_bar.contains(self.active_tab) {
+                            self.active_tab = self.tab_bar.first(self.git_enabled);
+                        }
+                    }
+                }
+                KeyCode::Char('n') | KeyCode::Char('N') => {
+                    self.decline_init_repo();
+                }
+                KeyCode::Char('q') => return Command::Quit,
+                _ => {}
+            }
+            return Command::None;
+        }
+
+        // A focused text input gets every printable key before any shortcut is considered
+        if self.is_text_input_focused() && is_printable(&key_event) {
+            self.type_into_focused_input(key_event);
+            return Command::None;
+        }
+
+        // Only allow navigation to enabled tabs
+        match (key_event.code, key_event.modifiers) {
+            (KeyCode::Tab, KeyModifiers::NONE) => return self.update(AppMessage::NextTab),
+            (KeyCode::BackTab, _) | (KeyCode::Tab, KeyModifiers::SHIFT) => {
+                return self.update(AppMessage::PreviousTab);
+            }
+            // Jump straight to a tab by its position: 1-9, or Alt+1-9 while typing
+            (KeyCode::Char(c @ '1'..='9'), KeyModifiers::NONE | KeyModifiers::ALT) => {
+                let index = c as usize - '1' as usize;
+                if let Some(&tab) = self.tab_bar.tabs().get(index) {
+                    return self.update(AppMessage::SwitchTab(tab));
+                }
+            }
+            // F1 explains the repository everywhere except while typing a commit message,
+            // where it keeps opening the commit help
+            (KeyCode::F(1), _)
+                if self.git_enabled
+                    && !(active_tab == Tab::SaveChanges
+                        && self.save_changes_focus == SaveChangesFocus::CommitMessage) =>
+            {
+                self.open_explain();
+            }
+            (KeyCode::Char('q'), _) => return self.update(AppMessage::Quit),
+            (KeyCode::Left, _) if active_tab == Tab::Overview && self.git_enabled => {
+                self.move_calendar_cursor(-1);
+            }
+            (KeyCode::Right, _) if active_tab == Tab::Overview && self.git_enabled => {
+                self.move_calendar_cursor(1);
+            }
+            (KeyCode::Up, _) if active_tab == Tab::Overview && self.git_enabled => {
+                self.move_calendar_cursor(-7);
+            }
+            (KeyCode::Down, _) if active_tab == Tab::Overview && self.git_enabled => {
+                self.move_calendar_cursor(7);
+            }
+            (KeyCode::Enter, _) if active_tab == Tab::Overview && self.git_enabled => {
+                self.open_day_commits();
+            }
+            (KeyCode::Char('n'), KeyModifiers::NONE) | (KeyCode::Char('N'), KeyModifiers::SHIFT)
+                if active_tab == Tab::Overview && self.git_enabled =>
+            {
+                self.nested_view = Some(0);
+            }
+            (KeyCode::Char('l'), KeyModifiers::NONE) if active_tab == Tab::Overview && self.git_enabled => {
+                self.language_view = Some(0);
+            }
+            (KeyCode::Char('h'), KeyModifiers::NONE) if active_tab == Tab::Overview && self.git_enabled => {
+                self.open_churn_view();
+            }
+            (KeyCode::Char('g'), KeyModifiers::NONE) if active_tab == Tab::Overview && self.git_enabled => {
+                self.cycle_sparkline_bucket();
+            }
+            (KeyCode::Char('r'), KeyModifiers::NONE) if active_tab == Tab::Overview && self.git_enabled => {
+                self.cycle_sparkline_range();
+            }
+            (KeyCode::Char('m'), KeyModifiers::NONE) | (KeyCode::Char('M'), KeyModifiers::SHIFT)
+                if active_tab == Tab::Overview && self.git_enabled =>
+            {
+                self.toggle_activity_merges();
+            }
+            (KeyCode::Char('b'), KeyModifiers::NONE) | (KeyCode::Char('B'), KeyModifiers::SHIFT)
+                if active_tab == Tab::Overview && self.git_enabled =>
+            {
+                self.toggle_activity_bots();
+            }
+            (KeyCode::Down, _) if active_tab == Tab::Files => {
+                // Files tab: move selection down
+                let add_parent = self.files_add_parent();
+                let files = crate::files::list_files(&self.current_dir, add_parent);
+                if !files.is_empty() {
+                    self.files_selected_row =
+                        (self.files_selected_row + 1).min(files.len() - 1);
+                }
+            }
+            (KeyCode::Up, _) if active_tab == Tab::Files => {
+                // Files tab: move selection up
+                let add_parent = self.files_add_parent();
+                let files = crate::files::list_files(&self.current_dir, add_parent);
+                if !files.is_empty() {
+                    self.files_selected_row =
+                        self.files_selected_row.saturating_sub(1);
+                }
+            }
+            (KeyCode::Char('w'), KeyModifiers::NONE) if active_tab == Tab::Files && self.git_enabled => {
+                self.toggle_selected_index_flag(crate::git::IndexFlag::SkipWorktree);
+            }
+            (KeyCode::Char('a'), KeyModifiers::NONE) if active_tab == Tab::Files && self.git_enabled => {
+                self.toggle_selected_index_flag(crate::git::IndexFlag::AssumeUnchanged);
+            }
+            (KeyCode::Char('W'), KeyModifiers::SHIFT) if active_tab == Tab::Files && self.git_enabled => {
+                self.load_status_git_status();
+                self.flagged_files_view = Some(0);
+            }
+            (KeyCode::Char('o'), KeyModifiers::NONE) if active_tab == Tab::Files => {
+                // Reveal the selected entry, or the directory itself from "..", in the file manager
+                let files = crate::files::list_files(&self.current_dir, self.files_add_parent());
+                let path = match files.get(self.files_selected_row) {
+                    Some(entry) if entry.name != ".." => self.current_dir.join(&entry.name),
+                    _ => self.current_dir.clone(),
+                };
+                if let Err(e) = crate::files::reveal_in_file_manager(&path) {
+                    self.show_error(
+                        "Cannot Open File Manager",
+                        &format!("Failed to reveal {}:\n\n{}", path.display(), e),
+                    );
+                }
+            }
+            (KeyCode::Char('h'), KeyModifiers::NONE) if active_tab == Tab::Files && self.git_enabled && self.tab_bar.contains(Tab::History) => {
+                // The selected file's history, across renames
+                if let Some(path) = self.selected_files_entry_path() {
+                    self.show_file_history(path);
+                    self.switch_tab(Tab::History);
+                }
+            }
+            (KeyCode::Char('b'), KeyModifiers::NONE) if active_tab == Tab::Files && self.git_enabled => {
+                self.open_blame();
+            }
+            (KeyCode::Char('d'), KeyModifiers::NONE) | (KeyCode::Delete, _) if active_tab == Tab::Files => {
+                self.request_files_delete();
+            }
+            (KeyCode::Char('s'), KeyModifiers::NONE) if active_tab == Tab::Files => {
+                // Launch directory -> repository root -> anywhere on disk
+                self.cycle_files_scope();
+            }
+            (KeyCode::Enter, _) if active_tab == Tab::Files => {
+                let add_parent = self.files_add_parent();
+                let files = crate::files::list_files(&self.current_dir, add_parent);
+                if files.is_empty() {
+                    return Command::None;
+                }
+                let idx = self.files_selected_row.min(files.len() - 1);
+                let entry = &files[idx];
+                if entry.name == ".." && add_parent {
+                    // Go up a directory
+                    if let Some(parent) = self.current_dir.parent() {
+                        self.current_dir = parent.to_path_buf();
+                        self.files_selected_row = 0;
+                    }
+                } else if entry.is_dir {
+                    // Go into directory, following symlinks to where they really lead
+                    let jail = self.files_jail();
+                    match crate::files::resolve_dir(&self.current_dir, &entry.name, jail.as_deref()) {
+                        Ok(new_dir) if new_dir.is_dir() => {
+                            self.current_dir = new_dir;
+                            self.files_selected_row = 0;
+                        }
+                        Ok(_) => {}
+                        Err(e) => self.show_error("Cannot Open Link", &e.to_string()),
+                    }
+                } else if entry.broken_link {
+                    let target = entry.link_target.as_ref().map(|t| t.display().to_string()).unwrap_or_default();
+                    self.show_error("Broken Link", &format!("'{}' points to {}, which doesn't exist.", entry.name, target));
+                } else {
+                    // Open file in $EDITOR
+                    let mut file_path = self.current_dir.clone();
+                    file_path.push(&entry.name);
+                    let _ = crate::files::open_in_editor(&file_path);
+                }
+            }
+            (KeyCode::Down, KeyModifiers::SHIFT) | (KeyCode::PageDown, _)
+                if active_tab == Tab::SaveChanges
+                    && !self.show_commit_help
+                    && !self.show_template_popup
+                    && self.save_changes_focus != SaveChangesFocus::CommitMessage =>
+            {
+                let lines = if key_event.code == KeyCode::PageDown { 10 } else { 1 };
+                self.scroll_save_changes_diff(lines);
+            }
+            (KeyCode::Up, KeyModifiers::SHIFT) | (KeyCode::PageUp, _)
+                if active_tab == Tab::SaveChanges
+                    && !self.show_commit_help
+                    && !self.show_template_popup
+                    && self.save_changes_focus != SaveChangesFocus::CommitMessage =>
+            {
+                let lines = if key_event.code == KeyCode::PageUp { 10 } else { 1 };
+                self.scroll_save_changes_diff(-lines);
+            }
+            (KeyCode::Right, _)
+                if active_tab == Tab::SaveChanges
+                    && !self.show_commit_help
+                    && !self.show_template_popup
+                    && self.save_changes_focus == SaveChangesFocus::FileList =>
+            {
+                self.open_hunk_view();
+            }
+            (KeyCode::Left, _)
+                if active_tab == Tab::SaveChanges
+                    && !self.show_commit_help
+                    && !self.show_template_popup
+                    && self.save_changes_focus == SaveChangesFocus::HunkView =>
+            {
+                self.save_changes_focus = SaveChangesFocus::FileList;
+            }
+            (KeyCode::Left, _)
+                if active_tab == Tab::SaveChanges
+                    && !self.show_commit_help
+                    && !self.show_template_popup
+                    && self.save_changes_focus == SaveChangesFocus::Review =>
+            {
+                self.save_changes_focus = SaveChangesFocus::HunkView;
+                self.hunk_view_navigate(0);
+            }
+            (KeyCode::Char('r') | KeyCode::Char('R'), _)
+                if active_tab == Tab::SaveChanges
+                    && !self.show_commit_help
+                    && !self.show_template_popup
+                    && self.save_changes_focus == SaveChangesFocus::HunkView =>
+            {
+                self.open_review();
+            }
+            (KeyCode::Char('n'), KeyModifiers::NONE) | (KeyCode::Enter, _)
+                if active_tab == Tab::SaveChanges
+                    && !self.show_commit_help
+                    && !self.show_template_popup
+                    && self.save_changes_focus == SaveChangesFocus::Review =>
+            {
+                self.open_review_note();
+            }
+            (KeyCode::Char('N'), KeyModifiers::SHIFT)
+                if active_tab == Tab::SaveChanges
+                    && !self.show_commit_help
+                    && !self.show_template_popup
+                    && self.save_changes_focus != SaveChangesFocus::CommitMessage =>
+            {
+                self.open_review_notes();
+            }
+            (KeyCode::Char('v'), KeyModifiers::NONE)
+                if active_tab == Tab::SaveChanges
+                    && !self.show_commit_help
+                    && !self.show_template_popup
+                    && self.save_changes_focus == SaveChangesFocus::FileList =>
+            {
+                self.show_save_changes_diff = !self.show_save_changes_diff;
+            }
+            (KeyCode::Down, _) if active_tab == Tab::SaveChanges => {
+                // Save changes tab navigation - only if no popups are shown
+                if !self.show_commit_help && !self.show_template_popup {
+                    self.save_changes_navigate_down();
+                } else if self.show_commit_help {
+                    // Scroll down in help popup
+                    self.help_popup_scroll_down();
+                }
+            }
+            (KeyCode::Up, _) if active_tab == Tab::SaveChanges => {
+                // Save changes tab navigation - only if no popups are shown
+                if !self.show_commit_help && !self.show_template_popup {
+                    self.save_changes_navigate_up();
+                } else if self.show_commit_help {
+                    // Scroll up in help popup
+                    self.help_popup_scroll_up();
+                }
+            }
+            (KeyCode::Char(' '), _) if active_tab == Tab::SaveChanges => {
+                // Save changes tab: toggle file staging - only if no popups are shown and focus is on file list
+                if !self.show_commit_help && !self.show_template_popup && self.save_changes_focus == SaveChangesFocus::FileList {
+                    self.toggle_file_staging();
+                } else if !self.show_commit_help && !self.show_template_popup && self.save_changes_focus == SaveChangesFocus::HunkView {
+                    self.toggle_selected_hunk();
+                }
+            }
+            (KeyCode::Enter, _) if active_tab == Tab::SaveChanges && self.show_commit_help => {
+                // Close help popup when Enter is pressed
+                self.show_commit_help = false;
+            }
+            (KeyCode::Enter, _) if active_tab == Tab::SaveChanges && self.show_template_popup => {
+                // Template popup: apply selection
+                self.apply_template_selection();
+            }
+            (KeyCode::Left, _) if active_tab == Tab::SaveChanges && self.show_template_popup => {
+                // Template popup: navigate to Yes button
+                self.template_popup_navigate_left();
+            }
+            (KeyCode::Right, _) if active_tab == Tab::SaveChanges && self.show_template_popup => {
+                // Template popup: navigate to No button
+                self.template_popup_navigate_right();
+            }
+            (KeyCode::Enter, _) if active_tab == Tab::SaveChanges && !self.show_commit_help && !self.show_template_popup => {
+                // Save changes tab: commit staged files (only works when in file list and no popups)
+                if self.save_changes_focus == SaveChangesFocus::FileList {
+                    // Ctrl+Enter commits once without running hooks (--no-verify), except for beginners
+                    if key_event.modifiers.contains(KeyModifiers::CONTROL)
+                        && self.profile != crate::app::Profile::Beginner
+                    {
+                        self.commit_skip_hooks = true;
+                    }
+                    if self.commit_amend.is_some() {
+                        self.request_amend();
+                        return Command::None;
+                    }
+                    self.start_commit();
+                } else {
+                    // In commit message area, add a new line
+                    self.commit_message.insert_newline();
+                }
+            }
+            (KeyCode::Char('?'), KeyModifiers::SHIFT) if active_tab == Tab::SaveChanges && !self.show_commit_help && !self.show_template_popup => {
+                // Save changes tab: show help popup
+                self.show_commit_help = true;
+            }
+            (KeyCode::Char('o'), KeyModifiers::CONTROL) if active_tab == Tab::SaveChanges && !self.show_commit_help && !self.show_template_popup => {
+                // Save changes tab: toggle skipping hooks for the next commit
+                if !self.hidden_by_profile("Skipping hooks") {
+                    self.commit_skip_hooks = !self.commit_skip_hooks;
+                }
+            }
+            (KeyCode::Char('i'), KeyModifiers::NONE)
+                if active_tab == Tab::SaveChanges
+                    && !self.show_commit_help
+                    && !self.show_template_popup
+                    && self.save_changes_focus == SaveChangesFocus::FileList =>
+            {
+                self.open_file_detail();
+            }
+            (KeyCode::Char('d'), KeyModifiers::NONE)
+                if active_tab == Tab::SaveChanges
+                    && !self.show_commit_help
+                    && !self.show_template_popup
+                    && self.save_changes_focus == SaveChangesFocus::FileList =>
+            {
+                self.request_discard_selected();
+            }
+            (KeyCode::Char('r'), KeyModifiers::NONE)
+                if active_tab == Tab::SaveChanges
+                    && !self.show_commit_help
+                    && !self.show_template_popup
+                    && self.save_changes_focus == SaveChangesFocus::FileList =>
+            {
+                self.restore_selected_deleted_file();
+            }
+            (KeyCode::Char('C'), KeyModifiers::SHIFT)
+                if active_tab == Tab::SaveChanges
+                    && !self.show_commit_help
+                    && !self.show_template_popup
+                    && self.save_changes_focus == SaveChangesFocus::FileList =>
+            {
+                self.open_clean_preview(false);
+            }
+            (KeyCode::Char('p'), KeyModifiers::NONE)
+                if active_tab == Tab::SaveChanges
+                    && !self.show_commit_help
+                    && !self.show_template_popup
+                    && self.save_changes_focus == SaveChangesFocus::FileList =>
+            {
+                self.open_stage_pattern();
+            }
+            (KeyCode::Char('w'), KeyModifiers::NONE)
+                if active_tab == Tab::SaveChanges
+                    && !self.show_commit_help
+                    && !self.show_template_popup
+                    && self.save_changes_focus == SaveChangesFocus::FileList =>
+            {
+                self.wip_save();
+            }
+            (KeyCode::Char('W'), KeyModifiers::SHIFT)
+                if active_tab == Tab::SaveChanges
+                    && !self.show_commit_help
+                    && !self.show_template_popup
+                    && self.save_changes_focus == SaveChangesFocus::FileList =>
+            {
+                self.wip_resume();
+            }
+            (KeyCode::Char('B'), KeyModifiers::SHIFT)
+                if active_tab == Tab::SaveChanges
+                    && !self.show_commit_help
+                    && !self.show_template_popup
+                    && self.save_changes_focus == SaveChangesFocus::FileList =>
+            {
+                self.open_backup_browser();
+            }
+            (KeyCode::Char('e'), KeyModifiers::NONE)
+                if active_tab == Tab::SaveChanges
+                    && !self.show_commit_help
+                    && !self.show_template_popup
+                    && self.save_changes_focus == SaveChangesFocus::FileList =>
+            {
+                // Edit the selected file, then pick up whatever changed
+                if let Some(file) = self.selected_save_changes_file() {
+                    let path = file.path.clone();
+                    if path.exists() {
+                        return edit_at_first_change(path);
+                    }
+                }
+            }
+            (KeyCode::Char('T'), KeyModifiers::SHIFT) if active_tab == Tab::SaveChanges && !self.show_commit_help && !self.show_template_popup => {
+                // Save changes tab: show template popup
+                self.toggle_template_popup();
+            }
+            (KeyCode::Char('A'), KeyModifiers::SHIFT)
+                if active_tab == Tab::SaveChanges
+                    && !self.show_commit_help
+                    && !self.show_template_popup
+                    && self.save_changes_focus == SaveChangesFocus::FileList =>
+            {
+                self.toggle_amend();
+            }
+            // Help and template shortcuts that also work while typing the commit message
+            (KeyCode::F(1), _) if active_tab == Tab::SaveChanges && !self.show_commit_help && !self.show_template_popup => {
+                self.show_commit_help = true;
+            }
+            (KeyCode::Char('t'), KeyModifiers::CONTROL) if active_tab == Tab::SaveChanges && !self.show_commit_help && !self.show_template_popup => {
+                self.toggle_template_popup();
+            }
+            (KeyCode::F(2), _) if active_tab == Tab::SaveChanges && !self.show_commit_help && !self.show_template_popup => {
+                self.toggle_amend();
+            }
+            (KeyCode::Char('s'), KeyModifiers::CONTROL)
+                if active_tab == Tab::SaveChanges
+                    && !self.show_commit_help
+                    && !self.show_template_popup
+                    && self.commit_amend.is_some() =>
+            {
+                self.request_amend();
+            }
+            // Handle commit message input when focused on commit message and no popups are shown
+            _ if active_tab == Tab::SaveChanges
+                && !self.show_commit_help
+                && !self.show_template_popup
+                && self.save_changes_focus == SaveChangesFocus::CommitMessage =>
+            {
+                self.type_into_focused_input(key_event);
+            }
+            // Settings tab key bindings
+            (KeyCode::Left, KeyModifiers::CONTROL) if active_tab == Tab::Settings && self.git_enabled => {
+                // Settings tab: cycle panels backward
+                self.settings_focus = match self.settings_focus {
+                    crate::app::SettingsFocus::Author => crate::app::SettingsFocus::Remotes,
+                    crate::app::SettingsFocus::Theme => crate::app::SettingsFocus::Author,
+                    crate::app::SettingsFocus::Git => crate::app::SettingsFocus::Theme,
+                    crate::app::SettingsFocus::Hooks => crate::app::SettingsFocus::Git,
+                    crate::app::SettingsFocus::Remotes => crate::app::SettingsFocus::Hooks,
+                };
+            }
+            (KeyCode::Right, KeyModifiers::CONTROL) if active_tab == Tab::Settings && self.git_enabled => {
+                // Settings tab: cycle panels forward
+                self.settings_focus = match self.settings_focus {
+                    crate::app::SettingsFocus::Author => crate::app::SettingsFocus::Theme,
+                    crate::app::SettingsFocus::Theme => crate::app::SettingsFocus::Git,
+                    crate::app::SettingsFocus::Git => crate::app::SettingsFocus::Hooks,
+                    crate::app::SettingsFocus::Hooks => crate::app::SettingsFocus::Remotes,
+                    crate::app::SettingsFocus::Remotes => crate::app::SettingsFocus::Author,
+                };
+            }
+            (KeyCode::Char('g'), KeyModifiers::CONTROL) if active_tab == Tab::Settings && self.git_enabled => {
+                self.start_tour();
+                if let Some(tab) = self.tour_tab() {
+                    self.switch_tab(tab);
+                }
+            }
+            (KeyCode::Left, _) if active_tab == Tab::Settings && self.git_enabled => {
+                // Settings tab: cycle theme colors backward (only works in Theme panel) or toggle Git settings
+                if self.settings_focus == crate::app::SettingsFocus::Theme {
+                    use crate::app::ThemeFocus;
+                    match self.settings_theme_focus {
+                        ThemeFocus::Accent => {
+                            self.current_theme_accent = super::cycle_accent_color_backward(self.current_theme_accent);
+                        }
+                        ThemeFocus::Accent2 => {
+                            self.current_theme_accent2 = super::cycle_accent_color_backward(self.current_theme_accent2);
+                        }
+                        ThemeFocus::Accent3 => {
+                            self.current_theme_accent3 = super::cycle_accent_color_backward(self.current_theme_accent3);
+                        }
+                        ThemeFocus::Title => {
+                            self.current_theme_title = super::cycle_title_color_backward(self.current_theme_title);
+                        }
+                    }
+                } else if self.settings_focus == crate::app::SettingsFocus::Git {
+                    // Toggle the focused Git setting
+                    match self.settings_git_focus {
+                        crate::app::GitFocus::Profile => self.profile = self.profile.cycle(false),
+                        crate::app::GitFocus::PullRebase => self.pull_rebase = !self.pull_rebase,
+                        crate::app::GitFocus::Notifications => {
+                            self.notifications_enabled = !self.notifications_enabled
+                        }
+                        crate::app::GitFocus::AutoRefresh => self.cycle_auto_refresh(false),
+                        crate::app::GitFocus::ForkPush => {
+                            self.fork_push_after_sync = !self.fork_push_after_sync
+                        }
+                        crate::app::GitFocus::CommitWrap => self.cycle_commit_wrap(false),
+                        crate::app::GitFocus::InitTemplate => self.cycle_init_template(false),
+                        crate::app::GitFocus::PermanentDelete => {
+                            self.permanent_delete = !self.permanent_delete
+                        }
+                        crate::app::GitFocus::UpdateCheck => self.update_check = !self.update_check,
+                        crate::app::GitFocus::Forge => self.cycle_forge_override(false),
+                    }
+                    // Clear status message when changing settings
+                    if self.settings_status_message.is_some() {
+                        self.settings_status_message = None;
+                    }
+                }
+            }
+            (KeyCode::Right, _) if active_tab == Tab::Settings && self.git_enabled => {
+                // Settings tab: cycle theme colors forward (only works in Theme panel) or toggle Git settings
+                if self.settings_focus == crate::app::SettingsFocus::Theme {
+                    use crate::app::ThemeFocus;
+                    match self.settings_theme_focus {
+                        ThemeFocus::Accent => {
+                            self.current_theme_accent = super::cycle_accent_color_forward(self.current_theme_accent);
+                        }
+                        ThemeFocus::Accent2 => {
+                            self.current_theme_accent2 = super::cycle_accent_color_forward(self.current_theme_accent2);
+                        }
+                        ThemeFocus::Accent3 => {
+                            self.current_theme_accent3 = super::cycle_accent_color_forward(self.current_theme_accent3);
+                        }
+                        ThemeFocus::Title => {
+                            self.current_theme_title = super::cycle_title_color_forward(self.current_theme_title);
+                        }
+                    }
+                } else if self.settings_focus == crate::app::SettingsFocus::Git {
+                    // Toggle the focused Git setting
+                    match self.settings_git_focus {
+                        crate::app::GitFocus::Profile => self.profile = self.profile.cycle(true),
+                        crate::app::GitFocus::PullRebase => self.pull_rebase = !self.pull_rebase,
+                        crate::app::GitFocus::Notifications => {
+                            self.notifications_enabled = !self.notifications_enabled
+                        }
+                        crate::app::GitFocus::AutoRefresh => self.cycle_auto_refresh(true),
+                        crate::app::GitFocus::ForkPush => {
+                            self.fork_push_after_sync = !self.fork_push_after_sync
+                        }
+                        crate::app::GitFocus::CommitWrap => self.cycle_commit_wrap(true),
+                        crate::app::GitFocus::InitTemplate => self.cycle_init_template(true),
+                        crate::app::GitFocus::PermanentDelete => {
+                            self.permanent_delete = !self.permanent_delete
+                        }
+                        crate::app::GitFocus::UpdateCheck => self.update_check = !self.update_check,
+                        crate::app::GitFocus::Forge => self.cycle_forge_override(true),
+                    }
+                    // Clear status message when changing settings
+                    if self.settings_status_message.is_some() {
+                        self.settings_status_message = None;
+                    }
+                }
+            }
+            (KeyCode::Up, _) if active_tab == Tab::Settings && self.git_enabled => {
+                match self.settings_focus {
+                    crate::app::SettingsFocus::Author => {
+                        self.settings_author_focus = crate::app::AuthorFocus::Name;
+                    }
+                    crate::app::SettingsFocus::Theme => {
+                        use crate::app::ThemeFocus;
+                        self.settings_theme_focus = match self.settings_theme_focus {
+                            ThemeFocus::Accent2 => ThemeFocus::Accent,
+                            ThemeFocus::Accent3 => ThemeFocus::Accent2,
+                            ThemeFocus::Title => ThemeFocus::Accent3,
+                            ThemeFocus::Accent => ThemeFocus::Title,
+                        };
+                    }
+                    crate::app::SettingsFocus::Git => {
+                        use crate::app::GitFocus;
+                        self.settings_git_focus = match self.settings_git_focus {
+                            GitFocus::Profile => GitFocus::Forge,
+                            GitFocus::PullRebase => GitFocus::Profile,
+                            GitFocus::Notifications => GitFocus::PullRebase,
+                            GitFocus::AutoRefresh => GitFocus::Notifications,
+                            GitFocus::ForkPush => GitFocus::AutoRefresh,
+                            GitFocus::CommitWrap => GitFocus::ForkPush,
+                            GitFocus::InitTemplate => GitFocus::CommitWrap,
+                            GitFocus::PermanentDelete => GitFocus::InitTemplate,
+                            GitFocus::UpdateCheck => GitFocus::PermanentDelete,
+                            GitFocus::Forge => GitFocus::UpdateCheck,
+                        };
+                    }
+                    crate::app::SettingsFocus::Hooks => {
+                        self.settings_hook_selected = self.settings_hook_selected.saturating_sub(1);
+                    }
+                    crate::app::SettingsFocus::Remotes => {
+                        self.settings_remote_selected = self.settings_remote_selected.saturating_sub(1);
+                    }
+                }
+            }
+            (KeyCode::Down, _) if active_tab == Tab::Settings && self.git_enabled => {
+                match self.settings_focus {
+                    crate::app::SettingsFocus::Author => {
+                        self.settings_author_focus = crate::app::AuthorFocus::Email;
+                    }
+                    crate::app::SettingsFocus::Theme => {
+                        use crate::app::ThemeFocus;
+                        self.settings_theme_focus = match self.settings_theme_focus {
+                            ThemeFocus::Accent => ThemeFocus::Accent2,
+                            ThemeFocus::Accent2 => ThemeFocus::Accent3,
+                            ThemeFocus::Accent3 => ThemeFocus::Title,
+                            ThemeFocus::Title => ThemeFocus::Accent,
+                        };
+                    }
+                    crate::app::SettingsFocus::Git => {
+                        use crate::app::GitFocus;
+                        self.settings_git_focus = match self.settings_git_focus {
+                            GitFocus::PullRebase => GitFocus::Notifications,
+                            GitFocus::Notifications => GitFocus::AutoRefresh,
+                            GitFocus::AutoRefresh => GitFocus::ForkPush,
+                            GitFocus::ForkPush => GitFocus::CommitWrap,
+                            GitFocus::CommitWrap => GitFocus::InitTemplate,
+                            GitFocus::InitTemplate => GitFocus::PermanentDelete,
+                            GitFocus::PermanentDelete => GitFocus::UpdateCheck,
+                            GitFocus::Profile => GitFocus::PullRebase,
+                            GitFocus::UpdateCheck => GitFocus::Forge,
+                            GitFocus::Forge => GitFocus::Profile,
+                        };
+                    }
+                    crate::app::SettingsFocus::Hooks => {
+                        if self.settings_hook_selected + 1 < self.settings_hooks.len() {
+                            self.settings_hook_selected += 1;
+                        }
+                    }
+                    crate::app::SettingsFocus::Remotes => {
+                        if self.settings_remote_selected + 1 < self.settings_remotes.len() {
+                            self.settings_remote_selected += 1;
+                        }
+                    }
+                }
+            }
+            (KeyCode::Char('s'), KeyModifiers::CONTROL) if active_tab == Tab::Settings && self.git_enabled => {
+                // Save settings
+                match self.save_settings() {
+                    Ok(()) => {
+                        self.settings_status_message = Some("✓ Settings saved successfully".to_string());
+                    }
+                    Err(e) => {
+                        self.settings_status_message = Some(format!("✗ Failed to save: {}", e));
+                    }
+                }
+            }
+            // Hooks panel actions
+            (KeyCode::Char(' '), _) | (KeyCode::Enter, _)
+                if active_tab == Tab::Settings
+                    && self.git_enabled
+                    && self.settings_focus == crate::app::SettingsFocus::Hooks =>
+            {
+                self.toggle_selected_hook();
+            }
+            (KeyCode::Char('e'), _) | (KeyCode::Char('E'), _)
+                if active_tab == Tab::Settings
+                    && self.git_enabled
+                    && self.settings_focus == crate::app::SettingsFocus::Hooks =>
+            {
+                if let Some(hook) = self.selected_hook() {
+                    return Command::Edit {
+                        path: hook.path.clone(),
+                        line: None,
+                        then: AppMessage::HookEdited,
+                    };
+                }
+            }
+            (KeyCode::Char('t'), _) | (KeyCode::Char('T'), _)
+                if active_tab == Tab::Settings
+                    && self.git_enabled
+                    && self.settings_focus == crate::app::SettingsFocus::Hooks =>
+            {
+                self.hook_template_selected = 0;
+                self.show_hook_template_popup = true;
+            }
+            // Remotes panel actions
+            (KeyCode::Char('a'), _) | (KeyCode::Char('A'), _)
+                if active_tab == Tab::Settings
+                    && self.git_enabled
+                    && self.settings_focus == crate::app::SettingsFocus::Remotes =>
+            {
+                self.open_remote_add();
+            }
+            (KeyCode::Char('e'), _) | (KeyCode::Char('E'), _) | (KeyCode::Enter, _)
+                if active_tab == Tab::Settings
+                    && self.git_enabled
+                    && self.settings_focus == crate::app::SettingsFocus::Remotes =>
+            {
+                self.open_remote_edit();
+            }
+            (KeyCode::Char('d'), _) | (KeyCode::Char('D'), _) | (KeyCode::Delete, _)
+                if active_tab == Tab::Settings
+                    && self.git_enabled
+                    && self.settings_focus == crate::app::SettingsFocus::Remotes =>
+            {
+                self.request_remote_remove();
+            }
+            // Handle author input when in settings tab and author panel
+            _ if active_tab == Tab::Settings
+                && self.git_enabled
+                && self.settings_focus == crate::app::SettingsFocus::Author =>
+            {
+                self.type_into_focused_input(key_event);
+            }
+            // Status tab operations
+            (KeyCode::Char('a'), _) | (KeyCode::Char('A'), _) if active_tab == Tab::Status && self.git_enabled => {
+                // Abort the merge or rebase shown in the State line
+                self.request_abort();
+            }
+            (KeyCode::Char('r'), _) | (KeyCode::Char('R'), _) if active_tab == Tab::Status && self.git_enabled => {
+                self.invalidate_status_git_status();
+            }
+            // History tab operations
+            (KeyCode::Up, _) if active_tab == Tab::History && self.git_enabled => {
+                self.history_navigate(-1);
+            }
+            (KeyCode::Down, _) if active_tab == Tab::History && self.git_enabled => {
+                self.history_navigate(1);
+            }
+            (KeyCode::PageUp, _) if active_tab == Tab::History && self.git_enabled => {
+                self.history_navigate(-20);
+            }
+            (KeyCode::PageDown, _) if active_tab == Tab::History && self.git_enabled => {
+                self.history_navigate(20);
+            }
+            (KeyCode::Enter, _) if active_tab == Tab::History && self.git_enabled => {
+                if !self.history.commits.is_empty() {
+                    self.history.message_scroll = Some(0);
+                }
+            }
+            (KeyCode::Home, _) if active_tab == Tab::History && self.git_enabled => {
+                self.history.table_state.select(Some(0));
+            }
+            (KeyCode::End, _) if active_tab == Tab::History && self.git_enabled => {
+                // The last loaded commit; the next page loads behind it
+                self.history_navigate(isize::MAX);
+            }
+            (KeyCode::Char('e'), _) | (KeyCode::Char('E'), _) if active_tab == Tab::History && self.git_enabled => {
+                self.start_history_export(crate::export::ExportFormat::Csv);
+            }
+            (KeyCode::Char('j'), _) | (KeyCode::Char('J'), _) if active_tab == Tab::History && self.git_enabled => {
+                self.start_history_export(crate::export::ExportFormat::Json);
+            }
+            (KeyCode::Char('/'), _) | (KeyCode::Char('f'), KeyModifiers::NONE) if active_tab == Tab::History && self.git_enabled => {
+                self.open_history_filter();
+            }
+            (KeyCode::Char('c'), KeyModifiers::NONE) | (KeyCode::Char('C'), KeyModifiers::SHIFT) if active_tab == Tab::History && self.git_enabled => {
+                self.clear_history_filter();
+            }
+            (KeyCode::Char('p'), KeyModifiers::NONE) | (KeyCode::Char('P'), KeyModifiers::SHIFT) if active_tab == Tab::History && self.git_enabled => {
+                self.toggle_history_first_parent();
+            }
+            (KeyCode::Char('m'), KeyModifiers::NONE) | (KeyCode::Char('M'), KeyModifiers::SHIFT) if active_tab == Tab::History && self.git_enabled => {
+                self.toggle_history_merges();
+            }
+            (KeyCode::Char('r'), KeyModifiers::NONE) | (KeyCode::Char('R'), KeyModifiers::SHIFT) if active_tab == Tab::History && self.git_enabled => {
+                self.toggle_history_follow();
+            }
+            (KeyCode::Char('n'), KeyModifiers::NONE) | (KeyCode::Char('N'), KeyModifiers::SHIFT) if active_tab == Tab::History && self.git_enabled => {
+                self.open_note_edit();
+            }
+            (KeyCode::Char('u'), KeyModifiers::NONE) | (KeyCode::Char('U'), KeyModifiers::SHIFT) if active_tab == Tab::History && self.git_enabled => {
+                self.sync_notes(true);
+            }
+            (KeyCode::Char('g'), KeyModifiers::NONE) | (KeyCode::Char('G'), KeyModifiers::SHIFT) if active_tab == Tab::History && self.git_enabled => {
+                self.sync_notes(false);
+            }
+            (KeyCode::Char('l'), KeyModifiers::NONE) | (KeyCode::Char('L'), KeyModifiers::SHIFT) if active_tab == Tab::History && self.git_enabled => {
+                self.open_reflog();
+            }
+            (KeyCode::Char('z'), KeyModifiers::NONE) | (KeyCode::Char('Z'), KeyModifiers::SHIFT) if active_tab == Tab::History && self.git_enabled => {
+                self.request_undo();
+            }
+            (KeyCode::Char('k'), KeyModifiers::NONE) | (KeyCode::Char('K'), KeyModifiers::SHIFT) if active_tab == Tab::History && self.git_enabled => {
+                self.request_cherry_pick_from_history();
+            }
+            (KeyCode::Char('x'), KeyModifiers::NONE) | (KeyCode::Char('X'), KeyModifiers::SHIFT) if active_tab == Tab::History && self.git_enabled => {
+                self.request_reset_from_history();
+            }
+            (KeyCode::Char('i'), KeyModifiers::NONE) | (KeyCode::Char('I'), KeyModifiers::SHIFT) if active_tab == Tab::History && self.git_enabled => {
+                self.request_rebase_plan();
+            }
+            // Branches tab operations
+            (KeyCode::Up, _) if active_tab == Tab::Branches && self.git_enabled => {
+                self.branches_navigate(-1);
+            }
+            (KeyCode::Down, _) if active_tab == Tab::Branches && self.git_enabled => {
+                self.branches_navigate(1);
+            }
+            (KeyCode::PageUp, _) if active_tab == Tab::Branches && self.git_enabled => {
+                self.branches_navigate(-20);
+            }
+            (KeyCode::PageDown, _) if active_tab == Tab::Branches && self.git_enabled => {
+                self.branches_navigate(20);
+            }
+            (KeyCode::Enter, _) if active_tab == Tab::Branches && self.git_enabled => {
+                self.open_branch_action();
+            }
+            (KeyCode::Char('n'), KeyModifiers::NONE) | (KeyCode::Char('N'), KeyModifiers::SHIFT)
+                if active_tab == Tab::Branches && self.git_enabled =>
+            {
+                self.open_branch_create();
+            }
+            (KeyCode::Char('d'), KeyModifiers::NONE) | (KeyCode::Char('D'), KeyModifiers::SHIFT) | (KeyCode::Delete, _)
+                if active_tab == Tab::Branches && self.git_enabled =>
+            {
+                self.request_branch_delete();
+            }
+            (KeyCode::Char('k'), KeyModifiers::NONE) | (KeyCode::Char('K'), KeyModifiers::SHIFT)
+                if active_tab == Tab::Branches && self.git_enabled =>
+            {
+                self.request_cherry_pick_from_branches();
+            }
+            (KeyCode::Char('h'), KeyModifiers::NONE) | (KeyCode::Char('H'), KeyModifiers::SHIFT)
+                if active_tab == Tab::Branches && self.git_enabled && self.tab_bar.contains(Tab::History) =>
+            {
+                // The branch's commits, to pick from with K
+                if let Some(branch) = self.selected_branch().filter(|b| !b.is_head) {
+                    let name = branch.name.clone();
+                    self.show_branch_history(name);
+                    self.switch_tab(Tab::History);
+                }
+            }
+            (KeyCode::Char('r'), KeyModifiers::NONE) | (KeyCode::Char('R'), KeyModifiers::SHIFT)
+                if active_tab == Tab::Branches && self.git_enabled =>
+            {
+                self.branches.loaded = false;
+            }
+            // Tags tab operations
+            (KeyCode::Up, _) if active_tab == Tab::Tags && self.git_enabled => {
+                self.tags_navigate(-1);
+            }
+            (KeyCode::Down, _) if active_tab == Tab::Tags && self.git_enabled => {
+                self.tags_navigate(1);
+            }
+            (KeyCode::PageUp, _) if active_tab == Tab::Tags && self.git_enabled => {
+                self.tags_navigate(-20);
+            }
+            (KeyCode::PageDown, _) if active_tab == Tab::Tags && self.git_enabled => {
+                self.tags_navigate(20);
+            }
+            (KeyCode::Char('n'), KeyModifiers::NONE) | (KeyCode::Char('N'), KeyModifiers::SHIFT)
+                if active_tab == Tab::Tags && self.git_enabled =>
+            {
+                self.open_tag_create();
+            }
+            (KeyCode::Char('d'), KeyModifiers::NONE) | (KeyCode::Char('D'), KeyModifiers::SHIFT) | (KeyCode::Delete, _)
+                if active_tab == Tab::Tags && self.git_enabled =>
+            {
+                self.request_tag_delete();
+            }
+            (KeyCode::Char('p'), KeyModifiers::NONE) if active_tab == Tab::Tags && self.git_enabled => {
+                self.push_tags(false);
+            }
+            (KeyCode::Char('P'), KeyModifiers::SHIFT) if active_tab == Tab::Tags && self.git_enabled => {
+                self.push_tags(true);
+            }
+            (KeyCode::Char('r'), KeyModifiers::NONE) | (KeyCode::Char('R'), KeyModifiers::SHIFT)
+                if active_tab == Tab::Tags && self.git_enabled =>
+            {
+                self.tags.loaded = false;
+            }
+            // Activity tab operations
+            (KeyCode::Up, _) if active_tab == Tab::Activity && self.git_enabled => {
+                self.activity_navigate_up();
+            }
+            (KeyCode::Down, _) if active_tab == Tab::Activity && self.git_enabled => {
+                self.activity_navigate_down();
+            }
+            (KeyCode::Char('e'), _) | (KeyCode::Char('E'), _) if active_tab == Tab::Activity && self.git_enabled => {
+                self.export_activity_log();
+            }
+            // Update tab operations
+            (KeyCode::Char('a'), KeyModifiers::NONE) | (KeyCode::Char('A'), KeyModifiers::SHIFT) if active_tab == Tab::Update && self.git_enabled => {
+                // Abort a merge or rebase that stopped part-way
+                self.request_abort();
+            }
+            (KeyCode::Char('p'), KeyModifiers::NONE) if active_tab == Tab::Update && self.git_enabled => {
+                // Pull operation
+                self.request_pull();
+            }
+            (KeyCode::Char('P'), KeyModifiers::NONE) if active_tab == Tab::Update && self.git_enabled => {
+                // Pull operation (uppercase)
+                self.request_pull();
+            }
+            (KeyCode::Char('u'), KeyModifiers::NONE) if active_tab == Tab::Update && self.git_enabled => {
+                // Push operation
+                self.request_push();
+            }
+            (KeyCode::Char('U'), KeyModifiers::NONE) if active_tab == Tab::Update && self.git_enabled => {
+                // Push operation (uppercase)
+                self.request_push();
+            }
+            (KeyCode::Char('f'), KeyModifiers::NONE) | (KeyCode::Char('F'), KeyModifiers::SHIFT)
+                if active_tab == Tab::Update && self.git_enabled =>
+            {
+                // Fetch every configured remote
+                self.fetch_all_remotes();
+            }
+            (KeyCode::Char('s'), KeyModifiers::NONE) | (KeyCode::Char('S'), KeyModifiers::SHIFT)
+                if active_tab == Tab::Update && self.git_enabled && self.update_fork.is_some() =>
+            {
+                // Sync fork's default branch with upstream
+                self.perform_fork_sync();
+            }
+            (KeyCode::Char('c'), KeyModifiers::NONE) | (KeyCode::Char('C'), KeyModifiers::SHIFT)
+                if active_tab == Tab::Update && self.git_enabled =>
+            {
+                // Resolve the conflicts of a stopped merge
+                self.open_conflicts();
+            }
+            (KeyCode::Char('w'), KeyModifiers::NONE) | (KeyCode::Char('W'), KeyModifiers::SHIFT)
+                if active_tab == Tab::Update && self.git_enabled =>
+            {
+                // Show the current branch on the forge
+                self.open_forge_page(super::pull_requests::ForgePage::Branch);
+            }
+            (KeyCode::Char('l'), KeyModifiers::NONE) | (KeyCode::Char('L'), KeyModifiers::SHIFT)
+                if active_tab == Tab::Update && self.git_enabled =>
+            {
+                // List the forge's open pull requests
+                self.open_pull_requests();
+            }
+            (KeyCode::Char('o'), KeyModifiers::NONE) | (KeyCode::Char('O'), KeyModifiers::SHIFT)
+                if active_tab == Tab::Update && self.git_enabled =>
+            {
+                // Write a pull request for the current branch
+                self.open_pull_request_draft();
+            }
+            (KeyCode::Char('r'), KeyModifiers::SHIFT) if active_tab == Tab::Update && self.git_enabled => {
+                // Refresh remote status
+                self.refresh_update_remote_status();
+            }
+            (KeyCode::Char('R'), KeyModifiers::SHIFT) if active_tab == Tab::Update && self.git_enabled => {
+                // Refresh remote status (uppercase)
+                self.refresh_update_remote_status();
+            }
+            (KeyCode::Char('t'), KeyModifiers::NONE) | (KeyCode::Char('T'), KeyModifiers::SHIFT)
+                if matches!(active_tab, Tab::History | Tab::Overview | Tab::Update) && self.git_enabled =>
+            {
+                // Cycle between relative, absolute and ISO times
+                self.time_format = self.time_format.next();
+            }
+            _ => {}
+        }
+        Command::None
+    }
+
+    /// Make `next` the active tab, running the leave/enter hooks of the tabs involved
+    pub(super) fn switch_tab(&mut self, next: Tab) {
+        if self.active_tab == next {
+            return;
+        }
+        self.remember_tab_focus(self.active_tab);
+        // Pick up branch and history changes made since the snapshot was read
+        self.invalidate_repo_snapshot();
+        // Invalidate save changes git status cache and keep the commit draft when leaving save changes tab
+        if self.active_tab == Tab::SaveChanges {
+            self.invalidate_save_changes_git_status();
+            self.save_commit_draft();
+        }
+        // Load update tab data when entering update tab
+        if next == Tab::Update {
+            self.load_update_tab();
+        }
+        // Pick up new commits when entering the History tab
+        if next == Tab::History {
+            self.reload_history_if_moved();
+        }
+        // Re-read what is committed and pushed here and in nested repositories
+        if next == Tab::Overview {
+            self.start_recursive_scan();
+        }
+        // Branches may have moved or been made outside gitix
+        if next == Tab::Branches {
+            self.branches.loaded = false;
+        }
+        if next == Tab::Tags {
+            self.tags.loaded = false;
+        }
+        // Re-read the working tree when entering a tab that shows it
+        if next == Tab::Status || next == Tab::Files {
+            self.invalidate_status_git_status();
+        }
+        self.active_tab = next;
+        self.restore_tab_focus(next);
+    }
+
+    /// Esc back-navigation (`AppMessage::Back`), one level per press
+    ///
+    /// Closes the topmost popup, otherwise moves focus out of a text input, otherwise
+    /// returns the Save Changes tab to its file list; anything else is a no-op.
+    fn back(&mut self) {
+        let active_tab = self.active_tab;
+        // Popups, topmost first
+        if self.credential_prompt.is_some() {
+            self.cancel_credentials();
+        } else if self.show_error_popup {
+            self.hide_error();
+        } else if self.quit_confirm.is_some() {
+            self.quit_confirm = None;
+        } else if self.init_setup.is_some() {
+            // Keep the repository exactly as `git init` left it
+            self.init_setup = None;
+        } else if let Some(setup) = &self.publish {
+            // Closing mid-request would leave the new repository without an origin pointing at it
+            if setup.rx.is_none() {
+                self.publish = None;
+            }
+        } else if self.hook_output.is_some() {
+            self.close_hook_output();
+        } else if self.abort_confirm.is_some() {
+            self.abort_confirm = None;
+        } else if self.cherry_pick_confirm.is_some() {
+            self.cherry_pick_confirm = None;
+        } else if self.format_report.is_some() {
+            self.format_report = None;
+        } else if self.reset_confirm.is_some() {
+            self.reset_confirm = None;
+        } else if self.rebase_plan.is_some() {
+            self.rebase_plan = None;
+        } else if self.conflicts.is_some() {
+            self.conflicts = None;
+        } else if let Some(draft) = &self.pull_request_draft {
+            // A submission in flight finishes in the popup
+            if draft.rx.is_none() {
+                self.pull_request_draft = None;
+            }
+        } else if self.pull_requests.is_some() {
+            self.pull_requests = None;
+        } else if self.explain.is_some() {
+            self.explain = None;
+        } else if self.tour.is_some() {
+            self.finish_tour();
+        } else if active_tab == Tab::Settings && self.show_hook_template_popup {
+            self.show_hook_template_popup = false;
+        } else if active_tab == Tab::Settings && self.remote_edit.is_some() {
+            self.remote_edit = None;
+        } else if active_tab == Tab::Settings && self.remote_remove.is_some() {
+            self.remote_remove = None;
+        } else if active_tab == Tab::Overview && self.nested_view.is_some() {
+            self.nested_view = None;
+        } else if active_tab == Tab::Overview && self.language_view.is_some() {
+            self.language_view = None;
+        } else if active_tab == Tab::Overview && self.churn_view.is_some() {
+            self.churn_view = None;
+        } else if active_tab == Tab::History && self.undo_confirm.is_some() {
+            self.undo_confirm = None;
+        } else if active_tab == Tab::History && self.reflog_view.is_some() {
+            self.reflog_view = None;
+        } else if active_tab == Tab::History && self.history.message_scroll.is_some() {
+            self.history.message_scroll = None;
+        } else if active_tab == Tab::Branches && self.branch_create.is_some() {
+            self.branch_create = None;
+        } else if active_tab == Tab::Branches && self.branch_action.is_some() {
+            self.branch_action = None;
+        } else if active_tab == Tab::Branches && self.merge_draft.is_some() {
+            self.merge_draft = None;
+        } else if active_tab == Tab::Branches && self.branch_delete.is_some() {
+            self.branch_delete = None;
+        } else if active_tab == Tab::Tags && self.tag_create.is_some() {
+            self.tag_create = None;
+        } else if active_tab == Tab::Tags && self.tag_delete.is_some() {
+            self.tag_delete = None;
+        } else if active_tab == Tab::History && self.history_filter_edit.is_some() {
+            self.history_filter_edit = None;
+        } else if active_tab == Tab::History && self.note_edit.is_some() {
+            self.note_edit = None;
+        } else if active_tab == Tab::Overview && self.day_commits.is_some() {
+            self.day_commits = None;
+        } else if active_tab == Tab::Files && self.files_delete_confirm.is_some() {
+            self.files_delete_confirm = None;
+        } else if active_tab == Tab::Files && self.blame_view.is_some() {
+            self.blame_view = None;
+        } else if active_tab == Tab::Files && self.flagged_files_view.is_some() {
+            self.flagged_files_view = None;
+        } else if active_tab == Tab::Update && self.sync_confirm.is_some() {
+            self.sync_confirm = None;
+        } else if active_tab == Tab::SaveChanges && self.amend_confirm {
+            self.amend_confirm = false;
+        } else if active_tab == Tab::SaveChanges && self.discard_confirm.is_some() {
+            self.discard_confirm = None;
+        } else if active_tab == Tab::SaveChanges && self.review_note_input.is_some() {
+            self.review_note_input = None;
+        } else if active_tab == Tab::SaveChanges && self.review_notes_view.is_some() {
+            self.review_notes_view = None;
+        } else if active_tab == Tab::SaveChanges && self.backup_browser.is_some() {
+            self.backup_browser_back();
+        } else if active_tab == Tab::SaveChanges && self.stage_pattern.is_some() {
+            self.stage_pattern = None;
+        } else if let (Tab::SaveChanges, Some(preview)) = (active_tab, self.clean_preview.as_mut()) {
+            // Step back from the final confirmation to the preview
+            if preview.confirming {
+                preview.confirming = false;
+            } else {
+                self.clean_preview = None;
+            }
+        } else if let (Tab::SaveChanges, Some(detail)) = (active_tab, self.file_detail.as_mut()) {
+            if detail.confirm_discard {
+                detail.confirm_discard = false;
+            } else {
+                self.file_detail = None;
+            }
+        } else if active_tab == Tab::SaveChanges && self.show_commit_help {
+            self.show_commit_help = false;
+        } else if active_tab == Tab::SaveChanges && self.show_template_popup {
+            self.show_template_popup = false;
+        } else if active_tab == Tab::Settings && self.is_text_input_focused() {
+            // Leave the author fields for the next panel, which has no text input
+            self.settings_focus = crate::app::SettingsFocus::Theme;
+        } else if active_tab == Tab::SaveChanges && self.save_changes_focus == SaveChangesFocus::Review {
+            self.save_changes_focus = SaveChangesFocus::HunkView;
+            self.hunk_view_navigate(0);
+        } else if active_tab == Tab::SaveChanges && self.save_changes_focus != SaveChangesFocus::FileList {
+            // Both the commit message and the hunk view step back to the file list
+            self.save_changes_focus = SaveChangesFocus::FileList;
+        }
+    }
+
+    /// Whether a popup covers the tab, so clicks shouldn't reach what is under it
+    ///
+    /// Mirrors what `AppMessage::Back` would close.
+    pub fn has_popup(&self) -> bool {
+        let global = self.credential_prompt.is_some()
+            || self.show_error_popup
+            || self.show_init_prompt
+            || self.quit_confirm.is_some()
+            || self.init_setup.is_some()
+            || self.publish.is_some()
+            || self.hook_output.is_some()
+            || self.conflicts.is_some()
+            || self.abort_confirm.is_some()
+            || self.cherry_pick_confirm.is_some()
+            || self.format_report.is_some()
+            || self.reset_confirm.is_some()
+            || self.rebase_plan.is_some()
+            || self.pull_request_draft.is_some()
+            || self.pull_requests.is_some()
+            || self.explain.is_some()
+            || self.tour.is_some();
+        global
+            || match self.active_tab {
+                Tab::Overview => {
+                    self.nested_view.is_some()
+                        || self.language_view.is_some()
+                        || self.churn_view.is_some()
+                        || self.day_commits.is_some()
+                }
+                Tab::Files => {
+                    self.files_delete_confirm.is_some()
+                        || self.flagged_files_view.is_some()
+                        || self.blame_view.is_some()
+                }
+                Tab::History => {
+                    self.history.message_scroll.is_some()
+                        || self.history_filter_edit.is_some()
+                        || self.note_edit.is_some()
+                        || self.reflog_view.is_some()
+                        || self.undo_confirm.is_some()
+                }
+                Tab::Branches => {
+                    self.branch_create.is_some()
+                        || self.branch_delete.is_some()
+                        || self.branch_action.is_some()
+                        || self.merge_draft.is_some()
+                }
+                Tab::Tags => self.tag_create.is_some() || self.tag_delete.is_some(),
+                Tab::Update => self.sync_confirm.is_some(),
+                Tab::SaveChanges => {
+                    self.amend_confirm
+                        || self.discard_confirm.is_some()
+                        || self.review_note_input.is_some()
+                        || self.review_notes_view.is_some()
+                        || self.backup_browser.is_some()
+                        || self.clean_preview.is_some()
+                        || self.stage_pattern.is_some()
+                        || self.file_detail.is_some()
+                        || self.show_commit_help
+                        || self.show_template_popup
+                }
+                Tab::Settings => {
+                    self.show_hook_template_popup
+                        || self.remote_edit.is_some()
+                        || self.remote_remove.is_some()
+                }
+                Tab::Status | Tab::Activity => false,
+            }
+    }
+
+    /// Whether keystrokes on the active tab are going into a text field
+    fn is_text_input_focused(&self) -> bool {
+        match self.active_tab {
+            Tab::SaveChanges => {
+                self.git_enabled
+                    && self.save_changes_focus == SaveChangesFocus::CommitMessage
+                    && !self.show_commit_help
+                    && !self.show_template_popup
+            }
+            Tab::Settings => {
+                self.git_enabled && self.settings_focus == crate::app::SettingsFocus::Author
+            }
+            _ => false,
+        }
+    }
+
+    /// Hand a key to whichever text input has focus (see `is_text_input_focused`)
+    fn type_into_focused_input(&mut self, key_event: KeyEvent) {
+        match self.active_tab {
+            Tab::SaveChanges => {
+                // Use TextArea's built-in input handling for full text editing support
+                self.commit_message.input(Event::Key(key_event));
+                self.wrap_commit_body();
+                self.commit_draft_restored = false;
+            }
+            Tab::Settings => {
+                match self.settings_author_focus {
+                    crate::app::AuthorFocus::Name => self.user_name_input.input(Event::Key(key_event)),
+                    crate::app::AuthorFocus::Email => self.user_email_input.input(Event::Key(key_event)),
+                };
+                if self.settings_status_message.is_some() {
+                    self.settings_status_message = None;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Keys that produce text: plain or shifted characters, including space
+fn is_printable(key_event: &KeyEvent) -> bool {
+    matches!(key_event.code, KeyCode::Char(_))
+        && (key_event.modifiers - KeyModifiers::SHIFT).is_empty()
+}
+
+/// Open a changed file in $EDITOR at its first changed line
+fn edit_at_first_change(path: PathBuf) -> Command {
+    let line = crate::git::first_changed_line(&path.display().to_string()).ok().flatten();
+    Command::Edit {
+        path,
+        line,
+        then: AppMessage::FileEdited,
+    }
+}
@@ -1,672 +1,529 @@
+mod activity;
+mod backups;
+mod blame;
+mod branches;
+mod cherry_pick;
+mod commit_message;
+mod conflicts;
+mod credentials;
+mod explain;
 mod files;
+mod format_report;
+mod history;
+mod hook_output;
+mod init_setup;
+pub mod message;
+mod mouse;
 mod overview;
+mod publish;
+mod pull_request_draft;
+mod pull_requests;
+mod rebase_plan;
+mod reflog;
+mod reset;
+mod review;
 mod save_changes;
 mod settings;
+mod status;
+mod tags;
+pub mod tabs;
+pub mod text;
 pub mod theme;
+mod tour;
 mod update;
+pub mod workspace;
 
 use crate::app::{AppState, SaveChangesFocus};
-use crate::git::get_git_status;
+use crate::tui::message::{AppMessage, Command};
+use crate::tui::tabs::{Tab, TabRegistry};
 use crate::tui::theme::Theme;
+use crossterm::event::{
+    DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture,
+};
 use crossterm::terminal::{
     EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
 };
-use ratatui::Terminal;
+use ratatui::{Frame, Terminal};
 use ratatui::backend::CrosstermBackend;
-use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
-use ratatui::text::Line;
+use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph, Tabs};
-use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::crossterm::event::{self, Event, KeyEventKind};
 use std::io;
 
-const TAB_TITLES: [&str; 5] = [
-    "Overview",
-    "Files",
-    "Save Changes",
-    "Update",
-    "Settings",
-];
-
-#[derive(Copy, Clone, Debug)]
-enum Tab {
-    Overview,
-    Files,
-    SaveChanges,
-    Update,
-    Settings,
-}
+pub fn start_tui(state: &mut AppState, initial_tab: Option<Tab>) {
+    enable_raw_mode().unwrap();
+    let mut stdout = io::stdout();
+    crossterm::execute!(stdout, EnterAlternateScreen, EnableFocusChange, EnableMouseCapture).unwrap();
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend).unwrap();
 
-impl Tab {
-    fn all() -> &'static [Tab] {
-        use Tab::*;
-        &[Overview, Files, SaveChanges, Update, Settings]
+    state.tab_bar = TabRegistry::load();
+    state.active_tab = state.tab_bar.first(state.git_enabled);
+    // A tab picked with --tab, if it is shown and usable here
+    if let Some(tab) = initial_tab {
+        state.update(AppMessage::SwitchTab(tab));
     }
-    fn as_usize(self) -> usize {
-        self as usize
+    state.restore_commit_draft();
+    state.load_review_notes();
+    state.load_sync_history();
+    state.start_update_check();
+    state.credential_requests = Some(crate::credentials::install_prompter());
+    state.start_recursive_scan();
+    state.maybe_start_tour();
+
+    'events: loop {
+        terminal.draw(|f| draw(f, state)).unwrap();
+        state.update(AppMessage::Tick);
+
+        if !event::poll(std::time::Duration::from_millis(100)).unwrap() {
+            continue;
+        }
+        let message = match event::read().unwrap() {
+            Event::Key(key_event) if key_event.kind == KeyEventKind::Press => AppMessage::Key(key_event),
+            Event::Mouse(mouse) => {
+                let size = terminal.size().unwrap_or_default();
+                AppMessage::Mouse(mouse, Rect::new(0, 0, size.width, size.height))
+            }
+            Event::FocusGained => AppMessage::Focus(true),
+            Event::FocusLost => AppMessage::Focus(false),
+            _ => continue,
+        };
+
+        // Carry out what needs the terminal, handing each result back as the next message
+        let mut command = state.update(message);
+        loop {
+            command = match command {
+                Command::None => break,
+                Command::Quit => break 'events,
+                Command::Edit { path, line, then } => {
+                    run_editor(&mut terminal, &path, line);
+                    state.update(then)
+                }
+                Command::Mergetool(path) => {
+                    let result = run_mergetool(&mut terminal, &path);
+                    state.update(AppMessage::MergetoolExited(path, result))
+                }
+            };
+        }
     }
+
+    // Keep an unfinished commit message for the next session
+    state.save_commit_draft();
+
+    // Restore terminal
+    disable_raw_mode().unwrap();
+    crossterm::execute!(io::stdout(), DisableMouseCapture, DisableFocusChange, LeaveAlternateScreen).unwrap();
 }
 
-pub fn start_tui(state: &mut AppState) {
-    let theme = Theme::new();
+/// Draw one frame: the tab bar, the active tab with its popups, and the status bar
+fn draw(f: &mut Frame, state: &mut AppState) {
+    let active_tab = state.active_tab;
+    let size = f.size();
+    
+    // Create theme with current settings for live preview
+    let theme = Theme::with_accents_and_title(
+        state.current_theme_accent,
+        state.current_theme_accent2,
+        state.current_theme_accent3,
+        state.current_theme_title,
+    );
+    
+    // Tabs record their clickable tables as they are drawn
+    state.mouse_targets.clear();
 
-    enable_raw_mode().unwrap();
-    let mut stdout = io::stdout();
-    crossterm::execute!(stdout, EnterAlternateScreen).unwrap();
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend).unwrap();
+    // Set main background
+    f.render_widget(
+        Block::default().style(theme.main_background_style()),
+        size
+    );
+    
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints(
+            [
+                Constraint::Length(3), // Tab bar
+                Constraint::Min(1),    // Main area
+                Constraint::Length(2), // Key hints (status bar)
+            ]
+            .as_ref(),
+        )
+        .split(size);
 
-    let mut active_tab = 0;
-    let tab_count = TAB_TITLES.len();
+    // Tab bar with semantic theme colors
+    let tab_titles: Vec<Line> = state.tab_bar.tabs().iter().map(|tab| {
+        if !state.git_enabled && tab.requires_git() {
+            Line::styled(tab.title(), theme.disabled_tab_style())
+        } else if active_tab == *tab {
+            Line::styled(tab.title(), theme.active_tab_style())
+        } else {
+            Line::styled(tab.title(), theme.inactive_tab_style())
+        }
+    }).collect();
+    let mut title = vec![
+        Span::styled("GIT-iX", Style::default().fg(theme.maroon)),
+        Span::styled(
+            format!(" v{}", crate::release::CURRENT_VERSION),
+            theme.muted_text_style(),
+        ),
+    ];
+    if state.read_only {
+        title.push(Span::styled(" read-only", theme.warning_style()));
+    }
+    let mut tab_block = Block::default()
+        .borders(Borders::ALL)
+        .title(Line::from(title))
+        .border_style(theme.border_style())
+        .style(theme.secondary_background_style()); // Mantle background for tab panel
+    if let Some(release) = &state.update_available {
+        tab_block = tab_block.title(
+            Line::styled(
+                format!(" v{} available: {} ", release.version, release.url),
+                theme.muted_text_style(),
+            )
+            .right_aligned(),
+        );
+    }
+    let tabs = Tabs::new(tab_titles)
+        .select(state.tab_bar.position(active_tab).unwrap_or(0))
+        .block(tab_block)
+        .style(theme.text_style());
+    f.render_widget(tabs, chunks[0]);
 
-    loop {
-        terminal
-            .draw(|f| {
-                let size = f.size();
-                
-                // Create theme with current settings for live preview
-                let theme = Theme::with_accents_and_title(
-                    state.current_theme_accent,
-                    state.current_theme_accent2,
-                    state.current_theme_accent3,
-                    state.current_theme_title,
-                );
-                
-                // Set main background
-                f.render_widget(
-                    Block::default().style(theme.main_background_style()),
-                    size
-                );
-                
-                let chunks = Layout::default()
-                    .direction(Direction::Vertical)
-                    .margin(1)
-                    .constraints(
-                        [
-                            Constraint::Length(3), // Tab bar
-                            Constraint::Min(1),    // Main area
-                            Constraint::Length(2), // Key hints (status bar)
-                        ]
-                        .as_ref(),
-                    )
-                    .split(size);
-
-                // Tab bar with semantic theme colors
-                let tab_titles: Vec<Line> = TAB_TITLES.iter().enumerate().map(|(i, t)| {
-                    if !state.git_enabled && i > 1 {
-                        Line::styled(*t, theme.disabled_tab_style())
-                    } else if active_tab == i {
-                        Line::styled(*t, theme.active_tab_style())
-                    } else {
-                        Line::styled(*t, theme.inactive_tab_style())
-                    }
-                }).collect();
-                let tabs = Tabs::new(tab_titles)
-                    .select(active_tab)
-                    .block(
-                        Block::default()
-                            .borders(Borders::ALL)
-                            .title("GIT-iX")
-                            .title_style(Style::default().fg(theme.maroon))
-                            .border_style(theme.border_style())
-                            .style(theme.secondary_background_style()) // Mantle background for tab panel
-                    )
-                    .style(theme.text_style());
-                f.render_widget(tabs, chunks[0]);
-
-                // Main area: delegate to tab modules
-                match active_tab {
-                    0 => overview::render_overview_tab(f, chunks[1], state),
-                    1 => files::render_files_tab(f, chunks[1], state),
-                    2 => save_changes::render_save_changes_tab(f, chunks[1], state),
-                    3 => update::render_update_tab(f, chunks[1], state),
-                    4 => settings::render_settings_tab(f, chunks[1], state),
-                    _ => {}
-                }
+    // Main area: delegate to tab modules
+    match active_tab {
+        Tab::Overview => overview::render_overview_tab(f, chunks[1], state),
+        Tab::Files => files::render_files_tab(f, chunks[1], state),
+        Tab::Status => status::render_status_tab(f, chunks[1], state),
+        Tab::SaveChanges => save_changes::render_save_changes_tab(f, chunks[1], state),
+        Tab::History => history::render_history_tab(f, chunks[1], state),
+        Tab::Branches => branches::render_branches_tab(f, chunks[1], state),
+        Tab::Tags => tags::render_tags_tab(f, chunks[1], state),
+        Tab::Update => update::render_update_tab(f, chunks[1], state),
+        Tab::Activity => activity::render_activity_tab(f, chunks[1], state),
+        Tab::Settings => settings::render_settings_tab(f, chunks[1], state),
+    }
 
-                // Modal popup for git init prompt with proper semantic styling
-                if active_tab == 0 && state.show_init_prompt {
-                    let area = centered_rect(60, 7, size);
-                    let modal = Paragraph::new("This folder is not a Git repository.\n\nInitialize a new Git repository here? (Y/N)")
-                        .alignment(ratatui::layout::Alignment::Center)
-                        .style(theme.text_style())
-                        .block(
-                            Block::default()
-                                .title("Initialize Git Repository")
-                                .title_style(theme.title_style())
-                                .borders(Borders::ALL)
-                                .border_style(theme.focused_border_style()) // Accent color for focus
-                                .style(theme.secondary_background_style()), // Mantle background
-                        );
-                    f.render_widget(modal, area);
-                }
+    // Guided tour step, over the tab it describes
+    if state.tour.is_some() {
+        tour::render_tour_popup(f, chunks[1], state, &theme);
+    }
 
-                // Error popup modal
-                if state.show_error_popup {
-                    let area = centered_rect(70, 10, size);
-                    let error_text = format!("{}\n\nPress [Enter] or [Esc] to close", state.error_popup_message);
-                    let modal = Paragraph::new(error_text)
-                        .alignment(ratatui::layout::Alignment::Left)
-                        .wrap(ratatui::widgets::Wrap { trim: true })
-                        .style(theme.text_style())
-                        .block(
-                            Block::default()
-                                .title(state.error_popup_title.as_str())
-                                .title_style(theme.title_style())
-                                .borders(Borders::ALL)
-                                .border_style(theme.error_style()) // Red border for errors
-                                .style(theme.secondary_background_style()), // Mantle background
-                        );
-                    f.render_widget(modal, area);
-                }
+    // Plain-language explanation of the repository state
+    if state.explain.is_some() {
+        explain::render_explain_popup(f, size, state, &theme);
+    }
 
-                // Status bar with key hints (crust background per guidelines)
-                let hints = if state.is_loading {
-                    // Show loading indicator - simplified
-                    "⟳ Loading...".to_string()
-                } else {
-                    match active_tab {
-                        1 => "[Tab] Next Tab  [Shift+Tab] Previous Tab  [↑↓] Navigate  [Enter] Open  [q] Quit",
-                        2 if state.git_enabled && state.show_commit_help => "[Enter] OK  [Esc] Close Help",
-                        2 if state.git_enabled && state.show_template_popup => "[←→] Navigate  [Enter] Apply  [Esc] Cancel",
-                        2 if state.git_enabled => "[Tab] Next Tab  [↑↓] Navigate  [Space] Stage/Unstage  [Enter] Commit  [Shift+?] Help  [Shift+T] Template  [q] Quit",
-                        3 if state.git_enabled => "[Tab] Next Tab  [Shift+Tab] Previous Tab  [Shift+R] Refresh  [P] Pull  [U] Push  [q] Quit",
-                        _ => "[Tab] Next Tab  [Shift+Tab] Previous Tab  [q] Quit",
-                    }.to_string()
-                };
+    // Conflicted files of a stopped merge
+    if state.conflicts.is_some() {
+        conflicts::render_conflicts_popup(f, size, state, &theme);
+    }
 
-                // Create status bar - drop branch info when loading to save space
-                if state.git_enabled && !state.is_loading {
-                    // Build status line with branch info and hints (only when not loading)
-                    let mut status_spans = Vec::new();
-
-                    // Get current branch information when not loading
-                    let (current_branch, remote_branch) = if let (Ok(current), Ok(remote)) = 
-                        (crate::git::get_current_branch(), crate::git::get_current_remote_branch()) {
-                        (Some(current), remote)
-                    } else {
-                        (None, None)
-                    };
-
-                    // Add branch information at the beginning
-                    if let Some(branch) = current_branch {
-                        // Local branch with parentheses
-                        status_spans.push(ratatui::text::Span::styled("(", theme.accent_style()));
-                        status_spans.push(ratatui::text::Span::styled(branch, theme.accent_style()));
-                        status_spans.push(ratatui::text::Span::styled(")", theme.accent_style()));
-
-                        // Add remote branch if available
-                        if let Some(remote) = remote_branch {
-                            // Remove redundant "origin/" prefix if present
-                            let clean_remote = if remote.starts_with("origin/origin/") {
-                                remote.strip_prefix("origin/").unwrap_or(&remote).to_string()
-                            } else {
-                                remote
-                            };
-                            
-                            status_spans.push(ratatui::text::Span::raw(" "));
-                            status_spans.push(ratatui::text::Span::styled("(", theme.accent3_style()));
-                            status_spans.push(ratatui::text::Span::styled(clean_remote, theme.accent3_style()));
-                            status_spans.push(ratatui::text::Span::styled(")", theme.accent3_style()));
-                        }
-
-                        status_spans.push(ratatui::text::Span::raw("  |  "));
-                    }
-
-                    // Add the hints
-                    status_spans.push(ratatui::text::Span::styled(hints, theme.status_bar_style()));
-
-                    let status_line = ratatui::text::Line::from(status_spans);
-                    let hint_paragraph = Paragraph::new(status_line)
-                        .alignment(ratatui::layout::Alignment::Center);
-                    f.render_widget(hint_paragraph, chunks[2]);
-                } else {
-                    // No git or loading - just show hints (simplified when loading)
-                    let hint_paragraph = Paragraph::new(hints)
-                        .alignment(ratatui::layout::Alignment::Center)
-                        .style(if state.is_loading { 
-                            theme.info_style() 
-                        } else { 
-                            theme.status_bar_style() 
-                        });
-                    f.render_widget(hint_paragraph, chunks[2]);
-                }
-            })
-            .unwrap();
+    // Aborting the merge or rebase in progress
+    if let Some(operation) = state.abort_confirm {
+        conflicts::render_abort_popup(f, size, operation, &theme);
+    }
 
-        // Perform any pending refresh work immediately after UI is drawn
-        // This ensures the loading indicator is visible before the blocking operation
-        if state.pending_refresh_work {
-            state.perform_refresh_work();
+    // A reset to a History commit, while its mode is picked
+    if let Some(confirm) = &state.reset_confirm {
+        reset::render_reset_popup(f, size, confirm, &theme);
+    }
+
+    // An interactive rebase while it's planned
+    if let Some(view) = &state.rebase_plan {
+        rebase_plan::render_rebase_plan_popup(f, size, view, &theme);
+    }
+
+    // What the commit formatter changed
+    if let Some(view) = state.format_report.as_mut() {
+        format_report::render_format_report_popup(f, size, view, &theme);
+    }
+
+    // A commit waiting to be cherry-picked
+    if let Some(confirm) = &state.cherry_pick_confirm {
+        cherry_pick::render_cherry_pick_popup(f, size, confirm, &theme);
+    }
+
+    // Open pull requests on the forge
+    if state.pull_requests.is_some() {
+        pull_requests::render_pull_requests_popup(f, size, state, &theme);
+    }
+
+    // A pull request being written for the current branch
+    if state.pull_request_draft.is_some() {
+        pull_request_draft::render_pull_request_draft_popup(f, size, state, &theme);
+    }
+
+    // Modal popup for git init prompt with proper semantic styling
+    if state.show_init_prompt {
+        let area = centered_rect(60, 7, size);
+        let modal = Paragraph::new("This folder is not a Git repository.\n\nInitialize a new Git repository here? (Y/N)")
+            .alignment(ratatui::layout::Alignment::Center)
+            .style(theme.text_style())
+            .block(
+                Block::default()
+                    .title("Initialize Git Repository")
+                    .title_style(theme.title_style())
+                    .borders(Borders::ALL)
+                    .border_style(theme.focused_border_style()) // Accent color for focus
+                    .style(theme.secondary_background_style()), // Mantle background
+            );
+        f.render_widget(modal, area);
+    }
+
+    // Branch name and first commit for a repository just initialized
+    if state.init_setup.is_some() {
+        init_setup::render_init_setup_popup(f, size, state, &theme);
+    }
+
+    // A forge repository for the repository just initialized
+    if state.publish.is_some() {
+        publish::render_publish_popup(f, size, state, &theme);
+    }
+
+    // Hook output popup
+    if state.hook_output.is_some() {
+        hook_output::render_hook_output_popup(f, size, state, &theme);
+    }
+
+    // Quit confirmation modal
+    if let Some(confirm) = &state.quit_confirm {
+        let height = confirm.reasons.len() + confirm.warnings.len() + 6;
+        let area = centered_rect(60, height as u16, size);
+        let mut lines: Vec<Line> = confirm
+            .reasons
+            .iter()
+            .map(|reason| Line::styled(format!("• {}", reason), theme.warning_style()))
+            .collect();
+        lines.extend(
+            confirm
+                .warnings
+                .iter()
+                .map(|warning| Line::styled(format!("• {}", warning), theme.info_style())),
+        );
+        lines.push(Line::from(""));
+        if confirm.warnings.is_empty() {
+            lines.push(Line::styled("Quit anyway? (Y/N)", theme.text_style()));
+        } else {
+            lines.push(Line::styled(
+                "Quit anyway? (Y/N, S to quit and stop warning about unpushed work)",
+                theme.text_style(),
+            ));
         }
+        let modal = Paragraph::new(lines)
+            .alignment(ratatui::layout::Alignment::Center)
+            .wrap(ratatui::widgets::Wrap { trim: true })
+            .block(
+                Block::default()
+                    .title("Quit gitix?")
+                    .title_style(theme.title_style())
+                    .borders(Borders::ALL)
+                    .border_style(theme.warning_style())
+                    .style(theme.secondary_background_style()), // Mantle background
+            );
+        f.render_widget(ratatui::widgets::Clear, area);
+        f.render_widget(modal, area);
+    }
 
-        // Handle input
-        let poll_timeout = if state.is_loading { 
-            std::time::Duration::from_millis(100) // Reasonable timeout for spinner animation
-        } else { 
-            std::time::Duration::from_millis(100) // Normal timeout
-        };
-        
-        if event::poll(poll_timeout).unwrap() {
-            if let Event::Key(key_event) = event::read().unwrap() {
-                if key_event.kind == KeyEventKind::Press {
-                    // If showing error popup, only handle Enter/Esc to close it
-                    if state.show_error_popup {
-                        match key_event.code {
-                            KeyCode::Enter | KeyCode::Esc => {
-                                state.hide_error();
-                            }
-                            _ => {}
-                        }
-                        continue;
-                    }
-
-                    // If showing prompt, only handle Y/N
-                    if active_tab == 0 && state.show_init_prompt {
-                        match key_event.code {
-                            KeyCode::Char('y') | KeyCode::Char('Y') => {
-                                if let Err(e) = state.try_init_repo() {
-                                    // Show user-friendly error popup
-                                    state.show_error(
-                                        "Repository Initialization Failed",
-                                        &format!("Failed to initialize Git repository:\n\n{}", e)
-                                    );
-                                }
-                            }
-                            KeyCode::Char('n') | KeyCode::Char('N') => {
-                                state.decline_init_repo();
-                            }
-                            KeyCode::Char('q') => break,
-                            _ => {}
-                        }
-                        continue;
-                    }
-
-                    // Only allow navigation to enabled tabs
-                    let max_enabled_tab = if state.git_enabled { tab_count - 1 } else { 1 };
-                    match (key_event.code, key_event.modifiers) {
-                        (KeyCode::Tab, KeyModifiers::NONE) => {
-                            let mut next_tab = (active_tab + 1) % tab_count;
-                            while !state.git_enabled && next_tab > 1 {
-                                next_tab = (next_tab + 1) % tab_count;
-                            }
-                            // Invalidate save changes git status cache when leaving save changes tab
-                            if active_tab == 2 && next_tab != 2 {
-                                state.invalidate_save_changes_git_status();
-                            }
-                            // Load update tab data when entering update tab
-                            if next_tab == 3 && active_tab != 3 {
-                                state.load_update_tab();
-                            }
-                            active_tab = next_tab;
-                        }
-                        (KeyCode::BackTab, _) | (KeyCode::Tab, KeyModifiers::SHIFT) => {
-                            let mut prev_tab = (active_tab + tab_count - 1) % tab_count;
-                            while !state.git_enabled && prev_tab > 1 {
-                                prev_tab = (prev_tab + tab_count - 1) % tab_count;
-                            }
-                            // Invalidate save changes git status cache when leaving save changes tab
-                            if active_tab == 2 && prev_tab != 2 {
-                                state.invalidate_save_changes_git_status();
-                            }
-                            // Load update tab data when entering update tab
-                            if prev_tab == 3 && active_tab != 3 {
-                                state.load_update_tab();
-                            }
-                            active_tab = prev_tab;
-                        }
-                        (KeyCode::Char('q'), _) => {
-                            break;
-                        }
-                        (KeyCode::Down, _) if active_tab == 1 => {
-                            // Files tab: move selection down
-                            let add_parent = state.current_dir != state.root_dir;
-                            let files = crate::files::list_files(&state.current_dir, add_parent);
-                            if !files.is_empty() {
-                                state.files_selected_row =
-                                    (state.files_selected_row + 1).min(files.len() - 1);
-                            }
-                        }
-                        (KeyCode::Up, _) if active_tab == 1 => {
-                            // Files tab: move selection up
-                            let add_parent = state.current_dir != state.root_dir;
-                            let files = crate::files::list_files(&state.current_dir, add_parent);
-                            if !files.is_empty() {
-                                state.files_selected_row =
-                                    state.files_selected_row.saturating_sub(1);
-                            }
-                        }
-                        (KeyCode::Enter, _) if active_tab == 1 => {
-                            let add_parent = state.current_dir != state.root_dir;
-                            let files = crate::files::list_files(&state.current_dir, add_parent);
-                            if files.is_empty() {
-                                return;
-                            }
-                            let idx = state.files_selected_row.min(files.len() - 1);
-                            let entry = &files[idx];
-                            if entry.name == ".." && add_parent {
-                                // Go up a directory
-                                if let Some(parent) = state.current_dir.parent() {
-                                    if parent.starts_with(&state.root_dir) {
-                                        state.current_dir = parent.to_path_buf();
-                                        state.files_selected_row = 0;
-                                    }
-                                }
-                            } else if entry.is_dir {
-                                // Go into directory
-                                let mut new_dir = state.current_dir.clone();
-                                new_dir.push(&entry.name);
-                                if new_dir.starts_with(&state.root_dir) && new_dir.is_dir() {
-                                    state.current_dir = new_dir;
-                                    state.files_selected_row = 0;
-                                }
-                            } else {
-                                // Open file in $EDITOR
-                                let mut file_path = state.current_dir.clone();
-                                file_path.push(&entry.name);
-                                if let Ok(editor) = std::env::var("EDITOR") {
-                                    let mut cmd = std::process::Command::new(&editor);
-                                    // Add --wait for VSCode
-                                    if editor.contains("code") {
-                                        cmd.arg("--wait");
-                                    }
-                                    let _ = cmd.arg(&file_path).status();
-                                } else {
-                                    // Fallback to vi
-                                    let _ =
-                                        std::process::Command::new("vi").arg(&file_path).status();
-                                }
-                            }
-                        }
-                        (KeyCode::Down, _) if active_tab == 2 => {
-                            // Save changes tab navigation - only if no popups are shown
-                            if !state.show_commit_help && !state.show_template_popup {
-                                state.save_changes_navigate_down();
-                            } else if state.show_commit_help {
-                                // Scroll down in help popup
-                                state.help_popup_scroll_down();
-                            }
-                        }
-                        (KeyCode::Up, _) if active_tab == 2 => {
-                            // Save changes tab navigation - only if no popups are shown
-                            if !state.show_commit_help && !state.show_template_popup {
-                                state.save_changes_navigate_up();
-                            } else if state.show_commit_help {
-                                // Scroll up in help popup
-                                state.help_popup_scroll_up();
-                            }
-                        }
-                        (KeyCode::Char(' '), _) if active_tab == 2 => {
-                            // Save changes tab: toggle file staging - only if no popups are shown and focus is on file list
-                            if !state.show_commit_help && !state.show_template_popup && state.save_changes_focus == SaveChangesFocus::FileList {
-                                state.toggle_file_staging();
-                            } else if !state.show_commit_help && !state.show_template_popup && state.save_changes_focus == SaveChangesFocus::CommitMessage {
-                                // When focus is on commit message, pass space key to the TextArea input handler
-                                state.commit_message.input(Event::Key(key_event));
-                            }
-                        }
-                        (KeyCode::Enter, _) if active_tab == 2 && state.show_commit_help => {
-                            // Close help popup when Enter is pressed
-                            state.show_commit_help = false;
-                        }
-                        (KeyCode::Esc, _) if active_tab == 2 && state.show_commit_help => {
-                            // Close help popup when Escape is pressed
-                            state.show_commit_help = false;
-                        }
-                        (KeyCode::Enter, _) if active_tab == 2 && state.show_template_popup => {
-                            // Template popup: apply selection
-                            state.apply_template_selection();
-                        }
-                        (KeyCode::Esc, _) if active_tab == 2 && state.show_template_popup => {
-                            // Template popup: close without applying
-                            state.show_template_popup = false;
-                        }
-                        (KeyCode::Left, _) if active_tab == 2 && state.show_template_popup => {
-                            // Template popup: navigate to Yes button
-                            state.template_popup_navigate_left();
-                        }
-                        (KeyCode::Right, _) if active_tab == 2 && state.show_template_popup => {
-                            // Template popup: navigate to No button
-                            state.template_popup_navigate_right();
-                        }
-                        (KeyCode::Enter, _) if active_tab == 2 && !state.show_commit_help && !state.show_template_popup => {
-                            // Save changes tab: commit staged files (only works when in file list and no popups)
-                            if state.save_changes_focus == SaveChangesFocus::FileList {
-                                if let Err(e) = state.commit_staged_files() {
-                                    // Show user-friendly error popup
-                                    state.show_error("Commit Failed", &format!("Failed to commit changes:\n\n{}", e));
-                                }
-                            } else {
-                                // In commit message area, add a new line
-                                state.commit_message.insert_newline();
-                            }
-                        }
-                        (KeyCode::Char('?'), KeyModifiers::SHIFT) if active_tab == 2 && !state.show_commit_help && !state.show_template_popup => {
-                            // Save changes tab: show help popup
-                            state.show_commit_help = true;
-                        }
-                        (KeyCode::Char('T'), KeyModifiers::SHIFT) if active_tab == 2 && !state.show_commit_help && !state.show_template_popup => {
-                            // Save changes tab: show template popup
-                            state.toggle_template_popup();
-                        }
-                        // Handle commit message input when focused on commit message and no popups are shown
-                        _ if active_tab == 2
-                            && !state.show_commit_help
-                            && !state.show_template_popup
-                            && state.save_changes_focus == SaveChangesFocus::CommitMessage =>
-                        {
-                            // Use TextArea's built-in input handling for full text editing support
-                            state.commit_message.input(Event::Key(key_event));
-                        }
-                        // Settings tab key bindings (tab 4)
-                        (KeyCode::Tab, KeyModifiers::NONE) => {
-                            let mut next_tab = (active_tab + 1) % tab_count;
-                            while !state.git_enabled && next_tab > 1 {
-                                next_tab = (next_tab + 1) % tab_count;
-                            }
-                            // Invalidate save changes git status cache when leaving save changes tab
-                            if active_tab == 2 && next_tab != 2 {
-                                state.invalidate_save_changes_git_status();
-                            }
-                            // Load update tab data when entering update tab
-                            if next_tab == 3 && active_tab != 3 {
-                                state.load_update_tab();
-                            }
-                            active_tab = next_tab;
-                        }
-                        (KeyCode::BackTab, _) | (KeyCode::Tab, KeyModifiers::SHIFT) => {
-                            let mut prev_tab = (active_tab + tab_count - 1) % tab_count;
-                            while !state.git_enabled && prev_tab > 1 {
-                                prev_tab = (prev_tab + tab_count - 1) % tab_count;
-                            }
-                            // Invalidate save changes git status cache when leaving save changes tab
-                            if active_tab == 2 && prev_tab != 2 {
-                                state.invalidate_save_changes_git_status();
-                            }
-                            // Load update tab data when entering update tab
-                            if prev_tab == 3 && active_tab != 3 {
-                                state.load_update_tab();
-                            }
-                            active_tab = prev_tab;
-                        }
-                        (KeyCode::Left, KeyModifiers::CONTROL) if active_tab == 4 && state.git_enabled => {
-                            // Settings tab: cycle panels backward
-                            state.settings_focus = match state.settings_focus {
-                                crate::app::SettingsFocus::Author => crate::app::SettingsFocus::Git,
-                                crate::app::SettingsFocus::Theme => crate::app::SettingsFocus::Author,
-                                crate::app::SettingsFocus::Git => crate::app::SettingsFocus::Theme,
-                            };
-                        }
-                        (KeyCode::Right, KeyModifiers::CONTROL) if active_tab == 4 && state.git_enabled => {
-                            // Settings tab: cycle panels forward
-                            state.settings_focus = match state.settings_focus {
-                                crate::app::SettingsFocus::Author => crate::app::SettingsFocus::Theme,
-                                crate::app::SettingsFocus::Theme => crate::app::SettingsFocus::Git,
-                                crate::app::SettingsFocus::Git => crate::app::SettingsFocus::Author,
-                            };
-                        }
-                        (KeyCode::Left, _) if active_tab == 4 && state.git_enabled => {
-                            // Settings tab: cycle theme colors backward (only works in Theme panel) or toggle Git settings
-                            if state.settings_focus == crate::app::SettingsFocus::Theme {
-                                use crate::app::ThemeFocus;
-                                match state.settings_theme_focus {
-                                    ThemeFocus::Accent => {
-                                        state.current_theme_accent = cycle_accent_color_backward(state.current_theme_accent);
-                                    }
-                                    ThemeFocus::Accent2 => {
-                                        state.current_theme_accent2 = cycle_accent_color_backward(state.current_theme_accent2);
-                                    }
-                                    ThemeFocus::Accent3 => {
-                                        state.current_theme_accent3 = cycle_accent_color_backward(state.current_theme_accent3);
-                                    }
-                                    ThemeFocus::Title => {
-                                        state.current_theme_title = cycle_title_color_backward(state.current_theme_title);
-                                    }
-                                }
-                            } else if state.settings_focus == crate::app::SettingsFocus::Git {
-                                // Toggle pull rebase setting
-                                state.pull_rebase = !state.pull_rebase;
-                                // Clear status message when changing settings
-                                if state.settings_status_message.is_some() {
-                                    state.settings_status_message = None;
-                                }
-                            }
-                        }
-                        (KeyCode::Right, _) if active_tab == 4 && state.git_enabled => {
-                            // Settings tab: cycle theme colors forward (only works in Theme panel) or toggle Git settings
-                            if state.settings_focus == crate::app::SettingsFocus::Theme {
-                                use crate::app::ThemeFocus;
-                                match state.settings_theme_focus {
-                                    ThemeFocus::Accent => {
-                                        state.current_theme_accent = cycle_accent_color_forward(state.current_theme_accent);
-                                    }
-                                    ThemeFocus::Accent2 => {
-                                        state.current_theme_accent2 = cycle_accent_color_forward(state.current_theme_accent2);
-                                    }
-                                    ThemeFocus::Accent3 => {
-                                        state.current_theme_accent3 = cycle_accent_color_forward(state.current_theme_accent3);
-                                    }
-                                    ThemeFocus::Title => {
-                                        state.current_theme_title = cycle_title_color_forward(state.current_theme_title);
-                                    }
-                                }
-                            } else if state.settings_focus == crate::app::SettingsFocus::Git {
-                                // Toggle pull rebase setting
-                                state.pull_rebase = !state.pull_rebase;
-                                // Clear status message when changing settings
-                                if state.settings_status_message.is_some() {
-                                    state.settings_status_message = None;
-                                }
-                            }
-                        }
-                        (KeyCode::Up, _) if active_tab == 4 && state.git_enabled => {
-                            match state.settings_focus {
-                                crate::app::SettingsFocus::Author => {
-                                    state.settings_author_focus = crate::app::AuthorFocus::Name;
-                                }
-                                crate::app::SettingsFocus::Theme => {
-                                    use crate::app::ThemeFocus;
-                                    state.settings_theme_focus = match state.settings_theme_focus {
-                                        ThemeFocus::Accent2 => ThemeFocus::Accent,
-                                        ThemeFocus::Accent3 => ThemeFocus::Accent2,
-                                        ThemeFocus::Title => ThemeFocus::Accent3,
-                                        ThemeFocus::Accent => ThemeFocus::Title,
-                                    };
-                                }
-                                crate::app::SettingsFocus::Git => {
-                                    // Only one Git setting for now, so no navigation needed
-                                }
-                            }
-                        }
-                        (KeyCode::Down, _) if active_tab == 4 && state.git_enabled => {
-                            match state.settings_focus {
-                                crate::app::SettingsFocus::Author => {
-                                    state.settings_author_focus = crate::app::AuthorFocus::Email;
-                                }
-                                crate::app::SettingsFocus::Theme => {
-                                    use crate::app::ThemeFocus;
-                                    state.settings_theme_focus = match state.settings_theme_focus {
-                                        ThemeFocus::Accent => ThemeFocus::Accent2,
-                                        ThemeFocus::Accent2 => ThemeFocus::Accent3,
-                                        ThemeFocus::Accent3 => ThemeFocus::Title,
-                                        ThemeFocus::Title => ThemeFocus::Accent,
-                                    };
-                                }
-                                crate::app::SettingsFocus::Git => {
-                                    // Only one Git setting for now, so no navigation needed
-                                }
-                            }
-                        }
-                        (KeyCode::Char('s'), KeyModifiers::CONTROL) if active_tab == 4 && state.git_enabled => {
-                            // Save settings
-                            match state.save_settings() {
-                                Ok(()) => {
-                                    state.settings_status_message = Some("✓ Settings saved successfully".to_string());
-                                }
-                                Err(e) => {
-                                    state.settings_status_message = Some(format!("✗ Failed to save: {}", e));
-                                }
-                            }
-                        }
-                        // Handle author input when in settings tab and author panel
-                        _ if active_tab == 4
-                            && state.git_enabled
-                            && state.settings_focus == crate::app::SettingsFocus::Author =>
-                        {
-                            match state.settings_author_focus {
-                                crate::app::AuthorFocus::Name => {
-                                    state.user_name_input.input(Event::Key(key_event));
-                                    if state.settings_status_message.is_some() {
-                                        state.settings_status_message = None;
-                                    }
-                                }
-                                crate::app::AuthorFocus::Email => {
-                                    state.user_email_input.input(Event::Key(key_event));
-                                    if state.settings_status_message.is_some() {
-                                        state.settings_status_message = None;
-                                    }
-                                }
-                            }
-                        }
-                        // Update tab operations
-                        (KeyCode::Char('p'), KeyModifiers::NONE) if active_tab == 3 && state.git_enabled => {
-                            // Pull operation
-                            state.perform_pull();
-                        }
-                        (KeyCode::Char('P'), KeyModifiers::NONE) if active_tab == 3 && state.git_enabled => {
-                            // Pull operation (uppercase)
-                            state.perform_pull();
-                        }
-                        (KeyCode::Char('u'), KeyModifiers::NONE) if active_tab == 3 && state.git_enabled => {
-                            // Push operation
-                            state.perform_push();
-                        }
-                        (KeyCode::Char('U'), KeyModifiers::NONE) if active_tab == 3 && state.git_enabled => {
-                            // Push operation (uppercase)
-                            state.perform_push();
-                        }
-                        (KeyCode::Char('r'), KeyModifiers::SHIFT) if active_tab == 3 && state.git_enabled => {
-                            // Refresh remote status
-                            state.refresh_update_remote_status();
-                        }
-                        (KeyCode::Char('R'), KeyModifiers::SHIFT) if active_tab == 3 && state.git_enabled => {
-                            // Refresh remote status (uppercase)
-                            state.refresh_update_remote_status();
-                        }
-                        _ => {}
-                    }
-                }
+    // Error popup modal
+    if state.show_error_popup {
+        let area = centered_rect(70, 12, size);
+        let mut error_text = format!(
+            "{}\n\nPress [Enter] or [Esc] to close, [C] to copy a report for a bug report",
+            state.error_popup_message
+        );
+        if let Some(copied) = &state.error_popup_copied {
+            error_text.push_str(&format!("\n{}", copied));
+        }
+        let modal = Paragraph::new(error_text)
+            .alignment(ratatui::layout::Alignment::Left)
+            .wrap(ratatui::widgets::Wrap { trim: true })
+            .style(theme.text_style())
+            .block(
+                Block::default()
+                    .title(state.error_popup_title.as_str())
+                    .title_style(theme.title_style())
+                    .borders(Borders::ALL)
+                    .border_style(theme.error_style()) // Red border for errors
+                    .style(theme.secondary_background_style()), // Mantle background
+            );
+        f.render_widget(modal, area);
+    }
+
+    // Credentials a fetch or push is waiting for, over everything else
+    if let Some(prompt) = state.credential_prompt.as_mut() {
+        credentials::render_credential_popup(f, size, prompt, &theme);
+    }
+
+    // Status bar with key hints (crust background per guidelines)
+    let hints = if let Some(prompt) = &state.credential_prompt {
+        match prompt.request.kind {
+            crate::credentials::CredentialKind::UserPass => "[Tab] Field  [Enter] Sign In  [Esc] Cancel".to_string(),
+            crate::credentials::CredentialKind::Passphrase(_) => "[Enter] Unlock  [Esc] Cancel".to_string(),
+        }
+    } else if state.is_loading {
+        // Spinner, message and any transfer progress from a sync worker
+        state.loading_status()
+    } else if state.tour.is_some() {
+        "[Enter/→] Next  [←] Back  [Esc] Skip Tour".to_string()
+    } else if state.explain.is_some() {
+        "[Enter/Esc] Close".to_string()
+    } else if state.abort_confirm.is_some() {
+        "[Y] Abort  [N/Esc] Keep Going".to_string()
+    } else if state.cherry_pick_confirm.is_some() {
+        "[Y] Cherry-pick  [N/Esc] Cancel".to_string()
+    } else if state.format_report.is_some() {
+        "[↑↓] Scroll  [Esc] Close".to_string()
+    } else if state.reset_confirm.is_some() {
+        "[←→] Mode  [Enter] Reset  [Esc] Cancel".to_string()
+    } else if state.rebase_plan.is_some() {
+        "[↑↓] Select  [Shift+↑↓/K/J] Move  [P/S/F/D] Pick/Squash/Fixup/Drop  [Enter] Rebase  [Esc] Cancel".to_string()
+    } else if state.conflicts.is_some() {
+        "[↑↓] Files  [←→] Ours/Theirs/Base  [O/T] Keep Side  [E] Edit  [M] Mergetool  [R] Mark Resolved  [C] Commit  [A] Abort  [Esc] Close".to_string()
+    } else if state.pull_request_draft.as_ref().is_some_and(|d| d.created.is_some()) {
+        "[Ctrl+O] Open in Browser  [Enter/Esc] Close".to_string()
+    } else if state.pull_request_draft.is_some() {
+        "[Tab] Field  [←→] Base Branch  [Ctrl+S] Create  [Ctrl+O] Open Form in Browser  [Esc] Cancel".to_string()
+    } else if state.pull_requests.is_some() {
+        "[↑↓] Navigate  [Enter] Open  [N] New Pull Request  [W] Open List on the Web  [Esc] Close".to_string()
+    } else {
+        match active_tab {
+            Tab::Files if state.git_enabled && state.blame_view.is_some() => "[↑↓] Line  [PgUp/PgDn] Page  [Home/End] First/Last  [Esc] Close",
+            Tab::Files if state.git_enabled => "[Tab] Next Tab  [Shift+Tab] Previous Tab  [↑↓] Navigate  [Enter] Open  [H] History  [B] Blame  [O] Reveal  [D] Delete  [S] Scope  [W] Skip Worktree  [A] Assume Unchanged  [q] Quit",
+            Tab::Files => "[Tab] Next Tab  [Shift+Tab] Previous Tab  [↑↓] Navigate  [Enter] Open  [O] Reveal  [D] Delete  [S] Scope  [q] Quit",
+            Tab::SaveChanges if state.git_enabled && state.show_commit_help => "[Enter] OK  [Esc] Close Help",
+            Tab::SaveChanges if state.git_enabled && state.backup_browser.as_ref().is_some_and(|b| b.files.is_some()) => "[↑↓] Navigate  [Enter] Restore File  [Y/N] Confirm  [Esc] Back",
+            Tab::SaveChanges if state.git_enabled && state.backup_browser.is_some() => "[↑↓] Navigate  [Enter] Open Snapshot  [S] Snapshot Now  [Esc] Close",
+            Tab::SaveChanges if state.git_enabled && state.discard_confirm.is_some() => "[Y] Discard  [N/Esc] Keep",
+            Tab::SaveChanges if state.git_enabled && state.show_template_popup => "[←→] Navigate  [Enter] Apply  [Esc] Cancel",
+            Tab::SaveChanges if state.git_enabled && state.review_note_input.is_some() => "[Enter] Save Note  [Esc] Cancel",
+            Tab::SaveChanges if state.git_enabled && state.stage_pattern.is_some() => "[Enter] Stage Matches  [↑↓/PgUp/PgDn] Scroll  [Esc] Cancel",
+            Tab::SaveChanges if state.git_enabled && state.review_notes_view.is_some() => "[↑↓] Navigate  [Enter] Go to Line  [D] Delete  [E] Export Markdown  [Esc] Close",
+            Tab::SaveChanges if state.git_enabled && state.save_changes_focus == SaveChangesFocus::HunkView => "[↑↓] Hunk  [Space] Stage/Unstage Hunk  [R] Review Lines  [Shift+N] Review Notes  [PgUp/PgDn] Scroll Diff  [←/Esc] Back to Files",
+            Tab::SaveChanges if state.git_enabled && state.save_changes_focus == SaveChangesFocus::Review => "[↑↓/PgUp/PgDn] Line  [N/Enter] Note  [Shift+N] Review Notes  [←/Esc] Back to Hunks",
+            Tab::SaveChanges if state.git_enabled && state.profile == crate::app::Profile::Beginner => "[Tab] Next Tab  [↑↓] Navigate  [Space] Stage/Unstage  [I] Details  [D] Discard  [P] Stage by Pattern  [V] Diff  [→] Hunks  [Shift+↑↓/PgUp/PgDn] Scroll Diff  [E] Edit  [Enter] Commit  [W] WIP Save  [Shift+W] Resume WIP  [Shift+B] Backups  [Shift+?/F1] Help  [Shift+T/Ctrl+T] Template  [q] Quit",
+            Tab::SaveChanges if state.git_enabled => "[Tab] Next Tab  [↑↓] Navigate  [Space] Stage/Unstage  [I] Details  [D] Discard  [P] Stage by Pattern  [V] Diff  [→] Hunks  [Shift+↑↓/PgUp/PgDn] Scroll Diff  [E] Edit  [Enter] Commit  [W/Shift+W] WIP Save/Resume  [Shift+B] Backups  [Shift+N] Review Notes  [Ctrl+O] Skip Hooks  [Shift+A/F2] Amend  [Shift+?/F1] Help  [Shift+T/Ctrl+T] Template  [q] Quit",
+            Tab::Update if state.git_enabled && state.update_forge.is_some() => "[Tab] Next Tab  [Shift+Tab] Previous Tab  [Shift+R] Refresh  [F] Fetch All  [P] Pull  [U] Push  [W] Web  [L] Pull Requests  [O] Open Pull Request  [T] Times  [q] Quit",
+            Tab::Update if state.git_enabled => "[Tab] Next Tab  [Shift+Tab] Previous Tab  [Shift+R] Refresh  [F] Fetch All  [P] Pull  [U] Push  [T] Times  [q] Quit",
+            Tab::Overview if state.git_enabled && state.nested_view.is_some() => "[↑↓] Navigate  [Esc] Close",
+            Tab::Overview if state.git_enabled && state.language_view.is_some() => "[↑↓] Scroll  [Esc] Close",
+            Tab::Overview if state.git_enabled && state.churn_view.is_some() => "[↑↓] Scroll  [←→] Time Window  [Esc] Close",
+            Tab::Overview if state.git_enabled && state.day_commits.is_some() => "[↑↓] Navigate  [Enter] Details  [H] Open in History  [Esc] Close",
+            Tab::Overview if state.git_enabled => "[Tab] Next Tab  [Shift+Tab] Previous Tab  [←→↑↓] Pick Day  [Enter] Day's Commits  [G] Bars  [R] Range  [M] Merges  [B] Bots  [H] Hotspots  [L] Languages  [N] Nested  [T] Times  [q] Quit",
+            Tab::Status if state.git_enabled => "[Tab] Next Tab  [Shift+Tab] Previous Tab  [R] Refresh  [A] Abort Merge/Rebase  [q] Quit",
+            Tab::History if state.git_enabled && state.undo_confirm.is_some() => "[Y] Undo  [N/Esc] Cancel",
+            Tab::History if state.git_enabled && state.reflog_view.is_some() => "[↑↓] Navigate  [PgUp/PgDn] Page  [Z] Undo Last Operation  [Esc] Close",
+            Tab::History if state.git_enabled && state.history_filter_edit.is_some() => "[Tab/↑↓] Field  [Enter] Apply  [Esc] Cancel",
+            Tab::History if state.git_enabled && state.note_edit.is_some() => "[Ctrl+S] Save Note  [Esc] Cancel",
+            Tab::History if state.git_enabled && state.history.message_scroll.is_some() => "[↑↓] Scroll  [Esc] Close",
+            Tab::History if state.git_enabled => "[Tab] Next Tab  [Shift+Tab] Previous Tab  [↑↓] Navigate  [PgUp/PgDn] Page  [Home/End] First/Last  [Enter] Message  [N] Note  [U/G] Push/Fetch Notes  [E/J] Export CSV/JSON  [/] Filter  [C] Clear Filter  [P] First Parent  [M] Merges  [R] Follow Renames  [L] Reflog  [Z] Undo  [K] Cherry-pick  [X] Reset  [I] Rebase  [T] Times  [q] Quit",
+            Tab::Branches if state.git_enabled && state.branch_create.is_some() => "[Enter] Create and Switch  [Esc] Cancel",
+            Tab::Branches if state.git_enabled && state.branch_delete.is_some() => "[Y] Delete  [N/Esc] Cancel",
+            Tab::Branches if state.git_enabled && state.branch_action.is_some() => "[↑↓] Choose  [Enter] Go  [S] Switch  [M] Merge  [Esc] Cancel",
+            Tab::Branches if state.git_enabled && state.merge_draft.is_some() => "[Tab] Mode  [Ctrl+S] Merge  [Esc] Cancel",
+            Tab::Branches if state.git_enabled => "[Tab] Next Tab  [Shift+Tab] Previous Tab  [↑↓] Navigate  [Enter] Switch/Merge  [N] New  [D] Delete  [H] History  [K] Cherry-pick Tip  [R] Refresh  [q] Quit",
+            Tab::Tags if state.git_enabled && state.tag_create.is_some() => "[Tab] Name/Message  [Enter] Create (in Name)  [Ctrl+S] Create  [Esc] Cancel",
+            Tab::Tags if state.git_enabled && state.tag_delete.is_some() => "[Y] Delete  [N/Esc] Cancel",
+            Tab::Tags if state.git_enabled => "[Tab] Next Tab  [Shift+Tab] Previous Tab  [↑↓] Navigate  [N] Tag HEAD  [D] Delete  [P] Push  [Shift+P] Push All  [R] Refresh  [q] Quit",
+            Tab::Activity if state.git_enabled => "[Tab] Next Tab  [Shift+Tab] Previous Tab  [↑↓] Navigate  [E] Export  [q] Quit",
+            Tab::Settings if state.git_enabled => "[Tab] Next Tab  [Shift+Tab] Previous Tab  [1-9] Jump to Tab  [Ctrl+G] Replay Tour  [q] Quit",
+            _ => "[Tab] Next Tab  [Shift+Tab] Previous Tab  [1-9] Jump to Tab  [q] Quit",
+        }.to_string()
+    };
+
+    // Create status bar - drop branch info when loading to save space
+    if state.git_enabled && !state.is_loading {
+        // Build status line with branch info and hints (only when not loading)
+        let mut status_spans = Vec::new();
+
+        // Branch information from the cached snapshot, not re-read every frame
+        let snapshot = state.repo_snapshot();
+        let (current_branch, remote_branch) =
+            (snapshot.branch.clone(), snapshot.remote_branch.clone());
+
+        // Add branch information at the beginning
+        if let Some(branch) = current_branch {
+            // Local branch with parentheses
+            status_spans.push(ratatui::text::Span::styled("(", theme.accent_style()));
+            status_spans.push(ratatui::text::Span::styled(text::truncate(&branch, 30), theme.accent_style()));
+            status_spans.push(ratatui::text::Span::styled(")", theme.accent_style()));
+
+            // Add remote branch if available
+            if let Some(remote) = remote_branch {
+                // Remove redundant "origin/" prefix if present
+                let clean_remote = if remote.starts_with("origin/origin/") {
+                    remote.strip_prefix("origin/").unwrap_or(&remote).to_string()
+                } else {
+                    remote
+                };
+                
+                status_spans.push(ratatui::text::Span::raw(" "));
+                status_spans.push(ratatui::text::Span::styled("(", theme.accent3_style()));
+                status_spans.push(ratatui::text::Span::styled(text::truncate(&clean_remote, 30), theme.accent3_style()));
+                status_spans.push(ratatui::text::Span::styled(")", theme.accent3_style()));
             }
+
+            status_spans.push(ratatui::text::Span::raw("  |  "));
         }
+
+        // Add the hints, cut short on narrow terminals rather than clipped mid-word
+        let used: usize = status_spans.iter().map(|s| s.width()).sum();
+        let hints = text::truncate(&hints, (chunks[2].width as usize).saturating_sub(used));
+        status_spans.push(ratatui::text::Span::styled(hints, theme.status_bar_style()));
+
+        let status_line = ratatui::text::Line::from(status_spans);
+        let hint_paragraph = Paragraph::new(status_line)
+            .alignment(ratatui::layout::Alignment::Center);
+        f.render_widget(hint_paragraph, chunks[2]);
+    } else {
+        // No git or loading - just show hints (simplified when loading)
+        let hint_paragraph = Paragraph::new(text::truncate(&hints, chunks[2].width as usize))
+            .alignment(ratatui::layout::Alignment::Center)
+            .style(if state.is_loading { 
+                theme.info_style() 
+            } else { 
+                theme.status_bar_style() 
+            });
+        f.render_widget(hint_paragraph, chunks[2]);
     }
+}
 
-    // Restore terminal
-    disable_raw_mode().unwrap();
-    crossterm::execute!(io::stdout(), LeaveAlternateScreen).unwrap();
+/// Hand the terminal over to $EDITOR for `path` (at `line`, if given), restoring the TUI afterwards
+fn run_editor(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    path: &std::path::Path,
+    line: Option<usize>,
+) {
+    let _ = disable_raw_mode();
+    let _ = crossterm::execute!(io::stdout(), DisableMouseCapture, LeaveAlternateScreen);
+
+    let _ = crate::files::open_in_editor_at(path, line);
+
+    let _ = enable_raw_mode();
+    let _ = crossterm::execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture);
+    let _ = terminal.clear();
+}
+
+/// Hand the terminal to `git mergetool` for one conflicted file
+fn run_mergetool(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    path: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let _ = disable_raw_mode();
+    let _ = crossterm::execute!(io::stdout(), DisableMouseCapture, LeaveAlternateScreen);
+
+    let result = crate::git::run_mergetool(path);
+
+    let _ = enable_raw_mode();
+    let _ = crossterm::execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture);
+    let _ = terminal.clear();
+    result
 }
 
 // Helper function to create a centered rect for the modal
-fn centered_rect(percent_x: u16, height: u16, r: ratatui::layout::Rect) -> ratatui::layout::Rect {
+pub(crate) fn centered_rect(percent_x: u16, height: u16, r: ratatui::layout::Rect) -> ratatui::layout::Rect {
     let popup_layout = ratatui::layout::Layout::default()
         .direction(Direction::Vertical)
         .constraints(
@@ -0,0 +1,102 @@
+use crate::app::{AppState, MouseList, MouseTarget, SaveChangesFocus};
+use crate::tui::tabs::{Tab, TabRegistry};
+use crate::tui::text;
+use ratatui::crossterm::event::{
+    KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use ratatui::layout::{Margin, Position, Rect};
+
+/// Handle a mouse event on a screen of size `area`
+///
+/// A left click switches tabs or selects a table row, unless a popup is covering the tab.
+/// The wheel is returned as the arrow key it stands for, so it scrolls whatever ↑/↓ would:
+/// the focused list, or the popup on top.
+pub(super) fn handle_mouse(state: &mut AppState, mouse: MouseEvent, area: Rect) -> Option<KeyEvent> {
+    match mouse.kind {
+        MouseEventKind::ScrollUp => Some(KeyEvent::new(KeyCode::Up, KeyModifiers::NONE)),
+        MouseEventKind::ScrollDown => Some(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE)),
+        MouseEventKind::Down(MouseButton::Left) if !state.has_popup() => {
+            let position = Position::new(mouse.column, mouse.row);
+            if let Some(tab) = tab_at(&state.tab_bar, area, position) {
+                if state.git_enabled || !tab.requires_git() {
+                    state.switch_tab(tab);
+                }
+            } else if let Some((list, index)) = state.row_at(position) {
+                state.mouse_select(list, index);
+            }
+            None
+        }
+        _ => None,
+    }
+}
+
+/// The tab whose title is at `position`, laid out as the tab bar is drawn
+fn tab_at(tab_bar: &TabRegistry, area: Rect, position: Position) -> Option<Tab> {
+    // The screen has a margin of 1, then the bordered tab bar is 3 rows tall
+    let bar = Rect::new(area.x + 1, area.y + 1, area.width.saturating_sub(2), 3);
+    if !bar.contains(position) {
+        return None;
+    }
+    // Each title is padded by a space on both sides and followed by a one-column divider
+    let mut x = bar.x + 1;
+    for &tab in tab_bar.tabs() {
+        let end = x + 2 + text::width(tab.title()) as u16;
+        if (x..end).contains(&position.x) {
+            return Some(tab);
+        }
+        x = end + 1;
+    }
+    None
+}
+
+impl AppState {
+    /// Note where a bordered table with a one-line header was drawn, so clicks can select
+    /// its rows; `offset` is the first row shown and `len` the number of rows
+    pub fn record_mouse_target(&mut self, list: MouseList, area: Rect, offset: usize, len: usize) {
+        let inner = area.inner(Margin {
+            vertical: 1,
+            horizontal: 1,
+        });
+        let shown = len
+            .saturating_sub(offset)
+            .min(inner.height.saturating_sub(1) as usize);
+        self.mouse_targets.push(MouseTarget {
+            list,
+            rows: Rect {
+                y: inner.y + 1,
+                height: shown as u16,
+                ..inner
+            },
+            offset,
+        });
+    }
+
+    /// The table and row index drawn at `position` in the last frame
+    fn row_at(&self, position: Position) -> Option<(MouseList, usize)> {
+        self.mouse_targets
+            .iter()
+            .find(|target| target.rows.contains(position))
+            .map(|target| {
+                let row = (position.y - target.rows.y) as usize;
+                (target.list, target.offset + row)
+            })
+    }
+
+    /// Select row `index` of a table, as if it had been reached with the arrow keys
+    fn mouse_select(&mut self, list: MouseList, index: usize) {
+        match list {
+            MouseList::Files => self.files_selected_row = index,
+            MouseList::SaveChanges => {
+                // Rows include the section headers, which can't be selected
+                if let Some(file_idx) = self.save_changes_file_at_row(index) {
+                    self.save_changes_focus = SaveChangesFocus::FileList;
+                    self.save_changes_table_state.select(Some(file_idx));
+                }
+            }
+            MouseList::History => self.history.table_state.select(Some(index)),
+            MouseList::Branches => self.branches.table_state.select(Some(index)),
+            MouseList::Tags => self.tags.table_state.select(Some(index)),
+            MouseList::Activity => self.activity_table_state.select(Some(index)),
+        }
+    }
+}
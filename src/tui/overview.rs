@@ -1,61 +1,52 @@
-use crate::app::AppState;
+use crate::app::{
+    ActivityFilter, AppState, BranchActivity, BranchTip, ChurnView, CommitTimeZone, DayCommits, OverviewData,
+    RecentCommit, SparklineBucket, TimeFormat, CHURN_WINDOW_CHOICES, SPARKLINE_RANGE_CHOICES,
+};
+use crate::tui::commit_message::message_lines;
+use crate::tui::text;
 use crate::tui::theme::Theme;
-use chrono::{Datelike, NaiveDate, Utc};
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, Utc};
 use ratatui::layout::{Alignment, Constraint, Direction, Layout};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::calendar::{CalendarEventStore, Monthly};
-use ratatui::widgets::{Block, Borders, Paragraph, Sparkline};
+use ratatui::widgets::{
+    Block, Borders, Cell, Clear, Paragraph, Row, Sparkline, SparklineBar, Table, TableState, Wrap,
+};
 use ratatui::{layout::Rect, Frame};
 use time::{Date, Month};
 
-// Helper struct for commit information
-#[derive(Debug, Clone)]
-struct CommitInfo {
-    message: String,
-    author: String,
-    timestamp: i64,
-    oid: String, // Add commit OID for branch matching
-}
-
-// Helper struct for branch information
-#[derive(Debug, Clone)]
-struct BranchInfo {
-    name: String,
-    commit_oid: String,
-    is_remote: bool,
-}
-
 // Helper function to get recent commits from repository
-fn get_recent_commits(repo_root: &std::path::Path, limit: usize) -> Vec<CommitInfo> {
+fn get_recent_commits(repo: &gix::Repository, limit: usize) -> Vec<RecentCommit> {
     let mut commits = Vec::new();
 
-    if let Ok(repo) = gix::open(repo_root) {
-        if let Ok(head) = repo.head_ref() {
-            if let Some(head) = head {
-                if let Some(oid) = head.target().try_id() {
-                    if let Ok(obj) = repo.find_object(oid) {
-                        if let Ok(commit) = obj.try_into_commit() {
-                            if let Ok(walk) = commit.ancestors().all() {
-                                for info in walk.filter_map(Result::ok).take(limit) {
-                                    let oid = info.id();
-                                    if let Ok(obj) = repo.find_object(oid) {
-                                        if let Ok(commit_obj) = obj.try_into_commit() {
-                                            if let (Ok(message), Ok(author), Ok(time)) = (
-                                                commit_obj.message(),
-                                                commit_obj.author(),
-                                                commit_obj.time(),
-                                            ) {
-                                                let message_str = message.title.to_string();
-                                                let author_str = format!("{}", author.name);
-
-                                                commits.push(CommitInfo {
-                                                    message: message_str,
-                                                    author: author_str,
-                                                    timestamp: time.seconds,
-                                                    oid: oid.to_string(),
-                                                });
-                                            }
+    if let Ok(head) = repo.head_ref() {
+        if let Some(head) = head {
+            if let Some(oid) = head.target().try_id() {
+                if let Ok(obj) = repo.find_object(oid) {
+                    if let Ok(commit) = obj.try_into_commit() {
+                        if let Ok(walk) = commit.ancestors().all() {
+                            for info in walk.filter_map(Result::ok).take(limit) {
+                                let oid = info.id();
+                                if let Ok(obj) = repo.find_object(oid) {
+                                    if let Ok(commit_obj) = obj.try_into_commit() {
+                                        if let (Ok(message), Ok(author), Ok(time)) = (
+                                            commit_obj.message(),
+                                            commit_obj.author(),
+                                            commit_obj.time(),
+                                        ) {
+                                            let message_str = message.title.to_string();
+                                            let author_str = format!("{}", author.name);
+
+                                            commits.push(RecentCommit {
+                                                message: message_str,
+                                                author: author_str,
+                                                time: crate::git::time_with_offset(
+                                                    time.seconds,
+                                                    time.offset / 60,
+                                                ),
+                                                oid: oid.to_string(),
+                                            });
                                         }
                                     }
                                 }
@@ -71,9 +62,9 @@ fn get_recent_commits(repo_root: &std::path::Path, limit: usize) -> Vec<CommitIn
 }
 
 // Helper function to format relative time
-fn format_relative_time(timestamp: i64) -> String {
+fn format_relative_time(time: DateTime<FixedOffset>, time_zone: CommitTimeZone) -> String {
     let now = Utc::now().timestamp();
-    let diff = now - timestamp;
+    let diff = now - time.timestamp();
 
     if diff < 60 {
         "just now".to_string()
@@ -93,46 +84,52 @@ fn format_relative_time(timestamp: i64) -> String {
         }
     } else {
         // For commits older than a day, show the date
-        if let Some(naive) = chrono::NaiveDateTime::from_timestamp_opt(timestamp, 0) {
-            naive.format("%Y-%m-%d").to_string()
-        } else {
-            "unknown date".to_string()
-        }
+        time_zone.convert(time).format("%Y-%m-%d").to_string()
+    }
+}
+
+/// Format a commit time as picked with gitix.ui.timeformat or the T key
+fn format_commit_time(
+    time: DateTime<FixedOffset>,
+    time_format: TimeFormat,
+    time_zone: CommitTimeZone,
+) -> String {
+    match time_format {
+        TimeFormat::Relative => format_relative_time(time, time_zone),
+        other => other.format_commit(time, time_zone),
     }
 }
 
 // Helper function to get branch information
-fn get_branch_info(repo_root: &std::path::Path) -> Vec<BranchInfo> {
+fn get_branch_info(repo: &gix::Repository) -> Vec<BranchTip> {
     let mut branches = Vec::new();
 
-    if let Ok(repo) = gix::open(repo_root) {
-        if let Ok(refs) = repo.references() {
-            if let Ok(all_refs) = refs.all() {
-                for reference in all_refs.filter_map(Result::ok) {
-                    let name = reference.name().as_bstr();
-
-                    // Handle local branches (refs/heads/)
-                    if name.starts_with(b"refs/heads/") {
-                        if let Some(branch_name) = name.strip_prefix(b"refs/heads/") {
-                            if let Some(target) = reference.target().try_id() {
-                                branches.push(BranchInfo {
-                                    name: String::from_utf8_lossy(branch_name).to_string(),
-                                    commit_oid: target.to_string(),
-                                    is_remote: false,
-                                });
-                            }
+    if let Ok(refs) = repo.references() {
+        if let Ok(all_refs) = refs.all() {
+            for reference in all_refs.filter_map(Result::ok) {
+                let name = reference.name().as_bstr();
+
+                // Handle local branches (refs/heads/)
+                if name.starts_with(b"refs/heads/") {
+                    if let Some(branch_name) = name.strip_prefix(b"refs/heads/") {
+                        if let Some(target) = reference.target().try_id() {
+                            branches.push(BranchTip {
+                                name: String::from_utf8_lossy(branch_name).to_string(),
+                                commit_oid: target.to_string(),
+                                is_remote: false,
+                            });
                         }
                     }
-                    // Handle remote branches (refs/remotes/)
-                    else if name.starts_with(b"refs/remotes/") {
-                        if let Some(branch_name) = name.strip_prefix(b"refs/remotes/") {
-                            if let Some(target) = reference.target().try_id() {
-                                branches.push(BranchInfo {
-                                    name: String::from_utf8_lossy(branch_name).to_string(),
-                                    commit_oid: target.to_string(),
-                                    is_remote: true,
-                                });
-                            }
+                }
+                // Handle remote branches (refs/remotes/)
+                else if name.starts_with(b"refs/remotes/") {
+                    if let Some(branch_name) = name.strip_prefix(b"refs/remotes/") {
+                        if let Some(target) = reference.target().try_id() {
+                            branches.push(BranchTip {
+                                name: String::from_utf8_lossy(branch_name).to_string(),
+                                commit_oid: target.to_string(),
+                                is_remote: true,
+                            });
                         }
                     }
                 }
@@ -143,7 +140,159 @@ fn get_branch_info(repo_root: &std::path::Path) -> Vec<BranchInfo> {
     branches
 }
 
-pub fn render_overview_tab(f: &mut Frame, area: Rect, state: &AppState) {
+// Helper function to list branches by the date of their latest commit, newest first
+fn get_branch_activity(repo: &gix::Repository) -> Vec<BranchActivity> {
+    let mut branches = Vec::new();
+
+    if let Ok(refs) = repo.references() {
+        if let Ok(all_refs) = refs.all() {
+            for mut reference in all_refs.filter_map(Result::ok) {
+                let full_name = reference.name().as_bstr().to_string();
+                let local = full_name.strip_prefix("refs/heads/");
+                let remote = full_name.strip_prefix("refs/remotes/");
+                let (name, is_remote) = if let Some(name) = local {
+                    (name.to_string(), false)
+                } else if let Some(name) = remote {
+                    // origin/HEAD only repeats the default branch
+                    if name.ends_with("/HEAD") {
+                        continue;
+                    }
+                    (name.to_string(), true)
+                } else {
+                    continue;
+                };
+
+                if let Ok(commit) = reference.peel_to_commit() {
+                    if let (Ok(message), Ok(author), Ok(time)) =
+                        (commit.message(), commit.author(), commit.time())
+                    {
+                        branches.push(BranchActivity {
+                            name,
+                            is_remote,
+                            message: message.title.to_string(),
+                            author: author.name.to_string(),
+                            time: crate::git::time_with_offset(time.seconds, time.offset / 60),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    branches.sort_by(|a, b| b.time.cmp(&a.time));
+    branches
+}
+
+/// Read everything the Overview shows in one go, for `AppState::overview_data` to cache
+fn load_overview_data(repo_root: &std::path::Path, filter: &ActivityFilter) -> OverviewData {
+    let repo = match gix::open(repo_root) {
+        Ok(repo) => repo,
+        Err(_) => return OverviewData::default(),
+    };
+    let (num_commits, num_branches, latest_author, commit_dates) = get_repo_stats(&repo, filter);
+    OverviewData {
+        num_commits,
+        num_branches,
+        latest_author,
+        commit_dates,
+        recent_commits: get_recent_commits(&repo, 7),
+        branch_tips: get_branch_info(&repo),
+        branch_activity: get_branch_activity(&repo),
+    }
+}
+
+/// Commit and branch counts, HEAD's author and the date of every commit reachable from HEAD
+/// that `filter` counts
+fn get_repo_stats(
+    repo: &gix::Repository,
+    filter: &ActivityFilter,
+) -> (Option<u64>, Option<u64>, Option<String>, Vec<NaiveDate>) {
+    // Commit count
+    let num_commits = repo.head_ref().ok().and_then(|opt_head| {
+        opt_head.and_then(|head| {
+            let target = head.target();
+            let oid = target.try_id()?;
+            let commit = repo.find_object(oid).ok()?.try_into_commit().ok()?;
+            let walk = commit.ancestors().all().ok()?;
+            Some(walk.count() as u64)
+        })
+    });
+    // Branch count
+    let num_branches = repo.references().ok().and_then(|refs| {
+        refs.all().ok().map(|iter| {
+            iter.filter_map(Result::ok)
+                .filter(|r| r.name().as_bstr().starts_with(b"refs/heads/"))
+                .count() as u64
+        })
+    });
+    // Latest author
+    let latest_author = repo.head_ref().ok().and_then(|opt_head| {
+        opt_head.and_then(|head| {
+            let target = head.target();
+            let oid = target.try_id()?;
+            let commit = repo.find_object(oid).ok()?.try_into_commit().ok()?;
+            let sig = commit.author().ok()?;
+            let name = sig.name.to_string();
+            let email = sig.email.to_string();
+            Some(format!("{} <{}>", name, email))
+        })
+    });
+    // Gather commit dates for calendar
+    let mut commit_dates: Vec<NaiveDate> = Vec::new();
+    if let Ok(head) = repo.head_ref() {
+        if let Some(head) = head {
+            if let Some(oid) = head.target().try_id() {
+                if let Ok(obj) = repo.find_object(oid) {
+                    if let Ok(commit) = obj.try_into_commit() {
+                        if let Ok(walk) = commit.ancestors().all() {
+                            for info in walk.filter_map(Result::ok) {
+                                // Get the commit id from Info
+                                let oid = info.id();
+                                if let Ok(obj) = repo.find_object(oid) {
+                                    if let Ok(commit_obj) = obj.try_into_commit() {
+                                        let counted = commit_obj.author().is_ok_and(|author| {
+                                            filter.counts(
+                                                &author.name.to_string(),
+                                                &author.email.to_string(),
+                                                info.parent_ids.len(),
+                                            )
+                                        });
+                                        if !counted {
+                                            continue;
+                                        }
+                                        if let Ok(time) = commit_obj.time() {
+                                            let timestamp = time.seconds;
+                                            let naive = chrono::NaiveDateTime::from_timestamp_opt(
+                                                timestamp, 0,
+                                            );
+                                            if let Some(naive) = naive {
+                                                let date = NaiveDate::from_ymd_opt(
+                                                    naive.year(),
+                                                    naive.month(),
+                                                    naive.day(),
+                                                );
+                                                if let Some(date) = date {
+                                                    commit_dates.push(date);
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    (num_commits, num_branches, latest_author, commit_dates)
+}
+
+pub fn render_overview_tab(f: &mut Frame, area: Rect, state: &mut AppState) {
+    if state.git_enabled {
+        state.ensure_language_stats();
+    }
+
     // Use configured theme from app state
     let theme = Theme::with_accents_and_title(
         state.current_theme_accent,
@@ -215,95 +364,8 @@ pub fn render_overview_tab(f: &mut Frame, area: Rect, state: &AppState) {
 
     let mut chunk_idx = 0;
 
-    // --- Repo stats logic ---
-    let (num_commits, num_branches, latest_author, commit_dates): (
-        Option<u64>,
-        Option<u64>,
-        Option<String>,
-        Vec<NaiveDate>,
-    ) = if state.git_enabled {
-        if let Some(repo_root) = &state.repo_root {
-            match gix::open(repo_root) {
-                Ok(repo) => {
-                    // Commit count
-                    let num_commits = repo.head_ref().ok().and_then(|opt_head| {
-                        opt_head.and_then(|head| {
-                            let target = head.target();
-                            let oid = target.try_id()?;
-                            let commit = repo.find_object(oid).ok()?.try_into_commit().ok()?;
-                            let walk = commit.ancestors().all().ok()?;
-                            Some(walk.count() as u64)
-                        })
-                    });
-                    // Branch count
-                    let num_branches = repo.references().ok().and_then(|refs| {
-                        refs.all().ok().map(|iter| {
-                            iter.filter_map(Result::ok)
-                                .filter(|r| r.name().as_bstr().starts_with(b"refs/heads/"))
-                                .count() as u64
-                        })
-                    });
-                    // Latest author
-                    let latest_author = repo.head_ref().ok().and_then(|opt_head| {
-                        opt_head.and_then(|head| {
-                            let target = head.target();
-                            let oid = target.try_id()?;
-                            let commit = repo.find_object(oid).ok()?.try_into_commit().ok()?;
-                            let sig = commit.author().ok()?;
-                            let name = sig.name.to_string();
-                            let email = sig.email.to_string();
-                            Some(format!("{} <{}>", name, email))
-                        })
-                    });
-                    // Gather commit dates for calendar
-                    let mut commit_dates: Vec<NaiveDate> = Vec::new();
-                    if let Ok(head) = repo.head_ref() {
-                        if let Some(head) = head {
-                            if let Some(oid) = head.target().try_id() {
-                                if let Ok(obj) = repo.find_object(oid) {
-                                    if let Ok(commit) = obj.try_into_commit() {
-                                        if let Ok(walk) = commit.ancestors().all() {
-                                            for info in walk.filter_map(Result::ok) {
-                                                // Get the commit id from Info
-                                                let oid = info.id();
-                                                if let Ok(obj) = repo.find_object(oid) {
-                                                    if let Ok(commit_obj) = obj.try_into_commit() {
-                                                        if let Ok(time) = commit_obj.time() {
-                                                            let timestamp = time.seconds;
-                                                            let naive =
-                                                                chrono::NaiveDateTime::from_timestamp_opt(
-                                                                    timestamp, 0,
-                                                                );
-                                                            if let Some(naive) = naive {
-                                                                let date = NaiveDate::from_ymd_opt(
-                                                                    naive.year(),
-                                                                    naive.month(),
-                                                                    naive.day(),
-                                                                );
-                                                                if let Some(date) = date {
-                                                                    commit_dates.push(date);
-                                                                }
-                                                            }
-                                                        }
-                                                    }
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    (num_commits, num_branches, latest_author, commit_dates)
-                }
-                Err(_) => (None, None, None, Vec::new()),
-            }
-        } else {
-            (None, None, None, Vec::new())
-        }
-    } else {
-        (None, None, None, Vec::new())
-    };
+    // --- Repo stats, read once and cached until something changes them ---
+    let data = state.overview_data();
 
     // Stats row (always shown if we have minimum height)
     if show_stats {
@@ -320,12 +382,12 @@ pub fn render_overview_tab(f: &mut Frame, area: Rect, state: &AppState) {
         // Repository stats line with highlighted labels and values
         let mut stats_spans = Vec::new();
 
-        if let Some(n) = num_commits {
+        if let Some(n) = data.num_commits {
             stats_spans.push(Span::styled("Commits: ", theme.stats_label_style()));
             stats_spans.push(Span::styled(n.to_string(), theme.text_style()));
         }
 
-        if let Some(n) = num_branches {
+        if let Some(n) = data.num_branches {
             if !stats_spans.is_empty() {
                 stats_spans.push(Span::styled("    |    ", theme.secondary_text_style()));
             }
@@ -333,7 +395,7 @@ pub fn render_overview_tab(f: &mut Frame, area: Rect, state: &AppState) {
             stats_spans.push(Span::styled(n.to_string(), theme.text_style()));
         }
 
-        if let Some(ref author) = latest_author {
+        if let Some(author) = &data.latest_author {
             if !stats_spans.is_empty() {
                 stats_spans.push(Span::styled("    |    ", theme.secondary_text_style()));
             }
@@ -350,29 +412,61 @@ pub fn render_overview_tab(f: &mut Frame, area: Rect, state: &AppState) {
             Line::from(stats_spans)
         };
 
+        // The biggest languages ride along on the border; [L] has the full breakdown
+        let mut stats_block = Block::default()
+            .borders(Borders::ALL)
+            .title("Repository Stats")
+            .title_style(theme.title_style())
+            .border_style(theme.border_style())
+            .style(theme.secondary_background_style()); // Mantle background
+        if let Some(stats) = state.language_stats.as_ref().filter(|s| s.total_lines > 0) {
+            let top: Vec<String> = stats
+                .languages
+                .iter()
+                .take(3)
+                .map(|count| {
+                    format!("{} {}%", count.language, count.lines * 100 / stats.total_lines)
+                })
+                .collect();
+            stats_block = stats_block.title_bottom(
+                Line::styled(format!(" {} ", top.join(" · ")), theme.secondary_text_style())
+                    .right_aligned(),
+            );
+        }
+        // One answer for this repository and everything nested in it; [N] lists them
+        if let Some(recursive) = state.recursive_status.as_ref().filter(|r| !r.nested.is_empty()) {
+            let total = recursive.nested.len() + 1;
+            let summary = match recursive.unsettled() {
+                0 => Line::styled(
+                    format!(" ✓ All {} repositories committed and pushed · [N] Nested ", total),
+                    theme.success_style(),
+                ),
+                unsettled => Line::styled(
+                    format!(" ⚠ {} of {} repositories need work · [N] Nested ", unsettled, total),
+                    theme.warning_style(),
+                ),
+            };
+            stats_block = stats_block.title(summary.right_aligned());
+        }
         let stats_paragraph = Paragraph::new(stats_line)
             .alignment(Alignment::Center)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title("Repository Stats")
-                    .title_style(theme.title_style())
-                    .border_style(theme.border_style())
-                    .style(theme.secondary_background_style()), // Mantle background
-            );
+            .block(stats_block);
         f.render_widget(stats_paragraph, stats_chunks[0]);
 
-        // Get real commit history data with branch information
-        let (recent_commits, branches) = if state.git_enabled {
-            if let Some(repo_root) = &state.repo_root {
-                let commits = get_recent_commits(repo_root, 7); // Increased from 5 to 7
-                let branches = get_branch_info(repo_root);
-                (commits, branches)
-            } else {
-                (Vec::new(), Vec::new())
-            }
+        // Commit history with the branches pointing into it
+        let recent_commits = &data.recent_commits;
+        let branches = &data.branch_tips;
+
+        // Recent Changes gets the width unless there is room for the active branches too
+        let history_chunks = if stats_chunks[1].width >= 110 {
+            Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+                .split(stats_chunks[1])
         } else {
-            (Vec::new(), Vec::new())
+            Layout::default()
+                .constraints([Constraint::Min(0)])
+                .split(stats_chunks[1])
         };
 
         // Build commit history with colored spans and branch information
@@ -384,12 +478,12 @@ pub fn render_overview_tab(f: &mut Frame, area: Rect, state: &AppState) {
                 theme.muted_text_style(),
             )));
         } else {
-            for commit in &recent_commits {
-                let relative_time = format_relative_time(commit.timestamp);
+            for commit in recent_commits {
+                let relative_time = format_commit_time(commit.time, state.time_format, state.time_zone);
 
                 // Find branches that point to this commit
                 let mut commit_branches = Vec::new();
-                for branch in &branches {
+                for branch in branches {
                     if branch.commit_oid == commit.oid {
                         if branch.is_remote {
                             commit_branches.push(branch.name.clone());
@@ -435,15 +529,24 @@ pub fn render_overview_tab(f: &mut Frame, area: Rect, state: &AppState) {
                     line_spans.push(Span::styled(") ", primary_style));
                 }
 
-                line_spans.push(Span::styled(&commit.message, theme.commit_message_style()));
-
-                line_spans.extend(vec![
+                let suffix = vec![
                     Span::styled(" - ", theme.secondary_text_style()),
                     Span::styled(&commit.author, theme.author_style()),
                     Span::styled(" (", theme.secondary_text_style()),
                     Span::styled(relative_time, theme.timestamp_style()),
                     Span::styled(")", theme.secondary_text_style()),
-                ]);
+                ];
+
+                // Shorten the message so the author and time stay on the line
+                let used: usize = line_spans.iter().chain(&suffix).map(|s| s.width()).sum();
+                let message_width = (history_chunks[0].width as usize)
+                    .saturating_sub(2 + used)
+                    .max(10);
+                line_spans.push(Span::styled(
+                    text::truncate(&commit.message, message_width),
+                    theme.commit_message_style(),
+                ));
+                line_spans.extend(suffix);
 
                 let line = Line::from(line_spans);
                 commit_lines.push(line);
@@ -460,21 +563,36 @@ pub fn render_overview_tab(f: &mut Frame, area: Rect, state: &AppState) {
                     .border_style(theme.border_style())
                     .style(theme.secondary_background_style()), // Mantle background
             );
-        f.render_widget(commit_paragraph, stats_chunks[1]);
+        f.render_widget(commit_paragraph, history_chunks[0]);
+
+        // Most recently active branches, when there is room beside the history
+        if history_chunks.len() > 1 {
+            let rows = history_chunks[1].height.saturating_sub(2) as usize;
+            let shown = data.branch_activity.len().min(rows);
+            render_branch_activity(
+                f,
+                history_chunks[1],
+                &data.branch_activity[..shown],
+                state.time_format,
+                state.time_zone,
+                &theme,
+            );
+        }
 
         chunk_idx += 1;
     }
 
     // --- Responsive Calendar (adapts number of months based on screen size) ---
     if show_calendar {
-        if state.git_enabled && !commit_dates.is_empty() {
-            render_responsive_calendar(
+        if state.git_enabled && !data.commit_dates.is_empty() {
+            state.overview_calendar_start = Some(render_responsive_calendar(
                 f,
                 overview_chunks[chunk_idx],
-                &commit_dates,
+                &data.commit_dates,
+                state.overview_calendar_cursor,
                 &theme,
                 area.width,
-            );
+            ));
         } else {
             let calendar_paragraph = Paragraph::new("Calendar: [no data]")
                 .alignment(Alignment::Center)
@@ -494,13 +612,13 @@ pub fn render_overview_tab(f: &mut Frame, area: Rect, state: &AppState) {
 
     // Sparkline for commit activity (responsive height)
     if show_sparkline {
-        if state.git_enabled && !commit_dates.is_empty() {
+        if state.git_enabled && !data.commit_dates.is_empty() {
             render_responsive_sparkline(
                 f,
                 overview_chunks[chunk_idx],
-                &commit_dates,
+                &data.commit_dates,
+                state,
                 &theme,
-                sparkline_height,
             );
         } else {
             let sparkline_paragraph = Paragraph::new("Recent Activity: [no data]")
@@ -517,6 +635,630 @@ pub fn render_overview_tab(f: &mut Frame, area: Rect, state: &AppState) {
             f.render_widget(sparkline_paragraph, overview_chunks[chunk_idx]);
         }
     }
+
+    if let Some(day) = &state.day_commits {
+        render_day_commits_popup(f, area, day, state.time_zone, &theme);
+    }
+
+    if let Some(view) = &state.churn_view {
+        render_churn_popup(f, area, view, state.cached_churn(view.days), &theme);
+    }
+
+    if let Some(scroll) = state.language_view {
+        render_languages_popup(f, area, state, scroll, &theme);
+    }
+
+    if let Some(selected) = state.nested_view {
+        render_nested_popup(f, area, state, selected, &theme);
+    }
+}
+
+/// Render this repository and every submodule or clone inside it, with what each still needs
+fn render_nested_popup(f: &mut Frame, area: Rect, state: &AppState, selected: usize, theme: &Theme) {
+    let popup_area = crate::tui::centered_rect(80, area.height.saturating_sub(4).min(20), area);
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Nested Repositories - [Esc] to close")
+        .title_style(theme.popup_title_style())
+        .border_style(theme.popup_border_style())
+        .style(theme.popup_background_style());
+
+    let recursive = match &state.recursive_status {
+        Some(recursive) => recursive,
+        None => {
+            let message = if state.recursive_scan.is_some() {
+                "⟳ Looking for nested repositories..."
+            } else {
+                "Could not read the nested repositories."
+            };
+            f.render_widget(
+                Paragraph::new(message)
+                    .alignment(Alignment::Center)
+                    .style(theme.secondary_text_style())
+                    .block(block),
+                popup_area,
+            );
+            return;
+        }
+    };
+
+    let header = Row::new(vec![
+        Cell::from("Repository").style(theme.accent2_style()),
+        Cell::from("Kind").style(theme.accent2_style()),
+        Cell::from("Branch").style(theme.accent2_style()),
+        Cell::from("Changes").style(theme.accent2_style()),
+        Cell::from("Remote").style(theme.accent2_style()),
+    ]);
+    let widths = [
+        Constraint::Percentage(35),
+        Constraint::Length(10),
+        Constraint::Percentage(20),
+        Constraint::Length(11),
+        Constraint::Min(12),
+    ];
+    let name_width = text::column_widths(popup_area, &widths, "► ")[0];
+
+    let own = (".".to_string(), "this", Ok(recursive.own.clone()));
+    let nested = recursive.nested.iter().map(|repo| {
+        let kind = if repo.submodule { "submodule" } else { "clone" };
+        (repo.path.display().to_string(), kind, repo.status.clone())
+    });
+    let rows: Vec<Row> = std::iter::once(own)
+        .chain(nested)
+        .map(|(path, kind, status)| {
+            let name = Cell::from(text::truncate_path(&path, name_width)).style(theme.text_style());
+            let kind = Cell::from(kind).style(theme.secondary_text_style());
+            let status = match status {
+                Ok(status) => status,
+                Err(e) => {
+                    return Row::new(vec![name, kind, Cell::from(e).style(theme.error_style())]);
+                }
+            };
+            let branch = Cell::from(status.branch.unwrap_or_else(|| "(detached)".to_string()))
+                .style(theme.accent_style());
+            let changes = match status.changed {
+                0 => Cell::from("clean").style(theme.success_style()),
+                n => Cell::from(format!("{} changed", n)).style(theme.warning_style()),
+            };
+            let remote = match (&status.upstream, status.ahead, status.behind) {
+                (None, _, _) => Cell::from("no upstream").style(theme.muted_text_style()),
+                (Some(_), 0, 0) => Cell::from("up to date").style(theme.success_style()),
+                (Some(_), ahead, behind) => Cell::from(Line::from(vec![
+                    Span::styled(format!("↑{} ", ahead), theme.info_style()),
+                    Span::styled(format!("↓{}", behind), theme.warning_style()),
+                ])),
+            };
+            Row::new(vec![name, kind, branch, changes, remote])
+        })
+        .collect();
+
+    let mut table_state = TableState::default().with_selected(Some(selected.min(rows.len() - 1)));
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(block)
+        .row_highlight_style(theme.highlight_style())
+        .highlight_symbol("► ");
+    f.render_stateful_widget(table, popup_area, &mut table_state);
+}
+
+// The Overview's figures, read once per repository snapshot
+impl AppState {
+    /// The Overview's history and branch figures, read now if the snapshot was dropped
+    pub fn overview_data(&mut self) -> std::rc::Rc<OverviewData> {
+        let repo_root = self.repo_root.clone().filter(|_| self.git_enabled);
+        let filter = self.activity_filter.clone();
+        let snapshot = self.repo_snapshot();
+        snapshot
+            .overview
+            .get_or_insert_with(|| {
+                std::rc::Rc::new(match repo_root {
+                    Some(repo_root) => load_overview_data(&repo_root, &filter),
+                    None => OverviewData::default(),
+                })
+            })
+            .clone()
+    }
+}
+
+// This repository plus its submodules and nested clones, read in the background
+impl AppState {
+    /// Re-read the status of this repository and those nested in it, unless a read is running
+    pub fn start_recursive_scan(&mut self) {
+        if !self.git_enabled || self.recursive_scan.is_some() {
+            return;
+        }
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let here = std::path::Path::new(".");
+            let result = crate::git::workspace_repo_status(here).and_then(|own| {
+                let nested = crate::git::nested_repositories(here)?;
+                Ok(crate::app::RecursiveStatus { own, nested })
+            });
+            let _ = tx.send(result.map_err(|e| e.to_string()));
+        });
+        self.recursive_scan = Some(rx);
+    }
+
+    /// Store a finished read; a failed one keeps the previous answer
+    pub fn poll_recursive_scan(&mut self) {
+        let result = match &self.recursive_scan {
+            Some(rx) => rx.try_recv(),
+            None => return,
+        };
+        match result {
+            Ok(result) => {
+                self.recursive_scan = None;
+                if let Ok(recursive) = result {
+                    self.recursive_status = Some(recursive);
+                }
+            }
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => self.recursive_scan = None,
+            Err(std::sync::mpsc::TryRecvError::Empty) => {}
+        }
+    }
+
+    /// Move the selection in the nested repositories popup
+    pub fn nested_navigate(&mut self, delta: isize) {
+        let count = self.recursive_status.as_ref().map_or(1, |r| r.nested.len() + 1);
+        if let Some(selected) = self.nested_view.as_mut() {
+            *selected = selected.saturating_add_signed(delta).min(count - 1);
+        }
+    }
+}
+
+/// Render lines of code per language at HEAD
+fn render_languages_popup(
+    f: &mut Frame,
+    area: Rect,
+    state: &AppState,
+    scroll: usize,
+    theme: &Theme,
+) {
+    let popup_area = crate::tui::centered_rect(70, area.height.saturating_sub(4).min(24), area);
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Languages at HEAD - [Esc] to close")
+        .title_style(theme.popup_title_style())
+        .border_style(theme.popup_border_style())
+        .style(theme.popup_background_style());
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let stats = match (&state.language_stats, &state.language_error) {
+        (Some(stats), _) if state.language_scan.is_none() => stats,
+        (_, Some((_, error))) => {
+            f.render_widget(
+                Paragraph::new(format!("Could not count lines: {}", error))
+                    .style(theme.error_style())
+                    .wrap(Wrap { trim: true }),
+                inner,
+            );
+            return;
+        }
+        _ => {
+            f.render_widget(
+                Paragraph::new("⟳ Counting lines...")
+                    .alignment(Alignment::Center)
+                    .style(theme.secondary_text_style()),
+                inner,
+            );
+            return;
+        }
+    };
+    if stats.languages.is_empty() {
+        f.render_widget(
+            Paragraph::new("No recognized source files at HEAD.")
+                .alignment(Alignment::Center)
+                .style(theme.secondary_text_style()),
+            inner,
+        );
+        return;
+    }
+
+    let total = stats.total_lines.max(1);
+    let bar_width = (inner.width as usize).saturating_sub(44).clamp(5, 30);
+    let mut lines = vec![Line::styled(
+        format!("{:<18} {:>9} {:>7} {:>5}", "Language", "Lines", "Files", "%"),
+        theme.accent2_style(),
+    )];
+    for count in stats.languages.iter().skip(scroll) {
+        let bar = "█".repeat((count.lines * bar_width).div_ceil(total));
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(
+                    "{:<18} {:>9} {:>7} {:>4}% ",
+                    text::truncate(count.language, 18),
+                    count.lines,
+                    count.files,
+                    count.lines * 100 / total
+                ),
+                theme.text_style(),
+            ),
+            Span::styled(bar, theme.accent_style()),
+        ]));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::styled(
+        format!("{} lines in total", stats.total_lines),
+        theme.secondary_text_style(),
+    ));
+    f.render_widget(Paragraph::new(lines), inner);
+}
+
+// Lines per language, counted in the background and cached by HEAD
+impl AppState {
+    /// Start counting lines per language when HEAD has moved since the last count
+    pub fn ensure_language_stats(&mut self) {
+        if self.language_scan.is_some() {
+            return;
+        }
+        let head = match self.repo_snapshot().head.clone() {
+            Some(head) => head,
+            None => return, // No commits yet
+        };
+        let counted = self.language_stats.as_ref().map(|stats| &stats.head) == Some(&head);
+        let failed = self.language_error.as_ref().map(|(failed, _)| failed) == Some(&head);
+        if counted || failed {
+            return;
+        }
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(crate::languages::language_stats().map_err(|e| (head, e.to_string())));
+        });
+        self.language_scan = Some(rx);
+    }
+
+    /// Store a finished language count, if there is one
+    pub fn poll_language_scan(&mut self) {
+        let result = match &self.language_scan {
+            Some(rx) => rx.try_recv(),
+            None => return,
+        };
+        match result {
+            Ok(Ok(stats)) => {
+                self.language_scan = None;
+                self.language_error = None;
+                self.language_stats = Some(stats);
+            }
+            Ok(Err(error)) => {
+                self.language_scan = None;
+                self.language_error = Some(error);
+            }
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => self.language_scan = None,
+            Err(std::sync::mpsc::TryRecvError::Empty) => {}
+        }
+    }
+}
+
+/// Render the files that changed most often over the chosen window
+fn render_churn_popup(
+    f: &mut Frame,
+    area: Rect,
+    view: &ChurnView,
+    report: Option<&crate::git::ChurnReport>,
+    theme: &Theme,
+) {
+    let popup_area = crate::tui::centered_rect(80, area.height.saturating_sub(4).min(24), area);
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(
+            "Hotspots - last {} days - [←→] window, [Esc] to close",
+            view.days
+        ))
+        .title_style(theme.popup_title_style())
+        .border_style(theme.popup_border_style())
+        .style(theme.popup_background_style());
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let report = match (report, &view.error) {
+        (Some(report), _) => report,
+        (None, Some(error)) => {
+            f.render_widget(
+                Paragraph::new(format!("Could not scan history: {}", error))
+                    .style(theme.error_style())
+                    .wrap(Wrap { trim: true }),
+                inner,
+            );
+            return;
+        }
+        (None, None) => {
+            f.render_widget(
+                Paragraph::new("⟳ Scanning history...")
+                    .alignment(Alignment::Center)
+                    .style(theme.secondary_text_style()),
+                inner,
+            );
+            return;
+        }
+    };
+    if report.files.is_empty() {
+        f.render_widget(
+            Paragraph::new("No files changed in this window.")
+                .alignment(Alignment::Center)
+                .style(theme.secondary_text_style()),
+            inner,
+        );
+        return;
+    }
+
+    let width = inner.width as usize;
+    let most = report.files[0].commits.max(1);
+    let mut lines = vec![Line::styled(
+        format!("{:>7}  {:>13}  {:<12} Path", "Commits", "+/-", ""),
+        theme.accent2_style(),
+    )];
+    for file in report.files.iter().skip(view.scroll) {
+        // A bar relative to the busiest file makes the hotspots stand out
+        let bar = "█".repeat((file.commits * 12).div_ceil(most));
+        let stats = format!("+{} -{}", file.insertions, file.deletions);
+        let prefix = format!("{:>7}  {:>13}  {:<12} ", file.commits, stats, bar);
+        let path_width = width.saturating_sub(prefix.chars().count());
+        lines.push(Line::from(vec![
+            Span::styled(prefix, theme.text_style()),
+            Span::styled(text::truncate_path(&file.path, path_width), theme.accent_style()),
+        ]));
+    }
+    f.render_widget(Paragraph::new(lines), inner);
+}
+
+// The hotspots report, computed in the background and cached per HEAD and window
+impl AppState {
+    /// Open the hotspots popup, scanning history unless a current report is cached
+    pub fn open_churn_view(&mut self) {
+        let days = self.churn_view.as_ref().map(|view| view.days).unwrap_or(90);
+        self.churn_view = Some(ChurnView {
+            days,
+            scroll: 0,
+            error: None,
+        });
+        self.start_churn_scan();
+    }
+
+    /// Move the hotspots popup to the next or previous time window
+    pub fn cycle_churn_window(&mut self, forward: bool) {
+        let view = match self.churn_view.as_mut() {
+            Some(view) => view,
+            None => return,
+        };
+        let choices = CHURN_WINDOW_CHOICES;
+        let current = choices.iter().position(|c| *c == view.days).unwrap_or(0);
+        let next = if forward {
+            (current + 1) % choices.len()
+        } else {
+            (current + choices.len() - 1) % choices.len()
+        };
+        view.days = choices[next];
+        view.scroll = 0;
+        view.error = None;
+        self.start_churn_scan();
+    }
+
+    /// The cached report for `days`, if it was computed for the current HEAD
+    pub fn cached_churn(&self, days: u32) -> Option<&crate::git::ChurnReport> {
+        self.churn_cache.iter().find(|report| report.days == days)
+    }
+
+    /// Compute the report for the open window on a background thread, unless cached
+    fn start_churn_scan(&mut self) {
+        let days = match &self.churn_view {
+            Some(view) => view.days,
+            None => return,
+        };
+        // New commits make every cached report stale
+        let head = crate::git::head_id().ok();
+        self.churn_cache
+            .retain(|report| Some(&report.head) == head.as_ref());
+        if self.cached_churn(days).is_some() {
+            return;
+        }
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(crate::git::file_churn(days).map_err(|e| e.to_string()));
+        });
+        self.churn_scan = Some(rx);
+    }
+
+    /// Store a finished hotspots scan, if there is one
+    pub fn poll_churn_scan(&mut self) {
+        let result = match &self.churn_scan {
+            Some(rx) => rx.try_recv(),
+            None => return,
+        };
+        match result {
+            Ok(Ok(report)) => {
+                self.churn_scan = None;
+                self.churn_cache.retain(|cached| cached.days != report.days);
+                self.churn_cache.push(report);
+            }
+            Ok(Err(error)) => {
+                self.churn_scan = None;
+                if let Some(view) = self.churn_view.as_mut() {
+                    view.error = Some(error);
+                }
+            }
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => self.churn_scan = None,
+            Err(std::sync::mpsc::TryRecvError::Empty) => {}
+        }
+    }
+}
+
+/// Render the most recently active branches with their latest commit and its age
+fn render_branch_activity(
+    f: &mut Frame,
+    area: Rect,
+    branches: &[BranchActivity],
+    time_format: TimeFormat,
+    time_zone: CommitTimeZone,
+    theme: &Theme,
+) {
+    let width = area.width.saturating_sub(2) as usize;
+    let mut lines = Vec::new();
+    if branches.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No branches found",
+            theme.muted_text_style(),
+        )));
+    }
+    for branch in branches {
+        let name_style = if branch.is_remote {
+            theme.accent3_style()
+        } else {
+            theme.accent_style()
+        };
+        let suffix = vec![
+            Span::styled(" - ", theme.secondary_text_style()),
+            Span::styled(branch.author.clone(), theme.author_style()),
+            Span::styled(" (", theme.secondary_text_style()),
+            Span::styled(
+                format_commit_time(branch.time, time_format, time_zone),
+                theme.timestamp_style(),
+            ),
+            Span::styled(")", theme.secondary_text_style()),
+        ];
+        let mut spans = vec![
+            Span::raw("• "),
+            Span::styled(text::truncate(&branch.name, width / 3), name_style),
+            Span::raw(" "),
+        ];
+        let used: usize = spans.iter().chain(&suffix).map(|s| s.width()).sum();
+        spans.push(Span::styled(
+            text::truncate(&branch.message, width.saturating_sub(used).max(5)),
+            theme.commit_message_style(),
+        ));
+        spans.extend(suffix);
+        lines.push(Line::from(spans));
+    }
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Active Branches")
+            .title_style(theme.title_style())
+            .border_style(theme.border_style())
+            .style(theme.secondary_background_style()),
+    );
+    f.render_widget(paragraph, area);
+}
+
+/// Render the commits of the day picked on the calendar
+fn render_day_commits_popup(
+    f: &mut Frame,
+    area: Rect,
+    day: &DayCommits,
+    time_zone: CommitTimeZone,
+    theme: &Theme,
+) {
+    let popup_area = crate::tui::centered_rect(70, area.height.saturating_sub(4).min(20), area);
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(
+            "Commits on {} - [Enter] details, [H] in History, [Esc] to close",
+            day.date.format("%a %Y-%m-%d")
+        ))
+        .title_style(theme.popup_title_style())
+        .border_style(theme.popup_border_style())
+        .style(theme.popup_background_style());
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    if day.commits.is_empty() {
+        f.render_widget(
+            Paragraph::new("No commits on this day.")
+                .alignment(Alignment::Center)
+                .style(theme.secondary_text_style()),
+            inner,
+        );
+        return;
+    }
+
+    let width = inner.width as usize;
+    let mut lines = Vec::new();
+    for (idx, commit) in day.commits.iter().enumerate() {
+        let selected = idx == day.selected;
+        let time = time_zone.format(commit.time, "%H:%M");
+        let marker = if selected { "►" } else { " " };
+        let prefix = format!("{} {} {} ", marker, commit.short_id, time);
+        let suffix = format!(" - {}", commit.author);
+        let summary_width = width.saturating_sub(prefix.chars().count() + suffix.chars().count());
+        let style = if selected {
+            theme.highlight_style()
+        } else {
+            theme.text_style()
+        };
+        lines.push(Line::from(vec![
+            Span::styled(prefix, style),
+            Span::styled(text::truncate(&commit.summary, summary_width), style),
+            Span::styled(suffix, theme.author_style()),
+        ]));
+        // The full message opens up under the selected commit
+        if selected && day.show_message {
+            for line in message_lines(&commit.message, width.saturating_sub(4), theme) {
+                let mut spans = vec![Span::raw("    ")];
+                spans.extend(line.spans);
+                lines.push(Line::from(spans));
+            }
+            lines.push(Line::styled(format!("    {}", commit.id), theme.muted_text_style()));
+        }
+    }
+
+    // Keep the selection in view
+    let scroll = (day.selected as u16).saturating_sub(inner.height.saturating_sub(1));
+    f.render_widget(
+        Paragraph::new(lines).wrap(Wrap { trim: false }).scroll((scroll, 0)),
+        inner,
+    );
+}
+
+// Calendar navigation and the day's commit list
+impl AppState {
+    /// Move the calendar day cursor, starting from today, within the days on screen
+    pub fn move_calendar_cursor(&mut self, days: i64) {
+        let today = Utc::now().date_naive();
+        let current = self.overview_calendar_cursor.unwrap_or(today);
+        let mut next = if self.overview_calendar_cursor.is_some() {
+            current + chrono::Duration::days(days)
+        } else {
+            today
+        };
+        next = next.min(today);
+        if let Some(start) = self.overview_calendar_start {
+            next = next.max(start);
+        }
+        self.overview_calendar_cursor = Some(next);
+    }
+
+    /// List the commits of the day under the calendar cursor
+    pub fn open_day_commits(&mut self) {
+        let date = match self.overview_calendar_cursor {
+            Some(date) => date,
+            None => return,
+        };
+        match crate::git::commits_on_day(date) {
+            Ok(commits) => {
+                self.day_commits = Some(DayCommits {
+                    date,
+                    commits,
+                    selected: 0,
+                    show_message: false,
+                })
+            }
+            Err(e) => self.show_error(
+                "History Unavailable",
+                &format!("Failed to list commits for {}:\n\n{}", date, e),
+            ),
+        }
+    }
 }
 
 // Helper function to calculate responsive heights based on screen size
@@ -564,9 +1306,10 @@ fn render_responsive_calendar(
     f: &mut Frame,
     area: Rect,
     commit_dates: &[NaiveDate],
+    cursor: Option<NaiveDate>,
     theme: &Theme,
     screen_width: u16,
-) {
+) -> NaiveDate {
     let today = Utc::now().date_naive();
     let mut event_store = CalendarEventStore::default();
 
@@ -617,6 +1360,17 @@ fn render_responsive_calendar(
         }
     }
 
+    // The day cursor, on top of any commit styling
+    if let Some(cursor) = cursor {
+        let month = Month::try_from(cursor.month() as u8).ok();
+        let day = u8::try_from(cursor.day()).ok();
+        if let (Some(month), Some(day)) = (month, day) {
+            if let Ok(time_date) = Date::from_calendar_date(cursor.year(), month, day) {
+                event_store.add(time_date, theme.highlight_style());
+            }
+        }
+    }
+
     // Split area into rows
     let row_constraints: Vec<Constraint> = (0..num_rows)
         .map(|_| Constraint::Percentage(100 / num_rows as u16))
@@ -669,6 +1423,10 @@ fn render_responsive_calendar(
             }
         }
     }
+
+    // The first day of the earliest month shown
+    let first_month = today - chrono::Duration::days(30 * (months_to_show - 1) as i64);
+    first_month.with_day(1).unwrap_or(first_month)
 }
 
 // Helper function to render responsive sparkline
@@ -676,52 +1434,159 @@ fn render_responsive_sparkline(
     f: &mut Frame,
     area: Rect,
     commit_dates: &[NaiveDate],
+    state: &AppState,
     theme: &Theme,
-    sparkline_height: u16,
 ) {
-    let width = area.width.saturating_sub(2); // account for borders
+    let width = area.width.saturating_sub(2) as usize; // account for borders
+    let today = Utc::now().date_naive();
+    let bucket = state.sparkline_bucket;
+    let buckets = activity_buckets(commit_dates, bucket, state.sparkline_range_months, today);
+
+    // The bar under the calendar's day cursor, or today's
+    let cursor = state.overview_calendar_cursor.unwrap_or(today);
+    let cursor_idx = buckets
+        .iter()
+        .rposition(|(start, _)| *start <= cursor)
+        .unwrap_or(0);
+
+    // Per-day bars over a long range don't fit; show the newest, scrolling back to the cursor
+    let mut offset = buckets.len().saturating_sub(width);
+    if cursor_idx < offset {
+        offset = cursor_idx;
+    }
+    let bars: Vec<SparklineBar> = buckets
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(width)
+        .map(|(idx, (_, count))| {
+            let bar = SparklineBar::from(*count);
+            if idx == cursor_idx {
+                bar.style(Some(theme.accent_style().add_modifier(Modifier::BOLD)))
+            } else {
+                bar
+            }
+        })
+        .collect();
 
-    // Adjust time range based on sparkline height - more height = longer time range
-    let num_days = if sparkline_height <= 6 {
-        90 // 3 months for small sparklines
-    } else if sparkline_height <= 8 {
-        180 // 6 months for medium sparklines
-    } else {
-        365 // 1 year for large sparklines
+    let excluded = state
+        .activity_filter
+        .describe()
+        .map(|what| format!(", {}", what))
+        .unwrap_or_default();
+    let title = format!(
+        "Recent Activity (per {}, last {} months{}) - [G] per day/week/month, [R] range, [M] merges, [B] bots",
+        bucket.as_str(),
+        state.sparkline_range_months,
+        excluded
+    );
+    let cursor_label = buckets.get(cursor_idx).map(|(start, count)| {
+        let when = match bucket {
+            SparklineBucket::Day => start.format("%a %Y-%m-%d").to_string(),
+            SparklineBucket::Week => format!("Week of {}", start.format("%Y-%m-%d")),
+            SparklineBucket::Month => start.format("%B %Y").to_string(),
+        };
+        let plural = if *count == 1 { "" } else { "s" };
+        format!(" {}: {} commit{} ", when, count, plural)
+    });
+
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .title_style(theme.title_style())
+        .border_style(theme.border_style())
+        .style(theme.secondary_background_style());
+    if let Some(label) = cursor_label {
+        block = block.title_bottom(Line::styled(label, theme.accent_style()));
+    }
+
+    let sparkline = Sparkline::default()
+        .block(block)
+        .data(bars)
+        .style(theme.accent2_style());
+    f.render_widget(sparkline, area);
+}
+
+/// Commit counts per bucket over the last `months`, oldest first, keyed by each bucket's first day
+fn activity_buckets(
+    commit_dates: &[NaiveDate],
+    bucket: SparklineBucket,
+    months: u32,
+    today: NaiveDate,
+) -> Vec<(NaiveDate, u64)> {
+    let start = today
+        .checked_sub_months(chrono::Months::new(months))
+        .unwrap_or(today);
+    let first = match bucket {
+        SparklineBucket::Day => start,
+        SparklineBucket::Week => {
+            start - chrono::Duration::days(start.weekday().num_days_from_monday() as i64)
+        }
+        SparklineBucket::Month => start.with_day(1).unwrap_or(start),
     };
 
-    let today = Utc::now().date_naive();
-    let start_date = today - chrono::Duration::days(num_days - 1);
-    let bars = width as usize;
-    let days_per_bar = (num_days as f32 / bars as f32).ceil() as usize;
-    let mut buckets = vec![0u64; bars];
+    let mut buckets = Vec::new();
+    let mut current = first;
+    while current <= today {
+        buckets.push((current, 0));
+        current = match bucket {
+            SparklineBucket::Day => current + chrono::Duration::days(1),
+            SparklineBucket::Week => current + chrono::Duration::days(7),
+            SparklineBucket::Month => current
+                .checked_add_months(chrono::Months::new(1))
+                .unwrap_or(today + chrono::Duration::days(1)),
+        };
+    }
 
     for date in commit_dates {
-        if *date >= start_date && *date <= today {
-            let days_since_start = (*date - start_date).num_days() as usize;
-            let bar_idx = (days_since_start / days_per_bar).min(bars - 1);
-            buckets[bar_idx] += 1;
+        if *date < first || *date > today {
+            continue;
+        }
+        let idx = match bucket {
+            SparklineBucket::Day => (*date - first).num_days() as usize,
+            SparklineBucket::Week => (*date - first).num_days() as usize / 7,
+            SparklineBucket::Month => {
+                let months_since = |d: &NaiveDate| d.year() as usize * 12 + d.month0() as usize;
+                months_since(date) - months_since(&first)
+            }
+        };
+        if let Some((_, count)) = buckets.get_mut(idx) {
+            *count += 1;
         }
     }
+    buckets
+}
 
-    let title = if num_days <= 90 {
-        "Recent Activity (last 3 months)"
-    } else if num_days <= 180 {
-        "Recent Activity (last 6 months)"
-    } else {
-        "Recent Activity (last year)"
-    };
+// Sparkline controls
+impl AppState {
+    /// Switch the activity sparkline between per-day, per-week and per-month bars
+    pub fn cycle_sparkline_bucket(&mut self) {
+        self.sparkline_bucket = match self.sparkline_bucket {
+            SparklineBucket::Day => SparklineBucket::Week,
+            SparklineBucket::Week => SparklineBucket::Month,
+            SparklineBucket::Month => SparklineBucket::Day,
+        };
+    }
 
-    let sparkline = Sparkline::default()
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(title)
-                .title_style(theme.title_style())
-                .border_style(theme.border_style())
-                .style(theme.secondary_background_style()),
-        )
-        .data(&buckets)
-        .style(theme.accent2_style());
-    f.render_widget(sparkline, area);
+    /// Step the activity sparkline through the 3/6/12-month ranges
+    pub fn cycle_sparkline_range(&mut self) {
+        let choices = SPARKLINE_RANGE_CHOICES;
+        let current = choices
+            .iter()
+            .position(|c| *c == self.sparkline_range_months)
+            .unwrap_or(0);
+        self.sparkline_range_months = choices[(current + 1) % choices.len()];
+    }
+
+    /// Count merge commits in the calendar and sparkline, or leave them out
+    pub fn toggle_activity_merges(&mut self) {
+        self.activity_filter.hide_merges = !self.activity_filter.hide_merges;
+        self.invalidate_repo_snapshot();
+    }
+
+    /// Count commits by the excluded authors (bots, by default) in the calendar and sparkline, or leave them out
+    pub fn toggle_activity_bots(&mut self) {
+        self.activity_filter.hide_authors = !self.activity_filter.hide_authors;
+        self.invalidate_repo_snapshot();
+    }
 }
@@ -0,0 +1,266 @@
+use crate::activity::ActivityKind;
+use crate::app::{AppState, PublishField, PublishSetup};
+use crate::forge::{ForgeKind, CREATE_KINDS};
+use crate::tui::theme::Theme;
+use ratatui::layout::{Constraint, Layout, Margin};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::{layout::Rect, Frame};
+use std::sync::mpsc::TryRecvError;
+use tui_textarea::TextArea;
+
+/// Render the offer to create a forge repository for origin: forge, name, visibility, URL
+pub fn render_publish_popup(f: &mut Frame, area: Rect, state: &mut AppState, theme: &Theme) {
+    let setup = match state.publish.as_mut() {
+        Some(setup) => setup,
+        None => return,
+    };
+
+    let popup_area = super::centered_rect(60, 13, area);
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title("Publish to a Forge")
+        .title_style(theme.title_style())
+        .borders(Borders::ALL)
+        .border_style(theme.focused_border_style())
+        .style(theme.secondary_background_style());
+    let inner = block.inner(popup_area).inner(Margin {
+        vertical: 0,
+        horizontal: 1,
+    });
+    f.render_widget(block, popup_area);
+
+    let chunks = Layout::vertical([
+        Constraint::Length(1), // Forge
+        Constraint::Length(1), // Spacer
+        Constraint::Length(3), // Repository name
+        Constraint::Length(1), // Spacer
+        Constraint::Length(1), // Visibility
+        Constraint::Length(1), // Remote URL
+        Constraint::Length(1), // Spacer
+        Constraint::Length(1), // Progress or error
+        Constraint::Length(1), // Help
+    ])
+    .split(inner);
+
+    let focus = setup.field;
+    let label = |field: PublishField, text: &'static str| {
+        let style = if focus == field {
+            theme.accent2_style()
+        } else {
+            theme.secondary_text_style()
+        };
+        Span::styled(format!("{:<13}", text), style)
+    };
+    let choice = |selected: bool, text: &'static str| {
+        let style = if selected {
+            theme.highlight_style()
+        } else {
+            theme.secondary_text_style()
+        };
+        [Span::styled(format!(" {} ", text), style), Span::raw("  ")]
+    };
+
+    let mut forge = vec![label(PublishField::Forge, "Forge:")];
+    for kind in CREATE_KINDS {
+        forge.extend(choice(kind == setup.kind, kind.title()));
+    }
+    f.render_widget(Paragraph::new(Line::from(forge)), chunks[0]);
+
+    let name_style = if setup.field == PublishField::Name {
+        theme.focused_border_style()
+    } else {
+        theme.border_style()
+    };
+    setup.name.set_block(
+        Block::default()
+            .title("Repository name")
+            .borders(Borders::ALL)
+            .border_style(name_style),
+    );
+    setup.name.set_style(theme.text_style());
+    f.render_widget(&setup.name, chunks[2]);
+
+    let mut visibility = vec![label(PublishField::Visibility, "Visibility:")];
+    visibility.extend(choice(setup.private, "Private"));
+    visibility.extend(choice(!setup.private, "Public"));
+    f.render_widget(Paragraph::new(Line::from(visibility)), chunks[4]);
+
+    let mut protocol = vec![label(PublishField::Protocol, "Remote URL:")];
+    protocol.extend(choice(setup.ssh, "SSH"));
+    protocol.extend(choice(!setup.ssh, "HTTPS"));
+    f.render_widget(Paragraph::new(Line::from(protocol)), chunks[5]);
+
+    let status = if setup.rx.is_some() {
+        Line::styled(
+            format!("Creating the repository on {}...", setup.kind.title()),
+            theme.muted_text_style(),
+        )
+    } else if let Some(message) = &setup.message {
+        Line::styled(message.as_str(), theme.warning_style())
+    } else {
+        Line::styled(
+            format!(
+                "Uses the API token in {}, then pushes the first commit",
+                crate::forge::create_token_variable(setup.kind)
+            ),
+            theme.muted_text_style(),
+        )
+    };
+    f.render_widget(Paragraph::new(status), chunks[7]);
+
+    f.render_widget(
+        Paragraph::new("[↑↓] Field  [←→] Change  [Enter] Create and Push  [Esc] Skip")
+            .style(theme.muted_text_style()),
+        chunks[8],
+    );
+}
+
+impl AppState {
+    /// Offer to create a forge repository named after the folder, unless origin exists
+    pub fn open_publish(&mut self) {
+        if crate::git::has_remote_origin().unwrap_or(false) {
+            return;
+        }
+        let folder = self
+            .current_dir
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let mut name = TextArea::new(vec![folder]);
+        name.move_cursor(tui_textarea::CursorMove::End);
+        let kind = self
+            .forge_override
+            .filter(|kind| CREATE_KINDS.contains(kind))
+            .unwrap_or(ForgeKind::GitHub);
+        self.publish = Some(PublishSetup {
+            kind,
+            name,
+            private: true,
+            ssh: true,
+            field: PublishField::Name,
+            rx: None,
+            message: None,
+        });
+    }
+
+    /// Move to the next or previous row of the publish popup
+    pub fn publish_move(&mut self, down: bool) {
+        if let Some(setup) = self.publish.as_mut() {
+            let fields = [
+                PublishField::Forge,
+                PublishField::Name,
+                PublishField::Visibility,
+                PublishField::Protocol,
+            ];
+            let idx = fields.iter().position(|f| *f == setup.field).unwrap_or(0);
+            let next = if down {
+                (idx + 1) % fields.len()
+            } else {
+                (idx + fields.len() - 1) % fields.len()
+            };
+            setup.field = fields[next];
+        }
+    }
+
+    /// Change the choice on the focused row of the publish popup
+    pub fn publish_change(&mut self, forward: bool) {
+        if let Some(setup) = self.publish.as_mut() {
+            match setup.field {
+                PublishField::Forge => {
+                    let idx = CREATE_KINDS
+                        .iter()
+                        .position(|k| *k == setup.kind)
+                        .unwrap_or(0);
+                    let next = if forward {
+                        (idx + 1) % CREATE_KINDS.len()
+                    } else {
+                        (idx + CREATE_KINDS.len() - 1) % CREATE_KINDS.len()
+                    };
+                    setup.kind = CREATE_KINDS[next];
+                }
+                PublishField::Visibility => setup.private = !setup.private,
+                PublishField::Protocol => setup.ssh = !setup.ssh,
+                PublishField::Name => {}
+            }
+        }
+    }
+
+    /// Create the repository on the forge in the background
+    pub fn start_publish(&mut self) {
+        let setup = match self.publish.as_mut() {
+            Some(setup) if setup.rx.is_none() => setup,
+            _ => return,
+        };
+        let name = setup.name.lines().join("").trim().to_string();
+        if name.is_empty() {
+            setup.message = Some("Enter a name for the repository".to_string());
+            return;
+        }
+        let (kind, private) = (setup.kind, setup.private);
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(crate::forge::create_repository(kind, &name, private));
+        });
+        setup.rx = Some(rx);
+        setup.message = None;
+    }
+
+    /// Once the repository exists, add it as origin and push the first commit
+    pub fn poll_publish(&mut self) {
+        let setup = match self.publish.as_mut() {
+            Some(setup) => setup,
+            None => return,
+        };
+        let result = match &setup.rx {
+            Some(rx) => rx.try_recv(),
+            None => return,
+        };
+        let created = match result {
+            Ok(Ok(created)) => created,
+            Ok(Err(e)) => {
+                setup.rx = None;
+                setup.message = Some(e);
+                return;
+            }
+            Err(TryRecvError::Disconnected) => {
+                setup.rx = None;
+                return;
+            }
+            Err(TryRecvError::Empty) => return,
+        };
+
+        let url = if setup.ssh {
+            created.ssh_url
+        } else {
+            created.https_url
+        };
+        let kind = setup.kind;
+        self.publish = None;
+        let branch = crate::git::get_current_branch().unwrap_or_else(|_| "main".to_string());
+        let result = crate::git::add_origin(&url, &branch);
+        self.record_activity(
+            ActivityKind::Publish,
+            &url,
+            &format!(
+                "{} API: create the repository, then libgit2: add it as origin",
+                kind.title()
+            ),
+            match &result {
+                Ok(()) => Ok(format!("Created {}", created.web_url)),
+                Err(e) => Err(e.to_string()),
+            },
+        );
+        match result {
+            Ok(()) => self.perform_push(),
+            Err(e) => self.show_error(
+                "Publish Failed",
+                &format!(
+                    "{} was created, but adding it as origin failed:\n\n{}",
+                    created.web_url, e
+                ),
+            ),
+        }
+    }
+}
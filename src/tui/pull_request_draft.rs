@@ -0,0 +1,400 @@
+use crate::activity::ActivityKind;
+use crate::app::{AppState, DraftField, PullRequestDraft};
+use crate::git::CommitSummary;
+use crate::tui::theme::Theme;
+use ratatui::layout::{Constraint, Direction, Layout, Margin};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::{layout::Rect, Frame};
+use std::sync::mpsc::TryRecvError;
+use tui_textarea::TextArea;
+
+/// Render the pull request being written for the current branch
+pub fn render_pull_request_draft_popup(
+    f: &mut Frame,
+    area: Rect,
+    state: &mut AppState,
+    theme: &Theme,
+) {
+    let name = match &state.update_forge {
+        Some(forge) => forge.kind.pull_request_name().trim_end_matches('s'),
+        None => "Pull Request",
+    };
+    let draft = match state.pull_request_draft.as_mut() {
+        Some(draft) => draft,
+        None => return,
+    };
+
+    let popup_area = area.inner(Margin {
+        vertical: 3,
+        horizontal: area.width / 8,
+    });
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!("New {} - {}", name, draft.branch))
+        .title_style(theme.popup_title_style())
+        .border_style(theme.popup_border_style())
+        .style(theme.popup_background_style());
+    f.render_widget(block, popup_area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Base branch
+            Constraint::Length(3), // Title
+            Constraint::Min(3),    // Body
+            Constraint::Length(1), // Progress, error or result
+            Constraint::Length(1), // Help
+        ])
+        .split(popup_area.inner(Margin {
+            vertical: 1,
+            horizontal: 1,
+        }));
+
+    let base_style = if draft.field == DraftField::Base {
+        theme.highlight_style()
+    } else {
+        theme.text_style()
+    };
+    let base = draft
+        .bases
+        .get(draft.base)
+        .map(String::as_str)
+        .unwrap_or("");
+    f.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled("Into: ", theme.accent2_style()),
+            Span::styled(format!(" ◀ {} ▶ ", base), base_style),
+            Span::styled(format!("  from {}", draft.branch), theme.muted_text_style()),
+        ])),
+        rows[0],
+    );
+
+    for (field, title) in [
+        (DraftField::Title, "Title"),
+        (DraftField::Body, "Description"),
+    ] {
+        let border = if draft.field == field {
+            theme.focused_border_style()
+        } else {
+            theme.border_style()
+        };
+        let input = match field {
+            DraftField::Title => &mut draft.title,
+            _ => &mut draft.body,
+        };
+        input.set_block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .border_style(border),
+        );
+        input.set_style(theme.text_style());
+    }
+    f.render_widget(&draft.title, rows[1]);
+    f.render_widget(&draft.body, rows[2]);
+
+    let status = if draft.rx.is_some() {
+        Line::styled(format!("Opening the {}...", name), theme.muted_text_style())
+    } else if let Some(url) = &draft.created {
+        Line::styled(format!("Opened {}", url), theme.success_style())
+    } else if let Some(message) = &draft.message {
+        Line::styled(message.as_str(), theme.warning_style())
+    } else {
+        Line::from("")
+    };
+    f.render_widget(Paragraph::new(status), rows[3]);
+
+    let help = if draft.created.is_some() {
+        "[Ctrl+O] Open in Browser  [Enter/Esc] Close"
+    } else {
+        "[Tab] Field  [←→] Base  [Ctrl+S] Create  [Ctrl+O] Open Form in Browser  [Esc] Cancel"
+    };
+    f.render_widget(
+        Paragraph::new(Line::styled(help, theme.muted_text_style())),
+        rows[4],
+    );
+}
+
+impl AppState {
+    /// Open a pull request draft for the current branch, explaining why when there can't be one
+    pub fn open_pull_request_draft(&mut self) {
+        let branch = match crate::git::get_current_branch() {
+            Ok(branch) => branch,
+            Err(e) => {
+                self.show_error(
+                    "New Pull Request",
+                    &format!("No branch is checked out:\n\n{}", e),
+                );
+                return;
+            }
+        };
+        match self.pull_request_draft_for(&branch) {
+            Ok(draft) => self.pull_request_draft = Some(draft),
+            Err(message) => self.show_error("New Pull Request", &message),
+        }
+    }
+
+    /// Open a pull request draft for a branch just pushed, if it has anything to propose
+    pub fn offer_pull_request(&mut self, branch: &str) {
+        if let Ok(draft) = self.pull_request_draft_for(branch) {
+            self.pull_request_draft = Some(draft);
+        }
+    }
+
+    fn pull_request_draft_for(&self, branch: &str) -> Result<PullRequestDraft, String> {
+        if self.update_forge.is_none() {
+            return Err("The origin remote isn't on a recognized forge.".to_string());
+        }
+        if !crate::git::remote_branch_exists("origin", branch) {
+            return Err(format!(
+                "Push {} before opening a pull request for it.",
+                branch
+            ));
+        }
+        let bases = crate::git::origin_branches().map_err(|e| e.to_string())?;
+        let base = bases
+            .iter()
+            .position(|base| base != branch)
+            .ok_or_else(|| "Origin has no other branch to open a pull request into.".to_string())?;
+        let commits =
+            crate::git::commits_ahead_of(branch, &bases[base]).map_err(|e| e.to_string())?;
+        if commits.is_empty() {
+            return Err(format!(
+                "{} has no commits that {} doesn't have.",
+                branch, bases[base]
+            ));
+        }
+
+        let (title, body) = draft_text(branch, &commits);
+        let mut title = TextArea::new(vec![title]);
+        title.move_cursor(tui_textarea::CursorMove::End);
+        let body = TextArea::new(body.lines().map(str::to_string).collect());
+        Ok(PullRequestDraft {
+            branch: branch.to_string(),
+            bases,
+            base,
+            title,
+            body,
+            field: DraftField::Title,
+            rx: None,
+            created: None,
+            message: None,
+        })
+    }
+
+    /// Move focus to the next or previous part of the draft
+    pub fn draft_move(&mut self, forward: bool) {
+        if let Some(draft) = self.pull_request_draft.as_mut() {
+            draft.field = match (draft.field, forward) {
+                (DraftField::Base, true) | (DraftField::Body, false) => DraftField::Title,
+                (DraftField::Title, true) | (DraftField::Base, false) => DraftField::Body,
+                (DraftField::Body, true) | (DraftField::Title, false) => DraftField::Base,
+            };
+        }
+    }
+
+    /// Pick the next or previous branch to merge into, skipping the branch itself
+    pub fn draft_cycle_base(&mut self, forward: bool) {
+        if let Some(draft) = self.pull_request_draft.as_mut() {
+            let len = draft.bases.len();
+            for step in 1..len {
+                let next = if forward {
+                    (draft.base + step) % len
+                } else {
+                    (draft.base + len - step) % len
+                };
+                if draft.bases[next] != draft.branch {
+                    draft.base = next;
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Create the pull request through the forge's API, or open the filled-in form in the
+    /// browser when there is no API token
+    pub fn submit_pull_request(&mut self) {
+        let forge = match &self.update_forge {
+            Some(forge) => forge.clone(),
+            None => return,
+        };
+        let draft = match self.pull_request_draft.as_mut() {
+            Some(draft) if draft.rx.is_none() && draft.created.is_none() => draft,
+            _ => return,
+        };
+        let title = draft.title.lines().join(" ").trim().to_string();
+        if title.is_empty() {
+            draft.message = Some("Give the pull request a title".to_string());
+            return;
+        }
+        if !crate::forge::has_token(forge.host()) {
+            self.open_pull_request_draft_in_browser();
+            if let Some(draft) = self.pull_request_draft.as_mut() {
+                draft.message = Some(format!(
+                    "No API token in {}, so the form was opened in the browser",
+                    crate::forge::token_variable(forge.host())
+                ));
+            }
+            return;
+        }
+
+        let branch = draft.branch.clone();
+        let base = draft.bases[draft.base].clone();
+        let body = draft.body.lines().join("\n").trim().to_string();
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(forge.create_pull_request(&branch, &base, &title, &body));
+        });
+        draft.rx = Some(rx);
+        draft.message = None;
+    }
+
+    /// Open the created pull request, or the forge's form filled in with the draft
+    pub fn open_pull_request_draft_in_browser(&mut self) {
+        let (forge, draft) = match (&self.update_forge, self.pull_request_draft.as_mut()) {
+            (Some(forge), Some(draft)) => (forge, draft),
+            _ => return,
+        };
+        let url = match &draft.created {
+            Some(url) => url.clone(),
+            None => forge.compare_url(
+                &draft.branch,
+                &draft.bases[draft.base],
+                draft.title.lines().join(" ").trim(),
+                draft.body.lines().join("\n").trim(),
+            ),
+        };
+        if let Err(e) = crate::forge::open_in_browser(&url) {
+            draft.message = Some(format!("Could not open a browser: {}", e));
+        }
+    }
+
+    /// Pick up the result of creating the pull request
+    pub fn poll_pull_request_draft(&mut self) {
+        let draft = match self.pull_request_draft.as_mut() {
+            Some(draft) => draft,
+            None => return,
+        };
+        let result = match &draft.rx {
+            Some(rx) => match rx.try_recv() {
+                Ok(result) => result,
+                Err(TryRecvError::Disconnected) => Err("The request stopped unexpectedly".into()),
+                Err(TryRecvError::Empty) => return,
+            },
+            None => return,
+        };
+        draft.rx = None;
+        let branch = draft.branch.clone();
+        let base = draft.bases[draft.base].clone();
+        match &result {
+            Ok(url) => draft.created = Some(url.clone()),
+            Err(e) => draft.message = Some(e.clone()),
+        }
+        self.record_activity(
+            ActivityKind::PullRequest,
+            &branch,
+            &format!(
+                "forge API: open a pull request from {} into {}",
+                branch, base
+            ),
+            result.map(|url| format!("Opened {}", url)),
+        );
+    }
+}
+
+/// Headings for conventional commit types, in the order the description lists them
+const SECTIONS: [(&str, &str); 6] = [
+    ("feat", "Features"),
+    ("fix", "Fixes"),
+    ("perf", "Performance"),
+    ("refactor", "Refactoring"),
+    ("docs", "Documentation"),
+    ("test", "Tests"),
+];
+
+/// The type and description of a conventional commit subject, e.g. `feat(ui)!: add tabs`
+fn conventional(subject: &str) -> Option<(&str, &str)> {
+    let (head, description) = subject.split_once(": ")?;
+    let kind = head.split('(').next()?.trim_end_matches('!');
+    let valid = !kind.is_empty() && kind.chars().all(|c| c.is_ascii_lowercase());
+    valid.then(|| (kind, description.trim()))
+}
+
+/// A title and description for a pull request of `commits`, oldest first
+///
+/// A single commit gives its own subject and body. Several give a title from the
+/// branch name, typed when every commit shares a conventional type, and a list of
+/// their subjects grouped by type.
+fn draft_text(branch: &str, commits: &[CommitSummary]) -> (String, String) {
+    if let [commit] = commits {
+        let body = commit
+            .message
+            .split_once('\n')
+            .map(|(_, body)| body.trim().to_string())
+            .unwrap_or_default();
+        return (commit.summary.clone(), body);
+    }
+
+    let name = branch
+        .rsplit('/')
+        .next()
+        .unwrap_or(branch)
+        .replace(['-', '_'], " ");
+    let kinds: Vec<Option<&str>> = commits
+        .iter()
+        .map(|c| conventional(&c.summary).map(|(kind, _)| kind))
+        .collect();
+    let title = match kinds.first() {
+        Some(Some(kind)) if kinds.iter().all(|k| k == &Some(*kind)) => {
+            format!("{}: {}", kind, name)
+        }
+        _ => name,
+    };
+
+    if kinds.iter().all(Option::is_none) {
+        let body = commits
+            .iter()
+            .map(|c| format!("- {}", c.summary))
+            .collect::<Vec<_>>()
+            .join("\n");
+        return (title, body);
+    }
+
+    let mut sections: Vec<(&str, Vec<&str>)> = Vec::new();
+    for commit in commits {
+        let (heading, line) = match conventional(&commit.summary) {
+            Some((kind, description)) => {
+                let heading = SECTIONS
+                    .iter()
+                    .find(|(k, _)| *k == kind)
+                    .map_or("Other Changes", |(_, heading)| *heading);
+                (heading, description)
+            }
+            None => ("Other Changes", commit.summary.as_str()),
+        };
+        match sections.iter_mut().find(|(h, _)| *h == heading) {
+            Some((_, lines)) => lines.push(line),
+            None => sections.push((heading, vec![line])),
+        }
+    }
+    let order = |heading: &str| {
+        SECTIONS
+            .iter()
+            .position(|(_, h)| *h == heading)
+            .unwrap_or(SECTIONS.len())
+    };
+    sections.sort_by_key(|(heading, _)| order(heading));
+
+    let body = sections
+        .iter()
+        .map(|(heading, lines)| {
+            let items: Vec<String> = lines.iter().map(|line| format!("- {}", line)).collect();
+            format!("## {}\n\n{}", heading, items.join("\n"))
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    (title, body)
+}
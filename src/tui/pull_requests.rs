@@ -0,0 +1,258 @@
+use crate::app::{AppState, PullRequestsView};
+use crate::forge::CiStatus;
+use crate::tui::text;
+use crate::tui::theme::Theme;
+use ratatui::layout::{Constraint, Direction, Layout, Margin};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph};
+use ratatui::{layout::Rect, Frame};
+use std::sync::mpsc::TryRecvError;
+
+/// Render the open pull requests of the forge origin is hosted on
+pub fn render_pull_requests_popup(f: &mut Frame, area: Rect, state: &AppState, theme: &Theme) {
+    let (view, forge) = match (&state.pull_requests, &state.update_forge) {
+        (Some(view), Some(forge)) => (view, forge),
+        _ => return,
+    };
+
+    let popup_area = area.inner(Margin {
+        vertical: 3,
+        horizontal: area.width / 8,
+    });
+    f.render_widget(Clear, popup_area);
+
+    let title = format!(
+        "{} - {}/{} on {}",
+        forge.kind.pull_request_name(),
+        forge.owner,
+        forge.repo,
+        forge.kind.title()
+    );
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .title_style(theme.popup_title_style())
+        .border_style(theme.popup_border_style())
+        .style(theme.popup_background_style());
+    f.render_widget(block, popup_area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(popup_area.inner(Margin {
+            vertical: 1,
+            horizontal: 1,
+        }));
+
+    if view.pulls.is_empty() {
+        let note = if view.rx.is_some() {
+            Line::styled("Loading...", theme.muted_text_style())
+        } else if view.message.is_some() {
+            Line::from("")
+        } else {
+            Line::styled("No open pull requests", theme.muted_text_style())
+        };
+        f.render_widget(Paragraph::new(note), rows[0]);
+    } else {
+        let width = rows[0].width as usize;
+        let items: Vec<ListItem> = view
+            .pulls
+            .iter()
+            .map(|pull| {
+                let number = format!("#{:<5} ", pull.number);
+                let details = format!("  {} ← {}", pull.author, pull.branch);
+                let title_width =
+                    width.saturating_sub(2 + text::width(&number) + text::width(&details));
+                ListItem::new(Line::from(vec![
+                    Span::styled(number, theme.accent2_style()),
+                    Span::styled(text::truncate(&pull.title, title_width), theme.text_style()),
+                    Span::styled(details, theme.muted_text_style()),
+                ]))
+            })
+            .collect();
+        let mut list_state = ListState::default().with_selected(Some(view.selected));
+        let list = List::new(items)
+            .highlight_style(theme.highlight_style())
+            .highlight_symbol("► ");
+        f.render_stateful_widget(list, rows[0], &mut list_state);
+    }
+
+    let footer = match &view.message {
+        Some(message) => Line::styled(message.as_str(), theme.warning_style()),
+        None => Line::styled(
+            "[Enter] Open in browser  [N] New from this branch  [W] All on the web  [Esc] Close",
+            theme.muted_text_style(),
+        ),
+    };
+    f.render_widget(Paragraph::new(footer), rows[1]);
+}
+
+impl AppState {
+    /// Work out which forge origin is hosted on, from its URL and the gitix.forge setting
+    pub fn detect_forge(&mut self) {
+        let detected = self
+            .update_remote_status
+            .as_ref()
+            .and_then(|status| crate::forge::detect(&status.url, self.forge_override));
+        if detected != self.update_forge {
+            self.forge_ci = None;
+        }
+        self.update_forge = detected;
+    }
+
+    /// Look up the forge's CI result for HEAD in the background
+    pub fn start_forge_status(&mut self) {
+        let forge = match &self.update_forge {
+            Some(forge) if self.forge_ci_rx.is_none() => forge.clone(),
+            _ => return,
+        };
+        let sha = match crate::git::head_id() {
+            Ok(sha) => sha,
+            Err(_) => return,
+        };
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(forge.commit_status(&sha));
+        });
+        self.forge_ci_rx = Some(rx);
+    }
+
+    /// Pick up finished forge lookups: the CI result and the pull request listing
+    pub fn poll_forge(&mut self) {
+        if let Some(rx) = &self.forge_ci_rx {
+            match rx.try_recv() {
+                Ok(result) => {
+                    self.forge_ci_rx = None;
+                    // A failed lookup (offline, private repository) just leaves the status unknown
+                    self.forge_ci = result.ok();
+                }
+                Err(TryRecvError::Disconnected) => self.forge_ci_rx = None,
+                Err(TryRecvError::Empty) => {}
+            }
+        }
+
+        let view = match self.pull_requests.as_mut() {
+            Some(view) => view,
+            None => return,
+        };
+        let result = match &view.rx {
+            Some(rx) => rx.try_recv(),
+            None => return,
+        };
+        match result {
+            Ok(Ok(pulls)) => {
+                view.rx = None;
+                view.pulls = pulls;
+            }
+            Ok(Err(e)) => {
+                view.rx = None;
+                view.message = Some(e);
+            }
+            Err(TryRecvError::Disconnected) => view.rx = None,
+            Err(TryRecvError::Empty) => {}
+        }
+    }
+
+    /// Open the pull request popup and list the forge's open pull requests in the background
+    pub fn open_pull_requests(&mut self) {
+        let forge = match &self.update_forge {
+            Some(forge) => forge.clone(),
+            None => {
+                self.show_forge_unknown();
+                return;
+            }
+        };
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(forge.list_pull_requests());
+        });
+        self.pull_requests = Some(PullRequestsView {
+            pulls: Vec::new(),
+            selected: 0,
+            rx: Some(rx),
+            message: None,
+        });
+    }
+
+    /// Move the selection up or down the pull requests
+    pub fn pull_requests_navigate(&mut self, down: bool) {
+        if let Some(view) = self.pull_requests.as_mut() {
+            let last = view.pulls.len().saturating_sub(1);
+            view.selected = if down {
+                (view.selected + 1).min(last)
+            } else {
+                view.selected.saturating_sub(1)
+            };
+        }
+    }
+
+    /// Open the selected pull request in the browser
+    pub fn open_selected_pull_request(&mut self) {
+        let url = self
+            .pull_requests
+            .as_ref()
+            .and_then(|view| view.pulls.get(view.selected))
+            .map(|pull| pull.url.clone());
+        if let Some(url) = url {
+            self.open_forge_url(&url);
+        }
+    }
+
+    /// Open one of the forge's pages for the current branch: its files, its new pull
+    /// request form, or the list of pull requests
+    pub fn open_forge_page(&mut self, page: ForgePage) {
+        let forge = match &self.update_forge {
+            Some(forge) => forge.clone(),
+            None => {
+                self.show_forge_unknown();
+                return;
+            }
+        };
+        let branch = crate::git::get_current_branch().unwrap_or_else(|_| "HEAD".to_string());
+        let url = match page {
+            ForgePage::Branch => forge.branch_url(&branch),
+            ForgePage::NewPullRequest => forge.new_pull_request_url(&branch),
+            ForgePage::PullRequests => forge.pull_requests_url(),
+        };
+        self.open_forge_url(&url);
+    }
+
+    fn open_forge_url(&mut self, url: &str) {
+        if let Err(e) = crate::forge::open_in_browser(url) {
+            let message = format!("Could not open a browser: {}", e);
+            match self.pull_requests.as_mut() {
+                Some(view) => view.message = Some(message),
+                None => self.show_error("Open in Browser", &format!("{}\n\n{}", message, url)),
+            }
+        }
+    }
+
+    fn show_forge_unknown(&mut self) {
+        self.show_error(
+            "Unknown Forge",
+            "The origin remote isn't on a recognized forge.\n\n\
+             Pick GitHub, GitLab, Gitea/Forgejo or Bitbucket under Settings > Forge \
+             for a self-hosted instance.",
+        );
+    }
+}
+
+/// Forge pages the Update tab can open
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ForgePage {
+    Branch,
+    NewPullRequest,
+    PullRequests,
+}
+
+/// The CI result shown on the Update tab, with the style it is shown in
+pub fn ci_span<'a>(state: &AppState, theme: &Theme) -> Span<'a> {
+    match (state.forge_ci, state.forge_ci_rx.is_some()) {
+        (_, true) => Span::styled("checking...", theme.muted_text_style()),
+        (Some(CiStatus::Success), _) => Span::styled("✓ passing", theme.success_style()),
+        (Some(CiStatus::Pending), _) => Span::styled("● running", theme.warning_style()),
+        (Some(CiStatus::Failure), _) => Span::styled("✗ failing", theme.error_style()),
+        (Some(CiStatus::None), _) => Span::styled("no checks", theme.muted_text_style()),
+        (None, _) => Span::styled("unknown", theme.muted_text_style()),
+    }
+}
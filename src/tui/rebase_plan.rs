@@ -0,0 +1,260 @@
+use crate::activity::ActivityKind;
+use crate::app::{AppState, RebasePlanView};
+use crate::git::{PlannedRebaseOutcome, RebaseAction, RebaseStep};
+use crate::tui::text;
+use crate::tui::theme::Theme;
+use ratatui::layout::{Constraint, Layout, Margin};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::{layout::Rect, Frame};
+
+/// How many commits the plan leaves on top of the upstream
+fn kept_commits(steps: &[RebaseStep]) -> usize {
+    steps
+        .iter()
+        .filter(|step| step.action == RebaseAction::Pick)
+        .count()
+}
+
+/// Render the interactive rebase planner: one row per commit, oldest first, with what happens to it
+pub fn render_rebase_plan_popup(f: &mut Frame, area: Rect, view: &RebasePlanView, theme: &Theme) {
+    let height = (view.steps.len() as u16 + 9).min(area.height.saturating_sub(2));
+    let popup_area = super::centered_rect(80, height, area);
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!("Interactive Rebase of {} onto {}", view.branch, view.upstream))
+        .title_style(theme.popup_title_style())
+        .border_style(theme.popup_border_style())
+        .style(theme.popup_background_style());
+    let inner = block.inner(popup_area).inner(Margin {
+        vertical: 0,
+        horizontal: 1,
+    });
+    f.render_widget(block, popup_area);
+
+    let chunks = Layout::vertical([
+        Constraint::Length(2), // What the plan does
+        Constraint::Min(1),    // Steps
+        Constraint::Length(2), // Problem with the plan, if any
+        Constraint::Length(1), // Help
+    ])
+    .split(inner);
+
+    let kept = kept_commits(&view.steps);
+    f.render_widget(
+        Paragraph::new(format!(
+            "Oldest first. {} commit{} become{} {} on top of {}.",
+            view.steps.len(),
+            if view.steps.len() == 1 { "" } else { "s" },
+            if view.steps.len() == 1 { "s" } else { "" },
+            kept,
+            view.upstream
+        ))
+        .style(theme.secondary_text_style()),
+        chunks[0],
+    );
+
+    // Keep the selected row in view
+    let visible = chunks[1].height as usize;
+    let offset = (view.selected + 1).saturating_sub(visible);
+    let width = chunks[1].width as usize;
+    let lines: Vec<Line> = view
+        .steps
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(visible)
+        .map(|(idx, step)| {
+            let (marker, style) = match step.action {
+                RebaseAction::Pick => ("  ", theme.text_style()),
+                RebaseAction::Squash | RebaseAction::Fixup => ("↳ ", theme.accent2_style()),
+                RebaseAction::Drop => ("  ", theme.muted_text_style()),
+            };
+            let row = format!(
+                "{}{:<7} {}  {}",
+                marker,
+                step.action.as_str(),
+                step.commit.short_id,
+                step.commit.summary
+            );
+            let row = text::truncate(&row, width);
+            if idx == view.selected {
+                Line::from(Span::styled(row, theme.highlight_style()))
+            } else {
+                Line::from(Span::styled(row, style))
+            }
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines), chunks[1]);
+
+    if let Some(message) = &view.message {
+        f.render_widget(
+            Paragraph::new(message.as_str()).style(theme.warning_style()),
+            chunks[2],
+        );
+    }
+    f.render_widget(
+        Paragraph::new(
+            "[↑↓] Select  [Shift+↑↓/K/J] Move  [P/S/F/D] Pick/Squash/Fixup/Drop  \
+             [Enter] Rebase  [Esc] Cancel",
+        )
+        .style(theme.secondary_text_style()),
+        chunks[3],
+    );
+}
+
+impl AppState {
+    /// Open the planner on the commits the current branch has on top of its upstream
+    pub fn request_rebase_plan(&mut self) {
+        if self.refuse_read_only("Rebasing") {
+            return;
+        }
+        match crate::git::rebase_plan_commits() {
+            Ok((upstream, commits)) => {
+                let branch =
+                    crate::git::get_current_branch().unwrap_or_else(|_| "HEAD".to_string());
+                self.rebase_plan = Some(RebasePlanView {
+                    branch,
+                    upstream,
+                    steps: commits
+                        .into_iter()
+                        .map(|commit| RebaseStep {
+                            action: RebaseAction::Pick,
+                            commit,
+                        })
+                        .collect(),
+                    selected: 0,
+                    message: None,
+                });
+            }
+            Err(e) => self.show_error("Interactive Rebase", &e.to_string()),
+        }
+    }
+
+    /// Select the row `delta` rows away in the planner
+    pub fn rebase_plan_navigate(&mut self, delta: isize) {
+        if let Some(view) = self.rebase_plan.as_mut() {
+            let last = view.steps.len().saturating_sub(1);
+            view.selected = view.selected.saturating_add_signed(delta).min(last);
+        }
+    }
+
+    /// Move the selected commit one row earlier or later in the plan
+    pub fn rebase_plan_move(&mut self, earlier: bool) {
+        if let Some(view) = self.rebase_plan.as_mut() {
+            let from = view.selected;
+            let to = if earlier {
+                from.saturating_sub(1)
+            } else {
+                (from + 1).min(view.steps.len().saturating_sub(1))
+            };
+            view.steps.swap(from, to);
+            view.selected = to;
+            view.message = None;
+        }
+    }
+
+    /// Choose what happens to the selected commit
+    pub fn rebase_plan_mark(&mut self, action: RebaseAction) {
+        if let Some(view) = self.rebase_plan.as_mut() {
+            if let Some(step) = view.steps.get_mut(view.selected) {
+                step.action = action;
+                view.message = None;
+            }
+        }
+    }
+
+    /// Run the plan, unless it folds its first commit into nothing
+    pub fn confirm_rebase_plan(&mut self) {
+        let first_kept = self.rebase_plan.as_ref().and_then(|view| {
+            view.steps
+                .iter()
+                .find(|step| step.action != RebaseAction::Drop)
+                .map(|step| step.action)
+        });
+        let problem = match first_kept {
+            Some(RebaseAction::Pick) => None,
+            Some(_) => Some(
+                "The first commit kept has to be a pick: there's nothing before it to fold into",
+            ),
+            None => Some("Every commit is dropped; pick at least one, or reset to the upstream"),
+        };
+        if let Some(problem) = problem {
+            if let Some(view) = self.rebase_plan.as_mut() {
+                view.message = Some(problem.to_string());
+            }
+            return;
+        }
+        let view = match self.rebase_plan.take() {
+            Some(view) => view,
+            None => return,
+        };
+
+        let steps: Vec<(RebaseAction, String)> = view
+            .steps
+            .iter()
+            .map(|step| (step.action, step.commit.id.clone()))
+            .collect();
+        let plan: Vec<String> = view
+            .steps
+            .iter()
+            .map(|step| format!("{} {}", step.action.as_str(), step.commit.short_id))
+            .collect();
+        let result = crate::git::start_planned_rebase(&steps);
+        self.finish_planned_rebase_step(
+            &view.branch,
+            &format!(
+                "libgit2: rebase -i {} ({}), one cherry-pick per step",
+                view.upstream,
+                plan.join(", ")
+            ),
+            result,
+        );
+    }
+
+    /// Commit the step the rebase stopped on, now its conflicts are resolved, and carry on
+    pub fn continue_planned_rebase(&mut self) {
+        let branch = crate::git::planned_rebase_branch().unwrap_or_else(|| "HEAD".to_string());
+        let result = crate::git::continue_planned_rebase();
+        self.finish_planned_rebase_step(&branch, "libgit2: rebase --continue", result);
+    }
+
+    /// Log how far the rebase got, then open the conflicts view or report it done
+    fn finish_planned_rebase_step(
+        &mut self,
+        branch: &str,
+        operation: &str,
+        result: Result<PlannedRebaseOutcome, crate::git::GitError>,
+    ) {
+        self.record_activity(
+            ActivityKind::Rebase,
+            branch,
+            operation,
+            match &result {
+                Ok(PlannedRebaseOutcome::Rebased(tip)) => {
+                    Ok(format!("Rebased {}, now at {}", branch, tip))
+                }
+                Ok(PlannedRebaseOutcome::Conflicts(paths)) => Err(format!(
+                    "Rebase stopped on conflicts in {} file{}",
+                    paths.len(),
+                    if paths.len() == 1 { "" } else { "s" }
+                )),
+                Err(e) => Err(e.to_string()),
+            },
+        );
+        match result {
+            Ok(PlannedRebaseOutcome::Rebased(tip)) => {
+                self.conflicts = None;
+                self.history_message = Some(format!("✓ Rebased {}, now at {}", branch, tip));
+            }
+            Ok(PlannedRebaseOutcome::Conflicts(_)) => self.open_conflicts(),
+            Err(e) => self.show_error("Rebase Failed", &format!("Failed to rebase:\n\n{}", e)),
+        }
+        self.refresh_save_changes_git_status();
+        self.invalidate_status_git_status();
+        self.reload_history_if_moved();
+        self.branches.loaded = false;
+    }
+}
@@ -0,0 +1,248 @@
+use crate::activity::ActivityKind;
+use crate::app::{AppState, ReflogView};
+use crate::git::{UndoKind, UndoPlan};
+use crate::tui::text;
+use crate::tui::theme::Theme;
+use ratatui::layout::{Constraint, Layout, Margin};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::{layout::Rect, Frame};
+
+/// HEAD movements read into the popup
+const REFLOG_LIMIT: usize = 200;
+
+/// Width of the id and action columns
+const ID_WIDTH: usize = 8;
+const ACTION_WIDTH: usize = 18;
+
+/// Render HEAD's reflog, newest first, with the selected entry highlighted
+pub fn render_reflog_popup(f: &mut Frame, area: Rect, view: &ReflogView, theme: &Theme) {
+    let popup_area = area.inner(Margin {
+        vertical: 2,
+        horizontal: 4,
+    });
+    f.render_widget(Clear, popup_area);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!("Reflog - HEAD ({} entries)", view.entries.len()))
+        .title_style(theme.popup_title_style())
+        .border_style(theme.popup_border_style())
+        .style(theme.popup_background_style());
+    let inner = block.inner(popup_area).inner(Margin {
+        vertical: 0,
+        horizontal: 1,
+    });
+    f.render_widget(block, popup_area);
+
+    let chunks = Layout::vertical([
+        Constraint::Min(1),    // Entries
+        Constraint::Length(1), // Help
+    ])
+    .split(inner);
+
+    let height = chunks[0].height as usize;
+    let offset = (view.selected + 1).saturating_sub(height);
+    let age_width = 14;
+    let message_width =
+        (chunks[0].width as usize).saturating_sub(ID_WIDTH + ACTION_WIDTH + age_width);
+    let lines: Vec<Line> = if view.entries.is_empty() {
+        vec![Line::styled(
+            "HEAD has no reflog entries yet",
+            theme.muted_text_style(),
+        )]
+    } else {
+        view.entries
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(height)
+            .map(|(idx, entry)| {
+                let line = Line::from(vec![
+                    Span::styled(
+                        format!("{:<ID_WIDTH$}", entry.short_id),
+                        theme.accent_style(),
+                    ),
+                    Span::styled(
+                        format!(
+                            "{:<ACTION_WIDTH$}",
+                            text::truncate(&entry.action, ACTION_WIDTH - 1)
+                        ),
+                        theme.accent2_style(),
+                    ),
+                    Span::styled(
+                        format!(
+                            "{:<message_width$}",
+                            text::truncate(&entry.message, message_width)
+                        ),
+                        theme.text_style(),
+                    ),
+                    Span::styled(
+                        crate::git::format_relative_time(entry.time),
+                        theme.timestamp_style(),
+                    ),
+                ]);
+                if idx == view.selected {
+                    line.style(theme.highlight_style())
+                } else {
+                    line
+                }
+            })
+            .collect()
+    };
+    f.render_widget(Paragraph::new(lines), chunks[0]);
+
+    f.render_widget(
+        Paragraph::new("[↑↓] Select  [Z] Undo last operation  [Esc] Close")
+            .style(theme.secondary_text_style()),
+        chunks[1],
+    );
+}
+
+/// Render the confirmation for an undo, saying exactly where HEAD goes and what is kept
+pub fn render_undo_popup(f: &mut Frame, area: Rect, plan: &UndoPlan, theme: &Theme) {
+    let popup_area = super::centered_rect(64, 11, area);
+    f.render_widget(Clear, popup_area);
+
+    let target = &plan.target[..7];
+    let (title, question, effect) = match plan.kind {
+        UndoKind::Commit => (
+            "Undo Commit",
+            format!("Undo commit {} \"{}\"?", plan.entry.short_id, plan.entry.message),
+            format!("HEAD goes back to {}. The commit's changes stay staged.", target),
+        ),
+        UndoKind::Amend => (
+            "Undo Amend",
+            format!("Undo amending {} \"{}\"?", plan.entry.short_id, plan.entry.message),
+            format!(
+                "HEAD goes back to {}, the commit as it was before. \
+                 What the amend added stays staged.",
+                target
+            ),
+        ),
+        UndoKind::Merge => (
+            "Undo Merge",
+            format!("Undo \"{}\"?", plan.entry.message),
+            format!(
+                "The branch goes back to {} and tracked files are reset to match it. \
+                 Untracked files are kept.",
+                target
+            ),
+        ),
+        UndoKind::Rebase => (
+            "Undo Rebase",
+            format!("Undo the rebase that ended at {}?", plan.entry.short_id),
+            format!(
+                "The branch goes back to {}, where it was before the rebase, and tracked \
+                 files are reset to match it. Untracked files are kept.",
+                target
+            ),
+        ),
+        UndoKind::Reset if plan.hard => (
+            "Undo Reset",
+            format!("Undo \"{}\"?", plan.entry.message),
+            format!("HEAD, the staged changes and tracked files go back to {}.", target),
+        ),
+        UndoKind::Reset => (
+            "Undo Reset",
+            format!("Undo \"{}\"?", plan.entry.message),
+            format!("HEAD goes back to {}. Your current changes are kept as they are.", target),
+        ),
+    };
+    let lines = vec![
+        Line::styled(question, theme.text_style()),
+        Line::from(""),
+        Line::styled(effect, theme.secondary_text_style()),
+        Line::from(""),
+        Line::styled(
+            "The undo is recorded in the reflog too, so it can be undone in turn.",
+            theme.muted_text_style(),
+        ),
+        Line::from(""),
+        Line::styled("[Y] Undo  [N] Cancel", theme.secondary_text_style()),
+    ];
+    let popup = Paragraph::new(lines)
+        .wrap(ratatui::widgets::Wrap { trim: true })
+        .block(
+            Block::default()
+                .title(title)
+                .title_style(theme.popup_title_style())
+                .borders(Borders::ALL)
+                .border_style(theme.popup_border_style())
+                .style(theme.popup_background_style()),
+        );
+    f.render_widget(popup, popup_area);
+}
+
+impl AppState {
+    /// Open the reflog popup on HEAD's newest movement
+    pub fn open_reflog(&mut self) {
+        match crate::git::get_reflog(REFLOG_LIMIT) {
+            Ok(entries) => {
+                self.reflog_view = Some(ReflogView {
+                    entries,
+                    selected: 0,
+                })
+            }
+            Err(e) => {
+                self.show_error("Reflog", &format!("Failed to read HEAD's reflog:\n\n{}", e))
+            }
+        }
+    }
+
+    /// Move the reflog selection by `delta` entries, stopping at either end
+    pub fn reflog_navigate(&mut self, delta: isize) {
+        if let Some(view) = self.reflog_view.as_mut() {
+            let last = view.entries.len().saturating_sub(1);
+            view.selected = view.selected.saturating_add_signed(delta).min(last);
+        }
+    }
+
+    /// Work out how to undo the last operation and ask before doing it
+    pub fn request_undo(&mut self) {
+        if self.refuse_read_only("Undoing the last operation") {
+            return;
+        }
+        match crate::git::plan_undo() {
+            Ok(plan) => self.undo_confirm = Some(plan),
+            Err(e) => self.show_error("Can't Undo", &e.to_string()),
+        }
+    }
+
+    /// Reset HEAD as the confirmed plan says, then re-read everything HEAD affects
+    pub fn confirm_undo(&mut self) {
+        let plan = match self.undo_confirm.take() {
+            Some(plan) => plan,
+            None => return,
+        };
+        let branch = crate::git::get_current_branch().unwrap_or_else(|_| "HEAD".to_string());
+        let result = crate::git::undo_operation(&plan);
+        let mode = if plan.hard { "--hard" } else { "--soft" };
+        self.record_activity(
+            ActivityKind::Reset,
+            &branch,
+            &format!("git reset {} {}", mode, &plan.target[..7]),
+            match &result {
+                Ok(()) => Ok(format!(
+                    "Undid {}: {}",
+                    plan.entry.action, plan.entry.message
+                )),
+                Err(e) => Err(e.to_string()),
+            },
+        );
+        match result {
+            Ok(()) => {
+                self.history_message = Some(format!(
+                    "✓ Undid {} - HEAD is back at {}",
+                    plan.entry.action,
+                    &plan.target[..7]
+                ));
+                if self.reflog_view.is_some() {
+                    self.open_reflog();
+                }
+            }
+            Err(e) => self.show_error("Undo Failed", &format!("Failed to undo:\n\n{}", e)),
+        }
+        self.refresh_save_changes_git_status();
+        self.reload_history_if_moved();
+    }
+}
@@ -0,0 +1,253 @@
+use crate::activity::ActivityKind;
+use crate::app::{AppState, ResetConfirm};
+use crate::git::{ResetMode, ResetPreview};
+use crate::tui::theme::Theme;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::{layout::Rect, Frame};
+
+/// The modes in the order the modal lists them
+const MODES: [ResetMode; 3] = [ResetMode::Soft, ResetMode::Mixed, ResetMode::Hard];
+
+/// "3 commits" or "1 commit"
+fn commits(count: usize) -> String {
+    format!("{} commit{}", count, if count == 1 { "" } else { "s" })
+}
+
+/// What happens to HEAD, the index and the working tree, one line each
+fn effects(confirm: &ResetConfirm) -> [(&'static str, String); 3] {
+    let ResetPreview {
+        left_behind,
+        unpushed,
+        ahead,
+        staged,
+        unstaged,
+    } = confirm.preview;
+    let target = &confirm.commit.short_id;
+
+    let mut head = format!("{} moves to {}", confirm.branch, target);
+    if left_behind > 0 {
+        head.push_str(&format!(", leaving {} behind", commits(left_behind)));
+        if unpushed > 0 {
+            head.push_str(&format!(" ({} on no remote)", unpushed));
+        }
+    }
+    if ahead > 0 {
+        head.push_str(&format!(", picking up {} it doesn't have yet", commits(ahead)));
+    }
+
+    let changes = if left_behind > 0 {
+        format!("the changes of the {} left behind", commits(left_behind))
+    } else {
+        "the difference".to_string()
+    };
+    let (index, worktree) = match confirm.mode {
+        ResetMode::Soft => (
+            format!("kept as it is; {} become staged", changes),
+            "untouched".to_string(),
+        ),
+        ResetMode::Mixed => (
+            format!(
+                "reset to {}; {} and the {} staged file(s) become unstaged",
+                target, changes, staged
+            ),
+            "untouched".to_string(),
+        ),
+        ResetMode::Hard => (
+            format!("reset to {}; {} staged file(s) lose their changes", target, staged),
+            format!(
+                "tracked files reset to {}; {} file(s) with unstaged changes lose them. \
+                 Untracked files are kept",
+                target, unstaged
+            ),
+        ),
+    };
+    [("HEAD", head), ("Index", index), ("Working tree", worktree)]
+}
+
+/// Render the reset modal: the three modes, what the picked one does and, for a hard reset, the typed confirmation
+pub fn render_reset_popup(f: &mut Frame, area: Rect, confirm: &ResetConfirm, theme: &Theme) {
+    let hard = confirm.mode == ResetMode::Hard;
+    let popup_area = super::centered_rect(70, if hard { 18 } else { 15 }, area);
+    f.render_widget(Clear, popup_area);
+
+    let commit = &confirm.commit;
+    let mut modes = vec![Span::raw("  ")];
+    for mode in MODES {
+        let label = format!(" {} ", mode.as_str());
+        modes.push(if mode == confirm.mode {
+            Span::styled(label, theme.highlight_style())
+        } else {
+            Span::styled(label, theme.secondary_text_style())
+        });
+        modes.push(Span::raw("  "));
+    }
+
+    let mut lines = vec![
+        Line::styled(
+            format!("Reset {} to {} \"{}\"?", confirm.branch, commit.short_id, commit.summary),
+            theme.text_style(),
+        ),
+        Line::from(""),
+        Line::from(modes),
+        Line::from(""),
+    ];
+    for (label, effect) in effects(confirm) {
+        lines.push(Line::from(vec![
+            Span::styled(format!("{:<14}", label), theme.accent2_style()),
+            Span::styled(effect, theme.text_style()),
+        ]));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::styled(
+        "The reset is recorded in the reflog, so [Z] in History can undo it.",
+        theme.muted_text_style(),
+    ));
+    lines.push(Line::from(""));
+    if hard {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("Type {} to discard these changes: ", commit.short_id),
+                theme.warning_style(),
+            ),
+            Span::styled(format!("{}▏", confirm.typed), theme.text_style()),
+        ]));
+        lines.push(Line::from(""));
+    }
+    lines.push(Line::styled(
+        "[←→] Mode  [Enter] Reset  [Esc] Cancel",
+        theme.secondary_text_style(),
+    ));
+
+    let border_style = if hard {
+        theme.warning_style()
+    } else {
+        theme.popup_border_style()
+    };
+    let popup = Paragraph::new(lines)
+        .wrap(ratatui::widgets::Wrap { trim: false })
+        .block(
+            Block::default()
+                .title("Reset to Commit")
+                .title_style(theme.popup_title_style())
+                .borders(Borders::ALL)
+                .border_style(border_style)
+                .style(theme.popup_background_style()),
+        );
+    f.render_widget(popup, popup_area);
+}
+
+impl AppState {
+    /// Open the reset modal for the commit History has selected, starting on a mixed reset like git
+    pub fn request_reset_from_history(&mut self) {
+        if self.refuse_read_only("Resetting") {
+            return;
+        }
+        let commit = match self
+            .history
+            .table_state
+            .selected()
+            .and_then(|idx| self.history.commits.get(idx))
+            .cloned()
+        {
+            Some(commit) => commit,
+            None => return,
+        };
+        match crate::git::preview_reset(&commit.id) {
+            Ok(preview) => {
+                let branch =
+                    crate::git::get_current_branch().unwrap_or_else(|_| "HEAD".to_string());
+                self.reset_confirm = Some(ResetConfirm {
+                    commit,
+                    branch,
+                    mode: ResetMode::Mixed,
+                    preview,
+                    typed: String::new(),
+                });
+            }
+            Err(e) => self.show_error(
+                "Reset",
+                &format!("Failed to look at {}:\n\n{}", commit.short_id, e),
+            ),
+        }
+    }
+
+    /// Pick the previous or next mode in the reset modal
+    pub fn reset_select_mode(&mut self, forward: bool) {
+        if let Some(confirm) = self.reset_confirm.as_mut() {
+            let current = MODES.iter().position(|mode| *mode == confirm.mode).unwrap_or(1);
+            let next = if forward {
+                (current + 1).min(MODES.len() - 1)
+            } else {
+                current.saturating_sub(1)
+            };
+            confirm.mode = MODES[next];
+        }
+    }
+
+    /// Type into the hard reset confirmation; `None` deletes the last character
+    pub fn reset_type(&mut self, c: Option<char>) {
+        let confirm = self
+            .reset_confirm
+            .as_mut()
+            .filter(|confirm| confirm.mode == ResetMode::Hard);
+        if let Some(confirm) = confirm {
+            match c {
+                Some(c) => confirm.typed.push(c),
+                None => {
+                    confirm.typed.pop();
+                }
+            }
+        }
+    }
+
+    /// Reset as the modal says, unless a hard reset hasn't been confirmed by typing the id
+    pub fn confirm_reset(&mut self) {
+        let ready = self.reset_confirm.as_ref().is_some_and(|confirm| {
+            confirm.mode != ResetMode::Hard || confirm.typed.trim() == confirm.commit.short_id
+        });
+        if !ready {
+            return;
+        }
+        let ResetConfirm {
+            commit,
+            branch,
+            mode,
+            ..
+        } = match self.reset_confirm.take() {
+            Some(confirm) => confirm,
+            None => return,
+        };
+        let result = crate::git::reset_to(&commit.id, mode);
+        self.record_activity(
+            ActivityKind::Reset,
+            &branch,
+            &format!("libgit2: reset --{} {}", mode.as_str(), commit.short_id),
+            match &result {
+                Ok(()) => Ok(format!(
+                    "Reset {} to {} \"{}\" ({})",
+                    branch,
+                    commit.short_id,
+                    commit.summary,
+                    mode.as_str()
+                )),
+                Err(e) => Err(e.to_string()),
+            },
+        );
+        match result {
+            Ok(()) => {
+                self.history_message = Some(format!(
+                    "✓ Reset {} to {} ({})",
+                    branch,
+                    commit.short_id,
+                    mode.as_str()
+                ));
+            }
+            Err(e) => self.show_error("Reset Failed", &format!("Failed to reset:\n\n{}", e)),
+        }
+        self.refresh_save_changes_git_status();
+        self.invalidate_status_git_status();
+        self.reload_history_if_moved();
+        self.branches.loaded = false;
+    }
+}
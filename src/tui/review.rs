@@ -0,0 +1,353 @@
+use crate::app::{AppState, ReviewNoteInput, ReviewNotesView, SaveChangesFocus};
+use crate::review::ReviewNote;
+use crate::tui::text;
+use crate::tui::theme::Theme;
+use ratatui::layout::{Constraint, Direction, Layout, Margin};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph};
+use ratatui::{layout::Rect, Frame};
+use tui_textarea::TextArea;
+
+/// Render the note being written for the line under the review cursor
+pub fn render_review_note_popup(
+    f: &mut Frame,
+    area: Rect,
+    note: &mut ReviewNoteInput,
+    theme: &Theme,
+) {
+    let popup_area = super::centered_rect(60, 7, area);
+    f.render_widget(Clear, popup_area);
+
+    let location = if note.old {
+        format!("{}, line {} (removed)", note.path, note.line)
+    } else {
+        format!("{}, line {}", note.path, note.line)
+    };
+    let block = Block::default()
+        .title(format!("Review Note - {}", location))
+        .title_style(theme.popup_title_style())
+        .borders(Borders::ALL)
+        .border_style(theme.popup_border_style())
+        .style(theme.popup_background_style());
+    let inner = block.inner(popup_area).inner(Margin {
+        vertical: 0,
+        horizontal: 1,
+    });
+    f.render_widget(block, popup_area);
+
+    let chunks = Layout::vertical([
+        Constraint::Length(1), // The diff line
+        Constraint::Length(3), // Note
+        Constraint::Length(1), // Help
+    ])
+    .split(inner);
+
+    let code = note.code.replace('\t', "    ");
+    f.render_widget(
+        Paragraph::new(text::truncate(&code, chunks[0].width as usize))
+            .style(theme.muted_text_style()),
+        chunks[0],
+    );
+
+    note.input.set_block(
+        Block::default()
+            .title("Note")
+            .borders(Borders::ALL)
+            .border_style(theme.focused_border_style()),
+    );
+    note.input.set_style(theme.text_style());
+    note.input.set_cursor_style(
+        theme
+            .text_style()
+            .add_modifier(ratatui::style::Modifier::REVERSED),
+    );
+    f.render_widget(&note.input, chunks[1]);
+
+    f.render_widget(
+        Paragraph::new("[Enter] Save (empty removes the note)  [Esc] Cancel")
+            .style(theme.muted_text_style()),
+        chunks[2],
+    );
+}
+
+/// Render every review note in the repository, by file and line
+pub fn render_review_notes_popup(f: &mut Frame, area: Rect, state: &AppState, theme: &Theme) {
+    let view = match &state.review_notes_view {
+        Some(view) => view,
+        None => return,
+    };
+
+    let popup_area = area.inner(Margin {
+        vertical: 3,
+        horizontal: area.width / 8,
+    });
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!("Review Notes ({})", state.review_notes.len()))
+        .title_style(theme.popup_title_style())
+        .border_style(theme.popup_border_style())
+        .style(theme.popup_background_style());
+    f.render_widget(block, popup_area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(popup_area.inner(Margin {
+            vertical: 1,
+            horizontal: 1,
+        }));
+
+    if state.review_notes.is_empty() {
+        f.render_widget(
+            Paragraph::new(Line::styled(
+                "No notes yet - press [N] on a diff line in review mode",
+                theme.muted_text_style(),
+            )),
+            rows[0],
+        );
+    } else {
+        let width = rows[0].width as usize;
+        let items: Vec<ListItem> = state
+            .review_notes
+            .iter()
+            .map(|note| {
+                let location = format!("{}:{}  ", note.path, note.location());
+                let note_width = width.saturating_sub(2 + text::width(&location));
+                ListItem::new(Line::from(vec![
+                    Span::styled(location, theme.accent2_style()),
+                    Span::styled(text::truncate(&note.text, note_width), theme.text_style()),
+                ]))
+            })
+            .collect();
+        let mut list_state = ListState::default().with_selected(Some(view.selected));
+        let list = List::new(items)
+            .highlight_style(theme.highlight_style())
+            .highlight_symbol("► ");
+        f.render_stateful_widget(list, rows[0], &mut list_state);
+    }
+
+    let footer = match &view.message {
+        Some(message) => Line::styled(message.as_str(), theme.warning_style()),
+        None => Line::styled(
+            "[Enter] Go to line  [D] Delete  [E] Export markdown  [Esc] Close",
+            theme.muted_text_style(),
+        ),
+    };
+    f.render_widget(Paragraph::new(footer), rows[1]);
+}
+
+impl AppState {
+    /// Read the repository's review notes from .git/gitix/notes.json
+    pub fn load_review_notes(&mut self) {
+        self.review_notes = crate::review::load();
+    }
+
+    /// Move a line cursor over the diff pane, starting at the selected hunk's first change
+    pub fn open_review(&mut self) {
+        let diff = match self.save_changes_diff.as_mut() {
+            Some(diff) if diff.numbers.iter().any(Option::is_some) => diff,
+            _ => {
+                self.save_changes_focus = SaveChangesFocus::FileList;
+                return;
+            }
+        };
+        if self.save_changes_focus == SaveChangesFocus::HunkView {
+            let start = diff
+                .hunks
+                .get(diff.selected_hunk)
+                .map_or(0, |hunk| hunk.start);
+            let first_change = diff.lines[start..]
+                .iter()
+                .position(|line| line.starts_with(['+', '-']))
+                .map_or(start, |offset| start + offset);
+            diff.cursor = first_change;
+        }
+        self.save_changes_focus = SaveChangesFocus::Review;
+        self.review_navigate(0);
+    }
+
+    /// Move the review cursor by `delta` lines, skipping hunk headers and headings
+    pub fn review_navigate(&mut self, delta: isize) {
+        let diff = match self.save_changes_diff.as_mut() {
+            Some(diff) if !diff.numbers.is_empty() => diff,
+            _ => return,
+        };
+        let last = diff.numbers.len().saturating_sub(1);
+        let target = diff.cursor.saturating_add_signed(delta).min(last);
+        let numbered = |idx: &usize| diff.numbers[*idx].is_some();
+        // Look past the target in the direction of travel first, then back the other way
+        let found = if delta < 0 {
+            (0..=target)
+                .rev()
+                .find(numbered)
+                .or_else(|| (target..=last).find(numbered))
+        } else {
+            (target..=last)
+                .find(numbered)
+                .or_else(|| (0..=target).rev().find(numbered))
+        };
+        if let Some(idx) = found {
+            diff.cursor = idx;
+        }
+    }
+
+    /// The path and line under the review cursor, with the diff line itself
+    fn review_cursor(&self) -> Option<(String, u32, bool, String)> {
+        let diff = self.save_changes_diff.as_ref()?;
+        let (line, old) = diff.numbers.get(diff.cursor).copied().flatten()?;
+        Some((
+            diff.path.display().to_string(),
+            line,
+            old,
+            diff.lines[diff.cursor].clone(),
+        ))
+    }
+
+    /// Write or edit the note on the line under the review cursor
+    pub fn open_review_note(&mut self) {
+        let (path, line, old, code) = match self.review_cursor() {
+            Some(cursor) => cursor,
+            None => return,
+        };
+        let text = self
+            .review_notes
+            .iter()
+            .find(|note| note.is_at(&path, line, old))
+            .map(|note| note.text.clone())
+            .unwrap_or_default();
+        let mut input = TextArea::new(vec![text]);
+        input.move_cursor(tui_textarea::CursorMove::End);
+        self.review_note_input = Some(ReviewNoteInput {
+            path,
+            line,
+            old,
+            code,
+            input,
+        });
+    }
+
+    /// Save the note being written, replacing the line's earlier note; an empty note removes it
+    pub fn save_review_note(&mut self) {
+        let note = match self.review_note_input.take() {
+            Some(note) => note,
+            None => return,
+        };
+        let text = note.input.lines().join(" ").trim().to_string();
+        self.review_notes
+            .retain(|existing| !existing.is_at(&note.path, note.line, note.old));
+        if !text.is_empty() {
+            self.review_notes.push(ReviewNote {
+                path: note.path,
+                line: note.line,
+                old: note.old,
+                code: note.code,
+                text,
+                created: chrono::Local::now().timestamp(),
+            });
+            crate::review::sort(&mut self.review_notes);
+        }
+        if let Err(e) = crate::review::save(&self.review_notes) {
+            self.show_error(
+                "Review Note",
+                &format!("Failed to save the review notes:\n\n{}", e),
+            );
+        }
+    }
+
+    /// List every review note, re-read in case another session added some
+    pub fn open_review_notes(&mut self) {
+        self.load_review_notes();
+        self.review_notes_view = Some(ReviewNotesView {
+            selected: 0,
+            message: None,
+        });
+    }
+
+    /// Move the selection up or down the review notes
+    pub fn review_notes_navigate(&mut self, down: bool) {
+        let last = self.review_notes.len().saturating_sub(1);
+        if let Some(view) = self.review_notes_view.as_mut() {
+            view.selected = if down {
+                (view.selected + 1).min(last)
+            } else {
+                view.selected.saturating_sub(1)
+            };
+            view.message = None;
+        }
+    }
+
+    /// Delete the selected review note
+    pub fn delete_selected_review_note(&mut self) {
+        let selected = match &self.review_notes_view {
+            Some(view) if view.selected < self.review_notes.len() => view.selected,
+            _ => return,
+        };
+        self.review_notes.remove(selected);
+        let message = match crate::review::save(&self.review_notes) {
+            Ok(()) => None,
+            Err(e) => Some(format!("Failed to save the review notes: {}", e)),
+        };
+        if let Some(view) = self.review_notes_view.as_mut() {
+            view.selected = selected.min(self.review_notes.len().saturating_sub(1));
+            view.message = message;
+        }
+    }
+
+    /// Write the notes as a markdown review summary inside the git directory
+    pub fn export_review_notes(&mut self) {
+        if self.review_notes.is_empty() {
+            return;
+        }
+        let branch = crate::git::get_current_branch().unwrap_or_else(|_| "HEAD".to_string());
+        let path = crate::export::default_export_path("review", "md");
+        let message =
+            match std::fs::write(&path, crate::review::summary(&self.review_notes, &branch)) {
+                Ok(()) => format!("Review summary written to {}", path.display()),
+                Err(e) => format!("Export failed: {}", e),
+            };
+        if let Some(view) = self.review_notes_view.as_mut() {
+            view.message = Some(message);
+        }
+    }
+
+    /// Open the selected note's file in review mode with the cursor on its line
+    pub fn go_to_selected_review_note(&mut self) {
+        let note = match &self.review_notes_view {
+            Some(view) => match self.review_notes.get(view.selected) {
+                Some(note) => note.clone(),
+                None => return,
+            },
+            None => return,
+        };
+        let row = self
+            .save_changes_git_status
+            .iter()
+            .position(|file| file.path.display().to_string() == note.path);
+        let row = match row {
+            Some(row) => row,
+            None => {
+                if let Some(view) = self.review_notes_view.as_mut() {
+                    view.message = Some(format!("{} has no changes to review now", note.path));
+                }
+                return;
+            }
+        };
+
+        self.review_notes_view = None;
+        self.save_changes_table_state.select(Some(row));
+        self.show_save_changes_diff = true;
+        self.save_changes_focus = SaveChangesFocus::FileList;
+        self.load_save_changes_diff();
+        let found = self.save_changes_diff.as_ref().and_then(|diff| {
+            diff.numbers
+                .iter()
+                .position(|number| *number == Some((note.line, note.old)))
+        });
+        if let Some(diff) = self.save_changes_diff.as_mut() {
+            diff.cursor = found.unwrap_or(0);
+        }
+        self.open_review();
+    }
+}
@@ -1,14 +1,26 @@
-use crate::app::{AppState, SaveChangesFocus, TemplatePopupSelection};
-use crate::git::{commit, format_file_size, get_git_status, stage_file, unstage_file};
+use crate::activity::ActivityKind;
+use crate::app::{
+    AppState, CleanPreview, DiffHunk, DiscardConfirm, FileDetail, MouseList, Profile,
+    SaveChangesFocus, StagePattern, TemplatePopupSelection,
+};
+use crate::encoding::TextEncoding;
+use crate::git::{
+    commit, format_file_size, get_git_status, stage_file, unstage_file, FileStatusType,
+    GitFileStatus, StatusGroup,
+};
+use crate::review::ReviewNote;
+use crate::tui::text;
 use crate::tui::theme::Theme;
 use ratatui::layout::{Alignment, Constraint, Direction, Flex, Layout, Margin};
 use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
 use ratatui::widgets::{
     Block, Borders, Cell, Clear, Paragraph, Row, Scrollbar, ScrollbarOrientation, ScrollbarState,
-    Table, Wrap,
+    Table, TableState, Wrap,
 };
 use ratatui::{layout::Rect, Frame};
 use std::path::PathBuf;
+use tui_textarea::CursorMove;
 
 pub fn render_save_changes_tab(f: &mut Frame, area: Rect, state: &mut AppState) {
     // Use configured theme from app state
@@ -24,7 +36,7 @@ pub fn render_save_changes_tab(f: &mut Frame, area: Rect, state: &mut AppState)
 
     // Safety check: ensure focus is on commit message if there are no changes to commit
     if state.save_changes_git_status.is_empty()
-        && state.save_changes_focus == SaveChangesFocus::FileList
+        && state.save_changes_focus != SaveChangesFocus::CommitMessage
     {
         state.save_changes_focus = SaveChangesFocus::CommitMessage;
     }
@@ -84,6 +96,67 @@ pub fn render_save_changes_tab(f: &mut Frame, area: Rect, state: &mut AppState)
     if state.show_template_popup {
         render_template_popup(f, area, state, &theme);
     }
+
+    // Render file detail popup if shown
+    if let Some(detail) = &state.file_detail {
+        render_file_detail_popup(f, area, detail, state.deletes_permanently(), &theme);
+    }
+
+    // Render the discard confirmation if shown
+    if let Some(confirm) = &state.discard_confirm {
+        render_discard_popup(f, area, confirm, state.deletes_permanently(), &theme);
+    }
+
+    // Render the review note being written, or the list of notes
+    if let Some(note) = state.review_note_input.as_mut() {
+        super::review::render_review_note_popup(f, area, note, &theme);
+    }
+    if state.review_notes_view.is_some() {
+        super::review::render_review_notes_popup(f, area, state, &theme);
+    }
+
+    // Render clean preview if shown
+    if let Some(preview) = &state.clean_preview {
+        render_clean_popup(f, area, preview, state.deletes_permanently(), &theme);
+    }
+
+    // Render the stage-by-pattern prompt if shown
+    if let Some(pattern) = state.stage_pattern.as_mut() {
+        render_stage_pattern_popup(f, area, pattern, &theme);
+    }
+
+    // Render the amend confirmation if shown
+    if state.amend_confirm {
+        render_amend_popup(f, area, state, &theme);
+    }
+
+    // Render the backup browser if shown
+    if state.backup_browser.is_some() {
+        super::backups::render_backup_browser(f, area, state, &theme);
+    }
+}
+
+/// A row of the Save Changes list
+enum StatusRow {
+    Section(StatusGroup, usize), // Header of a section holding this many files
+    File(usize),                 // Index into `save_changes_git_status`
+}
+
+/// Lay out the (sorted) status list in sections, each under a header, like `git status`
+fn status_rows(files: &[GitFileStatus]) -> Vec<StatusRow> {
+    let mut rows = Vec::with_capacity(files.len() + 3);
+    for (idx, file) in files.iter().enumerate() {
+        let group = file.group();
+        if idx == 0 || files[idx - 1].group() != group {
+            let count = files[idx..]
+                .iter()
+                .take_while(|other| other.group() == group)
+                .count();
+            rows.push(StatusRow::Section(group, count));
+        }
+        rows.push(StatusRow::File(idx));
+    }
+    rows
 }
 
 fn render_file_list(f: &mut Frame, area: Rect, state: &mut AppState, theme: &Theme) {
@@ -104,16 +177,36 @@ fn render_file_list(f: &mut Frame, area: Rect, state: &mut AppState, theme: &The
         return;
     }
 
-    // Ensure table state selection is valid
+    // Ensure table state selection is valid, keeping it near where it was if the list shrank
     if !state.save_changes_git_status.is_empty() {
+        let last = state.save_changes_git_status.len() - 1;
         let current_selection = state.save_changes_table_state.selected().unwrap_or(0);
-        if current_selection >= state.save_changes_git_status.len() {
-            state.save_changes_table_state.select(Some(0));
-        } else if state.save_changes_table_state.selected().is_none() {
-            state.save_changes_table_state.select(Some(0));
-        }
+        state.save_changes_table_state.select(Some(current_selection.min(last)));
+    }
+
+    // Picking hunks and reviewing need the diff even where there is no room for the list beside it
+    let in_diff = matches!(
+        state.save_changes_focus,
+        SaveChangesFocus::HunkView | SaveChangesFocus::Review
+    );
+    if in_diff && area.width < DIFF_PANE_MIN_WIDTH {
+        state.load_save_changes_diff();
+        render_diff_pane(f, area, state, theme);
+        return;
     }
 
+    // Diff of the selected file beside the list, when there is room for both
+    let area = if state.show_save_changes_diff && area.width >= DIFF_PANE_MIN_WIDTH {
+        let [list_area, diff_area] =
+            Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .areas(area);
+        state.load_save_changes_diff();
+        render_diff_pane(f, diff_area, state, theme);
+        list_area
+    } else {
+        area
+    };
+
     // Create table headers
     let header = Row::new(vec![
         Cell::from("Staged").style(theme.accent2_style()),
@@ -122,11 +215,29 @@ fn render_file_list(f: &mut Frame, area: Rect, state: &mut AppState, theme: &The
         Cell::from("Size").style(theme.accent2_style()),
     ]);
 
-    // Create table rows
-    let rows: Vec<Row> = state
-        .save_changes_git_status
+    let widths = [
+        Constraint::Length(6),      // Staged indicator
+        Constraint::Percentage(50), // File path
+        Constraint::Percentage(25), // Status column
+        Constraint::Percentage(15), // Size column
+    ];
+    let path_width = text::column_widths(area, &widths, "► ")[1];
+
+    // Create table rows, with a header above each section
+    let layout = status_rows(&state.save_changes_git_status);
+    let rows: Vec<Row> = layout
         .iter()
-        .map(|file| {
+        .map(|row| {
+            let file = match row {
+                StatusRow::Section(group, count) => {
+                    return Row::new(vec![
+                        Cell::from(""),
+                        Cell::from(format!("{} ({})", group.title(), count))
+                            .style(theme.accent2_bold_style()),
+                    ]);
+                }
+                StatusRow::File(idx) => &state.save_changes_git_status[*idx],
+            };
             let is_staged = file.staged; // Use staging info from git status directly
 
             let staged_cell = Cell::from(if is_staged { "✔" } else { "○" }).style(if is_staged {
@@ -135,7 +246,8 @@ fn render_file_list(f: &mut Frame, area: Rect, state: &mut AppState, theme: &The
                 Style::default().fg(theme.surface0)
             });
 
-            let path_cell = Cell::from(file.path.display().to_string()).style(if is_staged {
+            let path = text::truncate_path(&file.path.display().to_string(), path_width);
+            let path_cell = Cell::from(path).style(if is_staged {
                 theme.accent3_style()
             } else {
                 Style::default().fg(theme.surface0)
@@ -143,7 +255,7 @@ fn render_file_list(f: &mut Frame, area: Rect, state: &mut AppState, theme: &The
 
             let status_cell = Cell::from(file.status.as_description()).style(
                 Style::default()
-                    .fg(file.status.color())
+                    .fg(theme.adapt(file.status.color()))
                     .add_modifier(Modifier::BOLD),
             );
 
@@ -169,32 +281,258 @@ fn render_file_list(f: &mut Frame, area: Rect, state: &mut AppState, theme: &The
         .count();
 
     // Create the table
-    let table = Table::new(
-        rows,
-        [
-            Constraint::Length(6),      // Staged indicator
-            Constraint::Percentage(50), // File path
-            Constraint::Percentage(25), // Status column
-            Constraint::Percentage(15), // Size column
-        ],
-    )
-    .header(header)
-    .block(
-        Block::default()
-            .borders(Borders::ALL)
-            .border_style(border_style)
-            .title(format!(
-                "Files to Commit ({} total, {} staged) - [Space] to stage/unstage",
-                state.save_changes_git_status.len(),
-                staged_count
-            ))
-            .title_style(theme.title_style())
-            .style(theme.secondary_background_style()),
-    )
-    .row_highlight_style(theme.highlight_style())
-    .highlight_symbol("► ");
-
-    f.render_stateful_widget(table, area, &mut state.save_changes_table_state);
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(border_style)
+                .title(format!(
+                    "Files to Commit ({} total, {} staged) - [Space] stage/unstage, [I] details, [D] discard, [E] edit",
+                    state.save_changes_git_status.len(),
+                    staged_count
+                ))
+                .title_style(theme.title_style())
+                .style(theme.secondary_background_style()),
+        )
+        .row_highlight_style(theme.highlight_style())
+        .highlight_symbol("► ");
+
+    // The selection counts files, the table counts rows; keep a section's header in view
+    // when scrolling up to its first file
+    let selected = state.save_changes_table_state.selected().and_then(|file_idx| {
+        layout
+            .iter()
+            .position(|row| matches!(row, StatusRow::File(idx) if *idx == file_idx))
+    });
+    let mut offset = state.save_changes_table_state.offset();
+    if let Some(row) = selected {
+        if row > 0 && row <= offset && matches!(layout[row - 1], StatusRow::Section(..)) {
+            offset = row - 1;
+        }
+    }
+    let mut table_state = TableState::default()
+        .with_offset(offset)
+        .with_selected(selected);
+    f.render_stateful_widget(table, area, &mut table_state);
+    *state.save_changes_table_state.offset_mut() = table_state.offset();
+    state.record_mouse_target(MouseList::SaveChanges, area, table_state.offset(), layout.len());
+}
+
+/// Locate the hunks of one side of a file's diff, whose lines start at `offset` in the pane
+fn diff_hunks(lines: &[String], offset: usize, staged: bool) -> Vec<DiffHunk> {
+    let mut hunks: Vec<DiffHunk> = Vec::new();
+    for (idx, line) in lines.iter().enumerate() {
+        if line.starts_with("@@") {
+            hunks.push(DiffHunk {
+                staged,
+                index: hunks.len(),
+                start: offset + idx,
+                len: 1,
+            });
+        } else if let Some(hunk) = hunks.last_mut() {
+            hunk.len += 1;
+        }
+    }
+    hunks
+}
+
+/// Number each diff line as the review notes do: `(line, removed)`, where removed lines count
+/// in the old file and the rest in the new one; headers and headings get `None`
+fn diff_line_numbers(lines: &[String]) -> Vec<Option<(u32, bool)>> {
+    // "@@ -12,3 +14,5 @@ context" starts the old side at 12 and the new one at 14
+    let start = |header: &str, sign: char| -> Option<u32> {
+        let field = header.split(' ').find(|field| field.starts_with(sign))?;
+        field[1..].split(',').next()?.parse().ok()
+    };
+    let (mut old, mut new) = (None, None);
+    lines
+        .iter()
+        .map(|line| {
+            if line.starts_with("@@") {
+                old = start(line, '-');
+                new = start(line, '+');
+                return None;
+            }
+            let (number, removed) = match line.chars().next() {
+                Some('-') => (old.as_mut()?, true),
+                Some('+') => (new.as_mut()?, false),
+                Some(' ') => {
+                    *old.as_mut()? += 1;
+                    (new.as_mut()?, false)
+                }
+                _ => return None,
+            };
+            *number += 1;
+            Some((*number - 1, removed))
+        })
+        .collect()
+}
+
+/// Below this width the file list keeps the whole row and the diff pane is left out
+const DIFF_PANE_MIN_WIDTH: u16 = 80;
+
+fn render_diff_pane(f: &mut Frame, area: Rect, state: &mut AppState, theme: &Theme) {
+    let picking = state.save_changes_focus == SaveChangesFocus::HunkView;
+    let reviewing = state.save_changes_focus == SaveChangesFocus::Review;
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(if picking || reviewing {
+            theme.focused_border_style()
+        } else {
+            theme.border_style()
+        })
+        .title_style(theme.title_style())
+        .style(theme.secondary_background_style());
+    let diff = match state.save_changes_diff.as_mut() {
+        Some(diff) => diff,
+        None => {
+            let hint = Paragraph::new("Select a file to see its changes")
+                .style(theme.muted_text_style())
+                .block(block.title("Diff"));
+            f.render_widget(hint, area);
+            return;
+        }
+    };
+    let path = diff.path.display().to_string();
+    let notes: Vec<&ReviewNote> = state
+        .review_notes
+        .iter()
+        .filter(|note| note.path == path)
+        .collect();
+    let note_at = |idx: usize| {
+        let (line, old) = diff.numbers.get(idx).copied().flatten()?;
+        notes.iter().find(|note| note.line == line && note.old == old).copied()
+    };
+
+    let block = if picking {
+        block.title(format!(
+            "Hunk {} of {} - [Space] {}, [R] review, [Esc] back",
+            diff.selected_hunk + 1,
+            diff.hunks.len(),
+            match diff.hunks.get(diff.selected_hunk) {
+                Some(hunk) if hunk.staged => "unstage",
+                _ => "stage",
+            }
+        ))
+    } else if reviewing {
+        let line = match diff.numbers.get(diff.cursor).copied().flatten() {
+            Some((line, true)) => format!("line {} (removed)", line),
+            Some((line, false)) => format!("line {}", line),
+            None => "no line".to_string(),
+        };
+        block.title(format!(
+            "Review: {}, {} - [N] note, [Esc] back",
+            text::truncate_path(&path, area.width.saturating_sub(40) as usize),
+            line
+        ))
+    } else {
+        block.title(format!(
+            "Diff: {} - [→] hunks",
+            text::truncate_path(&path, area.width.saturating_sub(22) as usize)
+        ))
+    };
+    // Anything but plain UTF-8 gets a badge, so replacement characters or odd letters aren't a mystery
+    let block = match diff.encoding {
+        Some(encoding) if encoding != TextEncoding::Utf8 => block.title_top(
+            Line::styled(format!(" {} ", encoding.label()), theme.warning_style()).right_aligned(),
+        ),
+        _ => block,
+    };
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    if diff.lines.is_empty() {
+        f.render_widget(
+            Paragraph::new("No text changes").style(theme.muted_text_style()),
+            inner,
+        );
+        return;
+    }
+
+    let height = inner.height as usize;
+    let width = inner.width as usize;
+    // Notes take a row of their own under their line, so count them to keep the cursor in view
+    let mut scroll = diff.scroll;
+    if reviewing {
+        scroll = scroll.min(diff.cursor);
+        let rows = |from: usize| {
+            (from..=diff.cursor)
+                .map(|idx| 1 + note_at(idx).is_some() as usize)
+                .sum::<usize>()
+        };
+        while scroll < diff.cursor && rows(scroll) > height {
+            scroll += 1;
+        }
+    }
+    let selected = diff
+        .hunks
+        .get(diff.selected_hunk)
+        .filter(|_| picking)
+        .map(|hunk| hunk.start..hunk.start + hunk.len);
+    let gutter = picking || reviewing;
+    let mut lines: Vec<Line> = Vec::new();
+    for (idx, line) in diff.lines.iter().enumerate().skip(scroll) {
+        if lines.len() >= height {
+            break;
+        }
+        let mut style = if line.starts_with("@@") {
+            theme.info_style()
+        } else if line.starts_with('+') {
+            theme.success_style()
+        } else if line.starts_with('-') {
+            theme.error_style()
+        } else if line.starts_with(' ') {
+            theme.text_style()
+        } else {
+            // Section headings and git's notes on the file
+            theme.accent2_style()
+        };
+        let line = line.replace('\t', "    ");
+        if !gutter {
+            lines.push(Line::styled(text::truncate(&line, width), style));
+        } else {
+            // A bar in the gutter marks the selected hunk or line without hiding the diff colors
+            let marked = if reviewing {
+                idx == diff.cursor
+            } else {
+                selected.as_ref().is_some_and(|hunk| hunk.contains(&idx))
+            };
+            let marker = if marked {
+                Span::styled("▌", theme.accent_style())
+            } else {
+                Span::raw(" ")
+            };
+            if reviewing && marked {
+                style = style.add_modifier(Modifier::REVERSED);
+            }
+            lines.push(Line::from(vec![
+                marker,
+                Span::styled(text::truncate(&line, width.saturating_sub(1)), style),
+            ]));
+        }
+        if let Some(note) = note_at(idx) {
+            lines.push(Line::styled(
+                text::truncate(&format!("  ✎ {}", note.text), width),
+                theme.accent3_style(),
+            ));
+        }
+    }
+    f.render_widget(Paragraph::new(lines), inner);
+    diff.scroll = scroll;
+
+    if diff.lines.len() > height {
+        let mut scrollbar_state =
+            ScrollbarState::new(diff.lines.len().saturating_sub(height)).position(diff.scroll);
+        f.render_stateful_widget(
+            Scrollbar::new(ScrollbarOrientation::VerticalRight),
+            area.inner(Margin {
+                vertical: 1,
+                horizontal: 0,
+            }),
+            &mut scrollbar_state,
+        );
+    }
 }
 
 fn render_commit_area(f: &mut Frame, area: Rect, state: &mut AppState, theme: &Theme) {
@@ -237,8 +575,18 @@ fn render_commit_area(f: &mut Frame, area: Rect, state: &mut AppState, theme: &T
     let commit_block = Block::default()
         .borders(Borders::ALL)
         .border_style(border_style)
-        .title("✎ Commit Message - [↑↓] to navigate, [Shift+?] for help, [Shift+T] for template")
+        .title(if state.commit_amend.is_some() {
+            "✎ Amend Last Commit - [↑↓] to navigate, [F1] for help, [F2] to stop amending"
+        } else {
+            "✎ Commit Message - [↑↓] to navigate, [F1] for help, [Ctrl+T] for template"
+        })
         .title_style(theme.title_style())
+        .title_bottom(commit_counters(state, theme).right_aligned())
+        .title_bottom(if state.commit_draft_restored {
+            Line::styled(" ↺ Draft restored from last session ", theme.info_style())
+        } else {
+            Line::default()
+        })
         .style(theme.secondary_background_style());
 
     let inner_area = commit_block.inner(chunks[0]);
@@ -263,16 +611,40 @@ fn render_commit_area(f: &mut Frame, area: Rect, state: &mut AppState, theme: &T
         .iter()
         .filter(|f| f.staged)
         .count();
-    let status_text = if staged_count > 0 {
+    let status_text = if state.commit_amend.is_some() {
+        format!(
+            "Amending the last commit{} - [Ctrl+S] to review and amend, [F2] to stop amending",
+            if staged_count > 0 {
+                format!(" with {} staged file(s)", staged_count)
+            } else {
+                String::new()
+            }
+        )
+    } else if let Some(warning) = state.staged_encoding_warning.as_ref().filter(|_| staged_count > 0) {
+        format!("⚠ {} - [Enter] to commit anyway", warning)
+    } else if staged_count > 0 && state.commit_skip_hooks {
+        format!(
+            "Ready to commit {} file(s) without hooks - [Enter] to commit, [Ctrl+O] to run hooks",
+            staged_count
+        )
+    } else if staged_count > 0 {
         format!(
-            "Ready to commit {} file(s) - [Enter] to commit",
+            "Ready to commit {} file(s) - [Enter] to commit, [Ctrl+Enter] to skip hooks",
             staged_count
         )
+    } else if let Some(summary) = &state.last_commit_summary {
+        summary.clone()
     } else {
         "No files staged for commit".to_string()
     };
 
-    let status_style = if staged_count > 0 {
+    let status_style = if state.commit_amend.is_some() {
+        theme.warning_style()
+    } else if staged_count > 0 && state.commit_skip_hooks {
+        theme.warning_style()
+    } else if staged_count > 0 && state.staged_encoding_warning.is_some() {
+        theme.warning_style()
+    } else if staged_count > 0 || state.last_commit_summary.is_some() {
         theme.success_style()
     } else {
         theme.warning_style()
@@ -291,6 +663,56 @@ fn render_commit_area(f: &mut Frame, area: Rect, state: &mut AppState, theme: &T
     f.render_widget(status_paragraph, chunks[1]);
 }
 
+/// Where the unfinished commit message is kept, inside the git directory like COMMIT_EDITMSG
+fn commit_draft_path() -> Option<PathBuf> {
+    git2::Repository::open(".")
+        .ok()
+        .map(|repo| repo.path().join("gitix-commit-draft"))
+}
+
+/// Live subject/body length counters, colored at the 50 and 72 character guides
+fn commit_counters(state: &AppState, theme: &Theme) -> Line<'static> {
+    let lines = state.commit_message.lines();
+    let length = |line: &String| line.chars().count();
+
+    let subject = lines.first().map(length).unwrap_or(0);
+    let subject_style = match subject {
+        0..=50 => theme.success_style(),
+        51..=72 => theme.warning_style(),
+        _ => theme.error_style(),
+    };
+
+    let mut spans = vec![
+        Span::styled(" Subject ", theme.secondary_text_style()),
+        Span::styled(format!("{}/50", subject), subject_style),
+    ];
+
+    // Git expects a blank line between the subject and the body
+    if lines.len() > 1 && !lines[1].trim().is_empty() {
+        spans.push(Span::styled(" │ ", theme.muted_text_style()));
+        spans.push(Span::styled("Line 2 should be blank", theme.warning_style()));
+    }
+
+    let body_longest = lines.iter().skip(1).map(length).max().unwrap_or(0);
+    if body_longest > 0 {
+        let body_style = if body_longest > 72 {
+            theme.error_style()
+        } else {
+            theme.success_style()
+        };
+        spans.push(Span::styled(" │ Body ", theme.secondary_text_style()));
+        spans.push(Span::styled(format!("{}/72", body_longest), body_style));
+    }
+
+    let (row, col) = state.commit_message.cursor();
+    spans.push(Span::styled(
+        format!(" │ Ln {}, Col {} ", row + 1, col + 1),
+        theme.muted_text_style(),
+    ));
+
+    Line::from(spans)
+}
+
 /// Helper function to create a centered popup area
 fn popup_area(area: Rect, percent_x: u16, percent_y: u16) -> Rect {
     let vertical = Layout::vertical([Constraint::Percentage(percent_y)]).flex(Flex::Center);
@@ -564,130 +986,1496 @@ fn render_template_popup(f: &mut Frame, area: Rect, state: &AppState, theme: &Th
     f.render_widget(no_button, button_area[3]);
 }
 
-// Helper functions for handling user input
-impl AppState {
-    pub fn toggle_file_staging(&mut self) {
-        if !self.save_changes_git_status.is_empty() {
-            if let Some(selected_idx) = self.save_changes_table_state.selected() {
-                if selected_idx < self.save_changes_git_status.len() {
-                    let file_path = &self.save_changes_git_status[selected_idx].path;
-                    let is_currently_staged = self.save_changes_git_status[selected_idx].staged;
-
-                    // Convert path to string using display() for better path handling
-                    let path_str = file_path.display().to_string();
-
-                    if is_currently_staged {
-                        // Unstage the file
-                        if let Ok(()) = unstage_file(&path_str) {
-                            // Update the staging status in-place to avoid reordering
-                            self.save_changes_git_status[selected_idx].staged = false;
-                        }
-                    } else {
-                        // Stage the file
-                        if let Ok(()) = stage_file(&path_str) {
-                            // Update the staging status in-place to avoid reordering
-                            self.save_changes_git_status[selected_idx].staged = true;
-                        }
-                    }
+/// Render the detail popup for one file, with its quick actions
+fn render_file_detail_popup(
+    f: &mut Frame,
+    area: Rect,
+    detail: &FileDetail,
+    permanent: bool,
+    theme: &Theme,
+) {
+    let popup_area = popup_area(area, 60, 50);
+    f.render_widget(Clear, popup_area);
 
-                    // No need to refresh git status cache - we updated it in-place
-                    // This preserves the file order and selection
-                }
+    let file = &detail.file;
+    let path = file.path.display().to_string();
+    let full_path = std::env::current_dir()
+        .map(|dir| dir.join(&file.path).display().to_string())
+        .unwrap_or_else(|_| path.clone());
+    let field = |label: &str, value: String, style: Style| {
+        Line::from(vec![
+            Span::styled(format!("{:<14}", label), theme.accent2_style()),
+            Span::styled(value, style),
+        ])
+    };
+
+    let mut lines = vec![
+        field("Path", path.clone(), theme.text_style()),
+        field("Full path", full_path, theme.secondary_text_style()),
+        field(
+            "Status",
+            format!(
+                "{} ({})",
+                file.status.as_description(),
+                if file.staged { "staged" } else { "not staged" }
+            ),
+            Style::default()
+                .fg(theme.adapt(file.status.color()))
+                .add_modifier(Modifier::BOLD),
+        ),
+    ];
+    if let FileStatusType::Renamed { from } = &file.status {
+        lines.push(field("Renamed from", from.clone(), theme.text_style()));
+    }
+    lines.push(field("Size", format_file_size(file.file_size), theme.text_style()));
+
+    match &detail.diff {
+        Some(diff) => {
+            let mode = match (diff.old_mode, diff.new_mode) {
+                (Some(old), Some(new)) if old != new => format!("{:o} → {:o}", old, new),
+                (Some(mode), _) | (None, Some(mode)) => format!("{:o}", mode),
+                (None, None) => "-".to_string(),
+            };
+            lines.push(field("Mode", mode, theme.text_style()));
+            if let Some(encoding) = diff.encoding {
+                let style = match encoding {
+                    TextEncoding::Utf8 => theme.text_style(),
+                    _ => theme.warning_style(),
+                };
+                lines.push(field("Encoding", encoding.label().to_string(), style));
+            }
+            if diff.binary {
+                lines.push(field("Changes", "binary file".to_string(), theme.text_style()));
+            } else {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("{:<14}", "Changes"), theme.accent2_style()),
+                    Span::styled(format!("+{}", diff.insertions), theme.success_style()),
+                    Span::raw(" "),
+                    Span::styled(format!("-{}", diff.deletions), theme.error_style()),
+                ]));
             }
         }
+        None => lines.push(field("Changes", "unavailable".to_string(), theme.muted_text_style())),
     }
 
-    pub fn commit_staged_files(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        // Check if there are any staged files from cached git status
-        let staged_count = self
-            .save_changes_git_status
-            .iter()
-            .filter(|f| f.staged)
-            .count();
-
-        if staged_count == 0 {
-            return Err("No files staged for commit".into());
+    lines.push(Line::from(""));
+    if detail.confirm_discard {
+        lines.push(Line::styled(
+            discard_question(&path, is_new(&file.status), permanent),
+            theme.warning_style(),
+        ));
+        lines.push(Line::styled("[Y] Discard  [N] Keep", theme.text_style()));
+    } else {
+        let stage = if file.staged { "[S] Unstage" } else { "[S] Stage" };
+        let mut actions = vec![stage];
+        match file.status {
+            FileStatusType::Deleted => actions.push("[R] Restore"),
+            FileStatusType::Untracked => actions.extend(["[D] Discard", "[G] Ignore", "[O] Open"]),
+            _ => actions.extend(["[D] Discard", "[O] Open"]),
         }
+        lines.push(Line::styled(actions.join("  "), theme.text_style()));
+    }
 
-        let commit_message = self.commit_message.lines().join("\n");
-        if commit_message.trim().is_empty() {
-            return Err("Commit message cannot be empty".into());
-        }
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("File Details - [Esc] to close")
+        .title_style(theme.popup_title_style())
+        .border_style(theme.popup_border_style())
+        .style(theme.popup_background_style());
+    let inner = block.inner(popup_area).inner(Margin {
+        vertical: 1,
+        horizontal: 2,
+    });
+    f.render_widget(block, popup_area);
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
+}
 
-        // Start loading indicator
-        self.start_loading("Creating commit...");
+/// Render the list of files a clean would remove, then the final confirmation
+fn render_clean_popup(
+    f: &mut Frame,
+    area: Rect,
+    preview: &CleanPreview,
+    permanent: bool,
+    theme: &Theme,
+) {
+    let popup_area = popup_area(area, 60, 60);
+    f.render_widget(Clear, popup_area);
 
-        // Perform the commit
-        let result = commit(&commit_message);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Clean Untracked Files - [↑↓] to scroll, [Esc] to cancel")
+        .title_style(theme.popup_title_style())
+        .border_style(if preview.confirming {
+            theme.warning_style()
+        } else {
+            theme.popup_border_style()
+        })
+        .style(theme.popup_background_style());
+    let inner = block.inner(popup_area).inner(Margin {
+        vertical: 1,
+        horizontal: 2,
+    });
+    f.render_widget(block, popup_area);
 
-        // Stop loading indicator
-        self.stop_loading();
+    let chunks = Layout::vertical([
+        Constraint::Length(2), // Summary
+        Constraint::Min(1),    // Paths
+        Constraint::Length(2), // Actions
+    ])
+    .split(inner);
 
-        // Handle result
-        result?;
+    let what = if preview.include_ignored {
+        "untracked and ignored"
+    } else {
+        "untracked"
+    };
+    let summary = if preview.paths.is_empty() {
+        format!("No {} files to remove.", what)
+    } else {
+        format!("{} {} path(s) would be removed:", preview.paths.len(), what)
+    };
+    f.render_widget(Paragraph::new(summary).style(theme.text_style()), chunks[0]);
 
-        // Clear commit message
-        self.commit_message = tui_textarea::TextArea::new(vec![String::new()]);
+    let width = chunks[1].width as usize;
+    let paths: Vec<Line> = preview
+        .paths
+        .iter()
+        .skip(preview.scroll)
+        .map(|path| Line::styled(text::truncate_path(path, width), theme.error_style()))
+        .collect();
+    f.render_widget(Paragraph::new(paths), chunks[1]);
+
+    let actions = if preview.confirming && !permanent {
+        Line::styled(
+            format!(
+                "Move {} path(s) to the trash?  [Y] Delete  [N] Cancel",
+                preview.paths.len()
+            ),
+            theme.warning_style(),
+        )
+    } else if preview.confirming {
+        Line::styled(
+            format!(
+                "Permanently delete {} path(s)? This cannot be undone.  [Y] Delete  [N] Cancel",
+                preview.paths.len()
+            ),
+            theme.warning_style(),
+        )
+    } else {
+        Line::styled(
+            format!(
+                "[Enter] Continue  [I] Include ignored files: {}",
+                if preview.include_ignored { "on" } else { "off" }
+            ),
+            theme.text_style(),
+        )
+    };
+    f.render_widget(Paragraph::new(actions).wrap(Wrap { trim: true }), chunks[2]);
+}
 
-        // Refresh git status cache after commit, preserving selection if possible
-        self.refresh_save_changes_git_status_preserve_selection();
+/// Render the stage-by-pattern prompt: the pathspec and a preview of the files it would stage
+fn render_stage_pattern_popup(f: &mut Frame, area: Rect, pattern: &mut StagePattern, theme: &Theme) {
+    let popup_area = popup_area(area, 60, 60);
+    f.render_widget(Clear, popup_area);
 
-        Ok(())
-    }
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Stage by Pattern")
+        .title_style(theme.popup_title_style())
+        .border_style(theme.popup_border_style())
+        .style(theme.popup_background_style());
+    let inner = block.inner(popup_area).inner(Margin {
+        vertical: 0,
+        horizontal: 1,
+    });
+    f.render_widget(block, popup_area);
 
-    /// Refresh git status while trying to preserve the current selection
-    pub fn refresh_save_changes_git_status_preserve_selection(&mut self) {
-        // Remember the currently selected file path
-        let selected_file_path =
-            if let Some(selected_idx) = self.save_changes_table_state.selected() {
-                if selected_idx < self.save_changes_git_status.len() {
-                    Some(self.save_changes_git_status[selected_idx].path.clone())
-                } else {
-                    None
-                }
-            } else {
-                None
-            };
+    let chunks = Layout::vertical([
+        Constraint::Length(3), // Pathspec
+        Constraint::Length(1), // Summary
+        Constraint::Min(1),    // Matching paths
+        Constraint::Length(1), // Help
+    ])
+    .split(inner);
 
-        // Refresh the git status
-        self.refresh_save_changes_git_status();
+    pattern.input.set_block(
+        Block::default()
+            .title("Pathspec, e.g. src/**/*.rs or *.md docs/")
+            .borders(Borders::ALL)
+            .border_style(theme.focused_border_style()),
+    );
+    pattern.input.set_style(theme.text_style());
+    pattern
+        .input
+        .set_cursor_style(theme.text_style().add_modifier(Modifier::REVERSED));
+    f.render_widget(&pattern.input, chunks[0]);
+
+    let summary = if let Some(error) = &pattern.error {
+        Line::styled(error.clone(), theme.error_style())
+    } else {
+        Line::styled(
+            format!(
+                "{} of {} unstaged file(s) match",
+                pattern.matches.len(),
+                pattern.candidates.len()
+            ),
+            theme.secondary_text_style(),
+        )
+    };
+    f.render_widget(Paragraph::new(summary), chunks[1]);
+
+    let width = chunks[2].width as usize;
+    let height = chunks[2].height as usize;
+    pattern.scroll = pattern
+        .scroll
+        .min(pattern.matches.len().saturating_sub(height));
+    let paths: Vec<Line> = pattern
+        .matches
+        .iter()
+        .skip(pattern.scroll)
+        .take(height)
+        .map(|path| Line::styled(text::truncate_path(path, width), theme.success_style()))
+        .collect();
+    f.render_widget(Paragraph::new(paths), chunks[2]);
 
-        // Try to restore selection to the same file
-        if let Some(target_path) = selected_file_path {
-            if let Some(new_idx) = self
-                .save_changes_git_status
-                .iter()
-                .position(|f| f.path == target_path)
-            {
-                self.save_changes_table_state.select(Some(new_idx));
-            } else {
-                // File no longer exists, select a reasonable fallback
-                if !self.save_changes_git_status.is_empty() {
-                    let fallback_idx =
-                        if let Some(old_idx) = self.save_changes_table_state.selected() {
-                            // Try to select the same index, or the last item if the list is shorter
-                            old_idx.min(self.save_changes_git_status.len() - 1)
-                        } else {
-                            0
-                        };
-                    self.save_changes_table_state.select(Some(fallback_idx));
-                } else {
-                    self.save_changes_table_state.select(None);
-                }
-            }
-        }
+    f.render_widget(
+        Paragraph::new(format!(
+            "[Enter] Stage {}  [↑↓] Scroll  [Esc] Cancel",
+            pattern.matches.len()
+        ))
+        .style(theme.secondary_text_style()),
+        chunks[3],
+    );
+}
+
+/// Whether a file isn't in HEAD, so discarding its changes removes it
+fn is_new(status: &FileStatusType) -> bool {
+    matches!(status, FileStatusType::Untracked | FileStatusType::Added)
+}
+
+/// The question asked before discarding `path`, saying what happens to it
+fn discard_question(path: &str, new: bool, permanent: bool) -> String {
+    match (new, permanent) {
+        (true, false) => format!("{} isn't committed. Move it to the trash?", path),
+        (true, true) => format!(
+            "{} isn't committed. Delete it permanently? This cannot be undone.",
+            path
+        ),
+        (false, _) => format!(
+            "Discard all changes to {}, staged and unstaged? This cannot be undone.",
+            path
+        ),
     }
+}
 
-    pub fn switch_save_changes_focus(&mut self) {
-        // Only allow focus switching if there are changes to commit
-        if self.save_changes_git_status.is_empty() {
-            // No changes to commit, keep focus on commit message
-            self.save_changes_focus = SaveChangesFocus::CommitMessage;
-            return;
-        }
+/// Render the confirmation before the selected file's changes are thrown away
+fn render_discard_popup(
+    f: &mut Frame,
+    area: Rect,
+    confirm: &DiscardConfirm,
+    permanent: bool,
+    theme: &Theme,
+) {
+    let popup_area = super::centered_rect(60, 7, area);
+    f.render_widget(Clear, popup_area);
 
-        self.save_changes_focus = match self.save_changes_focus {
-            SaveChangesFocus::FileList => SaveChangesFocus::CommitMessage,
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Discard Changes")
+        .title_style(theme.popup_title_style())
+        .border_style(theme.warning_style())
+        .style(theme.popup_background_style());
+    let inner = block.inner(popup_area).inner(Margin {
+        vertical: 1,
+        horizontal: 2,
+    });
+    f.render_widget(block, popup_area);
+
+    let lines = vec![
+        Line::styled(
+            discard_question(&confirm.path, confirm.untracked, permanent),
+            theme.warning_style(),
+        ),
+        Line::from(""),
+        Line::styled("[Y] Discard  [N] Keep", theme.text_style()),
+    ];
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }), inner);
+}
+
+/// Render the word-level changes to the message of the commit being amended
+fn render_amend_popup(f: &mut Frame, area: Rect, state: &AppState, theme: &Theme) {
+    let popup_area = popup_area(area, 70, 60);
+    f.render_widget(Clear, popup_area);
+
+    let original = state.commit_amend.as_deref().unwrap_or_default();
+    let edited = state.commit_message.lines().join("\n");
+    let changes = word_diff(original, &edited);
+
+    let count = |kind: WordChange| {
+        changes
+            .iter()
+            .filter(|(change, word)| *change == kind && *word != "\n")
+            .count()
+    };
+    let (added, removed) = (count(WordChange::Added), count(WordChange::Removed));
+    let staged_count = state
+        .save_changes_git_status
+        .iter()
+        .filter(|f| f.staged)
+        .count();
+
+    let mut lines = vec![Line::from(vec![
+        Span::styled(format!("+{} word(s)", added), theme.success_style()),
+        Span::raw("  "),
+        Span::styled(format!("-{} word(s)", removed), theme.error_style()),
+    ])];
+    if added == 0 && removed == 0 {
+        lines.push(Line::styled("The message is unchanged.", theme.muted_text_style()));
+    }
+    if staged_count > 0 {
+        lines.push(Line::styled(
+            format!("{} staged file(s) will be added to the commit.", staged_count),
+            theme.info_style(),
+        ));
+    }
+    lines.push(Line::from(""));
+
+    let mut line = Vec::new();
+    for (change, word) in changes {
+        if word == "\n" {
+            lines.push(Line::from(std::mem::take(&mut line)));
+            continue;
+        }
+        if !line.is_empty() {
+            line.push(Span::raw(" "));
+        }
+        line.push(match change {
+            WordChange::Same => Span::styled(word, theme.text_style()),
+            WordChange::Removed => {
+                Span::styled(word, theme.error_style().add_modifier(Modifier::CROSSED_OUT))
+            }
+            WordChange::Added => {
+                Span::styled(word, theme.success_style().add_modifier(Modifier::UNDERLINED))
+            }
+        });
+    }
+    lines.push(Line::from(line));
+
+    lines.push(Line::from(""));
+    lines.push(Line::styled(
+        if state.commit_skip_hooks {
+            "Amend the last commit without hooks?  [Y] Amend  [N] Keep editing"
+        } else {
+            "Amend the last commit?  [Y] Amend  [N] Keep editing"
+        },
+        theme.warning_style(),
+    ));
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Amend Commit - Message Changes")
+        .title_style(theme.popup_title_style())
+        .border_style(theme.popup_border_style())
+        .style(theme.popup_background_style());
+    let inner = block.inner(popup_area).inner(Margin {
+        vertical: 1,
+        horizontal: 2,
+    });
+    f.render_widget(block, popup_area);
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WordChange {
+    Same,
+    Removed,
+    Added,
+}
+
+/// Diff two messages word by word; line breaks come through as "\n" words
+///
+/// A plain longest-common-subsequence table, which is fine at commit message sizes.
+fn word_diff<'a>(old: &'a str, new: &'a str) -> Vec<(WordChange, &'a str)> {
+    let words = |text: &'a str| -> Vec<&'a str> {
+        let mut words = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            if i > 0 {
+                words.push("\n");
+            }
+            words.extend(line.split_whitespace());
+        }
+        words
+    };
+    let (old, new) = (words(old), words(new));
+
+    // common[i][j]: length of the common subsequence of old[i..] and new[j..]
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut changes = Vec::with_capacity(old.len().max(new.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            changes.push((WordChange::Same, old[i]));
+            i += 1;
+            j += 1;
+        } else if common[i + 1][j] >= common[i][j + 1] {
+            changes.push((WordChange::Removed, old[i]));
+            i += 1;
+        } else {
+            changes.push((WordChange::Added, new[j]));
+            j += 1;
+        }
+    }
+    changes.extend(old[i..].iter().map(|word| (WordChange::Removed, *word)));
+    changes.extend(new[j..].iter().map(|word| (WordChange::Added, *word)));
+    changes
+}
+
+// Helper functions for handling user input
+impl AppState {
+    /// Compute the diff of the selected file for the diff pane, unless it is already cached
+    pub fn load_save_changes_diff(&mut self) {
+        let path = match self.selected_save_changes_file() {
+            Some(file) => file.path.clone(),
+            None => {
+                self.save_changes_diff = None;
+                return;
+            }
+        };
+        if self.save_changes_diff.as_ref().is_some_and(|diff| diff.path == path) {
+            return;
+        }
+
+        let path_str = path.display().to_string();
+        let staged = crate::git::get_file_diff(&path_str, true);
+        let unstaged = crate::git::get_file_diff(&path_str, false);
+        let mut lines = Vec::new();
+        let mut hunks = Vec::new();
+        match (staged, unstaged) {
+            (Ok(staged), Ok(unstaged)) => {
+                let both = !staged.is_empty() && !unstaged.is_empty();
+                for (side, is_staged, heading) in
+                    [(staged, true, "Staged"), (unstaged, false, "Not staged")]
+                {
+                    if side.is_empty() {
+                        continue;
+                    }
+                    if both {
+                        if !lines.is_empty() {
+                            lines.push(String::new());
+                        }
+                        lines.push(heading.to_string());
+                    }
+                    hunks.extend(diff_hunks(&side, lines.len(), is_staged));
+                    lines.extend(side);
+                }
+            }
+            (Err(e), _) | (_, Err(e)) => lines.push(format!("Failed to compute the diff: {}", e)),
+        }
+        let encoding = crate::encoding::detect_file(&path);
+        self.save_changes_diff = Some(crate::app::SaveChangesDiff {
+            path,
+            numbers: diff_line_numbers(&lines),
+            lines,
+            scroll: 0,
+            hunks,
+            selected_hunk: 0,
+            cursor: 0,
+            encoding,
+        });
+        match self.save_changes_focus {
+            SaveChangesFocus::HunkView => self.hunk_view_navigate(0),
+            SaveChangesFocus::Review => self.open_review(),
+            _ => {}
+        }
+    }
+
+    /// Pick the hunks of the selected file one by one in the diff pane
+    pub fn open_hunk_view(&mut self) {
+        self.show_save_changes_diff = true;
+        self.load_save_changes_diff();
+        if self.save_changes_diff.as_ref().is_some_and(|diff| !diff.hunks.is_empty()) {
+            self.save_changes_focus = SaveChangesFocus::HunkView;
+            self.hunk_view_navigate(0);
+        }
+    }
+
+    /// Move the hunk selection by `delta`, scrolling the diff so the hunk is in view
+    pub fn hunk_view_navigate(&mut self, delta: isize) {
+        let diff = match self.save_changes_diff.as_mut() {
+            Some(diff) if !diff.hunks.is_empty() => diff,
+            _ => {
+                self.save_changes_focus = SaveChangesFocus::FileList;
+                return;
+            }
+        };
+        diff.selected_hunk = diff
+            .selected_hunk
+            .saturating_add_signed(delta)
+            .min(diff.hunks.len() - 1);
+        let hunk = &diff.hunks[diff.selected_hunk];
+        // Keep the section heading above the first hunk of a side in view
+        diff.scroll = if diff.hunks[..diff.selected_hunk].iter().all(|h| h.staged != hunk.staged) {
+            hunk.start.saturating_sub(1)
+        } else {
+            hunk.start
+        };
+    }
+
+    /// Stage the selected hunk if it is unstaged, unstage it if it is staged
+    pub fn toggle_selected_hunk(&mut self) {
+        if self.refuse_read_only("Staging hunks") {
+            return;
+        }
+        let (path, index, staged, selected) = match &self.save_changes_diff {
+            Some(diff) => match diff.hunks.get(diff.selected_hunk) {
+                Some(hunk) => (
+                    diff.path.display().to_string(),
+                    hunk.index,
+                    hunk.staged,
+                    diff.selected_hunk,
+                ),
+                None => return,
+            },
+            None => return,
+        };
+        self.last_commit_summary = None;
+
+        let result = crate::git::apply_hunk_to_index(&path, index, staged);
+        let (kind, verb) = if staged {
+            (ActivityKind::Unstage, "Unstaged")
+        } else {
+            (ActivityKind::Stage, "Staged")
+        };
+        self.record_activity(
+            kind,
+            &path,
+            &format!(
+                "libgit2: apply hunk {} of the {} diff to the index",
+                index + 1,
+                if staged { "reversed HEAD-to-index" } else { "index-to-workdir" }
+            ),
+            match &result {
+                Ok(()) => Ok(format!("{} hunk {}", verb, index + 1)),
+                Err(e) => Err(e.to_string()),
+            },
+        );
+        if let Err(e) = result {
+            self.show_error(
+                "Hunk Failed",
+                &format!("Failed to apply the hunk to the index:\n\n{}", e),
+            );
+        }
+
+        // Re-read the file's diff, staying at the same place in the hunk list
+        self.refresh_save_changes_git_status();
+        self.load_save_changes_diff();
+        if let Some(diff) = self.save_changes_diff.as_mut() {
+            diff.selected_hunk = selected;
+        }
+        self.hunk_view_navigate(0);
+    }
+
+    /// Scroll the diff pane by `delta` lines, keeping at least the last line in view
+    ///
+    /// In review mode the pane follows the cursor, so the cursor moves instead.
+    pub fn scroll_save_changes_diff(&mut self, delta: isize) {
+        if self.save_changes_focus == SaveChangesFocus::Review {
+            self.review_navigate(delta);
+            return;
+        }
+        if let Some(diff) = self.save_changes_diff.as_mut() {
+            diff.scroll = diff
+                .scroll
+                .saturating_add_signed(delta)
+                .min(diff.lines.len().saturating_sub(1));
+        }
+    }
+
+    /// Stage or unstage the selected file
+    ///
+    /// The cached entry is flipped before git is touched so the list updates at
+    /// once; a background re-scan then reconciles it with the real status.
+    pub fn toggle_file_staging(&mut self) {
+        if self.refuse_read_only("Staging files") {
+            return;
+        }
+        // Staging starts the next commit, so the previous summary is stale
+        self.last_commit_summary = None;
+
+        let selected_idx = match self.save_changes_table_state.selected() {
+            Some(idx) if idx < self.save_changes_git_status.len() => idx,
+            _ => return,
+        };
+        let previous = self.save_changes_git_status[selected_idx].clone();
+        let path_str = previous.path.display().to_string();
+
+        // Optimistic update of the single entry; it moves to its new section and the
+        // selection goes with it
+        let file = &mut self.save_changes_git_status[selected_idx];
+        file.staged = !previous.staged;
+        file.status = match (&previous.status, file.staged) {
+            (FileStatusType::Untracked, true) => FileStatusType::Added,
+            (FileStatusType::Added, false) => FileStatusType::Untracked,
+            (status, _) => status.clone(),
+        };
+        let before_toggle = self.save_changes_git_status.clone();
+        crate::git::sort_status(&mut self.save_changes_git_status);
+        let moved_idx = self
+            .save_changes_git_status
+            .iter()
+            .position(|file| file.path == previous.path)
+            .unwrap_or(selected_idx);
+        self.save_changes_table_state.select(Some(moved_idx));
+
+        let result = if previous.staged {
+            self.unstage_path(&path_str)
+        } else {
+            self.stage_path(&path_str)
+        };
+        match result {
+            Ok(()) => {
+                self.emit_status_changed(crate::app::status_counts(&self.save_changes_git_status));
+                self.start_save_changes_reconcile();
+            }
+            Err(e) => {
+                let action = if previous.staged { "unstage" } else { "stage" };
+                self.save_changes_git_status = before_toggle;
+                self.save_changes_git_status[selected_idx] = previous;
+                self.save_changes_table_state.select(Some(selected_idx));
+                self.show_error(
+                    "Staging Failed",
+                    &format!("Failed to {} {}:\n\n{}", action, path_str, e),
+                );
+            }
+        }
+    }
+
+    /// The file drawn on row `row` of the Save Changes list, skipping section headers
+    pub fn save_changes_file_at_row(&self, row: usize) -> Option<usize> {
+        match status_rows(&self.save_changes_git_status).get(row) {
+            Some(StatusRow::File(idx)) => Some(*idx),
+            _ => None,
+        }
+    }
+
+    /// The file under the cursor in the Save Changes list
+    pub fn selected_save_changes_file(&self) -> Option<&crate::git::GitFileStatus> {
+        self.save_changes_table_state
+            .selected()
+            .and_then(|idx| self.save_changes_git_status.get(idx))
+    }
+
+    /// Open the detail popup for the selected file
+    pub fn open_file_detail(&mut self) {
+        let file = match self.selected_save_changes_file() {
+            Some(file) => file.clone(),
+            None => return,
+        };
+        let diff = crate::git::get_file_diff_detail(&file.path.display().to_string()).ok();
+        self.file_detail = Some(FileDetail {
+            file,
+            diff,
+            confirm_discard: false,
+        });
+    }
+
+    /// Re-read the popup's file after an action; closes the popup once the file has no changes left
+    pub fn refresh_file_detail(&mut self) {
+        let path = match &self.file_detail {
+            Some(detail) => detail.file.path.clone(),
+            None => return,
+        };
+        match self.save_changes_git_status.iter().position(|file| file.path == path) {
+            Some(idx) => {
+                self.save_changes_table_state.select(Some(idx));
+                self.open_file_detail();
+            }
+            None => self.file_detail = None,
+        }
+    }
+
+    /// Stage or unstage the file shown in the detail popup
+    pub fn file_detail_toggle_staging(&mut self) {
+        self.toggle_file_staging();
+        self.refresh_file_detail();
+    }
+
+    /// Discard the changes of the file shown in the detail popup (after the user confirmed)
+    pub fn file_detail_discard(&mut self) {
+        if let Some(detail) = self.file_detail.take() {
+            let path = detail.file.path.display().to_string();
+            self.discard_path(&path, is_new(&detail.file.status));
+        }
+    }
+
+    /// Ask before discarding the changes of the selected file
+    pub fn request_discard_selected(&mut self) {
+        if self.refuse_read_only("Discarding changes") {
+            return;
+        }
+        if let Some(file) = self.selected_save_changes_file() {
+            self.discard_confirm = Some(DiscardConfirm {
+                path: file.path.display().to_string(),
+                untracked: is_new(&file.status),
+            });
+        }
+    }
+
+    /// Discard the changes of the file the user just confirmed
+    pub fn confirm_discard(&mut self) {
+        if let Some(confirm) = self.discard_confirm.take() {
+            self.discard_path(&confirm.path, confirm.untracked);
+        }
+    }
+
+    /// Put `path` back the way HEAD has it, removing it when HEAD doesn't have it
+    fn discard_path(&mut self, path: &str, new: bool) {
+        if self.refuse_read_only("Discarding changes") {
+            return;
+        }
+        let permanent = self.deletes_permanently();
+        let result = crate::git::discard_file_changes(path, permanent);
+        self.record_activity(
+            ActivityKind::Discard,
+            path,
+            &if new {
+                format!("libgit2: remove {} from the index, then delete it", path)
+            } else {
+                format!("libgit2: checkout {} from HEAD", path)
+            },
+            match &result {
+                Ok(()) if new && !permanent => Ok("Moved to the trash".to_string()),
+                Ok(()) if new => Ok("Deleted".to_string()),
+                Ok(()) => Ok("Discarded".to_string()),
+                Err(e) => Err(e.to_string()),
+            },
+        );
+        if let Err(e) = result {
+            self.show_error("Discard Failed", &format!("Failed to discard {}:\n\n{}", path, e));
+        }
+        self.refresh_save_changes_git_status();
+    }
+
+    /// Check the selected deleted file back out from HEAD
+    pub fn restore_selected_deleted_file(&mut self) {
+        if self.refuse_read_only("Restoring files") {
+            return;
+        }
+        let path = match self.selected_save_changes_file() {
+            Some(file) if matches!(file.status, FileStatusType::Deleted) => {
+                file.path.display().to_string()
+            }
+            _ => return,
+        };
+        let result = crate::git::restore_deleted_file(&path);
+        self.record_activity(
+            ActivityKind::Restore,
+            &path,
+            &format!("libgit2: reset and checkout {} from HEAD", path),
+            match &result {
+                Ok(()) => Ok("Restored".to_string()),
+                Err(e) => Err(e.to_string()),
+            },
+        );
+        if let Err(e) = result {
+            self.show_error("Restore Failed", &format!("Failed to restore {}:\n\n{}", path, e));
+        }
+        self.refresh_save_changes_git_status();
+        self.refresh_file_detail();
+    }
+
+    /// Quick-save everything as a WIP commit or stash (gitix.wip.mode), with a generated message
+    pub fn wip_save(&mut self) {
+        if self.refuse_read_only("Saving work in progress") {
+            return;
+        }
+        let files = crate::git::get_git_status().unwrap_or_default();
+        if files.is_empty() {
+            self.last_commit_summary =
+                Some("Nothing to save: the working tree is clean".to_string());
+            return;
+        }
+        let mode = crate::config::get_wip_mode()
+            .ok()
+            .flatten()
+            .unwrap_or(crate::git::WipMode::Commit);
+        let branch = crate::git::get_current_branch().unwrap_or_else(|_| "HEAD".to_string());
+        let message = format!(
+            "{} on {}: {} file(s), {}",
+            crate::git::WIP_MARKER,
+            branch,
+            files.len(),
+            chrono::Local::now().format("%Y-%m-%d %H:%M")
+        );
+
+        let result = crate::git::wip_save(mode, &message);
+        let (kind, operation) = match mode {
+            crate::git::WipMode::Commit => (
+                ActivityKind::Commit,
+                format!("git add --all && git commit --no-verify -m {:?}", message),
+            ),
+            crate::git::WipMode::Stash => (
+                ActivityKind::Stash,
+                format!("git stash push --include-untracked -m {:?}", message),
+            ),
+        };
+        self.record_activity(
+            kind,
+            &branch,
+            &operation,
+            match &result {
+                Ok(()) => Ok(format!("Saved {} file(s) as WIP", files.len())),
+                Err(e) => Err(e.to_string()),
+            },
+        );
+        match result {
+            Ok(()) => {
+                self.last_commit_summary = Some(match mode {
+                    crate::git::WipMode::Commit => {
+                        format!("✓ WIP committed ({} files) - [Shift+W] to resume", files.len())
+                    }
+                    crate::git::WipMode::Stash => {
+                        format!("✓ WIP stashed ({} files) - [Shift+W] to resume", files.len())
+                    }
+                });
+            }
+            Err(e) => self.show_error(
+                "WIP Save Failed",
+                &format!("Failed to save work in progress:\n\n{}", e),
+            ),
+        }
+        self.refresh_save_changes_git_status();
+    }
+
+    /// Undo the latest WIP quick-save: soft-reset a WIP commit or pop a WIP stash
+    pub fn wip_resume(&mut self) {
+        if self.refuse_read_only("Resuming work in progress") {
+            return;
+        }
+        let save = match crate::git::find_wip() {
+            Ok(Some(save)) => save,
+            Ok(None) => {
+                self.show_error(
+                    "Nothing to Resume",
+                    "There is no WIP quick-save: HEAD isn't a WIP commit and no stash was \
+                     saved with W.",
+                );
+                return;
+            }
+            Err(e) => {
+                self.show_error(
+                    "Resume Failed",
+                    &format!("Failed to look for WIP saves:\n\n{}", e),
+                );
+                return;
+            }
+        };
+        if save == (crate::git::WipSave::Commit { pushed: true }) {
+            self.show_error(
+                "Resume Failed",
+                "The WIP commit has already been pushed, so undoing it here would rewrite \
+                 shared history.\n\n\
+                 Make a new commit on top of it instead.",
+            );
+            return;
+        }
+        if matches!(save, crate::git::WipSave::Stash(_))
+            && !crate::git::get_git_status().unwrap_or_default().is_empty()
+        {
+            // Popping over local changes can conflict; let them be saved first
+            self.show_error(
+                "Resume Failed",
+                "Commit or save your current changes before resuming a stashed WIP.",
+            );
+            return;
+        }
+
+        let branch = crate::git::get_current_branch().unwrap_or_else(|_| "HEAD".to_string());
+        let result = crate::git::wip_resume(&save);
+        let (kind, operation) = match &save {
+            crate::git::WipSave::Commit { .. } => {
+                (ActivityKind::Reset, "git reset --soft HEAD~1".to_string())
+            }
+            crate::git::WipSave::Stash(index) => {
+                (ActivityKind::Stash, format!("git stash pop stash@{{{}}}", index))
+            }
+        };
+        self.record_activity(
+            kind,
+            &branch,
+            &operation,
+            match &result {
+                Ok(()) => Ok("Resumed WIP".to_string()),
+                Err(e) => Err(e.to_string()),
+            },
+        );
+        match result {
+            Ok(()) => self.last_commit_summary = Some("✓ WIP resumed".to_string()),
+            Err(e) => self.show_error(
+                "Resume Failed",
+                &format!("Failed to resume work in progress:\n\n{}", e),
+            ),
+        }
+        self.refresh_save_changes_git_status();
+    }
+
+    /// Start a clean: list what would be removed, without touching anything yet
+    pub fn open_clean_preview(&mut self, include_ignored: bool) {
+        if self.refuse_read_only("Removing untracked files") {
+            return;
+        }
+        match crate::git::list_cleanable(include_ignored) {
+            Ok(paths) => {
+                self.clean_preview = Some(CleanPreview {
+                    paths,
+                    include_ignored,
+                    confirming: false,
+                    scroll: 0,
+                })
+            }
+            Err(e) => self.show_error(
+                "Clean Failed",
+                &format!("Failed to list untracked files:\n\n{}", e),
+            ),
+        }
+    }
+
+    /// Remove the files listed in the clean preview (after both confirmation steps)
+    pub fn perform_clean(&mut self) {
+        let preview = match self.clean_preview.take() {
+            Some(preview) if preview.confirming => preview,
+            other => {
+                self.clean_preview = other;
+                return;
+            }
+        };
+        let permanent = self.deletes_permanently();
+        let result = crate::git::clean(&preview.paths, preview.include_ignored, permanent);
+        self.record_activity(
+            ActivityKind::Clean,
+            &format!("{} path(s)", preview.paths.len()),
+            if preview.include_ignored {
+                "git clean -d -x (libgit2 status + remove)"
+            } else {
+                "git clean -d (libgit2 status + remove)"
+            },
+            match &result {
+                Ok(removed) if permanent => Ok(format!("Removed {} path(s)", removed)),
+                Ok(removed) => Ok(format!("Moved {} path(s) to the trash", removed)),
+                Err(e) => Err(e.to_string()),
+            },
+        );
+        if let Err(e) = result {
+            self.show_error("Clean Failed", &format!("Failed to remove untracked files:\n\n{}", e));
+        }
+        self.refresh_save_changes_git_status();
+    }
+
+    /// Open the stage-by-pattern prompt over every file with unstaged changes
+    pub fn open_stage_pattern(&mut self) {
+        if self.refuse_read_only("Staging") {
+            return;
+        }
+        match crate::git::unstaged_paths() {
+            Ok(candidates) => {
+                self.stage_pattern = Some(StagePattern {
+                    input: tui_textarea::TextArea::new(vec![String::new()]),
+                    candidates,
+                    matches: Vec::new(),
+                    error: None,
+                    scroll: 0,
+                })
+            }
+            Err(e) => self.show_error(
+                "Stage by Pattern",
+                &format!("Failed to list unstaged files:\n\n{}", e),
+            ),
+        }
+    }
+
+    /// Match the pathspec typed so far against the unstaged files again
+    pub fn update_stage_pattern_matches(&mut self) {
+        if let Some(pattern) = self.stage_pattern.as_mut() {
+            let spec = pattern.input.lines().join(" ");
+            let (matches, error) = if spec.trim().is_empty() {
+                (Vec::new(), None)
+            } else {
+                match crate::git::match_pathspec(&spec, &pattern.candidates) {
+                    Ok(matches) => (matches, None),
+                    Err(e) => (Vec::new(), Some(e.to_string())),
+                }
+            };
+            pattern.matches = matches;
+            pattern.error = error;
+            pattern.scroll = 0;
+        }
+    }
+
+    /// Scroll the stage-by-pattern preview by `delta` lines; rendering stops it at the end
+    pub fn stage_pattern_scroll(&mut self, delta: isize) {
+        if let Some(pattern) = self.stage_pattern.as_mut() {
+            pattern.scroll = pattern.scroll.saturating_add_signed(delta);
+        }
+    }
+
+    /// Stage every file the pathspec matches in one go
+    pub fn confirm_stage_pattern(&mut self) {
+        if self
+            .stage_pattern
+            .as_ref()
+            .is_none_or(|pattern| pattern.matches.is_empty())
+        {
+            return;
+        }
+        let pattern = match self.stage_pattern.take() {
+            Some(pattern) => pattern,
+            None => return,
+        };
+        let spec = pattern.input.lines().join(" ");
+        let paths: Vec<&str> = pattern.matches.iter().map(String::as_str).collect();
+        let result = crate::git::stage_files(&paths);
+        self.record_activity(
+            ActivityKind::Stage,
+            spec.trim(),
+            &format!("libgit2: index.add_path for {} path(s) matching {}", paths.len(), spec.trim()),
+            match &result {
+                Ok(()) => Ok(format!("Staged {} file(s)", paths.len())),
+                Err(e) => Err(e.to_string()),
+            },
+        );
+        if let Err(e) = result {
+            self.show_error(
+                "Stage Failed",
+                &format!("Failed to stage the files matching {}:\n\n{}", spec.trim(), e),
+            );
+        }
+        self.refresh_save_changes_git_status();
+        self.invalidate_status_git_status();
+    }
+
+    /// Add the untracked file shown in the detail popup to .gitignore
+    pub fn file_detail_ignore(&mut self) {
+        if self.refuse_read_only("Editing .gitignore") {
+            return;
+        }
+        let path = match &self.file_detail {
+            Some(detail) if matches!(detail.file.status, FileStatusType::Untracked) => {
+                detail.file.path.display().to_string()
+            }
+            _ => return,
+        };
+        self.file_detail = None;
+        if let Err(e) = crate::git::ignore_path(&path) {
+            self.show_error("Ignore Failed", &format!("Failed to add {} to .gitignore:\n\n{}", path, e));
+        }
+        self.refresh_save_changes_git_status();
+    }
+
+    /// Save the commit message box as the repository's draft, or drop the draft when the box is empty
+    ///
+    /// Failures are ignored: losing a draft must never get in the way of quitting.
+    pub fn save_commit_draft(&self) {
+        // While amending the editor holds HEAD's message, not a draft
+        if !self.git_enabled || self.commit_amend.is_some() {
+            return;
+        }
+        let path = match commit_draft_path() {
+            Some(path) => path,
+            None => return,
+        };
+
+        let message = self.commit_message.lines().join("\n");
+        if message.trim().is_empty() {
+            let _ = std::fs::remove_file(path);
+        } else {
+            let _ = std::fs::write(path, message);
+        }
+    }
+
+    /// Put a draft left by an earlier session back into an empty commit message box
+    pub fn restore_commit_draft(&mut self) {
+        if !self.git_enabled || !self.commit_message.is_empty() {
+            return;
+        }
+        let draft = commit_draft_path().and_then(|path| std::fs::read_to_string(path).ok());
+        let draft = match draft {
+            Some(draft) if !draft.trim().is_empty() => draft,
+            _ => return,
+        };
+
+        self.commit_message =
+            tui_textarea::TextArea::new(draft.lines().map(str::to_string).collect());
+        self.commit_message.move_cursor(CursorMove::Bottom);
+        self.commit_message.move_cursor(CursorMove::End);
+        self.commit_draft_restored = true;
+    }
+
+    /// Hard-wrap the body line under the cursor at the configured column (gitix.commit.wrap)
+    ///
+    /// The subject line is never wrapped. The line is broken at the last space
+    /// that fits, and the cursor follows the text it was on.
+    pub fn wrap_commit_body(&mut self) {
+        let column = self.commit_wrap_column;
+        let (row, col) = self.commit_message.cursor();
+        if column == 0 || row == 0 {
+            return;
+        }
+
+        let line: Vec<char> = self.commit_message.lines()[row].chars().collect();
+        if line.len() <= column {
+            return;
+        }
+        let space = match line[..=column].iter().rposition(|c| *c == ' ') {
+            Some(space) if space > 0 => space,
+            _ => return, // A single word longer than the column stays as is
+        };
+
+        self.commit_message
+            .move_cursor(CursorMove::Jump(row as u16, space as u16));
+        self.commit_message.delete_next_char();
+        self.commit_message.insert_newline();
+
+        let (row, col) = if col > space {
+            (row + 1, col - space - 1)
+        } else {
+            (row, col)
+        };
+        self.commit_message
+            .move_cursor(CursorMove::Jump(row as u16, col as u16));
+    }
+
+    /// Stage one path and record it in the activity log
+    pub fn stage_path(&mut self, path: &str) -> Result<(), crate::git::GitError> {
+        let result = stage_file(path);
+        self.record_activity(
+            ActivityKind::Stage,
+            path,
+            &format!("libgit2: index.add_path({})", path),
+            match &result {
+                Ok(()) => Ok("Staged".to_string()),
+                Err(e) => Err(e.to_string()),
+            },
+        );
+        result
+    }
+
+    /// Unstage one path and record it in the activity log
+    pub fn unstage_path(&mut self, path: &str) -> Result<(), crate::git::GitError> {
+        let result = unstage_file(path);
+        self.record_activity(
+            ActivityKind::Unstage,
+            path,
+            &format!("libgit2: restore index entry for {} from HEAD", path),
+            match &result {
+                Ok(()) => Ok("Unstaged".to_string()),
+                Err(e) => Err(e.to_string()),
+            },
+        );
+        result
+    }
+
+    /// Run the commit formatter and re-stage what it changed; the report is `None` if nothing changed
+    fn format_staged_files(
+        &mut self,
+        formatter: &str,
+    ) -> Result<Option<crate::hooks::FormatReport>, Box<dyn std::error::Error>> {
+        self.start_loading("Formatting staged files...");
+        let result = crate::hooks::format_staged_files(formatter);
+        self.stop_loading();
+        if let Ok(report) = &result {
+            if report.formatted.is_empty() {
+                return Ok(None);
+            }
+        }
+        self.record_activity(
+            ActivityKind::Stage,
+            &match &result {
+                Ok(report) => format!("{} file(s)", report.formatted.len()),
+                Err(_) => "staged files".to_string(),
+            },
+            &format!("{} <staged files>, then libgit2: add the changed ones", formatter),
+            match &result {
+                Ok(report) => Ok(format!(
+                    "Formatted and re-staged {}",
+                    report
+                        .formatted
+                        .iter()
+                        .map(|file| file.path.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )),
+                Err(e) => Err(e.to_string()),
+            },
+        );
+        Ok(Some(result?))
+    }
+
+    /// The number of staged files and the commit message, or why there is nothing to commit yet
+    fn ready_to_commit(&self) -> Result<(usize, String), Box<dyn std::error::Error>> {
+        if self.read_only {
+            return Err("Committing is disabled: gitix was started with --read-only".into());
+        }
+        // Check if there are any staged files from cached git status
+        let staged_count = self
+            .save_changes_git_status
+            .iter()
+            .filter(|f| f.staged)
+            .count();
+
+        if staged_count == 0 {
+            return Err("No files staged for commit".into());
+        }
+
+        let commit_message = self.commit_message.lines().join("\n");
+        if commit_message.trim().is_empty() {
+            return Err("Commit message cannot be empty".into());
+        }
+        Ok((staged_count, commit_message))
+    }
+
+    /// Commit the staged files, running the gitix.verify.command check first when one is set
+    ///
+    /// The commit happens once the check passes; skipping hooks skips the check too.
+    pub fn start_commit(&mut self) {
+        let verify = match crate::config::get_verify_command() {
+            Ok(Some(command)) if !self.commit_skip_hooks => command,
+            _ => return self.finish_commit(),
+        };
+        if let Err(e) = self.ready_to_commit() {
+            self.show_error("Commit Failed", &format!("Failed to commit changes:\n\n{}", e));
+            return;
+        }
+        match crate::hooks::spawn_verify(&verify) {
+            Ok(running) => self.show_hook_output(running, crate::app::AfterHook::Commit),
+            Err(e) => self.show_error(
+                "Commit Failed",
+                &format!("Failed to run the verify command ({}):\n\n{}", verify, e),
+            ),
+        }
+    }
+
+    /// Commit the staged files now, showing why if that fails
+    pub fn finish_commit(&mut self) {
+        match self.commit_staged_files() {
+            // The message is in history now, so the draft is no longer needed
+            Ok(()) => self.save_commit_draft(),
+            Err(e) => {
+                // Show user-friendly error popup
+                self.show_error_with_source(
+                    "Commit Failed",
+                    &format!("Failed to commit changes:\n\n{}", e),
+                    e.as_ref(),
+                );
+            }
+        }
+    }
+
+    /// Commit even though the verify command failed
+    pub fn commit_despite_verify(&mut self) {
+        let failed = self.hook_output.as_ref().is_some_and(|output| {
+            output.then == crate::app::AfterHook::Commit
+                && matches!(output.status, crate::app::HookRunStatus::Failed(_))
+        });
+        if failed {
+            self.close_hook_output();
+            self.finish_commit();
+        }
+    }
+
+    pub fn commit_staged_files(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        let (staged_count, commit_message) = self.ready_to_commit()?;
+
+        // Format the staged files first, unless hooks are skipped for this commit
+        let skip_hooks = self.commit_skip_hooks;
+        let mut formatted = None;
+        if !skip_hooks {
+            if let Ok(Some(formatter)) = crate::config::get_commit_formatter() {
+                formatted = self.format_staged_files(&formatter)?;
+            }
+        }
+
+        // Start loading indicator
+        self.start_loading("Creating commit...");
+
+        // Perform the commit
+        let result = commit(&commit_message, skip_hooks);
+
+        // Stop loading indicator
+        self.stop_loading();
+
+        let operation = format!(
+            "libgit2: commit -m {:?}{}",
+            commit_message,
+            if skip_hooks { " --no-verify" } else { "" }
+        );
+        let branch = crate::git::get_current_branch().unwrap_or_else(|_| "HEAD".to_string());
+        self.record_activity(
+            ActivityKind::Commit,
+            &branch,
+            &operation,
+            match &result {
+                Ok(()) => Ok(format!("Committed {} file(s)", staged_count)),
+                Err(e) => Err(e.to_string()),
+            },
+        );
+
+        // Handle result
+        result?;
+
+        // Hooks are only ever skipped for a single commit
+        self.commit_skip_hooks = false;
+
+        let head = crate::git::head_commit_summary();
+        let mut summary = match &head {
+            Ok((short_id, subject)) => format!("✓ Committed {} {}", short_id, subject),
+            Err(_) => "✓ Commit created".to_string(),
+        };
+        if skip_hooks {
+            summary.push_str(" (hooks skipped)");
+        }
+        if let Some(report) = formatted {
+            summary.push_str(&format!(" (formatted {} file(s))", report.formatted.len()));
+            self.format_report = Some(crate::app::FormatReportView { report, scroll: 0 });
+        }
+        self.last_commit_summary = Some(summary);
+
+        if let Ok((short_id, subject)) = &head {
+            crate::events::emit(
+                "commit-created",
+                serde_json::json!({
+                    "branch": branch,
+                    "commit": short_id,
+                    "summary": subject,
+                    "no_verify": skip_hooks,
+                }),
+            );
+        }
+
+        let mut env = vec![("GITIX_BRANCH", branch.clone())];
+        if let Ok((short_id, _)) = head {
+            env.push(("GITIX_COMMIT", short_id));
+        }
+        self.run_ui_hook("post-commit-ui", &env);
+
+        // Clear commit message
+        self.commit_message = tui_textarea::TextArea::new(vec![String::new()]);
+        self.commit_draft_restored = false;
+
+        // Refresh git status cache after commit, preserving selection if possible
+        self.refresh_save_changes_git_status();
+
+        Ok(())
+    }
+
+    /// Start amending HEAD with its message in the editor, or give up amending
+    pub fn toggle_amend(&mut self) {
+        if self.commit_amend.is_none() && self.hidden_by_profile("Amending commits") {
+            return;
+        }
+        if self.commit_amend.take().is_some() {
+            self.commit_message = tui_textarea::TextArea::new(vec![String::new()]);
+            self.restore_commit_draft();
+            return;
+        }
+        match crate::git::head_message() {
+            Ok(message) => {
+                // Keep whatever was being typed for a regular commit
+                self.save_commit_draft();
+                self.commit_message =
+                    tui_textarea::TextArea::new(message.lines().map(str::to_string).collect());
+                self.commit_message.move_cursor(CursorMove::Bottom);
+                self.commit_message.move_cursor(CursorMove::End);
+                self.commit_draft_restored = false;
+                self.commit_amend = Some(message);
+                self.save_changes_focus = SaveChangesFocus::CommitMessage;
+            }
+            Err(e) => self.show_error(
+                "Cannot Amend",
+                &format!("There is no commit to amend:\n\n{}", e),
+            ),
+        }
+    }
+
+    /// Show how the message changed before rewriting HEAD
+    pub fn request_amend(&mut self) {
+        if self.refuse_read_only("Amending commits") {
+            return;
+        }
+        if self.commit_message.lines().join("\n").trim().is_empty() {
+            self.show_error("Amend Failed", "Commit message cannot be empty");
+            return;
+        }
+        if self.profile == Profile::Expert {
+            self.amend_commit();
+            return;
+        }
+        self.amend_confirm = true;
+    }
+
+    /// Rewrite HEAD with the edited message and anything staged
+    pub fn amend_commit(&mut self) {
+        self.amend_confirm = false;
+        let message = self.commit_message.lines().join("\n");
+        let skip_hooks = self.commit_skip_hooks;
+
+        self.start_loading("Amending commit...");
+        let result = crate::git::amend_commit(&message, skip_hooks);
+        self.stop_loading();
+
+        let operation = format!(
+            "git commit --amend -m {:?}{}",
+            message,
+            if skip_hooks { " --no-verify" } else { "" }
+        );
+        let branch = crate::git::get_current_branch().unwrap_or_else(|_| "HEAD".to_string());
+        self.record_activity(
+            ActivityKind::Commit,
+            &branch,
+            &operation,
+            match &result {
+                Ok(()) => Ok("Amended the last commit".to_string()),
+                Err(e) => Err(e.to_string()),
+            },
+        );
+
+        if let Err(e) = result {
+            self.show_error("Amend Failed", &format!("Failed to amend the commit:\n\n{}", e));
+            return;
+        }
+
+        self.commit_skip_hooks = false;
+        self.commit_amend = None;
+        self.last_commit_summary = Some(match crate::git::head_commit_summary() {
+            Ok((short_id, subject)) => format!("✓ Amended {} {}", short_id, subject),
+            Err(_) => "✓ Commit amended".to_string(),
+        });
+        self.commit_message = tui_textarea::TextArea::new(vec![String::new()]);
+        self.restore_commit_draft();
+        self.refresh_save_changes_git_status();
+    }
+
+    pub fn switch_save_changes_focus(&mut self) {
+        // Only allow focus switching if there are changes to commit
+        if self.save_changes_git_status.is_empty() {
+            // No changes to commit, keep focus on commit message
+            self.save_changes_focus = SaveChangesFocus::CommitMessage;
+            return;
+        }
+
+        self.save_changes_focus = match self.save_changes_focus {
+            SaveChangesFocus::FileList | SaveChangesFocus::HunkView | SaveChangesFocus::Review => {
+                SaveChangesFocus::CommitMessage
+            }
             SaveChangesFocus::CommitMessage => SaveChangesFocus::FileList,
         };
     }
@@ -724,6 +2512,8 @@ impl AppState {
                     // If at the last item, stay there (no wrapping to commit message)
                 }
             }
+            SaveChangesFocus::HunkView => self.hunk_view_navigate(1),
+            SaveChangesFocus::Review => self.review_navigate(1),
         }
     }
 
@@ -754,6 +2544,8 @@ impl AppState {
                 self.commit_message
                     .move_cursor(tui_textarea::CursorMove::Up);
             }
+            SaveChangesFocus::HunkView => self.hunk_view_navigate(-1),
+            SaveChangesFocus::Review => self.review_navigate(-1),
         }
     }
 
@@ -1,9 +1,14 @@
-use crate::app::{AppState, AuthorFocus, GitFocus, SettingsFocus, ThemeFocus};
+use crate::app::{
+    AppState, AuthorFocus, GitFocus, Profile, RemoteEdit, SettingsFocus, ThemeFocus,
+};
+use crate::tui::text;
 use crate::tui::theme::{AccentColor, Theme, TitleColor};
 use ratatui::layout::{Alignment, Constraint, Direction, Layout, Margin};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap};
+use ratatui::widgets::{
+    Block, Borders, Cell, Clear, List, ListItem, Paragraph, Row, Table, TableState, Wrap,
+};
 use ratatui::{layout::Rect, Frame};
 
 pub fn render_settings_tab(f: &mut Frame, area: Rect, state: &AppState) {
@@ -32,7 +37,24 @@ pub fn render_settings_tab(f: &mut Frame, area: Rect, state: &AppState) {
         .constraints([Constraint::Min(1), Constraint::Length(3)])
         .split(area);
 
-    // Split main area into three columns: Author, Theme, and Git
+    // Split main area into the configuration panels (top) and hooks and remotes (bottom)
+    let section_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(1),     // Author, Theme, and Git panels
+            Constraint::Length(10), // Hooks and Remotes
+        ])
+        .margin(1)
+        .split(main_chunks[0]);
+    let bottom_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(55), // Hooks
+            Constraint::Percentage(45), // Remotes
+        ])
+        .split(section_chunks[1]);
+
+    // Split configuration area into three columns: Author, Theme, and Git
     let content_chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
@@ -40,8 +62,7 @@ pub fn render_settings_tab(f: &mut Frame, area: Rect, state: &AppState) {
             Constraint::Percentage(33), // Theme
             Constraint::Percentage(34), // Git
         ])
-        .margin(1)
-        .split(main_chunks[0]);
+        .split(section_chunks[0]);
 
     // Render Author panel
     render_author_panel(f, content_chunks[0], state, &theme);
@@ -52,8 +73,25 @@ pub fn render_settings_tab(f: &mut Frame, area: Rect, state: &AppState) {
     // Render Git panel
     render_git_panel(f, content_chunks[2], state, &theme);
 
+    // Render Hooks panel
+    render_hooks_panel(f, bottom_chunks[0], state, &theme);
+
+    // Render Remotes panel
+    render_remotes_panel(f, bottom_chunks[1], state, &theme);
+
     // Render status bar
     render_status_bar(f, main_chunks[1], state, &theme);
+
+    // Render hook template picker if shown
+    if state.show_hook_template_popup {
+        render_hook_template_popup(f, area, state, &theme);
+    }
+    if let Some(edit) = &state.remote_edit {
+        render_remote_edit_popup(f, area, edit, &theme);
+    }
+    if let Some(name) = &state.remote_remove {
+        render_remote_remove_popup(f, area, name, &theme);
+    }
 }
 
 fn render_author_panel(f: &mut Frame, area: Rect, state: &AppState, theme: &Theme) {
@@ -490,11 +528,64 @@ fn render_status_bar(f: &mut Frame, area: Rect, state: &AppState, theme: &Theme)
                 }
             },
             SettingsFocus::Git => match state.settings_git_focus {
+                GitFocus::Profile => {
+                    "←/→: Change profile • ↑/↓: Switch option • Ctrl+←/→: Switch panel • Ctrl+S: Save"
+                        .to_string()
+                }
                 GitFocus::PullRebase => {
-                    "←/→: Toggle pull strategy • Ctrl+←/→: Switch panel • Ctrl+S: Save"
+                    "←/→: Toggle pull strategy • ↑/↓: Switch option • Ctrl+←/→: Switch panel • Ctrl+S: Save"
+                        .to_string()
+                }
+                GitFocus::Notifications => {
+                    "←/→: Toggle desktop notifications • ↑/↓: Switch option • Ctrl+←/→: Switch panel • Ctrl+S: Save"
+                        .to_string()
+                }
+                GitFocus::AutoRefresh => {
+                    "←/→: Change refresh interval • ↑/↓: Switch option • Ctrl+←/→: Switch panel • Ctrl+S: Save"
+                        .to_string()
+                }
+                GitFocus::ForkPush => {
+                    "←/→: Toggle push after fork sync • ↑/↓: Switch option • Ctrl+←/→: Switch panel • Ctrl+S: Save"
+                        .to_string()
+                }
+                GitFocus::CommitWrap => {
+                    "←/→: Change commit body wrap • ↑/↓: Switch option • Ctrl+←/→: Switch panel • Ctrl+S: Save"
+                        .to_string()
+                }
+                GitFocus::InitTemplate => {
+                    "←/→: Change new repository template • ↑/↓: Switch option • Ctrl+←/→: Switch panel • Ctrl+S: Save"
+                        .to_string()
+                }
+                GitFocus::PermanentDelete => {
+                    "←/→: Toggle trash or permanent delete • ↑/↓: Switch option • Ctrl+←/→: Switch panel • Ctrl+S: Save"
+                        .to_string()
+                }
+                GitFocus::UpdateCheck => {
+                    "←/→: Toggle checking for new releases • ↑/↓: Switch option • Ctrl+←/→: Switch panel • Ctrl+S: Save"
+                        .to_string()
+                }
+                GitFocus::Forge => {
+                    "←/→: Change forge (auto-detect or pick one) • ↑/↓: Switch option • Ctrl+←/→: Switch panel • Ctrl+S: Save"
                         .to_string()
                 }
             },
+            SettingsFocus::Hooks if state.show_hook_template_popup => {
+                "↑/↓: Select template • Enter: Install • Esc: Cancel".to_string()
+            }
+            SettingsFocus::Hooks => {
+                "↑/↓: Select hook • Space: Enable/disable • E: Edit • T: Install template • Ctrl+←/→: Switch panel"
+                    .to_string()
+            }
+            SettingsFocus::Remotes if state.remote_edit.is_some() => {
+                "Tab: Switch field • Enter: Save • Esc: Cancel".to_string()
+            }
+            SettingsFocus::Remotes if state.remote_remove.is_some() => {
+                "Y: Remove • N: Cancel".to_string()
+            }
+            SettingsFocus::Remotes => {
+                "↑/↓: Select remote • A: Add • E/Enter: Rename or change URL • D: Remove • Ctrl+←/→: Switch panel"
+                    .to_string()
+            }
         }
     };
 
@@ -563,16 +654,70 @@ fn render_git_panel(f: &mut Frame, area: Rect, state: &AppState, theme: &Theme)
     let inner_area = block.inner(area);
     f.render_widget(block, area);
 
-    // Split into pull rebase section and help text
+    // Split into pull rebase section, notifications section and help text
     let git_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
+            Constraint::Length(3), // Profile setting
             Constraint::Length(3), // Pull rebase setting
+            Constraint::Length(3), // Notifications setting
+            Constraint::Length(3), // Auto-refresh setting
+            Constraint::Length(3), // Fork sync push setting
+            Constraint::Length(3), // Commit body wrap setting
+            Constraint::Length(3), // New repository template setting
+            Constraint::Length(3), // Delete setting
+            Constraint::Length(3), // Update check setting
+            Constraint::Length(3), // Forge setting
             Constraint::Min(1),    // Help text
         ])
         .margin(1)
         .split(inner_area);
 
+    // Profile setting
+    let profile_focused = is_focused && state.settings_git_focus == GitFocus::Profile;
+
+    let profile_block = Block::default()
+        .borders(Borders::ALL)
+        .title("Profile")
+        .title_style(if profile_focused {
+            theme.accent_style()
+        } else {
+            theme.secondary_text_style()
+        })
+        .border_style(if profile_focused {
+            theme.focused_border_style()
+        } else {
+            theme.border_style()
+        })
+        .style(theme.secondary_background_style());
+
+    f.render_widget(profile_block, git_chunks[0]);
+
+    let profile_inner = git_chunks[0].inner(Margin {
+        vertical: 1,
+        horizontal: 1,
+    });
+
+    let profile_style = if profile_focused {
+        Style::default()
+            .fg(theme.accent())
+            .add_modifier(Modifier::BOLD)
+    } else {
+        theme.text_style()
+    };
+    let profile_note = match state.profile {
+        Profile::Beginner => "  (safe actions only, asks before pull/push)",
+        Profile::Standard => "",
+        Profile::Expert => "  (everything, fewer prompts)",
+    };
+    f.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled(state.profile.title(), profile_style),
+            Span::styled(profile_note, theme.muted_text_style()),
+        ])),
+        profile_inner,
+    );
+
     // Pull rebase setting
     let pull_rebase_focused = is_focused && state.settings_git_focus == GitFocus::PullRebase;
 
@@ -591,15 +736,21 @@ fn render_git_panel(f: &mut Frame, area: Rect, state: &AppState, theme: &Theme)
         })
         .style(theme.secondary_background_style());
 
-    f.render_widget(pull_rebase_block, git_chunks[0]);
+    f.render_widget(pull_rebase_block, git_chunks[1]);
 
     // Render pull rebase content
-    let pull_rebase_inner = git_chunks[0].inner(Margin {
+    let pull_rebase_inner = git_chunks[1].inner(Margin {
         vertical: 1,
         horizontal: 1,
     });
 
-    let rebase_text = if state.pull_rebase { "Rebase" } else { "Merge" };
+    let rebase_text = if state.pull_rebase && state.profile == Profile::Beginner {
+        "Merge (rebase is off in the beginner profile)"
+    } else if state.pull_rebase {
+        "Rebase"
+    } else {
+        "Merge"
+    };
     let rebase_style = if pull_rebase_focused {
         Style::default()
             .fg(theme.accent())
@@ -611,6 +762,352 @@ fn render_git_panel(f: &mut Frame, area: Rect, state: &AppState, theme: &Theme)
     let rebase_paragraph = Paragraph::new(Span::styled(rebase_text, rebase_style));
     f.render_widget(rebase_paragraph, pull_rebase_inner);
 
+    // Notifications setting
+    let notifications_focused =
+        is_focused && state.settings_git_focus == GitFocus::Notifications;
+
+    let notifications_block = Block::default()
+        .borders(Borders::ALL)
+        .title("Desktop Notifications")
+        .title_style(if notifications_focused {
+            theme.accent_style()
+        } else {
+            theme.secondary_text_style()
+        })
+        .border_style(if notifications_focused {
+            theme.focused_border_style()
+        } else {
+            theme.border_style()
+        })
+        .style(theme.secondary_background_style());
+
+    f.render_widget(notifications_block, git_chunks[2]);
+
+    let notifications_inner = git_chunks[2].inner(Margin {
+        vertical: 1,
+        horizontal: 1,
+    });
+
+    let notifications_text = if state.notifications_enabled {
+        "On"
+    } else {
+        "Off"
+    };
+    let notifications_style = if notifications_focused {
+        Style::default()
+            .fg(theme.accent())
+            .add_modifier(Modifier::BOLD)
+    } else {
+        theme.text_style()
+    };
+
+    let notifications_paragraph =
+        Paragraph::new(Span::styled(notifications_text, notifications_style));
+    f.render_widget(notifications_paragraph, notifications_inner);
+
+    // Auto-refresh setting
+    let auto_refresh_focused = is_focused && state.settings_git_focus == GitFocus::AutoRefresh;
+
+    let auto_refresh_block = Block::default()
+        .borders(Borders::ALL)
+        .title("Update Tab Auto-Refresh")
+        .title_style(if auto_refresh_focused {
+            theme.accent_style()
+        } else {
+            theme.secondary_text_style()
+        })
+        .border_style(if auto_refresh_focused {
+            theme.focused_border_style()
+        } else {
+            theme.border_style()
+        })
+        .style(theme.secondary_background_style());
+
+    f.render_widget(auto_refresh_block, git_chunks[3]);
+
+    let auto_refresh_inner = git_chunks[3].inner(Margin {
+        vertical: 1,
+        horizontal: 1,
+    });
+
+    let auto_refresh_text = match state.auto_refresh_minutes {
+        0 => "Off".to_string(),
+        1 => "Every minute".to_string(),
+        minutes => format!("Every {} minutes", minutes),
+    };
+    let auto_refresh_style = if auto_refresh_focused {
+        Style::default()
+            .fg(theme.accent())
+            .add_modifier(Modifier::BOLD)
+    } else {
+        theme.text_style()
+    };
+
+    let auto_refresh_paragraph =
+        Paragraph::new(Span::styled(auto_refresh_text, auto_refresh_style));
+    f.render_widget(auto_refresh_paragraph, auto_refresh_inner);
+
+    // Fork sync push setting
+    let fork_push_focused = is_focused && state.settings_git_focus == GitFocus::ForkPush;
+
+    let fork_push_block = Block::default()
+        .borders(Borders::ALL)
+        .title("Fork Sync")
+        .title_style(if fork_push_focused {
+            theme.accent_style()
+        } else {
+            theme.secondary_text_style()
+        })
+        .border_style(if fork_push_focused {
+            theme.focused_border_style()
+        } else {
+            theme.border_style()
+        })
+        .style(theme.secondary_background_style());
+
+    f.render_widget(fork_push_block, git_chunks[4]);
+
+    let fork_push_inner = git_chunks[4].inner(Margin {
+        vertical: 1,
+        horizontal: 1,
+    });
+
+    let fork_push_text = if state.fork_push_after_sync {
+        "Update local branch and push to origin"
+    } else {
+        "Update local branch only"
+    };
+    let fork_push_style = if fork_push_focused {
+        Style::default()
+            .fg(theme.accent())
+            .add_modifier(Modifier::BOLD)
+    } else {
+        theme.text_style()
+    };
+
+    let fork_push_paragraph = Paragraph::new(Span::styled(fork_push_text, fork_push_style));
+    f.render_widget(fork_push_paragraph, fork_push_inner);
+
+    // Commit body wrap setting
+    let commit_wrap_focused = is_focused && state.settings_git_focus == GitFocus::CommitWrap;
+
+    let commit_wrap_block = Block::default()
+        .borders(Borders::ALL)
+        .title("Commit Body Wrap")
+        .title_style(if commit_wrap_focused {
+            theme.accent_style()
+        } else {
+            theme.secondary_text_style()
+        })
+        .border_style(if commit_wrap_focused {
+            theme.focused_border_style()
+        } else {
+            theme.border_style()
+        })
+        .style(theme.secondary_background_style());
+
+    f.render_widget(commit_wrap_block, git_chunks[5]);
+
+    let commit_wrap_inner = git_chunks[5].inner(Margin {
+        vertical: 1,
+        horizontal: 1,
+    });
+
+    let commit_wrap_text = match state.commit_wrap_column {
+        0 => "Off".to_string(),
+        column => format!("Wrap at column {}", column),
+    };
+    let commit_wrap_style = if commit_wrap_focused {
+        Style::default()
+            .fg(theme.accent())
+            .add_modifier(Modifier::BOLD)
+    } else {
+        theme.text_style()
+    };
+
+    let commit_wrap_paragraph = Paragraph::new(Span::styled(commit_wrap_text, commit_wrap_style));
+    f.render_widget(commit_wrap_paragraph, commit_wrap_inner);
+
+    // New repository template setting
+    let init_template_focused = is_focused && state.settings_git_focus == GitFocus::InitTemplate;
+
+    let init_template_block = Block::default()
+        .borders(Borders::ALL)
+        .title("New Repository Template")
+        .title_style(if init_template_focused {
+            theme.accent_style()
+        } else {
+            theme.secondary_text_style()
+        })
+        .border_style(if init_template_focused {
+            theme.focused_border_style()
+        } else {
+            theme.border_style()
+        })
+        .style(theme.secondary_background_style());
+
+    f.render_widget(init_template_block, git_chunks[6]);
+
+    let init_template_inner = git_chunks[6].inner(Margin {
+        vertical: 1,
+        horizontal: 1,
+    });
+
+    let init_template_text = match &state.init_template {
+        Some(name) => name.clone(),
+        None => "None".to_string(),
+    };
+    let init_template_style = if init_template_focused {
+        Style::default()
+            .fg(theme.accent())
+            .add_modifier(Modifier::BOLD)
+    } else {
+        theme.text_style()
+    };
+
+    let init_template_paragraph =
+        Paragraph::new(Span::styled(init_template_text, init_template_style));
+    f.render_widget(init_template_paragraph, init_template_inner);
+
+    // Delete setting
+    let delete_focused = is_focused && state.settings_git_focus == GitFocus::PermanentDelete;
+
+    let delete_block = Block::default()
+        .borders(Borders::ALL)
+        .title("Deleting Files")
+        .title_style(if delete_focused {
+            theme.accent_style()
+        } else {
+            theme.secondary_text_style()
+        })
+        .border_style(if delete_focused {
+            theme.focused_border_style()
+        } else {
+            theme.border_style()
+        })
+        .style(theme.secondary_background_style());
+
+    f.render_widget(delete_block, git_chunks[7]);
+
+    let delete_inner = git_chunks[7].inner(Margin {
+        vertical: 1,
+        horizontal: 1,
+    });
+
+    let delete_text = if state.permanent_delete && state.profile == Profile::Beginner {
+        "Move to trash (always, in the beginner profile)"
+    } else if state.permanent_delete {
+        "Delete permanently"
+    } else {
+        "Move to trash"
+    };
+    let delete_style = if delete_focused {
+        Style::default()
+            .fg(theme.accent())
+            .add_modifier(Modifier::BOLD)
+    } else {
+        theme.text_style()
+    };
+
+    let delete_paragraph = Paragraph::new(Span::styled(delete_text, delete_style));
+    f.render_widget(delete_paragraph, delete_inner);
+
+    // Update check setting
+    let update_focused = is_focused && state.settings_git_focus == GitFocus::UpdateCheck;
+
+    let update_block = Block::default()
+        .borders(Borders::ALL)
+        .title("Check for Updates")
+        .title_style(if update_focused {
+            theme.accent_style()
+        } else {
+            theme.secondary_text_style()
+        })
+        .border_style(if update_focused {
+            theme.focused_border_style()
+        } else {
+            theme.border_style()
+        })
+        .style(theme.secondary_background_style());
+
+    f.render_widget(update_block, git_chunks[8]);
+
+    let update_inner = git_chunks[8].inner(Margin {
+        vertical: 1,
+        horizontal: 1,
+    });
+
+    let update_style = if update_focused {
+        Style::default()
+            .fg(theme.accent())
+            .add_modifier(Modifier::BOLD)
+    } else {
+        theme.text_style()
+    };
+    let mut update_spans = vec![
+        Span::styled(
+            if state.update_check { "On" } else { "Off" },
+            update_style,
+        ),
+        Span::styled(
+            format!("  (running v{})", crate::release::CURRENT_VERSION),
+            theme.muted_text_style(),
+        ),
+    ];
+    if let Some(release) = &state.update_available {
+        update_spans.push(Span::styled(
+            format!("  v{} available", release.version),
+            theme.accent2_style(),
+        ));
+    }
+    f.render_widget(Paragraph::new(Line::from(update_spans)), update_inner);
+
+    // Forge setting
+    let forge_focused = is_focused && state.settings_git_focus == GitFocus::Forge;
+
+    let forge_block = Block::default()
+        .borders(Borders::ALL)
+        .title("Forge")
+        .title_style(if forge_focused {
+            theme.accent_style()
+        } else {
+            theme.secondary_text_style()
+        })
+        .border_style(if forge_focused {
+            theme.focused_border_style()
+        } else {
+            theme.border_style()
+        })
+        .style(theme.secondary_background_style());
+
+    f.render_widget(forge_block, git_chunks[9]);
+
+    let forge_inner = git_chunks[9].inner(Margin {
+        vertical: 1,
+        horizontal: 1,
+    });
+
+    let forge_style = if forge_focused {
+        Style::default()
+            .fg(theme.accent())
+            .add_modifier(Modifier::BOLD)
+    } else {
+        theme.text_style()
+    };
+    // Auto shows what detection found, so an override is only needed when it found nothing
+    let forge_spans = match (state.forge_override, &state.update_forge) {
+        (Some(kind), _) => vec![Span::styled(kind.title(), forge_style)],
+        (None, Some(forge)) => vec![
+            Span::styled("Auto", forge_style),
+            Span::styled(
+                format!("  (detected {})", forge.kind.title()),
+                theme.muted_text_style(),
+            ),
+        ],
+        (None, None) => vec![Span::styled("Auto", forge_style)],
+    };
+    f.render_widget(Paragraph::new(Line::from(forge_spans)), forge_inner);
+
     // Help text
     let help_lines = vec![
         Line::from(vec![Span::styled(
@@ -618,6 +1115,14 @@ fn render_git_panel(f: &mut Frame, area: Rect, state: &AppState, theme: &Theme)
             theme.secondary_text_style(),
         )]),
         Line::from(""),
+        Line::from(vec![
+            Span::styled("Profile: ", theme.stats_label_style()),
+            Span::styled(
+                "Beginner hides amend, rebase and skipping hooks; Expert asks less",
+                theme.secondary_text_style(),
+            ),
+        ]),
+        Line::from(""),
         Line::from(vec![
             Span::styled("Pull Strategy: ", theme.stats_label_style()),
             Span::styled(
@@ -641,6 +1146,14 @@ fn render_git_panel(f: &mut Frame, area: Rect, state: &AppState, theme: &Theme)
             ),
         ]),
         Line::from(""),
+        Line::from(vec![
+            Span::styled("Notifications: ", theme.stats_label_style()),
+            Span::styled(
+                "Alert when a long pull/push finishes out of view",
+                theme.secondary_text_style(),
+            ),
+        ]),
+        Line::from(""),
         Line::from(vec![Span::styled(
             "Use ←→ to change, Ctrl+S to save",
             theme.muted_text_style(),
@@ -648,5 +1161,323 @@ fn render_git_panel(f: &mut Frame, area: Rect, state: &AppState, theme: &Theme)
     ];
 
     let help_paragraph = Paragraph::new(help_lines).wrap(Wrap { trim: false });
-    f.render_widget(help_paragraph, git_chunks[1]);
+    f.render_widget(help_paragraph, git_chunks[10]);
+}
+
+fn render_hooks_panel(f: &mut Frame, area: Rect, state: &AppState, theme: &Theme) {
+    let is_focused = state.settings_focus == SettingsFocus::Hooks;
+
+    let border_style = if is_focused {
+        theme.focused_border_style()
+    } else {
+        theme.border_style()
+    };
+
+    // Show where hooks live, calling out a core.hooksPath override
+    let title = match &state.settings_hooks_dir {
+        Some(dir) if crate::hooks::has_custom_hooks_path() => {
+            format!("Hooks - {} (core.hooksPath)", dir.display())
+        }
+        Some(dir) => format!("Hooks - {}", dir.display()),
+        None => "Hooks".to_string(),
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .title_style(theme.title_style())
+        .border_style(border_style)
+        .style(theme.secondary_background_style());
+
+    if state.settings_hooks.is_empty() {
+        let empty = Paragraph::new(vec![
+            Line::from(Span::styled(
+                "No hooks found in the hooks directory",
+                theme.muted_text_style(),
+            )),
+            Line::from(""),
+            Line::from(Span::styled(
+                "Press T to install a hook from a bundled template",
+                theme.secondary_text_style(),
+            )),
+        ])
+        .alignment(Alignment::Center)
+        .block(block);
+        f.render_widget(empty, area);
+        return;
+    }
+
+    let header = Row::new(vec![
+        Cell::from("State").style(theme.accent2_style()),
+        Cell::from("Hook").style(theme.accent2_style()),
+        Cell::from("Executable").style(theme.accent2_style()),
+        Cell::from("File").style(theme.accent2_style()),
+    ]);
+
+    let rows: Vec<Row> = state
+        .settings_hooks
+        .iter()
+        .map(|hook| {
+            let (state_text, state_style) = if hook.enabled {
+                ("● enabled", theme.success_style())
+            } else {
+                ("○ disabled", theme.muted_text_style())
+            };
+
+            // An enabled hook without the executable bit is silently skipped by git
+            let (exec_text, exec_style) = match (hook.enabled, hook.executable) {
+                (_, true) => ("✓", theme.accent3_style()),
+                (true, false) => ("✗ not executable", theme.warning_style()),
+                (false, false) => ("-", theme.muted_text_style()),
+            };
+
+            let file_name = hook
+                .path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+
+            Row::new(vec![
+                Cell::from(state_text).style(state_style),
+                Cell::from(hook.name.clone()).style(theme.text_style()),
+                Cell::from(exec_text).style(exec_style),
+                Cell::from(file_name).style(theme.secondary_text_style()),
+            ])
+        })
+        .collect();
+
+    let mut table_state = TableState::default();
+    if is_focused {
+        table_state.select(Some(state.settings_hook_selected));
+    }
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(12), // State
+            Constraint::Length(20), // Hook
+            Constraint::Length(18), // Executable
+            Constraint::Min(10),    // File
+        ],
+    )
+    .header(header)
+    .block(block)
+    .row_highlight_style(theme.highlight_style())
+    .highlight_symbol("► ");
+
+    f.render_stateful_widget(table, area, &mut table_state);
+}
+
+fn render_hook_template_popup(f: &mut Frame, area: Rect, state: &AppState, theme: &Theme) {
+    let popup_area = super::centered_rect(60, 12, area);
+
+    // Clear the background
+    f.render_widget(Clear, popup_area);
+
+    let items: Vec<ListItem> = crate::hooks::HOOK_TEMPLATES
+        .iter()
+        .enumerate()
+        .map(|(i, template)| {
+            let selected = i == state.hook_template_selected;
+            let marker = if selected { "► " } else { "  " };
+            let name_style = if selected {
+                theme.accent_style().add_modifier(Modifier::BOLD)
+            } else {
+                theme.text_style()
+            };
+
+            ListItem::new(vec![
+                Line::from(vec![
+                    Span::styled(marker, theme.accent_style()),
+                    Span::styled(template.name, name_style),
+                    Span::styled(format!(" ({})", template.hook), theme.accent3_style()),
+                ]),
+                Line::from(Span::styled(
+                    format!("    {}", template.description),
+                    theme.secondary_text_style(),
+                )),
+            ])
+        })
+        .collect();
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Install Hook Template - [Enter] Install, [Esc] Cancel")
+            .title_style(theme.popup_title_style())
+            .border_style(theme.popup_border_style())
+            .style(theme.popup_background_style()),
+    );
+
+    f.render_widget(list, popup_area);
+}
+
+fn render_remotes_panel(f: &mut Frame, area: Rect, state: &AppState, theme: &Theme) {
+    let is_focused = state.settings_focus == SettingsFocus::Remotes;
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Remotes")
+        .title_style(theme.title_style())
+        .border_style(if is_focused {
+            theme.focused_border_style()
+        } else {
+            theme.border_style()
+        })
+        .style(theme.secondary_background_style());
+
+    if state.settings_remotes.is_empty() {
+        let empty = Paragraph::new(vec![
+            Line::from(Span::styled(
+                "No remotes configured",
+                theme.muted_text_style(),
+            )),
+            Line::from(""),
+            Line::from(Span::styled(
+                "Press A to add origin and start syncing from the Update tab",
+                theme.secondary_text_style(),
+            )),
+        ])
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .block(block);
+        f.render_widget(empty, area);
+        return;
+    }
+
+    let header = Row::new(vec![
+        Cell::from("Name").style(theme.accent2_style()),
+        Cell::from("URL").style(theme.accent2_style()),
+    ]);
+    let widths = [
+        Constraint::Length(14), // Name
+        Constraint::Min(10),    // URL
+    ];
+    let columns = text::column_widths(area, &widths, "► ");
+
+    let rows: Vec<Row> = state
+        .settings_remotes
+        .iter()
+        .map(|remote| {
+            // A separate push URL is shown after the fetch URL
+            let url = match &remote.push_url {
+                Some(push_url) => format!("{} (push: {})", remote.url, push_url),
+                None => remote.url.clone(),
+            };
+            Row::new(vec![
+                Cell::from(text::truncate(&remote.name, columns[0])).style(theme.accent_style()),
+                Cell::from(text::truncate(&url, columns[1])).style(theme.text_style()),
+            ])
+        })
+        .collect();
+
+    let mut table_state = TableState::default();
+    if is_focused {
+        table_state.select(Some(state.settings_remote_selected));
+    }
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(block)
+        .row_highlight_style(theme.highlight_style())
+        .highlight_symbol("► ");
+
+    f.render_stateful_widget(table, area, &mut table_state);
+}
+
+/// Render the add or edit remote form: the remote's name, then its URL
+fn render_remote_edit_popup(f: &mut Frame, area: Rect, edit: &RemoteEdit, theme: &Theme) {
+    let popup_area = super::centered_rect(70, 10, area);
+    f.render_widget(Clear, popup_area);
+
+    let title = match &edit.original {
+        Some(name) => format!("Edit Remote {}", name),
+        None => "Add Remote".to_string(),
+    };
+    let block = Block::default()
+        .title(title)
+        .title_style(theme.popup_title_style())
+        .borders(Borders::ALL)
+        .border_style(theme.popup_border_style())
+        .style(theme.popup_background_style());
+    let inner = block.inner(popup_area).inner(Margin {
+        vertical: 0,
+        horizontal: 1,
+    });
+    f.render_widget(block, popup_area);
+
+    let chunks = Layout::vertical([
+        Constraint::Length(3), // Name
+        Constraint::Length(3), // URL
+        Constraint::Length(1), // Error
+        Constraint::Length(1), // Help
+    ])
+    .split(inner);
+
+    let fields = [
+        ("Name", &edit.name, !edit.editing_url),
+        (
+            "URL (https://…, git@host:path or a local path)",
+            &edit.url,
+            edit.editing_url,
+        ),
+    ];
+    for (chunk, (title, input, focused)) in chunks.iter().zip(fields) {
+        let field_block = Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(if focused {
+                theme.focused_border_style()
+            } else {
+                theme.border_style()
+            });
+        let field_inner = field_block.inner(*chunk);
+        f.render_widget(field_block, *chunk);
+        if focused {
+            f.render_widget(input.widget(), field_inner);
+        } else {
+            f.render_widget(
+                Paragraph::new(input.lines()[0].as_str()).style(theme.text_style()),
+                field_inner,
+            );
+        }
+    }
+
+    if let Some(error) = &edit.error {
+        f.render_widget(
+            Paragraph::new(text::truncate(error, chunks[2].width as usize))
+                .style(theme.error_style()),
+            chunks[2],
+        );
+    }
+    f.render_widget(
+        Paragraph::new("[Tab] Field  [Enter] Save  [Esc] Cancel")
+            .style(theme.secondary_text_style()),
+        chunks[3],
+    );
+}
+
+fn render_remote_remove_popup(f: &mut Frame, area: Rect, name: &str, theme: &Theme) {
+    let popup_area = super::centered_rect(60, 8, area);
+    f.render_widget(Clear, popup_area);
+
+    let lines = vec![
+        Line::styled(format!("Remove remote '{}'?", name), theme.text_style()),
+        Line::from(""),
+        Line::styled(
+            "Its remote-tracking branches go with it; nothing on the server changes.",
+            theme.secondary_text_style(),
+        ),
+        Line::from(""),
+        Line::styled("[Y] Remove  [N] Cancel", theme.secondary_text_style()),
+    ];
+    let popup = Paragraph::new(lines).wrap(Wrap { trim: true }).block(
+        Block::default()
+            .title("Remove Remote")
+            .title_style(theme.popup_title_style())
+            .borders(Borders::ALL)
+            .border_style(theme.popup_border_style())
+            .style(theme.popup_background_style()),
+    );
+    f.render_widget(popup, popup_area);
 }
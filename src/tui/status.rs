@@ -0,0 +1,296 @@
+use crate::app::AppState;
+use crate::git::{StatusEntry, StatusSummary};
+use crate::tui::text;
+use crate::tui::theme::Theme;
+use ratatui::layout::{Alignment, Constraint, Direction, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph};
+use ratatui::{layout::Rect, Frame};
+
+pub fn render_status_tab(f: &mut Frame, area: Rect, state: &mut AppState) {
+    // Use configured theme from app state
+    let theme = Theme::with_accents_and_title(
+        state.current_theme_accent,
+        state.current_theme_accent2,
+        state.current_theme_accent3,
+        state.current_theme_title,
+    );
+
+    // Set panel background
+    f.render_widget(
+        Block::default().style(theme.secondary_background_style()),
+        area,
+    );
+
+    if !state.git_enabled {
+        render_no_git_message(f, area, &theme);
+        return;
+    }
+
+    state.load_status_git_status();
+
+    let summary = match &state.status_summary {
+        Some(summary) => summary,
+        None => {
+            let message = Paragraph::new("Unable to read repository status. Press [R] to retry.")
+                .alignment(Alignment::Center)
+                .style(theme.error_style())
+                .block(panel_block("Status", &theme));
+            f.render_widget(message, area);
+            return;
+        }
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(6), // Branch state
+            Constraint::Min(5),    // File sections
+        ])
+        .split(area);
+
+    render_branch_state(f, chunks[0], summary, &theme);
+    render_sections(f, chunks[1], summary, &theme);
+}
+
+fn render_branch_state(f: &mut Frame, area: Rect, summary: &StatusSummary, theme: &Theme) {
+    let branch = match &summary.branch {
+        Some(branch) => Span::styled(branch.clone(), theme.accent_style()),
+        None => Span::styled("(detached HEAD)", theme.warning_style()),
+    };
+
+    let tracking = match &summary.upstream {
+        Some(upstream) => {
+            let (text, style) = match (summary.ahead, summary.behind) {
+                (0, 0) => ("up to date".to_string(), theme.success_style()),
+                (ahead, 0) => (format!("ahead {}", ahead), theme.info_style()),
+                (0, behind) => (format!("behind {}", behind), theme.warning_style()),
+                (ahead, behind) => (
+                    format!("ahead {}, behind {}", ahead, behind),
+                    theme.warning_style(),
+                ),
+            };
+            vec![
+                Span::styled(format!(" → {} ", upstream), theme.secondary_text_style()),
+                Span::styled(format!("[{}]", text), style),
+            ]
+        }
+        None => vec![Span::styled(" (no upstream)", theme.muted_text_style())],
+    };
+
+    let mut branch_line = vec![Span::styled("Branch: ", theme.accent2_style()), branch];
+    branch_line.extend(tracking);
+
+    let state_line = match summary.operation {
+        Some(operation) => {
+            let mut spans = vec![
+                Span::styled("State: ", theme.accent2_style()),
+                Span::styled(operation, theme.error_style().add_modifier(Modifier::BOLD)),
+            ];
+            if matches!(operation, "Merge in progress" | "Rebase in progress") {
+                spans.push(Span::styled("  [A] Abort", theme.muted_text_style()));
+            }
+            Line::from(spans)
+        }
+        None => Line::from(vec![
+            Span::styled("State: ", theme.accent2_style()),
+            Span::styled("No operation in progress", theme.text_style()),
+        ]),
+    };
+
+    let clean = summary.staged.is_empty()
+        && summary.unstaged.is_empty()
+        && summary.untracked.is_empty()
+        && summary.conflicted.is_empty();
+    let counts = if clean {
+        Span::styled("Working tree clean", theme.success_style())
+    } else {
+        Span::styled(
+            format!(
+                "{} staged, {} unstaged, {} untracked{}",
+                summary.staged.len(),
+                summary.unstaged.len(),
+                summary.untracked.len(),
+                if summary.conflicted.is_empty() {
+                    String::new()
+                } else {
+                    format!(", {} conflicted", summary.conflicted.len())
+                }
+            ),
+            theme.text_style(),
+        )
+    };
+
+    let text = vec![
+        Line::from(branch_line),
+        Line::from(vec![
+            Span::styled("HEAD: ", theme.accent2_style()),
+            Span::styled(
+                summary.head.as_deref().unwrap_or("No commits yet"),
+                theme.commit_message_style(),
+            ),
+        ]),
+        state_line,
+        Line::from(vec![Span::styled("Changes: ", theme.accent2_style()), counts]),
+    ];
+
+    let paragraph = Paragraph::new(text)
+        .style(theme.text_style())
+        .block(panel_block("Repository Status - Read Only - [R] Refresh", theme));
+    f.render_widget(paragraph, area);
+}
+
+fn render_sections(f: &mut Frame, area: Rect, summary: &StatusSummary, theme: &Theme) {
+    let mut constraints = vec![
+        Constraint::Ratio(1, 3),
+        Constraint::Ratio(1, 3),
+        Constraint::Ratio(1, 3),
+    ];
+    if !summary.conflicted.is_empty() {
+        constraints = vec![Constraint::Ratio(1, 4); 4];
+    }
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(constraints)
+        .split(area);
+
+    render_entry_list(
+        f,
+        columns[0],
+        "Staged",
+        &summary.staged,
+        theme.success_style(),
+        theme,
+    );
+    render_entry_list(
+        f,
+        columns[1],
+        "Unstaged",
+        &summary.unstaged,
+        theme.warning_style(),
+        theme,
+    );
+    render_path_list(
+        f,
+        columns[2],
+        "Untracked",
+        "??",
+        &summary.untracked,
+        theme.info_style(),
+        theme,
+    );
+    if !summary.conflicted.is_empty() {
+        render_path_list(
+            f,
+            columns[3],
+            "Conflicts",
+            "UU",
+            &summary.conflicted,
+            theme.error_style(),
+            theme,
+        );
+    }
+}
+
+fn render_entry_list(
+    f: &mut Frame,
+    area: Rect,
+    title: &str,
+    entries: &[StatusEntry],
+    symbol_style: Style,
+    theme: &Theme,
+) {
+    let items: Vec<ListItem> = entries
+        .iter()
+        .map(|entry| {
+            let symbol = format!("{} ", entry.status.as_symbol());
+            let max = path_width(area, &symbol);
+            let path = match &entry.status {
+                crate::git::FileStatusType::Renamed { from } if !from.is_empty() => {
+                    text::truncate_middle(&format!("{} -> {}", from, entry.path), max)
+                }
+                _ => text::truncate_path(&entry.path, max),
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(symbol, symbol_style),
+                Span::styled(path, theme.text_style()),
+            ]))
+        })
+        .collect();
+
+    render_list(f, area, title, items, theme);
+}
+
+fn render_path_list(
+    f: &mut Frame,
+    area: Rect,
+    title: &str,
+    symbol: &str,
+    paths: &[String],
+    symbol_style: Style,
+    theme: &Theme,
+) {
+    let symbol = format!("{} ", symbol);
+    let max = path_width(area, &symbol);
+    let items: Vec<ListItem> = paths
+        .iter()
+        .map(|path| {
+            ListItem::new(Line::from(vec![
+                Span::styled(symbol.clone(), symbol_style),
+                Span::styled(text::truncate_path(path, max), theme.text_style()),
+            ]))
+        })
+        .collect();
+
+    render_list(f, area, title, items, theme);
+}
+
+/// Columns left for a path after the borders and the status symbol
+fn path_width(area: Rect, symbol: &str) -> usize {
+    (area.width as usize).saturating_sub(2 + text::width(symbol))
+}
+
+fn render_list(f: &mut Frame, area: Rect, title: &str, items: Vec<ListItem>, theme: &Theme) {
+    let count = items.len();
+    let items = if items.is_empty() {
+        vec![ListItem::new(Span::styled("Nothing here", theme.muted_text_style()))]
+    } else {
+        items
+    };
+
+    let title = format!("{} ({})", title, count);
+    let list = List::new(items)
+        .style(theme.text_style())
+        .block(panel_block(&title, theme));
+    f.render_widget(list, area);
+}
+
+fn panel_block<'a>(title: &'a str, theme: &Theme) -> Block<'a> {
+    Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .title_style(theme.title_style())
+        .border_style(theme.border_style())
+        .style(theme.secondary_background_style())
+}
+
+fn render_no_git_message(f: &mut Frame, area: Rect, theme: &Theme) {
+    let message = Paragraph::new(vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            "⚠ Not a Git Repository",
+            Style::default()
+                .fg(theme.yellow)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from("This directory is not a Git repository."),
+        Line::from("Use the Overview tab to initialize a new repository."),
+    ])
+    .alignment(Alignment::Center)
+    .style(theme.text_style())
+    .block(panel_block("Status", theme));
+    f.render_widget(message, area);
+}
@@ -0,0 +1,162 @@
+/// A tab that can appear in the tab bar
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Tab {
+    Overview,
+    Files,
+    Status,
+    SaveChanges,
+    History,
+    Branches,
+    Tags,
+    Update,
+    Activity,
+    Settings,
+}
+
+impl Tab {
+    /// Every tab, in the default display order
+    pub const ALL: [Tab; 10] = [
+        Tab::Overview,
+        Tab::Files,
+        Tab::Status,
+        Tab::SaveChanges,
+        Tab::History,
+        Tab::Branches,
+        Tab::Tags,
+        Tab::Update,
+        Tab::Activity,
+        Tab::Settings,
+    ];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Tab::Overview => "Overview",
+            Tab::Files => "Files",
+            Tab::Status => "Status",
+            Tab::SaveChanges => "Save Changes",
+            Tab::History => "History",
+            Tab::Branches => "Branches",
+            Tab::Tags => "Tags",
+            Tab::Update => "Update",
+            Tab::Activity => "Activity",
+            Tab::Settings => "Settings",
+        }
+    }
+
+    /// Name used for the tab in gitix.ui.tabs
+    pub fn key(self) -> &'static str {
+        match self {
+            Tab::Overview => "overview",
+            Tab::Files => "files",
+            Tab::Status => "status",
+            Tab::SaveChanges => "save-changes",
+            Tab::History => "history",
+            Tab::Branches => "branches",
+            Tab::Tags => "tags",
+            Tab::Update => "update",
+            Tab::Activity => "activity",
+            Tab::Settings => "settings",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Tab> {
+        let key = key.trim().to_lowercase().replace(['_', ' '], "-");
+        Tab::ALL.iter().copied().find(|tab| tab.key() == key)
+    }
+
+    /// Whether the tab only works inside a git repository
+    pub fn requires_git(self) -> bool {
+        !matches!(self, Tab::Overview | Tab::Files)
+    }
+}
+
+/// The tabs shown in the tab bar, in display order
+#[derive(Clone, Debug)]
+pub struct TabRegistry {
+    tabs: Vec<Tab>,
+}
+
+impl Default for TabRegistry {
+    fn default() -> Self {
+        Self {
+            tabs: Tab::ALL.to_vec(),
+        }
+    }
+}
+
+impl TabRegistry {
+    /// Build the tab bar from gitix.ui.tabs, falling back to every tab
+    pub fn load() -> Self {
+        match crate::config::get_ui_tabs() {
+            Ok(Some(spec)) => Self::from_spec(&spec),
+            _ => Self::default(),
+        }
+    }
+
+    /// Parse a comma-separated list of tab names, e.g. "overview,save-changes,update,settings"
+    ///
+    /// Unknown names and duplicates are ignored. Settings is always kept so the
+    /// configuration stays reachable, and an empty list shows every tab.
+    pub fn from_spec(spec: &str) -> Self {
+        let mut tabs: Vec<Tab> = Vec::new();
+        for tab in spec.split(',').filter_map(Tab::from_key) {
+            if !tabs.contains(&tab) {
+                tabs.push(tab);
+            }
+        }
+
+        if tabs.is_empty() {
+            return Self::default();
+        }
+        if !tabs.contains(&Tab::Settings) {
+            tabs.push(Tab::Settings);
+        }
+
+        Self { tabs }
+    }
+
+    pub fn tabs(&self) -> &[Tab] {
+        &self.tabs
+    }
+
+    /// Position of a tab in the bar, if it is shown
+    pub fn position(&self, tab: Tab) -> Option<usize> {
+        self.tabs.iter().position(|t| *t == tab)
+    }
+
+    pub fn contains(&self, tab: Tab) -> bool {
+        self.tabs.contains(&tab)
+    }
+
+    /// The first tab that can be used, given whether we're in a git repository
+    pub fn first(&self, git_enabled: bool) -> Tab {
+        self.tabs
+            .iter()
+            .copied()
+            .find(|tab| git_enabled || !tab.requires_git())
+            .unwrap_or(Tab::Settings)
+    }
+
+    /// The next usable tab after `current`, wrapping around
+    pub fn next(&self, current: Tab, git_enabled: bool) -> Tab {
+        self.step(current, git_enabled, 1)
+    }
+
+    /// The previous usable tab before `current`, wrapping around
+    pub fn prev(&self, current: Tab, git_enabled: bool) -> Tab {
+        self.step(current, git_enabled, self.tabs.len().saturating_sub(1))
+    }
+
+    fn step(&self, current: Tab, git_enabled: bool, offset: usize) -> Tab {
+        let count = self.tabs.len();
+        let start = match self.position(current) {
+            Some(idx) => idx,
+            None => return self.first(git_enabled),
+        };
+
+        (1..=count)
+            .map(|i| self.tabs[(start + i * offset) % count])
+            .find(|tab| git_enabled || !tab.requires_git())
+            .unwrap_or(current)
+    }
+}
@@ -0,0 +1,427 @@
+use crate::activity::ActivityKind;
+use crate::app::{AppState, MouseList, TagCreate, TagsView};
+use crate::tui::text;
+use crate::tui::theme::Theme;
+use ratatui::layout::{Alignment, Constraint, Layout, Margin};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table};
+use ratatui::{layout::Rect, Frame};
+use std::sync::mpsc::TryRecvError;
+use tui_textarea::TextArea;
+
+pub fn render_tags_tab(f: &mut Frame, area: Rect, state: &mut AppState) {
+    // Use configured theme from app state
+    let theme = Theme::with_accents_and_title(
+        state.current_theme_accent,
+        state.current_theme_accent2,
+        state.current_theme_accent3,
+        state.current_theme_title,
+    );
+
+    state.load_tags();
+
+    // Set panel background
+    f.render_widget(
+        Block::default().style(theme.secondary_background_style()),
+        area,
+    );
+
+    render_tag_table(f, area, &mut state.tags, &theme);
+    state.record_mouse_target(
+        MouseList::Tags,
+        area,
+        state.tags.table_state.offset(),
+        state.tags.tags.len(),
+    );
+
+    if let Some(create) = state.tag_create.as_mut() {
+        render_create_popup(f, area, create, &theme);
+    }
+    if let Some(name) = &state.tag_delete {
+        render_delete_popup(f, area, name, &theme);
+    }
+}
+
+fn render_tag_table(f: &mut Frame, area: Rect, view: &mut TagsView, theme: &Theme) {
+    let status = if view.push_rx.is_some() {
+        Line::styled(" Pushing to origin... ", theme.info_style())
+    } else {
+        match &view.message {
+            Some(Ok(message)) => Line::styled(format!(" ✓ {} ", message), theme.success_style()),
+            Some(Err(message)) => Line::styled(format!(" ✗ {} ", message), theme.error_style()),
+            None => Line::from(""),
+        }
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(
+            "Tags ({}) - [N] Tag HEAD, [D] Delete, [P] Push, [Shift+P] Push All",
+            view.tags.len()
+        ))
+        .title_bottom(status)
+        .title_style(theme.title_style())
+        .border_style(theme.border_style())
+        .style(theme.secondary_background_style());
+
+    if view.tags.is_empty() {
+        let message = match &view.error {
+            Some(error) => Line::styled(
+                format!("Failed to list tags: {}", error),
+                theme.error_style(),
+            ),
+            None => Line::styled(
+                "No tags yet: press N to tag the current commit",
+                theme.muted_text_style(),
+            ),
+        };
+        let empty = Paragraph::new(vec![Line::from(""), message])
+            .alignment(Alignment::Center)
+            .block(block);
+        f.render_widget(empty, area);
+        return;
+    }
+
+    match view.table_state.selected() {
+        Some(idx) if idx < view.tags.len() => {}
+        Some(_) => view.table_state.select(Some(view.tags.len() - 1)),
+        None => view.table_state.select(Some(0)),
+    }
+
+    let header = Row::new(vec![
+        Cell::from("Tag").style(theme.accent2_style()),
+        Cell::from("Commit").style(theme.accent2_style()),
+        Cell::from("Kind").style(theme.accent2_style()),
+        Cell::from("Message").style(theme.accent2_style()),
+    ]);
+    let widths = [
+        Constraint::Percentage(25), // Tag
+        Constraint::Length(8),      // Commit
+        Constraint::Length(11),     // Annotated or lightweight
+        Constraint::Min(20),        // When, and the message
+    ];
+    let column_widths = text::column_widths(area, &widths, "► ");
+
+    let rows: Vec<Row> = view
+        .tags
+        .iter()
+        .map(|tag| {
+            let name = Cell::from(text::truncate(&tag.name, column_widths[0]))
+                .style(theme.accent3_bold_style());
+            let commit = Cell::from(tag.commit.clone()).style(theme.accent_style());
+            let kind = if tag.annotated {
+                Cell::from("annotated").style(theme.text_style())
+            } else {
+                Cell::from("lightweight").style(theme.muted_text_style())
+            };
+            let message = Cell::from(Line::from(vec![
+                Span::styled(
+                    format!("{}  ", crate::git::format_relative_time(tag.time)),
+                    theme.timestamp_style(),
+                ),
+                Span::styled(tag.message.clone(), theme.commit_message_style()),
+            ]));
+            Row::new(vec![name, commit, kind, message])
+        })
+        .collect();
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(block)
+        .row_highlight_style(theme.highlight_style())
+        .highlight_symbol("► ");
+    f.render_stateful_widget(table, area, &mut view.table_state);
+}
+
+fn render_create_popup(f: &mut Frame, area: Rect, create: &mut TagCreate, theme: &Theme) {
+    let popup_area = super::centered_rect(60, 12, area);
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title("Tag HEAD")
+        .title_style(theme.popup_title_style())
+        .borders(Borders::ALL)
+        .border_style(theme.popup_border_style())
+        .style(theme.popup_background_style());
+    let inner = block.inner(popup_area).inner(Margin {
+        vertical: 0,
+        horizontal: 1,
+    });
+    f.render_widget(block, popup_area);
+
+    let chunks = Layout::vertical([
+        Constraint::Length(3), // Name
+        Constraint::Min(3),    // Message
+        Constraint::Length(1), // Help
+    ])
+    .split(inner);
+
+    let focused = |active: bool| {
+        if active {
+            theme.focused_border_style()
+        } else {
+            theme.border_style()
+        }
+    };
+    let cursor = |active: bool| {
+        if active {
+            theme
+                .text_style()
+                .add_modifier(ratatui::style::Modifier::REVERSED)
+        } else {
+            theme.text_style()
+        }
+    };
+
+    create.name.set_block(
+        Block::default()
+            .title("Name")
+            .borders(Borders::ALL)
+            .border_style(focused(!create.editing_message)),
+    );
+    create.name.set_style(theme.text_style());
+    create
+        .name
+        .set_cursor_style(cursor(!create.editing_message));
+    f.render_widget(&create.name, chunks[0]);
+
+    create.message.set_block(
+        Block::default()
+            .title("Message (leave empty for a lightweight tag)")
+            .borders(Borders::ALL)
+            .border_style(focused(create.editing_message)),
+    );
+    create.message.set_style(theme.text_style());
+    create
+        .message
+        .set_cursor_style(cursor(create.editing_message));
+    f.render_widget(&create.message, chunks[1]);
+
+    f.render_widget(
+        Paragraph::new("[Tab] Field  [Ctrl+S] Create  [Esc] Cancel")
+            .style(theme.secondary_text_style()),
+        chunks[2],
+    );
+}
+
+fn render_delete_popup(f: &mut Frame, area: Rect, name: &str, theme: &Theme) {
+    let popup_area = super::centered_rect(60, 8, area);
+    f.render_widget(Clear, popup_area);
+
+    let lines = vec![
+        Line::styled(format!("Delete tag '{}'?", name), theme.text_style()),
+        Line::from(""),
+        Line::styled(
+            "Only the local tag is deleted; a copy already pushed stays on origin.",
+            theme.secondary_text_style(),
+        ),
+        Line::from(""),
+        Line::styled("[Y] Delete  [N] Cancel", theme.secondary_text_style()),
+    ];
+    let popup = Paragraph::new(lines)
+        .wrap(ratatui::widgets::Wrap { trim: true })
+        .block(
+            Block::default()
+                .title("Delete Tag")
+                .title_style(theme.popup_title_style())
+                .borders(Borders::ALL)
+                .border_style(theme.popup_border_style())
+                .style(theme.popup_background_style()),
+        );
+    f.render_widget(popup, popup_area);
+}
+
+impl AppState {
+    /// Read the tag list unless it is already current
+    pub fn load_tags(&mut self) {
+        if self.tags.loaded {
+            return;
+        }
+        self.tags.loaded = true;
+        match crate::git::list_tags() {
+            Ok(tags) => {
+                self.tags.tags = tags;
+                self.tags.error = None;
+            }
+            Err(e) => {
+                self.tags.tags.clear();
+                self.tags.error = Some(e.to_string());
+            }
+        }
+    }
+
+    /// Re-read the tag list, selecting `select` if it is given
+    fn reload_tags(&mut self, select: Option<&str>) {
+        self.tags.loaded = false;
+        self.load_tags();
+        let idx = select.and_then(|name| self.tags.tags.iter().position(|tag| tag.name == name));
+        if idx.is_some() {
+            self.tags.table_state.select(idx);
+        }
+    }
+
+    pub fn tags_navigate(&mut self, delta: isize) {
+        if self.tags.tags.is_empty() {
+            return;
+        }
+        let current = self.tags.table_state.selected().unwrap_or(0);
+        let next = current
+            .saturating_add_signed(delta)
+            .min(self.tags.tags.len() - 1);
+        self.tags.table_state.select(Some(next));
+    }
+
+    pub fn selected_tag(&self) -> Option<&crate::git::TagInfo> {
+        self.tags
+            .table_state
+            .selected()
+            .and_then(|idx| self.tags.tags.get(idx))
+    }
+
+    /// Ask for the name and message of a tag on HEAD
+    pub fn open_tag_create(&mut self) {
+        if self.refuse_read_only("Creating tags") {
+            return;
+        }
+        self.tag_create = Some(TagCreate {
+            name: TextArea::default(),
+            message: TextArea::default(),
+            editing_message: false,
+        });
+    }
+
+    /// Create the tag described in the form
+    pub fn create_tag_from_input(&mut self) {
+        let (name, message) = match &self.tag_create {
+            Some(create) => (
+                create.name.lines().join("").trim().to_string(),
+                create.message.lines().join("\n").trim().to_string(),
+            ),
+            None => return,
+        };
+        if name.is_empty() {
+            return;
+        }
+        self.tag_create = None;
+
+        let message = (!message.is_empty()).then_some(message);
+        let result = crate::git::create_tag(&name, message.as_deref());
+        self.record_activity(
+            ActivityKind::Tag,
+            &name,
+            &format!(
+                "libgit2: create {} refs/tags/{} at HEAD",
+                if message.is_some() {
+                    "an annotated tag object and"
+                } else {
+                    "the lightweight tag"
+                },
+                name
+            ),
+            match &result {
+                Ok(()) => Ok(format!("Tagged HEAD as {}", name)),
+                Err(e) => Err(e.to_string()),
+            },
+        );
+        if let Err(e) = result {
+            self.show_error(
+                "Tag Failed",
+                &format!("Failed to create {}:\n\n{}", name, e),
+            );
+        }
+        self.tags.message = None;
+        self.reload_tags(Some(&name));
+    }
+
+    /// Ask before deleting the selected tag
+    pub fn request_tag_delete(&mut self) {
+        if let Some(tag) = self.selected_tag() {
+            self.tag_delete = Some(tag.name.clone());
+        }
+    }
+
+    /// Delete the tag waiting for confirmation
+    pub fn confirm_tag_delete(&mut self) {
+        if self.refuse_read_only("Deleting tags") {
+            return;
+        }
+        let name = match self.tag_delete.take() {
+            Some(name) => name,
+            None => return,
+        };
+        let result = crate::git::delete_tag(&name);
+        self.record_activity(
+            ActivityKind::Tag,
+            &name,
+            &format!("libgit2: delete refs/tags/{}", name),
+            match &result {
+                Ok(()) => Ok(format!("Deleted {}", name)),
+                Err(e) => Err(e.to_string()),
+            },
+        );
+        if let Err(e) = result {
+            self.show_error(
+                "Delete Failed",
+                &format!("Failed to delete {}:\n\n{}", name, e),
+            );
+        }
+        self.reload_tags(None);
+    }
+
+    /// Push the selected tag, or every tag, to origin in the background
+    pub fn push_tags(&mut self, all: bool) {
+        if self.refuse_read_only("Pushing tags") || self.tags.push_rx.is_some() {
+            return;
+        }
+        let names: Vec<String> = if all {
+            self.tags.tags.iter().map(|tag| tag.name.clone()).collect()
+        } else {
+            self.selected_tag()
+                .map(|tag| tag.name.clone())
+                .into_iter()
+                .collect()
+        };
+        if names.is_empty() {
+            return;
+        }
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let target = match names.as_slice() {
+                [name] => name.clone(),
+                _ => format!("{} tags", names.len()),
+            };
+            let result = crate::git::push_tags(&names).map_err(|e| e.to_string());
+            let _ = tx.send((target, result));
+        });
+        self.tags.push_rx = Some(rx);
+        self.tags.message = None;
+    }
+
+    /// Pick up a finished tag push
+    pub fn poll_tag_push(&mut self) {
+        let (target, result) = match &self.tags.push_rx {
+            Some(rx) => match rx.try_recv() {
+                Ok(finished) => finished,
+                Err(TryRecvError::Disconnected) => {
+                    self.tags.push_rx = None;
+                    return;
+                }
+                Err(TryRecvError::Empty) => return,
+            },
+            None => return,
+        };
+        self.tags.push_rx = None;
+        self.record_activity(
+            ActivityKind::Tag,
+            &target,
+            "libgit2: push refs/tags/<name> to the same name on origin",
+            match &result {
+                Ok(()) => Ok(format!("Pushed {} to origin", target)),
+                Err(e) => Err(e.clone()),
+            },
+        );
+        self.tags.message = Some(match result {
+            Ok(()) => Ok(format!("Pushed {} to origin", target)),
+            Err(e) => Err(format!("Pushing {} failed: {}", target, e)),
+        });
+    }
+}
@@ -0,0 +1,172 @@
+use ratatui::layout::{Constraint, Layout, Rect};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+const ELLIPSIS: char = '…';
+
+/// Number of terminal columns a string occupies (CJK and emoji count as two)
+///
+/// Measured per grapheme cluster, the way ratatui lays out text, so a ZWJ emoji sequence or a letter
+/// with combining marks is one cell group that is never split.
+pub fn width(text: &str) -> usize {
+    text.graphemes(true).map(UnicodeWidthStr::width).sum()
+}
+
+/// Cut `text` to at most `max` columns, ending with an ellipsis when shortened
+pub fn truncate(text: &str, max: usize) -> String {
+    if width(text) <= max {
+        return text.to_string();
+    }
+    if max == 0 {
+        return String::new();
+    }
+
+    let mut out = take_width(text, max - 1);
+    out.push(ELLIPSIS);
+    out
+}
+
+/// Cut the middle out of `text`, keeping its start and end
+pub fn truncate_middle(text: &str, max: usize) -> String {
+    if width(text) <= max {
+        return text.to_string();
+    }
+    if max < 3 {
+        return truncate(text, max);
+    }
+
+    let budget = max - 1;
+    let tail = take_width_from_end(text, budget / 2);
+    let mut out = take_width(text, budget - width(&tail));
+    out.push(ELLIPSIS);
+    out.push_str(&tail);
+    out
+}
+
+/// Shorten a path to `max` columns, keeping the file name whenever it fits
+///
+/// `src/tui/components/commit_message.rs` becomes `src/tui/c…/commit_message.rs`.
+pub fn truncate_path(path: &str, max: usize) -> String {
+    if width(path) <= max {
+        return path.to_string();
+    }
+
+    match path.rsplit_once('/') {
+        // Leave room for at least one character of the directory, the ellipsis and the slash
+        Some((dir, name)) if width(name) + 3 <= max => {
+            let mut out = take_width(dir, max - width(name) - 2);
+            out.push(ELLIPSIS);
+            out.push('/');
+            out.push_str(name);
+            out
+        }
+        _ => truncate_middle(path, max),
+    }
+}
+
+/// Widths a Table will give its columns when drawn in `area` (inside its borders)
+pub fn column_widths(area: Rect, constraints: &[Constraint], highlight_symbol: &str) -> Vec<usize> {
+    let inner = Rect {
+        width: area
+            .width
+            .saturating_sub(2)
+            .saturating_sub(width(highlight_symbol) as u16),
+        ..area
+    };
+    Layout::horizontal(constraints.iter().copied())
+        .spacing(1)
+        .split(inner)
+        .iter()
+        .map(|column| column.width as usize)
+        .collect()
+}
+
+/// Longest prefix of `text` that fits in `max` columns, without splitting a grapheme cluster
+fn take_width(text: &str, max: usize) -> String {
+    let mut used = 0;
+    text.graphemes(true)
+        .take_while(|g| {
+            used += g.width();
+            used <= max
+        })
+        .collect()
+}
+
+/// Longest suffix of `text` that fits in `max` columns, without splitting a grapheme cluster
+fn take_width_from_end(text: &str, max: usize) -> String {
+    let mut used = 0;
+    let mut tail: Vec<&str> = text
+        .graphemes(true)
+        .rev()
+        .take_while(|g| {
+            used += g.width();
+            used <= max
+        })
+        .collect();
+    tail.reverse();
+    tail.concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FAMILY: &str = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+    const E_ACUTE: &str = "e\u{301}";
+
+    #[test]
+    fn width_counts_grapheme_clusters() {
+        assert_eq!(width("abc"), 3);
+        assert_eq!(width("日本語"), 6);
+        assert_eq!(width(FAMILY), 2);
+        assert_eq!(width(E_ACUTE), 1);
+        assert_eq!(width(&format!("caf{}", E_ACUTE)), 4);
+    }
+
+    #[test]
+    fn truncate_does_not_split_wide_characters() {
+        // The second character would straddle the limit, so it is dropped rather than cut in half
+        assert_eq!(truncate("日本語", 4), "日…");
+        assert_eq!(truncate("日本語", 5), "日本…");
+        assert_eq!(width(&truncate("日本語", 4)), 3);
+    }
+
+    #[test]
+    fn truncate_keeps_zwj_sequences_whole() {
+        let text = format!("a{}b{}", FAMILY, FAMILY);
+        assert_eq!(truncate(&text, 4), format!("a{}…", FAMILY));
+        assert_eq!(truncate(&text, 3), "a…");
+        assert_eq!(truncate(&text, 6), text);
+    }
+
+    #[test]
+    fn truncate_keeps_combining_marks_with_their_letter() {
+        let text = format!("{}{}{}", E_ACUTE, E_ACUTE, E_ACUTE);
+        assert_eq!(truncate(&text, 3), text);
+        assert_eq!(truncate(&text, 2), format!("{}…", E_ACUTE));
+        assert_eq!(truncate(&text, 1), "…");
+    }
+
+    #[test]
+    fn truncate_middle_keeps_clusters_at_both_ends() {
+        let text = format!("{}xxxxxx{}", FAMILY, E_ACUTE);
+        let out = truncate_middle(&text, 5);
+        assert_eq!(out, format!("{}…x{}", FAMILY, E_ACUTE));
+        assert!(width(&out) <= 5);
+
+        assert_eq!(truncate_middle("日本語中文", 7), "日本…文");
+        assert_eq!(truncate_middle("日本語中文", 6), "日…文");
+        assert_eq!(truncate_middle("日本語中文", 5), "日…文");
+    }
+
+    #[test]
+    fn truncate_path_measures_by_cluster() {
+        let path = format!("目录/子目录/{}.txt", FAMILY);
+        let out = truncate_path(&path, 10);
+        assert_eq!(out, format!("目…/{}.txt", FAMILY));
+        assert_eq!(width(&out), 10);
+
+        // One column less leaves no room for a wide character of the directory
+        assert_eq!(truncate_path(&path, 9), format!("…/{}.txt", FAMILY));
+    }
+}
@@ -1,5 +1,149 @@
 use catppuccin::PALETTE;
 use ratatui::style::{Color, Modifier, Style};
+use std::sync::OnceLock;
+
+/// How many colors the terminal can display
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorSupport {
+    None,      // NO_COLOR or a dumb terminal: no colors at all
+    Ansi16,    // The 16 basic ANSI colors
+    Ansi256,   // The xterm 256-color palette
+    TrueColor, // 24-bit RGB
+}
+
+impl ColorSupport {
+    /// Detect the terminal's color support once per process
+    pub fn detect() -> Self {
+        static DETECTED: OnceLock<ColorSupport> = OnceLock::new();
+        *DETECTED.get_or_init(|| {
+            let var = |name: &str| std::env::var(name).unwrap_or_default().to_lowercase();
+            Self::from_env(
+                std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()),
+                &var("COLORTERM"),
+                &var("TERM"),
+                &var("TERM_PROGRAM"),
+                std::env::var_os("WT_SESSION").is_some(),
+            )
+        })
+    }
+
+    /// Decide from NO_COLOR (https://no-color.org), COLORTERM, TERM, TERM_PROGRAM and
+    /// whether WT_SESSION is set (Windows Terminal)
+    pub fn from_env(
+        no_color: bool,
+        colorterm: &str,
+        term: &str,
+        term_program: &str,
+        wt_session: bool,
+    ) -> Self {
+        if no_color || term == "dumb" {
+            return ColorSupport::None;
+        }
+        if colorterm == "truecolor" || colorterm == "24bit" || term.contains("direct") {
+            return ColorSupport::TrueColor;
+        }
+        if matches!(term_program, "iterm.app" | "wezterm" | "vscode") || wt_session {
+            return ColorSupport::TrueColor;
+        }
+        if term.contains("256color") {
+            return ColorSupport::Ansi256;
+        }
+        if term.is_empty() && cfg!(windows) {
+            // Modern Windows consoles understand RGB escape sequences
+            return ColorSupport::TrueColor;
+        }
+        ColorSupport::Ansi16
+    }
+
+    /// Convert a color to the nearest one the terminal can show
+    pub fn adapt(self, color: Color) -> Color {
+        let (r, g, b) = match color {
+            Color::Rgb(r, g, b) => (r, g, b),
+            _ if self == ColorSupport::None => return Color::Reset,
+            other => return other,
+        };
+
+        match self {
+            ColorSupport::TrueColor => color,
+            ColorSupport::Ansi256 => Color::Indexed(nearest_256(r, g, b)),
+            ColorSupport::Ansi16 => nearest_16(r, g, b),
+            ColorSupport::None => Color::Reset,
+        }
+    }
+}
+
+/// Nearest entry in the 6x6x6 color cube or the grayscale ramp of the xterm palette
+fn nearest_256(r: u8, g: u8, b: u8) -> u8 {
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    let level = |v: u8| {
+        (0..6)
+            .min_by_key(|&i| (LEVELS[i] as i32 - v as i32).abs())
+            .unwrap_or(0)
+    };
+    let distance = |(r2, g2, b2): (u8, u8, u8)| {
+        let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+        d(r, r2) + d(g, g2) + d(b, b2)
+    };
+
+    let (ri, gi, bi) = (level(r), level(g), level(b));
+    let cube = (LEVELS[ri], LEVELS[gi], LEVELS[bi]);
+    let cube_index = 16 + 36 * ri as u8 + 6 * gi as u8 + bi as u8;
+
+    let average = (r as u32 + g as u32 + b as u32) / 3;
+    let gray_step = ((average.saturating_sub(8) + 5) / 10).min(23) as u8;
+    let gray = 8 + 10 * gray_step;
+
+    if distance((gray, gray, gray)) < distance(cube) {
+        232 + gray_step
+    } else {
+        cube_index
+    }
+}
+
+/// Nearest basic ANSI color, matching by hue so pastel colors keep their character
+fn nearest_16(r: u8, g: u8, b: u8) -> Color {
+    let (rf, gf, bf) = (r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0);
+    let max = rf.max(gf).max(bf);
+    let min = rf.min(gf).min(bf);
+    let chroma = max - min;
+    let lightness = (max + min) / 2.0;
+
+    // Nearly gray colors map onto the four gray levels
+    if chroma < 0.25 {
+        return match lightness {
+            l if l < 0.2 => Color::Black,
+            l if l < 0.5 => Color::DarkGray,
+            l if l < 0.8 => Color::Gray,
+            _ => Color::White,
+        };
+    }
+
+    let hue = if max == rf {
+        60.0 * (((gf - bf) / chroma).rem_euclid(6.0))
+    } else if max == gf {
+        60.0 * ((bf - rf) / chroma + 2.0)
+    } else {
+        60.0 * ((rf - gf) / chroma + 4.0)
+    };
+
+    let bright = lightness > 0.6;
+    match hue {
+        h if !(20.0..330.0).contains(&h) => pick(bright, Color::LightRed, Color::Red),
+        h if h < 90.0 => pick(bright, Color::LightYellow, Color::Yellow),
+        h if h < 150.0 => pick(bright, Color::LightGreen, Color::Green),
+        h if h < 210.0 => pick(bright, Color::LightCyan, Color::Cyan),
+        h if h < 255.0 => pick(bright, Color::LightBlue, Color::Blue),
+        _ => pick(bright, Color::LightMagenta, Color::Magenta),
+    }
+}
+
+fn pick(bright: bool, light: Color, dark: Color) -> Color {
+    if bright {
+        light
+    } else {
+        dark
+    }
+}
 
 /// Available accent colors for the theme
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -120,6 +264,8 @@ pub struct Theme {
 
     // Configurable title color for panel headers
     title_color: TitleColor, // Color for all panel headers/titles
+
+    color_support: ColorSupport, // What the palette was downgraded to for this terminal
 }
 
 impl Theme {
@@ -159,49 +305,58 @@ impl Theme {
         title_color: TitleColor,
     ) -> Self {
         let macchiato = &PALETTE.macchiato.colors;
+        let color_support = ColorSupport::detect();
+        let color = |c: catppuccin::Color| color_support.adapt(c.into());
+        // On 16-color terminals the dark backgrounds all collapse to black, so keep the terminal's own background
+        let background = |c: catppuccin::Color| match color_support {
+            ColorSupport::Ansi16 => Color::Reset,
+            _ => color(c),
+        };
 
         Self {
             // Base colors (semantic usage per updated guidelines)
-            base: macchiato.base.into(), // Tab bar and status bar backgrounds
-            mantle: macchiato.mantle.into(), // Unified background for terminal and all content
-            crust: macchiato.crust.into(), // Deepest accent color
+            base: background(macchiato.base), // Tab bar and status bar backgrounds
+            mantle: background(macchiato.mantle), // Unified background for terminal and all content
+            crust: background(macchiato.crust), // Deepest accent color
 
             // Surface colors
-            surface0: macchiato.surface0.into(),
-            surface1: macchiato.surface1.into(),
-            surface2: macchiato.surface2.into(),
+            surface0: color(macchiato.surface0),
+            surface1: color(macchiato.surface1),
+            surface2: color(macchiato.surface2),
 
             // Overlay colors
-            overlay0: macchiato.overlay0.into(),
-            overlay1: macchiato.overlay1.into(),
-            overlay2: macchiato.overlay2.into(),
+            overlay0: color(macchiato.overlay0),
+            overlay1: color(macchiato.overlay1),
+            overlay2: color(macchiato.overlay2),
 
             // Text colors
-            text: macchiato.text.into(),
-            subtext0: macchiato.subtext0.into(),
-            subtext1: macchiato.subtext1.into(),
+            text: color(macchiato.text),
+            subtext0: color(macchiato.subtext0),
+            subtext1: color(macchiato.subtext1),
 
             // All accent colors
-            rosewater: macchiato.rosewater.into(),
-            flamingo: macchiato.flamingo.into(),
-            pink: macchiato.pink.into(),
-            mauve: macchiato.mauve.into(),
-            red: macchiato.red.into(),
-            maroon: macchiato.maroon.into(),
-            peach: macchiato.peach.into(),
-            yellow: macchiato.yellow.into(),
-            green: macchiato.green.into(),
-            teal: macchiato.teal.into(),
-            sky: macchiato.sky.into(),
-            sapphire: macchiato.sapphire.into(),
-            blue: macchiato.blue.into(),
-            lavender: macchiato.lavender.into(),
+            rosewater: color(macchiato.rosewater),
+            flamingo: color(macchiato.flamingo),
+            pink: color(macchiato.pink),
+            mauve: color(macchiato.mauve),
+            red: color(macchiato.red),
+            maroon: color(macchiato.maroon),
+            peach: color(macchiato.peach),
+            yellow: color(macchiato.yellow),
+            green: color(macchiato.green),
+            teal: color(macchiato.teal),
+            sky: color(macchiato.sky),
+            sapphire: color(macchiato.sapphire),
+            blue: color(macchiato.blue),
+            lavender: color(macchiato.lavender),
 
             accent_color,
             accent2_color,
             accent3_color,
 
             title_color,
+
+            color_support,
         }
     }
 
@@ -265,6 +420,16 @@ impl Theme {
         }
     }
 
+    /// Whether the theme emits any colors (false when NO_COLOR is set)
+    pub fn uses_color(&self) -> bool {
+        self.color_support != ColorSupport::None
+    }
+
+    /// Downgrade a color that doesn't come from the palette, e.g. a file status color
+    pub fn adapt(&self, color: Color) -> Color {
+        self.color_support.adapt(color)
+    }
+
     /// Get the title color
     pub fn title_color(&self) -> Color {
         self.title_color.get_color(self)
@@ -472,11 +637,13 @@ impl Theme {
         Style::default().fg(self.subtext0)
     }
 
-    /// Highlighted/active elements (uses accent color)
+    /// Highlighted/active elements (uses accent color, reverse video without colors)
     pub fn highlight_style(&self) -> Style {
-        Style::default()
-            .fg(self.accent())
-            .add_modifier(Modifier::BOLD)
+        self.monochrome_reversed(
+            Style::default()
+                .fg(self.accent())
+                .add_modifier(Modifier::BOLD),
+        )
     }
 
     /// Active selection style (uses accent color)
@@ -640,10 +807,21 @@ impl Theme {
 
     /// Popup button style (blue background with base text)
     pub fn popup_button_style(&self) -> Style {
-        Style::default()
-            .fg(self.base)
-            .bg(self.blue)
-            .add_modifier(Modifier::BOLD)
+        self.monochrome_reversed(
+            Style::default()
+                .fg(self.base)
+                .bg(self.blue)
+                .add_modifier(Modifier::BOLD),
+        )
+    }
+
+    /// Without colors, selections and buttons stand out through reverse video instead
+    fn monochrome_reversed(&self, style: Style) -> Style {
+        if self.uses_color() {
+            style
+        } else {
+            style.add_modifier(Modifier::REVERSED)
+        }
     }
 
     /// Popup button border style (blue to match button)
@@ -0,0 +1,223 @@
+use crate::app::{AppState, Tour};
+use crate::tui::tabs::Tab;
+use crate::tui::text;
+use crate::tui::theme::Theme;
+use ratatui::layout::{Constraint, Layout, Margin};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+use ratatui::{layout::Rect, Frame};
+
+/// One stop of the tour: the tab it shows and what to say about it
+pub struct TourStep {
+    pub tab: Option<Tab>, // None keeps whatever tab is open
+    pub title: &'static str,
+    pub text: &'static [&'static str],
+}
+
+/// The guided tour, in order; stage → commit → push is walked through on the tabs that do it
+pub const TOUR_STEPS: &[TourStep] = &[
+    TourStep {
+        tab: None,
+        title: "Welcome to gitix",
+        text: &[
+            "gitix puts the everyday Git workflow into a few tabs.",
+            "Move between them with Tab and Shift+Tab, or jump straight to one with 1-9. \
+             The bottom line always lists the keys the current screen understands.",
+        ],
+    },
+    TourStep {
+        tab: Some(Tab::Overview),
+        title: "Overview",
+        text: &[
+            "Your repository at a glance: the current branch, recent activity and a calendar of commits.",
+            "Pick a day with the arrow keys and press Enter to see what was committed then.",
+        ],
+    },
+    TourStep {
+        tab: Some(Tab::Files),
+        title: "Files",
+        text: &[
+            "Browse the files in your project, open them in your editor and see their history with H.",
+        ],
+    },
+    TourStep {
+        tab: Some(Tab::Status),
+        title: "Status",
+        text: &["Everything you changed since the last commit, grouped by what Git thinks of it."],
+    },
+    TourStep {
+        tab: Some(Tab::SaveChanges),
+        title: "Step 1 and 2: Stage and Commit",
+        text: &[
+            "Saving your work is called committing. First pick the changed files to include with \
+             Space: this is staging.",
+            "Then write a short message saying what you changed and press Enter to commit. \
+             Press Shift+? here any time for help.",
+        ],
+    },
+    TourStep {
+        tab: Some(Tab::Update),
+        title: "Step 3: Push",
+        text: &[
+            "Commits stay on your computer until you push them. Press U here to send them to the \
+             remote repository, and P to pull in what others pushed.",
+            "The tab shows how far your branch is ahead of or behind the remote.",
+        ],
+    },
+    TourStep {
+        tab: Some(Tab::History),
+        title: "History",
+        text: &["Every commit, newest first. Press / to filter by author, path or date."],
+    },
+    TourStep {
+        tab: Some(Tab::Branches),
+        title: "Branches",
+        text: &[
+            "Branches let you work on something without touching the main line. Press N to start one \
+             and Enter to switch between them.",
+        ],
+    },
+    TourStep {
+        tab: Some(Tab::Activity),
+        title: "Activity",
+        text: &["A log of what gitix ran for you this session, including the exact Git commands."],
+    },
+    TourStep {
+        tab: Some(Tab::Settings),
+        title: "Settings",
+        text: &[
+            "Your name and email for commits, colors and how pulls and pushes behave.",
+            "That's the tour! Press Ctrl+G on this tab whenever you want to see it again.",
+        ],
+    },
+];
+
+/// Render the current tour step along the bottom of `area`, leaving the tab it describes visible
+pub fn render_tour_popup(f: &mut Frame, area: Rect, state: &AppState, theme: &Theme) {
+    let tour = match &state.tour {
+        Some(tour) => tour,
+        None => return,
+    };
+    let step = match tour.steps.get(tour.current).map(|idx| &TOUR_STEPS[*idx]) {
+        Some(step) => step,
+        None => return,
+    };
+
+    let width = area.width.saturating_sub(4).min(80);
+    // Wrapped text, a blank line between paragraphs, a spacer, the key line and the borders
+    let text_width = width.saturating_sub(4).max(1) as usize;
+    let text_lines: usize = step
+        .text
+        .iter()
+        .map(|paragraph| text::width(paragraph).div_ceil(text_width).max(1))
+        .sum();
+    let height = ((text_lines + step.text.len() - 1 + 4) as u16).min(area.height);
+    let popup_area = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + area.height.saturating_sub(height + 1),
+        width,
+        height,
+    };
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(format!(
+            " {} ({}/{}) ",
+            step.title,
+            tour.current + 1,
+            tour.steps.len()
+        ))
+        .title_style(theme.title_style())
+        .borders(Borders::ALL)
+        .border_style(theme.focused_border_style())
+        .style(theme.secondary_background_style());
+    let inner = block.inner(popup_area).inner(Margin {
+        vertical: 0,
+        horizontal: 1,
+    });
+    f.render_widget(block, popup_area);
+
+    let chunks = Layout::vertical([Constraint::Min(1), Constraint::Length(1), Constraint::Length(1)]).split(inner);
+
+    let mut lines = Vec::new();
+    for (i, paragraph) in step.text.iter().enumerate() {
+        if i > 0 {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::styled(*paragraph, theme.text_style()));
+    }
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }), chunks[0]);
+
+    let next = if tour.current + 1 == tour.steps.len() {
+        "Finish"
+    } else {
+        "Next"
+    };
+    f.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled("[Enter/→] ", theme.accent_style()),
+            Span::styled(format!("{}  ", next), theme.secondary_text_style()),
+            Span::styled("[←] ", theme.accent_style()),
+            Span::styled("Back  ", theme.secondary_text_style()),
+            Span::styled("[Esc] ", theme.accent_style()),
+            Span::styled("Skip tour", theme.secondary_text_style()),
+        ])),
+        chunks[2],
+    );
+}
+
+impl AppState {
+    /// Show the tour on first launch, unless it was already completed or skipped
+    pub fn maybe_start_tour(&mut self) {
+        if !self.git_enabled || self.show_init_prompt {
+            return;
+        }
+        if let Ok(Some(true)) = crate::config::get_tour_completed() {
+            return;
+        }
+        self.start_tour();
+    }
+
+    /// Start the tour from the beginning, skipping tabs that are hidden from the tab bar
+    pub fn start_tour(&mut self) {
+        let steps = TOUR_STEPS
+            .iter()
+            .enumerate()
+            .filter(|(_, step)| step.tab.is_none_or(|tab| self.tab_bar.contains(tab)))
+            .map(|(idx, _)| idx)
+            .collect();
+        self.tour = Some(Tour { steps, current: 0 });
+    }
+
+    /// The tab the current tour step is about, if it names one
+    pub fn tour_tab(&self) -> Option<Tab> {
+        let tour = self.tour.as_ref()?;
+        TOUR_STEPS[*tour.steps.get(tour.current)?].tab
+    }
+
+    /// Move one step forward or back; going past the last step finishes the tour
+    pub fn advance_tour(&mut self, forward: bool) {
+        let tour = match self.tour.as_mut() {
+            Some(tour) => tour,
+            None => return,
+        };
+        if !forward {
+            tour.current = tour.current.saturating_sub(1);
+        } else if tour.current + 1 < tour.steps.len() {
+            tour.current += 1;
+        } else {
+            self.finish_tour();
+        }
+    }
+
+    /// Close the tour and remember not to show it on the next launch
+    pub fn finish_tour(&mut self) {
+        self.tour = None;
+        if let Err(e) = crate::config::set_tour_completed(true) {
+            self.show_error(
+                "Settings Error",
+                &format!("Failed to save gitix.tour.completed: {}", e),
+            );
+        }
+    }
+}
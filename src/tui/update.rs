@@ -1,11 +1,16 @@
-use crate::app::AppState;
+use crate::app::{AppState, RepoSnapshot};
+use crate::tui::pull_requests;
+use crate::tui::text;
 use crate::tui::theme::Theme;
 use ratatui::layout::{Alignment, Constraint, Direction, Layout};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph, Wrap};
+use ratatui::widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap};
 use ratatui::{layout::Rect, Frame};
 
+/// Conflicted files listed in the download panel before the rest are only counted
+const CONFLICTS_SHOWN: usize = 3;
+
 // Mock data structures for UI design
 #[derive(Debug, Clone)]
 struct RemoteStatus {
@@ -79,7 +84,15 @@ impl OperationStatus {
     }
 }
 
-pub fn render_update_tab(f: &mut Frame, area: Rect, state: &AppState) {
+pub fn render_update_tab(f: &mut Frame, area: Rect, state: &mut AppState) {
+    // Branch, remote and merge/rebase state come from the snapshot, not the repository on every
+    // frame
+    if state.git_enabled {
+        state.repo_snapshot();
+    }
+    let state = &*state;
+    let snapshot = state.repo_snapshot.as_ref();
+
     // Use configured theme from app state
     let theme = Theme::with_accents_and_title(
         state.current_theme_accent,
@@ -100,16 +113,76 @@ pub fn render_update_tab(f: &mut Frame, area: Rect, state: &AppState) {
         return;
     }
 
-    // Check for remote origin using real git operations
-    let has_remote = crate::git::has_remote_origin().unwrap_or(false);
-
-    if !has_remote {
+    if !snapshot.is_some_and(|snapshot| snapshot.has_origin) {
         render_no_remote_message(f, area, &theme);
         return;
     }
 
     // Main sync interface
     render_sync_interface(f, area, state, &theme);
+
+    if let Some(operation) = &state.sync_confirm {
+        render_sync_confirm_popup(f, area, state, operation, &theme);
+    }
+}
+
+/// Say what a pull or push is about to do and ask before doing it (beginner profile)
+fn render_sync_confirm_popup(
+    f: &mut Frame,
+    area: Rect,
+    state: &AppState,
+    operation: &crate::git::SyncOperationType,
+    theme: &Theme,
+) {
+    let branch = state
+        .repo_snapshot
+        .as_ref()
+        .and_then(|snapshot| snapshot.branch.clone())
+        .unwrap_or_else(|| "HEAD".to_string());
+    let (ahead, behind) = state
+        .update_remote_status
+        .as_ref()
+        .map_or((0, 0), |status| (status.ahead, status.behind));
+    let (title, lines) = match operation {
+        crate::git::SyncOperationType::Push => (
+            "Push to origin?",
+            vec![
+                format!("This uploads {} commit(s) from {} to origin/{}.", ahead, branch, branch),
+                "Anyone with access to the remote will see them.".to_string(),
+            ],
+        ),
+        _ => (
+            "Pull from origin?",
+            vec![
+                format!(
+                    "This downloads {} commit(s) from origin/{} and merges them into {}.",
+                    behind, branch, branch
+                ),
+                "Your committed work is kept; commit any changes first.".to_string(),
+            ],
+        ),
+    };
+
+    let popup_area = crate::tui::centered_rect(60, 8, area);
+    f.render_widget(Clear, popup_area);
+    let mut text: Vec<Line> = lines
+        .into_iter()
+        .map(|line| Line::styled(line, theme.text_style()))
+        .collect();
+    text.push(Line::from(""));
+    text.push(Line::styled("[Y] Continue  [N] Cancel", theme.accent_style()));
+    let popup = Paragraph::new(text)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .title_style(theme.popup_title_style())
+                .border_style(theme.popup_border_style())
+                .style(theme.popup_background_style()),
+        );
+    f.render_widget(popup, popup_area);
 }
 
 fn render_no_git_message(f: &mut Frame, area: Rect, theme: &Theme) {
@@ -162,6 +235,8 @@ fn render_no_remote_message(f: &mut Frame, area: Rect, theme: &Theme) {
             Style::default().fg(theme.sky).add_modifier(Modifier::BOLD),
         )),
         Line::from(""),
+        Line::from("In the Settings tab, focus the Remotes panel and press A, or run"),
+        Line::from(""),
         Line::from(Span::styled(
             "git remote add origin <repository-url>",
             Style::default()
@@ -248,7 +323,11 @@ fn render_remote_status(f: &mut Frame, area: Rect, state: &AppState, theme: &The
         }
     };
 
-    let url_text = format!("({})", remote_status.url);
+    // Long remote URLs give way in the middle so the host and repository name stay visible
+    let inner_width = (area.width as usize).saturating_sub(2);
+    let url_width = inner_width
+        .saturating_sub(text::width("Remote: ") + text::width(&remote_status.name) + 3);
+    let url_text = format!("({})", text::truncate_middle(&remote_status.url, url_width));
     let ahead_behind_text = if remote_status.ahead > 0 && remote_status.behind > 0 {
         format!(
             "{} local changes not uploaded to remote, {} new changes to download from remote",
@@ -268,14 +347,46 @@ fn render_remote_status(f: &mut Frame, area: Rect, state: &AppState, theme: &The
         "Up to date".to_string()
     };
 
-    let status_text = vec![
-        Line::from(vec![
-            Span::styled("Remote: ", theme.accent2_style()),
-            Span::styled(&remote_status.name, theme.text_style()),
-            Span::raw(" "),
-            Span::styled(&url_text, theme.muted_text_style()),
-        ]),
-        Line::from(""),
+    // In a fork setup the spacer line shows the upstream remote and the sync action instead
+    let fork_line = match &state.update_fork {
+        Some(fork) => {
+            let action = format!("[S] Sync fork ({})", fork.default_branch);
+            let upstream_width = inner_width
+                .saturating_sub(text::width("Upstream: ") + 2 + text::width(&action));
+            Line::from(vec![
+                Span::styled("Upstream: ", theme.accent2_style()),
+                Span::styled(
+                    text::truncate_middle(&fork.upstream_url, upstream_width),
+                    theme.muted_text_style(),
+                ),
+                Span::raw("  "),
+                Span::styled(action, theme.accent_style()),
+            ])
+        }
+        None => Line::from(""),
+    };
+
+    let mut status_text = vec![Line::from(vec![
+        Span::styled("Remote: ", theme.accent2_style()),
+        Span::styled(&remote_status.name, theme.text_style()),
+        Span::raw(" "),
+        Span::styled(&url_text, theme.muted_text_style()),
+    ])];
+    // The forge origin is hosted on, with the CI result for HEAD
+    if let Some(forge) = &state.update_forge {
+        status_text.push(Line::from(vec![
+            Span::styled("Forge: ", theme.accent2_style()),
+            Span::styled(forge.kind.title(), theme.text_style()),
+            Span::styled("  CI: ", theme.accent2_style()),
+            pull_requests::ci_span(state, theme),
+            Span::raw("  "),
+            Span::styled("[W] Web  [L] ", theme.accent_style()),
+            Span::styled(forge.kind.pull_request_name(), theme.accent_style()),
+            Span::styled("  [O] Open New", theme.accent_style()),
+        ]));
+    }
+    status_text.extend([
+        fork_line,
         Line::from(vec![
             Span::styled("Status: ", theme.accent2_style()),
             if remote_status.ahead > 0 && remote_status.behind > 0 {
@@ -291,11 +402,19 @@ fn render_remote_status(f: &mut Frame, area: Rect, state: &AppState, theme: &The
         Line::from(vec![
             Span::styled("Last updated: ", theme.accent2_style()),
             Span::styled(
-                remote_status.last_fetch.as_deref().unwrap_or("Never"),
+                remote_status
+                    .last_fetch
+                    .map(|time| state.time_format.format(time))
+                    .unwrap_or_else(|| "Never".to_string()),
                 theme.accent3_style(),
             ),
         ]),
-    ];
+        Line::from(vec![
+            Span::styled("Last refreshed: ", theme.accent2_style()),
+            Span::styled(format_last_refreshed(state), theme.accent3_style()),
+            Span::styled(format_auto_refresh(state), theme.muted_text_style()),
+        ]),
+    ]);
 
     let status_block = Paragraph::new(status_text).style(theme.text_style()).block(
         Block::default()
@@ -309,6 +428,33 @@ fn render_remote_status(f: &mut Frame, area: Rect, state: &AppState, theme: &The
     f.render_widget(status_block, area);
 }
 
+/// Time since gitix last fetched the remote in this session
+fn format_last_refreshed(state: &AppState) -> String {
+    match state.update_last_refreshed {
+        Some(instant) => {
+            let secs = instant.elapsed().as_secs();
+            if secs < 60 {
+                format!("{}s ago", secs)
+            } else if secs < 3600 {
+                format!("{}m {}s ago", secs / 60, secs % 60)
+            } else {
+                format!("{}h {}m ago", secs / 3600, (secs % 3600) / 60)
+            }
+        }
+        None => "Not yet this session".to_string(),
+    }
+}
+
+/// Auto-refresh schedule shown after the last refresh time
+fn format_auto_refresh(state: &AppState) -> String {
+    if state.auto_refresh_minutes == 0 {
+        return "  (auto-refresh off)".to_string();
+    }
+    let interval = u64::from(state.auto_refresh_minutes) * 60;
+    let remaining = interval.saturating_sub(state.update_auto_refresh_since.elapsed().as_secs());
+    format!("  (next auto-refresh in {}m {}s)", remaining / 60, remaining % 60)
+}
+
 fn render_sync_actions(f: &mut Frame, area: Rect, state: &AppState, theme: &Theme) {
     // Split into two columns for actions
     let chunks = Layout::default()
@@ -363,9 +509,38 @@ fn render_download_section(f: &mut Frame, area: Rect, state: &AppState, theme: &
         "No new changes to download from remote".to_string()
     };
 
-    let pull_mode = if state.pull_rebase { "rebase" } else { "merge" };
+    let pull_mode = if state.pulls_with_rebase() { "rebase" } else { "merge" };
+
+    // A pull reports each rebased commit (or the merge) as it goes
+    let step = state
+        .sync_progress
+        .as_ref()
+        .filter(|progress| progress.detail.is_some() && state.is_syncing())
+        .map(|progress| {
+            let detail = progress.detail.as_deref().unwrap_or_default();
+            if progress.total > 0 {
+                format!(
+                    "{} {}/{}: {}",
+                    progress.stage.to_lowercase(),
+                    progress.current,
+                    progress.total,
+                    detail
+                )
+            } else {
+                format!("{} {}", progress.stage.to_lowercase(), detail)
+            }
+        });
+
+    let no_snapshot = RepoSnapshot::default();
+    let snapshot = state.repo_snapshot.as_ref().unwrap_or(&no_snapshot);
+    let conflicted = &snapshot.conflicted;
+    let conflicts_text = format!(
+        "Merge stopped on {} conflicted file{} - [C] Resolve  [A] Abort",
+        conflicted.len(),
+        if conflicted.len() == 1 { "" } else { "s" }
+    );
 
-    let download_text = vec![
+    let mut download_text = vec![
         Line::from(vec![Span::styled(
             "↓ Download Changes",
             Style::default()
@@ -373,7 +548,46 @@ fn render_download_section(f: &mut Frame, area: Rect, state: &AppState, theme: &
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
-        if remote_status.behind > 0 {
+        if let Some(step) = &step {
+            Line::from(vec![
+                Span::styled("Status: ", theme.accent2_style()),
+                Span::styled(step, theme.warning_style()),
+            ])
+        } else if !conflicted.is_empty() {
+            Line::from(vec![
+                Span::styled("Status: ", theme.accent2_style()),
+                Span::styled(&conflicts_text, theme.error_style()),
+            ])
+        } else if snapshot.merge_in_progress {
+            Line::from(vec![
+                Span::styled("Status: ", theme.accent2_style()),
+                Span::styled(
+                    "Merge waiting to be committed - [C] Continue  [A] Abort",
+                    theme.warning_style(),
+                ),
+            ])
+        } else if snapshot.planned_rebase_in_progress {
+            Line::from(vec![
+                Span::styled("Status: ", theme.accent2_style()),
+                Span::styled(
+                    "Rebase waiting to continue - [C] Continue  [A] Abort",
+                    theme.warning_style(),
+                ),
+            ])
+        } else if snapshot.cherry_pick_in_progress {
+            Line::from(vec![
+                Span::styled("Status: ", theme.accent2_style()),
+                Span::styled(
+                    "Cherry-pick waiting to be committed - [C] Continue  [A] Abort",
+                    theme.warning_style(),
+                ),
+            ])
+        } else if snapshot.rebase_in_progress {
+            Line::from(vec![
+                Span::styled("Status: ", theme.accent2_style()),
+                Span::styled("Rebase stopped part-way - [A] Abort", theme.warning_style()),
+            ])
+        } else if remote_status.behind > 0 {
             Line::from(vec![
                 Span::styled("Status: ", theme.accent2_style()),
                 Span::styled(&available_text, theme.info_style()),
@@ -403,8 +617,29 @@ fn render_download_section(f: &mut Frame, area: Rect, state: &AppState, theme: &
                 Span::raw(" - Nothing to download"),
             ])
         },
+        Line::from(vec![
+            Span::raw("  ◦ "),
+            Span::styled("[F] Fetch all", theme.accent_style()),
+            Span::raw(" - Check every remote"),
+        ]),
     ];
 
+    // The files a stopped pull left conflicted, under the status; [C] resolves them one by one
+    if step.is_none() && !conflicted.is_empty() {
+        let mut files: Vec<Line> = conflicted
+            .iter()
+            .take(CONFLICTS_SHOWN)
+            .map(|path| Line::styled(format!("  ✗ {}", path), theme.error_style()))
+            .collect();
+        if conflicted.len() > CONFLICTS_SHOWN {
+            files.push(Line::styled(
+                format!("  … and {} more", conflicted.len() - CONFLICTS_SHOWN),
+                theme.muted_text_style(),
+            ));
+        }
+        download_text.splice(3..3, files);
+    }
+
     let download_block = Paragraph::new(download_text)
         .style(theme.text_style())
         .block(
@@ -534,10 +769,12 @@ fn render_recent_activity(f: &mut Frame, area: Rect, state: &AppState, theme: &T
                     crate::git::SyncOperationType::Pull => "Download",
                     crate::git::SyncOperationType::Push => "Upload",
                     crate::git::SyncOperationType::Refresh => "Refresh",
+                    crate::git::SyncOperationType::ForkSync => "Sync Fork",
+                    crate::git::SyncOperationType::CherryPick => "Cherry-pick",
+                    crate::git::SyncOperationType::Merge => "Merge",
                 };
 
-                // Format the timestamp as relative time
-                let relative_time = crate::git::format_system_time_relative(op.timestamp);
+                let time = state.time_format.format(op.timestamp.into());
 
                 ListItem::new(Line::from(vec![
                     Span::styled(status_symbol, status_style),
@@ -546,7 +783,7 @@ fn render_recent_activity(f: &mut Frame, area: Rect, state: &AppState, theme: &T
                     Span::raw(" - "),
                     Span::styled(&op.message, theme.text_style()),
                     Span::raw(" "),
-                    Span::styled(format!("({})", relative_time), theme.muted_text_style()),
+                    Span::styled(format!("({})", time), theme.muted_text_style()),
                 ]))
             })
             .collect()
@@ -556,7 +793,11 @@ fn render_recent_activity(f: &mut Frame, area: Rect, state: &AppState, theme: &T
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title("Recent Sync Activity")
+                .title(if operations.is_empty() {
+                    "Recent Sync Activity".to_string()
+                } else {
+                    format!("Recent Sync Activity ({})", operations.len())
+                })
                 .title_style(theme.title_style())
                 .border_style(theme.border_style())
                 .style(theme.secondary_background_style()),
@@ -0,0 +1,315 @@
+use crate::app::AppState;
+use crate::git::{NestedRepo, WorkspaceRepoStatus};
+use crate::tui::text;
+use crate::tui::theme::Theme;
+use crossterm::event::{DisableFocusChange, EnableFocusChange};
+use crossterm::terminal::{
+    EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
+};
+use ratatui::Terminal;
+use ratatui::backend::CrosstermBackend;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState};
+use ratatui::{Frame, layout::Rect};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+
+/// How many directories below the workspace to look for repositories
+const WORKSPACE_DEPTH: usize = 3;
+
+/// One repository on the dashboard
+struct WorkspaceRow {
+    path: PathBuf,
+    name: String,                                        // Relative to the workspace directory
+    status: Option<Result<WorkspaceRepoStatus, String>>, // None while it is being read
+    nested: Vec<NestedRepo>,                             // Submodules and clones inside it
+}
+
+/// The dashboard: every repository found, and the status scan filling them in
+struct Workspace {
+    root: PathBuf,
+    rows: Vec<WorkspaceRow>,
+    table_state: TableState,
+    scan: Option<mpsc::Receiver<RepoScan>>,
+}
+
+/// What the status scan read for one row
+type RepoScan = (usize, Result<WorkspaceRepoStatus, String>, Vec<NestedRepo>);
+
+impl Workspace {
+    /// Read the status of the given rows on a background thread
+    fn start_scan(&mut self, indices: Vec<usize>) {
+        let paths: Vec<(usize, PathBuf)> = indices
+            .into_iter()
+            .map(|idx| {
+                self.rows[idx].status = None;
+                (idx, self.rows[idx].path.clone())
+            })
+            .collect();
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            for (idx, path) in paths {
+                let status = crate::git::workspace_repo_status(&path).map_err(|e| e.to_string());
+                let nested = crate::git::nested_repositories(&path).unwrap_or_default();
+                if tx.send((idx, status, nested)).is_err() {
+                    return;
+                }
+            }
+        });
+        self.scan = Some(rx);
+    }
+
+    /// Fill in whatever statuses the scan has read so far
+    fn poll_scan(&mut self) {
+        let rx = match &self.scan {
+            Some(rx) => rx,
+            None => return,
+        };
+        loop {
+            match rx.try_recv() {
+                Ok((idx, status, nested)) => {
+                    self.rows[idx].status = Some(status);
+                    self.rows[idx].nested = nested;
+                }
+                Err(mpsc::TryRecvError::Empty) => return,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.scan = None;
+                    return;
+                }
+            }
+        }
+    }
+
+    fn navigate(&mut self, delta: isize) {
+        let current = self.table_state.selected().unwrap_or(0);
+        let next = current.saturating_add_signed(delta).min(self.rows.len() - 1);
+        self.table_state.select(Some(next));
+    }
+}
+
+/// Run the multi-repository dashboard for `root` until the user quits
+///
+/// Enter opens the full interface for the selected repository; quitting it
+/// comes back to the dashboard with that repository's row re-read.
+pub fn run_workspace(root: &Path) -> i32 {
+    let root = match root.canonicalize() {
+        Ok(root) if root.is_dir() => root,
+        _ => {
+            eprintln!("gitix --workspace: {} is not a directory", root.display());
+            return 2;
+        }
+    };
+    let rows: Vec<WorkspaceRow> = crate::git::find_repositories(&root, WORKSPACE_DEPTH)
+        .into_iter()
+        .map(|path| {
+            let name = match path.strip_prefix(&root) {
+                Ok(relative) if relative.as_os_str().is_empty() => ".".to_string(),
+                Ok(relative) => relative.display().to_string(),
+                Err(_) => path.display().to_string(),
+            };
+            WorkspaceRow {
+                path,
+                name,
+                status: None,
+                nested: Vec::new(),
+            }
+        })
+        .collect();
+    if rows.is_empty() {
+        eprintln!("gitix --workspace: no git repositories under {}", root.display());
+        return 1;
+    }
+    let original_dir = std::env::current_dir().unwrap_or_else(|_| root.clone());
+
+    let mut workspace = Workspace {
+        root,
+        rows,
+        table_state: TableState::default().with_selected(Some(0)),
+        scan: None,
+    };
+    workspace.start_scan((0..workspace.rows.len()).collect());
+
+    enter_terminal();
+    let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout())).unwrap();
+    let theme = Theme::new();
+
+    loop {
+        workspace.poll_scan();
+        terminal
+            .draw(|f| render_workspace(f, &mut workspace, &theme))
+            .unwrap();
+
+        if !event::poll(std::time::Duration::from_millis(100)).unwrap_or(false) {
+            continue;
+        }
+        let key = match event::read() {
+            Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => key,
+            _ => continue,
+        };
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => break,
+            KeyCode::Up => workspace.navigate(-1),
+            KeyCode::Down => workspace.navigate(1),
+            KeyCode::Home => workspace.table_state.select(Some(0)),
+            KeyCode::End => workspace.table_state.select(Some(workspace.rows.len() - 1)),
+            KeyCode::Char('r') | KeyCode::Char('R') => {
+                workspace.start_scan((0..workspace.rows.len()).collect());
+            }
+            KeyCode::Enter => {
+                let idx = workspace.table_state.selected().unwrap_or(0);
+                // The interface works on the current directory, like running gitix there
+                if std::env::set_current_dir(&workspace.rows[idx].path).is_err() {
+                    workspace.rows[idx].status =
+                        Some(Err("Cannot enter the directory".to_string()));
+                    continue;
+                }
+                leave_terminal();
+                let mut state = AppState::default();
+                super::start_tui(&mut state, None);
+                let _ = std::env::set_current_dir(&original_dir);
+                enter_terminal();
+                terminal.clear().unwrap();
+                workspace.start_scan(vec![idx]);
+            }
+            _ => {}
+        }
+    }
+
+    leave_terminal();
+    0
+}
+
+fn enter_terminal() {
+    enable_raw_mode().unwrap();
+    crossterm::execute!(io::stdout(), EnterAlternateScreen, EnableFocusChange).unwrap();
+}
+
+fn leave_terminal() {
+    disable_raw_mode().unwrap();
+    crossterm::execute!(io::stdout(), DisableFocusChange, LeaveAlternateScreen).unwrap();
+}
+
+fn render_workspace(f: &mut Frame, workspace: &mut Workspace, theme: &Theme) {
+    let size = f.area();
+    f.render_widget(Block::default().style(theme.main_background_style()), size);
+    let chunks = Layout::vertical([
+        Constraint::Min(3),    // Repositories
+        Constraint::Length(1), // Key hints
+    ])
+    .margin(1)
+    .split(size);
+
+    render_repo_table(f, chunks[0], workspace, theme);
+
+    let hints = "[↑↓] Navigate  [Enter] Open  [R] Rescan  [q] Quit";
+    f.render_widget(
+        Paragraph::new(Line::styled(hints, theme.status_bar_style()))
+            .alignment(ratatui::layout::Alignment::Center),
+        chunks[1],
+    );
+}
+
+/// Roll the repositories inside a row up into one answer
+fn nested_cell(nested: &[NestedRepo], theme: &Theme) -> Cell<'static> {
+    let unsettled = nested
+        .iter()
+        .filter(|repo| !repo.status.as_ref().is_ok_and(WorkspaceRepoStatus::is_settled))
+        .count();
+    match (nested.len(), unsettled) {
+        (0, _) => Cell::from(""),
+        (total, 0) => Cell::from(format!("{} settled", total)).style(theme.success_style()),
+        (total, unsettled) => Cell::from(format!("{} of {} need work", unsettled, total))
+            .style(theme.warning_style()),
+    }
+}
+
+fn render_repo_table(f: &mut Frame, area: Rect, workspace: &mut Workspace, theme: &Theme) {
+    let dirty = workspace
+        .rows
+        .iter()
+        .filter(|row| matches!(&row.status, Some(Ok(status)) if status.changed > 0))
+        .count();
+    let title = format!(
+        "Workspace: {} ({} repositories, {} with changes)",
+        text::truncate_path(
+            &workspace.root.display().to_string(),
+            area.width.saturating_sub(50) as usize
+        ),
+        workspace.rows.len(),
+        dirty
+    );
+
+    let header = Row::new(vec![
+        Cell::from("Repository").style(theme.accent2_style()),
+        Cell::from("Branch").style(theme.accent2_style()),
+        Cell::from("Changes").style(theme.accent2_style()),
+        Cell::from("Remote").style(theme.accent2_style()),
+        Cell::from("Nested").style(theme.accent2_style()),
+    ]);
+    let widths = [
+        Constraint::Percentage(35),
+        Constraint::Percentage(20),
+        Constraint::Length(12),
+        Constraint::Length(14),
+        Constraint::Min(16),
+    ];
+    let name_width = text::column_widths(area, &widths, "► ")[0];
+
+    let rows: Vec<Row> = workspace
+        .rows
+        .iter()
+        .map(|row| {
+            let name = Cell::from(text::truncate_path(&row.name, name_width))
+                .style(theme.text_style());
+            let status = match &row.status {
+                None => {
+                    return Row::new(vec![
+                        name,
+                        Cell::from("…").style(theme.muted_text_style()),
+                    ]);
+                }
+                Some(Err(e)) => {
+                    return Row::new(vec![
+                        name,
+                        Cell::from(e.clone()).style(theme.error_style()),
+                    ]);
+                }
+                Some(Ok(status)) => status,
+            };
+
+            let branch = Cell::from(status.branch.clone().unwrap_or_else(|| "(detached)".into()))
+                .style(theme.accent_style());
+            let changes = if status.changed == 0 {
+                Cell::from("clean").style(theme.success_style())
+            } else {
+                Cell::from(format!("{} changed", status.changed)).style(theme.warning_style())
+            };
+            let remote = match (&status.upstream, status.ahead, status.behind) {
+                (None, _, _) => Cell::from("no upstream").style(theme.muted_text_style()),
+                (Some(_), 0, 0) => Cell::from("up to date").style(theme.success_style()),
+                (Some(_), ahead, behind) => Cell::from(Line::from(vec![
+                    Span::styled(format!("↑{} ", ahead), theme.info_style()),
+                    Span::styled(format!("↓{}", behind), theme.warning_style()),
+                ])),
+            };
+            Row::new(vec![name, branch, changes, remote, nested_cell(&row.nested, theme)])
+        })
+        .collect();
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .title_style(theme.title_style())
+                .border_style(theme.focused_border_style())
+                .style(theme.secondary_background_style()),
+        )
+        .row_highlight_style(theme.highlight_style())
+        .highlight_symbol("► ");
+    f.render_stateful_widget(table, area, &mut workspace.table_state);
+}
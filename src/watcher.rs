@@ -0,0 +1,118 @@
+use notify::{EventKind, RecursiveMode, Watcher};
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::time::{Duration, Instant};
+
+/// Wait this long after the last change before refreshing, so a checkout or a build
+/// that touches hundreds of files costs one re-scan rather than hundreds
+const QUIET_PERIOD: Duration = Duration::from_millis(300);
+
+/// Files in the git directory whose changes show up in status: what HEAD is, what
+/// is staged, where branches point and whether a merge or rebase is underway
+const GIT_STATE: &[&str] = &[
+    "HEAD",
+    "index",
+    "refs",
+    "packed-refs",
+    "MERGE_HEAD",
+    "CHERRY_PICK_HEAD",
+    "REVERT_HEAD",
+    "rebase-merge",
+    "rebase-apply",
+];
+
+/// Watches the working tree and the git directory for changes made outside gitix
+///
+/// Watching happens on a background thread, which also sets the watches up so a
+/// large tree doesn't hold up startup. Ignored files (build output and the like)
+/// and git's own bookkeeping (objects, logs, lock files) are filtered out there.
+/// Dropping the watcher stops it.
+pub struct RepoWatcher {
+    changes: Receiver<()>,
+    last_change: Option<Instant>, // Latest change not yet reported
+    _stop: Sender<()>,            // Dropped with the watcher, ending the thread
+}
+
+impl RepoWatcher {
+    /// Start watching the repository containing `dir`; `None` if there is none
+    pub fn start(dir: &Path) -> Option<RepoWatcher> {
+        let repo = git2::Repository::discover(dir).ok()?;
+        let workdir = repo.workdir()?.to_path_buf();
+        let git_dir = repo.path().to_path_buf();
+        let (changes_tx, changes) = mpsc::channel();
+        let (stop, stop_rx) = mpsc::channel::<()>();
+
+        std::thread::spawn(move || {
+            // Out of watches or unsupported: refreshing by hand still works
+            if let Ok(_watcher) = watch(repo, &workdir, &git_dir, changes_tx) {
+                let _ = stop_rx.recv();
+            }
+        });
+
+        Some(RepoWatcher {
+            changes,
+            last_change: None,
+            _stop: stop,
+        })
+    }
+
+    /// Whether something changed and has since been quiet for a moment
+    pub fn poll(&mut self) -> bool {
+        loop {
+            match self.changes.try_recv() {
+                Ok(()) => self.last_change = Some(Instant::now()),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => break,
+            }
+        }
+        match self.last_change {
+            Some(at) if at.elapsed() >= QUIET_PERIOD => {
+                self.last_change = None;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Set up the watches, sending on `changes` for every change that can affect status
+fn watch(
+    repo: git2::Repository,
+    workdir: &Path,
+    git_dir: &Path,
+    changes: Sender<()>,
+) -> notify::Result<notify::RecommendedWatcher> {
+    let (root, git) = (workdir.to_path_buf(), git_dir.to_path_buf());
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let event = match event {
+            Ok(event) => event,
+            Err(_) => return,
+        };
+        if matches!(event.kind, EventKind::Access(_)) {
+            return; // Reads, including gitix's own
+        }
+        if event.paths.iter().any(|path| affects_status(&repo, &root, &git, path)) {
+            let _ = changes.send(());
+        }
+    })?;
+    watcher.watch(workdir, RecursiveMode::Recursive)?;
+    if !git_dir.starts_with(workdir) {
+        // A linked worktree keeps its git directory elsewhere
+        watcher.watch(git_dir, RecursiveMode::Recursive)?;
+    }
+    Ok(watcher)
+}
+
+/// Whether a change to `path` can change what status shows
+fn affects_status(repo: &git2::Repository, workdir: &Path, git_dir: &Path, path: &Path) -> bool {
+    if let Ok(relative) = path.strip_prefix(git_dir) {
+        let is_lock = relative.extension().is_some_and(|ext| ext == "lock");
+        let first = relative.components().next().map(|part| part.as_os_str());
+        return !is_lock && first.is_some_and(|first| GIT_STATE.iter().any(|name| first == *name));
+    }
+    match path.strip_prefix(workdir) {
+        // Also true inside an ignored directory
+        Ok(relative) => !repo.is_path_ignored(relative).unwrap_or(false),
+        Err(_) => false,
+    }
+}
@@ -0,0 +1,74 @@
+//! `gitix run` drives the same actions as the TUI; fetches, pulls and pushes run in the
+//! background there, so these tests check a script waits for them and reports what happened.
+
+mod common;
+
+use common::{TempDir, TempRepo};
+use std::path::Path;
+use std::process::Command;
+
+/// A `common::init_repo` repository whose `main` tracks a bare origin of its own
+struct ClonedRepo {
+    work: TempRepo,
+    _origin: TempDir,
+}
+
+impl ClonedRepo {
+    fn new(name: &str) -> Self {
+        let work = common::init_repo(name);
+        let origin = common::temp_dir(&format!("{}-origin", name));
+        git2::Repository::init_bare(origin.path())
+            .unwrap()
+            .set_head("refs/heads/main")
+            .unwrap();
+
+        let repo = work.open();
+        let mut remote = repo.remote("origin", origin.path().to_str().unwrap()).unwrap();
+        remote.push(&["refs/heads/main:refs/heads/main"], None).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("branch.main.remote", "origin").unwrap();
+        config.set_str("branch.main.merge", "refs/heads/main").unwrap();
+
+        ClonedRepo {
+            work,
+            _origin: origin,
+        }
+    }
+
+    fn work(&self) -> &Path {
+        self.work.path()
+    }
+}
+
+/// Run a script with `gitix run` in `dir`, returning its exit code and stdout
+fn run_script(dir: &Path, script: &str) -> (i32, String) {
+    let path = dir.join("test.gitix");
+    std::fs::write(&path, script).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_gitix"))
+        .args(["run", path.to_str().unwrap()])
+        .current_dir(dir)
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap();
+    let _ = std::fs::remove_file(&path);
+    (
+        output.status.code().unwrap(),
+        String::from_utf8(output.stdout).unwrap(),
+    )
+}
+
+#[test]
+fn fetch_waits_for_the_background_fetch_and_reports_it() {
+    let clone = ClonedRepo::new("run-fetch");
+    let (code, stdout) = run_script(clone.work(), "fetch\n");
+    assert_eq!(code, 0, "{}", stdout);
+    assert!(stdout.starts_with("> fetch\n✓ "), "{}", stdout);
+}
+
+#[test]
+fn pull_waits_for_the_background_pull_and_reports_it() {
+    let clone = ClonedRepo::new("run-pull");
+    let (code, stdout) = run_script(clone.work(), "pull\n");
+    assert_eq!(code, 0, "{}", stdout);
+    assert!(stdout.starts_with("> pull\n✓ "), "{}", stdout);
+}
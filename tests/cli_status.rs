@@ -0,0 +1,71 @@
+//! `gitix status` is a scripting contract: these tests pin its exit codes and
+//! `--porcelain` output so a change to either is a deliberate one.
+
+mod common;
+
+use std::path::Path;
+use std::process::Command;
+
+/// Run gitix in `dir`, returning its exit code and stdout
+fn gitix(dir: &Path, args: &[&str]) -> (i32, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_gitix"))
+        .args(args)
+        .current_dir(dir)
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap();
+    (
+        output.status.code().unwrap(),
+        String::from_utf8(output.stdout).unwrap(),
+    )
+}
+
+#[test]
+fn clean_tree_exits_zero() {
+    let repo = common::init_repo("clean");
+    let (code, stdout) = gitix(repo.path(), &["status", "--porcelain"]);
+    assert_eq!(code, 0);
+    assert_eq!(stdout, "# gitix-porcelain v1\n# branch main\n");
+}
+
+#[test]
+fn dirty_tree_exits_one_and_lists_paths_in_order() {
+    let repo = common::init_repo("dirty");
+    repo.write("tracked.txt", "two\n");
+    repo.write("added.txt", "new\n");
+    repo.stage("added.txt");
+    repo.write("added.txt", "newer\n");
+    repo.write("untracked.txt", "?\n");
+
+    let (code, stdout) = gitix(repo.path(), &["status", "--porcelain"]);
+    assert_eq!(code, 1);
+    assert_eq!(
+        stdout,
+        "# gitix-porcelain v1\n\
+         # branch main\n\
+         AM added.txt\n\
+         .M tracked.txt\n\
+         ?? untracked.txt\n"
+    );
+}
+
+#[test]
+fn human_output_uses_the_same_exit_codes() {
+    let repo = common::init_repo("human");
+    assert_eq!(gitix(repo.path(), &["status"]).0, 0);
+    repo.write("untracked.txt", "?\n");
+    assert_eq!(gitix(repo.path(), &["status"]).0, 1);
+}
+
+#[test]
+fn errors_exit_two() {
+    let dir = std::env::temp_dir().join(format!("gitix-test-norepo-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let (code, stdout) = gitix(&dir, &["status", "--porcelain"]);
+    let _ = std::fs::remove_dir_all(&dir);
+    assert_eq!(code, 2);
+    assert!(stdout.is_empty());
+
+    let repo = common::init_repo("usage");
+    assert_eq!(gitix(repo.path(), &["status", "--bogus"]).0, 2);
+}
@@ -0,0 +1,72 @@
+//! Fixtures shared by the integration tests. Each test file uses only part of them.
+#![allow(dead_code)]
+
+use std::path::{Path, PathBuf};
+
+/// A directory of its own under the system temp dir, removed again on drop
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
+
+/// An empty `gitix-test-<name>-<pid>` directory
+pub fn temp_dir(name: &str) -> TempDir {
+    let path = std::env::temp_dir().join(format!("gitix-test-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&path);
+    std::fs::create_dir_all(&path).unwrap();
+    TempDir { path }
+}
+
+/// A repository on `main` whose one commit, "Initial", adds `tracked.txt`
+pub struct TempRepo {
+    dir: TempDir,
+}
+
+impl TempRepo {
+    pub fn path(&self) -> &Path {
+        self.dir.path()
+    }
+
+    pub fn open(&self) -> git2::Repository {
+        git2::Repository::open(self.path()).unwrap()
+    }
+
+    pub fn write(&self, path: &str, contents: &str) {
+        std::fs::write(self.path().join(path), contents).unwrap();
+    }
+
+    pub fn stage(&self, path: &str) {
+        let repo = self.open();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(path)).unwrap();
+        index.write().unwrap();
+    }
+}
+
+/// A fresh `TempRepo` in `gitix-test-<name>-<pid>`
+pub fn init_repo(name: &str) -> TempRepo {
+    let dir = temp_dir(name);
+    let repo = git2::Repository::init(dir.path()).unwrap();
+    repo.set_head("refs/heads/main").unwrap();
+    let temp = TempRepo { dir };
+    temp.write("tracked.txt", "one\n");
+    temp.stage("tracked.txt");
+    let tree = repo
+        .find_tree(repo.index().unwrap().write_tree().unwrap())
+        .unwrap();
+    let signature = git2::Signature::now("Test", "test@example.com").unwrap();
+    repo.commit(Some("HEAD"), &signature, &signature, "Initial", &tree, &[])
+        .unwrap();
+    temp
+}
@@ -0,0 +1,168 @@
+//! `AppState::update` is where the TUI reacts to the user: these tests drive it with
+//! messages and key presses, without a terminal.
+
+mod common;
+
+use common::TempRepo;
+use gitix::app::{AppState, SaveChangesFocus};
+use gitix::tui::message::{AppMessage, Command};
+use gitix::tui::tabs::Tab;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard, OnceLock};
+
+/// gitix works on the current directory, so every test shares one repository and runs alone
+///
+/// The repository has one commit and a change to `tracked.txt`. It is never removed, since
+/// the process is still inside it when the tests finish.
+fn state() -> (MutexGuard<'static, TempRepo>, AppState) {
+    static REPO: OnceLock<Mutex<TempRepo>> = OnceLock::new();
+    let lock = REPO.get_or_init(|| {
+        let repo = common::init_repo("update");
+        repo.write("tracked.txt", "two\n");
+        std::env::set_current_dir(repo.path()).unwrap();
+        Mutex::new(repo)
+    });
+    let guard = lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    (guard, AppState::default())
+}
+
+fn key(code: KeyCode) -> AppMessage {
+    AppMessage::Key(KeyEvent::new(code, KeyModifiers::NONE))
+}
+
+#[test]
+fn tab_and_shift_tab_step_through_the_tab_bar_and_wrap() {
+    let (_repo, mut state) = state();
+    assert!(state.git_enabled);
+    assert_eq!(state.active_tab, Tab::Overview);
+
+    state.update(key(KeyCode::Tab));
+    assert_eq!(state.active_tab, Tab::Files);
+    state.update(key(KeyCode::BackTab));
+    assert_eq!(state.active_tab, Tab::Overview);
+    state.update(AppMessage::PreviousTab);
+    assert_eq!(state.active_tab, Tab::Settings);
+    state.update(AppMessage::NextTab);
+    assert_eq!(state.active_tab, Tab::Overview);
+}
+
+#[test]
+fn number_keys_jump_to_a_tab_by_position() {
+    let (_repo, mut state) = state();
+    state.update(key(KeyCode::Char('5')));
+    assert_eq!(state.active_tab, Tab::History);
+    state.update(key(KeyCode::Char('1')));
+    assert_eq!(state.active_tab, Tab::Overview);
+}
+
+#[test]
+fn tabs_that_need_git_are_skipped_outside_a_repository() {
+    let (_repo, mut state) = state();
+    state.git_enabled = false;
+
+    state.update(AppMessage::NextTab);
+    assert_eq!(state.active_tab, Tab::Files);
+    state.update(AppMessage::NextTab);
+    assert_eq!(state.active_tab, Tab::Overview);
+    state.update(AppMessage::SwitchTab(Tab::History));
+    assert_eq!(state.active_tab, Tab::Overview);
+}
+
+#[test]
+fn a_popup_keeps_keys_from_the_tab_under_it_until_esc_closes_it() {
+    let (_repo, mut state) = state();
+    state.show_error("Test", "Something went wrong");
+    assert!(state.has_popup());
+
+    state.update(key(KeyCode::Tab));
+    state.update(key(KeyCode::Char('q')));
+    assert_eq!(state.active_tab, Tab::Overview);
+    assert!(state.show_error_popup);
+    assert!(state.quit_confirm.is_none());
+
+    state.update(key(KeyCode::Esc));
+    assert!(!state.show_error_popup);
+    assert!(!state.has_popup());
+    state.update(key(KeyCode::Tab));
+    assert_eq!(state.active_tab, Tab::Files);
+}
+
+#[test]
+fn esc_closes_the_topmost_popup_first() {
+    let (_repo, mut state) = state();
+    state.update(AppMessage::SwitchTab(Tab::SaveChanges));
+    state.open_stage_pattern();
+    state.show_error("Test", "Something went wrong");
+
+    state.update(AppMessage::Back);
+    assert!(!state.show_error_popup);
+    assert!(state.stage_pattern.is_some());
+    state.update(AppMessage::Back);
+    assert!(state.stage_pattern.is_none());
+}
+
+#[test]
+fn keys_go_into_the_commit_message_until_esc_moves_focus_to_the_files() {
+    let (_repo, mut state) = state();
+    state.update(AppMessage::SwitchTab(Tab::SaveChanges));
+    state.save_changes_focus = SaveChangesFocus::CommitMessage;
+
+    // Typing, not quitting or switching tabs
+    for c in "q1".chars() {
+        state.update(key(KeyCode::Char(c)));
+    }
+    assert_eq!(state.commit_message.lines(), ["q1"]);
+    assert_eq!(state.active_tab, Tab::SaveChanges);
+    assert!(state.quit_confirm.is_none());
+
+    state.update(key(KeyCode::Esc));
+    assert_eq!(state.save_changes_focus, SaveChangesFocus::FileList);
+    state.update(key(KeyCode::Up));
+    assert_eq!(state.save_changes_focus, SaveChangesFocus::CommitMessage);
+}
+
+#[test]
+fn the_hunk_view_opens_from_the_file_list_and_esc_steps_back() {
+    let (_repo, mut state) = state();
+    state.update(AppMessage::SwitchTab(Tab::SaveChanges));
+    state.save_changes_focus = SaveChangesFocus::CommitMessage;
+    state.update(key(KeyCode::Down));
+    assert_eq!(state.save_changes_focus, SaveChangesFocus::FileList);
+
+    state.update(key(KeyCode::Right));
+    assert_eq!(state.save_changes_focus, SaveChangesFocus::HunkView);
+    state.update(key(KeyCode::Esc));
+    assert_eq!(state.save_changes_focus, SaveChangesFocus::FileList);
+}
+
+#[test]
+fn editing_a_file_hands_the_terminal_to_the_event_loop() {
+    let (_repo, mut state) = state();
+    state.update(AppMessage::SwitchTab(Tab::SaveChanges));
+    state.save_changes_focus = SaveChangesFocus::CommitMessage;
+    state.update(key(KeyCode::Down));
+
+    match state.update(key(KeyCode::Char('e'))) {
+        Command::Edit { path, line, then } => {
+            assert_eq!(path, Path::new("tracked.txt"));
+            assert_eq!(line, Some(1));
+            assert!(matches!(then, AppMessage::FileEdited));
+        }
+        other => panic!("expected an edit, got {:?}", other),
+    }
+}
+
+#[test]
+fn quitting_with_an_unfinished_commit_message_asks_first() {
+    let (_repo, mut state) = state();
+    state.commit_message.insert_str("Half a thought");
+
+    assert!(matches!(state.update(key(KeyCode::Char('q'))), Command::None));
+    assert!(state.quit_confirm.is_some());
+    assert!(matches!(state.update(key(KeyCode::Char('n'))), Command::None));
+    assert!(state.quit_confirm.is_none());
+
+    state.update(AppMessage::Quit);
+    assert!(matches!(state.update(key(KeyCode::Char('y'))), Command::Quit));
+}
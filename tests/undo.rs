@@ -0,0 +1,84 @@
+//! "Undo last operation" reads HEAD's reflog to find where a rebase started. These tests
+//! make real rebases with git so the entries are exactly what git writes.
+
+mod common;
+
+use gitix::git::{plan_undo, UndoKind};
+use std::path::Path;
+use std::process::Command;
+use std::sync::Mutex;
+
+/// plan_undo works on the current directory, which the tests share
+static CWD: Mutex<()> = Mutex::new(());
+
+/// Run git in `dir` and return its trimmed stdout, failing the test if it fails
+fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+        .args(["-c", "init.defaultBranch=main", "-c", "advice.detachedHead=false"])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "git {:?}: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap().trim().to_string()
+}
+
+fn commit(dir: &Path, file: &str, message: &str) -> String {
+    std::fs::write(dir.join(file), message).unwrap();
+    git(dir, &["add", file]);
+    git(dir, &["commit", "-q", "-m", message]);
+    git(dir, &["rev-parse", "HEAD"])
+}
+
+#[test]
+fn undoing_the_second_of_two_rebases_goes_back_to_the_first() {
+    let _cwd = CWD.lock().unwrap_or_else(|e| e.into_inner());
+    let repo = common::init_repo("undo-two-rebases");
+    let dir = repo.path();
+    git(dir, &["checkout", "-q", "-b", "upstream"]);
+    commit(dir, "one.txt", "Upstream one");
+    commit(dir, "two.txt", "Upstream two");
+    git(dir, &["checkout", "-q", "-b", "feature", "main"]);
+    commit(dir, "feature.txt", "Feature");
+
+    git(dir, &["rebase", "-q", "upstream~1"]);
+    let after_first = git(dir, &["rev-parse", "HEAD"]);
+    git(dir, &["rebase", "-q", "upstream"]);
+
+    std::env::set_current_dir(dir).unwrap();
+    let plan = plan_undo().unwrap();
+    assert_eq!(plan.kind, UndoKind::Rebase);
+    assert_eq!(plan.target, after_first);
+    assert!(plan.hard);
+}
+
+#[test]
+fn pull_rebase_is_undone_like_a_rebase() {
+    let _cwd = CWD.lock().unwrap_or_else(|e| e.into_inner());
+    let repo = common::init_repo("undo-pull-rebase");
+    let work = repo.path();
+    let scratch = common::temp_dir("undo-pull-rebase-remote");
+    let origin = scratch.path().join("origin.git");
+    let other = scratch.path().join("other");
+    git(scratch.path(), &["init", "-q", "--bare", "origin.git"]);
+    git(work, &["remote", "add", "origin", origin.to_str().unwrap()]);
+    git(work, &["push", "-q", "-u", "origin", "main"]);
+
+    git(scratch.path(), &["clone", "-q", origin.to_str().unwrap(), "other"]);
+    commit(&other, "remote.txt", "Remote");
+    git(&other, &["push", "-q", "origin", "main"]);
+
+    let local = commit(work, "local.txt", "Local");
+    git(work, &["pull", "-q", "--rebase"]);
+
+    std::env::set_current_dir(work).unwrap();
+    let plan = plan_undo().unwrap();
+    assert_eq!(plan.kind, UndoKind::Rebase);
+    assert_eq!(plan.target, local);
+}